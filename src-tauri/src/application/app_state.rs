@@ -1,10 +1,30 @@
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use tauri::{AppHandle, Manager, State};
 use tokio::sync::RwLock;
 
-use crate::application::services::{ProjectService, WorkspaceNavigationService};
+use crate::application::jobs::{JobManager, TauriJobEventSink, DEFAULT_WORKER_COUNT};
+use crate::application::services::{
+    AnnotationService, AnonymizationService, ArchiveService, CategoryService, CitationService,
+    CodingService, CostTableService, CreateSnapshotJobHandler, DerivationService, DocumentService,
+    EmailService, ExportService, ExtractDocumentsJobHandler, ExtractionService, FileSummaryService,
+    FindDuplicatesJobHandler, HashingService, MediaService, ParseEmailsJobHandler, PreviewService,
+    ProbeMediaJobHandler, ProjectService, ReconcileDocumentsJobHandler, ReportService,
+    ReviewService, SavedSearchService, SearchIndexService, SearchService, SnapshotService,
+    WorkspaceNavigationService,
+};
 use crate::domain::project::ProjectRepository;
-use crate::infrastructure::{AppError, AppResult, DatabaseConnection, SqliteProjectRepository};
+use crate::infrastructure::{
+    AppError, AppResult, CostTableCodecRegistry, DatabaseConnection, ExporterRegistry,
+    ExtractorRegistry, FileDetStore, FilePseudonymMapRepository, MediaProbeRegistry, PreviewCache,
+    PreviewGeneratorRegistry, SqliteAnnotationRepository, SqliteCategoryRepository,
+    SqliteCitationRepository, SqliteCodingRepository, SqliteDerivationRepository,
+    SqliteDocumentRepository, SqliteEmailRepository, SqliteFileCategoryConfigRepository,
+    SqliteFileHashRepository, SqliteJobRepository, SqliteManifestSnapshotRepository,
+    SqliteMediaMetadataRepository, SqliteProjectRepository, SqliteReportRepository,
+    SqliteReviewRepository, SqliteSavedSearchRepository, SqliteSearchIndexRepository,
+    DEFAULT_PREVIEW_CACHE_BYTES,
+};
 
 /// Application state container for dependency injection
 ///
@@ -24,6 +44,72 @@ pub struct AppState {
     /// Workspace navigation service
     workspace_navigation_service: Arc<WorkspaceNavigationService>,
 
+    /// Per-project file type summary service
+    file_summary_service: Arc<FileSummaryService>,
+
+    /// File content hashing service
+    hashing_service: Arc<HashingService>,
+
+    /// Stable document identity service
+    document_service: Arc<DocumentService>,
+
+    /// Source folder manifest snapshot service
+    snapshot_service: Arc<SnapshotService>,
+
+    /// Text extraction service
+    extraction_service: Arc<ExtractionService>,
+
+    /// Media metadata probing service
+    media_service: Arc<MediaService>,
+
+    /// Email parsing and threading service
+    email_service: Arc<EmailService>,
+
+    /// Archive browsing and member extraction service
+    archive_service: Arc<ArchiveService>,
+
+    /// File preview service
+    preview_service: Arc<PreviewService>,
+
+    /// Derivative processing chain service
+    derivation_service: Arc<DerivationService>,
+
+    /// Anonymized derivative service
+    anonymization_service: Arc<AnonymizationService>,
+
+    /// Cost table derivative service
+    cost_table_service: Arc<CostTableService>,
+
+    /// Document export service
+    export_service: Arc<ExportService>,
+
+    /// Reports management service
+    report_service: Arc<ReportService>,
+
+    /// Report citation service
+    citation_service: Arc<CitationService>,
+
+    /// Project-wide search service
+    search_service: Arc<SearchService>,
+
+    /// Saved search service
+    saved_search_service: Arc<SavedSearchService>,
+
+    /// Document category and tag service
+    category_service: Arc<CategoryService>,
+
+    /// Document annotation service
+    annotation_service: Arc<AnnotationService>,
+
+    /// Document coding service
+    coding_service: Arc<CodingService>,
+
+    /// Reviewer profile and review batch service
+    review_service: Arc<ReviewService>,
+
+    /// Background job worker pool
+    job_manager: Arc<JobManager>,
+
     /// Application metadata
     metadata: Arc<RwLock<AppMetadata>>,
 }
@@ -88,12 +174,6 @@ impl AppState {
         // Create repository
         let project_repository = Arc::new(SqliteProjectRepository::new(database.pool()));
 
-        // Create services
-        let project_service = Arc::new(ProjectService::new(project_repository.clone()));
-
-        // Create workspace navigation service
-        let workspace_navigation_service = Arc::new(WorkspaceNavigationService::new());
-
         // Initialize metadata
         let metadata = AppMetadata {
             version: env!("CARGO_PKG_VERSION").to_string(),
@@ -104,13 +184,8 @@ impl AppState {
             stats: AppStats::default(),
         };
 
-        let app_state = AppState {
-            database,
-            project_repository,
-            project_service,
-            workspace_navigation_service,
-            metadata: Arc::new(RwLock::new(metadata)),
-        };
+        let roots = StorageRoots::next_to(&database);
+        let app_state = Self::build(database, project_repository, &roots, metadata);
 
         // Perform initial health check
         app_state.health_check().await?;
@@ -125,10 +200,6 @@ impl AppState {
 
         // Create mock repository for testing
         let project_repository = Arc::new(MockProjectRepository::new());
-        let project_service = Arc::new(ProjectService::new(project_repository.clone()));
-
-        // Create workspace navigation service for testing
-        let workspace_navigation_service = Arc::new(WorkspaceNavigationService::new());
 
         // Create temporary database for testing
         let (database, _temp_dir) = DatabaseConnection::new_temp().await?;
@@ -143,13 +214,281 @@ impl AppState {
             stats: AppStats::default(),
         };
 
-        Ok(AppState {
+        let roots = StorageRoots::next_to(&database);
+        Ok(Self::build(database, project_repository, &roots, metadata))
+    }
+
+    /// Create all services on top of a database and project repository and
+    /// register the background job handlers
+    fn build(
+        database: Arc<DatabaseConnection>,
+        project_repository: Arc<dyn ProjectRepository>,
+        roots: &StorageRoots,
+        metadata: AppMetadata,
+    ) -> Self {
+        // Create services
+        let project_service = Arc::new(ProjectService::new(project_repository.clone()));
+
+        // Create file content hashing service
+        let hashing_service = Arc::new(HashingService::new(
+            project_repository.clone(),
+            Arc::new(SqliteFileHashRepository::new(database.pool())),
+        ));
+
+        // Create report citation service, reading extracted text next to the database
+        let citation_service = Arc::new(CitationService::new(
+            project_repository.clone(),
+            Arc::new(SqliteReportRepository::new(database.pool())),
+            Arc::new(SqliteCitationRepository::new(database.pool())),
+            Arc::new(SqliteDocumentRepository::new(database.pool())),
+            Arc::new(FileDetStore::new()),
+            roots.derivatives.clone(),
+        ));
+
+        // Create search index service over derivatives and reports
+        let search_index_service = Arc::new(SearchIndexService::new(
+            Arc::new(SqliteDocumentRepository::new(database.pool())),
+            Arc::new(SqliteReportRepository::new(database.pool())),
+            Arc::new(SqliteSearchIndexRepository::new(database.pool())),
+            Arc::new(FileDetStore::new()),
+            roots.derivatives.clone(),
+        ));
+
+        // Create document identity service
+        let document_service = Arc::new(
+            DocumentService::new(
+                project_repository.clone(),
+                Arc::new(SqliteDocumentRepository::new(database.pool())),
+                hashing_service.clone(),
+            )
+            .with_citation_service(citation_service.clone())
+            .with_search_index(search_index_service.clone()),
+        );
+
+        // Create manifest snapshot service
+        let snapshot_service = Arc::new(SnapshotService::new(
+            project_repository.clone(),
+            Arc::new(SqliteManifestSnapshotRepository::new(database.pool())),
+            hashing_service.clone(),
+        ));
+
+        // Create derivative processing chain service
+        let derivation_service = Arc::new(DerivationService::new(
+            Arc::new(SqliteDocumentRepository::new(database.pool())),
+            Arc::new(SqliteDerivationRepository::new(database.pool())),
+            roots.derivatives.clone(),
+        ));
+
+        // Create document annotation service
+        let annotation_service = Arc::new(
+            AnnotationService::new(
+                Arc::new(SqliteDocumentRepository::new(database.pool())),
+                Arc::new(SqliteAnnotationRepository::new(database.pool())),
+                Arc::new(FileDetStore::new()),
+                roots.derivatives.clone(),
+            )
+            .with_review_repository(Arc::new(SqliteReviewRepository::new(database.pool()))),
+        );
+
+        // Create text extraction service, writing next to the database
+        let extraction_service = Arc::new(
+            ExtractionService::new(
+                Arc::new(SqliteDocumentRepository::new(database.pool())),
+                Arc::new(ExtractorRegistry::with_defaults()),
+                Arc::new(FileDetStore::new()),
+                roots.derivatives.clone(),
+            )
+            .with_derivation_service(derivation_service.clone())
+            .with_annotation_service(annotation_service.clone())
+            .with_search_index(search_index_service.clone()),
+        );
+
+        // Create media metadata probing service
+        let media_service = Arc::new(MediaService::new(
+            Arc::new(SqliteDocumentRepository::new(database.pool())),
+            Arc::new(SqliteMediaMetadataRepository::new(database.pool())),
+            Arc::new(MediaProbeRegistry::with_defaults()),
+        ));
+
+        // Create email service, keeping attachments with the derivatives
+        let email_service = Arc::new(EmailService::new(
+            Arc::new(SqliteDocumentRepository::new(database.pool())),
+            Arc::new(SqliteEmailRepository::new(database.pool())),
+            roots.derivatives.clone(),
+        ));
+
+        // Create archive service, extracting members to the temp folder
+        let archive_service = Arc::new(ArchiveService::new(roots.archive_members.clone()));
+
+        // Create preview service, caching previews next to the database
+        let preview_service = Arc::new(PreviewService::new(
+            hashing_service.clone(),
+            Arc::new(PreviewGeneratorRegistry::with_defaults()),
+            Arc::new(PreviewCache::new(
+                roots.previews.clone(),
+                DEFAULT_PREVIEW_CACHE_BYTES,
+            )),
+        ));
+
+        // Create anonymization service, keeping pseudonym maps in the project folders
+        let anonymization_service = Arc::new(
+            AnonymizationService::new(
+                Arc::new(SqliteDocumentRepository::new(database.pool())),
+                Arc::new(FileDetStore::new()),
+                Arc::new(FilePseudonymMapRepository::new(project_repository.clone())),
+                roots.derivatives.clone(),
+            )
+            .with_derivation_service(derivation_service.clone()),
+        );
+
+        // Create cost table service
+        let cost_table_service = Arc::new(
+            CostTableService::new(
+                Arc::new(SqliteDocumentRepository::new(database.pool())),
+                project_repository.clone(),
+                Arc::new(FileDetStore::new()),
+                Arc::new(CostTableCodecRegistry::with_defaults()),
+                roots.derivatives.clone(),
+            )
+            .with_derivation_service(derivation_service.clone()),
+        );
+
+        // Create document export service
+        let export_service = Arc::new(ExportService::new(
+            project_repository.clone(),
+            Arc::new(FileDetStore::new()),
+            Arc::new(ExporterRegistry::with_defaults()),
+            roots.derivatives.clone(),
+        ));
+
+        // Create reports management service
+        let report_service = Arc::new(ReportService::new(
+            project_repository.clone(),
+            Arc::new(SqliteReportRepository::new(database.pool())),
+            Arc::new(FileDetStore::new()),
+            citation_service.clone(),
+        ));
+
+        // Create search service over originals, derivatives, reports and metadata
+        let search_service = Arc::new(
+            SearchService::new(
+                project_repository.clone(),
+                Arc::new(SqliteDocumentRepository::new(database.pool())),
+                Arc::new(SqliteDerivationRepository::new(database.pool())),
+                Arc::new(SqliteReportRepository::new(database.pool())),
+                Arc::new(SqliteMediaMetadataRepository::new(database.pool())),
+                search_index_service.clone(),
+                roots.derivatives.clone(),
+            )
+            .with_email_repository(Arc::new(SqliteEmailRepository::new(database.pool())))
+            .with_archive_service(archive_service.clone())
+            .with_category_config_repository(Arc::new(SqliteFileCategoryConfigRepository::new(
+                database.pool(),
+            )))
+            .with_category_repository(Arc::new(SqliteCategoryRepository::new(database.pool()))),
+        );
+
+        // Create saved search service for smart folders
+        let saved_search_service = Arc::new(SavedSearchService::new(
+            Arc::new(SqliteSavedSearchRepository::new(database.pool())),
+            search_service.clone(),
+        ));
+
+        // Create document category and tag service
+        let category_service = Arc::new(CategoryService::new(
+            project_repository.clone(),
+            Arc::new(SqliteDocumentRepository::new(database.pool())),
+            Arc::new(SqliteCategoryRepository::new(database.pool())),
+        ));
+
+        // Create document coding service
+        let coding_service = Arc::new(
+            CodingService::new(
+                project_repository.clone(),
+                Arc::new(SqliteDocumentRepository::new(database.pool())),
+                Arc::new(SqliteCodingRepository::new(database.pool())),
+            )
+            .with_review_repository(Arc::new(SqliteReviewRepository::new(database.pool()))),
+        );
+
+        // Create reviewer profile and review batch service
+        let review_service = Arc::new(ReviewService::new(
+            project_repository.clone(),
+            Arc::new(SqliteDocumentRepository::new(database.pool())),
+            Arc::new(SqliteReviewRepository::new(database.pool())),
+            Arc::new(SqliteCodingRepository::new(database.pool())),
+            Arc::new(SqliteAnnotationRepository::new(database.pool())),
+            search_service.clone(),
+            category_service.clone(),
+        ));
+
+        // Create workspace navigation service
+        let workspace_navigation_service = Arc::new(
+            WorkspaceNavigationService::new()
+                .with_hashing_service(hashing_service.clone())
+                .with_saved_searches(saved_search_service.clone())
+                .with_email_service(email_service.clone())
+                .with_archive_service(archive_service.clone()),
+        );
+
+        // Create file summary service
+        let file_summary_service = Arc::new(
+            FileSummaryService::new(
+                project_repository.clone(),
+                Arc::new(SqliteFileCategoryConfigRepository::new(database.pool())),
+            )
+            .with_archive_service(archive_service.clone()),
+        );
+
+        // Create background job worker pool
+        let job_manager = Arc::new(JobManager::new(
+            Arc::new(SqliteJobRepository::new(database.pool())),
+            DEFAULT_WORKER_COUNT,
+        ));
+        job_manager.register_handler(Arc::new(FindDuplicatesJobHandler::new(
+            hashing_service.clone(),
+        )));
+        job_manager.register_handler(Arc::new(ReconcileDocumentsJobHandler::new(
+            document_service.clone(),
+        )));
+        job_manager.register_handler(Arc::new(CreateSnapshotJobHandler::new(
+            snapshot_service.clone(),
+        )));
+        job_manager.register_handler(Arc::new(ExtractDocumentsJobHandler::new(
+            extraction_service.clone(),
+        )));
+        job_manager.register_handler(Arc::new(ProbeMediaJobHandler::new(media_service.clone())));
+        job_manager.register_handler(Arc::new(ParseEmailsJobHandler::new(email_service.clone())));
+
+        AppState {
             database,
             project_repository,
             project_service,
             workspace_navigation_service,
+            file_summary_service,
+            hashing_service,
+            document_service,
+            snapshot_service,
+            extraction_service,
+            media_service,
+            email_service,
+            archive_service,
+            preview_service,
+            derivation_service,
+            anonymization_service,
+            cost_table_service,
+            export_service,
+            report_service,
+            citation_service,
+            search_service,
+            saved_search_service,
+            category_service,
+            annotation_service,
+            coding_service,
+            review_service,
+            job_manager,
             metadata: Arc::new(RwLock::new(metadata)),
-        })
+        }
     }
 
     /// Get the database connection
@@ -172,6 +511,116 @@ impl AppState {
         self.workspace_navigation_service.clone()
     }
 
+    /// Get the file summary service
+    pub fn file_summary_service(&self) -> Arc<FileSummaryService> {
+        self.file_summary_service.clone()
+    }
+
+    /// Get the file content hashing service
+    pub fn hashing_service(&self) -> Arc<HashingService> {
+        self.hashing_service.clone()
+    }
+
+    /// Get the document identity service
+    pub fn document_service(&self) -> Arc<DocumentService> {
+        self.document_service.clone()
+    }
+
+    /// Get the manifest snapshot service
+    pub fn snapshot_service(&self) -> Arc<SnapshotService> {
+        self.snapshot_service.clone()
+    }
+
+    /// Get the text extraction service
+    pub fn extraction_service(&self) -> Arc<ExtractionService> {
+        self.extraction_service.clone()
+    }
+
+    /// Get the media metadata probing service
+    pub fn media_service(&self) -> Arc<MediaService> {
+        self.media_service.clone()
+    }
+
+    /// Get the email parsing and threading service
+    pub fn email_service(&self) -> Arc<EmailService> {
+        self.email_service.clone()
+    }
+
+    /// Get the archive browsing and member extraction service
+    pub fn archive_service(&self) -> Arc<ArchiveService> {
+        self.archive_service.clone()
+    }
+
+    /// Get the file preview service
+    pub fn preview_service(&self) -> Arc<PreviewService> {
+        self.preview_service.clone()
+    }
+
+    /// Get the derivative processing chain service
+    pub fn derivation_service(&self) -> Arc<DerivationService> {
+        self.derivation_service.clone()
+    }
+
+    /// Get the anonymized derivative service
+    pub fn anonymization_service(&self) -> Arc<AnonymizationService> {
+        self.anonymization_service.clone()
+    }
+
+    /// Get the cost table derivative service
+    pub fn cost_table_service(&self) -> Arc<CostTableService> {
+        self.cost_table_service.clone()
+    }
+
+    /// Get the document export service
+    pub fn export_service(&self) -> Arc<ExportService> {
+        self.export_service.clone()
+    }
+
+    /// Get the reports management service
+    pub fn report_service(&self) -> Arc<ReportService> {
+        self.report_service.clone()
+    }
+
+    /// Get the report citation service
+    pub fn citation_service(&self) -> Arc<CitationService> {
+        self.citation_service.clone()
+    }
+
+    /// Get the project-wide search service
+    pub fn search_service(&self) -> Arc<SearchService> {
+        self.search_service.clone()
+    }
+
+    /// Get the saved search service
+    pub fn saved_search_service(&self) -> Arc<SavedSearchService> {
+        self.saved_search_service.clone()
+    }
+
+    /// Get the document category and tag service
+    pub fn category_service(&self) -> Arc<CategoryService> {
+        self.category_service.clone()
+    }
+
+    /// Get the document annotation service
+    pub fn annotation_service(&self) -> Arc<AnnotationService> {
+        self.annotation_service.clone()
+    }
+
+    /// Get the document coding service
+    pub fn coding_service(&self) -> Arc<CodingService> {
+        self.coding_service.clone()
+    }
+
+    /// Get the reviewer profile and review batch service
+    pub fn review_service(&self) -> Arc<ReviewService> {
+        self.review_service.clone()
+    }
+
+    /// Get the background job manager
+    pub fn job_manager(&self) -> Arc<JobManager> {
+        self.job_manager.clone()
+    }
+
     /// Get application metadata (read-only)
     pub async fn metadata(&self) -> AppMetadata {
         self.metadata.read().await.clone()
@@ -240,6 +689,9 @@ impl AppState {
     pub async fn shutdown(&self) {
         tracing::info!("Shutting down application state");
 
+        // Stop background jobs; their records are resumed on next start
+        self.job_manager.shutdown().await;
+
         // Close database connection
         self.database.close().await;
 
@@ -268,6 +720,39 @@ impl AppState {
     }
 }
 
+/// Folders the services keep files in, outside any source folder
+struct StorageRoots {
+    /// Derived files (`.det` extractions and the like)
+    derivatives: PathBuf,
+
+    /// Cached file previews
+    previews: PathBuf,
+
+    /// Archive members extracted for viewing
+    archive_members: PathBuf,
+}
+
+impl StorageRoots {
+    /// Derivatives and previews in the application data folder next to the
+    /// database, archive members in the temporary folder
+    fn next_to(database: &DatabaseConnection) -> Self {
+        let data_folder = database.path().parent().map(Path::to_path_buf);
+        let in_data_folder = |name: &str| {
+            data_folder
+                .as_ref()
+                .map_or_else(|| PathBuf::from(name), |folder| folder.join(name))
+        };
+
+        StorageRoots {
+            derivatives: in_data_folder("derivatives"),
+            previews: in_data_folder("previews"),
+            archive_members: std::env::temp_dir()
+                .join("corpus-review")
+                .join("archive-members"),
+        }
+    }
+}
+
 /// Application status information for monitoring
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct AppStatus {
@@ -326,8 +811,18 @@ impl StateManager {
         let development_mode = cfg!(debug_assertions);
         let app_state = AppState::new(development_mode).await?;
 
+        // Forward job updates to the frontend and pick up interrupted jobs
+        let job_manager = app_state.job_manager();
+        job_manager.set_event_sink(Arc::new(TauriJobEventSink::new(app.clone())));
+
         app.manage(app_state);
 
+        match job_manager.resume_interrupted().await {
+            Ok(0) => {}
+            Ok(resumed) => tracing::info!("Resumed {} interrupted jobs", resumed),
+            Err(error) => tracing::warn!("Failed to resume interrupted jobs: {}", error.message),
+        }
+
         tracing::info!("Tauri application state initialized successfully");
         Ok(())
     }
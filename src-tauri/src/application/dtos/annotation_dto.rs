@@ -0,0 +1,152 @@
+use serde::{Deserialize, Serialize};
+
+use crate::domain::annotation::{AnchorStatus, Annotation, AnnotationAnchor};
+use crate::domain::category::CategoryColor;
+
+/// DTO for a highlight or comment on a document
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct AnnotationDto {
+    /// Stable identifier ("annotation_<uuid>")
+    pub id: String,
+
+    pub project_id: String,
+
+    pub document_id: String,
+
+    pub anchor: AnnotationAnchor,
+
+    /// The highlighted text
+    pub quote: String,
+
+    /// Colour as `#rrggbb`
+    pub color: Option<String>,
+
+    /// The comment; empty for a plain highlight
+    pub body: String,
+
+    pub author: String,
+
+    /// Whether the anchor still points at the quote: "attached",
+    /// "reattached" or "orphaned"
+    pub status: AnchorStatus,
+
+    pub created_at: String,
+
+    pub updated_at: String,
+}
+
+impl From<&Annotation> for AnnotationDto {
+    fn from(annotation: &Annotation) -> Self {
+        AnnotationDto {
+            id: annotation.id().value().to_string(),
+            project_id: annotation.project_id().value().to_string(),
+            document_id: annotation.document_id().value().to_string(),
+            anchor: annotation.anchor().clone(),
+            quote: annotation.quote().to_string(),
+            color: annotation
+                .color()
+                .map(CategoryColor::value)
+                .map(str::to_string),
+            body: annotation.body().to_string(),
+            author: annotation.author().to_string(),
+            status: annotation.status(),
+            created_at: annotation.created_at().to_rfc3339(),
+            updated_at: annotation.updated_at().to_rfc3339(),
+        }
+    }
+}
+
+/// DTO for the outcome of re-attaching a document's annotations to a
+/// changed extraction
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct AnnotationReattachmentDto {
+    pub document_id: String,
+
+    /// Annotations whose quote was still at their anchor
+    pub unchanged: usize,
+
+    /// Annotations moved to where their quote is now
+    pub reattached: usize,
+
+    /// Annotations whose quote is no longer in the extraction
+    pub orphaned: usize,
+}
+
+/// DTO for an annotation in a project export, with its document's path
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportedAnnotationDto {
+    /// Path of the document when exported; missing if it is no longer known
+    pub document_path: Option<String>,
+
+    #[serde(flatten)]
+    pub annotation: AnnotationDto,
+}
+
+/// DTO for all annotations of a project
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct AnnotationExportDto {
+    pub project_id: String,
+
+    /// When the export was made, as ISO string
+    pub exported_at: String,
+
+    /// By document path, then oldest first
+    pub annotations: Vec<ExportedAnnotationDto>,
+}
+
+impl AnnotationExportDto {
+    /// Render the annotations as CSV, one row per annotation with a header
+    /// row
+    pub fn to_csv(&self) -> Result<String, csv::Error> {
+        let mut writer = csv::Writer::from_writer(Vec::new());
+        writer.write_record([
+            "id",
+            "document_id",
+            "document_path",
+            "anchor_type",
+            "page",
+            "start",
+            "end",
+            "quote",
+            "color",
+            "comment",
+            "author",
+            "status",
+            "created_at",
+            "updated_at",
+        ])?;
+
+        for exported in &self.annotations {
+            let annotation = &exported.annotation;
+            let (page, start, end) = match annotation.anchor {
+                AnnotationAnchor::Page { page, start, end } => (page.to_string(), start, end),
+                AnnotationAnchor::Range { from, to } => (String::new(), from, to),
+            };
+            writer.write_record([
+                annotation.id.as_str(),
+                annotation.document_id.as_str(),
+                exported.document_path.as_deref().unwrap_or_default(),
+                annotation.anchor.type_name(),
+                page.as_str(),
+                start.to_string().as_str(),
+                end.to_string().as_str(),
+                annotation.quote.as_str(),
+                annotation.color.as_deref().unwrap_or_default(),
+                annotation.body.as_str(),
+                annotation.author.as_str(),
+                annotation.status.as_str(),
+                annotation.created_at.as_str(),
+                annotation.updated_at.as_str(),
+            ])?;
+        }
+
+        let bytes = writer
+            .into_inner()
+            .map_err(|e| csv::Error::from(e.into_error()))?;
+        Ok(String::from_utf8_lossy(&bytes).into_owned())
+    }
+}
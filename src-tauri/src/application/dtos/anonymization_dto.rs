@@ -0,0 +1,89 @@
+use serde::{Deserialize, Serialize};
+
+use crate::domain::anonymization::{AnonymizationRule, BuiltInDetector, Replacement};
+
+/// Request to anonymize a document's extracted text
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct AnonymizationRequest {
+    /// Regex and dictionary rules, applied before the detectors
+    #[serde(default)]
+    pub rules: Vec<AnonymizationRule>,
+
+    /// Built-in detectors to run; None runs all of them
+    #[serde(default)]
+    pub detectors: Option<Vec<BuiltInDetector>>,
+
+    /// Values to leave in place, e.g. false positives found in review
+    #[serde(default)]
+    pub excluded: Vec<String>,
+}
+
+impl AnonymizationRequest {
+    /// The configured rules followed by one rule per selected detector
+    pub fn to_rules(&self) -> Vec<AnonymizationRule> {
+        let detectors = self
+            .detectors
+            .clone()
+            .unwrap_or_else(|| BuiltInDetector::ALL.to_vec());
+
+        self.rules
+            .iter()
+            .cloned()
+            .chain(detectors.into_iter().map(AnonymizationRule::detector))
+            .collect()
+    }
+}
+
+/// DTO for one value replaced by a pseudonym
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ReplacementDto {
+    /// Label of the rule that matched, e.g. "EMAIL"
+    pub label: String,
+
+    pub original: String,
+
+    pub pseudonym: String,
+
+    /// Index of the text block in reading order; None for the title
+    pub block: Option<usize>,
+
+    /// Character offset within the block
+    pub offset: usize,
+
+    /// Text around the value
+    pub context: String,
+}
+
+impl From<&Replacement> for ReplacementDto {
+    fn from(replacement: &Replacement) -> Self {
+        ReplacementDto {
+            label: replacement.label.clone(),
+            original: replacement.original.clone(),
+            pseudonym: replacement.pseudonym.clone(),
+            block: replacement.block,
+            offset: replacement.offset,
+            context: replacement.context.clone(),
+        }
+    }
+}
+
+/// DTO for an anonymization run or its preview
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct AnonymizationDto {
+    pub document_id: String,
+
+    /// Whether `anonymized.det` and the pseudonym map were written
+    pub committed: bool,
+
+    /// Path of the written `anonymized.det`; None for previews
+    pub det_path: Option<String>,
+
+    /// Every replacement, in reading order
+    pub replacements: Vec<ReplacementDto>,
+
+    /// Values that get a pseudonym for the first time in this project
+    pub new_pseudonyms: usize,
+}
@@ -0,0 +1,117 @@
+use serde::{Deserialize, Serialize};
+
+use crate::domain::category::{Category, CategoryColor, CategoryId, CategoryKind, Shortcut};
+
+/// DTO for a category or tag, with the documents assigned to it
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CategoryDto {
+    /// Stable identifier ("category_<uuid>")
+    pub id: String,
+
+    pub project_id: String,
+
+    pub kind: CategoryKind,
+
+    pub name: String,
+
+    pub parent_id: Option<String>,
+
+    /// Colour as `#rrggbb`
+    pub color: Option<String>,
+
+    /// Keyboard shortcut, e.g. "Ctrl+Shift+1"
+    pub shortcut: Option<String>,
+
+    /// Names from the top level down to this node
+    pub path: Vec<String>,
+
+    /// Documents assigned to this node itself
+    pub document_count: usize,
+
+    /// Distinct documents assigned to this node or any node below it
+    pub total_count: usize,
+
+    /// Nodes directly below this one, by name
+    #[serde(default)]
+    pub children: Vec<CategoryDto>,
+
+    pub created_at: String,
+
+    pub updated_at: String,
+}
+
+impl CategoryDto {
+    /// Create without counts or children
+    pub fn from_category(category: &Category, path: Vec<String>) -> Self {
+        CategoryDto {
+            id: category.id().value().to_string(),
+            project_id: category.project_id().value().to_string(),
+            kind: category.kind(),
+            name: category.name().to_string(),
+            parent_id: category.parent_id().map(|id| id.value().to_string()),
+            color: category
+                .color()
+                .map(CategoryColor::value)
+                .map(str::to_string),
+            shortcut: category.shortcut().map(Shortcut::value).map(str::to_string),
+            path,
+            document_count: 0,
+            total_count: 0,
+            children: Vec::new(),
+            created_at: category.created_at().to_rfc3339(),
+            updated_at: category.updated_at().to_rfc3339(),
+        }
+    }
+
+    /// Find a node by id in this subtree
+    pub fn find(&self, id: &CategoryId) -> Option<&CategoryDto> {
+        if self.id == id.value() {
+            return Some(self);
+        }
+        self.children.iter().find_map(|child| child.find(id))
+    }
+}
+
+/// DTO for the Category Explorer: the category tree and tags of a project
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CategoryTreeDto {
+    pub project_id: String,
+
+    /// Top-level categories, each with its subtree
+    pub categories: Vec<CategoryDto>,
+
+    /// Top-level tags, each with its subtree
+    pub tags: Vec<CategoryDto>,
+
+    /// Documents known in the project
+    pub document_count: usize,
+
+    /// Documents not assigned to any category (tags do not count)
+    pub uncategorized_count: usize,
+}
+
+impl CategoryTreeDto {
+    /// Find a category or tag by id anywhere in the tree
+    pub fn find(&self, id: &CategoryId) -> Option<&CategoryDto> {
+        self.categories
+            .iter()
+            .chain(&self.tags)
+            .find_map(|node| node.find(id))
+    }
+}
+
+/// DTO for the result of assigning or unassigning documents in bulk
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct BulkAssignmentDto {
+    pub category_id: String,
+
+    /// Number of documents given
+    pub requested: usize,
+
+    /// Number of documents whose assignment changed; the others already had
+    /// (or lacked) the category
+    pub changed: usize,
+}
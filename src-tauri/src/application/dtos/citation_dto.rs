@@ -0,0 +1,47 @@
+use crate::domain::report::CitationLocator;
+use serde::{Deserialize, Serialize};
+
+/// DTO for a report's citation resolved against the current workspace
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ResolvedCitationDto {
+    /// The citing report
+    pub report_id: String,
+
+    /// Cited document; None if the file was not tracked when cited
+    pub document_id: Option<String>,
+
+    /// Path of the file when it was cited
+    pub cited_path: String,
+
+    /// Page or range within the document
+    pub locator: CitationLocator,
+
+    /// Where the file is now; None if it cannot be found
+    pub current_path: Option<String>,
+
+    /// Cited text from the document's extracted text, when available
+    pub snippet: Option<String>,
+
+    /// "valid", "moved", "missing" or "out_of_range"
+    pub status: String,
+
+    /// Whether the citation no longer leads to the cited passage
+    pub broken: bool,
+}
+
+/// DTO for the outcome of checking a project's citations
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct CitationCheckDto {
+    pub project_id: String,
+
+    /// Number of citations checked across all reports
+    pub checked: u64,
+
+    /// Citations that are missing or out of range
+    pub broken: Vec<ResolvedCitationDto>,
+
+    /// When the check finished, as ISO string
+    pub checked_at: String,
+}
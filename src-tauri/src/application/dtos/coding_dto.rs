@@ -0,0 +1,86 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use crate::domain::coding::{CodingDecision, CodingField, CodingSchema, CodingValues};
+
+/// DTO for the coding schema of a project
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct CodingSchemaDto {
+    pub project_id: String,
+
+    /// Fields in display order
+    pub fields: Vec<CodingField>,
+
+    /// Whether the project uses the default schema, not one of its own
+    pub is_default: bool,
+
+    /// When the schema was saved, as ISO string; missing for the default
+    pub updated_at: Option<String>,
+}
+
+impl From<&CodingSchema> for CodingSchemaDto {
+    fn from(schema: &CodingSchema) -> Self {
+        CodingSchemaDto {
+            project_id: schema.project_id().value().to_string(),
+            fields: schema.fields().to_vec(),
+            is_default: schema.is_default(),
+            updated_at: schema.updated_at().map(|at| at.to_rfc3339()),
+        }
+    }
+}
+
+/// DTO for one coding decision on a document
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct CodingDecisionDto {
+    /// Stable identifier ("decision_<uuid>")
+    pub id: String,
+
+    pub project_id: String,
+
+    pub document_id: String,
+
+    /// Values by field key: a string, a list of strings or a boolean
+    pub values: CodingValues,
+
+    pub reviewer: String,
+
+    /// When the decision was made, as ISO string
+    pub decided_at: String,
+}
+
+impl From<&CodingDecision> for CodingDecisionDto {
+    fn from(decision: &CodingDecision) -> Self {
+        CodingDecisionDto {
+            id: decision.id().value().to_string(),
+            project_id: decision.project_id().value().to_string(),
+            document_id: decision.document_id().value().to_string(),
+            values: decision.values().clone(),
+            reviewer: decision.reviewer().to_string(),
+            decided_at: decision.decided_at().to_rfc3339(),
+        }
+    }
+}
+
+/// DTO for how far the coding of a project has come
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct CodingProgressDto {
+    pub project_id: String,
+
+    /// Documents to code; missing documents are left out
+    pub total: usize,
+
+    /// Documents with a decision
+    pub coded: usize,
+
+    pub uncoded: usize,
+
+    /// Coded share of the documents, 0 to 100; 0 for an empty project
+    pub percent: f64,
+
+    /// Current decisions per value of each choice field, e.g.
+    /// `{ "designation": { "Privileged": 3 } }`
+    pub counts: BTreeMap<String, BTreeMap<String, usize>>,
+}
@@ -0,0 +1,107 @@
+use serde::{Deserialize, Serialize};
+
+use crate::domain::cost_table::{CostRow, CostTable, CostTotal};
+
+/// Request to save a document's cost table, replacing its rows
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct SaveCostTableRequest {
+    pub title: String,
+
+    /// Distinguishes several tables of one document, e.g. "damages"
+    #[serde(default)]
+    pub purpose: Option<String>,
+
+    #[serde(default)]
+    pub rows: Vec<CostRow>,
+}
+
+/// DTO for the sum of one category, or of all categories, in one currency
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct CostTotalDto {
+    /// "direct", "indirect", "contingent" or "other"; None for a grand total
+    pub category: Option<String>,
+
+    /// Display name, e.g. "Direct costs" or "All categories"
+    pub label: String,
+
+    pub currency: String,
+
+    /// Decimal with two places, e.g. "1234.50"
+    pub amount: String,
+
+    pub row_count: usize,
+}
+
+impl From<&CostTotal> for CostTotalDto {
+    fn from(total: &CostTotal) -> Self {
+        CostTotalDto {
+            category: total.category.map(|c| c.as_str().to_string()),
+            label: total
+                .category
+                .map_or("All categories", |c| c.label())
+                .to_string(),
+            currency: total.currency.to_string(),
+            amount: total.amount.to_string(),
+            row_count: total.row_count,
+        }
+    }
+}
+
+/// DTO for a cost table derivative with its totals
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct CostTableDto {
+    pub document_id: String,
+
+    /// Path of the `cost-table*.det` file
+    pub det_path: String,
+
+    pub title: String,
+
+    pub purpose: Option<String>,
+
+    pub rows: Vec<CostRow>,
+
+    /// Per category and currency
+    pub totals: Vec<CostTotalDto>,
+
+    /// Per currency over all categories
+    pub grand_totals: Vec<CostTotalDto>,
+}
+
+impl CostTableDto {
+    pub fn new(document_id: &str, det_path: String, table: &CostTable) -> Self {
+        CostTableDto {
+            document_id: document_id.to_string(),
+            det_path,
+            title: table.title().to_string(),
+            purpose: table.purpose().map(str::to_string),
+            rows: table.rows().to_vec(),
+            totals: table.totals().iter().map(CostTotalDto::from).collect(),
+            grand_totals: table
+                .grand_totals()
+                .iter()
+                .map(CostTotalDto::from)
+                .collect(),
+        }
+    }
+}
+
+/// DTO for a cost table exported to a spreadsheet
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct CostTableExportDto {
+    /// Written file, inside the project's reports folder
+    pub output_path: String,
+
+    /// "csv" or "xlsx"
+    pub format: String,
+
+    pub size_bytes: u64,
+
+    pub row_count: usize,
+
+    pub exported_at: String,
+}
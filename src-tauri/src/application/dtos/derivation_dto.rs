@@ -0,0 +1,81 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::domain::document::DerivationStep;
+
+/// DTO for the derivation graph of a document family, shaped for drawing
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct DerivationGraphDto {
+    pub document_id: String,
+
+    pub project_id: String,
+
+    /// The original first, then derivatives in the order they were recorded;
+    /// `.det` files of the family without recorded steps come last
+    pub nodes: Vec<DerivationNodeDto>,
+
+    /// One edge per recorded step, from input to output
+    pub edges: Vec<DerivationEdgeDto>,
+}
+
+/// DTO for one file of a document family
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct DerivationNodeDto {
+    /// "original" or the derivative's file name
+    pub id: String,
+
+    /// Display name of the file
+    pub label: String,
+
+    /// "original" or "derivative"
+    pub kind: String,
+
+    /// Absolute path of the file
+    pub path: String,
+
+    /// Whether the file is on disk
+    pub exists: bool,
+
+    /// Steps on the longest path from the original
+    pub generation: u32,
+
+    /// Whether a file it was derived from changed after it was produced
+    pub stale: bool,
+}
+
+/// DTO for one derivation step
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct DerivationEdgeDto {
+    /// Node the step read
+    pub from: String,
+
+    /// Node the step produced
+    pub to: String,
+
+    pub operation: String,
+
+    /// Operation parameters as recorded
+    pub parameters: Value,
+
+    /// Tool and version that performed the step
+    pub tool_version: String,
+
+    /// When the step ran, as ISO string
+    pub produced_at: String,
+}
+
+impl From<&DerivationStep> for DerivationEdgeDto {
+    fn from(step: &DerivationStep) -> Self {
+        DerivationEdgeDto {
+            from: step.parent().key().to_string(),
+            to: step.child().key().to_string(),
+            operation: step.operation().to_string(),
+            parameters: step.parameters().clone(),
+            tool_version: step.tool_version().to_string(),
+            produced_at: step.produced_at().to_rfc3339(),
+        }
+    }
+}
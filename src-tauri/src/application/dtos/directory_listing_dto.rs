@@ -19,6 +19,18 @@ pub struct DirectoryListingDto {
 
     /// Whether navigation up is possible
     pub can_navigate_up: bool,
+
+    /// Saved search whose results this virtual folder lists
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub saved_search_id: Option<String>,
+
+    /// Email document whose attachments this virtual folder lists
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub email_document_id: Option<String>,
+
+    /// Archive file whose members this read-only folder lists
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub archive_path: Option<String>,
 }
 
 impl DirectoryListingDto {
@@ -34,9 +46,30 @@ impl DirectoryListingDto {
             is_root,
             parent_path,
             can_navigate_up,
+            saved_search_id: None,
+            email_document_id: None,
+            archive_path: None,
         }
     }
 
+    /// Mark the listing as the virtual folder of a saved search
+    pub fn with_saved_search(mut self, saved_search_id: String) -> Self {
+        self.saved_search_id = Some(saved_search_id);
+        self
+    }
+
+    /// Mark the listing as the attachments of an email document
+    pub fn with_email(mut self, document_id: String) -> Self {
+        self.email_document_id = Some(document_id);
+        self
+    }
+
+    /// Mark the listing as a folder inside an archive file
+    pub fn with_archive(mut self, archive_path: String) -> Self {
+        self.archive_path = Some(archive_path);
+        self
+    }
+
     /// Create an empty directory listing
     pub fn empty(is_root: bool, parent_path: Option<String>) -> Self {
         DirectoryListingDto {
@@ -44,6 +77,9 @@ impl DirectoryListingDto {
             is_root,
             parent_path: parent_path.clone(),
             can_navigate_up: !is_root && parent_path.is_some(),
+            saved_search_id: None,
+            email_document_id: None,
+            archive_path: None,
         }
     }
 
@@ -140,6 +176,12 @@ mod tests {
             },
             size: if is_directory { None } else { Some(1024) },
             modified: "2025-09-25T12:00:00Z".to_string(),
+            content_hash: None,
+            saved_search_id: None,
+            new_results: None,
+            attachment_count: None,
+            is_virtual: false,
+            is_archive: false,
         }
     }
 
@@ -0,0 +1,119 @@
+use crate::domain::document::Document;
+use serde::{Deserialize, Serialize};
+
+/// DTO for transferring a tracked source document
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct DocumentDto {
+    /// Stable document identifier ("document_<uuid>")
+    pub id: String,
+
+    /// The project the document belongs to
+    pub project_id: String,
+
+    /// Current path of the file
+    pub path: String,
+
+    /// File name component of the path
+    pub name: String,
+
+    /// Content hash as `<algorithm>:<digest>`
+    pub content_hash: String,
+
+    /// Size in bytes
+    pub size: u64,
+
+    /// Whether the file was not found during the last reconciliation
+    pub missing: bool,
+
+    /// Document this one was taken out of, e.g. the email of an attachment
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent_id: Option<String>,
+
+    /// When the document was first seen, as ISO string
+    pub created_at: String,
+
+    /// When the document record last changed, as ISO string
+    pub updated_at: String,
+}
+
+impl From<&Document> for DocumentDto {
+    fn from(document: &Document) -> Self {
+        DocumentDto {
+            id: document.id().value().to_string(),
+            project_id: document.project_id().value().to_string(),
+            path: document.path().to_string(),
+            name: document.file_name().unwrap_or_default().to_string(),
+            content_hash: document.content_hash().to_string(),
+            size: document.size(),
+            missing: document.is_missing(),
+            parent_id: document.parent_id().map(|id| id.value().to_string()),
+            created_at: document.created_at().to_rfc3339(),
+            updated_at: document.updated_at().to_rfc3339(),
+        }
+    }
+}
+
+/// DTO for a document whose file was found at a new path
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct RelinkedDocumentDto {
+    pub document_id: String,
+    pub from_path: String,
+    pub to_path: String,
+}
+
+/// DTO for the outcome of reconciling documents with the source folder
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct DocumentReconciliationDto {
+    /// The project that was reconciled
+    pub project_id: String,
+
+    /// Number of files in the source folder
+    pub files_scanned: u64,
+
+    /// Documents registered for files seen for the first time
+    pub created: u64,
+
+    /// Documents whose file was edited in place
+    pub updated: u64,
+
+    /// Documents whose file was unchanged
+    pub unchanged: u64,
+
+    /// Documents re-linked to a renamed or moved file
+    pub relinked: Vec<RelinkedDocumentDto>,
+
+    /// Documents whose file could not be found
+    pub missing: u64,
+
+    /// Citations that no longer resolve; only checked when documents changed
+    #[serde(default)]
+    pub broken_citations: u64,
+
+    /// When reconciliation finished, as ISO string
+    pub reconciled_at: String,
+}
+
+impl DocumentReconciliationDto {
+    /// Create an empty result for a project
+    pub fn empty(project_id: String) -> Self {
+        DocumentReconciliationDto {
+            project_id,
+            files_scanned: 0,
+            created: 0,
+            updated: 0,
+            unchanged: 0,
+            relinked: Vec::new(),
+            missing: 0,
+            broken_citations: 0,
+            reconciled_at: chrono::Utc::now().to_rfc3339(),
+        }
+    }
+
+    /// Whether reconciliation changed any document record
+    pub fn has_changes(&self) -> bool {
+        self.created > 0 || self.updated > 0 || self.missing > 0 || !self.relinked.is_empty()
+    }
+}
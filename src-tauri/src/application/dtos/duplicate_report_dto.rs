@@ -0,0 +1,126 @@
+use serde::{Deserialize, Serialize};
+
+/// DTO for a set of files with identical contents
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct DuplicateGroupDto {
+    /// Shared content hash, as `<algorithm>:<digest>`
+    pub hash: String,
+
+    /// Size of each copy in bytes
+    pub size: u64,
+
+    /// Paths of all copies, sorted
+    pub paths: Vec<String>,
+
+    /// Bytes that would be freed by keeping a single copy
+    pub wasted_bytes: u64,
+}
+
+impl DuplicateGroupDto {
+    /// Create a group from the paths sharing a hash
+    pub fn new(hash: String, size: u64, mut paths: Vec<String>) -> Self {
+        paths.sort();
+        let wasted_bytes = size * (paths.len().saturating_sub(1) as u64);
+        DuplicateGroupDto {
+            hash,
+            size,
+            paths,
+            wasted_bytes,
+        }
+    }
+}
+
+/// DTO for transferring the duplicate files found in a project
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct DuplicateReportDto {
+    /// The project that was scanned
+    pub project_id: String,
+
+    /// Hash algorithm used ("blake3" or "sha256")
+    pub algorithm: String,
+
+    /// Groups of identical files, largest waste first
+    pub groups: Vec<DuplicateGroupDto>,
+
+    /// Number of files in the source folder
+    pub files_scanned: u64,
+
+    /// Number of files whose hash had to be computed (not cached)
+    pub files_hashed: u64,
+
+    /// Sum of wasted bytes over all groups
+    pub total_wasted_bytes: u64,
+
+    /// When the report was computed, as ISO string
+    pub computed_at: String,
+}
+
+impl DuplicateReportDto {
+    /// Build a report, ordering groups by wasted bytes
+    pub fn new(
+        project_id: String,
+        algorithm: String,
+        mut groups: Vec<DuplicateGroupDto>,
+        files_scanned: u64,
+        files_hashed: u64,
+    ) -> Self {
+        groups.sort_by(|a, b| {
+            b.wasted_bytes
+                .cmp(&a.wasted_bytes)
+                .then_with(|| a.paths.cmp(&b.paths))
+        });
+        let total_wasted_bytes = groups.iter().map(|g| g.wasted_bytes).sum();
+
+        DuplicateReportDto {
+            project_id,
+            algorithm,
+            groups,
+            files_scanned,
+            files_hashed,
+            total_wasted_bytes,
+            computed_at: chrono::Utc::now().to_rfc3339(),
+        }
+    }
+
+    /// Total number of redundant copies across all groups
+    pub fn duplicate_file_count(&self) -> usize {
+        self.groups.iter().map(|g| g.paths.len() - 1).sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_report_totals_and_order() {
+        let small = DuplicateGroupDto::new(
+            "blake3:aa".to_string(),
+            10,
+            vec!["/b".to_string(), "/a".to_string()],
+        );
+        let large = DuplicateGroupDto::new(
+            "blake3:bb".to_string(),
+            100,
+            vec!["/c".to_string(), "/d".to_string(), "/e".to_string()],
+        );
+
+        assert_eq!(small.paths, vec!["/a".to_string(), "/b".to_string()]);
+        assert_eq!(small.wasted_bytes, 10);
+        assert_eq!(large.wasted_bytes, 200);
+
+        let report = DuplicateReportDto::new(
+            "proj_1".to_string(),
+            "blake3".to_string(),
+            vec![small, large],
+            7,
+            5,
+        );
+
+        assert_eq!(report.groups[0].hash, "blake3:bb");
+        assert_eq!(report.total_wasted_bytes, 210);
+        assert_eq!(report.duplicate_file_count(), 3);
+    }
+}
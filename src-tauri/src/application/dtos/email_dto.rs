@@ -0,0 +1,148 @@
+use serde::{Deserialize, Serialize};
+
+use crate::domain::document::Document;
+use crate::domain::email::{EmailMessage, EmailThread};
+
+/// DTO for a file attached to an email, tracked as a child document
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct EmailAttachmentDto {
+    pub document_id: String,
+    pub file_name: String,
+
+    /// Path of the extracted copy below the email's derivatives
+    pub path: String,
+
+    pub size: u64,
+}
+
+impl EmailAttachmentDto {
+    pub fn new(document: &Document) -> Self {
+        EmailAttachmentDto {
+            document_id: document.id().value().to_string(),
+            file_name: document.file_name().unwrap_or(document.path()).to_string(),
+            path: document.path().to_string(),
+            size: document.size(),
+        }
+    }
+}
+
+/// DTO for the headers of one parsed email message
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct EmailMessageDto {
+    pub document_id: String,
+
+    /// Path of the email file
+    pub path: String,
+
+    /// Position of the message in its file, from 0
+    pub index: u32,
+
+    pub from: Option<String>,
+    pub to: Vec<String>,
+    pub cc: Vec<String>,
+
+    /// When the message was sent, as ISO string
+    pub date: Option<String>,
+
+    pub subject: Option<String>,
+    pub message_id: Option<String>,
+    pub in_reply_to: Vec<String>,
+    pub references: Vec<String>,
+
+    pub attachments: Vec<EmailAttachmentDto>,
+
+    /// Versioned parse method, e.g. "email-parser-v1"
+    pub method: String,
+
+    /// When the file was parsed, as ISO string
+    pub parsed_at: String,
+}
+
+impl EmailMessageDto {
+    /// Build the DTO from a message, its file path and its attachments
+    pub fn new(
+        message: &EmailMessage,
+        path: impl Into<String>,
+        attachments: Vec<EmailAttachmentDto>,
+    ) -> Self {
+        let headers = message.headers();
+        EmailMessageDto {
+            document_id: message.document_id().value().to_string(),
+            path: path.into(),
+            index: message.index(),
+            from: headers.from.clone(),
+            to: headers.to.clone(),
+            cc: headers.cc.clone(),
+            date: headers.date.map(|date| date.to_rfc3339()),
+            subject: headers.subject.clone(),
+            message_id: headers.message_id.clone(),
+            in_reply_to: headers.in_reply_to.clone(),
+            references: headers.references.clone(),
+            attachments,
+            method: message.method().to_string(),
+            parsed_at: message.parsed_at().to_rfc3339(),
+        }
+    }
+}
+
+/// DTO for a reconstructed conversation
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct EmailThreadDto {
+    /// Message id of the oldest message in the thread
+    pub id: String,
+
+    pub subject: Option<String>,
+
+    /// When the first and last messages were sent, as ISO strings
+    pub started_at: Option<String>,
+    pub last_message_at: Option<String>,
+
+    /// Distinct senders and recipients, in order of first appearance
+    pub participants: Vec<String>,
+
+    /// Messages, oldest first
+    pub messages: Vec<EmailMessageDto>,
+}
+
+impl EmailThreadDto {
+    pub fn new(thread: &EmailThread, messages: Vec<EmailMessageDto>) -> Self {
+        EmailThreadDto {
+            id: thread.id.clone(),
+            subject: thread.subject.clone(),
+            started_at: thread.started_at().map(|at| at.to_rfc3339()),
+            last_message_at: thread.last_message_at().map(|at| at.to_rfc3339()),
+            participants: thread.participants(),
+            messages,
+        }
+    }
+}
+
+/// DTO for an email file that could not be parsed
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct EmailParseFailureDto {
+    pub document_id: String,
+    pub path: String,
+    pub error: String,
+}
+
+/// DTO for the outcome of parsing all email documents of a project
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct EmailParseBatchDto {
+    pub project_id: String,
+
+    /// Messages read in this run, including those of attached emails
+    pub results: Vec<EmailMessageDto>,
+
+    /// Documents whose stored messages already matched the file contents
+    pub up_to_date: u64,
+
+    /// Documents that are not email files
+    pub unsupported: u64,
+
+    pub failed: Vec<EmailParseFailureDto>,
+}
@@ -0,0 +1,22 @@
+use serde::{Deserialize, Serialize};
+
+/// DTO for a `.det` document exported to a deliverable file
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportResultDto {
+    /// Written file, inside the project's reports folder
+    pub output_path: String,
+
+    /// "markdown", "html", "docx" or "pdf"
+    pub format: String,
+
+    pub size_bytes: u64,
+
+    /// Entries in the table of contents; 0 if it was not included
+    pub toc_entries: usize,
+
+    /// Distinct source documents cited
+    pub cited_sources: usize,
+
+    pub exported_at: String,
+}
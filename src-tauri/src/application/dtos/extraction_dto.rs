@@ -0,0 +1,105 @@
+use serde::{Deserialize, Serialize};
+
+use crate::domain::extraction::{ExtractionWarning, WarningLocation};
+
+/// DTO for a problem found while extracting one page or section
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ExtractionWarningDto {
+    /// "document", "page" or "section"
+    pub location: String,
+
+    /// Page number, for page warnings
+    pub page: Option<u32>,
+
+    /// Heading of the section, for section warnings
+    pub section: Option<String>,
+
+    pub message: String,
+}
+
+impl From<&ExtractionWarning> for ExtractionWarningDto {
+    fn from(warning: &ExtractionWarning) -> Self {
+        let (location, page, section) = match &warning.location {
+            WarningLocation::Document => ("document", None, None),
+            WarningLocation::Page(page) => ("page", Some(*page), None),
+            WarningLocation::Section(section) => ("section", None, Some(section.clone())),
+        };
+
+        ExtractionWarningDto {
+            location: location.to_string(),
+            page,
+            section,
+            message: warning.message.clone(),
+        }
+    }
+}
+
+/// DTO for the extraction of one document into `extracted.det`
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ExtractionResultDto {
+    pub document_id: String,
+
+    /// Path of the original file
+    pub source_path: String,
+
+    /// Path of the written `.det` file
+    pub det_path: String,
+
+    /// Versioned extraction method, e.g. "pdf-text-v1"
+    pub method: String,
+
+    /// Extraction quality between 0 and 1
+    pub quality_score: f64,
+
+    /// "good", "fair" or "poor"
+    pub quality_level: String,
+
+    /// Whether the quality is low enough that a person should check it
+    pub needs_review: bool,
+
+    pub warnings: Vec<ExtractionWarningDto>,
+
+    /// Number of top-level blocks in the extracted document
+    pub block_count: usize,
+
+    /// Number of pages, for paginated formats
+    pub page_count: Option<u32>,
+
+    /// Pages whose text was recognized by OCR
+    pub ocr_pages: Vec<u32>,
+
+    /// OCR pages recognized with low confidence that need manual correction
+    pub pages_needing_correction: Vec<u32>,
+
+    /// When the extraction was made, as ISO string
+    pub extracted_at: String,
+}
+
+/// DTO for a file that could not be extracted
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ExtractionFailureDto {
+    pub document_id: String,
+    pub path: String,
+    pub error: String,
+}
+
+/// DTO for the outcome of extracting all documents of a project
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ExtractionBatchDto {
+    pub project_id: String,
+
+    /// Documents extracted in this run
+    pub results: Vec<ExtractionResultDto>,
+
+    /// Documents whose `extracted.det` already matched the file contents
+    pub up_to_date: u64,
+
+    /// Documents in a format no extractor handles
+    pub unsupported: u64,
+
+    pub failed: Vec<ExtractionFailureDto>,
+}
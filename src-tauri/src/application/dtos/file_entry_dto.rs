@@ -21,6 +21,30 @@ pub struct FileEntryDto {
 
     /// Last modification time as ISO string
     pub modified: String,
+
+    /// Content hash as `<algorithm>:<digest>`, when already computed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content_hash: Option<String>,
+
+    /// Saved search shown by this virtual folder
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub saved_search_id: Option<String>,
+
+    /// Results of the saved search found since it was last opened
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub new_results: Option<usize>,
+
+    /// Attachments of an email file, which can be listed like a folder
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub attachment_count: Option<usize>,
+
+    /// Whether the entry is a read-only member of an archive file
+    #[serde(default)]
+    pub is_virtual: bool,
+
+    /// Whether the entry is an archive file, which can be listed like a folder
+    #[serde(default)]
+    pub is_archive: bool,
 }
 
 impl FileEntryDto {
@@ -38,9 +62,27 @@ impl FileEntryDto {
             entry_type,
             size,
             modified,
+            content_hash: None,
+            saved_search_id: None,
+            new_results: None,
+            attachment_count: None,
+            is_virtual: false,
+            is_archive: false,
         }
     }
 
+    /// Attach a known content hash
+    pub fn with_content_hash(mut self, content_hash: String) -> Self {
+        self.content_hash = Some(content_hash);
+        self
+    }
+
+    /// Mark the entry as a read-only member of an archive
+    pub fn into_virtual(mut self) -> Self {
+        self.is_virtual = true;
+        self
+    }
+
     /// Create a file entry DTO
     pub fn file(name: String, path: String, size: Option<u64>, modified: String) -> Self {
         FileEntryDto::new(name, path, "file".to_string(), size, modified)
@@ -359,5 +401,12 @@ mod tests {
         let deserialized: FileEntryDto = serde_json::from_str(&serialized).unwrap();
 
         assert_eq!(file_dto, deserialized);
+        assert!(!serialized.contains("contentHash"));
+
+        let hashed = file_dto.with_content_hash("blake3:abc".to_string());
+        let serialized = serde_json::to_string(&hashed).unwrap();
+        assert!(serialized.contains("\"contentHash\":\"blake3:abc\""));
+        let deserialized: FileEntryDto = serde_json::from_str(&serialized).unwrap();
+        assert_eq!(hashed, deserialized);
     }
 }
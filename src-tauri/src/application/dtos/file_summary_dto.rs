@@ -0,0 +1,125 @@
+use crate::domain::workspace::value_objects::FileCategory;
+use serde::{Deserialize, Serialize};
+
+/// DTO for transferring per-project file type counts
+///
+/// Backs the "file type counts" column of the project list and the
+/// workspace header.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectFileSummaryDto {
+    /// The project the counts belong to
+    pub project_id: String,
+
+    /// Number of documents (PDF, Office, text, ...)
+    pub documents: u64,
+
+    /// Number of audio files
+    pub audio: u64,
+
+    /// Number of video files
+    pub video: u64,
+
+    /// Number of images
+    pub images: u64,
+
+    /// Number of files that match no other category
+    pub other: u64,
+
+    /// Total number of files
+    pub total_files: u64,
+
+    /// Total size of all files in bytes
+    pub total_size: u64,
+
+    /// Number of files inside archives, also counted in their categories
+    #[serde(default)]
+    pub archive_members: u64,
+
+    /// When the counts were computed, as ISO string
+    pub computed_at: String,
+}
+
+impl ProjectFileSummaryDto {
+    /// Create an empty summary for a project
+    pub fn empty(project_id: String, computed_at: String) -> Self {
+        ProjectFileSummaryDto {
+            project_id,
+            documents: 0,
+            audio: 0,
+            video: 0,
+            images: 0,
+            other: 0,
+            total_files: 0,
+            total_size: 0,
+            archive_members: 0,
+            computed_at,
+        }
+    }
+
+    /// Count one file of the given category
+    pub fn record(&mut self, category: FileCategory, size: u64) {
+        match category {
+            FileCategory::Documents => self.documents += 1,
+            FileCategory::Audio => self.audio += 1,
+            FileCategory::Video => self.video += 1,
+            FileCategory::Images => self.images += 1,
+            FileCategory::Other => self.other += 1,
+        }
+        self.total_files += 1;
+        self.total_size += size;
+    }
+
+    /// Count one file found inside an archive
+    pub fn record_archive_member(&mut self, category: FileCategory, size: u64) {
+        self.record(category, size);
+        self.archive_members += 1;
+    }
+
+    /// Get the count for a category
+    pub fn count_for(&self, category: FileCategory) -> u64 {
+        match category {
+            FileCategory::Documents => self.documents,
+            FileCategory::Audio => self.audio,
+            FileCategory::Video => self.video,
+            FileCategory::Images => self.images,
+            FileCategory::Other => self.other,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_updates_counts() {
+        let mut summary = ProjectFileSummaryDto::empty(
+            "proj_test".to_string(),
+            "2024-01-01T00:00:00Z".to_string(),
+        );
+
+        summary.record(FileCategory::Documents, 100);
+        summary.record(FileCategory::Documents, 50);
+        summary.record(FileCategory::Video, 1000);
+
+        assert_eq!(summary.count_for(FileCategory::Documents), 2);
+        assert_eq!(summary.count_for(FileCategory::Video), 1);
+        assert_eq!(summary.count_for(FileCategory::Audio), 0);
+        assert_eq!(summary.total_files, 3);
+        assert_eq!(summary.total_size, 1150);
+    }
+
+    #[test]
+    fn test_serialization_uses_camel_case() {
+        let summary = ProjectFileSummaryDto::empty(
+            "proj_test".to_string(),
+            "2024-01-01T00:00:00Z".to_string(),
+        );
+        let json = serde_json::to_string(&summary).unwrap();
+
+        assert!(json.contains("\"projectId\""));
+        assert!(json.contains("\"totalFiles\""));
+        assert!(json.contains("\"computedAt\""));
+    }
+}
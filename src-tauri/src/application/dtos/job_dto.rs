@@ -0,0 +1,111 @@
+use crate::application::jobs::JobRecord;
+use serde::{Deserialize, Serialize};
+
+/// DTO for transferring background job state
+///
+/// Sent in response to job commands and as the payload of job update
+/// events, so the frontend can render progress bars and results.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct JobDto {
+    /// Job identifier
+    pub id: String,
+
+    /// Handler kind (e.g. "hash_project")
+    pub kind: String,
+
+    /// Project the job operates on, if any
+    pub project_id: Option<String>,
+
+    /// One of "queued", "running", "succeeded", "failed", "cancelled"
+    pub status: String,
+
+    /// Units of work completed
+    pub progress_current: u64,
+
+    /// Total units of work, if known
+    pub progress_total: Option<u64>,
+
+    /// Completion percentage, if the total is known
+    pub progress_percentage: Option<f64>,
+
+    /// Description of the current step
+    pub progress_message: Option<String>,
+
+    /// Handler result for succeeded jobs
+    pub result: Option<serde_json::Value>,
+
+    /// Error message for failed jobs
+    pub error: Option<String>,
+
+    /// Whether the job is resumed after an application restart
+    pub idempotent: bool,
+
+    /// Number of times the job has been started
+    pub attempts: u32,
+
+    /// Creation time as ISO string
+    pub created_at: String,
+
+    /// Start time of the latest attempt as ISO string
+    pub started_at: Option<String>,
+
+    /// Completion time as ISO string
+    pub finished_at: Option<String>,
+}
+
+impl JobDto {
+    /// Check if the job has finished
+    pub fn is_finished(&self) -> bool {
+        matches!(self.status.as_str(), "succeeded" | "failed" | "cancelled")
+    }
+}
+
+impl From<&JobRecord> for JobDto {
+    fn from(job: &JobRecord) -> Self {
+        JobDto {
+            id: job.id.to_string(),
+            kind: job.kind.clone(),
+            project_id: job.project_id.clone(),
+            status: job.status.as_str().to_string(),
+            progress_current: job.progress.current,
+            progress_total: job.progress.total,
+            progress_percentage: job.progress.percentage(),
+            progress_message: job.progress.message.clone(),
+            result: job.result.clone(),
+            error: job.error.clone(),
+            idempotent: job.idempotent,
+            attempts: job.attempts,
+            created_at: job.created_at.to_rfc3339(),
+            started_at: job.started_at.map(|t| t.to_rfc3339()),
+            finished_at: job.finished_at.map(|t| t.to_rfc3339()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_job_record() {
+        let mut job = JobRecord::new(
+            "hash_project",
+            Some("proj_123".to_string()),
+            serde_json::Value::Null,
+            true,
+        );
+        job.mark_running();
+        job.mark_failed("disk unplugged");
+
+        let dto = JobDto::from(&job);
+        assert_eq!(dto.id, job.id.to_string());
+        assert_eq!(dto.status, "failed");
+        assert_eq!(dto.error.as_deref(), Some("disk unplugged"));
+        assert!(dto.is_finished());
+
+        let json = serde_json::to_string(&dto).unwrap();
+        assert!(json.contains("\"progressCurrent\""));
+        assert!(json.contains("\"projectId\""));
+    }
+}
@@ -0,0 +1,97 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use crate::domain::media::MediaRecord;
+
+/// DTO for the probed metadata of one audio or video document
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct MediaMetadataDto {
+    pub document_id: String,
+
+    /// Path of the media file
+    pub path: String,
+
+    /// "audio" or "video"
+    pub kind: String,
+
+    /// Container format, e.g. "MP3", "MP4" or "WebM"
+    pub container: String,
+
+    pub duration_ms: Option<u64>,
+    pub duration_seconds: Option<f64>,
+
+    pub audio_codec: Option<String>,
+    pub video_codec: Option<String>,
+    pub bitrate_kbps: Option<u32>,
+    pub sample_rate: Option<u32>,
+    pub channels: Option<u16>,
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+    pub frame_rate: Option<f64>,
+
+    /// Embedded tags by canonical name, e.g. "title" or "artist"
+    pub tags: BTreeMap<String, String>,
+
+    /// Versioned probe method, e.g. "mp3-probe-v1"
+    pub method: String,
+
+    /// When the file was probed, as ISO string
+    pub probed_at: String,
+}
+
+impl MediaMetadataDto {
+    pub fn new(record: &MediaRecord, path: impl Into<String>) -> Self {
+        let metadata = record.metadata();
+        MediaMetadataDto {
+            document_id: record.document_id().value().to_string(),
+            path: path.into(),
+            kind: metadata.kind.as_str().to_string(),
+            container: metadata.container.clone(),
+            duration_ms: metadata.duration_ms,
+            duration_seconds: metadata.duration_seconds(),
+            audio_codec: metadata.audio_codec.clone(),
+            video_codec: metadata.video_codec.clone(),
+            bitrate_kbps: metadata.bitrate_kbps,
+            sample_rate: metadata.sample_rate,
+            channels: metadata.channels,
+            width: metadata.width,
+            height: metadata.height,
+            frame_rate: metadata.frame_rate,
+            tags: metadata
+                .tags
+                .iter()
+                .map(|(name, value)| (name.to_string(), value.to_string()))
+                .collect(),
+            method: record.method().to_string(),
+            probed_at: record.probed_at().to_rfc3339(),
+        }
+    }
+}
+
+/// DTO for a media file that could not be probed
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct MediaProbeFailureDto {
+    pub document_id: String,
+    pub path: String,
+    pub error: String,
+}
+
+/// DTO for the outcome of probing all media documents of a project
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct MediaProbeBatchDto {
+    pub project_id: String,
+
+    /// Documents probed in this run
+    pub results: Vec<MediaMetadataDto>,
+
+    /// Documents whose stored metadata already matched the file contents
+    pub up_to_date: u64,
+
+    /// Documents that are not in a supported media format
+    pub unsupported: u64,
+
+    pub failed: Vec<MediaProbeFailureDto>,
+}
@@ -1,7 +1,47 @@
+pub mod annotation_dto;
+pub mod anonymization_dto;
+pub mod category_dto;
+pub mod citation_dto;
+pub mod coding_dto;
+pub mod cost_table_dto;
+pub mod derivation_dto;
 pub mod directory_listing_dto;
+pub mod document_dto;
+pub mod duplicate_report_dto;
+pub mod email_dto;
+pub mod export_dto;
+pub mod extraction_dto;
 pub mod file_entry_dto;
+pub mod file_summary_dto;
+pub mod job_dto;
+pub mod media_dto;
+pub mod preview_dto;
+pub mod report_dto;
+pub mod review_dto;
+pub mod search_dto;
+pub mod snapshot_dto;
 pub mod workspace_dto;
 
+pub use annotation_dto::*;
+pub use anonymization_dto::*;
+pub use category_dto::*;
+pub use citation_dto::*;
+pub use coding_dto::*;
+pub use cost_table_dto::*;
+pub use derivation_dto::*;
 pub use directory_listing_dto::*;
+pub use document_dto::*;
+pub use duplicate_report_dto::*;
+pub use email_dto::*;
+pub use export_dto::*;
+pub use extraction_dto::*;
 pub use file_entry_dto::*;
+pub use file_summary_dto::*;
+pub use job_dto::*;
+pub use media_dto::*;
+pub use preview_dto::*;
+pub use report_dto::*;
+pub use review_dto::*;
+pub use search_dto::*;
+pub use snapshot_dto::*;
 pub use workspace_dto::*;
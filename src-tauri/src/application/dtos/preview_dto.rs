@@ -0,0 +1,86 @@
+use serde::{Deserialize, Serialize};
+
+use crate::domain::preview::PreviewFormat;
+
+/// DTO for the preview of one file in the explorer
+///
+/// Previews are generated in the background. A `pending` preview is being
+/// generated; ask again later to get it.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PreviewDto {
+    /// Path of the previewed file
+    pub path: String,
+
+    /// "ready", "pending" or "unavailable"
+    pub status: String,
+
+    /// "image" or "snippet" for a ready preview
+    pub kind: Option<String>,
+
+    /// Media type of the preview, e.g. "image/png"
+    pub media_type: Option<String>,
+
+    /// Cached image file of an image preview
+    pub image_path: Option<String>,
+
+    /// Contents of the cached image, when requested
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub image_bytes: Option<Vec<u8>>,
+
+    /// Text of a snippet preview
+    pub text: Option<String>,
+
+    /// Why no preview is available
+    pub message: Option<String>,
+}
+
+impl PreviewDto {
+    fn with_status(path: &str, status: &str) -> Self {
+        PreviewDto {
+            path: path.to_string(),
+            status: status.to_string(),
+            kind: None,
+            media_type: None,
+            image_path: None,
+            image_bytes: None,
+            text: None,
+            message: None,
+        }
+    }
+
+    pub fn pending(path: &str) -> Self {
+        Self::with_status(path, "pending")
+    }
+
+    pub fn unavailable(path: &str, message: impl Into<String>) -> Self {
+        PreviewDto {
+            message: Some(message.into()),
+            ..Self::with_status(path, "unavailable")
+        }
+    }
+
+    /// A ready image preview stored at `image_path`
+    pub fn image(path: &str, format: PreviewFormat, image_path: String) -> Self {
+        PreviewDto {
+            kind: Some("image".to_string()),
+            media_type: Some(format.media_type().to_string()),
+            image_path: Some(image_path),
+            ..Self::with_status(path, "ready")
+        }
+    }
+
+    /// A ready text snippet
+    pub fn snippet(path: &str, text: String) -> Self {
+        PreviewDto {
+            kind: Some("snippet".to_string()),
+            media_type: Some(PreviewFormat::Text.media_type().to_string()),
+            text: Some(text),
+            ..Self::with_status(path, "ready")
+        }
+    }
+
+    pub fn is_ready(&self) -> bool {
+        self.status == "ready"
+    }
+}
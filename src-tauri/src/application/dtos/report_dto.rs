@@ -0,0 +1,99 @@
+use std::path::Path;
+
+use crate::domain::det::PmNode;
+use crate::domain::report::Report;
+use serde::{Deserialize, Serialize};
+
+/// DTO for transferring a report's index entry
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ReportDto {
+    /// Report identifier ("report_<uuid>")
+    pub id: String,
+
+    pub project_id: String,
+
+    pub title: String,
+
+    /// "findings", "analysis_summary", "deliverable" or "client_report"
+    pub category: String,
+
+    /// "draft" or "final"
+    pub status: String,
+
+    /// Absolute path of the `.det` file
+    pub path: String,
+
+    /// Path of the `.det` file relative to the reports folder
+    pub relative_path: String,
+
+    /// Linked source document identifiers
+    pub source_documents: Vec<String>,
+
+    /// Paths of linked derivative `.det` files
+    pub derivatives: Vec<String>,
+
+    /// When the report was created, as ISO string
+    pub created_at: String,
+
+    /// When the report last changed, as ISO string
+    pub updated_at: String,
+}
+
+impl ReportDto {
+    /// Convert a report stored below `reports_folder`
+    pub fn from_report(report: &Report, reports_folder: &Path) -> Self {
+        ReportDto {
+            id: report.id().value().to_string(),
+            project_id: report.project_id().value().to_string(),
+            title: report.title().to_string(),
+            category: report.category().as_str().to_string(),
+            status: report.status().as_str().to_string(),
+            path: reports_folder
+                .join(report.path())
+                .to_string_lossy()
+                .to_string(),
+            relative_path: report.path().to_string(),
+            source_documents: report
+                .source_documents()
+                .iter()
+                .map(|id| id.value().to_string())
+                .collect(),
+            derivatives: report.derivatives().to_vec(),
+            created_at: report.created_at().to_rfc3339(),
+            updated_at: report.updated_at().to_rfc3339(),
+        }
+    }
+}
+
+/// DTO for a report together with its `.det` content
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ReportContentDto {
+    pub report: ReportDto,
+
+    /// ProseMirror document of the report body
+    pub content: PmNode,
+}
+
+/// Changes to apply to a report; absent fields are left as they are
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateReportRequest {
+    pub title: Option<String>,
+
+    /// New category; the file moves to the category's folder
+    pub category: Option<String>,
+
+    /// "draft" or "final"
+    pub status: Option<String>,
+
+    /// New ProseMirror document for the report body
+    pub content: Option<PmNode>,
+
+    /// Source document identifiers, replacing the current links
+    pub source_documents: Option<Vec<String>>,
+
+    /// Derivative `.det` paths, replacing the current links
+    pub derivatives: Option<Vec<String>>,
+}
@@ -0,0 +1,138 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use crate::domain::review::{BatchSource, BatchStatus, ReviewBatch, Reviewer};
+
+/// DTO for a local reviewer profile
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ReviewerDto {
+    /// Stable identifier ("reviewer_<uuid>")
+    pub id: String,
+
+    pub name: String,
+
+    /// Whether this is the reviewer currently working on this machine
+    pub active: bool,
+
+    pub created_at: String,
+}
+
+impl ReviewerDto {
+    pub fn from_reviewer(reviewer: &Reviewer, active: bool) -> Self {
+        ReviewerDto {
+            id: reviewer.id().value().to_string(),
+            name: reviewer.name().to_string(),
+            active,
+            created_at: reviewer.created_at().to_rfc3339(),
+        }
+    }
+}
+
+/// DTO for a review batch with its coding progress
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ReviewBatchDto {
+    /// Stable identifier ("batch_<uuid>")
+    pub id: String,
+
+    pub project_id: String,
+
+    pub name: String,
+
+    /// What the batch was built from
+    pub source: BatchSource,
+
+    pub reviewer_id: Option<String>,
+
+    /// Name of the assigned reviewer
+    pub reviewer_name: Option<String>,
+
+    /// "unassigned", "assigned" or "closed"
+    pub status: BatchStatus,
+
+    /// Documents in review order
+    pub document_ids: Vec<String>,
+
+    pub total: usize,
+
+    /// Documents of the batch with a coding decision
+    pub coded: usize,
+
+    pub remaining: usize,
+
+    pub created_at: String,
+
+    pub updated_at: String,
+
+    pub closed_at: Option<String>,
+}
+
+impl ReviewBatchDto {
+    /// Build the DTO given the assigned reviewer's name and how many of
+    /// the batch's documents are coded
+    pub fn from_batch(batch: &ReviewBatch, reviewer_name: Option<String>, coded: usize) -> Self {
+        let total = batch.document_ids().len();
+        ReviewBatchDto {
+            id: batch.id().value().to_string(),
+            project_id: batch.project_id().value().to_string(),
+            name: batch.name().to_string(),
+            source: batch.source().clone(),
+            reviewer_id: batch.reviewer_id().map(|id| id.value().to_string()),
+            reviewer_name,
+            status: batch.status(),
+            document_ids: batch
+                .document_ids()
+                .iter()
+                .map(|id| id.value().to_string())
+                .collect(),
+            total,
+            coded,
+            remaining: total - coded,
+            created_at: batch.created_at().to_rfc3339(),
+            updated_at: batch.updated_at().to_rfc3339(),
+            closed_at: batch.closed_at().map(|at| at.to_rfc3339()),
+        }
+    }
+}
+
+/// DTO for how much a reviewer has done in a project
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ReviewerStatsDto {
+    pub reviewer_id: String,
+
+    pub name: String,
+
+    pub active: bool,
+
+    /// Batches assigned to the reviewer and not closed
+    pub open_batches: usize,
+
+    pub closed_batches: usize,
+
+    /// Documents in the reviewer's open batches
+    pub assigned_documents: usize,
+
+    /// Of those, the documents with a coding decision
+    pub coded_assigned_documents: usize,
+
+    /// Coding decisions made by the reviewer, recodings included
+    pub decisions: usize,
+
+    /// Distinct documents the reviewer has coded
+    pub documents_coded: usize,
+
+    pub annotations: usize,
+
+    pub first_decision_at: Option<String>,
+
+    pub last_decision_at: Option<String>,
+
+    /// Decisions per hour between the first and the last one; missing
+    /// until they are at least a minute apart
+    pub decisions_per_hour: Option<f64>,
+
+    /// Decisions per day, keyed by `YYYY-MM-DD` (UTC)
+    pub decisions_by_day: BTreeMap<String, usize>,
+}
@@ -0,0 +1,178 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use crate::domain::search::{SavedSearch, SourceKind};
+use crate::infrastructure::ProjectDto;
+
+/// DTO for an item related to a search hit
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchRelationDto {
+    /// How the hit relates to the item: "derivedFrom", "hasDerivative",
+    /// "describes", "citedBy" or "cites"
+    pub relation: String,
+
+    pub kind: SourceKind,
+
+    pub title: String,
+
+    pub path: String,
+
+    pub document_id: Option<String>,
+
+    pub report_id: Option<String>,
+}
+
+/// DTO for one search hit
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchHitDto {
+    /// Where the hit comes from: "original", "derivative", "report" or
+    /// "metadata"
+    pub kind: SourceKind,
+
+    /// File name, derivative name or report title
+    pub title: String,
+
+    /// Path of the file that matched
+    pub path: String,
+
+    /// Source document the hit belongs to, if any
+    pub document_id: Option<String>,
+
+    pub report_id: Option<String>,
+
+    /// Relevance; higher is better
+    pub score: f64,
+
+    /// Passage around the first match in the text
+    pub snippet: Option<String>,
+
+    /// Parts of the item that matched: "title", "tags", "text" or "path"
+    pub matched_fields: Vec<String>,
+
+    /// When the item last changed, as ISO string
+    pub modified_at: Option<String>,
+
+    pub relations: Vec<SearchRelationDto>,
+}
+
+/// DTO for the results of a search in one project
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchResultsDto {
+    pub project_id: String,
+
+    pub query: String,
+
+    /// Number of matching items, before the limit was applied
+    pub total: usize,
+
+    /// Number of matching items per kind
+    pub counts: BTreeMap<String, usize>,
+
+    /// Best hits first
+    pub hits: Vec<SearchHitDto>,
+}
+
+/// DTO for the hits of one project in a search across all projects
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectSearchGroupDto {
+    pub project: ProjectDto,
+
+    /// Number of matching items in the project
+    pub total: usize,
+
+    /// Number of matching items in the project per kind
+    pub counts: BTreeMap<String, usize>,
+
+    /// The project's hits on the requested page, best first
+    pub hits: Vec<SearchHitDto>,
+}
+
+/// DTO for a project left out of a search across all projects
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SkippedProjectDto {
+    pub project: ProjectDto,
+
+    /// Why the project could not be searched
+    pub reason: String,
+}
+
+/// DTO for one page of results of a search across all projects
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GlobalSearchResultsDto {
+    pub query: String,
+
+    /// Number of matching items in all searched projects
+    pub total: usize,
+
+    /// Number of hits skipped before this page
+    pub offset: usize,
+
+    /// Most hits on one page
+    pub limit: usize,
+
+    /// Whether there are hits after this page
+    pub has_more: bool,
+
+    /// Number of projects that were searched
+    pub projects_searched: usize,
+
+    /// Hits on this page, grouped by project; the group with the best hit
+    /// comes first
+    pub groups: Vec<ProjectSearchGroupDto>,
+
+    /// Projects that could not be searched
+    pub skipped: Vec<SkippedProjectDto>,
+}
+
+/// DTO for a saved search
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SavedSearchDto {
+    pub id: String,
+
+    pub project_id: String,
+
+    pub name: String,
+
+    pub query: String,
+
+    /// Whether results found since the last view are counted
+    pub track_new: bool,
+
+    /// Results found since the last view, when tracked
+    pub new_results: Option<usize>,
+
+    /// Path of the virtual folder listing the results
+    pub folder_path: String,
+
+    /// When the results were last viewed, as ISO string
+    pub last_viewed_at: Option<String>,
+
+    pub created_at: String,
+
+    pub updated_at: String,
+}
+
+impl SavedSearchDto {
+    /// Create from a saved search shown at `folder_path`
+    pub fn from_saved_search(search: &SavedSearch, folder_path: String) -> Self {
+        SavedSearchDto {
+            id: search.id().value().to_string(),
+            project_id: search.project_id().value().to_string(),
+            name: search.name().to_string(),
+            query: search.query().to_string(),
+            track_new: search.tracks_new(),
+            new_results: search.new_result_count(),
+            folder_path,
+            last_viewed_at: search.last_viewed_at().map(|at| at.to_rfc3339()),
+            created_at: search.created_at().to_rfc3339(),
+            updated_at: search.updated_at().to_rfc3339(),
+        }
+    }
+}
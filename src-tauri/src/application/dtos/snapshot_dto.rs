@@ -0,0 +1,195 @@
+use serde::{Deserialize, Serialize};
+
+use crate::domain::workspace::entities::{
+    ManifestChange, ManifestChangeKind, ManifestDiff, ManifestSnapshotSummary,
+};
+
+/// DTO for a stored manifest snapshot, without its entries
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SnapshotSummaryDto {
+    pub id: String,
+    pub project_id: String,
+    pub label: Option<String>,
+
+    /// Hash algorithm used ("blake3" or "sha256")
+    pub algorithm: String,
+
+    pub file_count: u64,
+    pub total_size: u64,
+
+    /// When the snapshot was taken, as ISO string
+    pub created_at: String,
+}
+
+impl From<&ManifestSnapshotSummary> for SnapshotSummaryDto {
+    fn from(summary: &ManifestSnapshotSummary) -> Self {
+        SnapshotSummaryDto {
+            id: summary.id.as_str().to_string(),
+            project_id: summary.project_id.value().to_string(),
+            label: summary.label.clone(),
+            algorithm: summary.algorithm.as_str().to_string(),
+            file_count: summary.file_count as u64,
+            total_size: summary.total_size,
+            created_at: summary.created_at.to_rfc3339(),
+        }
+    }
+}
+
+/// DTO for one file-level change between two manifests
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SnapshotChangeDto {
+    /// "added", "removed", "modified" or "renamed"
+    pub kind: String,
+
+    /// Path relative to the source folder (the new path for renames)
+    pub path: String,
+
+    /// Path in the base snapshot, set for renames only
+    pub previous_path: Option<String>,
+
+    pub size: Option<u64>,
+    pub previous_size: Option<u64>,
+
+    /// Content hashes as `<algorithm>:<digest>`
+    pub hash: Option<String>,
+    pub previous_hash: Option<String>,
+}
+
+impl From<&ManifestChange> for SnapshotChangeDto {
+    fn from(change: &ManifestChange) -> Self {
+        let previous_path = match change.kind {
+            ManifestChangeKind::Renamed => change.previous.as_ref().map(|e| e.path.clone()),
+            _ => None,
+        };
+
+        SnapshotChangeDto {
+            kind: change.kind.as_str().to_string(),
+            path: change.path().to_string(),
+            previous_path,
+            size: change.current.as_ref().map(|e| e.size),
+            previous_size: change.previous.as_ref().map(|e| e.size),
+            hash: change.current.as_ref().map(|e| e.hash.to_string()),
+            previous_hash: change.previous.as_ref().map(|e| e.hash.to_string()),
+        }
+    }
+}
+
+/// DTO for the differences between two snapshots
+///
+/// When `target_snapshot_id` is None the base snapshot was compared with the
+/// live source folder.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SnapshotDiffDto {
+    pub project_id: String,
+    pub base_snapshot_id: String,
+    pub target_snapshot_id: Option<String>,
+
+    pub added: u64,
+    pub removed: u64,
+    pub modified: u64,
+    pub renamed: u64,
+    pub unchanged: u64,
+
+    /// All changes, sorted by path
+    pub changes: Vec<SnapshotChangeDto>,
+
+    /// When the diff was computed, as ISO string
+    pub computed_at: String,
+}
+
+impl SnapshotDiffDto {
+    /// Create a DTO from a computed manifest diff
+    pub fn new(
+        project_id: String,
+        base_snapshot_id: String,
+        target_snapshot_id: Option<String>,
+        diff: &ManifestDiff,
+    ) -> Self {
+        SnapshotDiffDto {
+            project_id,
+            base_snapshot_id,
+            target_snapshot_id,
+            added: diff.count(ManifestChangeKind::Added) as u64,
+            removed: diff.count(ManifestChangeKind::Removed) as u64,
+            modified: diff.count(ManifestChangeKind::Modified) as u64,
+            renamed: diff.count(ManifestChangeKind::Renamed) as u64,
+            unchanged: diff.unchanged as u64,
+            changes: diff.changes.iter().map(SnapshotChangeDto::from).collect(),
+            computed_at: chrono::Utc::now().to_rfc3339(),
+        }
+    }
+
+    /// Render the changes as CSV, one row per change with a header row
+    pub fn to_csv(&self) -> Result<String, csv::Error> {
+        let mut writer = csv::Writer::from_writer(Vec::new());
+        writer.write_record([
+            "kind",
+            "path",
+            "previous_path",
+            "size",
+            "previous_size",
+            "hash",
+            "previous_hash",
+        ])?;
+
+        let optional = |value: Option<u64>| value.map(|v| v.to_string()).unwrap_or_default();
+        for change in &self.changes {
+            writer.write_record([
+                change.kind.as_str(),
+                change.path.as_str(),
+                change.previous_path.as_deref().unwrap_or_default(),
+                optional(change.size).as_str(),
+                optional(change.previous_size).as_str(),
+                change.hash.as_deref().unwrap_or_default(),
+                change.previous_hash.as_deref().unwrap_or_default(),
+            ])?;
+        }
+
+        let bytes = writer
+            .into_inner()
+            .map_err(|e| csv::Error::from(e.into_error()))?;
+        Ok(String::from_utf8_lossy(&bytes).into_owned())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::workspace::entities::ManifestEntry;
+    use crate::domain::workspace::value_objects::{ContentHash, HashAlgorithm};
+
+    fn entry(path: &str, contents: &str) -> ManifestEntry {
+        ManifestEntry {
+            path: path.to_string(),
+            size: contents.len() as u64,
+            modified_ns: 0,
+            hash: ContentHash::of_bytes(HashAlgorithm::Blake3, contents.as_bytes()),
+        }
+    }
+
+    #[test]
+    fn test_diff_dto_counts_and_csv() {
+        let diff = ManifestDiff::between(
+            &[entry("old, name.pdf", "moved"), entry("keep.pdf", "same")],
+            &[
+                entry("new.pdf", "moved"),
+                entry("keep.pdf", "same"),
+                entry("added.pdf", "new"),
+            ],
+        );
+        let dto = SnapshotDiffDto::new("proj".to_string(), "snap".to_string(), None, &diff);
+
+        assert_eq!(dto.added, 1);
+        assert_eq!(dto.renamed, 1);
+        assert_eq!(dto.unchanged, 1);
+
+        let csv = dto.to_csv().unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert!(lines[0].starts_with("kind,path,previous_path"));
+        assert!(lines[2].starts_with("renamed,new.pdf,\"old, name.pdf\",5,5,blake3:"));
+    }
+}
@@ -73,6 +73,9 @@ mod tests {
             is_root: true,
             parent_path: None,
             can_navigate_up: false,
+            saved_search_id: None,
+            email_document_id: None,
+            archive_path: None,
         };
 
         let workspace = WorkspaceDto::new(
@@ -101,6 +104,9 @@ mod tests {
             is_root: false,
             parent_path: Some("/Users/test/project".to_string()),
             can_navigate_up: true,
+            saved_search_id: None,
+            email_document_id: None,
+            archive_path: None,
         };
 
         let workspace = WorkspaceDto::new(
@@ -122,6 +128,9 @@ mod tests {
             is_root: true,
             parent_path: None,
             can_navigate_up: false,
+            saved_search_id: None,
+            email_document_id: None,
+            archive_path: None,
         };
 
         let workspace = WorkspaceDto::new(
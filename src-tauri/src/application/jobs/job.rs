@@ -0,0 +1,245 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Display};
+use uuid::Uuid;
+
+/// JobId value object - prefixed UUID for background jobs
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct JobId(String);
+
+impl JobId {
+    pub fn new() -> Self {
+        JobId(format!("job_{}", Uuid::new_v4().hyphenated()))
+    }
+
+    pub fn from_string(id: String) -> Result<Self, String> {
+        let uuid_part = id
+            .strip_prefix("job_")
+            .ok_or_else(|| "JobId must start with 'job_'".to_string())?;
+
+        Uuid::parse_str(uuid_part).map_err(|_| "Invalid UUID format".to_string())?;
+
+        Ok(JobId(id))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Default for JobId {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Display for JobId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Lifecycle state of a background job
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum JobStatus {
+    Queued,
+    Running,
+    Succeeded,
+    Failed,
+    Cancelled,
+}
+
+impl JobStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            JobStatus::Queued => "queued",
+            JobStatus::Running => "running",
+            JobStatus::Succeeded => "succeeded",
+            JobStatus::Failed => "failed",
+            JobStatus::Cancelled => "cancelled",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Result<Self, String> {
+        match s {
+            "queued" => Ok(JobStatus::Queued),
+            "running" => Ok(JobStatus::Running),
+            "succeeded" => Ok(JobStatus::Succeeded),
+            "failed" => Ok(JobStatus::Failed),
+            "cancelled" => Ok(JobStatus::Cancelled),
+            _ => Err(format!("Invalid job status: {}", s)),
+        }
+    }
+
+    /// Whether the job has finished and will not change again
+    pub fn is_terminal(&self) -> bool {
+        matches!(
+            self,
+            JobStatus::Succeeded | JobStatus::Failed | JobStatus::Cancelled
+        )
+    }
+}
+
+/// Progress reported by a running job
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct JobProgress {
+    /// Units of work completed so far
+    pub current: u64,
+    /// Total units of work, if known
+    pub total: Option<u64>,
+    /// Human-readable description of the current step
+    pub message: Option<String>,
+}
+
+impl JobProgress {
+    /// Completion percentage, if the total is known
+    pub fn percentage(&self) -> Option<f64> {
+        match self.total {
+            Some(0) => Some(100.0),
+            Some(total) => Some((self.current.min(total) as f64 / total as f64) * 100.0),
+            None => None,
+        }
+    }
+}
+
+/// Persistent record of a background job
+#[derive(Debug, Clone, PartialEq)]
+pub struct JobRecord {
+    pub id: JobId,
+    /// Handler kind that executes this job
+    pub kind: String,
+    /// Project the job operates on, if any
+    pub project_id: Option<String>,
+    /// Handler-specific parameters
+    pub params: serde_json::Value,
+    pub status: JobStatus,
+    /// Whether the job can safely be re-run after an interrupted attempt
+    pub idempotent: bool,
+    pub progress: JobProgress,
+    pub result: Option<serde_json::Value>,
+    pub error: Option<String>,
+    /// Number of times the job has been started
+    pub attempts: u32,
+    pub created_at: DateTime<Utc>,
+    pub started_at: Option<DateTime<Utc>>,
+    pub finished_at: Option<DateTime<Utc>>,
+}
+
+impl JobRecord {
+    /// Create a new queued job
+    pub fn new(
+        kind: impl Into<String>,
+        project_id: Option<String>,
+        params: serde_json::Value,
+        idempotent: bool,
+    ) -> Self {
+        JobRecord {
+            id: JobId::new(),
+            kind: kind.into(),
+            project_id,
+            params,
+            status: JobStatus::Queued,
+            idempotent,
+            progress: JobProgress::default(),
+            result: None,
+            error: None,
+            attempts: 0,
+            created_at: Utc::now(),
+            started_at: None,
+            finished_at: None,
+        }
+    }
+
+    pub fn mark_running(&mut self) {
+        self.status = JobStatus::Running;
+        self.attempts += 1;
+        self.started_at = Some(Utc::now());
+        self.finished_at = None;
+        self.error = None;
+    }
+
+    pub fn mark_succeeded(&mut self, result: serde_json::Value) {
+        self.status = JobStatus::Succeeded;
+        self.result = Some(result);
+        self.finished_at = Some(Utc::now());
+        if let Some(total) = self.progress.total {
+            self.progress.current = total;
+        }
+    }
+
+    pub fn mark_failed(&mut self, error: impl Into<String>) {
+        self.status = JobStatus::Failed;
+        self.error = Some(error.into());
+        self.finished_at = Some(Utc::now());
+    }
+
+    pub fn mark_cancelled(&mut self) {
+        self.status = JobStatus::Cancelled;
+        self.finished_at = Some(Utc::now());
+    }
+
+    /// Put an interrupted job back in the queue
+    pub fn requeue(&mut self) {
+        self.status = JobStatus::Queued;
+        self.progress = JobProgress::default();
+        self.started_at = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_job_id_format() {
+        let id = JobId::new();
+        assert!(id.as_str().starts_with("job_"));
+        assert!(JobId::from_string(id.to_string()).is_ok());
+        assert!(JobId::from_string("proj_123".to_string()).is_err());
+        assert!(JobId::from_string("job_not-a-uuid".to_string()).is_err());
+    }
+
+    #[test]
+    fn test_status_round_trip() {
+        for status in [
+            JobStatus::Queued,
+            JobStatus::Running,
+            JobStatus::Succeeded,
+            JobStatus::Failed,
+            JobStatus::Cancelled,
+        ] {
+            assert_eq!(JobStatus::from_str(status.as_str()).unwrap(), status);
+        }
+        assert!(!JobStatus::Running.is_terminal());
+        assert!(JobStatus::Cancelled.is_terminal());
+    }
+
+    #[test]
+    fn test_lifecycle_transitions() {
+        let mut job = JobRecord::new("test", None, serde_json::Value::Null, true);
+        assert_eq!(job.status, JobStatus::Queued);
+
+        job.mark_running();
+        assert_eq!(job.status, JobStatus::Running);
+        assert_eq!(job.attempts, 1);
+        assert!(job.started_at.is_some());
+
+        job.progress.total = Some(10);
+        job.mark_succeeded(serde_json::json!({"ok": true}));
+        assert_eq!(job.status, JobStatus::Succeeded);
+        assert_eq!(job.progress.current, 10);
+        assert!(job.finished_at.is_some());
+    }
+
+    #[test]
+    fn test_progress_percentage() {
+        let progress = JobProgress {
+            current: 5,
+            total: Some(20),
+            message: None,
+        };
+        assert_eq!(progress.percentage(), Some(25.0));
+        assert_eq!(JobProgress::default().percentage(), None);
+    }
+}
@@ -0,0 +1,40 @@
+use tauri::{AppHandle, Emitter};
+
+use crate::application::dtos::JobDto;
+
+/// Name of the event emitted to the frontend whenever a job changes
+pub const JOB_UPDATED_EVENT: &str = "job-updated";
+
+/// Receiver for job status and progress updates
+///
+/// Keeps the job manager independent of Tauri so it can run in tests.
+pub trait JobEventSink: Send + Sync {
+    /// Called after every status change and progress report
+    fn job_updated(&self, job: &JobDto);
+}
+
+/// Event sink that discards all updates
+pub struct NoopJobEventSink;
+
+impl JobEventSink for NoopJobEventSink {
+    fn job_updated(&self, _job: &JobDto) {}
+}
+
+/// Event sink that forwards updates to the frontend as Tauri events
+pub struct TauriJobEventSink {
+    app: AppHandle,
+}
+
+impl TauriJobEventSink {
+    pub fn new(app: AppHandle) -> Self {
+        TauriJobEventSink { app }
+    }
+}
+
+impl JobEventSink for TauriJobEventSink {
+    fn job_updated(&self, job: &JobDto) {
+        if let Err(error) = self.app.emit(JOB_UPDATED_EVENT, job) {
+            tracing::warn!("Failed to emit job update for {}: {}", job.id, error);
+        }
+    }
+}
@@ -0,0 +1,103 @@
+use async_trait::async_trait;
+use std::sync::Arc;
+use tokio_util::sync::CancellationToken;
+
+use crate::application::jobs::job::{JobId, JobProgress};
+use crate::application::jobs::job_manager::JobManager;
+use crate::infrastructure::{AppError, AppResult};
+
+/// Executes one kind of background job
+///
+/// Handlers are registered with the `JobManager` under their `kind`. A
+/// handler that returns `true` from `is_idempotent` is re-run from the start
+/// when the application restarts while the job was queued or running;
+/// other interrupted jobs are marked as failed.
+#[async_trait]
+pub trait JobHandler: Send + Sync {
+    /// Unique name of the job kind, stored with each job record
+    fn kind(&self) -> &'static str;
+
+    /// Whether the job can safely be re-run after an interrupted attempt
+    fn is_idempotent(&self) -> bool {
+        false
+    }
+
+    /// Run the job to completion and return its result
+    ///
+    /// Long-running handlers should call `context.check_cancelled()` between
+    /// units of work and report progress as they go.
+    async fn run(
+        &self,
+        context: JobContext,
+        params: serde_json::Value,
+    ) -> AppResult<serde_json::Value>;
+}
+
+/// Per-run handle passed to a `JobHandler`
+#[derive(Clone)]
+pub struct JobContext {
+    job_id: JobId,
+    project_id: Option<String>,
+    token: CancellationToken,
+    manager: Arc<JobManager>,
+}
+
+impl JobContext {
+    pub(crate) fn new(
+        job_id: JobId,
+        project_id: Option<String>,
+        token: CancellationToken,
+        manager: Arc<JobManager>,
+    ) -> Self {
+        JobContext {
+            job_id,
+            project_id,
+            token,
+            manager,
+        }
+    }
+
+    /// The ID of the running job
+    pub fn job_id(&self) -> &JobId {
+        &self.job_id
+    }
+
+    /// The project the job operates on, if any
+    pub fn project_id(&self) -> Option<&str> {
+        self.project_id.as_deref()
+    }
+
+    /// Token that is cancelled when the user cancels the job
+    pub fn cancellation_token(&self) -> &CancellationToken {
+        &self.token
+    }
+
+    /// Whether cancellation has been requested
+    pub fn is_cancelled(&self) -> bool {
+        self.token.is_cancelled()
+    }
+
+    /// Return an error if cancellation has been requested
+    pub fn check_cancelled(&self) -> AppResult<()> {
+        if self.is_cancelled() {
+            return Err(AppError::new(
+                "JOB_CANCELLED",
+                "Job was cancelled",
+                None,
+                true,
+                false,
+            ));
+        }
+        Ok(())
+    }
+
+    /// Report progress to the job record and the frontend
+    pub async fn report_progress(&self, current: u64, total: Option<u64>, message: Option<&str>) {
+        let progress = JobProgress {
+            current,
+            total,
+            message: message.map(str::to_string),
+        };
+        self.manager.update_progress(&self.job_id, progress).await;
+    }
+}
@@ -0,0 +1,556 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::{Mutex, Semaphore};
+use tokio_util::sync::CancellationToken;
+
+use crate::application::dtos::JobDto;
+use crate::application::jobs::job::{JobId, JobProgress, JobRecord, JobStatus};
+use crate::application::jobs::job_events::{JobEventSink, NoopJobEventSink};
+use crate::application::jobs::job_handler::{JobContext, JobHandler};
+use crate::application::jobs::job_repository::{JobFilter, JobRepository};
+use crate::infrastructure::{AppError, AppResult};
+
+/// Number of jobs that may run at the same time by default
+pub const DEFAULT_WORKER_COUNT: usize = 2;
+
+/// Minimum time between two progress writes to the database
+const PROGRESS_PERSIST_INTERVAL: Duration = Duration::from_millis(500);
+
+/// In-memory state of a job that is queued or running in this process
+struct ActiveJob {
+    record: JobRecord,
+    token: CancellationToken,
+    last_persisted: Instant,
+}
+
+/// Worker pool and registry for background jobs
+///
+/// Jobs are persisted before they are queued, so their state survives a
+/// restart. Each job runs on its own task but waits for one of
+/// `worker_count` permits before starting, which bounds how many
+/// long-running operations compete for disk and CPU at once.
+pub struct JobManager {
+    repository: Arc<dyn JobRepository>,
+    handlers: std::sync::RwLock<HashMap<&'static str, Arc<dyn JobHandler>>>,
+    active: Mutex<HashMap<JobId, ActiveJob>>,
+    workers: Arc<Semaphore>,
+    worker_count: usize,
+    event_sink: std::sync::RwLock<Arc<dyn JobEventSink>>,
+    shutting_down: AtomicBool,
+}
+
+impl JobManager {
+    /// Create a new JobManager running at most `worker_count` jobs at a time
+    pub fn new(repository: Arc<dyn JobRepository>, worker_count: usize) -> Self {
+        let worker_count = worker_count.max(1);
+
+        JobManager {
+            repository,
+            handlers: std::sync::RwLock::new(HashMap::new()),
+            active: Mutex::new(HashMap::new()),
+            workers: Arc::new(Semaphore::new(worker_count)),
+            worker_count,
+            event_sink: std::sync::RwLock::new(Arc::new(NoopJobEventSink)),
+            shutting_down: AtomicBool::new(false),
+        }
+    }
+
+    /// Number of jobs that may run concurrently
+    pub fn worker_count(&self) -> usize {
+        self.worker_count
+    }
+
+    /// Register the handler for a job kind, replacing any previous one
+    pub fn register_handler(&self, handler: Arc<dyn JobHandler>) {
+        if let Ok(mut handlers) = self.handlers.write() {
+            handlers.insert(handler.kind(), handler);
+        }
+    }
+
+    /// Route job updates to a new event sink
+    pub fn set_event_sink(&self, sink: Arc<dyn JobEventSink>) {
+        if let Ok(mut event_sink) = self.event_sink.write() {
+            *event_sink = sink;
+        }
+    }
+
+    /// Persist and queue a new job
+    pub async fn submit(
+        self: &Arc<Self>,
+        kind: &str,
+        project_id: Option<String>,
+        params: serde_json::Value,
+    ) -> AppResult<JobDto> {
+        let handler = self.handler(kind).ok_or_else(|| {
+            AppError::validation_error("Unknown job kind", Some(kind.to_string()))
+        })?;
+
+        let record = JobRecord::new(kind, project_id, params, handler.is_idempotent());
+        self.repository.save(&record).await?;
+
+        tracing::info!("Queued job {} ({})", record.id, record.kind);
+
+        let dto = JobDto::from(&record);
+        self.enqueue(record, handler).await;
+        Ok(dto)
+    }
+
+    /// Get the current state of a job
+    pub async fn get_job(&self, job_id: &str) -> AppResult<JobDto> {
+        let id = Self::parse_job_id(job_id)?;
+
+        if let Some(active) = self.active.lock().await.get(&id) {
+            return Ok(JobDto::from(&active.record));
+        }
+
+        self.repository
+            .find_by_id(&id)
+            .await?
+            .map(|record| JobDto::from(&record))
+            .ok_or_else(|| AppError::not_found(format!("Job '{}'", job_id)))
+    }
+
+    /// List jobs, newest first
+    pub async fn list_jobs(&self, filter: &JobFilter) -> AppResult<Vec<JobDto>> {
+        let records = self.repository.list(filter).await?;
+        let active = self.active.lock().await;
+
+        Ok(records
+            .iter()
+            .map(|record| match active.get(&record.id) {
+                Some(active_job) => JobDto::from(&active_job.record),
+                None => JobDto::from(record),
+            })
+            .collect())
+    }
+
+    /// Request cancellation of a queued or running job
+    ///
+    /// Cancellation is asynchronous: the returned job reflects the state at
+    /// the time of the request, and a job update event follows once the
+    /// job has stopped.
+    pub async fn cancel_job(&self, job_id: &str) -> AppResult<JobDto> {
+        let id = Self::parse_job_id(job_id)?;
+
+        if let Some(active) = self.active.lock().await.get(&id) {
+            tracing::info!("Cancelling job {}", id);
+            active.token.cancel();
+            return Ok(JobDto::from(&active.record));
+        }
+
+        let mut record = self
+            .repository
+            .find_by_id(&id)
+            .await?
+            .ok_or_else(|| AppError::not_found(format!("Job '{}'", job_id)))?;
+
+        if !record.status.is_terminal() {
+            // Left over from a previous run and never resumed
+            record.mark_cancelled();
+            self.repository.save(&record).await?;
+            self.emit(&record);
+        }
+
+        Ok(JobDto::from(&record))
+    }
+
+    /// Re-queue idempotent jobs interrupted by the last shutdown
+    ///
+    /// Interrupted jobs that are not idempotent, or whose handler is no
+    /// longer registered, are marked as failed. Returns the number of
+    /// resumed jobs.
+    pub async fn resume_interrupted(self: &Arc<Self>) -> AppResult<usize> {
+        let mut resumed = 0;
+
+        for mut record in self.repository.find_unfinished().await? {
+            if self.active.lock().await.contains_key(&record.id) {
+                continue;
+            }
+
+            match self.handler(&record.kind) {
+                Some(handler) if record.idempotent => {
+                    tracing::info!("Resuming interrupted job {} ({})", record.id, record.kind);
+                    record.requeue();
+                    self.repository.save(&record).await?;
+                    self.enqueue(record, handler).await;
+                    resumed += 1;
+                }
+                _ => {
+                    tracing::warn!("Job {} was interrupted and cannot be resumed", record.id);
+                    record.mark_failed("Interrupted by application restart");
+                    self.repository.save(&record).await?;
+                }
+            }
+        }
+
+        Ok(resumed)
+    }
+
+    /// Stop all jobs without recording them as cancelled
+    ///
+    /// Their records stay queued or running so `resume_interrupted` can
+    /// pick them up on the next start.
+    pub async fn shutdown(&self) {
+        self.shutting_down.store(true, Ordering::SeqCst);
+        self.workers.close();
+
+        for active in self.active.lock().await.values() {
+            active.token.cancel();
+        }
+    }
+
+    /// Record progress reported by a running job
+    pub(crate) async fn update_progress(&self, job_id: &JobId, progress: JobProgress) {
+        let (record, persist) = {
+            let mut active = self.active.lock().await;
+            let Some(active_job) = active.get_mut(job_id) else {
+                return;
+            };
+
+            active_job.record.progress = progress;
+            let persist = active_job.last_persisted.elapsed() >= PROGRESS_PERSIST_INTERVAL;
+            if persist {
+                active_job.last_persisted = Instant::now();
+            }
+            (active_job.record.clone(), persist)
+        };
+
+        if persist {
+            self.save_logged(&record).await;
+        }
+        self.emit(&record);
+    }
+
+    async fn enqueue(self: &Arc<Self>, record: JobRecord, handler: Arc<dyn JobHandler>) {
+        let token = CancellationToken::new();
+        let job_id = record.id.clone();
+
+        self.emit(&record);
+        self.active.lock().await.insert(
+            job_id.clone(),
+            ActiveJob {
+                record,
+                token: token.clone(),
+                last_persisted: Instant::now(),
+            },
+        );
+
+        let manager = Arc::clone(self);
+        tokio::spawn(async move {
+            manager.execute(job_id, handler, token).await;
+        });
+    }
+
+    async fn execute(
+        self: Arc<Self>,
+        job_id: JobId,
+        handler: Arc<dyn JobHandler>,
+        token: CancellationToken,
+    ) {
+        let permit = tokio::select! {
+            permit = Arc::clone(&self.workers).acquire_owned() => permit.ok(),
+            _ = token.cancelled() => None,
+        };
+
+        let Some(_permit) = permit else {
+            self.finish(&job_id, JobStatus::Cancelled, None, None).await;
+            return;
+        };
+
+        let Some(record) = self.transition_to_running(&job_id).await else {
+            return;
+        };
+
+        let context = JobContext::new(
+            job_id.clone(),
+            record.project_id.clone(),
+            token.clone(),
+            Arc::clone(&self),
+        );
+
+        let outcome = tokio::select! {
+            result = handler.run(context, record.params.clone()) => Some(result),
+            _ = token.cancelled() => None,
+        };
+
+        match outcome {
+            Some(Ok(result)) => {
+                self.finish(&job_id, JobStatus::Succeeded, Some(result), None)
+                    .await;
+            }
+            Some(Err(_)) if token.is_cancelled() => {
+                self.finish(&job_id, JobStatus::Cancelled, None, None).await;
+            }
+            Some(Err(error)) => {
+                tracing::error!("Job {} failed: {}", job_id, error.user_message());
+                self.finish(&job_id, JobStatus::Failed, None, Some(error.user_message()))
+                    .await;
+            }
+            None => {
+                self.finish(&job_id, JobStatus::Cancelled, None, None).await;
+            }
+        }
+    }
+
+    async fn transition_to_running(&self, job_id: &JobId) -> Option<JobRecord> {
+        let record = {
+            let mut active = self.active.lock().await;
+            let active_job = active.get_mut(job_id)?;
+            active_job.record.mark_running();
+            active_job.last_persisted = Instant::now();
+            active_job.record.clone()
+        };
+
+        tracing::debug!("Starting job {} ({})", record.id, record.kind);
+        self.save_logged(&record).await;
+        self.emit(&record);
+        Some(record)
+    }
+
+    async fn finish(
+        &self,
+        job_id: &JobId,
+        status: JobStatus,
+        result: Option<serde_json::Value>,
+        error: Option<String>,
+    ) {
+        let Some(ActiveJob { mut record, .. }) = self.active.lock().await.remove(job_id) else {
+            return;
+        };
+
+        if status == JobStatus::Cancelled && self.shutting_down.load(Ordering::SeqCst) {
+            // Keep the persisted state so the job is handled on next start
+            return;
+        }
+
+        match status {
+            JobStatus::Succeeded => record.mark_succeeded(result.unwrap_or_default()),
+            JobStatus::Failed => record.mark_failed(error.unwrap_or_default()),
+            _ => record.mark_cancelled(),
+        }
+
+        tracing::info!("Job {} finished: {}", record.id, record.status.as_str());
+        self.save_logged(&record).await;
+        self.emit(&record);
+    }
+
+    async fn save_logged(&self, record: &JobRecord) {
+        if let Err(error) = self.repository.save(record).await {
+            tracing::warn!("Failed to persist job {}: {}", record.id, error);
+        }
+    }
+
+    fn emit(&self, record: &JobRecord) {
+        let sink = match self.event_sink.read() {
+            Ok(sink) => Arc::clone(&sink),
+            Err(_) => return,
+        };
+        sink.job_updated(&JobDto::from(record));
+    }
+
+    fn handler(&self, kind: &str) -> Option<Arc<dyn JobHandler>> {
+        self.handlers
+            .read()
+            .ok()
+            .and_then(|handlers| handlers.get(kind).cloned())
+    }
+
+    fn parse_job_id(job_id: &str) -> AppResult<JobId> {
+        JobId::from_string(job_id.to_string())
+            .map_err(|e| AppError::validation_error("Invalid job ID", Some(e)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::repositories::SqliteJobRepository;
+    use crate::infrastructure::DatabaseConnection;
+    use async_trait::async_trait;
+    use tempfile::TempDir;
+
+    /// Counts to `params.steps`, reporting progress after each step
+    struct CountingHandler;
+
+    #[async_trait]
+    impl JobHandler for CountingHandler {
+        fn kind(&self) -> &'static str {
+            "count"
+        }
+
+        fn is_idempotent(&self) -> bool {
+            true
+        }
+
+        async fn run(
+            &self,
+            context: JobContext,
+            params: serde_json::Value,
+        ) -> AppResult<serde_json::Value> {
+            let steps = params["steps"].as_u64().unwrap_or(1);
+            let delay = params["delay_ms"].as_u64().unwrap_or(0);
+
+            for step in 1..=steps {
+                context.check_cancelled()?;
+                tokio::time::sleep(Duration::from_millis(delay)).await;
+                context.report_progress(step, Some(steps), None).await;
+            }
+
+            Ok(serde_json::json!({ "counted": steps }))
+        }
+    }
+
+    struct FailingHandler;
+
+    #[async_trait]
+    impl JobHandler for FailingHandler {
+        fn kind(&self) -> &'static str {
+            "fail"
+        }
+
+        async fn run(
+            &self,
+            _context: JobContext,
+            _params: serde_json::Value,
+        ) -> AppResult<serde_json::Value> {
+            Err(AppError::filesystem_error("Folder disappeared"))
+        }
+    }
+
+    async fn create_test_manager() -> (Arc<JobManager>, Arc<SqliteJobRepository>, TempDir) {
+        let (database, temp_dir) = DatabaseConnection::new_temp().await.unwrap();
+        let repository = Arc::new(SqliteJobRepository::new(database.pool()));
+        let manager = Arc::new(JobManager::new(repository.clone(), 1));
+        manager.register_handler(Arc::new(CountingHandler));
+        manager.register_handler(Arc::new(FailingHandler));
+        (manager, repository, temp_dir)
+    }
+
+    async fn wait_until_finished(manager: &JobManager, job_id: &str) -> JobDto {
+        for _ in 0..200 {
+            let job = manager.get_job(job_id).await.unwrap();
+            if job.is_finished() {
+                return job;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        panic!("Job {} did not finish in time", job_id);
+    }
+
+    #[tokio::test]
+    async fn test_job_runs_to_completion() {
+        let (manager, _repository, _temp_dir) = create_test_manager().await;
+
+        let job = manager
+            .submit("count", None, serde_json::json!({ "steps": 3 }))
+            .await
+            .unwrap();
+        assert_eq!(job.status, "queued");
+
+        let finished = wait_until_finished(&manager, &job.id).await;
+        assert_eq!(finished.status, "succeeded");
+        assert_eq!(finished.progress_current, 3);
+        assert_eq!(finished.result, Some(serde_json::json!({ "counted": 3 })));
+    }
+
+    #[tokio::test]
+    async fn test_failed_job_records_error() {
+        let (manager, _repository, _temp_dir) = create_test_manager().await;
+
+        let job = manager
+            .submit("fail", None, serde_json::Value::Null)
+            .await
+            .unwrap();
+
+        let finished = wait_until_finished(&manager, &job.id).await;
+        assert_eq!(finished.status, "failed");
+        assert_eq!(finished.error.as_deref(), Some("Folder disappeared"));
+    }
+
+    #[tokio::test]
+    async fn test_unknown_kind_is_rejected() {
+        let (manager, _repository, _temp_dir) = create_test_manager().await;
+
+        let result = manager
+            .submit("unknown", None, serde_json::Value::Null)
+            .await;
+        assert_eq!(result.unwrap_err().code, "VALIDATION_ERROR");
+    }
+
+    #[tokio::test]
+    async fn test_cancel_running_and_queued_jobs() {
+        let (manager, _repository, _temp_dir) = create_test_manager().await;
+        let slow = serde_json::json!({ "steps": 100, "delay_ms": 20 });
+
+        // With a single worker the second job waits in the queue
+        let running = manager.submit("count", None, slow.clone()).await.unwrap();
+        let queued = manager.submit("count", None, slow).await.unwrap();
+
+        manager.cancel_job(&queued.id).await.unwrap();
+        manager.cancel_job(&running.id).await.unwrap();
+
+        assert_eq!(
+            wait_until_finished(&manager, &running.id).await.status,
+            "cancelled"
+        );
+        assert_eq!(
+            wait_until_finished(&manager, &queued.id).await.status,
+            "cancelled"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_resume_interrupted_jobs() {
+        let (manager, repository, _temp_dir) = create_test_manager().await;
+
+        // Simulate records left behind by a previous process
+        let mut idempotent = JobRecord::new("count", None, serde_json::json!({ "steps": 2 }), true);
+        idempotent.mark_running();
+        repository.save(&idempotent).await.unwrap();
+
+        let mut one_shot = JobRecord::new("fail", None, serde_json::Value::Null, false);
+        one_shot.mark_running();
+        repository.save(&one_shot).await.unwrap();
+
+        let resumed = manager.resume_interrupted().await.unwrap();
+        assert_eq!(resumed, 1);
+
+        let finished = wait_until_finished(&manager, idempotent.id.as_str()).await;
+        assert_eq!(finished.status, "succeeded");
+        assert_eq!(finished.attempts, 2);
+
+        let failed = manager.get_job(one_shot.id.as_str()).await.unwrap();
+        assert_eq!(failed.status, "failed");
+    }
+
+    #[tokio::test]
+    async fn test_list_jobs_filters_by_status() {
+        let (manager, _repository, _temp_dir) = create_test_manager().await;
+
+        let ok = manager
+            .submit("count", None, serde_json::json!({ "steps": 1 }))
+            .await
+            .unwrap();
+        let failed = manager
+            .submit("fail", None, serde_json::Value::Null)
+            .await
+            .unwrap();
+        wait_until_finished(&manager, &ok.id).await;
+        wait_until_finished(&manager, &failed.id).await;
+
+        let all = manager.list_jobs(&JobFilter::default()).await.unwrap();
+        assert_eq!(all.len(), 2);
+
+        let only_failed = manager
+            .list_jobs(&JobFilter {
+                status: Some(JobStatus::Failed),
+                ..JobFilter::default()
+            })
+            .await
+            .unwrap();
+        assert_eq!(only_failed.len(), 1);
+        assert_eq!(only_failed[0].id, failed.id);
+    }
+}
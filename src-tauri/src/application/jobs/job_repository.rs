@@ -0,0 +1,31 @@
+use async_trait::async_trait;
+
+use crate::application::jobs::job::{JobId, JobRecord, JobStatus};
+use crate::domain::workspace::repositories::RepositoryError;
+
+/// Filter for listing jobs
+#[derive(Debug, Clone, Default)]
+pub struct JobFilter {
+    /// Only jobs in this status
+    pub status: Option<JobStatus>,
+    /// Only jobs for this project
+    pub project_id: Option<String>,
+    /// Maximum number of jobs to return (newest first)
+    pub limit: Option<usize>,
+}
+
+/// Repository for persisting background job records
+#[async_trait]
+pub trait JobRepository: Send + Sync {
+    /// Insert or update a job record
+    async fn save(&self, job: &JobRecord) -> Result<(), RepositoryError>;
+
+    /// Find a job by its ID
+    async fn find_by_id(&self, id: &JobId) -> Result<Option<JobRecord>, RepositoryError>;
+
+    /// List jobs matching a filter, newest first
+    async fn list(&self, filter: &JobFilter) -> Result<Vec<JobRecord>, RepositoryError>;
+
+    /// Find jobs that were queued or running, oldest first
+    async fn find_unfinished(&self) -> Result<Vec<JobRecord>, RepositoryError>;
+}
@@ -0,0 +1,11 @@
+pub mod job;
+pub mod job_events;
+pub mod job_handler;
+pub mod job_manager;
+pub mod job_repository;
+
+pub use job::{JobId, JobProgress, JobRecord, JobStatus};
+pub use job_events::{JobEventSink, NoopJobEventSink, TauriJobEventSink, JOB_UPDATED_EVENT};
+pub use job_handler::{JobContext, JobHandler};
+pub use job_manager::{JobManager, DEFAULT_WORKER_COUNT};
+pub use job_repository::{JobFilter, JobRepository};
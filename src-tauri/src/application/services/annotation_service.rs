@@ -0,0 +1,442 @@
+use chrono::Utc;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use crate::application::dtos::{
+    AnnotationDto, AnnotationExportDto, AnnotationReattachmentDto, ExportedAnnotationDto,
+};
+use crate::application::services::project_service::parse_project_id;
+use crate::application::services::review_service::resolve_reviewer;
+use crate::domain::annotation::{
+    AnchorStatus, Annotation, AnnotationAnchor, AnnotationId, AnnotationRepository,
+};
+use crate::domain::category::CategoryColor;
+use crate::domain::det::{DetStore, PmNode};
+use crate::domain::document::{DerivativeFamily, Document, DocumentId, DocumentRepository};
+use crate::domain::review::ReviewRepository;
+use crate::infrastructure::{AppError, AppResult};
+
+/// Application service for highlights and comments on documents
+///
+/// Annotations are anchored either to a page of the original or to a range
+/// of the document's `extracted.det`. The highlighted text is kept with the
+/// anchor, so when the extraction is corrected or redone, range anchors are
+/// moved to where that text is now, or marked orphaned if it is gone.
+pub struct AnnotationService {
+    document_repository: Arc<dyn DocumentRepository>,
+    annotation_repository: Arc<dyn AnnotationRepository>,
+    det_store: Arc<dyn DetStore>,
+    derivatives_root: PathBuf,
+    review_repository: Option<Arc<dyn ReviewRepository>>,
+}
+
+impl AnnotationService {
+    /// Create a new AnnotationService reading extractions below
+    /// `derivatives_root`
+    pub fn new(
+        document_repository: Arc<dyn DocumentRepository>,
+        annotation_repository: Arc<dyn AnnotationRepository>,
+        det_store: Arc<dyn DetStore>,
+        derivatives_root: PathBuf,
+    ) -> Self {
+        AnnotationService {
+            document_repository,
+            annotation_repository,
+            det_store,
+            derivatives_root,
+            review_repository: None,
+        }
+    }
+
+    /// Record the active reviewer as the author of annotations made
+    /// without one
+    pub fn with_review_repository(mut self, review_repository: Arc<dyn ReviewRepository>) -> Self {
+        self.review_repository = Some(review_repository);
+        self
+    }
+
+    /// Highlight a passage of a document, optionally with a comment
+    ///
+    /// For range anchors the quote is read from the extraction; for page
+    /// anchors it is taken as given. Without an author, the active reviewer
+    /// is recorded.
+    pub async fn create_annotation(
+        &self,
+        document_id: &str,
+        anchor: AnnotationAnchor,
+        quote: Option<String>,
+        color: Option<String>,
+        body: Option<String>,
+        author: Option<String>,
+    ) -> AppResult<AnnotationDto> {
+        let document = self.load_document(document_id).await?;
+        let author = resolve_reviewer(self.review_repository.as_ref(), author).await?;
+
+        let quote = match anchor {
+            AnnotationAnchor::Range { from, to } => {
+                let content = self.extracted_content(&document).await?;
+                content
+                    .text_between(from, to)
+                    .filter(|text| !text.trim().is_empty())
+                    .ok_or_else(|| {
+                        AppError::validation_error(
+                            "The range does not cover any extracted text",
+                            Some(format!("{}..{}", from, to)),
+                        )
+                    })?
+            }
+            AnnotationAnchor::Page { .. } => quote.unwrap_or_default(),
+        };
+
+        let mut annotation = Annotation::new(
+            document.project_id().clone(),
+            document.id().clone(),
+            anchor,
+            quote,
+            author,
+        )?;
+        if let Some(body) = body {
+            annotation.set_body(body)?;
+        }
+        annotation.set_color(parse_color(color)?);
+        self.annotation_repository.save(&annotation).await?;
+
+        Ok(AnnotationDto::from(&annotation))
+    }
+
+    /// Change the comment or colour of an annotation
+    ///
+    /// Fields left out are unchanged; an empty colour removes it.
+    pub async fn update_annotation(
+        &self,
+        annotation_id: &str,
+        body: Option<String>,
+        color: Option<String>,
+    ) -> AppResult<AnnotationDto> {
+        let mut annotation = self.load(annotation_id).await?;
+
+        if let Some(body) = body {
+            annotation.set_body(body)?;
+        }
+        if color.is_some() {
+            annotation.set_color(parse_color(color)?);
+        }
+        self.annotation_repository.save(&annotation).await?;
+
+        Ok(AnnotationDto::from(&annotation))
+    }
+
+    pub async fn delete_annotation(&self, annotation_id: &str) -> AppResult<()> {
+        let annotation = self.load(annotation_id).await?;
+        self.annotation_repository.delete(annotation.id()).await?;
+        Ok(())
+    }
+
+    /// The annotations of a document, oldest first
+    pub async fn list_annotations(&self, document_id: &str) -> AppResult<Vec<AnnotationDto>> {
+        let document = self.load_document(document_id).await?;
+
+        Ok(self
+            .annotation_repository
+            .list_by_document(document.id())
+            .await?
+            .iter()
+            .map(AnnotationDto::from)
+            .collect())
+    }
+
+    /// Move the range anchors of a document's annotations to where their
+    /// text is in the current extraction
+    pub async fn reattach_annotations(
+        &self,
+        document_id: &str,
+    ) -> AppResult<AnnotationReattachmentDto> {
+        let document = self.load_document(document_id).await?;
+        let content = self.extracted_content(&document).await?;
+        self.reattach_to(&document, &content).await
+    }
+
+    /// Re-attach a document's annotations to a new extraction
+    pub(crate) async fn reattach_to(
+        &self,
+        document: &Document,
+        content: &PmNode,
+    ) -> AppResult<AnnotationReattachmentDto> {
+        let mut result = AnnotationReattachmentDto {
+            document_id: document.id().value().to_string(),
+            ..AnnotationReattachmentDto::default()
+        };
+
+        for mut annotation in self
+            .annotation_repository
+            .list_by_document(document.id())
+            .await?
+        {
+            let before = annotation.clone();
+            match annotation.reattach(content) {
+                AnchorStatus::Attached => result.unchanged += 1,
+                AnchorStatus::Reattached => result.reattached += 1,
+                AnchorStatus::Orphaned => result.orphaned += 1,
+            }
+            if annotation != before {
+                self.annotation_repository.save(&annotation).await?;
+            }
+        }
+
+        Ok(result)
+    }
+
+    /// All annotations of a project with the paths of their documents
+    pub async fn export_annotations(&self, project_id: &str) -> AppResult<AnnotationExportDto> {
+        let id = parse_project_id(project_id)?;
+
+        let paths: HashMap<DocumentId, String> = self
+            .document_repository
+            .list_by_project(&id)
+            .await?
+            .iter()
+            .map(|document| (document.id().clone(), document.path().to_string()))
+            .collect();
+
+        let mut annotations: Vec<ExportedAnnotationDto> = self
+            .annotation_repository
+            .list_by_project(&id)
+            .await?
+            .iter()
+            .map(|annotation| ExportedAnnotationDto {
+                document_path: paths.get(annotation.document_id()).cloned(),
+                annotation: AnnotationDto::from(annotation),
+            })
+            .collect();
+        // The sort is stable, so annotations stay oldest first per document
+        annotations.sort_by(|a, b| a.document_path.cmp(&b.document_path));
+
+        Ok(AnnotationExportDto {
+            project_id: id.value().to_string(),
+            exported_at: Utc::now().to_rfc3339(),
+            annotations,
+        })
+    }
+
+    /// Render an annotation export
+    ///
+    /// Supported formats are "csv" (one row per annotation) and "json" (the
+    /// full `AnnotationExportDto`).
+    pub fn render_export(export: &AnnotationExportDto, format: &str) -> AppResult<String> {
+        match format.to_ascii_lowercase().as_str() {
+            "csv" => export
+                .to_csv()
+                .map_err(|e| AppError::internal_error(format!("Failed to write CSV: {}", e))),
+            "json" => serde_json::to_string_pretty(export)
+                .map_err(|e| AppError::internal_error(format!("Failed to write JSON: {}", e))),
+            other => Err(AppError::validation_error(
+                "Unsupported export format",
+                Some(format!("'{}' is not one of csv, json", other)),
+            )),
+        }
+    }
+
+    async fn load(&self, annotation_id: &str) -> AppResult<Annotation> {
+        let id = AnnotationId::from_string(annotation_id.to_string())?;
+        self.annotation_repository
+            .find_by_id(&id)
+            .await?
+            .ok_or_else(|| AppError::not_found(format!("Annotation with ID '{}'", id)))
+    }
+
+    async fn load_document(&self, document_id: &str) -> AppResult<Document> {
+        let id = DocumentId::from_string(document_id.to_string()).map_err(|e| {
+            AppError::validation_error("Invalid document ID format", Some(e.to_string()))
+        })?;
+
+        self.document_repository
+            .find_by_id(&id)
+            .await?
+            .ok_or_else(|| AppError::not_found(format!("Document with ID '{}'", document_id)))
+    }
+
+    async fn extracted_content(&self, document: &Document) -> AppResult<PmNode> {
+        let path =
+            DerivativeFamily::new(&self.derivatives_root, document.project_id(), document.id())
+                .extracted_path();
+        if !path.exists() {
+            return Err(AppError::validation_error(
+                "The document has not been extracted yet",
+                Some(document.path().to_string()),
+            ));
+        }
+
+        Ok(self.det_store.load(&path).await?.content().clone())
+    }
+}
+
+fn parse_color(color: Option<String>) -> AppResult<Option<CategoryColor>> {
+    Ok(color
+        .filter(|color| !color.trim().is_empty())
+        .map(|color| CategoryColor::parse(&color))
+        .transpose()?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::det::{DetDocument, DetKind, ProcessingMetadata};
+    use crate::domain::project::ProjectId;
+    use crate::domain::workspace::value_objects::{ContentHash, HashAlgorithm};
+    use crate::infrastructure::{
+        DatabaseConnection, FileDetStore, SqliteAnnotationRepository, SqliteDocumentRepository,
+    };
+    use tempfile::TempDir;
+
+    async fn write_extraction(root: &TempDir, document: &Document, paragraphs: &[&str]) {
+        let det = DetDocument::new(
+            DetKind::Extracted,
+            PmNode::doc(
+                paragraphs
+                    .iter()
+                    .map(|text| PmNode::paragraph(PmNode::text_nodes(text, Vec::new())))
+                    .collect(),
+            ),
+            ProcessingMetadata::completed("test"),
+        );
+        let family = DerivativeFamily::new(root.path(), document.project_id(), document.id());
+        FileDetStore::new()
+            .save(&family.extracted_path(), &det)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_annotations_follow_corrected_extraction_and_export() {
+        let (database, _db_dir) = DatabaseConnection::new_temp().await.unwrap();
+        let root = TempDir::new().unwrap();
+        let documents = Arc::new(SqliteDocumentRepository::new(database.pool()));
+        let service = AnnotationService::new(
+            documents.clone(),
+            Arc::new(SqliteAnnotationRepository::new(database.pool())),
+            Arc::new(FileDetStore::new()),
+            root.path().to_path_buf(),
+        );
+
+        let project_id = ProjectId::new();
+        let document = Document::new(
+            project_id.clone(),
+            "/corpus/scan.pdf".to_string(),
+            ContentHash::of_bytes(HashAlgorithm::Blake3, b"scan"),
+            4,
+            0,
+        );
+        documents.save(&document).await.unwrap();
+        let document_id = document.id().value();
+        write_extraction(&root, &document, &["Tbe invoice total is 420 EUR."]).await;
+
+        // "invoice" sits at positions 5..12
+        let highlight = service
+            .create_annotation(
+                document_id,
+                AnnotationAnchor::Range { from: 5, to: 12 },
+                None,
+                Some("#FDE047".to_string()),
+                Some("Matches the ledger".to_string()),
+                Some("Ana".to_string()),
+            )
+            .await
+            .unwrap();
+        assert_eq!(highlight.quote, "invoice");
+        assert_eq!(highlight.color.as_deref(), Some("#fde047"));
+        let total = service
+            .create_annotation(
+                document_id,
+                AnnotationAnchor::Range { from: 22, to: 29 },
+                None,
+                None,
+                None,
+                Some("Ben".to_string()),
+            )
+            .await
+            .unwrap();
+        assert_eq!(total.quote, "420 EUR");
+        service
+            .create_annotation(
+                document_id,
+                AnnotationAnchor::Page {
+                    page: 1,
+                    start: 0,
+                    end: 3,
+                },
+                Some("Tbe".to_string()),
+                None,
+                Some("OCR error".to_string()),
+                Some("Ana".to_string()),
+            )
+            .await
+            .unwrap();
+        assert!(service
+            .create_annotation(
+                document_id,
+                AnnotationAnchor::Range { from: 5, to: 500 },
+                None,
+                None,
+                None,
+                Some("Ana".to_string()),
+            )
+            .await
+            .is_err());
+
+        // The OCR text is corrected: the first highlight moves, the amount is gone
+        write_extraction(
+            &root,
+            &document,
+            &["Scanned page", "The invoice total is 402 EUR."],
+        )
+        .await;
+        let result = service.reattach_annotations(document_id).await.unwrap();
+        assert_eq!(
+            (result.unchanged, result.reattached, result.orphaned),
+            (1, 1, 1)
+        );
+
+        let annotations = service.list_annotations(document_id).await.unwrap();
+        assert_eq!(annotations[0].status, AnchorStatus::Reattached);
+        assert_eq!(
+            annotations[0].anchor,
+            AnnotationAnchor::Range { from: 19, to: 26 }
+        );
+        assert_eq!(annotations[1].status, AnchorStatus::Orphaned);
+        assert_eq!(annotations[2].status, AnchorStatus::Attached);
+
+        let updated = service
+            .update_annotation(
+                &total.id,
+                Some("Amount changed".to_string()),
+                Some(String::new()),
+            )
+            .await
+            .unwrap();
+        assert_eq!(updated.body, "Amount changed");
+        assert_eq!(updated.color, None);
+
+        let export = service
+            .export_annotations(project_id.value())
+            .await
+            .unwrap();
+        assert_eq!(export.annotations.len(), 3);
+        assert_eq!(
+            export.annotations[0].document_path.as_deref(),
+            Some("/corpus/scan.pdf")
+        );
+        let csv = AnnotationService::render_export(&export, "csv").unwrap();
+        assert_eq!(csv.lines().count(), 4);
+        assert!(csv.contains("Matches the ledger"));
+        let json = AnnotationService::render_export(&export, "json").unwrap();
+        assert!(json.contains("\"documentPath\""));
+        assert!(AnnotationService::render_export(&export, "xml").is_err());
+
+        service.delete_annotation(&total.id).await.unwrap();
+        assert_eq!(
+            service.list_annotations(document_id).await.unwrap().len(),
+            2
+        );
+    }
+}
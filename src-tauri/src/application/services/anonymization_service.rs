@@ -0,0 +1,340 @@
+use std::path::PathBuf;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+use crate::application::dtos::{AnonymizationDto, AnonymizationRequest, ReplacementDto};
+use crate::application::services::{DerivationService, TOOL_VERSION};
+use crate::domain::anonymization::{Anonymizer, PseudonymMapRepository};
+use crate::domain::det::{DetDocument, DetKind, DetStore, ProcessingMetadata};
+use crate::domain::document::{
+    DerivationStep, DerivativeFamily, Document, DocumentId, DocumentRepository, FamilyMember,
+};
+use crate::infrastructure::{AppError, AppResult};
+
+/// Method recorded in the metadata of anonymized documents
+const ANONYMIZATION_METHOD: &str = "rule-anonymizer-v1";
+
+/// Application service producing the `anonymized.det` derivative
+///
+/// Anonymizes a document's `extracted.det` with regex, dictionary and
+/// built-in detector rules. Pseudonyms come from one map per project, so a
+/// person or account keeps the same pseudonym across documents; the map is
+/// only ever written to a private file in the project folder. Source
+/// references keep the document identifier and hash but not the path. A
+/// preview lists the replacements without writing anything.
+pub struct AnonymizationService {
+    document_repository: Arc<dyn DocumentRepository>,
+    det_store: Arc<dyn DetStore>,
+    pseudonym_repository: Arc<dyn PseudonymMapRepository>,
+    derivatives_root: PathBuf,
+    derivation_service: Option<Arc<DerivationService>>,
+    /// Serializes read-modify-write cycles of the pseudonym maps
+    map_lock: Mutex<()>,
+}
+
+impl AnonymizationService {
+    /// Create a new AnonymizationService for families below `derivatives_root`
+    pub fn new(
+        document_repository: Arc<dyn DocumentRepository>,
+        det_store: Arc<dyn DetStore>,
+        pseudonym_repository: Arc<dyn PseudonymMapRepository>,
+        derivatives_root: PathBuf,
+    ) -> Self {
+        AnonymizationService {
+            document_repository,
+            det_store,
+            pseudonym_repository,
+            derivatives_root,
+            derivation_service: None,
+            map_lock: Mutex::new(()),
+        }
+    }
+
+    /// Record each anonymization as a step in the document's derivation graph
+    pub fn with_derivation_service(mut self, derivation_service: Arc<DerivationService>) -> Self {
+        self.derivation_service = Some(derivation_service);
+        self
+    }
+
+    /// List the replacements an anonymization would make, writing nothing
+    pub async fn preview_anonymization(
+        &self,
+        document_id: &str,
+        request: &AnonymizationRequest,
+    ) -> AppResult<AnonymizationDto> {
+        self.run(document_id, request, false).await
+    }
+
+    /// Anonymize a document into `anonymized.det`, replacing any earlier one
+    pub async fn anonymize_document(
+        &self,
+        document_id: &str,
+        request: &AnonymizationRequest,
+    ) -> AppResult<AnonymizationDto> {
+        self.run(document_id, request, true).await
+    }
+
+    async fn run(
+        &self,
+        document_id: &str,
+        request: &AnonymizationRequest,
+        commit: bool,
+    ) -> AppResult<AnonymizationDto> {
+        let document = self.load_document(document_id).await?;
+        let rules = request.to_rules();
+        let anonymizer = Anonymizer::new(&rules)?.with_exclusions(&request.excluded);
+
+        let family =
+            DerivativeFamily::new(&self.derivatives_root, document.project_id(), document.id());
+        let extracted_path = family.extracted_path();
+        if !extracted_path.exists() {
+            return Err(AppError::validation_error(
+                "The document has not been extracted yet",
+                Some(extracted_path.to_string_lossy().to_string()),
+            ));
+        }
+        let extracted = self.det_store.load(&extracted_path).await?;
+
+        let _guard = self.map_lock.lock().await;
+        let mut map = self
+            .pseudonym_repository
+            .load(document.project_id())
+            .await?;
+        let known = map.len();
+        let result = anonymizer.anonymize(extracted.content(), extracted.title(), &mut map);
+
+        let mut dto = AnonymizationDto {
+            document_id: document.id().value().to_string(),
+            committed: commit,
+            det_path: None,
+            replacements: result
+                .replacements
+                .iter()
+                .map(ReplacementDto::from)
+                .collect(),
+            new_pseudonyms: map.len() - known,
+        };
+        if !commit {
+            return Ok(dto);
+        }
+
+        let labels: Vec<&str> = rules.iter().map(|rule| rule.label()).collect();
+        let processing = ProcessingMetadata::completed(ANONYMIZATION_METHOD)
+            .with_page_count(extracted.processing().page_count)
+            .with_property("rules", labels.clone())
+            .with_property("replacements", result.replacements.len());
+        let mut anonymized = DetDocument::new(DetKind::Anonymized, result.content, processing);
+        anonymized.set_title(result.title);
+        for source in extracted.sources() {
+            anonymized = anonymized.with_source(source.without_path());
+        }
+
+        // The map goes first: unused pseudonyms are harmless, a document
+        // using pseudonyms the map forgot is not
+        self.pseudonym_repository
+            .save(document.project_id(), &map)
+            .await?;
+        let det_path = family.anonymized_path();
+        self.det_store.save(&det_path, &anonymized).await?;
+
+        if let Some(derivation_service) = &self.derivation_service {
+            let step = DerivationStep::new(
+                FamilyMember::extracted(),
+                FamilyMember::derivative(DerivativeFamily::ANONYMIZED_FILE)?,
+                "anonymization",
+                serde_json::json!({
+                    "rules": labels,
+                    "excluded": request.excluded.len(),
+                }),
+                TOOL_VERSION,
+            )?;
+            derivation_service.record_step(&document, step).await?;
+        }
+
+        tracing::debug!(
+            "Anonymized {} with {} replacement(s)",
+            document.path(),
+            dto.replacements.len()
+        );
+
+        dto.det_path = Some(det_path.to_string_lossy().to_string());
+        Ok(dto)
+    }
+
+    async fn load_document(&self, document_id: &str) -> AppResult<Document> {
+        let id = DocumentId::from_string(document_id.to_string()).map_err(|e| {
+            AppError::validation_error("Invalid document ID format", Some(e.to_string()))
+        })?;
+
+        self.document_repository
+            .find_by_id(&id)
+            .await?
+            .ok_or_else(|| AppError::not_found(format!("Document with ID '{}'", document_id)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::anonymization::AnonymizationRule;
+    use crate::domain::det::{PmNode, SourceReference};
+    use crate::domain::project::repositories::{MockProjectRepository, ProjectRepository};
+    use crate::domain::project::{Project, ProjectId};
+    use crate::domain::workspace::value_objects::{ContentHash, HashAlgorithm};
+    use crate::infrastructure::{
+        DatabaseConnection, FileDetStore, FilePseudonymMapRepository, SqliteDerivationRepository,
+        SqliteDocumentRepository,
+    };
+    use tempfile::TempDir;
+
+    async fn add_extracted(
+        documents: &SqliteDocumentRepository,
+        root: &TempDir,
+        project_id: &ProjectId,
+        name: &str,
+        text: &str,
+    ) -> Document {
+        let document = Document::new(
+            project_id.clone(),
+            format!("/corpus/{}", name),
+            ContentHash::new(HashAlgorithm::default(), "00".repeat(32)).unwrap(),
+            0,
+            0,
+        );
+        documents.save(&document).await.unwrap();
+
+        let det = DetDocument::new(
+            DetKind::Extracted,
+            PmNode::doc(vec![PmNode::paragraph(PmNode::text_nodes(
+                text,
+                Vec::new(),
+            ))]),
+            ProcessingMetadata::completed("test"),
+        )
+        .with_source(
+            SourceReference::new(document.path())
+                .with_document_id(document.id().value())
+                .with_content_hash(document.content_hash().to_string()),
+        );
+        let family = DerivativeFamily::new(root.path(), project_id, document.id());
+        FileDetStore::new()
+            .save(&family.extracted_path(), &det)
+            .await
+            .unwrap();
+        document
+    }
+
+    #[tokio::test]
+    async fn test_preview_then_commit_with_project_wide_pseudonyms() {
+        let (database, _db_dir) = DatabaseConnection::new_temp().await.unwrap();
+        let documents = Arc::new(SqliteDocumentRepository::new(database.pool()));
+        let root = TempDir::new().unwrap();
+        let derivations = Arc::new(DerivationService::new(
+            documents.clone(),
+            Arc::new(SqliteDerivationRepository::new(database.pool())),
+            root.path().to_path_buf(),
+        ));
+        let source = TempDir::new().unwrap();
+        let projects = Arc::new(MockProjectRepository::new());
+        let project = Project::new(
+            "Anonymization".to_string(),
+            source.path().to_string_lossy().to_string(),
+            None,
+        )
+        .unwrap();
+        projects.create(&project).await.unwrap();
+        let service = AnonymizationService::new(
+            documents.clone(),
+            Arc::new(FileDetStore::new()),
+            Arc::new(FilePseudonymMapRepository::new(projects)),
+            root.path().to_path_buf(),
+        )
+        .with_derivation_service(derivations.clone());
+
+        let project_id = project.id().clone();
+        let first = add_extracted(
+            &documents,
+            &root,
+            &project_id,
+            "a.txt",
+            "Jane Doe wrote to bob@example.com.",
+        )
+        .await;
+        let second = add_extracted(
+            &documents,
+            &root,
+            &project_id,
+            "b.txt",
+            "Bob replied to Jane Doe.",
+        )
+        .await;
+
+        let request = AnonymizationRequest {
+            rules: vec![AnonymizationRule::dictionary(
+                "person",
+                vec!["Jane Doe".to_string(), "Bob".to_string()],
+            )],
+            detectors: None,
+            excluded: Vec::new(),
+        };
+
+        let preview = service
+            .preview_anonymization(first.id().value(), &request)
+            .await
+            .unwrap();
+        assert!(!preview.committed);
+        assert_eq!(preview.replacements.len(), 2);
+        assert_eq!(preview.new_pseudonyms, 2);
+        let family = DerivativeFamily::new(root.path(), &project_id, first.id());
+        assert!(!family.anonymized_path().exists());
+
+        let result = service
+            .anonymize_document(first.id().value(), &request)
+            .await
+            .unwrap();
+        assert!(result.committed);
+        let saved = FileDetStore::new()
+            .load(&family.anonymized_path())
+            .await
+            .unwrap();
+        assert_eq!(saved.kind(), DetKind::Anonymized);
+        assert_eq!(saved.plain_text(), "[PERSON-1] wrote to [EMAIL-1].");
+        let sources = saved.sources();
+        assert_eq!(sources[0].path, "");
+        assert_eq!(sources[0].document_id.as_deref(), Some(first.id().value()));
+        assert!(sources[0].content_hash.is_some());
+        assert!(FilePseudonymMapRepository::path_for(&project).is_file());
+        assert!(!root
+            .path()
+            .join(project_id.value())
+            .join("pseudonyms.json")
+            .exists());
+
+        let result = service
+            .anonymize_document(second.id().value(), &request)
+            .await
+            .unwrap();
+        assert_eq!(result.new_pseudonyms, 1);
+        let pseudonyms: Vec<&str> = result
+            .replacements
+            .iter()
+            .map(|r| r.pseudonym.as_str())
+            .collect();
+        assert_eq!(pseudonyms, vec!["[PERSON-2]", "[PERSON-1]"]);
+
+        let graph = derivations
+            .get_derivation_graph(first.id().value())
+            .await
+            .unwrap();
+        assert_eq!(graph.edges[0].from, "extracted.det");
+        assert_eq!(graph.edges[0].to, "anonymized.det");
+
+        let missing = add_extracted(&documents, &root, &project_id, "c.txt", "x").await;
+        let family = DerivativeFamily::new(root.path(), &project_id, missing.id());
+        std::fs::remove_file(family.extracted_path()).unwrap();
+        assert!(service
+            .anonymize_document(missing.id().value(), &request)
+            .await
+            .is_err());
+    }
+}
@@ -0,0 +1,407 @@
+use chrono::{DateTime, Utc};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::fs::File;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::SystemTime;
+use tokio::sync::RwLock;
+
+use crate::application::dtos::{DirectoryListingDto, FileEntryDto};
+use crate::domain::workspace::value_objects::{ArchiveFormat, ArchiveMember, ArchivePath};
+use crate::infrastructure::archive::MAX_MEMBER_SIZE;
+use crate::infrastructure::{AppError, AppResult, ArchiveReader};
+
+/// Every file of an archive, including those of nested archives
+pub type ArchiveFiles = Vec<(ArchivePath, ArchiveMember)>;
+
+/// Cached archive contents together with the archive file state they were
+/// read from
+struct Cached<T> {
+    size: u64,
+    modified: SystemTime,
+    value: Arc<T>,
+}
+
+/// Application service for browsing archive files as read-only folders
+///
+/// ZIP, 7z, TAR and gzipped TAR archives are listed like folders, with
+/// paths of the form `<archive>!/<member>` (see `ArchivePath`). Nothing is
+/// unpacked except single members opened for viewing, which are copied to
+/// a temporary folder as read-only files. Member lists are cached until the
+/// archive file changes.
+pub struct ArchiveService {
+    temp_root: PathBuf,
+    listings: RwLock<HashMap<String, Cached<Vec<ArchiveMember>>>>,
+    walks: RwLock<HashMap<PathBuf, Cached<ArchiveFiles>>>,
+}
+
+impl ArchiveService {
+    /// Create a new ArchiveService extracting members below `temp_root`
+    pub fn new(temp_root: PathBuf) -> Self {
+        ArchiveService {
+            temp_root,
+            listings: RwLock::new(HashMap::new()),
+            walks: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// The archive folder a path shows: a folder inside an archive, an
+    /// archive stored in one, or an archive file itself
+    pub fn folder_location(path: &str) -> Option<ArchivePath> {
+        if let Some(location) = ArchivePath::parse(path) {
+            return Some(location.enter().unwrap_or(location));
+        }
+        let path = Path::new(path);
+        let is_archive = path
+            .file_name()
+            .is_some_and(|name| ArchiveFormat::is_archive_name(&name.to_string_lossy()));
+        (is_archive && path.is_file()).then(|| ArchivePath::root(path))
+    }
+
+    /// List a folder inside an archive
+    ///
+    /// Returns `None` for paths that are neither in an archive nor an
+    /// archive file.
+    pub async fn listing(&self, path: &str) -> AppResult<Option<DirectoryListingDto>> {
+        let Some(folder) = Self::folder_location(path) else {
+            return Ok(None);
+        };
+        let (members, archive_modified) = self.members(&folder).await?;
+        if !ArchiveMember::is_folder(&members, folder.member()) {
+            return Err(AppError::validation_error("Path is not a directory", None));
+        }
+
+        let container = folder.container();
+        let entries = ArchiveMember::children(&members, folder.member())
+            .iter()
+            .map(|member| entry_dto(&container.join(&member.path), member, archive_modified))
+            .collect();
+        let parent_path = folder
+            .to_path_buf()
+            .parent()
+            .map(|parent| parent.to_string_lossy().to_string());
+
+        Ok(Some(
+            DirectoryListingDto::new(entries, false, parent_path, true)
+                .with_archive(folder.archive().to_string_lossy().to_string()),
+        ))
+    }
+
+    /// Get the entry for a path inside an archive
+    ///
+    /// Returns `None` for paths that are not in an archive.
+    pub async fn entry(&self, path: &str) -> AppResult<Option<FileEntryDto>> {
+        let Some(location) = ArchivePath::parse(path) else {
+            return Ok(None);
+        };
+        let (members, archive_modified) = self.members(&location).await?;
+
+        let member = if location.is_root() {
+            ArchiveMember {
+                path: location.name(),
+                is_dir: true,
+                size: 0,
+                modified: None,
+            }
+        } else if let Some(member) = members
+            .iter()
+            .find(|member| !member.is_dir && member.path == location.member())
+        {
+            member.clone()
+        } else if ArchiveMember::is_folder(&members, location.member()) {
+            ArchiveMember {
+                path: location.member().to_string(),
+                is_dir: true,
+                size: 0,
+                modified: None,
+            }
+        } else {
+            return Err(AppError::not_found(format!("Archive member '{}'", path)));
+        };
+
+        Ok(Some(entry_dto(&location, &member, archive_modified)))
+    }
+
+    /// Copy one member of an archive to a temporary read-only file for
+    /// viewing, returning the file's path
+    ///
+    /// Copies are reused until the archive file changes.
+    pub async fn extract_member(&self, path: &str) -> AppResult<String> {
+        let location = ArchivePath::parse(path)
+            .filter(|location| !location.is_root())
+            .ok_or_else(|| AppError::validation_error("Path is not a file in an archive", None))?;
+        let (size, modified) = archive_state(location.archive()).await?;
+
+        // One folder per member and archive state
+        let mut hasher = DefaultHasher::new();
+        (location.to_string(), size, modified).hash(&mut hasher);
+        let folder = self.temp_root.join(format!("{:016x}", hasher.finish()));
+        let target = folder.join(safe_file_name(&location.name()));
+        if tokio::fs::try_exists(&target).await.unwrap_or(false) {
+            return Ok(target.to_string_lossy().to_string());
+        }
+
+        {
+            let target = target.clone();
+            tokio::task::spawn_blocking(move || extract_to(&location, &folder, &target))
+                .await
+                .map_err(|e| AppError::internal_error(format!("Archive task failed: {}", e)))??;
+        }
+
+        Ok(target.to_string_lossy().to_string())
+    }
+
+    /// Every file of an archive file, including those of nested archives
+    pub async fn files(&self, archive: &Path) -> AppResult<Arc<ArchiveFiles>> {
+        let (size, modified) = archive_state(archive).await?;
+        if let Some(cached) = self.walks.read().await.get(archive) {
+            if cached.size == size && cached.modified == modified {
+                return Ok(cached.value.clone());
+            }
+        }
+
+        let files = {
+            let archive = archive.to_path_buf();
+            tokio::task::spawn_blocking(move || ArchiveReader::walk(&archive))
+                .await
+                .map_err(|e| AppError::internal_error(format!("Archive task failed: {}", e)))??
+        };
+        let files = Arc::new(files);
+        self.walks.write().await.insert(
+            archive.to_path_buf(),
+            Cached {
+                size,
+                modified,
+                value: files.clone(),
+            },
+        );
+        Ok(files)
+    }
+
+    /// Members of the innermost archive of a location, and when the archive
+    /// file was last modified
+    async fn members(
+        &self,
+        location: &ArchivePath,
+    ) -> AppResult<(Arc<Vec<ArchiveMember>>, DateTime<Utc>)> {
+        let (size, modified) = archive_state(location.archive()).await?;
+        let container = location.container();
+        let key = container.to_string();
+        if let Some(cached) = self.listings.read().await.get(&key) {
+            if cached.size == size && cached.modified == modified {
+                return Ok((cached.value.clone(), modified.into()));
+            }
+        }
+
+        let members = tokio::task::spawn_blocking(move || ArchiveReader::list(&container))
+            .await
+            .map_err(|e| AppError::internal_error(format!("Archive task failed: {}", e)))??;
+        let members = Arc::new(members);
+        self.listings.write().await.insert(
+            key,
+            Cached {
+                size,
+                modified,
+                value: members.clone(),
+            },
+        );
+        Ok((members, modified.into()))
+    }
+}
+
+/// Size and modification time of an archive file
+async fn archive_state(archive: &Path) -> AppResult<(u64, SystemTime)> {
+    let metadata = tokio::fs::metadata(archive)
+        .await
+        .map_err(|_| AppError::filesystem_error("Archive not found"))?;
+    if !metadata.is_file() {
+        return Err(AppError::validation_error(
+            "Path is not an archive file",
+            None,
+        ));
+    }
+    let modified = metadata
+        .modified()
+        .map_err(|_| AppError::filesystem_error("Failed to read modification time"))?;
+    Ok((metadata.len(), modified))
+}
+
+/// Read-only entry for an archive member; members without a time of their
+/// own show the archive's
+/// Stream a member into a read-only file at `target` inside `folder`
+fn extract_to(location: &ArchivePath, folder: &Path, target: &Path) -> AppResult<()> {
+    let write_failed =
+        |e: std::io::Error| AppError::filesystem_error(format!("Failed to extract member: {}", e));
+    std::fs::create_dir_all(folder).map_err(write_failed)?;
+
+    // Written under another name first, so no half-written copy is reused
+    let partial = folder.join(format!(".partial-{}", uuid::Uuid::new_v4()));
+    let result = (|| -> AppResult<()> {
+        let mut file = std::io::BufWriter::new(File::create(&partial).map_err(write_failed)?);
+        ArchiveReader::copy_member(location, MAX_MEMBER_SIZE, &mut file)?;
+        let file = file
+            .into_inner()
+            .map_err(|e| write_failed(e.into_error()))?;
+        let mut permissions = file.metadata().map_err(write_failed)?.permissions();
+        permissions.set_readonly(true);
+        std::fs::set_permissions(&partial, permissions).map_err(write_failed)?;
+        std::fs::rename(&partial, target).map_err(write_failed)
+    })();
+
+    if result.is_err() {
+        let _ = std::fs::remove_file(&partial);
+    }
+    result
+}
+
+fn entry_dto(
+    location: &ArchivePath,
+    member: &ArchiveMember,
+    archive_modified: DateTime<Utc>,
+) -> FileEntryDto {
+    let name = member.name().to_string();
+    let path = location.to_string();
+    let modified = member.modified.unwrap_or(archive_modified).to_rfc3339();
+
+    let mut entry = if member.is_dir {
+        FileEntryDto::directory(name, path, modified)
+    } else {
+        FileEntryDto::file(name, path, Some(member.size), modified)
+    }
+    .into_virtual();
+    entry.is_archive = !member.is_dir && ArchiveFormat::is_archive_name(&member.path);
+    entry
+}
+
+/// Member name usable as a file name on every platform
+fn safe_file_name(name: &str) -> String {
+    let name: String = name
+        .chars()
+        .map(|c| match c {
+            '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
+            c if c.is_control() => '_',
+            c => c,
+        })
+        .collect();
+    match name.trim_matches('.') {
+        "" => "member".to_string(),
+        _ => name,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use tempfile::TempDir;
+
+    fn create_archive(dir: &Path) -> PathBuf {
+        let mut inner = zip::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+        inner
+            .start_file("memo.txt", zip::write::SimpleFileOptions::default())
+            .unwrap();
+        inner.write_all(b"nested memo").unwrap();
+        let inner = inner.finish().unwrap().into_inner();
+
+        let mut writer = zip::ZipWriter::new(std::fs::File::create(dir.join("mail.zip")).unwrap());
+        for (name, data) in [
+            ("2019/offer.pdf", b"%PDF-1.4".as_slice()),
+            ("2019/old.zip", &inner),
+            ("readme.txt", b"read me"),
+        ] {
+            writer
+                .start_file(name, zip::write::SimpleFileOptions::default())
+                .unwrap();
+            writer.write_all(data).unwrap();
+        }
+        writer.finish().unwrap();
+        dir.join("mail.zip")
+    }
+
+    #[tokio::test]
+    async fn test_browse_extract_and_walk_archive() {
+        let source = TempDir::new().unwrap();
+        let temp = TempDir::new().unwrap();
+        let archive = create_archive(source.path());
+        let service = ArchiveService::new(temp.path().to_path_buf());
+        let archive_str = archive.to_string_lossy().to_string();
+
+        assert!(service
+            .listing(&source.path().to_string_lossy())
+            .await
+            .unwrap()
+            .is_none());
+
+        // The archive file itself lists as its root
+        let root = service.listing(&archive_str).await.unwrap().unwrap();
+        assert_eq!(root.archive_path.as_deref(), Some(archive_str.as_str()));
+        assert_eq!(
+            root.parent_path,
+            Some(source.path().to_string_lossy().to_string())
+        );
+        let names: Vec<(&str, bool)> = root
+            .entries
+            .iter()
+            .map(|entry| (entry.name.as_str(), entry.is_directory()))
+            .collect();
+        assert_eq!(names, [("2019", true), ("readme.txt", false)]);
+        assert!(root.entries.iter().all(|entry| entry.is_virtual));
+
+        let folder = service
+            .listing(&root.entries[0].path)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(folder.entries.len(), 2);
+        let nested = folder
+            .entries
+            .iter()
+            .find(|entry| entry.name == "old.zip")
+            .unwrap();
+        assert!(nested.is_archive);
+        let nested = service.listing(&nested.path).await.unwrap().unwrap();
+        assert_eq!(nested.entries[0].name, "memo.txt");
+        assert_eq!(
+            nested.entries[0].path,
+            format!("{}!/2019/old.zip!/memo.txt", archive_str)
+        );
+
+        let offer = format!("{}!/2019/offer.pdf", archive_str);
+        let entry = service.entry(&offer).await.unwrap().unwrap();
+        assert_eq!(entry.size, Some(8));
+        assert!(service
+            .listing(&offer)
+            .await
+            .is_err_and(|e| e.code == "VALIDATION_ERROR"));
+
+        let extracted = service
+            .extract_member(&nested.entries[0].path)
+            .await
+            .unwrap();
+        assert!(extracted.starts_with(&*temp.path().to_string_lossy()));
+        assert_eq!(std::fs::read(&extracted).unwrap(), b"nested memo");
+        assert!(std::fs::metadata(&extracted)
+            .unwrap()
+            .permissions()
+            .readonly());
+        assert_eq!(
+            service
+                .extract_member(&nested.entries[0].path)
+                .await
+                .unwrap(),
+            extracted
+        );
+        assert!(service
+            .extract_member(&format!("{}!/missing.txt", archive_str))
+            .await
+            .is_err_and(|e| e.code == "NOT_FOUND"));
+
+        let files = service.files(&archive).await.unwrap();
+        assert_eq!(files.len(), 4);
+        assert!(files
+            .iter()
+            .any(|(location, _)| location.to_string().ends_with("old.zip!/memo.txt")));
+    }
+}
@@ -0,0 +1,585 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+use crate::application::dtos::{BulkAssignmentDto, CategoryDto, CategoryTreeDto, DocumentDto};
+use crate::application::services::project_service::load_project;
+use crate::domain::category::{
+    Category, CategoryColor, CategoryId, CategoryKind, CategoryRepository, Shortcut,
+};
+use crate::domain::document::{DocumentId, DocumentRepository};
+use crate::domain::project::{ProjectId, ProjectRepository};
+use crate::infrastructure::{AppError, AppResult};
+
+/// Application service for the categories and tags of a project, backing
+/// the Category Explorer
+///
+/// Categories form a tree per project; tags are kept apart and may be
+/// nested as well. Documents are assigned by their stable id. Counts are
+/// given per node, both for the node itself and for its whole subtree,
+/// where a document assigned at several levels is counted once.
+pub struct CategoryService {
+    project_repository: Arc<dyn ProjectRepository>,
+    document_repository: Arc<dyn DocumentRepository>,
+    category_repository: Arc<dyn CategoryRepository>,
+}
+
+impl CategoryService {
+    /// Create a new CategoryService
+    pub fn new(
+        project_repository: Arc<dyn ProjectRepository>,
+        document_repository: Arc<dyn DocumentRepository>,
+        category_repository: Arc<dyn CategoryRepository>,
+    ) -> Self {
+        CategoryService {
+            project_repository,
+            document_repository,
+            category_repository,
+        }
+    }
+
+    /// Create a category or tag, optionally below a parent of the same kind
+    pub async fn create_category(
+        &self,
+        project_id: &str,
+        kind: &str,
+        name: String,
+        parent_id: Option<String>,
+        color: Option<String>,
+        shortcut: Option<String>,
+    ) -> AppResult<CategoryDto> {
+        let project_id = load_project(self.project_repository.as_ref(), project_id)
+            .await?
+            .id()
+            .clone();
+        let categories = self.categories_by_id(&project_id).await?;
+
+        let mut category = Category::new(project_id, CategoryKind::parse(kind)?, name)?;
+        if let Some(parent_id) = parent_id.filter(|id| !id.trim().is_empty()) {
+            Self::move_below(&mut category, &parent_id, &categories)?;
+        }
+        category.set_color(parse_optional(color, CategoryColor::parse)?);
+        category.set_shortcut(parse_optional(shortcut, Shortcut::parse)?);
+        self.category_repository.save(&category).await?;
+
+        Ok(CategoryDto::from_category(
+            &category,
+            path_of(&category, &categories),
+        ))
+    }
+
+    /// Change a category or tag
+    ///
+    /// Fields left out are unchanged. An empty parent moves the node to the
+    /// top level; an empty colour or shortcut removes it.
+    pub async fn update_category(
+        &self,
+        category_id: &str,
+        name: Option<String>,
+        parent_id: Option<String>,
+        color: Option<String>,
+        shortcut: Option<String>,
+    ) -> AppResult<CategoryDto> {
+        let mut category = self.load(category_id).await?;
+        let categories = self.categories_by_id(category.project_id()).await?;
+
+        if let Some(name) = name {
+            category.rename(name)?;
+        }
+        match parent_id {
+            Some(parent_id) if parent_id.trim().is_empty() => category.move_to(None, &[])?,
+            Some(parent_id) => Self::move_below(&mut category, &parent_id, &categories)?,
+            None => {}
+        }
+        if let Some(color) = color {
+            category.set_color(parse_optional(Some(color), CategoryColor::parse)?);
+        }
+        if let Some(shortcut) = shortcut {
+            category.set_shortcut(parse_optional(Some(shortcut), Shortcut::parse)?);
+        }
+        self.category_repository.save(&category).await?;
+
+        Ok(CategoryDto::from_category(
+            &category,
+            path_of(&category, &categories),
+        ))
+    }
+
+    /// Delete a category or tag with everything below it; returns the
+    /// number of nodes deleted
+    ///
+    /// Documents are not touched, only their assignments are removed.
+    pub async fn delete_category(&self, category_id: &str) -> AppResult<usize> {
+        let category = self.load(category_id).await?;
+        let categories = self
+            .category_repository
+            .list_by_project(category.project_id())
+            .await?;
+
+        let subtree = subtree_ids(category.id(), &categories);
+        self.category_repository.delete(&subtree).await?;
+        Ok(subtree.len())
+    }
+
+    /// The category tree and tags of a project, with document counts
+    pub async fn list_categories(&self, project_id: &str) -> AppResult<CategoryTreeDto> {
+        let project_id = load_project(self.project_repository.as_ref(), project_id)
+            .await?
+            .id()
+            .clone();
+        let categories = self
+            .category_repository
+            .list_by_project(&project_id)
+            .await?;
+        let documents: HashSet<DocumentId> = self
+            .document_repository
+            .list_by_project(&project_id)
+            .await?
+            .iter()
+            .map(|document| document.id().clone())
+            .collect();
+
+        // Assignments of documents no longer known are left out of the counts
+        let mut assigned: HashMap<CategoryId, HashSet<DocumentId>> = HashMap::new();
+        for assignment in self
+            .category_repository
+            .list_assignments(&project_id)
+            .await?
+            .into_iter()
+            .filter(|assignment| documents.contains(&assignment.document_id))
+        {
+            assigned
+                .entry(assignment.category_id)
+                .or_default()
+                .insert(assignment.document_id);
+        }
+
+        let mut children: HashMap<Option<&CategoryId>, Vec<&Category>> = HashMap::new();
+        for category in &categories {
+            children
+                .entry(category.parent_id())
+                .or_default()
+                .push(category);
+        }
+
+        let mut categorized = HashSet::new();
+        let mut tree = CategoryTreeDto {
+            project_id: project_id.value().to_string(),
+            categories: Vec::new(),
+            tags: Vec::new(),
+            document_count: documents.len(),
+            uncategorized_count: 0,
+        };
+        for root in children.get(&None).into_iter().flatten() {
+            let (node, documents) = build_node(root, Vec::new(), &children, &assigned);
+            match root.kind() {
+                CategoryKind::Category => {
+                    categorized.extend(documents);
+                    tree.categories.push(node);
+                }
+                CategoryKind::Tag => tree.tags.push(node),
+            }
+        }
+        tree.uncategorized_count = documents.len() - categorized.len();
+
+        Ok(tree)
+    }
+
+    /// Assign a category or tag to documents of its project
+    pub async fn assign_category(
+        &self,
+        category_id: &str,
+        document_ids: Vec<String>,
+    ) -> AppResult<BulkAssignmentDto> {
+        let category = self.load(category_id).await?;
+        let document_ids = self.project_documents(&category, document_ids).await?;
+
+        let changed = self
+            .category_repository
+            .assign(category.id(), &document_ids)
+            .await?;
+        Ok(BulkAssignmentDto {
+            category_id: category.id().value().to_string(),
+            requested: document_ids.len(),
+            changed,
+        })
+    }
+
+    /// Remove a category or tag from documents
+    pub async fn unassign_category(
+        &self,
+        category_id: &str,
+        document_ids: Vec<String>,
+    ) -> AppResult<BulkAssignmentDto> {
+        let category = self.load(category_id).await?;
+        let document_ids = document_ids
+            .into_iter()
+            .map(parse_document_id)
+            .collect::<AppResult<Vec<_>>>()?;
+
+        let changed = self
+            .category_repository
+            .unassign(category.id(), &document_ids)
+            .await?;
+        Ok(BulkAssignmentDto {
+            category_id: category.id().value().to_string(),
+            requested: document_ids.len(),
+            changed,
+        })
+    }
+
+    /// The documents assigned to a category or tag, by path
+    ///
+    /// With `include_descendants`, documents assigned to any node below it
+    /// are listed too.
+    pub async fn list_documents_by_category(
+        &self,
+        category_id: &str,
+        include_descendants: bool,
+    ) -> AppResult<Vec<DocumentDto>> {
+        let category = self.load(category_id).await?;
+        let categories = self
+            .category_repository
+            .list_by_project(category.project_id())
+            .await?;
+        let nodes: HashSet<CategoryId> = if include_descendants {
+            subtree_ids(category.id(), &categories)
+                .into_iter()
+                .collect()
+        } else {
+            HashSet::from([category.id().clone()])
+        };
+
+        let assigned: HashSet<DocumentId> = self
+            .category_repository
+            .list_assignments(category.project_id())
+            .await?
+            .into_iter()
+            .filter(|assignment| nodes.contains(&assignment.category_id))
+            .map(|assignment| assignment.document_id)
+            .collect();
+
+        let mut documents: Vec<DocumentDto> = self
+            .document_repository
+            .list_by_project(category.project_id())
+            .await?
+            .iter()
+            .filter(|document| assigned.contains(document.id()))
+            .map(DocumentDto::from)
+            .collect();
+        documents.sort_by(|a, b| a.path.cmp(&b.path));
+        Ok(documents)
+    }
+
+    /// The categories and tags assigned to a document, without counts
+    pub async fn categories_of_document(&self, document_id: &str) -> AppResult<Vec<CategoryDto>> {
+        let document_id = parse_document_id(document_id.to_string())?;
+        let document = self
+            .document_repository
+            .find_by_id(&document_id)
+            .await?
+            .ok_or_else(|| AppError::not_found(format!("Document with ID '{}'", document_id)))?;
+        let categories = self.categories_by_id(document.project_id()).await?;
+
+        Ok(self
+            .category_repository
+            .categories_of(&document_id)
+            .await?
+            .iter()
+            .filter_map(|id| categories.get(id))
+            .map(|category| CategoryDto::from_category(category, path_of(category, &categories)))
+            .collect())
+    }
+
+    async fn load(&self, category_id: &str) -> AppResult<Category> {
+        let id = CategoryId::from_string(category_id.to_string())?;
+        self.category_repository
+            .find_by_id(&id)
+            .await?
+            .ok_or_else(|| AppError::not_found(format!("Category with ID '{}'", id)))
+    }
+
+    async fn categories_by_id(
+        &self,
+        project_id: &ProjectId,
+    ) -> AppResult<HashMap<CategoryId, Category>> {
+        Ok(self
+            .category_repository
+            .list_by_project(project_id)
+            .await?
+            .into_iter()
+            .map(|category| (category.id().clone(), category))
+            .collect())
+    }
+
+    /// Parse document ids, refusing any that are not documents of the
+    /// category's project
+    async fn project_documents(
+        &self,
+        category: &Category,
+        document_ids: Vec<String>,
+    ) -> AppResult<Vec<DocumentId>> {
+        let known: HashSet<DocumentId> = self
+            .document_repository
+            .list_by_project(category.project_id())
+            .await?
+            .iter()
+            .map(|document| document.id().clone())
+            .collect();
+
+        document_ids
+            .into_iter()
+            .map(|id| {
+                let id = parse_document_id(id)?;
+                if known.contains(&id) {
+                    Ok(id)
+                } else {
+                    Err(AppError::validation_error(
+                        "Document does not belong to the project",
+                        Some(id.to_string()),
+                    ))
+                }
+            })
+            .collect()
+    }
+
+    fn move_below(
+        category: &mut Category,
+        parent_id: &str,
+        categories: &HashMap<CategoryId, Category>,
+    ) -> AppResult<()> {
+        let parent_id = CategoryId::from_string(parent_id.to_string())?;
+        let parent = categories
+            .get(&parent_id)
+            .ok_or_else(|| AppError::not_found(format!("Category with ID '{}'", parent_id)))?;
+
+        let mut ancestors = Vec::new();
+        let mut current = parent.parent_id();
+        while let Some(id) = current.filter(|id| !ancestors.contains(*id)) {
+            ancestors.push(id.clone());
+            current = categories.get(id).and_then(Category::parent_id);
+        }
+
+        category.move_to(Some(parent), &ancestors)?;
+        Ok(())
+    }
+}
+
+fn parse_document_id(id: String) -> AppResult<DocumentId> {
+    DocumentId::from_string(id)
+        .map_err(|e| AppError::validation_error("Invalid document ID", Some(e.to_string())))
+}
+
+/// Parse an optional value where an empty string means none
+fn parse_optional<T, E>(
+    value: Option<String>,
+    parse: impl Fn(&str) -> Result<T, E>,
+) -> Result<Option<T>, E> {
+    value
+        .filter(|value| !value.trim().is_empty())
+        .map(|value| parse(&value))
+        .transpose()
+}
+
+/// Names from the top level down to `category`
+fn path_of(category: &Category, categories: &HashMap<CategoryId, Category>) -> Vec<String> {
+    let mut path = vec![category.name().to_string()];
+    let mut current = category.parent_id();
+    while let Some(parent) = current.and_then(|id| categories.get(id)) {
+        if path.len() > categories.len() {
+            break;
+        }
+        path.push(parent.name().to_string());
+        current = parent.parent_id();
+    }
+    path.reverse();
+    path
+}
+
+/// `root` and the ids of all nodes below it
+fn subtree_ids(root: &CategoryId, categories: &[Category]) -> Vec<CategoryId> {
+    let mut ids = vec![root.clone()];
+    let mut index = 0;
+    while index < ids.len() {
+        let parent = ids[index].clone();
+        ids.extend(
+            categories
+                .iter()
+                .filter(|category| category.parent_id() == Some(&parent))
+                .filter(|category| !ids.contains(category.id()))
+                .map(|category| category.id().clone())
+                .collect::<Vec<_>>(),
+        );
+        index += 1;
+    }
+    ids
+}
+
+/// The node for `category` with its subtree and counts, and the distinct
+/// documents assigned anywhere in that subtree
+fn build_node(
+    category: &Category,
+    mut path: Vec<String>,
+    children: &HashMap<Option<&CategoryId>, Vec<&Category>>,
+    assigned: &HashMap<CategoryId, HashSet<DocumentId>>,
+) -> (CategoryDto, HashSet<DocumentId>) {
+    path.push(category.name().to_string());
+    let mut node = CategoryDto::from_category(category, path.clone());
+    let mut documents = assigned.get(category.id()).cloned().unwrap_or_default();
+    node.document_count = documents.len();
+
+    for child in children.get(&Some(category.id())).into_iter().flatten() {
+        let (child_node, child_documents) = build_node(child, path.clone(), children, assigned);
+        documents.extend(child_documents);
+        node.children.push(child_node);
+    }
+    node.total_count = documents.len();
+
+    (node, documents)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::document::Document;
+    use crate::domain::project::repositories::MockProjectRepository;
+    use crate::domain::project::Project;
+    use crate::domain::workspace::value_objects::{ContentHash, HashAlgorithm};
+    use crate::infrastructure::{
+        DatabaseConnection, SqliteCategoryRepository, SqliteDocumentRepository,
+    };
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_category_tree_counts_and_bulk_assignment() {
+        let (database, _db_dir) = DatabaseConnection::new_temp().await.unwrap();
+        let source = TempDir::new().unwrap();
+        let project = Project::new(
+            "Case".to_string(),
+            source.path().to_string_lossy().to_string(),
+            None,
+        )
+        .unwrap();
+        let project_repository = Arc::new(MockProjectRepository::new());
+        project_repository.create(&project).await.unwrap();
+        let document_repository = Arc::new(SqliteDocumentRepository::new(database.pool()));
+        let service = CategoryService::new(
+            project_repository,
+            document_repository.clone(),
+            Arc::new(SqliteCategoryRepository::new(database.pool())),
+        );
+        let project_id = project.id().value();
+
+        let documents: Vec<Document> = ["offer.pdf", "invoice.pdf", "notes.txt"]
+            .iter()
+            .map(|name| {
+                Document::new(
+                    project.id().clone(),
+                    source.path().join(name).to_string_lossy().to_string(),
+                    ContentHash::of_bytes(HashAlgorithm::Blake3, name.as_bytes()),
+                    1,
+                    0,
+                )
+            })
+            .collect();
+        document_repository.save_all(&documents).await.unwrap();
+        let ids: Vec<String> = documents
+            .iter()
+            .map(|document| document.id().value().to_string())
+            .collect();
+
+        let finance = service
+            .create_category(
+                project_id,
+                "category",
+                "Finance".to_string(),
+                None,
+                Some("#0F0".to_string()),
+                Some("ctrl+1".to_string()),
+            )
+            .await
+            .unwrap();
+        let invoices = service
+            .create_category(
+                project_id,
+                "category",
+                "Invoices".to_string(),
+                Some(finance.id.clone()),
+                None,
+                None,
+            )
+            .await
+            .unwrap();
+        assert_eq!(invoices.path, ["Finance", "Invoices"]);
+        assert_eq!(finance.color.as_deref(), Some("#00ff00"));
+        assert_eq!(finance.shortcut.as_deref(), Some("Ctrl+1"));
+        let urgent = service
+            .create_category(project_id, "tag", "Urgent".to_string(), None, None, None)
+            .await
+            .unwrap();
+
+        // Shortcuts are unique, and a node cannot move below its descendant
+        assert!(service
+            .create_category(
+                project_id,
+                "tag",
+                "Later".to_string(),
+                None,
+                None,
+                Some("Ctrl+1".to_string()),
+            )
+            .await
+            .is_err());
+        assert!(service
+            .update_category(&finance.id, None, Some(invoices.id.clone()), None, None)
+            .await
+            .is_err());
+
+        let assigned = service
+            .assign_category(&invoices.id, ids[..2].to_vec())
+            .await
+            .unwrap();
+        assert_eq!(assigned.changed, 2);
+        service
+            .assign_category(&finance.id, ids[..1].to_vec())
+            .await
+            .unwrap();
+        service
+            .assign_category(&urgent.id, ids[2..].to_vec())
+            .await
+            .unwrap();
+        assert!(service
+            .assign_category(&urgent.id, vec![DocumentId::new().value().to_string()])
+            .await
+            .is_err());
+
+        let tree = service.list_categories(project_id).await.unwrap();
+        let finance_id = CategoryId::from_string(finance.id.clone()).unwrap();
+        let finance_node = tree.find(&finance_id).unwrap();
+        assert_eq!(finance_node.document_count, 1);
+        assert_eq!(finance_node.total_count, 2);
+        assert_eq!(finance_node.children[0].document_count, 2);
+        assert_eq!(tree.tags[0].total_count, 1);
+        assert_eq!(tree.document_count, 3);
+        assert_eq!(tree.uncategorized_count, 1);
+
+        let listed = service
+            .list_documents_by_category(&finance.id, true)
+            .await
+            .unwrap();
+        assert_eq!(listed.len(), 2);
+        let of_offer = service.categories_of_document(&ids[0]).await.unwrap();
+        assert_eq!(of_offer.len(), 2);
+
+        let removed = service
+            .unassign_category(&invoices.id, ids.clone())
+            .await
+            .unwrap();
+        assert_eq!((removed.requested, removed.changed), (3, 2));
+
+        // Deleting a node removes its subtree
+        assert_eq!(service.delete_category(&finance.id).await.unwrap(), 2);
+        let tree = service.list_categories(project_id).await.unwrap();
+        assert!(tree.categories.is_empty());
+        assert_eq!(tree.uncategorized_count, 3);
+    }
+}
@@ -0,0 +1,473 @@
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use crate::application::dtos::{CitationCheckDto, DocumentDto, ReportDto, ResolvedCitationDto};
+use crate::application::services::project_service::parse_project_id;
+use crate::domain::det::{DetDocument, DetStore, PmNode};
+use crate::domain::document::{DerivativeFamily, Document, DocumentId, DocumentRepository};
+use crate::domain::project::ProjectRepository;
+use crate::domain::report::{
+    Citation, CitationLocator, CitationRepository, CitationStatus, Report, ReportId,
+    ReportRepository,
+};
+use crate::infrastructure::{AppError, AppResult};
+
+/// Longest snippet returned for a resolved citation, in characters
+const SNIPPET_LENGTH: usize = 280;
+
+/// Application service for citations from reports to source documents
+///
+/// Citations are read from the `citation` marks of a report's content and
+/// indexed when the report is saved. They point at documents by
+/// identifier, so a renamed or moved file keeps its citations once
+/// reconciliation has re-linked it. Snippets are taken from the cited
+/// document's extracted text.
+pub struct CitationService {
+    project_repository: Arc<dyn ProjectRepository>,
+    report_repository: Arc<dyn ReportRepository>,
+    citation_repository: Arc<dyn CitationRepository>,
+    document_repository: Arc<dyn DocumentRepository>,
+    det_store: Arc<dyn DetStore>,
+    derivatives_root: PathBuf,
+}
+
+impl CitationService {
+    /// Create a new CitationService reading derivatives below `derivatives_root`
+    pub fn new(
+        project_repository: Arc<dyn ProjectRepository>,
+        report_repository: Arc<dyn ReportRepository>,
+        citation_repository: Arc<dyn CitationRepository>,
+        document_repository: Arc<dyn DocumentRepository>,
+        det_store: Arc<dyn DetStore>,
+        derivatives_root: PathBuf,
+    ) -> Self {
+        CitationService {
+            project_repository,
+            report_repository,
+            citation_repository,
+            document_repository,
+            det_store,
+            derivatives_root,
+        }
+    }
+
+    /// Index the citations of a report's content, replacing earlier ones
+    ///
+    /// Citations without a document identifier are tied to the document
+    /// currently tracked at their path, if any. Returns the number of
+    /// citations indexed.
+    pub async fn index_report(&self, report: &Report, content: &PmNode) -> AppResult<usize> {
+        let mut citations = Vec::new();
+        for citation in Citation::collect(content) {
+            let citation = match citation.document_id() {
+                Some(_) => citation,
+                None => match self
+                    .document_repository
+                    .find_by_path(report.project_id(), citation.path())
+                    .await?
+                {
+                    Some(document) => citation.with_document_id(document.id().clone()),
+                    None => citation,
+                },
+            };
+            if !citations.contains(&citation) {
+                citations.push(citation);
+            }
+        }
+
+        self.citation_repository
+            .replace_for_report(report.id(), &citations)
+            .await?;
+        Ok(citations.len())
+    }
+
+    /// Resolve every citation of a report to a current path and snippet
+    pub async fn resolve_report_citations(
+        &self,
+        report_id: &str,
+    ) -> AppResult<Vec<ResolvedCitationDto>> {
+        let id = Self::parse_report_id(report_id)?;
+
+        let mut resolved = Vec::new();
+        for citation in self.citation_repository.list_by_report(&id).await? {
+            resolved.push(self.resolve(&id, &citation).await?);
+        }
+        Ok(resolved)
+    }
+
+    /// List the tracked documents a report cites, in order of first citation
+    pub async fn list_cited_documents(&self, report_id: &str) -> AppResult<Vec<DocumentDto>> {
+        let id = Self::parse_report_id(report_id)?;
+
+        let mut seen: Vec<&DocumentId> = Vec::new();
+        let citations = self.citation_repository.list_by_report(&id).await?;
+        let mut documents = Vec::new();
+        for document_id in citations.iter().filter_map(Citation::document_id) {
+            if seen.contains(&document_id) {
+                continue;
+            }
+            seen.push(document_id);
+            if let Some(document) = self.document_repository.find_by_id(document_id).await? {
+                documents.push(DocumentDto::from(&document));
+            }
+        }
+        Ok(documents)
+    }
+
+    /// List the reports citing a document, most recently updated first
+    pub async fn list_citing_reports(&self, document_id: &str) -> AppResult<Vec<ReportDto>> {
+        let id = DocumentId::from_string(document_id.to_string()).map_err(|e| {
+            AppError::validation_error("Invalid document ID format", Some(e.to_string()))
+        })?;
+        let document = self
+            .document_repository
+            .find_by_id(&id)
+            .await?
+            .ok_or_else(|| AppError::not_found(format!("Document with ID '{}'", document_id)))?;
+        let project = self
+            .project_repository
+            .find_by_id(document.project_id())
+            .await?
+            .ok_or_else(|| {
+                AppError::not_found(format!("Project with ID '{}'", document.project_id()))
+            })?;
+        let reports_folder = project.reports_folder();
+
+        let mut reports = Vec::new();
+        for report_id in self.citation_repository.find_reports_citing(&id).await? {
+            if let Some(report) = self.report_repository.find_by_id(&report_id).await? {
+                reports.push(ReportDto::from_report(&report, &reports_folder));
+            }
+        }
+        Ok(reports)
+    }
+
+    /// Check the citations of every report in a project
+    pub async fn check_project(&self, project_id: &str) -> AppResult<CitationCheckDto> {
+        let id = parse_project_id(project_id)?;
+
+        let mut checked = 0;
+        let mut broken = Vec::new();
+        for report in self.report_repository.list_by_project(&id).await? {
+            for citation in self.citation_repository.list_by_report(report.id()).await? {
+                checked += 1;
+                let resolved = self.resolve(report.id(), &citation).await?;
+                if resolved.broken {
+                    broken.push(resolved);
+                }
+            }
+        }
+
+        if !broken.is_empty() {
+            tracing::info!(
+                "{} of {} citations in project {} are broken",
+                broken.len(),
+                checked,
+                project_id
+            );
+        }
+
+        Ok(CitationCheckDto {
+            project_id: project_id.to_string(),
+            checked,
+            broken,
+            checked_at: chrono::Utc::now().to_rfc3339(),
+        })
+    }
+
+    async fn resolve(
+        &self,
+        report_id: &ReportId,
+        citation: &Citation,
+    ) -> AppResult<ResolvedCitationDto> {
+        let document = match citation.document_id() {
+            Some(id) => self.document_repository.find_by_id(id).await?,
+            None => None,
+        };
+        let current_path = match (&document, citation.document_id()) {
+            (Some(document), _) if !document.is_missing() => Some(document.path().to_string()),
+            (None, None) => Some(citation.path().to_string()),
+            _ => None,
+        }
+        .filter(|path| Path::new(path).is_file());
+
+        let (status, snippet) = match (&current_path, &document) {
+            (None, _) => (CitationStatus::Missing, None),
+            (Some(_), Some(document)) => match self.load_extracted(document).await {
+                Some(det) => locate(&det, citation.locator()),
+                None => (CitationStatus::Valid, None),
+            },
+            (Some(_), None) => (CitationStatus::Valid, None),
+        };
+        let status = match &current_path {
+            Some(path) if status == CitationStatus::Valid && path != citation.path() => {
+                CitationStatus::Moved
+            }
+            _ => status,
+        };
+
+        Ok(ResolvedCitationDto {
+            report_id: report_id.value().to_string(),
+            document_id: citation.document_id().map(|id| id.value().to_string()),
+            cited_path: citation.path().to_string(),
+            locator: citation.locator(),
+            current_path,
+            snippet,
+            status: status.as_str().to_string(),
+            broken: status.is_broken(),
+        })
+    }
+
+    /// The document's extracted text, if it has been extracted and can be read
+    async fn load_extracted(&self, document: &Document) -> Option<DetDocument> {
+        let path =
+            DerivativeFamily::new(&self.derivatives_root, document.project_id(), document.id())
+                .extracted_path();
+        if !path.is_file() {
+            return None;
+        }
+        match self.det_store.load(&path).await {
+            Ok(det) => Some(det),
+            Err(error) => {
+                tracing::warn!("Cannot read {} for citations: {}", path.display(), error);
+                None
+            }
+        }
+    }
+
+    fn parse_report_id(report_id: &str) -> AppResult<ReportId> {
+        Ok(ReportId::from_string(report_id.to_string())?)
+    }
+}
+
+/// Find the cited passage in a document's extracted text
+fn locate(det: &DetDocument, locator: CitationLocator) -> (CitationStatus, Option<String>) {
+    match locator {
+        CitationLocator::File => (
+            CitationStatus::Valid,
+            Some(shorten(&det.content().plain_text())),
+        ),
+        CitationLocator::Page { page } => match det.processing().page_count {
+            Some(count) if page > count => (CitationStatus::OutOfRange, None),
+            _ => (CitationStatus::Valid, None),
+        },
+        CitationLocator::CharRange { start, end } => {
+            let text = det.content().plain_text();
+            let (start, end) = (start as usize, end as usize);
+            if end > text.chars().count() {
+                (CitationStatus::OutOfRange, None)
+            } else {
+                let passage: String = text.chars().skip(start).take(end - start).collect();
+                (CitationStatus::Valid, Some(shorten(&passage)))
+            }
+        }
+        CitationLocator::Position { from, to } => {
+            match det.content().text_between(from as usize, to as usize) {
+                Some(passage) => (CitationStatus::Valid, Some(shorten(&passage))),
+                None => (CitationStatus::OutOfRange, None),
+            }
+        }
+    }
+}
+
+/// Trim a passage to `SNIPPET_LENGTH` characters, marking the cut
+fn shorten(text: &str) -> String {
+    let text = text.trim();
+    if text.chars().count() <= SNIPPET_LENGTH {
+        return text.to_string();
+    }
+    let mut snippet: String = text.chars().take(SNIPPET_LENGTH).collect();
+    snippet.push('…');
+    snippet
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::det::{DetKind, ProcessingMetadata};
+    use crate::domain::export::CitedSource;
+    use crate::domain::project::Project;
+    use crate::domain::report::ReportCategory;
+    use crate::domain::workspace::value_objects::{ContentHash, HashAlgorithm};
+    use crate::infrastructure::{
+        DatabaseConnection, FileDetStore, SqliteCitationRepository, SqliteDocumentRepository,
+        SqliteProjectRepository, SqliteReportRepository,
+    };
+    use serde_json::Value;
+    use std::fs;
+    use tempfile::TempDir;
+
+    struct Fixture {
+        service: CitationService,
+        documents: Arc<SqliteDocumentRepository>,
+        report: Report,
+        document: Document,
+        source: TempDir,
+        _derivatives: TempDir,
+        _db_dir: TempDir,
+    }
+
+    async fn create_fixture() -> Fixture {
+        let (database, db_dir) = DatabaseConnection::new_temp().await.unwrap();
+        let projects = Arc::new(SqliteProjectRepository::new(database.pool()));
+        let reports = Arc::new(SqliteReportRepository::new(database.pool()));
+        let documents = Arc::new(SqliteDocumentRepository::new(database.pool()));
+        let store = Arc::new(FileDetStore::new());
+        let source = TempDir::new().unwrap();
+        let derivatives = TempDir::new().unwrap();
+
+        let project = Project::new(
+            "Citations".to_string(),
+            source.path().to_string_lossy().to_string(),
+            None,
+        )
+        .unwrap();
+        projects.create(&project).await.unwrap();
+
+        let path = source.path().join("letter.txt");
+        fs::write(&path, "Dear client, the invoice is overdue.").unwrap();
+        let document = Document::new(
+            project.id().clone(),
+            path.to_string_lossy().to_string(),
+            ContentHash::new(HashAlgorithm::default(), "00".repeat(32)).unwrap(),
+            36,
+            0,
+        );
+        documents.save(&document).await.unwrap();
+
+        let extracted = DetDocument::new(
+            DetKind::Extracted,
+            PmNode::doc(vec![PmNode::paragraph(PmNode::text_nodes(
+                "Dear client, the invoice is overdue.",
+                Vec::new(),
+            ))]),
+            ProcessingMetadata::completed("text"),
+        );
+        store
+            .save(
+                &DerivativeFamily::new(derivatives.path(), project.id(), document.id())
+                    .extracted_path(),
+                &extracted,
+            )
+            .await
+            .unwrap();
+
+        let report = Report::new(
+            project.id().clone(),
+            "Findings".to_string(),
+            ReportCategory::Findings,
+            "findings/findings.det".to_string(),
+        )
+        .unwrap();
+        reports.save(&report).await.unwrap();
+
+        Fixture {
+            service: CitationService::new(
+                projects,
+                reports,
+                Arc::new(SqliteCitationRepository::new(database.pool())),
+                documents.clone(),
+                store,
+                derivatives.path().to_path_buf(),
+            ),
+            documents,
+            report,
+            document,
+            source,
+            _derivatives: derivatives,
+            _db_dir: db_dir,
+        }
+    }
+
+    fn cite(path: &str, range: Option<(u32, u32)>) -> PmNode {
+        let mut mark = CitedSource::new(path).to_mark();
+        if let Some((start, end)) = range {
+            let attrs = mark.attrs.as_mut().unwrap();
+            attrs.insert("charStart".to_string(), Value::from(start));
+            attrs.insert("charEnd".to_string(), Value::from(end));
+        }
+        PmNode::doc(vec![PmNode::paragraph(PmNode::text_nodes(
+            "As the letter says",
+            vec![mark],
+        ))])
+    }
+
+    #[tokio::test]
+    async fn test_citations_follow_moved_documents() {
+        let mut fixture = create_fixture().await;
+        let report_id = fixture.report.id().value().to_string();
+        let content = cite(fixture.document.path(), Some((13, 24)));
+
+        let indexed = fixture
+            .service
+            .index_report(&fixture.report, &content)
+            .await
+            .unwrap();
+        assert_eq!(indexed, 1);
+
+        let resolved = fixture
+            .service
+            .resolve_report_citations(&report_id)
+            .await
+            .unwrap();
+        assert_eq!(
+            resolved[0].document_id.as_deref(),
+            Some(fixture.document.id().value())
+        );
+        assert_eq!(resolved[0].status, "valid");
+        assert_eq!(resolved[0].snippet.as_deref(), Some("the invoice"));
+
+        let cited = fixture
+            .service
+            .list_cited_documents(&report_id)
+            .await
+            .unwrap();
+        assert_eq!(cited.len(), 1);
+        let citing = fixture
+            .service
+            .list_citing_reports(fixture.document.id().value())
+            .await
+            .unwrap();
+        assert_eq!(citing[0].id, report_id);
+
+        // Reconciliation re-links the document after a move
+        let moved = fixture.source.path().join("archive-letter.txt");
+        fs::rename(fixture.document.path(), &moved).unwrap();
+        fixture
+            .document
+            .relink(moved.to_string_lossy().to_string(), 36, 0);
+        fixture.documents.save(&fixture.document).await.unwrap();
+
+        let resolved = fixture
+            .service
+            .resolve_report_citations(&report_id)
+            .await
+            .unwrap();
+        assert_eq!(resolved[0].status, "moved");
+        assert_eq!(
+            resolved[0].current_path.as_deref(),
+            Some(moved.to_string_lossy().as_ref())
+        );
+        assert!(!resolved[0].broken);
+    }
+
+    #[tokio::test]
+    async fn test_check_reports_broken_citations() {
+        let fixture = create_fixture().await;
+        let project_id = fixture.report.project_id().value().to_string();
+        let mut content = cite(fixture.document.path(), Some((30, 90)));
+        content
+            .content
+            .extend(cite("/nowhere/gone.pdf", None).content);
+        fixture
+            .service
+            .index_report(&fixture.report, &content)
+            .await
+            .unwrap();
+
+        let check = fixture.service.check_project(&project_id).await.unwrap();
+        assert_eq!(check.checked, 2);
+        let statuses: Vec<&str> = check.broken.iter().map(|c| c.status.as_str()).collect();
+        assert_eq!(statuses, vec!["out_of_range", "missing"]);
+    }
+}
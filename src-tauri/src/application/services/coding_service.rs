@@ -0,0 +1,414 @@
+use std::collections::{BTreeMap, HashSet};
+use std::sync::Arc;
+
+use crate::application::dtos::{
+    CodingDecisionDto, CodingProgressDto, CodingSchemaDto, DocumentDto,
+};
+use crate::application::services::project_service::load_project;
+use crate::application::services::review_service::resolve_reviewer;
+use crate::domain::coding::{
+    CodingDecision, CodingField, CodingRepository, CodingSchema, CodingValue, CodingValues,
+};
+use crate::domain::document::{Document, DocumentId, DocumentRepository};
+use crate::domain::project::{ProjectId, ProjectRepository};
+use crate::domain::review::ReviewRepository;
+use crate::infrastructure::{AppError, AppResult};
+
+/// Application service for coding documents in review
+///
+/// Each project codes its documents with a schema of fields, by default a
+/// designation of Relevant, Not Relevant or Privileged with a reason.
+/// Recoding a document keeps the earlier decisions as its history; the
+/// latest one counts. Missing documents are not part of the review.
+pub struct CodingService {
+    project_repository: Arc<dyn ProjectRepository>,
+    document_repository: Arc<dyn DocumentRepository>,
+    coding_repository: Arc<dyn CodingRepository>,
+    review_repository: Option<Arc<dyn ReviewRepository>>,
+}
+
+impl CodingService {
+    /// Create a new CodingService
+    pub fn new(
+        project_repository: Arc<dyn ProjectRepository>,
+        document_repository: Arc<dyn DocumentRepository>,
+        coding_repository: Arc<dyn CodingRepository>,
+    ) -> Self {
+        CodingService {
+            project_repository,
+            document_repository,
+            coding_repository,
+            review_repository: None,
+        }
+    }
+
+    /// Record the active reviewer on decisions made without one
+    pub fn with_review_repository(mut self, review_repository: Arc<dyn ReviewRepository>) -> Self {
+        self.review_repository = Some(review_repository);
+        self
+    }
+
+    /// The coding schema of a project, or the default one
+    pub async fn get_coding_schema(&self, project_id: &str) -> AppResult<CodingSchemaDto> {
+        let project_id = load_project(self.project_repository.as_ref(), project_id)
+            .await?
+            .id()
+            .clone();
+        Ok(CodingSchemaDto::from(&self.schema_of(&project_id).await?))
+    }
+
+    /// Replace the coding schema of a project
+    ///
+    /// Decisions already made are kept as they were.
+    pub async fn save_coding_schema(
+        &self,
+        project_id: &str,
+        fields: Vec<CodingField>,
+    ) -> AppResult<CodingSchemaDto> {
+        let project_id = load_project(self.project_repository.as_ref(), project_id)
+            .await?
+            .id()
+            .clone();
+        let schema = CodingSchema::new(project_id, fields)?;
+        self.coding_repository.save_schema(&schema).await?;
+        Ok(CodingSchemaDto::from(&schema))
+    }
+
+    /// Code a document, checking the values against its project's schema
+    ///
+    /// Without a reviewer, the decision is recorded for the active one.
+    pub async fn code_document(
+        &self,
+        document_id: &str,
+        values: CodingValues,
+        reviewer: Option<String>,
+    ) -> AppResult<CodingDecisionDto> {
+        let document = self.load_document(document_id).await?;
+        let schema = self.schema_of(document.project_id()).await?;
+        let reviewer = resolve_reviewer(self.review_repository.as_ref(), reviewer).await?;
+
+        let decision = CodingDecision::new(
+            document.project_id().clone(),
+            document.id().clone(),
+            schema.validate(values)?,
+            reviewer,
+        )?;
+        self.coding_repository.record_decision(&decision).await?;
+        Ok(CodingDecisionDto::from(&decision))
+    }
+
+    /// The current coding of a document; `None` if it is not coded yet
+    pub async fn get_document_coding(
+        &self,
+        document_id: &str,
+    ) -> AppResult<Option<CodingDecisionDto>> {
+        Ok(self
+            .get_coding_history(document_id)
+            .await?
+            .into_iter()
+            .last())
+    }
+
+    /// All decisions on a document, oldest first
+    pub async fn get_coding_history(&self, document_id: &str) -> AppResult<Vec<CodingDecisionDto>> {
+        let document = self.load_document(document_id).await?;
+        Ok(self
+            .coding_repository
+            .history(document.id())
+            .await?
+            .iter()
+            .map(CodingDecisionDto::from)
+            .collect())
+    }
+
+    /// The next document without a decision, by path
+    ///
+    /// Starts after `after_document_id` when given and wraps around to the
+    /// start, so a reviewer can move on from the document in front of them.
+    /// `None` once every document is coded.
+    pub async fn next_uncoded_document(
+        &self,
+        project_id: &str,
+        after_document_id: Option<String>,
+    ) -> AppResult<Option<DocumentDto>> {
+        let project_id = load_project(self.project_repository.as_ref(), project_id)
+            .await?
+            .id()
+            .clone();
+        let coded = self.coded_documents(&project_id).await?;
+
+        let mut documents = self.reviewable_documents(&project_id).await?;
+        documents.sort_by(|a, b| a.path().cmp(b.path()));
+        let start = match after_document_id.filter(|id| !id.trim().is_empty()) {
+            Some(id) => {
+                let id = parse_document_id(id)?;
+                documents
+                    .iter()
+                    .position(|document| document.id() == &id)
+                    .map_or(0, |index| index + 1)
+            }
+            None => 0,
+        };
+
+        Ok(documents[start..]
+            .iter()
+            .chain(&documents[..start])
+            .find(|document| !coded.contains(document.id()))
+            .map(DocumentDto::from))
+    }
+
+    /// How many documents of a project are coded, with counts per value of
+    /// the choice fields
+    pub async fn get_coding_progress(&self, project_id: &str) -> AppResult<CodingProgressDto> {
+        let project_id = load_project(self.project_repository.as_ref(), project_id)
+            .await?
+            .id()
+            .clone();
+        self.progress_of(&project_id).await
+    }
+
+    /// The coding progress of every project, for the project list
+    pub async fn list_coding_progress(&self) -> AppResult<Vec<CodingProgressDto>> {
+        let projects = self
+            .project_repository
+            .list_all()
+            .await
+            .map_err(AppError::from)?;
+
+        let mut progress = Vec::with_capacity(projects.len());
+        for project in &projects {
+            progress.push(self.progress_of(project.id()).await?);
+        }
+        Ok(progress)
+    }
+
+    async fn progress_of(&self, project_id: &ProjectId) -> AppResult<CodingProgressDto> {
+        let schema = self.schema_of(project_id).await?;
+        let documents: HashSet<DocumentId> = self
+            .reviewable_documents(project_id)
+            .await?
+            .into_iter()
+            .map(|document| document.id().clone())
+            .collect();
+        let decisions: Vec<CodingDecision> = self
+            .coding_repository
+            .current_decisions(project_id)
+            .await?
+            .into_iter()
+            .filter(|decision| documents.contains(decision.document_id()))
+            .collect();
+
+        let mut counts: BTreeMap<String, BTreeMap<String, usize>> = BTreeMap::new();
+        for field in schema
+            .fields()
+            .iter()
+            .filter(|field| field.kind.is_choice())
+        {
+            let field_counts = counts.entry(field.key.clone()).or_default();
+            for value in &field.allowed_values {
+                let count = decisions
+                    .iter()
+                    .filter(|decision| {
+                        decision
+                            .values()
+                            .get(&field.key)
+                            .is_some_and(|coded: &CodingValue| coded.matches(value))
+                    })
+                    .count();
+                field_counts.insert(value.clone(), count);
+            }
+        }
+
+        let total = documents.len();
+        let coded = decisions.len();
+        Ok(CodingProgressDto {
+            project_id: project_id.value().to_string(),
+            total,
+            coded,
+            uncoded: total - coded,
+            percent: if total == 0 {
+                0.0
+            } else {
+                coded as f64 * 100.0 / total as f64
+            },
+            counts,
+        })
+    }
+
+    async fn schema_of(&self, project_id: &ProjectId) -> AppResult<CodingSchema> {
+        Ok(self
+            .coding_repository
+            .find_schema(project_id)
+            .await?
+            .unwrap_or_else(|| CodingSchema::default_for(project_id.clone())))
+    }
+
+    async fn coded_documents(&self, project_id: &ProjectId) -> AppResult<HashSet<DocumentId>> {
+        Ok(self
+            .coding_repository
+            .current_decisions(project_id)
+            .await?
+            .iter()
+            .map(|decision| decision.document_id().clone())
+            .collect())
+    }
+
+    async fn reviewable_documents(&self, project_id: &ProjectId) -> AppResult<Vec<Document>> {
+        Ok(self
+            .document_repository
+            .list_by_project(project_id)
+            .await?
+            .into_iter()
+            .filter(|document| !document.is_missing())
+            .collect())
+    }
+
+    async fn load_document(&self, document_id: &str) -> AppResult<Document> {
+        let id = parse_document_id(document_id.to_string())?;
+        self.document_repository
+            .find_by_id(&id)
+            .await?
+            .ok_or_else(|| AppError::not_found(format!("Document with ID '{}'", id)))
+    }
+}
+
+fn parse_document_id(id: String) -> AppResult<DocumentId> {
+    DocumentId::from_string(id)
+        .map_err(|e| AppError::validation_error("Invalid document ID", Some(e.to_string())))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::project::repositories::MockProjectRepository;
+    use crate::domain::project::Project;
+    use crate::domain::workspace::value_objects::{ContentHash, HashAlgorithm};
+    use crate::infrastructure::{
+        DatabaseConnection, SqliteCodingRepository, SqliteDocumentRepository,
+    };
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_code_documents_and_track_progress() {
+        let (database, _db_dir) = DatabaseConnection::new_temp().await.unwrap();
+        let source = TempDir::new().unwrap();
+        let project = Project::new(
+            "Case".to_string(),
+            source.path().to_string_lossy().to_string(),
+            None,
+        )
+        .unwrap();
+        let project_repository = Arc::new(MockProjectRepository::new());
+        project_repository.create(&project).await.unwrap();
+        let document_repository = Arc::new(SqliteDocumentRepository::new(database.pool()));
+        let service = CodingService::new(
+            project_repository,
+            document_repository.clone(),
+            Arc::new(SqliteCodingRepository::new(database.pool())),
+        );
+        let project_id = project.id().value();
+
+        let mut documents: Vec<Document> = ["a.pdf", "b.pdf", "c.pdf", "gone.pdf"]
+            .iter()
+            .map(|name| {
+                Document::new(
+                    project.id().clone(),
+                    source.path().join(name).to_string_lossy().to_string(),
+                    ContentHash::of_bytes(HashAlgorithm::Blake3, name.as_bytes()),
+                    1,
+                    0,
+                )
+            })
+            .collect();
+        documents[3].mark_missing();
+        document_repository.save_all(&documents).await.unwrap();
+        let ids: Vec<String> = documents
+            .iter()
+            .map(|document| document.id().value().to_string())
+            .collect();
+        let designation = |value: &str, reason: &str| {
+            CodingValues::from([
+                (
+                    "designation".to_string(),
+                    CodingValue::Text(value.to_string()),
+                ),
+                ("reason".to_string(), CodingValue::Text(reason.to_string())),
+            ])
+        };
+
+        let schema = service.get_coding_schema(project_id).await.unwrap();
+        assert!(schema.is_default);
+
+        // Privileged documents need a reason
+        assert!(service
+            .code_document(
+                &ids[0],
+                designation("Privileged", ""),
+                Some("Ana".to_string())
+            )
+            .await
+            .is_err());
+        service
+            .code_document(
+                &ids[0],
+                designation("Relevant", ""),
+                Some("Ana".to_string()),
+            )
+            .await
+            .unwrap();
+        service
+            .code_document(
+                &ids[0],
+                designation("Privileged", "Legal advice"),
+                Some("Ben".to_string()),
+            )
+            .await
+            .unwrap();
+
+        let history = service.get_coding_history(&ids[0]).await.unwrap();
+        assert_eq!(history.len(), 2);
+        let current = service.get_document_coding(&ids[0]).await.unwrap().unwrap();
+        assert_eq!(current.reviewer, "Ben");
+        assert!(service
+            .get_document_coding(&ids[1])
+            .await
+            .unwrap()
+            .is_none());
+
+        let next = service
+            .next_uncoded_document(project_id, Some(ids[1].clone()))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(next.id, ids[2]);
+        let next = service
+            .next_uncoded_document(project_id, Some(ids[2].clone()))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(next.id, ids[1]);
+
+        let progress = service.get_coding_progress(project_id).await.unwrap();
+        assert_eq!(
+            (progress.coded, progress.total, progress.uncoded),
+            (1, 3, 2)
+        );
+        assert_eq!(progress.counts["designation"]["Privileged"], 1);
+        assert_eq!(progress.counts["designation"]["Relevant"], 0);
+
+        for id in &ids[1..3] {
+            service
+                .code_document(id, designation("Not Relevant", ""), Some("Ana".to_string()))
+                .await
+                .unwrap();
+        }
+        assert!(service
+            .next_uncoded_document(project_id, None)
+            .await
+            .unwrap()
+            .is_none());
+        let all = service.list_coding_progress().await.unwrap();
+        assert_eq!(all.len(), 1);
+        assert_eq!(all[0].percent, 100.0);
+    }
+}
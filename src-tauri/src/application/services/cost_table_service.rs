@@ -0,0 +1,402 @@
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use crate::application::dtos::{CostTableDto, CostTableExportDto, SaveCostTableRequest};
+use crate::application::services::export_service::write_atomically;
+use crate::application::services::{DerivationService, TOOL_VERSION};
+use crate::domain::cost_table::{CostTable, CostTableError, CostTableFormat};
+use crate::domain::det::{DetDocument, DetKind, DetStore, ProcessingMetadata, SourceReference};
+use crate::domain::document::{
+    DerivationStep, DerivativeFamily, Document, DocumentId, DocumentRepository, FamilyMember,
+};
+use crate::domain::project::ProjectRepository;
+use crate::infrastructure::{AppError, AppResult, CostTableCodecRegistry};
+
+/// Method recorded in the metadata of cost table documents
+const COST_TABLE_METHOD: &str = "cost-table-v1";
+
+/// Application service for `cost-table.det` derivatives
+///
+/// A cost table keeps its rows as structured data in the `.det` envelope;
+/// the editor content is a rendering of them, with totals, regenerated on
+/// every save. Tables are imported from and exported to CSV and XLSX so
+/// accountants can work on them in a spreadsheet. Exports go to the
+/// project's reports folder.
+pub struct CostTableService {
+    document_repository: Arc<dyn DocumentRepository>,
+    project_repository: Arc<dyn ProjectRepository>,
+    det_store: Arc<dyn DetStore>,
+    registry: Arc<CostTableCodecRegistry>,
+    derivatives_root: PathBuf,
+    derivation_service: Option<Arc<DerivationService>>,
+}
+
+impl CostTableService {
+    /// Create a new CostTableService for families below `derivatives_root`
+    pub fn new(
+        document_repository: Arc<dyn DocumentRepository>,
+        project_repository: Arc<dyn ProjectRepository>,
+        det_store: Arc<dyn DetStore>,
+        registry: Arc<CostTableCodecRegistry>,
+        derivatives_root: PathBuf,
+    ) -> Self {
+        CostTableService {
+            document_repository,
+            project_repository,
+            det_store,
+            registry,
+            derivatives_root,
+            derivation_service: None,
+        }
+    }
+
+    /// Record each save as a step in the document's derivation graph
+    pub fn with_derivation_service(mut self, derivation_service: Arc<DerivationService>) -> Self {
+        self.derivation_service = Some(derivation_service);
+        self
+    }
+
+    /// Load a document's cost table with its totals
+    pub async fn get_cost_table(
+        &self,
+        document_id: &str,
+        purpose: Option<&str>,
+    ) -> AppResult<CostTableDto> {
+        let document = self.load_document(document_id).await?;
+        let path = self.table_path(&document, purpose);
+        let table = self.load_table(&path).await?.ok_or_else(|| {
+            AppError::not_found(format!("Cost table '{}'", path.to_string_lossy()))
+        })?;
+        Ok(CostTableDto::new(
+            document_id,
+            path.to_string_lossy().to_string(),
+            &table,
+        ))
+    }
+
+    /// Create or replace a document's cost table
+    pub async fn save_cost_table(
+        &self,
+        document_id: &str,
+        request: SaveCostTableRequest,
+    ) -> AppResult<CostTableDto> {
+        let document = self.load_document(document_id).await?;
+        let table =
+            CostTable::new(&request.title, request.purpose.as_deref())?.with_rows(request.rows);
+        table.validate()?;
+        self.save_table(&document, &table, "edit").await
+    }
+
+    /// Replace the rows of a document's cost table with those of a CSV or
+    /// XLSX file
+    ///
+    /// The format is taken from the file extension. A new table is titled
+    /// after the document; an existing one keeps its title. Nothing is
+    /// written if any row is invalid.
+    pub async fn import_cost_table(
+        &self,
+        document_id: &str,
+        purpose: Option<&str>,
+        file_path: &str,
+    ) -> AppResult<CostTableDto> {
+        let document = self.load_document(document_id).await?;
+        let file_path = Path::new(file_path);
+        let extension = file_path
+            .extension()
+            .map(|e| e.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let format = CostTableFormat::parse(&extension)?;
+        let codec = self
+            .registry
+            .for_format(format)
+            .ok_or_else(|| CostTableError::UnsupportedFormat(format.to_string()))?;
+
+        let bytes = tokio::fs::read(file_path).await.map_err(|e| {
+            AppError::filesystem_error(format!("Cannot read {}: {}", file_path.display(), e))
+        })?;
+        let rows = tokio::task::spawn_blocking(move || codec.decode(&bytes))
+            .await
+            .map_err(|e| AppError::internal_error(format!("Import task failed: {}", e)))??;
+
+        let existing = self
+            .load_table(&self.table_path(&document, purpose))
+            .await?;
+        let title = match &existing {
+            Some(table) => table.title().to_string(),
+            None => document.file_name().unwrap_or("Costs").to_string(),
+        };
+        let table = CostTable::new(&title, purpose)?.with_rows(rows);
+        self.save_table(&document, &table, "import").await
+    }
+
+    /// Write a document's cost table as CSV or XLSX into the reports folder
+    ///
+    /// The file is named after the original and the table, e.g.
+    /// "invoice cost-table.xlsx"; an earlier export of the same name is
+    /// replaced.
+    pub async fn export_cost_table(
+        &self,
+        document_id: &str,
+        purpose: Option<&str>,
+        format: CostTableFormat,
+    ) -> AppResult<CostTableExportDto> {
+        let document = self.load_document(document_id).await?;
+        let codec = self
+            .registry
+            .for_format(format)
+            .ok_or_else(|| CostTableError::UnsupportedFormat(format.to_string()))?;
+        let path = self.table_path(&document, purpose);
+        let table = self.load_table(&path).await?.ok_or_else(|| {
+            AppError::not_found(format!("Cost table '{}'", path.to_string_lossy()))
+        })?;
+
+        let project = self
+            .project_repository
+            .find_by_id(document.project_id())
+            .await?
+            .ok_or_else(|| {
+                AppError::not_found(format!(
+                    "Project with ID '{}'",
+                    document.project_id().value()
+                ))
+            })?;
+        let original_stem = Path::new(document.path())
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let table_stem = table.file_name().trim_end_matches(".det").to_string();
+        let output_path = project.reports_folder().join(format!(
+            "{} {}.{}",
+            original_stem,
+            table_stem,
+            format.extension()
+        ));
+
+        let row_count = table.rows().len();
+        let target = output_path.clone();
+        let size_bytes = tokio::task::spawn_blocking(move || -> AppResult<u64> {
+            let bytes = codec.encode(&table)?;
+            write_atomically(&target, &bytes)?;
+            Ok(bytes.len() as u64)
+        })
+        .await
+        .map_err(|e| AppError::internal_error(format!("Export task failed: {}", e)))??;
+
+        tracing::debug!(
+            "Exported cost table of {} as {} to {}",
+            document.path(),
+            format,
+            output_path.display()
+        );
+
+        Ok(CostTableExportDto {
+            output_path: output_path.to_string_lossy().to_string(),
+            format: format.to_string(),
+            size_bytes,
+            row_count,
+            exported_at: chrono::Utc::now().to_rfc3339(),
+        })
+    }
+
+    async fn save_table(
+        &self,
+        document: &Document,
+        table: &CostTable,
+        operation: &str,
+    ) -> AppResult<CostTableDto> {
+        let processing = ProcessingMetadata::completed(COST_TABLE_METHOD)
+            .with_property("rows", table.rows().len())
+            .with_property("operation", operation);
+        let source = SourceReference::new(document.path())
+            .with_document_id(document.id().value())
+            .with_content_hash(document.content_hash().to_string());
+        let det = DetDocument::new(DetKind::CostTable, table.render(), processing)
+            .with_title(table.title())
+            .with_source(source)
+            .with_data(table.to_value());
+
+        let file_name = table.file_name();
+        let path = self.table_path(document, table.purpose());
+        self.det_store.save(&path, &det).await?;
+
+        if let Some(derivation_service) = &self.derivation_service {
+            let step = DerivationStep::new(
+                FamilyMember::Original,
+                FamilyMember::derivative(&file_name)?,
+                "cost-table",
+                serde_json::json!({
+                    "operation": operation,
+                    "rows": table.rows().len(),
+                }),
+                TOOL_VERSION,
+            )?;
+            derivation_service.record_step(document, step).await?;
+        }
+
+        Ok(CostTableDto::new(
+            document.id().value(),
+            path.to_string_lossy().to_string(),
+            table,
+        ))
+    }
+
+    /// The table at `path`, or None if it has not been created
+    async fn load_table(&self, path: &Path) -> AppResult<Option<CostTable>> {
+        if !path.exists() {
+            return Ok(None);
+        }
+        let det = self.det_store.load(path).await?;
+        if det.kind() != DetKind::CostTable {
+            return Err(AppError::validation_error(
+                "The file is not a cost table",
+                Some(path.to_string_lossy().to_string()),
+            ));
+        }
+        let data = det.data().cloned().ok_or_else(|| {
+            AppError::validation_error(
+                "The cost table has no structured rows",
+                Some(path.to_string_lossy().to_string()),
+            )
+        })?;
+        Ok(Some(CostTable::from_value(data)?))
+    }
+
+    fn table_path(&self, document: &Document, purpose: Option<&str>) -> PathBuf {
+        DerivativeFamily::new(&self.derivatives_root, document.project_id(), document.id())
+            .file(&CostTable::file_name_for(purpose))
+    }
+
+    async fn load_document(&self, document_id: &str) -> AppResult<Document> {
+        let id = DocumentId::from_string(document_id.to_string()).map_err(|e| {
+            AppError::validation_error("Invalid document ID format", Some(e.to_string()))
+        })?;
+
+        self.document_repository
+            .find_by_id(&id)
+            .await?
+            .ok_or_else(|| AppError::not_found(format!("Document with ID '{}'", document_id)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::cost_table::{Amount, CostCategory, CostRow, Currency};
+    use crate::domain::project::Project;
+    use crate::domain::workspace::value_objects::{ContentHash, HashAlgorithm};
+    use crate::infrastructure::{
+        DatabaseConnection, FileDetStore, SqliteDerivationRepository, SqliteDocumentRepository,
+        SqliteProjectRepository,
+    };
+    use chrono::NaiveDate;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_save_export_and_import_round_trip() {
+        let (database, _db_dir) = DatabaseConnection::new_temp().await.unwrap();
+        let documents = Arc::new(SqliteDocumentRepository::new(database.pool()));
+        let projects = Arc::new(SqliteProjectRepository::new(database.pool()));
+        let root = TempDir::new().unwrap();
+        let source = TempDir::new().unwrap();
+        let derivations = Arc::new(DerivationService::new(
+            documents.clone(),
+            Arc::new(SqliteDerivationRepository::new(database.pool())),
+            root.path().to_path_buf(),
+        ));
+        let service = CostTableService::new(
+            documents.clone(),
+            projects.clone(),
+            Arc::new(FileDetStore::new()),
+            Arc::new(CostTableCodecRegistry::with_defaults()),
+            root.path().to_path_buf(),
+        )
+        .with_derivation_service(derivations.clone());
+
+        let project = Project::new(
+            "Claim".to_string(),
+            source.path().to_string_lossy().to_string(),
+            None,
+        )
+        .unwrap();
+        projects.create(&project).await.unwrap();
+        let document = Document::new(
+            project.id().clone(),
+            source
+                .path()
+                .join("invoice.pdf")
+                .to_string_lossy()
+                .to_string(),
+            ContentHash::new(HashAlgorithm::default(), "00".repeat(32)).unwrap(),
+            0,
+            0,
+        );
+        documents.save(&document).await.unwrap();
+        let document_id = document.id().value();
+
+        let row = CostRow::new(
+            NaiveDate::from_ymd_opt(2024, 4, 2).unwrap(),
+            "Roof repair",
+            CostCategory::Direct,
+            Amount::from_cents(250_000),
+            Currency::parse("EUR").unwrap(),
+        )
+        .unwrap();
+        let saved = service
+            .save_cost_table(
+                document_id,
+                SaveCostTableRequest {
+                    title: "Storm damage".to_string(),
+                    purpose: None,
+                    rows: vec![row],
+                },
+            )
+            .await
+            .unwrap();
+        assert!(saved.det_path.ends_with("cost-table.det"));
+        assert_eq!(saved.grand_totals[0].amount, "2500.00");
+
+        let export = service
+            .export_cost_table(document_id, None, CostTableFormat::Xlsx)
+            .await
+            .unwrap();
+        assert!(export.output_path.ends_with("invoice cost-table.xlsx"));
+        assert_eq!(export.row_count, 1);
+
+        let csv = source.path().join("edited.csv");
+        std::fs::write(
+            &csv,
+            "date,description,category,amount,currency\n\
+             2024-04-02,Roof repair,direct,2500,EUR\n\
+             2024-04-03,Scaffolding,indirect,300.50,EUR\n",
+        )
+        .unwrap();
+        let imported = service
+            .import_cost_table(document_id, None, &csv.to_string_lossy())
+            .await
+            .unwrap();
+        assert_eq!(imported.title, "Storm damage");
+        assert_eq!(imported.totals.len(), 2);
+        assert_eq!(imported.grand_totals[0].amount, "2800.50");
+
+        let loaded = service.get_cost_table(document_id, None).await.unwrap();
+        assert_eq!(loaded, imported);
+
+        let graph = derivations.get_derivation_graph(document_id).await.unwrap();
+        assert_eq!(graph.edges.len(), 1);
+        assert_eq!(graph.edges[0].to, "cost-table.det");
+
+        std::fs::write(
+            &csv,
+            "date,description,category,amount,currency\n2024-04-02,Fee,travel,1,EUR\n",
+        )
+        .unwrap();
+        assert!(service
+            .import_cost_table(document_id, None, &csv.to_string_lossy())
+            .await
+            .is_err());
+        assert!(service
+            .get_cost_table(document_id, Some("other"))
+            .await
+            .is_err());
+    }
+}
@@ -0,0 +1,272 @@
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use crate::application::dtos::{DerivationEdgeDto, DerivationGraphDto, DerivationNodeDto};
+use crate::domain::document::{
+    DerivationRepository, DerivationStep, DerivativeFamily, Document, DocumentId,
+    DocumentRepository, FamilyMember,
+};
+use crate::infrastructure::{AppError, AppResult};
+
+/// Tool version recorded on steps performed by this application
+pub const TOOL_VERSION: &str = concat!(env!("CARGO_PKG_NAME"), " ", env!("CARGO_PKG_VERSION"));
+
+/// Application service for the processing chains of document families
+///
+/// Records which family member each derivative was produced from and
+/// serves the resulting graph for visualization. Staleness is judged
+/// against the files' modification times on disk, so edits made outside
+/// the application are noticed too.
+pub struct DerivationService {
+    document_repository: Arc<dyn DocumentRepository>,
+    derivation_repository: Arc<dyn DerivationRepository>,
+    derivatives_root: PathBuf,
+}
+
+impl DerivationService {
+    /// Create a new DerivationService for families below `derivatives_root`
+    pub fn new(
+        document_repository: Arc<dyn DocumentRepository>,
+        derivation_repository: Arc<dyn DerivationRepository>,
+        derivatives_root: PathBuf,
+    ) -> Self {
+        DerivationService {
+            document_repository,
+            derivation_repository,
+            derivatives_root,
+        }
+    }
+
+    /// Record a step in a document's family, rejecting it if it would
+    /// close a cycle
+    pub async fn record_step(&self, document: &Document, step: DerivationStep) -> AppResult<()> {
+        let mut graph = self
+            .derivation_repository
+            .find_by_document(document.project_id(), document.id())
+            .await?;
+        graph.record(step)?;
+        self.derivation_repository.save(&graph).await?;
+        Ok(())
+    }
+
+    /// Nodes and edges of a document's derivation graph
+    pub async fn get_derivation_graph(&self, document_id: &str) -> AppResult<DerivationGraphDto> {
+        let id = DocumentId::from_string(document_id.to_string()).map_err(|e| {
+            AppError::validation_error("Invalid document ID format", Some(e.to_string()))
+        })?;
+        let document = self
+            .document_repository
+            .find_by_id(&id)
+            .await?
+            .ok_or_else(|| AppError::not_found(format!("Document with ID '{}'", document_id)))?;
+
+        let graph = self
+            .derivation_repository
+            .find_by_document(document.project_id(), document.id())
+            .await?;
+        let family =
+            DerivativeFamily::new(&self.derivatives_root, document.project_id(), document.id());
+
+        let mut members = graph.members();
+        for file_name in Self::det_files(family.folder()).await {
+            if let Ok(member) = FamilyMember::derivative(file_name) {
+                if !members.contains(&member) {
+                    members.push(member);
+                }
+            }
+        }
+
+        let mut changed_at = HashMap::new();
+        let mut paths = HashMap::new();
+        for member in &members {
+            let path = match member {
+                FamilyMember::Original => PathBuf::from(document.path()),
+                FamilyMember::Derivative(file_name) => family.file(file_name),
+            };
+            match Self::modified_at(&path).await {
+                Some(modified) => {
+                    changed_at.insert(member.clone(), modified);
+                }
+                // A missing original still has the mtime it was last seen with
+                None if member.is_original() && !document.is_missing() => {
+                    changed_at.insert(
+                        member.clone(),
+                        DateTime::from_timestamp_nanos(document.modified_ns()),
+                    );
+                }
+                None => {}
+            }
+            paths.insert(member.clone(), path);
+        }
+        let stale = graph.stale_members(&changed_at);
+
+        let nodes = members
+            .iter()
+            .map(|member| {
+                let path = &paths[member];
+                let (label, kind) = match member {
+                    FamilyMember::Original => (
+                        document.file_name().unwrap_or(document.path()).to_string(),
+                        "original",
+                    ),
+                    FamilyMember::Derivative(file_name) => (file_name.clone(), "derivative"),
+                };
+                DerivationNodeDto {
+                    id: member.key().to_string(),
+                    label,
+                    kind: kind.to_string(),
+                    path: path.to_string_lossy().to_string(),
+                    exists: path.exists(),
+                    generation: graph.generation(member) as u32,
+                    stale: stale.contains(member),
+                }
+            })
+            .collect();
+
+        Ok(DerivationGraphDto {
+            document_id: document.id().value().to_string(),
+            project_id: document.project_id().value().to_string(),
+            nodes,
+            edges: graph.steps().iter().map(DerivationEdgeDto::from).collect(),
+        })
+    }
+
+    /// Names of the `.det` files in a family folder, skipping temporary files
+    pub(crate) async fn det_files(folder: &Path) -> Vec<String> {
+        let mut names = Vec::new();
+        let Ok(mut entries) = tokio::fs::read_dir(folder).await else {
+            return names;
+        };
+        while let Ok(Some(entry)) = entries.next_entry().await {
+            let name = entry.file_name().to_string_lossy().to_string();
+            if name.ends_with(".det") && !name.starts_with('.') {
+                names.push(name);
+            }
+        }
+        names.sort();
+        names
+    }
+
+    async fn modified_at(path: &Path) -> Option<DateTime<Utc>> {
+        let metadata = tokio::fs::metadata(path).await.ok()?;
+        metadata.modified().ok().map(DateTime::<Utc>::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::services::ExtractionService;
+    use crate::domain::workspace::value_objects::{ContentHash, HashAlgorithm};
+    use crate::infrastructure::{
+        DatabaseConnection, ExtractorRegistry, FileDetStore, SqliteDerivationRepository,
+        SqliteDocumentRepository,
+    };
+    use serde_json::json;
+    use std::fs;
+    use std::time::{Duration, SystemTime};
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_graph_tracks_extraction_and_flags_stale_derivatives() {
+        let (database, _db_dir) = DatabaseConnection::new_temp().await.unwrap();
+        let documents = Arc::new(SqliteDocumentRepository::new(database.pool()));
+        let derivatives = TempDir::new().unwrap();
+        let corpus = TempDir::new().unwrap();
+
+        let service = Arc::new(DerivationService::new(
+            documents.clone(),
+            Arc::new(SqliteDerivationRepository::new(database.pool())),
+            derivatives.path().to_path_buf(),
+        ));
+        let extraction = ExtractionService::new(
+            documents.clone(),
+            Arc::new(ExtractorRegistry::with_defaults()),
+            Arc::new(FileDetStore::new()),
+            derivatives.path().to_path_buf(),
+        )
+        .with_derivation_service(service.clone());
+
+        let contents = b"# Notes\n\nSome text.\n";
+        let path = corpus.path().join("notes.md");
+        fs::write(&path, contents).unwrap();
+        let document = Document::new(
+            crate::domain::project::ProjectId::new(),
+            path.to_string_lossy().to_string(),
+            ContentHash::of_bytes(HashAlgorithm::Blake3, contents),
+            contents.len() as u64,
+            0,
+        );
+        documents.save(&document).await.unwrap();
+        extraction
+            .extract_document(document.id().value())
+            .await
+            .unwrap();
+
+        let summary = FamilyMember::derivative("summary.det").unwrap();
+        service
+            .record_step(
+                &document,
+                DerivationStep::new(
+                    FamilyMember::extracted(),
+                    summary.clone(),
+                    "summary",
+                    json!({}),
+                    "test",
+                )
+                .unwrap(),
+            )
+            .await
+            .unwrap();
+        let cycle = DerivationStep::new(
+            summary,
+            FamilyMember::extracted(),
+            "re-extract",
+            json!({}),
+            "test",
+        )
+        .unwrap();
+        assert!(service.record_step(&document, cycle).await.is_err());
+
+        let family =
+            DerivativeFamily::new(derivatives.path(), document.project_id(), document.id());
+        fs::write(family.file("notes.det"), b"{}").unwrap();
+
+        let graph = service
+            .get_derivation_graph(document.id().value())
+            .await
+            .unwrap();
+        let ids: Vec<&str> = graph.nodes.iter().map(|n| n.id.as_str()).collect();
+        assert_eq!(
+            ids,
+            vec!["original", "extracted.det", "summary.det", "notes.det"]
+        );
+        assert_eq!(graph.edges.len(), 2);
+        assert_eq!(graph.edges[0].operation, "extraction");
+        assert_eq!(graph.nodes[0].label, "notes.md");
+        assert!(!graph.nodes[2].exists);
+        assert_eq!(graph.nodes[2].generation, 2);
+        assert!(graph.nodes.iter().all(|n| !n.stale));
+
+        // Editing the original afterwards makes the whole chain stale
+        fs::File::options()
+            .write(true)
+            .open(&path)
+            .unwrap()
+            .set_modified(SystemTime::now() + Duration::from_secs(60))
+            .unwrap();
+        let graph = service
+            .get_derivation_graph(document.id().value())
+            .await
+            .unwrap();
+        let stale: Vec<&str> = graph
+            .nodes
+            .iter()
+            .filter(|n| n.stale)
+            .map(|n| n.id.as_str())
+            .collect();
+        assert_eq!(stale, vec!["extracted.det", "summary.det"]);
+    }
+}
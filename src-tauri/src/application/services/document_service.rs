@@ -0,0 +1,629 @@
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use crate::application::dtos::{DocumentDto, DocumentReconciliationDto, RelinkedDocumentDto};
+use crate::application::jobs::{JobContext, JobHandler};
+use crate::application::services::hashing_service::{list_files, FileStamp};
+use crate::application::services::project_service::{
+    load_accessible_project, output_folders, parse_project_id,
+};
+use crate::application::services::{CitationService, HashingService, SearchIndexService};
+use crate::domain::document::{Document, DocumentId, DocumentRepository};
+use crate::domain::project::{ProjectId, ProjectRepository};
+use crate::domain::workspace::value_objects::{ContentHash, HashAlgorithm};
+use crate::infrastructure::{AppError, AppResult};
+
+/// Job kind for background document reconciliation
+pub const RECONCILE_DOCUMENTS_JOB: &str = "reconcile_documents";
+
+/// Application service for stable document identities
+///
+/// Every file in a project's source folder is tracked as a `Document` with
+/// an identifier that does not depend on its path. Reconciliation compares
+/// the tracked documents with the folder: files at a known path keep their
+/// document, files at a new path are matched by content hash against
+/// documents whose file disappeared, and only unmatched files get a new
+/// identifier.
+pub struct DocumentService {
+    project_repository: Arc<dyn ProjectRepository>,
+    document_repository: Arc<dyn DocumentRepository>,
+    hashing_service: Arc<HashingService>,
+    citation_service: Option<Arc<CitationService>>,
+    search_index: Option<Arc<SearchIndexService>>,
+}
+
+impl DocumentService {
+    /// Create a new DocumentService
+    pub fn new(
+        project_repository: Arc<dyn ProjectRepository>,
+        document_repository: Arc<dyn DocumentRepository>,
+        hashing_service: Arc<HashingService>,
+    ) -> Self {
+        DocumentService {
+            project_repository,
+            document_repository,
+            hashing_service,
+            citation_service: None,
+            search_index: None,
+        }
+    }
+
+    /// Check report citations whenever reconciliation changes documents
+    pub fn with_citation_service(mut self, citation_service: Arc<CitationService>) -> Self {
+        self.citation_service = Some(citation_service);
+        self
+    }
+
+    /// Refresh the project's search index after each reconciliation
+    pub fn with_search_index(mut self, search_index: Arc<SearchIndexService>) -> Self {
+        self.search_index = Some(search_index);
+        self
+    }
+
+    /// Reconcile a project's documents with its source folder
+    pub async fn reconcile_project(
+        &self,
+        project_id: &str,
+        context: Option<&JobContext>,
+    ) -> AppResult<DocumentReconciliationDto> {
+        let project = load_accessible_project(self.project_repository.as_ref(), project_id).await?;
+        let id = project.id().clone();
+
+        let mut result = self
+            .reconcile_folder(
+                &id,
+                project.source_folder().value(),
+                &output_folders(&project),
+                context,
+            )
+            .await?;
+
+        if let (true, Some(citation_service)) = (result.has_changes(), &self.citation_service) {
+            match citation_service.check_project(project_id).await {
+                Ok(check) => result.broken_citations = check.broken.len() as u64,
+                Err(error) => tracing::warn!(
+                    "Citation check after reconciling {} failed: {}",
+                    project_id,
+                    error.user_message()
+                ),
+            }
+        }
+        if let Some(search_index) = &self.search_index {
+            if let Err(error) = search_index.refresh_project(&project).await {
+                tracing::warn!(
+                    "Refreshing the search index of {} failed: {}",
+                    project_id,
+                    error.user_message()
+                );
+            }
+        }
+        Ok(result)
+    }
+
+    /// Reconcile the documents of a project with the files below `folder`
+    pub async fn reconcile_folder(
+        &self,
+        project_id: &ProjectId,
+        folder: &Path,
+        excluded: &[PathBuf],
+        context: Option<&JobContext>,
+    ) -> AppResult<DocumentReconciliationDto> {
+        let files = {
+            let folder = folder.to_path_buf();
+            let excluded = excluded.to_vec();
+            tokio::task::spawn_blocking(move || list_files(&folder, &excluded))
+                .await
+                .map_err(|e| AppError::internal_error(format!("File scan failed: {}", e)))?
+        };
+
+        let mut result = DocumentReconciliationDto::empty(project_id.value().to_string());
+        result.files_scanned = files.len() as u64;
+
+        // Documents by current path; previously missing ones can only be
+        // found again by content
+        let mut by_path: HashMap<String, Document> = HashMap::new();
+        let mut vanished: Vec<Document> = Vec::new();
+        for document in self.document_repository.list_by_project(project_id).await? {
+            // Child documents live outside the source folder
+            if document.parent_id().is_some() {
+                continue;
+            }
+            if document.is_missing() || by_path.contains_key(document.path()) {
+                vanished.push(document);
+            } else {
+                by_path.insert(document.path().to_string(), document);
+            }
+        }
+
+        let mut changed: Vec<Document> = Vec::new();
+        let mut unmatched: Vec<(PathBuf, FileStamp)> = Vec::new();
+        let total = files.len() as u64;
+        let mut processed = 0;
+
+        for (path, stamp) in files {
+            let path_str = path.to_string_lossy().to_string();
+            let Some(mut document) = by_path.remove(&path_str) else {
+                unmatched.push((path, stamp));
+                continue;
+            };
+
+            processed += 1;
+            if document.is_current(stamp.size, stamp.modified_ns) {
+                result.unchanged += 1;
+                continue;
+            }
+
+            Self::report_progress(context, processed, total).await?;
+            let Some(hash) = self.hash(&path).await else {
+                result.unchanged += 1;
+                continue;
+            };
+            if &hash == document.content_hash() {
+                // Touched but not edited
+                result.unchanged += 1;
+            } else {
+                result.updated += 1;
+            }
+            document.update_contents(hash, stamp.size, stamp.modified_ns);
+            changed.push(document);
+        }
+
+        // Anything still in `by_path` is no longer at its recorded path
+        vanished.extend(by_path.into_values());
+        let mut vanished_by_hash: HashMap<ContentHash, Vec<Document>> = HashMap::new();
+        for document in vanished {
+            vanished_by_hash
+                .entry(document.content_hash().clone())
+                .or_default()
+                .push(document);
+        }
+
+        for (path, stamp) in unmatched {
+            processed += 1;
+            Self::report_progress(context, processed, total).await?;
+
+            let Some(hash) = self.hash(&path).await else {
+                continue;
+            };
+            let path_str = path.to_string_lossy().to_string();
+
+            match vanished_by_hash
+                .get_mut(&hash)
+                .and_then(|candidates| take_best_candidate(candidates, &path))
+            {
+                Some(mut document) => {
+                    result.relinked.push(RelinkedDocumentDto {
+                        document_id: document.id().value().to_string(),
+                        from_path: document.path().to_string(),
+                        to_path: path_str.clone(),
+                    });
+                    document.relink(path_str, stamp.size, stamp.modified_ns);
+                    changed.push(document);
+                }
+                None => {
+                    result.created += 1;
+                    changed.push(Document::new(
+                        project_id.clone(),
+                        path_str,
+                        hash,
+                        stamp.size,
+                        stamp.modified_ns,
+                    ));
+                }
+            }
+        }
+
+        for mut document in vanished_by_hash.into_values().flatten() {
+            result.missing += 1;
+            if !document.is_missing() {
+                document.mark_missing();
+                changed.push(document);
+            }
+        }
+
+        self.document_repository.save_all(&changed).await?;
+
+        if let Some(context) = context {
+            context.report_progress(total, Some(total), None).await;
+        }
+
+        tracing::debug!(
+            "Reconciled documents for {}: {} created, {} updated, {} relinked, {} missing",
+            project_id,
+            result.created,
+            result.updated,
+            result.relinked.len(),
+            result.missing
+        );
+
+        result.reconciled_at = chrono::Utc::now().to_rfc3339();
+        Ok(result)
+    }
+
+    /// List the documents of a project
+    pub async fn list_documents(
+        &self,
+        project_id: &str,
+        include_missing: bool,
+    ) -> AppResult<Vec<DocumentDto>> {
+        let id = parse_project_id(project_id)?;
+
+        Ok(self
+            .document_repository
+            .list_by_project(&id)
+            .await?
+            .iter()
+            .filter(|document| include_missing || !document.is_missing())
+            .map(DocumentDto::from)
+            .collect())
+    }
+
+    /// Get a document by its identifier
+    pub async fn get_document(&self, document_id: &str) -> AppResult<DocumentDto> {
+        let id = DocumentId::from_string(document_id.to_string()).map_err(|e| {
+            AppError::validation_error("Invalid document ID format", Some(e.to_string()))
+        })?;
+
+        self.document_repository
+            .find_by_id(&id)
+            .await?
+            .as_ref()
+            .map(DocumentDto::from)
+            .ok_or_else(|| AppError::not_found(format!("Document with ID '{}'", document_id)))
+    }
+
+    /// Find the document currently at a path
+    pub async fn find_document_by_path(
+        &self,
+        project_id: &str,
+        path: &str,
+    ) -> AppResult<Option<DocumentDto>> {
+        let id = parse_project_id(project_id)?;
+
+        Ok(self
+            .document_repository
+            .find_by_path(&id, path)
+            .await?
+            .as_ref()
+            .map(DocumentDto::from))
+    }
+
+    /// Hash a file, logging and skipping files that cannot be read
+    async fn hash(&self, path: &Path) -> Option<ContentHash> {
+        match self
+            .hashing_service
+            .hash_file(path, HashAlgorithm::default())
+            .await
+        {
+            Ok(hash) => Some(hash),
+            Err(error) => {
+                tracing::warn!(
+                    "Skipping {} during reconciliation: {}",
+                    path.display(),
+                    error.user_message()
+                );
+                None
+            }
+        }
+    }
+
+    async fn report_progress(
+        context: Option<&JobContext>,
+        processed: u64,
+        total: u64,
+    ) -> AppResult<()> {
+        if let Some(context) = context {
+            context.check_cancelled()?;
+            context
+                .report_progress(processed, Some(total), Some("Reconciling documents"))
+                .await;
+        }
+        Ok(())
+    }
+}
+
+/// Pick the vanished document that best matches a newly found file
+///
+/// Prefers a document with the same file name (a move), then falls back to
+/// any document with identical contents (a rename).
+fn take_best_candidate(candidates: &mut Vec<Document>, path: &Path) -> Option<Document> {
+    if candidates.is_empty() {
+        return None;
+    }
+
+    let file_name = path.file_name().and_then(|n| n.to_str());
+    let index = candidates
+        .iter()
+        .position(|document| document.file_name() == file_name)
+        .unwrap_or(0);
+
+    Some(candidates.swap_remove(index))
+}
+
+/// Runs document reconciliation on the background job pool
+///
+/// Takes no parameters and returns a serialized `DocumentReconciliationDto`.
+pub struct ReconcileDocumentsJobHandler {
+    service: Arc<DocumentService>,
+}
+
+impl ReconcileDocumentsJobHandler {
+    pub fn new(service: Arc<DocumentService>) -> Self {
+        ReconcileDocumentsJobHandler { service }
+    }
+}
+
+#[async_trait]
+impl JobHandler for ReconcileDocumentsJobHandler {
+    fn kind(&self) -> &'static str {
+        RECONCILE_DOCUMENTS_JOB
+    }
+
+    fn is_idempotent(&self) -> bool {
+        true
+    }
+
+    async fn run(
+        &self,
+        context: JobContext,
+        _params: serde_json::Value,
+    ) -> AppResult<serde_json::Value> {
+        let project_id = context
+            .project_id()
+            .ok_or_else(|| AppError::validation_error("Reconciliation requires a project", None))?
+            .to_string();
+
+        let result = self
+            .service
+            .reconcile_project(&project_id, Some(&context))
+            .await?;
+
+        serde_json::to_value(result)
+            .map_err(|e| AppError::internal_error(format!("Failed to serialize result: {}", e)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::anonymization::{PseudonymMap, PseudonymMapRepository};
+    use crate::domain::project::repositories::MockProjectRepository;
+    use crate::domain::project::Project;
+    use crate::infrastructure::{
+        DatabaseConnection, FilePseudonymMapRepository, SqliteDocumentRepository,
+        SqliteFileHashRepository,
+    };
+    use std::fs;
+    use tempfile::TempDir;
+
+    async fn create_test_service() -> (DocumentService, TempDir) {
+        let (database, temp_dir) = DatabaseConnection::new_temp().await.unwrap();
+        let project_repository = Arc::new(MockProjectRepository::new());
+        let hashing_service = Arc::new(HashingService::new(
+            project_repository.clone(),
+            Arc::new(SqliteFileHashRepository::new(database.pool())),
+        ));
+        let service = DocumentService::new(
+            project_repository,
+            Arc::new(SqliteDocumentRepository::new(database.pool())),
+            hashing_service,
+        );
+        (service, temp_dir)
+    }
+
+    fn create_test_corpus() -> TempDir {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+
+        fs::write(root.join("offer.pdf"), b"offer letter").unwrap();
+        fs::write(root.join("invoice.pdf"), b"invoice 42").unwrap();
+        fs::create_dir(root.join("archive")).unwrap();
+
+        temp_dir
+    }
+
+    fn id_for(documents: &[DocumentDto], path: &Path) -> String {
+        documents
+            .iter()
+            .find(|d| d.path == path.to_string_lossy())
+            .map(|d| d.id.clone())
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_initial_reconciliation_creates_documents() {
+        let (service, _db_dir) = create_test_service().await;
+        let corpus = create_test_corpus();
+        let project_id = ProjectId::new();
+
+        let result = service
+            .reconcile_folder(&project_id, corpus.path(), &[], None)
+            .await
+            .unwrap();
+        assert_eq!(result.created, 2);
+        assert!(result.has_changes());
+
+        let again = service
+            .reconcile_folder(&project_id, corpus.path(), &[], None)
+            .await
+            .unwrap();
+        assert_eq!(again.unchanged, 2);
+        assert!(!again.has_changes());
+    }
+
+    #[tokio::test]
+    async fn test_moved_and_renamed_files_keep_their_id() {
+        let (service, _db_dir) = create_test_service().await;
+        let corpus = create_test_corpus();
+        let root = corpus.path();
+        let project_id = ProjectId::new();
+
+        service
+            .reconcile_folder(&project_id, root, &[], None)
+            .await
+            .unwrap();
+        let before = service
+            .list_documents(project_id.value(), false)
+            .await
+            .unwrap();
+        let offer_id = id_for(&before, &root.join("offer.pdf"));
+        let invoice_id = id_for(&before, &root.join("invoice.pdf"));
+
+        fs::rename(
+            root.join("offer.pdf"),
+            root.join("archive").join("offer.pdf"),
+        )
+        .unwrap();
+        fs::rename(root.join("invoice.pdf"), root.join("invoice-2024.pdf")).unwrap();
+
+        let result = service
+            .reconcile_folder(&project_id, root, &[], None)
+            .await
+            .unwrap();
+        assert_eq!(result.relinked.len(), 2);
+        assert_eq!(result.created, 0);
+        assert_eq!(result.missing, 0);
+
+        let after = service
+            .list_documents(project_id.value(), false)
+            .await
+            .unwrap();
+        assert_eq!(
+            id_for(&after, &root.join("archive").join("offer.pdf")),
+            offer_id
+        );
+        assert_eq!(id_for(&after, &root.join("invoice-2024.pdf")), invoice_id);
+    }
+
+    #[tokio::test]
+    async fn test_deleted_file_is_missing_until_it_reappears() {
+        let (service, _db_dir) = create_test_service().await;
+        let corpus = create_test_corpus();
+        let root = corpus.path();
+        let project_id = ProjectId::new();
+
+        service
+            .reconcile_folder(&project_id, root, &[], None)
+            .await
+            .unwrap();
+        let offer = service
+            .find_document_by_path(
+                project_id.value(),
+                &root.join("offer.pdf").to_string_lossy(),
+            )
+            .await
+            .unwrap()
+            .unwrap();
+
+        let contents = fs::read(root.join("offer.pdf")).unwrap();
+        fs::remove_file(root.join("offer.pdf")).unwrap();
+
+        let result = service
+            .reconcile_folder(&project_id, root, &[], None)
+            .await
+            .unwrap();
+        assert_eq!(result.missing, 1);
+        assert!(service.get_document(&offer.id).await.unwrap().missing);
+        assert_eq!(
+            service
+                .list_documents(project_id.value(), false)
+                .await
+                .unwrap()
+                .len(),
+            1
+        );
+
+        fs::write(root.join("archive").join("restored.pdf"), contents).unwrap();
+
+        let result = service
+            .reconcile_folder(&project_id, root, &[], None)
+            .await
+            .unwrap();
+        assert_eq!(result.relinked.len(), 1);
+        assert_eq!(result.relinked[0].document_id, offer.id);
+        assert!(!service.get_document(&offer.id).await.unwrap().missing);
+    }
+
+    #[tokio::test]
+    async fn test_edited_file_keeps_id_and_updates_hash() {
+        let (service, _db_dir) = create_test_service().await;
+        let corpus = create_test_corpus();
+        let root = corpus.path();
+        let project_id = ProjectId::new();
+
+        service
+            .reconcile_folder(&project_id, root, &[], None)
+            .await
+            .unwrap();
+        let path = root.join("invoice.pdf").to_string_lossy().to_string();
+        let before = service
+            .find_document_by_path(project_id.value(), &path)
+            .await
+            .unwrap()
+            .unwrap();
+
+        fs::write(root.join("invoice.pdf"), b"invoice 42, corrected").unwrap();
+
+        let result = service
+            .reconcile_folder(&project_id, root, &[], None)
+            .await
+            .unwrap();
+        assert_eq!(result.updated, 1);
+
+        let after = service
+            .find_document_by_path(project_id.value(), &path)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(after.id, before.id);
+        assert_ne!(after.content_hash, before.content_hash);
+    }
+
+    #[tokio::test]
+    async fn test_pseudonym_map_never_becomes_a_document() {
+        let (database, _db_dir) = DatabaseConnection::new_temp().await.unwrap();
+        let corpus = create_test_corpus();
+        let projects = Arc::new(MockProjectRepository::new());
+        let project = Project::new(
+            "Private".to_string(),
+            corpus.path().to_string_lossy().to_string(),
+            None,
+        )
+        .unwrap();
+        projects.create(&project).await.unwrap();
+        let service = DocumentService::new(
+            projects.clone(),
+            Arc::new(SqliteDocumentRepository::new(database.pool())),
+            Arc::new(HashingService::new(
+                projects.clone(),
+                Arc::new(SqliteFileHashRepository::new(database.pool())),
+            )),
+        );
+
+        let mut map = PseudonymMap::new();
+        map.pseudonym_for("PERSON", "Jane Doe");
+        FilePseudonymMapRepository::new(projects)
+            .save(project.id(), &map)
+            .await
+            .unwrap();
+        let map_path = FilePseudonymMapRepository::path_for(&project);
+        assert!(map_path.starts_with(project.source_folder().value()));
+
+        let result = service
+            .reconcile_project(project.id().value(), None)
+            .await
+            .unwrap();
+        assert_eq!(result.files_scanned, 2);
+        assert_eq!(result.created, 2);
+
+        let documents = service
+            .list_documents(project.id().value(), false)
+            .await
+            .unwrap();
+        assert!(documents
+            .iter()
+            .all(|document| Path::new(&document.path) != map_path));
+    }
+}
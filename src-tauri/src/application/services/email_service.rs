@@ -0,0 +1,658 @@
+use async_trait::async_trait;
+use std::collections::{HashMap, VecDeque};
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use crate::application::dtos::{
+    DirectoryListingDto, EmailAttachmentDto, EmailMessageDto, EmailParseBatchDto,
+    EmailParseFailureDto, EmailThreadDto, FileEntryDto,
+};
+use crate::application::jobs::{JobContext, JobHandler};
+use crate::application::services::project_service::parse_project_id;
+use crate::domain::document::{DerivativeFamily, Document, DocumentId, DocumentRepository};
+use crate::domain::email::{
+    reconstruct_threads, EmailError, EmailMessage, EmailRepository, EmailThread, ParsedEmail,
+};
+use crate::domain::project::ProjectId;
+use crate::domain::workspace::value_objects::{ContentHash, HashAlgorithm};
+use crate::infrastructure::{AppError, AppResult, EmailParser};
+
+/// Job kind for parsing all email documents of a project in the background
+pub const PARSE_EMAILS_JOB: &str = "parse_emails";
+
+/// Depth up to which emails attached to emails are parsed in turn
+const MAX_NESTING: usize = 5;
+
+/// Application service for email messages and their attachments
+///
+/// Reads the headers of tracked EML, MBOX and MSG documents and stores them
+/// for threading and searching. Attachments are written to the email's
+/// derivatives folder and tracked as child documents, so they can be
+/// extracted, coded and searched like any other file; attached emails are
+/// parsed in turn. Messages keep the content hash they were read from, so a
+/// project run only parses files whose contents changed.
+pub struct EmailService {
+    document_repository: Arc<dyn DocumentRepository>,
+    email_repository: Arc<dyn EmailRepository>,
+    derivatives_root: PathBuf,
+}
+
+impl EmailService {
+    /// Create a new EmailService writing attachments below
+    /// `derivatives_root`
+    pub fn new(
+        document_repository: Arc<dyn DocumentRepository>,
+        email_repository: Arc<dyn EmailRepository>,
+        derivatives_root: PathBuf,
+    ) -> Self {
+        EmailService {
+            document_repository,
+            email_repository,
+            derivatives_root,
+        }
+    }
+
+    /// Whether a path names an email file, judged by its extension
+    pub fn is_email_path(path: &str) -> bool {
+        Path::new(path)
+            .extension()
+            .and_then(|e| e.to_str())
+            .is_some_and(EmailParser::supports)
+    }
+
+    /// Parse one document, replacing its messages and attachments
+    pub async fn parse_document(&self, document_id: &str) -> AppResult<Vec<EmailMessageDto>> {
+        let document = self.find_document(document_id).await?;
+        if !Self::is_email_path(document.path()) {
+            return Err(EmailError::unsupported(extension(document.path())).into());
+        }
+        self.parse(document).await
+    }
+
+    /// Parse every present email document of a project
+    ///
+    /// Documents whose stored messages were read from the current contents
+    /// are skipped unless `force` is set. Failures are collected per file.
+    pub async fn parse_project(
+        &self,
+        project_id: &str,
+        force: bool,
+        context: Option<&JobContext>,
+    ) -> AppResult<EmailParseBatchDto> {
+        let id = parse_project_id(project_id)?;
+
+        let documents: Vec<Document> = self
+            .document_repository
+            .list_by_project(&id)
+            .await?
+            .into_iter()
+            .filter(|document| !document.is_missing())
+            .collect();
+        let total = documents.len() as u64;
+
+        let mut batch = EmailParseBatchDto {
+            project_id: project_id.to_string(),
+            ..EmailParseBatchDto::default()
+        };
+
+        for (index, document) in documents.into_iter().enumerate() {
+            if let Some(context) = context {
+                context.check_cancelled()?;
+                context
+                    .report_progress(index as u64, Some(total), Some("Reading email"))
+                    .await;
+            }
+
+            if !Self::is_email_path(document.path()) {
+                batch.unsupported += 1;
+                continue;
+            }
+            if !force && self.is_up_to_date(&document).await? {
+                batch.up_to_date += 1;
+                continue;
+            }
+
+            let (document_id, path) = (document.id().clone(), document.path().to_string());
+            match self.parse(document).await {
+                Ok(messages) => batch.results.extend(messages),
+                Err(error) => {
+                    tracing::warn!("Parsing {} failed: {}", path, error.user_message());
+                    batch.failed.push(EmailParseFailureDto {
+                        document_id: document_id.value().to_string(),
+                        path,
+                        error: error.user_message(),
+                    });
+                }
+            }
+        }
+
+        if let Some(context) = context {
+            context.report_progress(total, Some(total), None).await;
+        }
+
+        Ok(batch)
+    }
+
+    /// Stored messages of a document, empty if it has not been parsed
+    pub async fn get_email(&self, document_id: &str) -> AppResult<Vec<EmailMessageDto>> {
+        let document = self.find_document(document_id).await?;
+        let messages = self
+            .email_repository
+            .find_by_document(document.id())
+            .await?;
+        let documents = self.documents_by_id(document.project_id()).await?;
+
+        Ok(messages
+            .iter()
+            .map(|message| message_dto(message, &documents))
+            .collect())
+    }
+
+    /// All conversations of a project, most recently active first
+    pub async fn list_threads(&self, project_id: &str) -> AppResult<Vec<EmailThreadDto>> {
+        let id = parse_project_id(project_id)?;
+        let documents = self.documents_by_id(&id).await?;
+
+        Ok(self
+            .threads(&id, &documents)
+            .await?
+            .iter()
+            .map(|thread| thread_dto(thread, &documents))
+            .collect())
+    }
+
+    /// The conversation a message belongs to
+    pub async fn get_thread(
+        &self,
+        document_id: &str,
+        message_index: u32,
+    ) -> AppResult<EmailThreadDto> {
+        let document = self.find_document(document_id).await?;
+        let documents = self.documents_by_id(document.project_id()).await?;
+        let key = format!("{}#{}", document.id(), message_index);
+
+        self.threads(document.project_id(), &documents)
+            .await?
+            .iter()
+            .find(|thread| thread.messages.iter().any(|m| m.key() == key))
+            .map(|thread| thread_dto(thread, &documents))
+            .ok_or_else(|| {
+                AppError::not_found(format!(
+                    "Message {} of document '{}'",
+                    message_index, document_id
+                ))
+            })
+    }
+
+    /// The attachments of an email file, listed like a folder
+    ///
+    /// Returns `None` when the path is not a parsed email of the project.
+    pub async fn attachment_listing(
+        &self,
+        project_id: &str,
+        path: &str,
+    ) -> AppResult<Option<DirectoryListingDto>> {
+        let id = parse_project_id(project_id)?;
+        let Some(email) = self.document_repository.find_by_path(&id, path).await? else {
+            return Ok(None);
+        };
+        let messages = self.email_repository.find_by_document(email.id()).await?;
+        if messages.is_empty() {
+            return Ok(None);
+        }
+        let documents = self.documents_by_id(&id).await?;
+
+        let entries = messages
+            .iter()
+            .flat_map(EmailMessage::attachment_ids)
+            .filter_map(|id| documents.get(id))
+            .filter(|child| !child.is_missing())
+            .map(|child| {
+                FileEntryDto::file(
+                    child.file_name().unwrap_or(child.path()).to_string(),
+                    child.path().to_string(),
+                    Some(child.size()),
+                    child.updated_at().to_rfc3339(),
+                )
+            })
+            .collect();
+        let parent_path = Path::new(path)
+            .parent()
+            .map(|parent| parent.to_string_lossy().to_string());
+
+        Ok(Some(
+            DirectoryListingDto::new(entries, false, parent_path, true)
+                .with_email(email.id().value().to_string()),
+        ))
+    }
+
+    /// Number of present attachments of each parsed email, by email path
+    pub async fn attachment_counts(&self, project_id: &str) -> AppResult<HashMap<String, usize>> {
+        let id = parse_project_id(project_id)?;
+        let documents = self.document_repository.list_by_project(&id).await?;
+        let paths: HashMap<&DocumentId, &str> = documents
+            .iter()
+            .map(|document| (document.id(), document.path()))
+            .collect();
+
+        let mut counts = HashMap::new();
+        for child in documents.iter().filter(|child| !child.is_missing()) {
+            if let Some(path) = child.parent_id().and_then(|parent| paths.get(parent)) {
+                *counts.entry(path.to_string()).or_insert(0) += 1;
+            }
+        }
+        Ok(counts)
+    }
+
+    async fn find_document(&self, document_id: &str) -> AppResult<Document> {
+        let id = DocumentId::from_string(document_id.to_string()).map_err(|e| {
+            AppError::validation_error("Invalid document ID format", Some(e.to_string()))
+        })?;
+
+        self.document_repository
+            .find_by_id(&id)
+            .await?
+            .ok_or_else(|| AppError::not_found(format!("Document with ID '{}'", document_id)))
+    }
+
+    async fn documents_by_id(
+        &self,
+        project_id: &ProjectId,
+    ) -> AppResult<HashMap<DocumentId, Document>> {
+        Ok(self
+            .document_repository
+            .list_by_project(project_id)
+            .await?
+            .into_iter()
+            .map(|document| (document.id().clone(), document))
+            .collect())
+    }
+
+    /// Threads of the messages of present documents
+    async fn threads(
+        &self,
+        project_id: &ProjectId,
+        documents: &HashMap<DocumentId, Document>,
+    ) -> AppResult<Vec<EmailThread>> {
+        let messages = self
+            .email_repository
+            .list_by_project(project_id)
+            .await?
+            .into_iter()
+            .filter(|message| {
+                documents
+                    .get(message.document_id())
+                    .is_some_and(|document| !document.is_missing())
+            })
+            .collect();
+        Ok(reconstruct_threads(messages))
+    }
+
+    /// Whether the stored messages were read from the current contents
+    async fn is_up_to_date(&self, document: &Document) -> AppResult<bool> {
+        let messages = self
+            .email_repository
+            .find_by_document(document.id())
+            .await?;
+        let hash = document.content_hash().to_string();
+        Ok(!messages.is_empty() && messages.iter().all(|message| message.is_current(&hash)))
+    }
+
+    /// Parse an email document and, in turn, the emails attached to it
+    ///
+    /// Returns the messages of all of them. Only a failure of the document
+    /// itself is an error; attached emails that cannot be read are skipped.
+    async fn parse(&self, document: Document) -> AppResult<Vec<EmailMessageDto>> {
+        let mut results = Vec::new();
+        let mut queue = VecDeque::from([(document, 0)]);
+
+        while let Some((document, depth)) = queue.pop_front() {
+            let (messages, children) = match self.parse_file(&document).await {
+                Ok(parsed) => parsed,
+                Err(error) if depth > 0 => {
+                    tracing::warn!(
+                        "Skipping attached email {}: {}",
+                        document.path(),
+                        error.user_message()
+                    );
+                    continue;
+                }
+                Err(error) => return Err(error),
+            };
+
+            let documents: HashMap<DocumentId, Document> = children
+                .iter()
+                .map(|child| (child.id().clone(), child.clone()))
+                .collect();
+            results.extend(
+                messages
+                    .iter()
+                    .map(|message| message_dto(message, &documents)),
+            );
+
+            if depth < MAX_NESTING {
+                for child in children {
+                    if !child.is_missing()
+                        && Self::is_email_path(child.path())
+                        && !self.is_up_to_date(&child).await?
+                    {
+                        queue.push_back((child, depth + 1));
+                    }
+                }
+            }
+        }
+
+        Ok(results)
+    }
+
+    /// Parse one file, storing its messages and attachment documents
+    ///
+    /// Returns the messages and the email's child documents, including
+    /// earlier attachments that are now gone and marked missing.
+    async fn parse_file(
+        &self,
+        document: &Document,
+    ) -> AppResult<(Vec<EmailMessage>, Vec<Document>)> {
+        let path = document.path().to_string();
+        let parsed = tokio::task::spawn_blocking(move || -> AppResult<Vec<ParsedEmail>> {
+            let bytes = std::fs::read(&path)
+                .map_err(|e| AppError::filesystem_error(format!("Cannot read {}: {}", path, e)))?;
+            Ok(EmailParser::parse(&bytes)?)
+        })
+        .await
+        .map_err(|e| AppError::internal_error(format!("Email parsing task failed: {}", e)))??;
+
+        let io_error = |path: &Path, e: std::io::Error| {
+            AppError::filesystem_error(format!("Cannot write {}: {}", path.display(), e))
+        };
+        let family =
+            DerivativeFamily::new(&self.derivatives_root, document.project_id(), document.id());
+
+        // Attachments are written afresh, so none of an earlier version of
+        // the file is left behind
+        let folder = family.attachments_folder();
+        match tokio::fs::remove_dir_all(&folder).await {
+            Err(e) if e.kind() != ErrorKind::NotFound => return Err(io_error(&folder, e)),
+            _ => {}
+        }
+
+        let mut existing: HashMap<String, Document> = self
+            .document_repository
+            .list_by_project(document.project_id())
+            .await?
+            .into_iter()
+            .filter(|child| child.parent_id() == Some(document.id()))
+            .map(|child| (child.path().to_string(), child))
+            .collect();
+
+        let mut messages = Vec::with_capacity(parsed.len());
+        let mut children = Vec::new();
+        for (index, message) in parsed.into_iter().enumerate() {
+            let index = index as u32;
+            let mut attachment_ids = Vec::with_capacity(message.attachments.len());
+
+            for (number, attachment) in message.attachments.iter().enumerate() {
+                let path = family.attachment_path(index, number, &attachment.safe_file_name());
+                if let Some(folder) = path.parent() {
+                    tokio::fs::create_dir_all(folder)
+                        .await
+                        .map_err(|e| io_error(folder, e))?;
+                }
+                tokio::fs::write(&path, &attachment.data)
+                    .await
+                    .map_err(|e| io_error(&path, e))?;
+
+                let path = path.to_string_lossy().to_string();
+                let hash = ContentHash::of_bytes(HashAlgorithm::default(), &attachment.data);
+                let size = attachment.data.len() as u64;
+                let child = match existing.remove(&path) {
+                    Some(mut child) => {
+                        if child.is_missing() || *child.content_hash() != hash {
+                            child.update_contents(hash, size, document.modified_ns());
+                        }
+                        child
+                    }
+                    None => Document::child(document, path, hash, size, document.modified_ns()),
+                };
+                attachment_ids.push(child.id().clone());
+                children.push(child);
+            }
+
+            messages.push(EmailMessage::new(
+                document.id().clone(),
+                document.project_id().clone(),
+                index,
+                document.content_hash().to_string(),
+                EmailParser::METHOD,
+                message.headers,
+                attachment_ids,
+            ));
+        }
+
+        for mut gone in existing.into_values() {
+            gone.mark_missing();
+            children.push(gone);
+        }
+        self.document_repository.save_all(&children).await?;
+        self.email_repository
+            .replace_messages(document.id(), &messages)
+            .await?;
+
+        tracing::debug!(
+            "Parsed {} messages and {} attachments from {}",
+            messages.len(),
+            children.iter().filter(|child| !child.is_missing()).count(),
+            document.path()
+        );
+
+        Ok((messages, children))
+    }
+}
+
+fn extension(path: &str) -> String {
+    Path::new(path)
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or_default()
+        .to_lowercase()
+}
+
+fn message_dto(
+    message: &EmailMessage,
+    documents: &HashMap<DocumentId, Document>,
+) -> EmailMessageDto {
+    let path = documents
+        .get(message.document_id())
+        .map(|document| document.path().to_string())
+        .unwrap_or_default();
+    let attachments = message
+        .attachment_ids()
+        .iter()
+        .filter_map(|id| documents.get(id))
+        .map(EmailAttachmentDto::new)
+        .collect();
+    EmailMessageDto::new(message, path, attachments)
+}
+
+fn thread_dto(thread: &EmailThread, documents: &HashMap<DocumentId, Document>) -> EmailThreadDto {
+    let messages = thread
+        .messages
+        .iter()
+        .map(|message| message_dto(message, documents))
+        .collect();
+    EmailThreadDto::new(thread, messages)
+}
+
+/// Runs project email parsing on the background job pool
+///
+/// Accepts `{ "force": bool }` and returns a serialized `EmailParseBatchDto`.
+pub struct ParseEmailsJobHandler {
+    service: Arc<EmailService>,
+}
+
+impl ParseEmailsJobHandler {
+    pub fn new(service: Arc<EmailService>) -> Self {
+        ParseEmailsJobHandler { service }
+    }
+}
+
+#[async_trait]
+impl JobHandler for ParseEmailsJobHandler {
+    fn kind(&self) -> &'static str {
+        PARSE_EMAILS_JOB
+    }
+
+    fn is_idempotent(&self) -> bool {
+        true
+    }
+
+    async fn run(
+        &self,
+        context: JobContext,
+        params: serde_json::Value,
+    ) -> AppResult<serde_json::Value> {
+        let project_id = context
+            .project_id()
+            .ok_or_else(|| AppError::validation_error("Email parsing requires a project", None))?
+            .to_string();
+        let force = params
+            .get("force")
+            .and_then(|f| f.as_bool())
+            .unwrap_or(false);
+
+        let result = self
+            .service
+            .parse_project(&project_id, force, Some(&context))
+            .await?;
+
+        serde_json::to_value(result)
+            .map_err(|e| AppError::internal_error(format!("Failed to serialize result: {}", e)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::services::test_corpus::TestCorpus;
+    use crate::infrastructure::SqliteEmailRepository;
+    use std::fs;
+
+    async fn create_test_service() -> (EmailService, TestCorpus) {
+        let corpus = TestCorpus::new().await;
+        let service = EmailService::new(
+            corpus.repository.clone(),
+            Arc::new(SqliteEmailRepository::new(corpus.database.pool())),
+            corpus.derivatives.path().to_path_buf(),
+        );
+        (service, corpus)
+    }
+
+    const OFFER: &str = "From: ana@example.com\r\n\
+        To: ben@example.com\r\n\
+        Date: Mon, 4 Mar 2024 09:00:00 +0000\r\n\
+        Subject: Offer\r\n\
+        Message-ID: <offer@example.com>\r\n\
+        Content-Type: multipart/mixed; boundary=b\r\n\
+        \r\n\
+        --b\r\n\
+        Content-Type: text/plain\r\n\
+        \r\n\
+        Our offer, and the earlier note.\r\n\
+        --b\r\n\
+        Content-Type: text/csv\r\n\
+        Content-Disposition: attachment; filename=prices.csv\r\n\
+        \r\n\
+        lot,price\r\n\
+        --b\r\n\
+        Content-Type: message/rfc822\r\n\
+        Content-Disposition: attachment; filename=note.eml\r\n\
+        \r\n\
+        From: carla@example.com\r\n\
+        Subject: Note\r\n\
+        Message-ID: <note@example.com>\r\n\
+        \r\n\
+        Remember the deadline.\r\n\
+        --b--\r\n";
+
+    const REPLY: &str = "From: ben@example.com\r\n\
+        To: ana@example.com\r\n\
+        Date: Tue, 5 Mar 2024 09:00:00 +0000\r\n\
+        Subject: Re: Offer\r\n\
+        Message-ID: <reply@example.com>\r\n\
+        In-Reply-To: <offer@example.com>\r\n\
+        \r\n\
+        Accepted.\r\n";
+
+    #[tokio::test]
+    async fn test_parse_project_threads_and_attachments() {
+        let (service, corpus) = create_test_service().await;
+        let offer = corpus.add_document("offer.eml", OFFER.as_bytes()).await;
+        corpus.add_document("reply.eml", REPLY.as_bytes()).await;
+        corpus.add_document("letter.txt", b"Dear Sir").await;
+        corpus.add_document("broken.msg", b"not a message").await;
+        let project_id = corpus.project_id.value().to_string();
+
+        let first = service
+            .parse_project(&project_id, false, None)
+            .await
+            .unwrap();
+        // The attached note is parsed as well
+        assert_eq!(first.results.len(), 3);
+        assert_eq!(first.unsupported, 1);
+        assert_eq!(first.failed.len(), 1);
+
+        let messages = service.get_email(offer.id().value()).await.unwrap();
+        let names: Vec<&str> = messages[0]
+            .attachments
+            .iter()
+            .map(|attachment| attachment.file_name.as_str())
+            .collect();
+        assert_eq!(names, ["prices.csv", "note.eml"]);
+        let prices = &messages[0].attachments[0];
+        assert_eq!(
+            fs::read_to_string(&prices.path).unwrap().trim(),
+            "lot,price"
+        );
+
+        let children = corpus
+            .repository
+            .list_by_project(&corpus.project_id)
+            .await
+            .unwrap();
+        let child = children
+            .iter()
+            .find(|document| document.id().value() == prices.document_id)
+            .unwrap();
+        assert_eq!(child.parent_id(), Some(offer.id()));
+
+        // Attachments are listed under their email
+        let listing = service
+            .attachment_listing(&project_id, offer.path())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(listing.entries.len(), 2);
+        assert_eq!(
+            listing.email_document_id.as_deref(),
+            Some(offer.id().value())
+        );
+        let counts = service.attachment_counts(&project_id).await.unwrap();
+        assert_eq!(counts.get(offer.path()), Some(&2));
+
+        let threads = service.list_threads(&project_id).await.unwrap();
+        assert_eq!(threads.len(), 2);
+        assert_eq!(threads[0].id, "offer@example.com");
+        assert_eq!(threads[0].messages.len(), 2);
+        assert_eq!(threads[0].messages[1].subject.as_deref(), Some("Re: Offer"));
+        assert_eq!(threads[1].subject.as_deref(), Some("Note"));
+
+        let second = service
+            .parse_project(&project_id, false, None)
+            .await
+            .unwrap();
+        assert!(second.results.is_empty());
+        // Both emails and the attached note
+        assert_eq!(second.up_to_date, 3);
+    }
+}
@@ -0,0 +1,440 @@
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use crate::application::dtos::ExportResultDto;
+use crate::application::services::project_service::load_accessible_project;
+use crate::domain::det::DetStore;
+use crate::domain::export::{ExportDocument, ExportError, ExportFormat, ExportOptions};
+use crate::domain::project::{Project, ProjectRepository};
+use crate::infrastructure::{AppError, AppResult, ExporterRegistry};
+
+/// Application service for exporting `.det` documents as deliverables
+///
+/// Renders reports and derivatives to Markdown, HTML, DOCX or PDF and
+/// writes the file into the project's reports folder. Exports never touch
+/// the source documents; an existing export of the same name is replaced.
+pub struct ExportService {
+    project_repository: Arc<dyn ProjectRepository>,
+    det_store: Arc<dyn DetStore>,
+    registry: Arc<ExporterRegistry>,
+    derivatives_root: PathBuf,
+}
+
+impl ExportService {
+    /// Create a new ExportService for derivatives below `derivatives_root`
+    pub fn new(
+        project_repository: Arc<dyn ProjectRepository>,
+        det_store: Arc<dyn DetStore>,
+        registry: Arc<ExporterRegistry>,
+        derivatives_root: PathBuf,
+    ) -> Self {
+        ExportService {
+            project_repository,
+            det_store,
+            registry,
+            derivatives_root,
+        }
+    }
+
+    /// Formats that documents can be exported to
+    pub fn formats(&self) -> Vec<ExportFormat> {
+        self.registry.formats()
+    }
+
+    /// Export the `.det` file at `det_path` into the reports folder
+    ///
+    /// `det_path` must lie in the project's reports folder or among its
+    /// derivatives. The file is named after `file_name`, or the document
+    /// title when no name is given; the format's extension is added.
+    pub async fn export_document(
+        &self,
+        project_id: &str,
+        det_path: &str,
+        format: ExportFormat,
+        options: ExportOptions,
+        file_name: Option<String>,
+    ) -> AppResult<ExportResultDto> {
+        options.validate()?;
+        let project = load_accessible_project(self.project_repository.as_ref(), project_id).await?;
+        let exporter = self
+            .registry
+            .for_format(format)
+            .ok_or_else(|| ExportError::UnsupportedFormat(format.to_string()))?;
+
+        let det_path = self.resolve_det_path(&project, det_path)?;
+        let det = self.det_store.load(&det_path).await?;
+        let fallback_title = det_path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let now = chrono::Utc::now();
+        let document = ExportDocument::new(
+            &det,
+            &fallback_title,
+            options,
+            now.format("%Y-%m-%d").to_string(),
+        );
+
+        let name = file_name
+            .filter(|name| !name.trim().is_empty())
+            .unwrap_or_else(|| document.title.clone());
+        let output_path = project.reports_folder().join(format!(
+            "{}.{}",
+            file_stem(&name, format),
+            format.extension()
+        ));
+        let toc_entries = document.toc().len();
+        let cited_sources = document.sources.len();
+
+        let path = output_path.clone();
+        let size_bytes = tokio::task::spawn_blocking(move || -> AppResult<u64> {
+            let bytes = exporter.export(&document)?;
+            write_atomically(&path, &bytes)?;
+            Ok(bytes.len() as u64)
+        })
+        .await
+        .map_err(|e| AppError::internal_error(format!("Export task failed: {}", e)))??;
+
+        tracing::debug!(
+            "Exported {} as {} to {}",
+            det_path.display(),
+            format,
+            output_path.display()
+        );
+
+        Ok(ExportResultDto {
+            output_path: output_path.to_string_lossy().to_string(),
+            format: format.to_string(),
+            size_bytes,
+            toc_entries,
+            cited_sources,
+            exported_at: now.to_rfc3339(),
+        })
+    }
+
+    /// A `.det` file in the project's reports folder or derivatives folder
+    ///
+    /// Symbolic links and `..` are resolved first, so no other file on disk
+    /// can be read through an export.
+    fn resolve_det_path(&self, project: &Project, det_path: &str) -> AppResult<PathBuf> {
+        let resolved = Path::new(det_path)
+            .canonicalize()
+            .map_err(|_| AppError::not_found(format!("Document '{}'", det_path)))?;
+
+        let allowed = [
+            project.reports_folder(),
+            self.derivatives_root.join(project.id().value()),
+        ];
+        let inside = allowed
+            .iter()
+            .filter_map(|folder| folder.canonicalize().ok())
+            .any(|folder| resolved.starts_with(folder));
+        if !inside {
+            return Err(AppError::validation_error(
+                "Only reports and derivatives of the project can be exported",
+                Some(det_path.to_string()),
+            ));
+        }
+        Ok(resolved)
+    }
+}
+
+/// File name without extension that is safe on every platform
+///
+/// Drops an extension matching `format`, so "Report.pdf" does not become
+/// "Report.pdf.pdf".
+fn file_stem(name: &str, format: ExportFormat) -> String {
+    let name = name.trim();
+    let name = Path::new(name)
+        .extension()
+        .and_then(|e| e.to_str())
+        .filter(|e| ExportFormat::parse(e).ok() == Some(format))
+        .map_or(name, |e| &name[..name.len() - e.len() - 1]);
+
+    let stem: String = name
+        .chars()
+        .map(|c| match c {
+            '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '-',
+            c if c.is_control() => '-',
+            c => c,
+        })
+        .collect();
+    let stem = stem.trim_matches(|c: char| c == '.' || c.is_whitespace());
+    if stem.is_empty() {
+        "export".to_string()
+    } else {
+        stem.to_string()
+    }
+}
+
+/// Write through a temporary sibling so readers never see a partial file
+pub(crate) fn write_atomically(path: &Path, bytes: &[u8]) -> AppResult<()> {
+    let io_error = |e: std::io::Error| {
+        AppError::filesystem_error(format!("Cannot write {}: {}", path.display(), e))
+    };
+
+    if let Some(folder) = path.parent() {
+        fs::create_dir_all(folder).map_err(io_error)?;
+    }
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let temp_path: PathBuf =
+        path.with_file_name(format!(".{}.{}.tmp", name, uuid::Uuid::new_v4().simple()));
+
+    let result = fs::File::create(&temp_path)
+        .and_then(|mut file| {
+            file.write_all(bytes)?;
+            file.sync_all()
+        })
+        .and_then(|()| fs::rename(&temp_path, path));
+    if let Err(error) = result {
+        let _ = fs::remove_file(&temp_path);
+        return Err(io_error(error));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::services::{DocumentService, HashingService, SnapshotService};
+    use crate::domain::det::{DetDocument, DetKind, PmNode, ProcessingMetadata};
+    use crate::domain::document::{DerivativeFamily, DocumentId};
+    use crate::domain::export::CitedSource;
+    use crate::domain::workspace::value_objects::HashAlgorithm;
+    use crate::infrastructure::{
+        DatabaseConnection, FileDetStore, SqliteDocumentRepository, SqliteFileHashRepository,
+        SqliteManifestSnapshotRepository, SqliteProjectRepository,
+    };
+    use tempfile::TempDir;
+
+    struct Fixture {
+        service: ExportService,
+        projects: Arc<SqliteProjectRepository>,
+        database: DatabaseConnection,
+        project: Project,
+        det_path: PathBuf,
+        _source: TempDir,
+        _derivatives: TempDir,
+        _db_dir: TempDir,
+    }
+
+    async fn create_fixture() -> Fixture {
+        let (database, db_dir) = DatabaseConnection::new_temp().await.unwrap();
+        let repository = Arc::new(SqliteProjectRepository::new(database.pool()));
+        let source = TempDir::new().unwrap();
+        let derivatives = TempDir::new().unwrap();
+        let project = Project::new(
+            "Export".to_string(),
+            source.path().to_string_lossy().to_string(),
+            None,
+        )
+        .unwrap();
+        repository.create(&project).await.unwrap();
+
+        let invoice = source.path().join("invoice.pdf");
+        fs::write(&invoice, b"%PDF-1.4").unwrap();
+        let cited = CitedSource::new(invoice.to_string_lossy());
+        let det = DetDocument::new(
+            DetKind::Report,
+            PmNode::doc(vec![
+                PmNode::heading(1, PmNode::text_nodes("Findings", Vec::new())),
+                PmNode::paragraph(PmNode::text_nodes("Costs rose.", vec![cited.to_mark()])),
+            ]),
+            ProcessingMetadata::completed("manual"),
+        )
+        .with_title("Q3: Costs / Fees");
+        let det_path = DerivativeFamily::new(derivatives.path(), project.id(), &DocumentId::new())
+            .extracted_path();
+        let store = Arc::new(FileDetStore::new());
+        store.save(&det_path, &det).await.unwrap();
+
+        Fixture {
+            service: ExportService::new(
+                repository.clone(),
+                store,
+                Arc::new(ExporterRegistry::with_defaults()),
+                derivatives.path().to_path_buf(),
+            ),
+            projects: repository,
+            database,
+            project,
+            det_path,
+            _source: source,
+            _derivatives: derivatives,
+            _db_dir: db_dir,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_export_writes_into_reports_folder() {
+        let fixture = create_fixture().await;
+        let project_id = fixture.project.id().value().to_string();
+        let det_path = fixture.det_path.to_string_lossy().to_string();
+        let options = ExportOptions {
+            include_toc: true,
+            ..ExportOptions::default()
+        };
+
+        for format in ExportFormat::ALL {
+            let result = fixture
+                .service
+                .export_document(&project_id, &det_path, format, options.clone(), None)
+                .await
+                .unwrap();
+
+            let expected = fixture
+                .project
+                .reports_folder()
+                .join(format!("Q3- Costs - Fees.{}", format.extension()));
+            assert_eq!(result.output_path, expected.to_string_lossy());
+            assert_eq!(fs::metadata(&expected).unwrap().len(), result.size_bytes);
+            assert_eq!(result.toc_entries, 1);
+            assert_eq!(result.cited_sources, 1);
+        }
+
+        let named = fixture
+            .service
+            .export_document(
+                &project_id,
+                &det_path,
+                ExportFormat::Markdown,
+                ExportOptions::default(),
+                Some("summary.md".to_string()),
+            )
+            .await
+            .unwrap();
+        assert!(named.output_path.ends_with("summary.md"));
+    }
+
+    #[tokio::test]
+    async fn test_exports_stay_out_of_source_scans() {
+        let fixture = create_fixture().await;
+        let project_id = fixture.project.id().value().to_string();
+        let pool = fixture.database.pool();
+        let hashing = Arc::new(HashingService::new(
+            fixture.projects.clone(),
+            Arc::new(SqliteFileHashRepository::new(pool.clone())),
+        ));
+        let documents = DocumentService::new(
+            fixture.projects.clone(),
+            Arc::new(SqliteDocumentRepository::new(pool.clone())),
+            hashing.clone(),
+        );
+        let snapshots = SnapshotService::new(
+            fixture.projects.clone(),
+            Arc::new(SqliteManifestSnapshotRepository::new(pool)),
+            hashing,
+        );
+
+        documents
+            .reconcile_project(&project_id, None)
+            .await
+            .unwrap();
+        let snapshot = snapshots
+            .create_snapshot(&project_id, None, HashAlgorithm::Blake3, None)
+            .await
+            .unwrap();
+
+        // The default reports folder lies inside the source folder
+        let det_path = fixture.det_path.to_string_lossy().to_string();
+        for format in ExportFormat::ALL {
+            let result = fixture
+                .service
+                .export_document(
+                    &project_id,
+                    &det_path,
+                    format,
+                    ExportOptions::default(),
+                    None,
+                )
+                .await
+                .unwrap();
+            assert!(
+                Path::new(&result.output_path).starts_with(fixture.project.source_folder().value())
+            );
+        }
+
+        let reconciled = documents
+            .reconcile_project(&project_id, None)
+            .await
+            .unwrap();
+        assert!(!reconciled.has_changes());
+        assert_eq!(reconciled.files_scanned, 1);
+
+        let diff = snapshots
+            .diff_snapshots(&project_id, &snapshot.id, None)
+            .await
+            .unwrap();
+        assert_eq!(diff.added, 0);
+        assert!(diff.changes.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_export_rejects_invalid_options() {
+        let fixture = create_fixture().await;
+        let options = ExportOptions {
+            toc_depth: 9,
+            ..ExportOptions::default()
+        };
+
+        let error = fixture
+            .service
+            .export_document(
+                fixture.project.id().value(),
+                &fixture.det_path.to_string_lossy(),
+                ExportFormat::Html,
+                options,
+                None,
+            )
+            .await
+            .unwrap_err();
+        assert_eq!(error.code, "VALIDATION_ERROR");
+        assert!(!fixture.project.reports_folder().exists());
+    }
+
+    #[tokio::test]
+    async fn test_export_reads_only_reports_and_derivatives() {
+        let fixture = create_fixture().await;
+        let project_id = fixture.project.id().value().to_string();
+        let export = |det_path: PathBuf| {
+            let (service, project_id) = (&fixture.service, &project_id);
+            async move {
+                service
+                    .export_document(
+                        project_id,
+                        &det_path.to_string_lossy(),
+                        ExportFormat::Markdown,
+                        ExportOptions::default(),
+                        None,
+                    )
+                    .await
+            }
+        };
+
+        let det = FileDetStore::new().load(&fixture.det_path).await.unwrap();
+        let report = fixture.project.reports_folder().join("draft.det");
+        FileDetStore::new().save(&report, &det).await.unwrap();
+        assert!(export(report).await.is_ok());
+
+        let outside = fixture.project.source_folder().value().join("notes.det");
+        FileDetStore::new().save(&outside, &det).await.unwrap();
+        let error = export(outside).await.unwrap_err();
+        assert_eq!(error.code, "VALIDATION_ERROR");
+
+        // Derivatives of another project, reached through the family folder
+        let family = fixture.det_path.parent().unwrap();
+        let escaping = family.join("../../other/notes.det");
+        FileDetStore::new().save(&escaping, &det).await.unwrap();
+        let error = export(escaping).await.unwrap_err();
+        assert_eq!(error.code, "VALIDATION_ERROR");
+
+        let missing = fixture.project.reports_folder().join("missing.det");
+        let error = export(missing).await.unwrap_err();
+        assert_eq!(error.code, "NOT_FOUND");
+    }
+}
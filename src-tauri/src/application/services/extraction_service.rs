@@ -0,0 +1,502 @@
+use async_trait::async_trait;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use crate::application::dtos::{
+    ExtractionBatchDto, ExtractionFailureDto, ExtractionResultDto, ExtractionWarningDto,
+};
+use crate::application::jobs::{JobContext, JobHandler};
+use crate::application::services::project_service::parse_project_id;
+use crate::application::services::{
+    AnnotationService, DerivationService, SearchIndexService, TOOL_VERSION,
+};
+use crate::domain::det::{DetDocument, DetKind, DetStore, ProcessingMetadata, SourceReference};
+use crate::domain::document::{
+    DerivationStep, DerivativeFamily, Document, DocumentId, DocumentRepository, FamilyMember,
+};
+use crate::domain::extraction::{DocumentExtractor, ExtractedDocument, ExtractionError, OcrPage};
+use crate::infrastructure::{AppError, AppResult, ExtractorRegistry};
+
+/// Job kind for extracting all documents of a project in the background
+pub const EXTRACT_DOCUMENTS_JOB: &str = "extract_documents";
+
+/// Application service for text extraction into `.det` files
+///
+/// Converts tracked source documents to ProseMirror JSON and writes the
+/// result as `extracted.det` in the document's derivative family. The
+/// `.det` envelope records the content hash of the original, so a project
+/// run only re-extracts documents whose contents changed.
+pub struct ExtractionService {
+    document_repository: Arc<dyn DocumentRepository>,
+    registry: Arc<ExtractorRegistry>,
+    det_store: Arc<dyn DetStore>,
+    derivatives_root: PathBuf,
+    derivation_service: Option<Arc<DerivationService>>,
+    annotation_service: Option<Arc<AnnotationService>>,
+    search_index: Option<Arc<SearchIndexService>>,
+}
+
+impl ExtractionService {
+    /// Create a new ExtractionService writing below `derivatives_root`
+    pub fn new(
+        document_repository: Arc<dyn DocumentRepository>,
+        registry: Arc<ExtractorRegistry>,
+        det_store: Arc<dyn DetStore>,
+        derivatives_root: PathBuf,
+    ) -> Self {
+        ExtractionService {
+            document_repository,
+            registry,
+            det_store,
+            derivatives_root,
+            derivation_service: None,
+            annotation_service: None,
+            search_index: None,
+        }
+    }
+
+    /// Record each extraction as a step in the document's derivation graph
+    pub fn with_derivation_service(mut self, derivation_service: Arc<DerivationService>) -> Self {
+        self.derivation_service = Some(derivation_service);
+        self
+    }
+
+    /// Move annotations to the new text whenever a document is re-extracted
+    pub fn with_annotation_service(mut self, annotation_service: Arc<AnnotationService>) -> Self {
+        self.annotation_service = Some(annotation_service);
+        self
+    }
+
+    /// Add each extracted text to the search index
+    pub fn with_search_index(mut self, search_index: Arc<SearchIndexService>) -> Self {
+        self.search_index = Some(search_index);
+        self
+    }
+
+    /// File extensions that can be extracted
+    pub fn supported_extensions(&self) -> Vec<String> {
+        self.registry.supported_extensions()
+    }
+
+    /// Extract one document, replacing any earlier extraction
+    pub async fn extract_document(&self, document_id: &str) -> AppResult<ExtractionResultDto> {
+        let id = DocumentId::from_string(document_id.to_string()).map_err(|e| {
+            AppError::validation_error("Invalid document ID format", Some(e.to_string()))
+        })?;
+
+        let document = self
+            .document_repository
+            .find_by_id(&id)
+            .await?
+            .ok_or_else(|| AppError::not_found(format!("Document with ID '{}'", document_id)))?;
+
+        let extractor = self.extractor_for(&document)?;
+        self.extract(&document, extractor).await
+    }
+
+    /// Extract every present document of a project
+    ///
+    /// Documents whose `extracted.det` was made from the current contents
+    /// are skipped unless `force` is set. Failures are collected per file.
+    pub async fn extract_project(
+        &self,
+        project_id: &str,
+        force: bool,
+        context: Option<&JobContext>,
+    ) -> AppResult<ExtractionBatchDto> {
+        let id = parse_project_id(project_id)?;
+
+        let documents: Vec<Document> = self
+            .document_repository
+            .list_by_project(&id)
+            .await?
+            .into_iter()
+            .filter(|document| !document.is_missing())
+            .collect();
+        let total = documents.len() as u64;
+
+        let mut batch = ExtractionBatchDto {
+            project_id: project_id.to_string(),
+            ..ExtractionBatchDto::default()
+        };
+
+        for (index, document) in documents.iter().enumerate() {
+            if let Some(context) = context {
+                context.check_cancelled()?;
+                context
+                    .report_progress(index as u64, Some(total), Some("Extracting text"))
+                    .await;
+            }
+
+            let Ok(extractor) = self.extractor_for(document) else {
+                batch.unsupported += 1;
+                continue;
+            };
+            if !force && self.is_up_to_date(document).await {
+                batch.up_to_date += 1;
+                continue;
+            }
+
+            match self.extract(document, extractor).await {
+                Ok(result) => batch.results.push(result),
+                Err(error) => {
+                    tracing::warn!(
+                        "Extraction of {} failed: {}",
+                        document.path(),
+                        error.user_message()
+                    );
+                    batch.failed.push(ExtractionFailureDto {
+                        document_id: document.id().value().to_string(),
+                        path: document.path().to_string(),
+                        error: error.user_message(),
+                    });
+                }
+            }
+        }
+
+        if let Some(context) = context {
+            context.report_progress(total, Some(total), None).await;
+        }
+
+        Ok(batch)
+    }
+
+    fn extractor_for(&self, document: &Document) -> AppResult<Arc<dyn DocumentExtractor>> {
+        let extension = Path::new(document.path())
+            .extension()
+            .and_then(|e| e.to_str())
+            .unwrap_or_default()
+            .to_lowercase();
+
+        self.registry
+            .for_extension(&extension)
+            .ok_or_else(|| ExtractionError::unsupported(extension).into())
+    }
+
+    fn family(&self, document: &Document) -> DerivativeFamily {
+        DerivativeFamily::new(&self.derivatives_root, document.project_id(), document.id())
+    }
+
+    /// Whether the existing `.det` was extracted from the current contents
+    async fn is_up_to_date(&self, document: &Document) -> bool {
+        let path = self.family(document).extracted_path();
+        let Ok(existing) = self.det_store.load(&path).await else {
+            return false;
+        };
+
+        let current = document.content_hash().to_string();
+        existing
+            .primary_source()
+            .and_then(|source| source.content_hash.as_deref())
+            == Some(current.as_str())
+    }
+
+    async fn extract(
+        &self,
+        document: &Document,
+        extractor: Arc<dyn DocumentExtractor>,
+    ) -> AppResult<ExtractionResultDto> {
+        let bytes = tokio::fs::read(document.path()).await.map_err(|e| {
+            AppError::filesystem_error(format!("Cannot read {}: {}", document.path(), e))
+        })?;
+
+        let method = extractor.method();
+        let extracted = tokio::task::spawn_blocking(move || extractor.extract(&bytes))
+            .await
+            .map_err(|e| AppError::internal_error(format!("Extraction task failed: {}", e)))??;
+
+        let quality = extracted.quality();
+        let warnings: Vec<ExtractionWarningDto> = extracted
+            .warnings
+            .iter()
+            .map(ExtractionWarningDto::from)
+            .collect();
+        let processing = ProcessingMetadata::completed(method)
+            .with_quality(quality.score(), quality.level())
+            .with_warnings(extracted.warnings.iter().map(ToString::to_string).collect())
+            .with_page_count(extracted.page_count);
+        let extracted_at = processing.created_at.to_rfc3339();
+        let block_count = extracted.content.content.len();
+        let ocr_pages: Vec<u32> = extracted.ocr_pages.iter().map(|page| page.page).collect();
+        let pages_needing_correction: Vec<u32> = extracted
+            .ocr_pages
+            .iter()
+            .filter(|page| page.needs_correction())
+            .map(|page| page.page)
+            .collect();
+        let ocr_data = ocr_data(&extracted);
+
+        let source = SourceReference::new(document.path())
+            .with_document_id(document.id().value())
+            .with_content_hash(document.content_hash().to_string());
+        let mut det = DetDocument::new(DetKind::Extracted, extracted.content, processing)
+            .with_title(document.file_name().unwrap_or_default())
+            .with_source(source);
+        if let Some(data) = ocr_data {
+            det = det.with_data(data);
+        }
+
+        let det_path = self.family(document).extracted_path();
+        self.det_store.save(&det_path, &det).await?;
+
+        if let Some(derivation_service) = &self.derivation_service {
+            let step = DerivationStep::new(
+                FamilyMember::Original,
+                FamilyMember::extracted(),
+                "extraction",
+                serde_json::json!({ "method": method, "ocrEngine": extracted.ocr_engine }),
+                TOOL_VERSION,
+            )?;
+            derivation_service.record_step(document, step).await?;
+        }
+
+        if let Some(search_index) = &self.search_index {
+            if let Err(error) = search_index.index_derivative(document, &det_path).await {
+                tracing::warn!(
+                    "Indexing the text of {} failed: {}",
+                    document.path(),
+                    error.user_message()
+                );
+            }
+        }
+
+        if let Some(annotation_service) = &self.annotation_service {
+            if let Err(error) = annotation_service
+                .reattach_to(document, det.content())
+                .await
+            {
+                tracing::warn!(
+                    "Re-attaching annotations of {} failed: {}",
+                    document.path(),
+                    error.user_message()
+                );
+            }
+        }
+
+        tracing::debug!(
+            "Extracted {} with {} (quality {:.2})",
+            document.path(),
+            method,
+            quality.score()
+        );
+
+        Ok(ExtractionResultDto {
+            document_id: document.id().value().to_string(),
+            source_path: document.path().to_string(),
+            det_path: det_path.to_string_lossy().to_string(),
+            method: method.to_string(),
+            quality_score: quality.score(),
+            quality_level: quality.level().to_string(),
+            needs_review: quality.needs_review(),
+            warnings,
+            block_count,
+            page_count: extracted.page_count,
+            ocr_pages,
+            pages_needing_correction,
+            extracted_at,
+        })
+    }
+}
+
+/// Words, confidences and positions of OCR pages for the `.det` data
+/// payload, so the recognized text can be checked against the scan
+fn ocr_data(extracted: &ExtractedDocument) -> Option<serde_json::Value> {
+    let engine = extracted.ocr_engine.as_ref()?;
+    let pages: Vec<serde_json::Value> = extracted
+        .ocr_pages
+        .iter()
+        .map(|page| {
+            serde_json::json!({
+                "page": page.page,
+                "confidence": page.confidence(),
+                "needsCorrection": page.needs_correction(),
+                "words": page.words,
+            })
+        })
+        .collect();
+
+    Some(serde_json::json!({
+        "ocr": {
+            "engine": engine,
+            "reviewConfidence": OcrPage::REVIEW_CONFIDENCE,
+            "pages": pages,
+        }
+    }))
+}
+
+/// Runs project extraction on the background job pool
+///
+/// Accepts `{ "force": bool }` and returns a serialized `ExtractionBatchDto`.
+pub struct ExtractDocumentsJobHandler {
+    service: Arc<ExtractionService>,
+}
+
+impl ExtractDocumentsJobHandler {
+    pub fn new(service: Arc<ExtractionService>) -> Self {
+        ExtractDocumentsJobHandler { service }
+    }
+}
+
+#[async_trait]
+impl JobHandler for ExtractDocumentsJobHandler {
+    fn kind(&self) -> &'static str {
+        EXTRACT_DOCUMENTS_JOB
+    }
+
+    fn is_idempotent(&self) -> bool {
+        true
+    }
+
+    async fn run(
+        &self,
+        context: JobContext,
+        params: serde_json::Value,
+    ) -> AppResult<serde_json::Value> {
+        let project_id = context
+            .project_id()
+            .ok_or_else(|| AppError::validation_error("Extraction requires a project", None))?
+            .to_string();
+        let force = params
+            .get("force")
+            .and_then(|f| f.as_bool())
+            .unwrap_or(false);
+
+        let result = self
+            .service
+            .extract_project(&project_id, force, Some(&context))
+            .await?;
+
+        serde_json::to_value(result)
+            .map_err(|e| AppError::internal_error(format!("Failed to serialize result: {}", e)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::services::test_corpus::TestCorpus;
+    use crate::infrastructure::FileDetStore;
+
+    async fn create_test_service() -> (ExtractionService, TestCorpus) {
+        let corpus = TestCorpus::new().await;
+        let service = ExtractionService::new(
+            corpus.repository.clone(),
+            Arc::new(ExtractorRegistry::with_defaults()),
+            Arc::new(FileDetStore::new()),
+            corpus.derivatives.path().to_path_buf(),
+        );
+        (service, corpus)
+    }
+
+    #[tokio::test]
+    async fn test_extract_document_writes_det_to_family() {
+        let (service, corpus) = create_test_service().await;
+        let document = corpus
+            .add_document("notes.md", b"# Notes\n\nSome text.\n")
+            .await;
+
+        let result = service
+            .extract_document(document.id().value())
+            .await
+            .unwrap();
+
+        let expected =
+            DerivativeFamily::new(corpus.derivatives.path(), &corpus.project_id, document.id())
+                .extracted_path();
+        assert_eq!(result.det_path, expected.to_string_lossy());
+        assert_eq!(result.method, "markdown-conversion-v1");
+        assert_eq!(result.block_count, 2);
+
+        let det = FileDetStore::new().load(&expected).await.unwrap();
+        assert_eq!(det.kind(), DetKind::Extracted);
+        assert_eq!(det.content().content[0].node_type, "heading");
+        assert_eq!(
+            det.primary_source().unwrap().document_id.as_deref(),
+            Some(document.id().value())
+        );
+        assert_eq!(det.processing().method, "markdown-conversion-v1");
+        assert_eq!(det.processing().quality.as_ref().unwrap().level, "good");
+    }
+
+    #[tokio::test]
+    async fn test_ocr_results_are_stored_in_det_data() {
+        use crate::infrastructure::extraction::ocr::tests::FakeOcrEngine;
+
+        let corpus = TestCorpus::new().await;
+        let engine = Arc::new(FakeOcrEngine {
+            lines: vec!["Received 3 March"],
+            confidence: 0.6,
+        });
+        let service = ExtractionService::new(
+            corpus.repository.clone(),
+            Arc::new(ExtractorRegistry::with_defaults().with_ocr(engine)),
+            Arc::new(FileDetStore::new()),
+            corpus.derivatives.path().to_path_buf(),
+        );
+        let document = corpus.add_document("stamp.png", b"scanned image").await;
+
+        let result = service
+            .extract_document(document.id().value())
+            .await
+            .unwrap();
+        assert_eq!(result.method, "image-ocr-v1");
+        assert_eq!(result.ocr_pages, [1]);
+        assert_eq!(result.pages_needing_correction, [1]);
+        assert!(result.needs_review);
+
+        let det = FileDetStore::new()
+            .load(Path::new(&result.det_path))
+            .await
+            .unwrap();
+        assert!(det.content().plain_text().contains("Received 3 March"));
+        let ocr = &det.data().unwrap()["ocr"];
+        assert_eq!(ocr["engine"], "fake-ocr");
+        assert_eq!(ocr["pages"][0]["needsCorrection"], true);
+        assert_eq!(ocr["pages"][0]["words"][2]["text"], "March");
+        assert_eq!(ocr["pages"][0]["words"][2]["bbox"]["x"], 100);
+    }
+
+    #[tokio::test]
+    async fn test_extract_project_skips_unchanged_and_unsupported() {
+        let (service, corpus) = create_test_service().await;
+        corpus
+            .add_document("letter.txt", b"Dear Sir,\nthank you.\n")
+            .await;
+        corpus.add_document("photo.jpg", b"\xFF\xD8\xFF").await;
+        corpus.add_document("broken.docx", b"not a zip").await;
+        let project_id = corpus.project_id.value().to_string();
+
+        let first = service
+            .extract_project(&project_id, false, None)
+            .await
+            .unwrap();
+        assert_eq!(first.results.len(), 1);
+        assert_eq!(first.unsupported, 1);
+        assert_eq!(first.failed.len(), 1);
+
+        let second = service
+            .extract_project(&project_id, false, None)
+            .await
+            .unwrap();
+        assert!(second.results.is_empty());
+        assert_eq!(second.up_to_date, 1);
+
+        let forced = service
+            .extract_project(&project_id, true, None)
+            .await
+            .unwrap();
+        assert_eq!(forced.results.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_extract_unsupported_document_fails() {
+        let (service, corpus) = create_test_service().await;
+        let document = corpus.add_document("photo.jpg", b"\xFF\xD8\xFF").await;
+
+        let error = service
+            .extract_document(document.id().value())
+            .await
+            .unwrap_err();
+        assert_eq!(error.code, "UNSUPPORTED_FORMAT");
+    }
+}
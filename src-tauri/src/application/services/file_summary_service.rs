@@ -0,0 +1,424 @@
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::UNIX_EPOCH;
+use tokio::sync::RwLock;
+
+use crate::application::dtos::ProjectFileSummaryDto;
+use crate::application::services::project_service::{
+    load_accessible_project, output_folders, parse_project_id,
+};
+use crate::application::services::ArchiveService;
+use crate::domain::project::ProjectRepository;
+use crate::domain::workspace::repositories::FileCategoryConfigRepository;
+use crate::domain::workspace::value_objects::{ArchiveFormat, FileCategoryConfig};
+use crate::infrastructure::{AppError, AppResult};
+
+/// Cached summary together with the folder state it was computed from
+#[derive(Debug, Clone)]
+struct CachedSummary {
+    fingerprint: u64,
+    config: FileCategoryConfig,
+    summary: ProjectFileSummaryDto,
+}
+
+/// Application service for per-project file type counts
+///
+/// Counting requires classifying every file in the source folder, so results
+/// are cached per project. The cache is keyed by a fingerprint of the path,
+/// size and modification time of every entry, so added, removed, renamed and
+/// edited files all invalidate the cached counts; computing it only reads
+/// metadata, never file contents. Files inside archives are counted too when
+/// an archive service is configured.
+pub struct FileSummaryService {
+    project_repository: Arc<dyn ProjectRepository>,
+    config_repository: Arc<dyn FileCategoryConfigRepository>,
+    archive_service: Option<Arc<ArchiveService>>,
+    cache: RwLock<HashMap<String, CachedSummary>>,
+}
+
+impl FileSummaryService {
+    /// Create a new FileSummaryService
+    pub fn new(
+        project_repository: Arc<dyn ProjectRepository>,
+        config_repository: Arc<dyn FileCategoryConfigRepository>,
+    ) -> Self {
+        FileSummaryService {
+            project_repository,
+            config_repository,
+            archive_service: None,
+            cache: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Count the files inside archives in the source folder
+    pub fn with_archive_service(mut self, archive_service: Arc<ArchiveService>) -> Self {
+        self.archive_service = Some(archive_service);
+        self
+    }
+
+    /// Get file type counts for a project's source folder
+    pub async fn get_project_file_summary(
+        &self,
+        project_id: &str,
+    ) -> AppResult<ProjectFileSummaryDto> {
+        let project = load_accessible_project(self.project_repository.as_ref(), project_id).await?;
+        let id = project.id().clone();
+
+        let config = self.config_repository.load(&id).await?;
+
+        self.summarize_folder(
+            project_id,
+            project.source_folder().value(),
+            &output_folders(&project),
+            config,
+        )
+        .await
+    }
+
+    /// Get the file classification settings for a project
+    pub async fn get_category_config(&self, project_id: &str) -> AppResult<FileCategoryConfig> {
+        let id = parse_project_id(project_id)?;
+        Ok(self.config_repository.load(&id).await?)
+    }
+
+    /// Replace the file classification settings for a project
+    pub async fn update_category_config(
+        &self,
+        project_id: &str,
+        config: FileCategoryConfig,
+    ) -> AppResult<FileCategoryConfig> {
+        let id = parse_project_id(project_id)?;
+        self.config_repository.save(&id, &config).await?;
+        self.invalidate(project_id).await;
+        Ok(config)
+    }
+
+    /// Drop any cached summary for a project
+    pub async fn invalidate(&self, project_id: &str) {
+        self.cache.write().await.remove(project_id);
+    }
+
+    /// Summarize a folder, leaving out the `excluded` folders below it and
+    /// reusing the cached result if nothing changed
+    pub async fn summarize_folder(
+        &self,
+        project_id: &str,
+        folder: &Path,
+        excluded: &[PathBuf],
+        config: FileCategoryConfig,
+    ) -> AppResult<ProjectFileSummaryDto> {
+        let folder = folder.to_path_buf();
+        let excluded = excluded.to_vec();
+
+        let fingerprint = {
+            let folder = folder.clone();
+            let excluded = excluded.clone();
+            tokio::task::spawn_blocking(move || folder_fingerprint(&folder, &excluded))
+                .await
+                .map_err(|e| AppError::internal_error(format!("Fingerprint task failed: {}", e)))?
+        };
+
+        if let Some(cached) = self.cache.read().await.get(project_id) {
+            if cached.fingerprint == fingerprint && cached.config == config {
+                return Ok(cached.summary.clone());
+            }
+        }
+
+        tracing::debug!("Computing file summary for project {}", project_id);
+
+        let (mut summary, archives) = {
+            let project_id = project_id.to_string();
+            let config = config.clone();
+            tokio::task::spawn_blocking(move || {
+                scan_folder(&project_id, &folder, &excluded, &config)
+            })
+            .await
+            .map_err(|e| AppError::internal_error(format!("Summary task failed: {}", e)))?
+        };
+
+        if let Some(archive_service) = &self.archive_service {
+            for archive in archives {
+                let files = match archive_service.files(&archive).await {
+                    Ok(files) => files,
+                    Err(e) => {
+                        tracing::warn!("Skipping archive {}: {}", archive.display(), e.message);
+                        continue;
+                    }
+                };
+
+                for (_, member) in files.iter().filter(|(_, member)| !member.is_dir) {
+                    summary.record_archive_member(
+                        config.classify_path(Path::new(member.name())),
+                        member.size,
+                    );
+                }
+            }
+        }
+
+        self.cache.write().await.insert(
+            project_id.to_string(),
+            CachedSummary {
+                fingerprint,
+                config,
+                summary: summary.clone(),
+            },
+        );
+
+        Ok(summary)
+    }
+}
+
+/// Walk all directories below `root` without following symlinks, leaving
+/// out the `excluded` folders and everything in them
+pub(crate) fn walk_directories(
+    root: &Path,
+    excluded: &[PathBuf],
+    mut visit: impl FnMut(&std::fs::DirEntry),
+) {
+    let mut pending: Vec<PathBuf> = vec![root.to_path_buf()];
+
+    while let Some(dir) = pending.pop() {
+        let Ok(entries) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+
+        for entry in entries.flatten() {
+            if entry.file_type().is_ok_and(|t| t.is_dir()) {
+                let path = entry.path();
+                if excluded.contains(&path) {
+                    continue;
+                }
+                pending.push(path);
+            }
+            visit(&entry);
+        }
+    }
+}
+
+/// Hash of every entry's path, size and modification time
+fn folder_fingerprint(root: &Path, excluded: &[PathBuf]) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    let mut entries = Vec::new();
+
+    walk_directories(root, excluded, |entry| {
+        let metadata = entry.metadata();
+        let size = metadata.as_ref().map(|m| m.len()).unwrap_or(0);
+        entries.push((entry.path(), size, modified_nanos(metadata)));
+    });
+
+    entries.sort();
+    entries.hash(&mut hasher);
+    hasher.finish()
+}
+
+fn modified_nanos(metadata: std::io::Result<std::fs::Metadata>) -> u128 {
+    metadata
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+        .map(|d| d.as_nanos())
+        .unwrap_or(0)
+}
+
+/// Classify every regular file below `root`, returning the archive files found
+fn scan_folder(
+    project_id: &str,
+    root: &Path,
+    excluded: &[PathBuf],
+    config: &FileCategoryConfig,
+) -> (ProjectFileSummaryDto, Vec<PathBuf>) {
+    let mut summary =
+        ProjectFileSummaryDto::empty(project_id.to_string(), chrono::Utc::now().to_rfc3339());
+    let mut archives = Vec::new();
+
+    walk_directories(root, excluded, |entry| {
+        if !entry.file_type().is_ok_and(|t| t.is_file()) {
+            return;
+        }
+
+        let size = entry.metadata().map(|m| m.len()).unwrap_or(0);
+        summary.record(config.classify_path(&entry.path()), size);
+        if ArchiveFormat::is_archive_name(&entry.file_name().to_string_lossy()) {
+            archives.push(entry.path());
+        }
+    });
+
+    (summary, archives)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::project::repositories::MockProjectRepository;
+    use crate::domain::workspace::value_objects::FileCategory;
+    use crate::infrastructure::{DatabaseConnection, SqliteFileCategoryConfigRepository};
+    use std::fs;
+    use tempfile::TempDir;
+
+    async fn create_test_service() -> (FileSummaryService, TempDir) {
+        let (database, temp_dir) = DatabaseConnection::new_temp().await.unwrap();
+        let service = FileSummaryService::new(
+            Arc::new(MockProjectRepository::new()),
+            Arc::new(SqliteFileCategoryConfigRepository::new(database.pool())),
+        );
+        (service, temp_dir)
+    }
+
+    fn create_test_corpus() -> TempDir {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+
+        fs::write(root.join("contract.pdf"), b"%PDF-1.7").unwrap();
+        fs::write(root.join("notes.txt"), b"notes").unwrap();
+        fs::create_dir(root.join("media")).unwrap();
+        fs::write(root.join("media").join("call.mp3"), b"ID3").unwrap();
+        fs::write(root.join("media").join("site.mov"), b"").unwrap();
+        fs::write(root.join("media").join("photo.jpg"), b"").unwrap();
+        fs::write(root.join("archive.bin"), [0u8, 1, 2, 3]).unwrap();
+
+        temp_dir
+    }
+
+    #[tokio::test]
+    async fn test_summarize_folder_counts_categories() {
+        let (service, _db_dir) = create_test_service().await;
+        let corpus = create_test_corpus();
+
+        let summary = service
+            .summarize_folder(
+                "proj_test",
+                corpus.path(),
+                &[],
+                FileCategoryConfig::default(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(summary.documents, 2);
+        assert_eq!(summary.audio, 1);
+        assert_eq!(summary.video, 1);
+        assert_eq!(summary.images, 1);
+        assert_eq!(summary.other, 1);
+        assert_eq!(summary.total_files, 6);
+    }
+
+    #[tokio::test]
+    async fn test_summary_is_cached_until_folder_changes() {
+        let (service, _db_dir) = create_test_service().await;
+        let corpus = create_test_corpus();
+        let config = FileCategoryConfig::default();
+
+        let first = service
+            .summarize_folder("proj_test", corpus.path(), &[], config.clone())
+            .await
+            .unwrap();
+        let second = service
+            .summarize_folder("proj_test", corpus.path(), &[], config.clone())
+            .await
+            .unwrap();
+        assert_eq!(first.computed_at, second.computed_at);
+
+        // Adding a file touches the parent directory and refreshes the counts
+        std::thread::sleep(std::time::Duration::from_millis(20));
+        fs::write(corpus.path().join("media").join("interview.wav"), b"RIFF").unwrap();
+
+        let third = service
+            .summarize_folder("proj_test", corpus.path(), &[], config.clone())
+            .await
+            .unwrap();
+        assert_eq!(third.audio, 2);
+        assert_eq!(third.total_files, 7);
+
+        // Editing a file in place leaves its directory alone
+        fs::write(corpus.path().join("notes.txt"), b"notes, now longer").unwrap();
+
+        let fourth = service
+            .summarize_folder("proj_test", corpus.path(), &[], config)
+            .await
+            .unwrap();
+        assert_eq!(fourth.total_size, third.total_size + 12);
+    }
+
+    #[tokio::test]
+    async fn test_config_change_refreshes_counts() {
+        let (service, _db_dir) = create_test_service().await;
+        let corpus = create_test_corpus();
+
+        service
+            .summarize_folder(
+                "proj_test",
+                corpus.path(),
+                &[],
+                FileCategoryConfig::default(),
+            )
+            .await
+            .unwrap();
+
+        let config = FileCategoryConfig::default().with_override("bin", FileCategory::Documents);
+        let summary = service
+            .summarize_folder("proj_test", corpus.path(), &[], config)
+            .await
+            .unwrap();
+
+        assert_eq!(summary.documents, 3);
+        assert_eq!(summary.other, 0);
+    }
+
+    #[tokio::test]
+    async fn test_invalid_project_id_is_rejected() {
+        let (service, _db_dir) = create_test_service().await;
+
+        let result = service.get_project_file_summary("not-a-project").await;
+        assert!(result.is_err());
+        assert_eq!(result.unwrap_err().code, "VALIDATION_ERROR");
+    }
+
+    #[tokio::test]
+    async fn test_archive_members_are_counted() {
+        use std::io::Write;
+
+        let (service, _db_dir) = create_test_service().await;
+        let corpus = create_test_corpus();
+        let mut writer =
+            zip::ZipWriter::new(fs::File::create(corpus.path().join("production.zip")).unwrap());
+        let options = zip::write::SimpleFileOptions::default();
+        writer.start_file("scans/letter.pdf", options).unwrap();
+        writer.write_all(b"%PDF-1.7").unwrap();
+        writer.start_file("scans/voicemail.mp3", options).unwrap();
+        writer.write_all(b"ID3").unwrap();
+        writer.finish().unwrap();
+
+        let plain = service
+            .summarize_folder(
+                "proj_plain",
+                corpus.path(),
+                &[],
+                FileCategoryConfig::default(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(plain.archive_members, 0);
+        assert_eq!(plain.total_files, 7);
+
+        let service = service.with_archive_service(Arc::new(ArchiveService::new(
+            corpus.path().join("extracted"),
+        )));
+        let summary = service
+            .summarize_folder(
+                "proj_test",
+                corpus.path(),
+                &[],
+                FileCategoryConfig::default(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(summary.archive_members, 2);
+        assert_eq!(summary.documents, 3);
+        assert_eq!(summary.audio, 2);
+        assert_eq!(summary.total_files, 9);
+    }
+}
@@ -0,0 +1,540 @@
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::UNIX_EPOCH;
+
+use crate::application::dtos::{DuplicateGroupDto, DuplicateReportDto};
+use crate::application::jobs::{JobContext, JobHandler};
+use crate::application::services::file_summary_service::walk_directories;
+use crate::application::services::project_service::{
+    load_accessible_project, load_project, output_folders, resolve_source_path,
+};
+use crate::domain::project::ProjectRepository;
+use crate::domain::workspace::repositories::FileHashRepository;
+use crate::domain::workspace::value_objects::{ContentHash, FileHashRecord, HashAlgorithm};
+use crate::infrastructure::{AppError, AppResult};
+
+/// Job kind for background duplicate scans
+pub const FIND_DUPLICATES_JOB: &str = "find_duplicates";
+
+/// Read buffer used when streaming file contents into the hasher
+const READ_BUFFER_SIZE: usize = 64 * 1024;
+
+/// Size and modification time identifying one version of a file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct FileStamp {
+    pub(crate) size: u64,
+    pub(crate) modified_ns: i64,
+}
+
+/// Application service for file content hashes and duplicate detection
+///
+/// Digests are computed by streaming the file through the hasher and are
+/// cached in SQLite keyed by path and algorithm. A cached digest is reused
+/// only while the file's size and modification time are unchanged.
+pub struct HashingService {
+    project_repository: Arc<dyn ProjectRepository>,
+    hash_repository: Arc<dyn FileHashRepository>,
+}
+
+impl HashingService {
+    /// Create a new HashingService
+    pub fn new(
+        project_repository: Arc<dyn ProjectRepository>,
+        hash_repository: Arc<dyn FileHashRepository>,
+    ) -> Self {
+        HashingService {
+            project_repository,
+            hash_repository,
+        }
+    }
+
+    /// Get the digest of a file, computing it only if the cache is stale
+    pub async fn hash_file(&self, path: &Path, algorithm: HashAlgorithm) -> AppResult<ContentHash> {
+        let path_str = path.to_string_lossy().to_string();
+        let stamp = file_stamp(path)
+            .map_err(|e| AppError::filesystem_error(format!("Cannot read {}: {}", path_str, e)))?;
+
+        if let Some(record) = self.hash_repository.find(&path_str, algorithm).await? {
+            if record.is_current(stamp.size, stamp.modified_ns) {
+                return Ok(record.hash);
+            }
+        }
+
+        self.compute_and_cache(path.to_path_buf(), algorithm).await
+    }
+
+    /// Get the digest of a file in a project's source folder
+    ///
+    /// `path` is relative to the source folder and may not lead out of it.
+    pub async fn hash_project_file(
+        &self,
+        project_id: &str,
+        path: &str,
+        algorithm: HashAlgorithm,
+    ) -> AppResult<ContentHash> {
+        let project = load_project(self.project_repository.as_ref(), project_id).await?;
+        let file = resolve_source_path(&project, path)?;
+        if !file.is_file() {
+            return Err(AppError::validation_error(
+                "Only files can be hashed",
+                Some(path.to_string()),
+            ));
+        }
+
+        self.hash_file(&file, algorithm).await
+    }
+
+    /// Cached digests that are still current for the given paths
+    ///
+    /// Never reads file contents, so it is cheap enough for directory
+    /// listings. Paths without a current cached digest are left out.
+    pub async fn known_hashes(
+        &self,
+        paths: &[String],
+        algorithm: HashAlgorithm,
+    ) -> AppResult<HashMap<String, ContentHash>> {
+        let records = self.hash_repository.find_many(paths, algorithm).await?;
+
+        Ok(records
+            .into_iter()
+            .filter(|record| {
+                file_stamp(Path::new(&record.path))
+                    .is_ok_and(|stamp| record.is_current(stamp.size, stamp.modified_ns))
+            })
+            .map(|record| (record.path, record.hash))
+            .collect())
+    }
+
+    /// Find identical files in a project's source folder
+    pub async fn find_duplicates(
+        &self,
+        project_id: &str,
+        algorithm: HashAlgorithm,
+        context: Option<&JobContext>,
+    ) -> AppResult<DuplicateReportDto> {
+        let project = load_accessible_project(self.project_repository.as_ref(), project_id).await?;
+
+        self.find_duplicates_in_folder(
+            project_id,
+            project.source_folder().value(),
+            &output_folders(&project),
+            algorithm,
+            context,
+        )
+        .await
+    }
+
+    /// Find identical files below a folder
+    ///
+    /// Only files that share their size with another file are hashed. When
+    /// running as a job, progress is reported per file and cancellation is
+    /// checked between files.
+    pub async fn find_duplicates_in_folder(
+        &self,
+        project_id: &str,
+        folder: &Path,
+        excluded: &[PathBuf],
+        algorithm: HashAlgorithm,
+        context: Option<&JobContext>,
+    ) -> AppResult<DuplicateReportDto> {
+        let files = {
+            let folder = folder.to_path_buf();
+            let excluded = excluded.to_vec();
+            tokio::task::spawn_blocking(move || list_files(&folder, &excluded))
+                .await
+                .map_err(|e| AppError::internal_error(format!("File scan failed: {}", e)))?
+        };
+        let files_scanned = files.len() as u64;
+
+        // Files of a unique size cannot have a duplicate
+        let mut by_size: HashMap<u64, Vec<(PathBuf, FileStamp)>> = HashMap::new();
+        for (path, stamp) in files {
+            if stamp.size > 0 {
+                by_size.entry(stamp.size).or_default().push((path, stamp));
+            }
+        }
+        let candidates: Vec<(PathBuf, FileStamp)> = by_size
+            .into_values()
+            .filter(|group| group.len() > 1)
+            .flatten()
+            .collect();
+
+        let candidate_paths: Vec<String> = candidates
+            .iter()
+            .map(|(path, _)| path.to_string_lossy().to_string())
+            .collect();
+        let cached: HashMap<String, FileHashRecord> = self
+            .hash_repository
+            .find_many(&candidate_paths, algorithm)
+            .await?
+            .into_iter()
+            .map(|record| (record.path.clone(), record))
+            .collect();
+
+        let total = candidates.len() as u64;
+        let mut files_hashed = 0;
+        let mut by_hash: HashMap<ContentHash, (u64, Vec<String>)> = HashMap::new();
+
+        for (index, (path, stamp)) in candidates.into_iter().enumerate() {
+            if let Some(context) = context {
+                context.check_cancelled()?;
+                context
+                    .report_progress(index as u64, Some(total), Some("Hashing files"))
+                    .await;
+            }
+
+            let path_str = path.to_string_lossy().to_string();
+            let hash = match cached.get(&path_str) {
+                Some(record) if record.is_current(stamp.size, stamp.modified_ns) => {
+                    record.hash.clone()
+                }
+                _ => match self.compute_and_cache(path, algorithm).await {
+                    Ok(hash) => {
+                        files_hashed += 1;
+                        hash
+                    }
+                    Err(error) => {
+                        // Files can vanish or become unreadable mid-scan
+                        tracing::warn!(
+                            "Skipping {} in duplicate scan: {}",
+                            path_str,
+                            error.user_message()
+                        );
+                        continue;
+                    }
+                },
+            };
+
+            by_hash
+                .entry(hash)
+                .or_insert_with(|| (stamp.size, Vec::new()))
+                .1
+                .push(path_str);
+        }
+
+        if let Some(context) = context {
+            context.report_progress(total, Some(total), None).await;
+        }
+
+        let groups = by_hash
+            .into_iter()
+            .filter(|(_, (_, paths))| paths.len() > 1)
+            .map(|(hash, (size, paths))| DuplicateGroupDto::new(hash.to_string(), size, paths))
+            .collect();
+
+        Ok(DuplicateReportDto::new(
+            project_id.to_string(),
+            algorithm.as_str().to_string(),
+            groups,
+            files_scanned,
+            files_hashed,
+        ))
+    }
+
+    /// Stream a file through the hasher and store the result
+    async fn compute_and_cache(
+        &self,
+        path: PathBuf,
+        algorithm: HashAlgorithm,
+    ) -> AppResult<ContentHash> {
+        let path_str = path.to_string_lossy().to_string();
+
+        let (hash, stamp) =
+            tokio::task::spawn_blocking(move || hash_file_contents(&path, algorithm))
+                .await
+                .map_err(|e| AppError::internal_error(format!("Hashing task failed: {}", e)))?
+                .map_err(|e| {
+                    AppError::filesystem_error(format!("Failed to hash {}: {}", path_str, e))
+                })?;
+
+        let record = FileHashRecord::new(path_str, stamp.size, stamp.modified_ns, hash.clone());
+        self.hash_repository.save(&record).await?;
+
+        Ok(hash)
+    }
+}
+
+/// Size and modification time of a file
+pub(crate) fn file_stamp(path: &Path) -> std::io::Result<FileStamp> {
+    let metadata = std::fs::metadata(path)?;
+    Ok(stamp_from_metadata(&metadata))
+}
+
+fn stamp_from_metadata(metadata: &std::fs::Metadata) -> FileStamp {
+    let modified_ns = metadata
+        .modified()
+        .ok()
+        .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+        .map(|d| d.as_nanos() as i64)
+        .unwrap_or(0);
+
+    FileStamp {
+        size: metadata.len(),
+        modified_ns,
+    }
+}
+
+/// Hash a file, returning the stamp it had when reading started
+fn hash_file_contents(
+    path: &Path,
+    algorithm: HashAlgorithm,
+) -> std::io::Result<(ContentHash, FileStamp)> {
+    let mut file = std::fs::File::open(path)?;
+    let stamp = stamp_from_metadata(&file.metadata()?);
+
+    let mut hasher = algorithm.hasher();
+    let mut buffer = vec![0u8; READ_BUFFER_SIZE];
+    loop {
+        let read = file.read(&mut buffer)?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+    }
+
+    Ok((hasher.finalize(), stamp))
+}
+
+/// Every regular file below `root`, outside the `excluded` folders, with
+/// its stamp
+pub(crate) fn list_files(root: &Path, excluded: &[PathBuf]) -> Vec<(PathBuf, FileStamp)> {
+    let mut files = Vec::new();
+
+    walk_directories(root, excluded, |entry| {
+        if !entry.file_type().is_ok_and(|t| t.is_file()) {
+            return;
+        }
+        if let Ok(metadata) = entry.metadata() {
+            files.push((entry.path(), stamp_from_metadata(&metadata)));
+        }
+    });
+
+    files
+}
+
+/// Parse an optional algorithm name, defaulting to BLAKE3
+pub fn parse_hash_algorithm(algorithm: Option<&str>) -> AppResult<HashAlgorithm> {
+    algorithm
+        .map(HashAlgorithm::from_str)
+        .transpose()
+        .map_err(|e| AppError::validation_error("Invalid hash algorithm", Some(e)))
+        .map(Option::unwrap_or_default)
+}
+
+/// Runs duplicate scans on the background job pool
+///
+/// Expects `{"algorithm": "blake3" | "sha256"}` as parameters and returns a
+/// serialized `DuplicateReportDto`. Re-running is cheap because digests
+/// computed before an interruption are already cached.
+pub struct FindDuplicatesJobHandler {
+    service: Arc<HashingService>,
+}
+
+impl FindDuplicatesJobHandler {
+    pub fn new(service: Arc<HashingService>) -> Self {
+        FindDuplicatesJobHandler { service }
+    }
+}
+
+#[async_trait]
+impl JobHandler for FindDuplicatesJobHandler {
+    fn kind(&self) -> &'static str {
+        FIND_DUPLICATES_JOB
+    }
+
+    fn is_idempotent(&self) -> bool {
+        true
+    }
+
+    async fn run(
+        &self,
+        context: JobContext,
+        params: serde_json::Value,
+    ) -> AppResult<serde_json::Value> {
+        let project_id = context
+            .project_id()
+            .ok_or_else(|| AppError::validation_error("Duplicate scan requires a project", None))?
+            .to_string();
+        let algorithm = parse_hash_algorithm(params.get("algorithm").and_then(|v| v.as_str()))?;
+
+        let report = self
+            .service
+            .find_duplicates(&project_id, algorithm, Some(&context))
+            .await?;
+
+        serde_json::to_value(report)
+            .map_err(|e| AppError::internal_error(format!("Failed to serialize report: {}", e)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::project::repositories::MockProjectRepository;
+    use crate::domain::project::Project;
+    use crate::infrastructure::{DatabaseConnection, SqliteFileHashRepository};
+    use std::fs;
+    use tempfile::TempDir;
+
+    async fn create_test_service() -> (HashingService, TempDir) {
+        let (database, temp_dir) = DatabaseConnection::new_temp().await.unwrap();
+        let service = HashingService::new(
+            Arc::new(MockProjectRepository::new()),
+            Arc::new(SqliteFileHashRepository::new(database.pool())),
+        );
+        (service, temp_dir)
+    }
+
+    fn create_test_corpus() -> TempDir {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+
+        fs::write(root.join("contract.pdf"), b"signed contract").unwrap();
+        fs::create_dir(root.join("attachments")).unwrap();
+        fs::write(
+            root.join("attachments").join("contract (1).pdf"),
+            b"signed contract",
+        )
+        .unwrap();
+        fs::write(
+            root.join("attachments").join("contract (2).pdf"),
+            b"signed contract",
+        )
+        .unwrap();
+        // Same size, different contents
+        fs::write(root.join("draft.pdf"), b"drafted contract").unwrap();
+        fs::write(root.join("draft-copy.pdf"), b"drafted-contract").unwrap();
+        // Empty files are never reported
+        fs::write(root.join("empty-a.txt"), b"").unwrap();
+        fs::write(root.join("empty-b.txt"), b"").unwrap();
+
+        temp_dir
+    }
+
+    #[tokio::test]
+    async fn test_hash_file_uses_cache_until_file_changes() {
+        let (service, _db_dir) = create_test_service().await;
+        let corpus = create_test_corpus();
+        let path = corpus.path().join("contract.pdf");
+
+        let first = service
+            .hash_file(&path, HashAlgorithm::Blake3)
+            .await
+            .unwrap();
+        assert_eq!(
+            first,
+            ContentHash::of_bytes(HashAlgorithm::Blake3, b"signed contract")
+        );
+
+        let known = service
+            .known_hashes(&[path.to_string_lossy().to_string()], HashAlgorithm::Blake3)
+            .await
+            .unwrap();
+        assert_eq!(known.get(path.to_str().unwrap()), Some(&first));
+
+        fs::write(&path, b"amended contract, longer").unwrap();
+
+        // The stale digest is no longer reported as known
+        let known = service
+            .known_hashes(&[path.to_string_lossy().to_string()], HashAlgorithm::Blake3)
+            .await
+            .unwrap();
+        assert!(known.is_empty());
+
+        let second = service
+            .hash_file(&path, HashAlgorithm::Blake3)
+            .await
+            .unwrap();
+        assert_ne!(first, second);
+    }
+
+    #[tokio::test]
+    async fn test_hash_project_file_stays_in_source_folder() {
+        let (database, _db_dir) = DatabaseConnection::new_temp().await.unwrap();
+        let corpus = create_test_corpus();
+        let projects = Arc::new(MockProjectRepository::new());
+        let project = Project::new(
+            "Hashing".to_string(),
+            corpus
+                .path()
+                .join("attachments")
+                .to_string_lossy()
+                .to_string(),
+            None,
+        )
+        .unwrap();
+        projects.create(&project).await.unwrap();
+        let service = HashingService::new(
+            projects,
+            Arc::new(SqliteFileHashRepository::new(database.pool())),
+        );
+        let project_id = project.id().value();
+
+        let hash = service
+            .hash_project_file(project_id, "contract (1).pdf", HashAlgorithm::Blake3)
+            .await
+            .unwrap();
+        assert_eq!(
+            hash,
+            ContentHash::of_bytes(HashAlgorithm::Blake3, b"signed contract")
+        );
+
+        let outside = service
+            .hash_project_file(project_id, "../draft.pdf", HashAlgorithm::Blake3)
+            .await;
+        assert_eq!(outside.unwrap_err().code, "VALIDATION_ERROR");
+        let absolute = corpus.path().join("draft.pdf");
+        let absolute = service
+            .hash_project_file(
+                project_id,
+                &absolute.to_string_lossy(),
+                HashAlgorithm::Blake3,
+            )
+            .await;
+        assert_eq!(absolute.unwrap_err().code, "VALIDATION_ERROR");
+        let missing = service
+            .hash_project_file(project_id, "gone.pdf", HashAlgorithm::Blake3)
+            .await;
+        assert_eq!(missing.unwrap_err().code, "NOT_FOUND");
+    }
+
+    #[tokio::test]
+    async fn test_find_duplicates_groups_identical_files() {
+        let (service, _db_dir) = create_test_service().await;
+        let corpus = create_test_corpus();
+
+        let report = service
+            .find_duplicates_in_folder("proj_test", corpus.path(), &[], HashAlgorithm::Sha256, None)
+            .await
+            .unwrap();
+
+        assert_eq!(report.files_scanned, 7);
+        assert_eq!(report.groups.len(), 1);
+        assert_eq!(report.groups[0].paths.len(), 3);
+        assert_eq!(report.groups[0].size, 15);
+        assert_eq!(report.total_wasted_bytes, 30);
+        assert!(report.groups[0].hash.starts_with("sha256:"));
+        // Three copies plus the two same-size drafts
+        assert_eq!(report.files_hashed, 5);
+
+        let again = service
+            .find_duplicates_in_folder("proj_test", corpus.path(), &[], HashAlgorithm::Sha256, None)
+            .await
+            .unwrap();
+        assert_eq!(again.files_hashed, 0);
+        assert_eq!(again.groups, report.groups);
+    }
+
+    #[test]
+    fn test_parse_hash_algorithm() {
+        assert_eq!(parse_hash_algorithm(None).unwrap(), HashAlgorithm::Blake3);
+        assert_eq!(
+            parse_hash_algorithm(Some("SHA256")).unwrap(),
+            HashAlgorithm::Sha256
+        );
+        assert!(parse_hash_algorithm(Some("md5")).is_err());
+    }
+}
@@ -0,0 +1,344 @@
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
+
+use crate::application::dtos::{MediaMetadataDto, MediaProbeBatchDto, MediaProbeFailureDto};
+use crate::application::jobs::{JobContext, JobHandler};
+use crate::application::services::project_service::parse_project_id;
+use crate::domain::document::{Document, DocumentId, DocumentRepository};
+use crate::domain::media::{
+    MediaFilter, MediaMetadataRepository, MediaProbe, MediaProbeError, MediaRecord,
+};
+use crate::infrastructure::{AppError, AppResult, MediaProbeRegistry};
+
+/// Job kind for probing all media documents of a project in the background
+pub const PROBE_MEDIA_JOB: &str = "probe_media";
+
+/// Application service for audio and video metadata
+///
+/// Reads duration, codecs, stream properties and embedded tags of tracked
+/// media documents with the built-in probes and stores them for searching.
+/// Each record keeps the content hash it was probed from, so a project run
+/// only probes files whose contents changed.
+pub struct MediaService {
+    document_repository: Arc<dyn DocumentRepository>,
+    media_repository: Arc<dyn MediaMetadataRepository>,
+    registry: Arc<MediaProbeRegistry>,
+}
+
+impl MediaService {
+    /// Create a new MediaService
+    pub fn new(
+        document_repository: Arc<dyn DocumentRepository>,
+        media_repository: Arc<dyn MediaMetadataRepository>,
+        registry: Arc<MediaProbeRegistry>,
+    ) -> Self {
+        MediaService {
+            document_repository,
+            media_repository,
+            registry,
+        }
+    }
+
+    /// File extensions that can be probed
+    pub fn supported_extensions(&self) -> Vec<String> {
+        self.registry.supported_extensions()
+    }
+
+    /// Probe one document, replacing any earlier metadata
+    pub async fn probe_document(&self, document_id: &str) -> AppResult<MediaMetadataDto> {
+        let document = self.find_document(document_id).await?;
+        let probe = self.probe_for(&document)?;
+        self.probe(&document, probe).await
+    }
+
+    /// Probe every present media document of a project
+    ///
+    /// Documents whose stored metadata was read from the current contents
+    /// are skipped unless `force` is set. Failures are collected per file.
+    pub async fn probe_project(
+        &self,
+        project_id: &str,
+        force: bool,
+        context: Option<&JobContext>,
+    ) -> AppResult<MediaProbeBatchDto> {
+        let id = parse_project_id(project_id)?;
+
+        let documents: Vec<Document> = self
+            .document_repository
+            .list_by_project(&id)
+            .await?
+            .into_iter()
+            .filter(|document| !document.is_missing())
+            .collect();
+        let total = documents.len() as u64;
+
+        let mut batch = MediaProbeBatchDto {
+            project_id: project_id.to_string(),
+            ..MediaProbeBatchDto::default()
+        };
+
+        for (index, document) in documents.iter().enumerate() {
+            if let Some(context) = context {
+                context.check_cancelled()?;
+                context
+                    .report_progress(index as u64, Some(total), Some("Reading media metadata"))
+                    .await;
+            }
+
+            let Ok(probe) = self.probe_for(document) else {
+                batch.unsupported += 1;
+                continue;
+            };
+            if !force && self.is_up_to_date(document).await? {
+                batch.up_to_date += 1;
+                continue;
+            }
+
+            match self.probe(document, probe).await {
+                Ok(result) => batch.results.push(result),
+                Err(error) => {
+                    tracing::warn!(
+                        "Probing {} failed: {}",
+                        document.path(),
+                        error.user_message()
+                    );
+                    batch.failed.push(MediaProbeFailureDto {
+                        document_id: document.id().value().to_string(),
+                        path: document.path().to_string(),
+                        error: error.user_message(),
+                    });
+                }
+            }
+        }
+
+        if let Some(context) = context {
+            context.report_progress(total, Some(total), None).await;
+        }
+
+        Ok(batch)
+    }
+
+    /// Stored metadata of a document, if it has been probed
+    pub async fn get_media_metadata(
+        &self,
+        document_id: &str,
+    ) -> AppResult<Option<MediaMetadataDto>> {
+        let document = self.find_document(document_id).await?;
+        let record = self
+            .media_repository
+            .find_by_document(document.id())
+            .await?;
+        Ok(record.map(|record| MediaMetadataDto::new(&record, document.path())))
+    }
+
+    /// Probed media documents of a project matching a filter, longest first
+    pub async fn search_media(
+        &self,
+        project_id: &str,
+        filter: &MediaFilter,
+    ) -> AppResult<Vec<MediaMetadataDto>> {
+        let id = parse_project_id(project_id)?;
+
+        let paths: HashMap<DocumentId, String> = self
+            .document_repository
+            .list_by_project(&id)
+            .await?
+            .into_iter()
+            .map(|document| (document.id().clone(), document.path().to_string()))
+            .collect();
+
+        let records = self.media_repository.search(&id, filter).await?;
+        // Records of documents no longer tracked are left out
+        Ok(records
+            .iter()
+            .filter_map(|record| {
+                paths
+                    .get(record.document_id())
+                    .map(|path| MediaMetadataDto::new(record, path.as_str()))
+            })
+            .collect())
+    }
+
+    async fn find_document(&self, document_id: &str) -> AppResult<Document> {
+        let id = DocumentId::from_string(document_id.to_string()).map_err(|e| {
+            AppError::validation_error("Invalid document ID format", Some(e.to_string()))
+        })?;
+
+        self.document_repository
+            .find_by_id(&id)
+            .await?
+            .ok_or_else(|| AppError::not_found(format!("Document with ID '{}'", document_id)))
+    }
+
+    fn probe_for(&self, document: &Document) -> AppResult<Arc<dyn MediaProbe>> {
+        let extension = Path::new(document.path())
+            .extension()
+            .and_then(|e| e.to_str())
+            .unwrap_or_default()
+            .to_lowercase();
+
+        self.registry
+            .for_extension(&extension)
+            .ok_or_else(|| MediaProbeError::unsupported(extension).into())
+    }
+
+    /// Whether the stored metadata was read from the current contents
+    async fn is_up_to_date(&self, document: &Document) -> AppResult<bool> {
+        let record = self
+            .media_repository
+            .find_by_document(document.id())
+            .await?;
+        Ok(record.is_some_and(|record| record.is_current(&document.content_hash().to_string())))
+    }
+
+    async fn probe(
+        &self,
+        document: &Document,
+        probe: Arc<dyn MediaProbe>,
+    ) -> AppResult<MediaMetadataDto> {
+        let path = document.path().to_string();
+        let method = probe.method();
+
+        // Probes only read headers, so the file is opened rather than loaded
+        let metadata = tokio::task::spawn_blocking(move || {
+            let mut file = std::fs::File::open(&path)?;
+            let size = file.metadata()?.len();
+            probe.probe(&mut file, size)
+        })
+        .await
+        .map_err(|e| AppError::internal_error(format!("Media probe task failed: {}", e)))??;
+
+        let record = MediaRecord::new(
+            document.id().clone(),
+            document.project_id().clone(),
+            document.content_hash().to_string(),
+            method,
+            metadata,
+        );
+        self.media_repository.save(&record).await?;
+
+        tracing::debug!("Probed {} with {}", document.path(), method);
+
+        Ok(MediaMetadataDto::new(&record, document.path()))
+    }
+}
+
+/// Runs project media probing on the background job pool
+///
+/// Accepts `{ "force": bool }` and returns a serialized `MediaProbeBatchDto`.
+pub struct ProbeMediaJobHandler {
+    service: Arc<MediaService>,
+}
+
+impl ProbeMediaJobHandler {
+    pub fn new(service: Arc<MediaService>) -> Self {
+        ProbeMediaJobHandler { service }
+    }
+}
+
+#[async_trait]
+impl JobHandler for ProbeMediaJobHandler {
+    fn kind(&self) -> &'static str {
+        PROBE_MEDIA_JOB
+    }
+
+    fn is_idempotent(&self) -> bool {
+        true
+    }
+
+    async fn run(
+        &self,
+        context: JobContext,
+        params: serde_json::Value,
+    ) -> AppResult<serde_json::Value> {
+        let project_id = context
+            .project_id()
+            .ok_or_else(|| AppError::validation_error("Media probing requires a project", None))?
+            .to_string();
+        let force = params
+            .get("force")
+            .and_then(|f| f.as_bool())
+            .unwrap_or(false);
+
+        let result = self
+            .service
+            .probe_project(&project_id, force, Some(&context))
+            .await?;
+
+        serde_json::to_value(result)
+            .map_err(|e| AppError::internal_error(format!("Failed to serialize result: {}", e)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::services::test_corpus::TestCorpus;
+    use crate::domain::media::MediaKind;
+    use crate::infrastructure::SqliteMediaMetadataRepository;
+
+    async fn create_test_service() -> (MediaService, TestCorpus) {
+        let corpus = TestCorpus::new().await;
+        let service = MediaService::new(
+            corpus.repository.clone(),
+            Arc::new(SqliteMediaMetadataRepository::new(corpus.database.pool())),
+            Arc::new(MediaProbeRegistry::with_defaults()),
+        );
+        (service, corpus)
+    }
+
+    /// A constant bitrate MP3 of `seconds` at 128 kbit/s
+    fn mp3(seconds: usize) -> Vec<u8> {
+        let mut file = vec![0u8; 16_000 * seconds];
+        file[..4].copy_from_slice(&[0xFF, 0xFB, 0x90, 0x00]);
+        file
+    }
+
+    #[tokio::test]
+    async fn test_probe_project_and_search_by_duration() {
+        let (service, corpus) = create_test_service().await;
+        let long = corpus.add_document("interview.mp3", &mp3(660)).await;
+        corpus.add_document("note.mp3", &mp3(30)).await;
+        corpus.add_document("letter.txt", b"Dear Sir").await;
+        corpus.add_document("broken.wav", b"not a wave").await;
+        let project_id = corpus.project_id.value().to_string();
+
+        let first = service
+            .probe_project(&project_id, false, None)
+            .await
+            .unwrap();
+        assert_eq!(first.results.len(), 2);
+        assert_eq!(first.unsupported, 1);
+        assert_eq!(first.failed.len(), 1);
+
+        let second = service
+            .probe_project(&project_id, false, None)
+            .await
+            .unwrap();
+        assert!(second.results.is_empty());
+        assert_eq!(second.up_to_date, 2);
+
+        let longer_than_ten_minutes = MediaFilter {
+            kind: Some(MediaKind::Audio),
+            min_duration_ms: Some(600_000),
+            ..MediaFilter::default()
+        };
+        let found = service
+            .search_media(&project_id, &longer_than_ten_minutes)
+            .await
+            .unwrap();
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].document_id, long.id().value());
+        assert_eq!(found[0].duration_ms, Some(660_000));
+        assert_eq!(found[0].audio_codec.as_deref(), Some("MP3"));
+
+        let stored = service
+            .get_media_metadata(long.id().value())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(stored.method, "mp3-probe-v1");
+    }
+}
@@ -1,5 +1,55 @@
+pub mod annotation_service;
+pub mod anonymization_service;
+pub mod archive_service;
+pub mod category_service;
+pub mod citation_service;
+pub mod coding_service;
+pub mod cost_table_service;
+pub mod derivation_service;
+pub mod document_service;
+pub mod email_service;
+pub mod export_service;
+pub mod extraction_service;
+pub mod file_summary_service;
+pub mod hashing_service;
+pub mod media_service;
+pub mod preview_service;
 pub mod project_service;
+pub mod report_service;
+pub mod review_service;
+pub mod saved_search_service;
+pub mod search_index_service;
+pub mod search_service;
+pub mod snapshot_service;
+#[cfg(test)]
+pub(crate) mod test_corpus;
 pub mod workspace_service;
 
+pub use annotation_service::AnnotationService;
+pub use anonymization_service::AnonymizationService;
+pub use archive_service::ArchiveService;
+pub use category_service::CategoryService;
+pub use citation_service::CitationService;
+pub use coding_service::CodingService;
+pub use cost_table_service::CostTableService;
+pub use derivation_service::{DerivationService, TOOL_VERSION};
+pub use document_service::{
+    DocumentService, ReconcileDocumentsJobHandler, RECONCILE_DOCUMENTS_JOB,
+};
+pub use email_service::{EmailService, ParseEmailsJobHandler, PARSE_EMAILS_JOB};
+pub use export_service::ExportService;
+pub use extraction_service::{
+    ExtractDocumentsJobHandler, ExtractionService, EXTRACT_DOCUMENTS_JOB,
+};
+pub use file_summary_service::FileSummaryService;
+pub use hashing_service::{FindDuplicatesJobHandler, HashingService, FIND_DUPLICATES_JOB};
+pub use media_service::{MediaService, ProbeMediaJobHandler, PROBE_MEDIA_JOB};
+pub use preview_service::PreviewService;
 pub use project_service::{BatchError, BatchResult, ProjectService};
+pub use report_service::ReportService;
+pub use review_service::ReviewService;
+pub use saved_search_service::{SavedSearchService, SAVED_SEARCHES_FOLDER};
+pub use search_index_service::SearchIndexService;
+pub use search_service::SearchService;
+pub use snapshot_service::{CreateSnapshotJobHandler, SnapshotService, CREATE_SNAPSHOT_JOB};
 pub use workspace_service::WorkspaceNavigationService;
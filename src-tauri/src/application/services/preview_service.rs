@@ -0,0 +1,341 @@
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::sync::{Mutex, Semaphore};
+
+use crate::application::dtos::PreviewDto;
+use crate::application::services::HashingService;
+use crate::domain::preview::{PreviewFormat, PreviewGenerator};
+use crate::domain::workspace::value_objects::{ContentHash, HashAlgorithm};
+use crate::infrastructure::{AppError, AppResult, PreviewCache, PreviewGeneratorRegistry};
+
+/// Number of previews generated at the same time
+const PREVIEW_WORKERS: usize = 2;
+
+/// Failed cache keys remembered before the list starts over
+const MAX_REMEMBERED_FAILURES: usize = 1024;
+
+/// Application service for file previews in the explorer
+///
+/// Previews are cached on disk keyed by the content hash of the file and the
+/// generator method, so renamed or copied files share one preview and edited
+/// files get a new one. A request for a preview that is not cached yet
+/// returns at once and starts generating it in the background; directory
+/// listings never wait for previews.
+pub struct PreviewService {
+    hashing_service: Arc<HashingService>,
+    registry: Arc<PreviewGeneratorRegistry>,
+    cache: Arc<PreviewCache>,
+    workers: Arc<Semaphore>,
+    /// Paths whose preview is being generated
+    pending: Mutex<HashSet<PathBuf>>,
+    /// Why generation failed, by cache key, so it is not retried while
+    /// remembered
+    failures: Mutex<HashMap<String, String>>,
+}
+
+impl PreviewService {
+    /// Create a new PreviewService
+    pub fn new(
+        hashing_service: Arc<HashingService>,
+        registry: Arc<PreviewGeneratorRegistry>,
+        cache: Arc<PreviewCache>,
+    ) -> Self {
+        PreviewService {
+            hashing_service,
+            registry,
+            cache,
+            workers: Arc::new(Semaphore::new(PREVIEW_WORKERS)),
+            pending: Mutex::new(HashSet::new()),
+            failures: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Get the preview of a file, starting its generation if needed
+    ///
+    /// Only cached content hashes are consulted here, so the call never
+    /// reads the file. With `include_bytes` the cached image is returned
+    /// inline as well as by path.
+    pub async fn get_preview(
+        self: &Arc<Self>,
+        path: &str,
+        include_bytes: bool,
+    ) -> AppResult<PreviewDto> {
+        let file = PathBuf::from(path);
+        if !file.is_file() {
+            return Err(AppError::not_found(format!("File '{}'", path)));
+        }
+
+        let extension = file
+            .extension()
+            .map(|extension| extension.to_string_lossy().to_lowercase())
+            .unwrap_or_default();
+        let Some(generator) = self.registry.for_extension(&extension) else {
+            return Ok(PreviewDto::unavailable(
+                path,
+                format!("No preview for '.{}' files", extension),
+            ));
+        };
+
+        let known = self
+            .hashing_service
+            .known_hashes(&[path.to_string()], HashAlgorithm::default())
+            .await?;
+        if let Some(hash) = known.get(path) {
+            let key = cache_key(hash, generator.as_ref());
+            if let Some(preview) = self.cached(path, &key, include_bytes).await? {
+                return Ok(preview);
+            }
+            if let Some(message) = self.failures.lock().await.get(&key) {
+                return Ok(PreviewDto::unavailable(path, message.clone()));
+            }
+        }
+
+        self.schedule(file, generator).await;
+        Ok(PreviewDto::pending(path))
+    }
+
+    /// The cached preview for a key, if there is one
+    async fn cached(
+        &self,
+        path: &str,
+        key: &str,
+        include_bytes: bool,
+    ) -> AppResult<Option<PreviewDto>> {
+        let cache = self.cache.clone();
+        let key = key.to_string();
+        let path = path.to_string();
+
+        tokio::task::spawn_blocking(move || -> AppResult<Option<PreviewDto>> {
+            let Some((file, format)) = cache.get(&key) else {
+                return Ok(None);
+            };
+            let read_failed = |e: std::io::Error| {
+                AppError::filesystem_error(format!("Cannot read preview of {}: {}", path, e))
+            };
+
+            if format == PreviewFormat::Text {
+                let text = std::fs::read_to_string(&file).map_err(read_failed)?;
+                return Ok(Some(PreviewDto::snippet(&path, text)));
+            }
+
+            let mut preview = PreviewDto::image(&path, format, file.to_string_lossy().to_string());
+            if include_bytes {
+                preview.image_bytes = Some(std::fs::read(&file).map_err(read_failed)?);
+            }
+            Ok(Some(preview))
+        })
+        .await
+        .map_err(|e| AppError::internal_error(format!("Preview task failed: {}", e)))?
+    }
+
+    /// Generate a preview on a background task unless one is under way
+    async fn schedule(self: &Arc<Self>, file: PathBuf, generator: Arc<dyn PreviewGenerator>) {
+        if !self.pending.lock().await.insert(file.clone()) {
+            return;
+        }
+
+        let service = Arc::clone(self);
+        tokio::spawn(async move {
+            let permit = Arc::clone(&service.workers).acquire_owned().await;
+            if permit.is_ok() {
+                if let Err(error) = service.generate(&file, generator).await {
+                    tracing::warn!(
+                        "Generating preview of {} failed: {}",
+                        file.display(),
+                        error.message
+                    );
+                }
+            }
+            service.pending.lock().await.remove(&file);
+        });
+    }
+
+    /// Hash a file and store its preview in the cache
+    ///
+    /// Files that have no preview are remembered by content, so asking
+    /// again answers at once until the file changes.
+    async fn generate(&self, file: &Path, generator: Arc<dyn PreviewGenerator>) -> AppResult<()> {
+        let hash = self
+            .hashing_service
+            .hash_file(file, HashAlgorithm::default())
+            .await?;
+        let key = cache_key(&hash, generator.as_ref());
+
+        let cache = self.cache.clone();
+        let source = file.to_path_buf();
+        let outcome = {
+            let key = key.clone();
+            tokio::task::spawn_blocking(move || -> AppResult<Result<(), String>> {
+                if cache.get(&key).is_some() {
+                    return Ok(Ok(()));
+                }
+
+                let mut reader = std::fs::File::open(&source).map_err(|e| {
+                    AppError::filesystem_error(format!("Cannot read {}: {}", source.display(), e))
+                })?;
+                let size = reader.metadata().map(|m| m.len()).unwrap_or(0);
+
+                match generator.generate(&mut reader, size) {
+                    Ok(preview) => {
+                        cache.put(&key, &preview).map_err(|e| {
+                            AppError::filesystem_error(format!("Cannot store preview: {}", e))
+                        })?;
+                        Ok(Ok(()))
+                    }
+                    Err(error) => Ok(Err(error.to_string())),
+                }
+            })
+            .await
+            .map_err(|e| AppError::internal_error(format!("Preview task failed: {}", e)))??
+        };
+
+        if let Err(message) = outcome {
+            tracing::debug!("No preview for {}: {}", file.display(), message);
+            remember_failure(&mut *self.failures.lock().await, key, message);
+        }
+        Ok(())
+    }
+}
+
+/// Remember why a preview failed, forgetting earlier failures once
+/// `MAX_REMEMBERED_FAILURES` is reached
+///
+/// A forgotten failure only costs one more attempt at generating it.
+fn remember_failure(failures: &mut HashMap<String, String>, key: String, message: String) {
+    if failures.len() >= MAX_REMEMBERED_FAILURES && !failures.contains_key(&key) {
+        failures.clear();
+    }
+    failures.insert(key, message);
+}
+
+/// Cache key of a file's preview: its content and how the preview was made
+fn cache_key(hash: &ContentHash, generator: &dyn PreviewGenerator) -> String {
+    format!(
+        "{}-{}-{}",
+        hash.algorithm().as_str(),
+        hash.digest(),
+        generator.method()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::project::repositories::MockProjectRepository;
+    use crate::infrastructure::{DatabaseConnection, SqliteFileHashRepository};
+    use std::time::Duration;
+    use tempfile::TempDir;
+
+    async fn create_service(cache_root: PathBuf) -> (Arc<PreviewService>, TempDir) {
+        let (database, db_dir) = DatabaseConnection::new_temp().await.unwrap();
+        let hashing_service = Arc::new(HashingService::new(
+            Arc::new(MockProjectRepository::new()),
+            Arc::new(SqliteFileHashRepository::new(database.pool())),
+        ));
+        let service = PreviewService::new(
+            hashing_service,
+            Arc::new(PreviewGeneratorRegistry::with_defaults()),
+            Arc::new(PreviewCache::new(cache_root, 1024 * 1024)),
+        );
+        (Arc::new(service), db_dir)
+    }
+
+    /// Ask for a preview until it is no longer pending
+    async fn settled(service: &Arc<PreviewService>, path: &str, include_bytes: bool) -> PreviewDto {
+        for _ in 0..200 {
+            let preview = service.get_preview(path, include_bytes).await.unwrap();
+            if preview.status != "pending" {
+                return preview;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        panic!("preview of {} never finished", path);
+    }
+
+    #[tokio::test]
+    async fn test_preview_is_generated_in_background_and_shared_by_content() {
+        let folder = TempDir::new().unwrap();
+        let (service, _db_dir) = create_service(folder.path().join("previews")).await;
+        let notes = folder.path().join("notes.txt");
+        std::fs::write(&notes, "Call with counsel\nAgreed terms").unwrap();
+        let path = notes.to_string_lossy().to_string();
+
+        let first = service.get_preview(&path, false).await.unwrap();
+        assert_eq!(first.status, "pending");
+
+        let ready = settled(&service, &path, false).await;
+        assert!(ready.is_ready());
+        assert_eq!(ready.kind.as_deref(), Some("snippet"));
+        assert_eq!(
+            ready.text.as_deref(),
+            Some("Call with counsel\nAgreed terms")
+        );
+
+        // A copy with the same contents reuses the cached preview
+        let copy = folder.path().join("copy.txt");
+        std::fs::copy(&notes, &copy).unwrap();
+        let copy = copy.to_string_lossy().to_string();
+        assert!(settled(&service, &copy, false).await.is_ready());
+        assert_eq!(
+            std::fs::read_dir(folder.path().join("previews"))
+                .unwrap()
+                .count(),
+            1
+        );
+    }
+
+    #[tokio::test]
+    async fn test_image_preview_and_unavailable_files() {
+        let folder = TempDir::new().unwrap();
+        let (service, _db_dir) = create_service(folder.path().join("previews")).await;
+
+        let gif = folder.path().join("logo.gif");
+        std::fs::write(&gif, b"GIF89a\x01\x00\x01\x00").unwrap();
+        let preview = settled(&service, &gif.to_string_lossy(), true).await;
+        assert_eq!(preview.kind.as_deref(), Some("image"));
+        assert_eq!(preview.media_type.as_deref(), Some("image/gif"));
+        assert!(preview.image_path.unwrap().ends_with(".gif"));
+        assert_eq!(preview.image_bytes.unwrap(), b"GIF89a\x01\x00\x01\x00");
+
+        let binary = folder.path().join("dump.log");
+        std::fs::write(&binary, [0u8, 1, 2, 3]).unwrap();
+        let preview = settled(&service, &binary.to_string_lossy(), false).await;
+        assert_eq!(preview.status, "unavailable");
+        assert!(preview.message.unwrap().contains("does not contain text"));
+
+        let video = folder.path().join("call.mp4");
+        std::fs::write(&video, b"").unwrap();
+        let preview = service
+            .get_preview(&video.to_string_lossy(), false)
+            .await
+            .unwrap();
+        assert_eq!(preview.status, "unavailable");
+
+        let missing = service
+            .get_preview(&folder.path().join("gone.txt").to_string_lossy(), false)
+            .await;
+        assert_eq!(missing.unwrap_err().code, "NOT_FOUND");
+    }
+
+    #[test]
+    fn test_remembered_failures_are_bounded() {
+        let mut failures = HashMap::new();
+        for i in 0..MAX_REMEMBERED_FAILURES {
+            remember_failure(&mut failures, format!("key-{}", i), "broken".to_string());
+        }
+        assert_eq!(failures.len(), MAX_REMEMBERED_FAILURES);
+
+        // Updating a remembered key keeps the others
+        remember_failure(
+            &mut failures,
+            "key-0".to_string(),
+            "still broken".to_string(),
+        );
+        assert_eq!(failures.len(), MAX_REMEMBERED_FAILURES);
+
+        remember_failure(&mut failures, "key-new".to_string(), "broken".to_string());
+        assert_eq!(failures.len(), 1);
+        assert!(failures.contains_key("key-new"));
+    }
+}
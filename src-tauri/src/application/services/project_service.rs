@@ -1,7 +1,8 @@
 use chrono::{DateTime, Utc};
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
-use crate::domain::project::{Project, ProjectId, ProjectRepository};
+use crate::domain::project::{Project, ProjectError, ProjectId, ProjectRepository};
 use crate::infrastructure::{
     AppError, AppResult, CreateProjectRequest, DeleteProjectRequest, ProjectDto, ProjectListDto,
     RepositoryStatsDto, UpdateProjectRequest,
@@ -28,7 +29,12 @@ impl ProjectService {
         let (name, source_folder, note) = request.to_domain_params().map_err(AppError::from)?;
 
         // Create domain object
-        let project = Project::new(name, source_folder, note).map_err(AppError::from)?;
+        let project = Project::new(name, source_folder, note)
+            .and_then(|project| {
+                project
+                    .with_reports_settings(request.get_reports_folder(), request.immutable_source)
+            })
+            .map_err(AppError::from)?;
 
         // Validate project business rules
         project.validate().map_err(AppError::from)?;
@@ -49,8 +55,7 @@ impl ProjectService {
         request.validate().map_err(AppError::from)?;
 
         // Find the existing project
-        let project_id = ProjectId::from_string(request.get_id().to_string())
-            .map_err(|_| AppError::validation_error("Invalid project ID format", None))?;
+        let project_id = parse_project_id(request.get_id())?;
 
         let mut project = self
             .repository
@@ -92,8 +97,7 @@ impl ProjectService {
         request.validate().map_err(AppError::from)?;
 
         // Parse project ID
-        let project_id = ProjectId::from_string(request.get_id().to_string())
-            .map_err(|_| AppError::validation_error("Invalid project ID format", None))?;
+        let project_id = parse_project_id(request.get_id())?;
 
         // Check if project exists
         let exists = self
@@ -118,8 +122,7 @@ impl ProjectService {
     /// Get a project by ID
     pub async fn get_project(&self, id: &str) -> AppResult<Option<ProjectDto>> {
         // Parse project ID
-        let project_id = ProjectId::from_string(id.to_string())
-            .map_err(|_| AppError::validation_error("Invalid project ID format", None))?;
+        let project_id = parse_project_id(id)?;
 
         // Find the project
         let project = self
@@ -338,6 +341,90 @@ impl ProjectService {
     }
 }
 
+/// Parse a project ID received from the frontend
+pub(crate) fn parse_project_id(project_id: &str) -> AppResult<ProjectId> {
+    ProjectId::from_string(project_id.to_string()).map_err(|_| ProjectError::InvalidId.into())
+}
+
+/// Load a project by its ID, failing if there is no such project
+pub(crate) async fn load_project(
+    repository: &dyn ProjectRepository,
+    project_id: &str,
+) -> AppResult<Project> {
+    let id = parse_project_id(project_id)?;
+    repository
+        .find_by_id(&id)
+        .await
+        .map_err(AppError::from)?
+        .ok_or_else(|| {
+            ProjectError::NotFound {
+                id: project_id.to_string(),
+            }
+            .into()
+        })
+}
+
+/// Load a project whose source folder must be readable, as for scanning it
+pub(crate) async fn load_accessible_project(
+    repository: &dyn ProjectRepository,
+    project_id: &str,
+) -> AppResult<Project> {
+    let project = load_project(repository, project_id).await?;
+    if !project.is_source_accessible() {
+        return Err(ProjectError::SourceNotAccessible.into());
+    }
+    Ok(project)
+}
+
+/// A file or folder in a project's source folder, given relative to it
+///
+/// Symbolic links and `..` are resolved first, so a path leading out of
+/// the source folder is rejected.
+pub(crate) fn resolve_source_path(project: &Project, path: &str) -> AppResult<PathBuf> {
+    let source = project.source_folder().value();
+    let root = source.canonicalize().map_err(|e| {
+        AppError::filesystem_error(format!("Cannot read {}: {}", source.display(), e))
+    })?;
+    let resolved = source
+        .join(path)
+        .canonicalize()
+        .map_err(|_| AppError::not_found(format!("Path '{}'", path)))?;
+
+    let relative = resolved.strip_prefix(&root).map_err(|_| {
+        AppError::validation_error(
+            "The path is outside the project's source folder",
+            Some(path.to_string()),
+        )
+    })?;
+    Ok(source.join(relative))
+}
+
+/// Folders inside a project's source folder that hold the app's own output,
+/// such as the default reports folder and the private folder
+///
+/// Scans of the source folder skip them, so reports, exports and the
+/// pseudonym map never count as source documents.
+pub(crate) fn output_folders(project: &Project) -> Vec<PathBuf> {
+    let source = project.source_folder().value();
+    [project.reports_folder(), project.private_folder()]
+        .into_iter()
+        .filter_map(|folder| inside_source(source, folder))
+        .collect()
+}
+
+/// `folder` as a path below `source`, if it lies inside it
+fn inside_source(source: &Path, folder: PathBuf) -> Option<PathBuf> {
+    if folder.starts_with(source) {
+        return Some(folder);
+    }
+
+    // A configured folder may still reach into the source folder by a link
+    let root = source.canonicalize().ok()?;
+    let resolved = folder.canonicalize().ok()?;
+    let relative = resolved.strip_prefix(&root).ok()?;
+    Some(source.join(relative))
+}
+
 /// Result of a batch operation
 #[derive(Debug, Clone)]
 pub struct BatchResult<T> {
@@ -584,6 +671,29 @@ mod tests {
         cleanup_test_folder(&test_folder);
     }
 
+    #[tokio::test]
+    async fn test_load_accessible_project() {
+        let repository = MockProjectRepository::new();
+        let test_folder = setup_test_folder("load_accessible");
+        let project = Project::new("Loaded".to_string(), test_folder.clone(), None).unwrap();
+        repository.add_project(project.clone());
+        let id = project.id().value().to_string();
+
+        let loaded = load_accessible_project(&repository, &id).await.unwrap();
+        assert_eq!(loaded.id(), project.id());
+
+        let invalid = load_project(&repository, "not-an-id").await.unwrap_err();
+        assert_eq!(invalid.code, "VALIDATION_ERROR");
+        let unknown = ProjectId::new().value().to_string();
+        let missing = load_project(&repository, &unknown).await.unwrap_err();
+        assert_eq!(missing.code, "NOT_FOUND");
+
+        cleanup_test_folder(&test_folder);
+        assert!(load_project(&repository, &id).await.is_ok());
+        let moved = load_accessible_project(&repository, &id).await.unwrap_err();
+        assert_eq!(moved.code, "FILESYSTEM_ERROR");
+    }
+
     #[test]
     fn test_batch_result() {
         let batch_result = BatchResult {
@@ -0,0 +1,483 @@
+use std::fs;
+use std::io::ErrorKind;
+use std::path::Path;
+use std::sync::Arc;
+
+use crate::application::dtos::{ReportContentDto, ReportDto, UpdateReportRequest};
+use crate::application::services::project_service::load_accessible_project;
+use crate::application::services::CitationService;
+use crate::domain::det::{DetDocument, DetKind, DetStore, PmNode, ProcessingMetadata};
+use crate::domain::document::DocumentId;
+use crate::domain::project::{Project, ProjectRepository};
+use crate::domain::report::{Report, ReportCategory, ReportId, ReportRepository, ReportStatus};
+use crate::infrastructure::{AppError, AppResult};
+
+/// Application service for managing project reports
+///
+/// Report content is stored as `.det` files below the project's reports
+/// folder, one subfolder per category; the index of titles, statuses and
+/// links lives in the database. Projects with an immutable source keep
+/// their reports folder outside the source, so nothing here writes into it.
+/// Citations in the content are re-indexed whenever it is written.
+pub struct ReportService {
+    project_repository: Arc<dyn ProjectRepository>,
+    report_repository: Arc<dyn ReportRepository>,
+    det_store: Arc<dyn DetStore>,
+    citation_service: Arc<CitationService>,
+}
+
+impl ReportService {
+    /// Create a new ReportService
+    pub fn new(
+        project_repository: Arc<dyn ProjectRepository>,
+        report_repository: Arc<dyn ReportRepository>,
+        det_store: Arc<dyn DetStore>,
+        citation_service: Arc<CitationService>,
+    ) -> Self {
+        ReportService {
+            project_repository,
+            report_repository,
+            det_store,
+            citation_service,
+        }
+    }
+
+    /// Create a draft report, with an empty paragraph unless `content` is given
+    pub async fn create_report(
+        &self,
+        project_id: &str,
+        title: String,
+        category: ReportCategory,
+        content: Option<PmNode>,
+    ) -> AppResult<ReportDto> {
+        let project = load_accessible_project(self.project_repository.as_ref(), project_id).await?;
+        let reports_folder = project.reports_folder();
+
+        let mut report = Report::new(project.id().clone(), title, category, String::new())?;
+        let path = self
+            .unique_path(&project, &reports_folder, category, report.title())
+            .await?;
+        report.move_to(category, path);
+
+        let det = DetDocument::new(
+            DetKind::Report,
+            content.unwrap_or_else(|| PmNode::doc(vec![PmNode::paragraph(Vec::new())])),
+            ProcessingMetadata::completed("manual"),
+        )
+        .with_title(report.title());
+        self.det_store
+            .save(&reports_folder.join(report.path()), &det)
+            .await?;
+        self.report_repository.save(&report).await?;
+        self.citation_service
+            .index_report(&report, det.content())
+            .await?;
+
+        tracing::debug!("Created report {} at {}", report.id(), report.path());
+        Ok(ReportDto::from_report(&report, &reports_folder))
+    }
+
+    /// Get a report with its content
+    pub async fn get_report(&self, report_id: &str) -> AppResult<ReportContentDto> {
+        let report = self.load_report(report_id).await?;
+        let project = load_accessible_project(
+            self.project_repository.as_ref(),
+            report.project_id().value(),
+        )
+        .await?;
+        let reports_folder = project.reports_folder();
+
+        let det = self
+            .det_store
+            .load(&reports_folder.join(report.path()))
+            .await?;
+
+        Ok(ReportContentDto {
+            report: ReportDto::from_report(&report, &reports_folder),
+            content: det.content().clone(),
+        })
+    }
+
+    /// List a project's reports, most recently updated first
+    pub async fn list_reports(
+        &self,
+        project_id: &str,
+        category: Option<ReportCategory>,
+    ) -> AppResult<Vec<ReportDto>> {
+        let project = load_accessible_project(self.project_repository.as_ref(), project_id).await?;
+        let reports_folder = project.reports_folder();
+
+        Ok(self
+            .report_repository
+            .list_by_project(project.id())
+            .await?
+            .iter()
+            .filter(|report| category.is_none_or(|c| report.category() == c))
+            .map(|report| ReportDto::from_report(report, &reports_folder))
+            .collect())
+    }
+
+    /// Apply changes to a report
+    ///
+    /// Setting the status to draft happens before the other changes and
+    /// setting it to final after them, so a final report can be reopened
+    /// and edited, or edited and finalised, in one request.
+    pub async fn update_report(
+        &self,
+        report_id: &str,
+        request: UpdateReportRequest,
+    ) -> AppResult<ReportDto> {
+        let mut report = self.load_report(report_id).await?;
+        let project = load_accessible_project(
+            self.project_repository.as_ref(),
+            report.project_id().value(),
+        )
+        .await?;
+        let reports_folder = project.reports_folder();
+
+        let status = request
+            .status
+            .as_deref()
+            .map(ReportStatus::parse)
+            .transpose()?;
+        let category = request
+            .category
+            .as_deref()
+            .map(ReportCategory::parse)
+            .transpose()?;
+        let source_documents = request
+            .source_documents
+            .map(|ids| {
+                ids.into_iter()
+                    .map(DocumentId::from_string)
+                    .collect::<Result<Vec<_>, _>>()
+            })
+            .transpose()
+            .map_err(|e| AppError::validation_error("Invalid document ID", Some(e.to_string())))?;
+
+        if status == Some(ReportStatus::Draft) {
+            report.set_status(ReportStatus::Draft);
+        }
+
+        let mut edited_content = None;
+        if request.title.is_some() || request.content.is_some() {
+            if let Some(title) = request.title {
+                report.rename(title)?;
+            }
+            if request.content.is_some() {
+                report.record_content_change()?;
+            }
+
+            let det_path = reports_folder.join(report.path());
+            let mut det = self.det_store.load(&det_path).await?;
+            det.set_title(Some(report.title().to_string()));
+            if let Some(content) = request.content {
+                det.set_content(content);
+                edited_content = Some(det.content().clone());
+            }
+            self.det_store.save(&det_path, &det).await?;
+        }
+
+        if let Some(documents) = source_documents {
+            report.set_source_documents(documents);
+        }
+        if let Some(derivatives) = request.derivatives {
+            report.set_derivatives(derivatives);
+        }
+        if status == Some(ReportStatus::Final) {
+            report.set_status(ReportStatus::Final);
+        }
+
+        let moved_from = match category {
+            Some(category) if category != report.category() => {
+                let from = report.path().to_string();
+                let to = self
+                    .unique_path(&project, &reports_folder, category, report.title())
+                    .await?;
+                move_file(&reports_folder.join(&from), &reports_folder.join(&to))?;
+                report.move_to(category, to);
+                Some(from)
+            }
+            _ => None,
+        };
+
+        if let Err(error) = self.report_repository.save(&report).await {
+            if let Some(from) = moved_from {
+                let _ = fs::rename(
+                    reports_folder.join(report.path()),
+                    reports_folder.join(from),
+                );
+            }
+            return Err(error.into());
+        }
+        if let Some(content) = edited_content {
+            self.citation_service
+                .index_report(&report, &content)
+                .await?;
+        }
+
+        Ok(ReportDto::from_report(&report, &reports_folder))
+    }
+
+    /// Delete a report from the index, and its file unless `keep_file` is set
+    pub async fn delete_report(&self, report_id: &str, keep_file: bool) -> AppResult<()> {
+        let report = self.load_report(report_id).await?;
+        let project = load_accessible_project(
+            self.project_repository.as_ref(),
+            report.project_id().value(),
+        )
+        .await?;
+
+        self.report_repository.delete(report.id()).await?;
+
+        if !keep_file {
+            let path = project.reports_folder().join(report.path());
+            match fs::remove_file(&path) {
+                Err(e) if e.kind() != ErrorKind::NotFound => {
+                    return Err(AppError::filesystem_error(format!(
+                        "Report was removed from the index but {} could not be deleted: {}",
+                        path.display(),
+                        e
+                    )));
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    /// Relative path for a new report that clashes with no indexed report or file
+    async fn unique_path(
+        &self,
+        project: &Project,
+        reports_folder: &Path,
+        category: ReportCategory,
+        title: &str,
+    ) -> AppResult<String> {
+        let mut suffix = None;
+        loop {
+            let path = Report::path_for(category, title, suffix);
+            if !self
+                .report_repository
+                .path_exists(project.id(), &path)
+                .await?
+                && !reports_folder.join(&path).exists()
+            {
+                return Ok(path);
+            }
+            suffix = Some(suffix.map_or(2, |n| n + 1));
+        }
+    }
+
+    async fn load_report(&self, report_id: &str) -> AppResult<Report> {
+        let id = ReportId::from_string(report_id.to_string())?;
+
+        self.report_repository
+            .find_by_id(&id)
+            .await?
+            .ok_or_else(|| AppError::not_found(format!("Report with ID '{}'", report_id)))
+    }
+}
+
+/// Move a report file, creating the destination folder
+fn move_file(from: &Path, to: &Path) -> AppResult<()> {
+    let io_error = |e: std::io::Error| {
+        AppError::filesystem_error(format!(
+            "Cannot move {} to {}: {}",
+            from.display(),
+            to.display(),
+            e
+        ))
+    };
+
+    if let Some(folder) = to.parent() {
+        fs::create_dir_all(folder).map_err(io_error)?;
+    }
+    fs::rename(from, to).map_err(io_error)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::export::CitedSource;
+    use crate::infrastructure::{
+        DatabaseConnection, FileDetStore, SqliteCitationRepository, SqliteDocumentRepository,
+        SqliteProjectRepository, SqliteReportRepository,
+    };
+    use tempfile::TempDir;
+
+    struct Fixture {
+        service: ReportService,
+        citations: Arc<CitationService>,
+        project: Project,
+        _source: TempDir,
+        _db_dir: TempDir,
+    }
+
+    async fn create_fixture(reports_folder: Option<&Path>) -> Fixture {
+        let (database, db_dir) = DatabaseConnection::new_temp().await.unwrap();
+        let projects = Arc::new(SqliteProjectRepository::new(database.pool()));
+        let source = TempDir::new().unwrap();
+        let project = Project::new(
+            "Reports".to_string(),
+            source.path().to_string_lossy().to_string(),
+            None,
+        )
+        .unwrap()
+        .with_reports_settings(
+            reports_folder.map(|folder| folder.to_string_lossy().to_string()),
+            reports_folder.is_some(),
+        )
+        .unwrap();
+        projects.create(&project).await.unwrap();
+
+        let reports = Arc::new(SqliteReportRepository::new(database.pool()));
+        let store = Arc::new(FileDetStore::new());
+        let citations = Arc::new(CitationService::new(
+            projects.clone(),
+            reports.clone(),
+            Arc::new(SqliteCitationRepository::new(database.pool())),
+            Arc::new(SqliteDocumentRepository::new(database.pool())),
+            store.clone(),
+            db_dir.path().join("derivatives"),
+        ));
+
+        Fixture {
+            service: ReportService::new(projects, reports, store, citations.clone()),
+            citations,
+            project,
+            _source: source,
+            _db_dir: db_dir,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_create_update_and_delete_report() {
+        let fixture = create_fixture(None).await;
+        let project_id = fixture.project.id().value();
+        let reports_folder = fixture.project.reports_folder();
+
+        let first = fixture
+            .service
+            .create_report(
+                project_id,
+                "Q3 Costs".to_string(),
+                ReportCategory::Findings,
+                None,
+            )
+            .await
+            .unwrap();
+        let second = fixture
+            .service
+            .create_report(
+                project_id,
+                "Q3 Costs".to_string(),
+                ReportCategory::Findings,
+                None,
+            )
+            .await
+            .unwrap();
+        assert_eq!(first.relative_path, "findings/q3-costs.det");
+        assert_eq!(second.relative_path, "findings/q3-costs-2.det");
+        assert!(reports_folder.join(&first.relative_path).is_file());
+
+        let cited = CitedSource::new("/corpus/invoice.pdf").with_page(2);
+        let content = PmNode::doc(vec![PmNode::paragraph(PmNode::text_nodes(
+            "Costs rose.",
+            vec![cited.to_mark()],
+        ))]);
+        let updated = fixture
+            .service
+            .update_report(
+                &first.id,
+                UpdateReportRequest {
+                    content: Some(content.clone()),
+                    category: Some("client_report".to_string()),
+                    status: Some("final".to_string()),
+                    ..UpdateReportRequest::default()
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(updated.relative_path, "client-reports/q3-costs.det");
+        assert_eq!(updated.status, "final");
+        assert!(!reports_folder.join(&first.relative_path).exists());
+        let loaded = fixture.service.get_report(&first.id).await.unwrap();
+        assert_eq!(loaded.content, content);
+        let citations = fixture
+            .citations
+            .resolve_report_citations(&first.id)
+            .await
+            .unwrap();
+        assert_eq!(citations.len(), 1);
+        assert_eq!(citations[0].cited_path, "/corpus/invoice.pdf");
+
+        let error = fixture
+            .service
+            .update_report(
+                &first.id,
+                UpdateReportRequest {
+                    title: Some("Renamed".to_string()),
+                    ..UpdateReportRequest::default()
+                },
+            )
+            .await
+            .unwrap_err();
+        assert_eq!(error.code, "CONFLICT");
+
+        let clients = fixture
+            .service
+            .list_reports(project_id, Some(ReportCategory::ClientReport))
+            .await
+            .unwrap();
+        assert_eq!(clients.len(), 1);
+
+        fixture
+            .service
+            .delete_report(&first.id, false)
+            .await
+            .unwrap();
+        assert!(!reports_folder.join(&updated.relative_path).exists());
+        fixture
+            .service
+            .delete_report(&second.id, true)
+            .await
+            .unwrap();
+        assert!(reports_folder.join(&second.relative_path).exists());
+        assert!(fixture
+            .service
+            .list_reports(project_id, None)
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_immutable_source_keeps_reports_outside_source() {
+        let reports = TempDir::new().unwrap();
+        let fixture = create_fixture(Some(reports.path())).await;
+
+        let report = fixture
+            .service
+            .create_report(
+                fixture.project.id().value(),
+                "Summary".to_string(),
+                ReportCategory::AnalysisSummary,
+                None,
+            )
+            .await
+            .unwrap();
+
+        assert!(Path::new(&report.path).starts_with(reports.path()));
+        assert!(reports
+            .path()
+            .join("analysis-summaries/summary.det")
+            .is_file());
+        assert_eq!(
+            fs::read_dir(fixture.project.source_folder().value())
+                .unwrap()
+                .count(),
+            0
+        );
+    }
+}
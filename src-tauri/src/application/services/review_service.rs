@@ -0,0 +1,730 @@
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use std::sync::Arc;
+
+use crate::application::dtos::{ReviewBatchDto, ReviewerDto, ReviewerStatsDto};
+use crate::application::services::project_service::{load_project, resolve_source_path};
+use crate::application::services::{CategoryService, SearchService};
+use crate::domain::annotation::AnnotationRepository;
+use crate::domain::coding::CodingRepository;
+use crate::domain::document::{Document, DocumentId, DocumentRepository};
+use crate::domain::project::{Project, ProjectId, ProjectRepository};
+use crate::domain::review::{
+    BatchSource, ReviewBatch, ReviewBatchId, ReviewRepository, Reviewer, ReviewerId,
+};
+use crate::infrastructure::{AppError, AppResult};
+
+/// Application service for reviewer profiles and review batches
+///
+/// Several reviewers can share one machine; the active reviewer is
+/// recorded on coding decisions and annotations when no other is given.
+/// A corpus is split into batches built from a folder, a search or a
+/// category. A document is in at most one open batch at a time, so
+/// reviewers do not code the same documents twice.
+pub struct ReviewService {
+    project_repository: Arc<dyn ProjectRepository>,
+    document_repository: Arc<dyn DocumentRepository>,
+    review_repository: Arc<dyn ReviewRepository>,
+    coding_repository: Arc<dyn CodingRepository>,
+    annotation_repository: Arc<dyn AnnotationRepository>,
+    search_service: Arc<SearchService>,
+    category_service: Arc<CategoryService>,
+}
+
+impl ReviewService {
+    /// Create a new ReviewService
+    pub fn new(
+        project_repository: Arc<dyn ProjectRepository>,
+        document_repository: Arc<dyn DocumentRepository>,
+        review_repository: Arc<dyn ReviewRepository>,
+        coding_repository: Arc<dyn CodingRepository>,
+        annotation_repository: Arc<dyn AnnotationRepository>,
+        search_service: Arc<SearchService>,
+        category_service: Arc<CategoryService>,
+    ) -> Self {
+        ReviewService {
+            project_repository,
+            document_repository,
+            review_repository,
+            coding_repository,
+            annotation_repository,
+            search_service,
+            category_service,
+        }
+    }
+
+    /// Add a reviewer profile
+    pub async fn create_reviewer(&self, name: String) -> AppResult<ReviewerDto> {
+        let reviewer = Reviewer::new(name)?;
+        self.review_repository.save_reviewer(&reviewer).await?;
+        Ok(ReviewerDto::from_reviewer(&reviewer, false))
+    }
+
+    /// All reviewer profiles, by name
+    pub async fn list_reviewers(&self) -> AppResult<Vec<ReviewerDto>> {
+        let active = self.active_reviewer_id().await?;
+        Ok(self
+            .review_repository
+            .list_reviewers()
+            .await?
+            .iter()
+            .map(|reviewer| {
+                ReviewerDto::from_reviewer(reviewer, Some(reviewer.id()) == active.as_ref())
+            })
+            .collect())
+    }
+
+    /// Switch the active reviewer, or clear it with `None`
+    pub async fn set_active_reviewer(
+        &self,
+        reviewer_id: Option<String>,
+    ) -> AppResult<Option<ReviewerDto>> {
+        let reviewer = match reviewer_id.filter(|id| !id.trim().is_empty()) {
+            Some(id) => Some(self.load_reviewer(&id).await?),
+            None => None,
+        };
+        self.review_repository
+            .set_active_reviewer(reviewer.as_ref().map(Reviewer::id))
+            .await?;
+        Ok(reviewer.map(|reviewer| ReviewerDto::from_reviewer(&reviewer, true)))
+    }
+
+    /// The reviewer currently working on this machine, if any
+    pub async fn get_active_reviewer(&self) -> AppResult<Option<ReviewerDto>> {
+        Ok(self
+            .review_repository
+            .active_reviewer()
+            .await?
+            .map(|reviewer| ReviewerDto::from_reviewer(&reviewer, true)))
+    }
+
+    /// Create batches of the documents from `source` that are not in
+    /// another open batch, in path order
+    ///
+    /// With no reviewers one unassigned batch is made. With several, the
+    /// documents are split into even runs, one batch per reviewer, named
+    /// after the reviewer.
+    pub async fn create_review_batches(
+        &self,
+        project_id: &str,
+        name: String,
+        source: BatchSource,
+        reviewer_ids: Vec<String>,
+    ) -> AppResult<Vec<ReviewBatchDto>> {
+        let project = load_project(self.project_repository.as_ref(), project_id).await?;
+        let mut reviewers = Vec::with_capacity(reviewer_ids.len());
+        for id in &reviewer_ids {
+            reviewers.push(self.load_reviewer(id).await?);
+        }
+
+        let batches = self.review_repository.list_batches(project.id()).await?;
+        let taken: HashSet<&DocumentId> = batches
+            .iter()
+            .filter(|batch| !batch.is_closed())
+            .flat_map(|batch| batch.document_ids())
+            .collect();
+        let documents: Vec<DocumentId> = self
+            .source_documents(&project, &source)
+            .await?
+            .into_iter()
+            .filter(|id| !taken.contains(id))
+            .collect();
+        if documents.is_empty() {
+            return Err(AppError::validation_error(
+                "No documents left for the batch",
+                Some("Every matching document is already in an open batch".to_string()),
+            ));
+        }
+
+        let mut created = Vec::new();
+        if reviewers.is_empty() {
+            created.push(ReviewBatch::new(
+                project.id().clone(),
+                name,
+                source,
+                documents,
+            )?);
+        } else {
+            let chunks = split_evenly(documents, reviewers.len());
+            for (reviewer, chunk) in reviewers.iter().zip(chunks) {
+                let batch_name = if reviewers.len() == 1 {
+                    name.clone()
+                } else {
+                    format!("{} ({})", name.trim(), reviewer.name())
+                };
+                let mut batch =
+                    ReviewBatch::new(project.id().clone(), batch_name, source.clone(), chunk)?;
+                batch.assign(Some(reviewer.id().clone()))?;
+                created.push(batch);
+            }
+        }
+
+        for batch in &created {
+            self.review_repository.save_batch(batch).await?;
+        }
+        self.batch_dtos(project.id(), &created).await
+    }
+
+    /// Hand a batch to a reviewer, or take it back with `None`
+    pub async fn assign_review_batch(
+        &self,
+        batch_id: &str,
+        reviewer_id: Option<String>,
+    ) -> AppResult<ReviewBatchDto> {
+        let mut batch = self.load_batch(batch_id).await?;
+        let reviewer = match reviewer_id.filter(|id| !id.trim().is_empty()) {
+            Some(id) => Some(self.load_reviewer(&id).await?),
+            None => None,
+        };
+
+        batch.assign(reviewer.map(|reviewer| reviewer.id().clone()))?;
+        self.review_repository.save_batch(&batch).await?;
+        self.batch_dto(&batch).await
+    }
+
+    /// The batches of a project, oldest first
+    pub async fn list_review_batches(&self, project_id: &str) -> AppResult<Vec<ReviewBatchDto>> {
+        let project = load_project(self.project_repository.as_ref(), project_id).await?;
+        let batches = self.review_repository.list_batches(project.id()).await?;
+        self.batch_dtos(project.id(), &batches).await
+    }
+
+    /// Even out the work left in the open batches of a project
+    ///
+    /// Coded documents stay where they are. The uncoded ones are pooled in
+    /// path order and dealt back in runs, so every open batch has the same
+    /// number of documents left, give or take one.
+    pub async fn rebalance_review_batches(
+        &self,
+        project_id: &str,
+    ) -> AppResult<Vec<ReviewBatchDto>> {
+        let project = load_project(self.project_repository.as_ref(), project_id).await?;
+        let coded = self.coded_documents(project.id()).await?;
+        let paths: HashMap<DocumentId, String> = self
+            .document_repository
+            .list_by_project(project.id())
+            .await?
+            .into_iter()
+            .map(|document| (document.id().clone(), document.path().to_string()))
+            .collect();
+
+        let mut batches: Vec<ReviewBatch> = self
+            .review_repository
+            .list_batches(project.id())
+            .await?
+            .into_iter()
+            .filter(|batch| !batch.is_closed())
+            .collect();
+
+        let mut pool: Vec<DocumentId> = batches
+            .iter()
+            .flat_map(|batch| batch.document_ids())
+            .filter(|id| !coded.contains(*id))
+            .cloned()
+            .collect();
+        pool.sort_by(|a, b| paths.get(a).cmp(&paths.get(b)).then_with(|| a.cmp(b)));
+
+        let chunks = split_evenly(pool, batches.len());
+        for (batch, chunk) in batches.iter_mut().zip(chunks) {
+            let mut documents: Vec<DocumentId> = batch
+                .document_ids()
+                .iter()
+                .filter(|id| coded.contains(*id))
+                .cloned()
+                .collect();
+            documents.extend(chunk);
+            if documents != batch.document_ids() {
+                batch.set_documents(documents)?;
+                self.review_repository.save_batch(batch).await?;
+            }
+        }
+
+        self.batch_dtos(project.id(), &batches).await
+    }
+
+    /// Close a batch; its uncoded documents may then go into new batches
+    pub async fn close_review_batch(&self, batch_id: &str) -> AppResult<ReviewBatchDto> {
+        let mut batch = self.load_batch(batch_id).await?;
+        batch.close()?;
+        self.review_repository.save_batch(&batch).await?;
+        self.batch_dto(&batch).await
+    }
+
+    /// Throughput of every reviewer in a project
+    ///
+    /// Decisions and annotations record the name of the reviewer profile
+    /// they were made under (see `resolve_reviewer`).
+    pub async fn get_reviewer_stats(&self, project_id: &str) -> AppResult<Vec<ReviewerStatsDto>> {
+        let project = load_project(self.project_repository.as_ref(), project_id).await?;
+        let active = self.active_reviewer_id().await?;
+        let batches = self.review_repository.list_batches(project.id()).await?;
+        let decisions = self.coding_repository.decisions(project.id()).await?;
+        let annotations = self
+            .annotation_repository
+            .list_by_project(project.id())
+            .await?;
+        let coded: HashSet<DocumentId> = decisions
+            .iter()
+            .map(|decision| decision.document_id().clone())
+            .collect();
+
+        let mut stats = Vec::new();
+        for reviewer in self.review_repository.list_reviewers().await? {
+            let name = reviewer.name();
+            let mut entry = ReviewerStatsDto {
+                reviewer_id: reviewer.id().value().to_string(),
+                name: reviewer.name().to_string(),
+                active: Some(reviewer.id()) == active.as_ref(),
+                ..ReviewerStatsDto::default()
+            };
+
+            for batch in batches
+                .iter()
+                .filter(|batch| batch.reviewer_id() == Some(reviewer.id()))
+            {
+                if batch.is_closed() {
+                    entry.closed_batches += 1;
+                } else {
+                    entry.open_batches += 1;
+                    entry.assigned_documents += batch.document_ids().len();
+                    entry.coded_assigned_documents += batch
+                        .document_ids()
+                        .iter()
+                        .filter(|id| coded.contains(*id))
+                        .count();
+                }
+            }
+
+            let own: Vec<_> = decisions
+                .iter()
+                .filter(|decision| decision.reviewer() == name)
+                .collect();
+            entry.decisions = own.len();
+            entry.documents_coded = own
+                .iter()
+                .map(|decision| decision.document_id())
+                .collect::<HashSet<_>>()
+                .len();
+            for decision in &own {
+                *entry
+                    .decisions_by_day
+                    .entry(decision.decided_at().format("%Y-%m-%d").to_string())
+                    .or_default() += 1;
+            }
+            if let (Some(first), Some(last)) = (own.first(), own.last()) {
+                entry.first_decision_at = Some(first.decided_at().to_rfc3339());
+                entry.last_decision_at = Some(last.decided_at().to_rfc3339());
+                let minutes = (last.decided_at() - first.decided_at()).num_seconds() as f64 / 60.0;
+                if minutes >= 1.0 {
+                    entry.decisions_per_hour = Some(own.len() as f64 * 60.0 / minutes);
+                }
+            }
+
+            entry.annotations = annotations
+                .iter()
+                .filter(|annotation| annotation.author() == name)
+                .count();
+            stats.push(entry);
+        }
+
+        Ok(stats)
+    }
+
+    /// The documents a source covers, in path order; missing documents and
+    /// documents of other projects are left out
+    async fn source_documents(
+        &self,
+        project: &Project,
+        source: &BatchSource,
+    ) -> AppResult<Vec<DocumentId>> {
+        let mut documents: Vec<Document> = self
+            .document_repository
+            .list_by_project(project.id())
+            .await?
+            .into_iter()
+            .filter(|document| !document.is_missing())
+            .collect();
+        documents.sort_by(|a, b| a.path().cmp(b.path()));
+
+        let selected: HashSet<String> = match source {
+            BatchSource::Folder { path } => {
+                let folder = resolve_source_path(project, path)?;
+                return Ok(documents
+                    .iter()
+                    .filter(|document| Path::new(document.path()).starts_with(&folder))
+                    .map(|document| document.id().clone())
+                    .collect());
+            }
+            BatchSource::Search { query } => self
+                .search_service
+                .matching_documents(project, query)
+                .await?
+                .into_iter()
+                .map(|id| id.value().to_string())
+                .collect(),
+            BatchSource::Category {
+                category_id,
+                include_descendants,
+            } => self
+                .category_service
+                .list_documents_by_category(category_id, *include_descendants)
+                .await?
+                .into_iter()
+                .map(|document| document.id)
+                .collect(),
+        };
+
+        Ok(documents
+            .iter()
+            .filter(|document| selected.contains(document.id().value()))
+            .map(|document| document.id().clone())
+            .collect())
+    }
+
+    async fn batch_dto(&self, batch: &ReviewBatch) -> AppResult<ReviewBatchDto> {
+        let mut dtos = self
+            .batch_dtos(batch.project_id(), std::slice::from_ref(batch))
+            .await?;
+        Ok(dtos.remove(0))
+    }
+
+    async fn batch_dtos(
+        &self,
+        project_id: &ProjectId,
+        batches: &[ReviewBatch],
+    ) -> AppResult<Vec<ReviewBatchDto>> {
+        let coded = self.coded_documents(project_id).await?;
+        let names: HashMap<ReviewerId, String> = self
+            .review_repository
+            .list_reviewers()
+            .await?
+            .into_iter()
+            .map(|reviewer| (reviewer.id().clone(), reviewer.name().to_string()))
+            .collect();
+
+        Ok(batches
+            .iter()
+            .map(|batch| {
+                ReviewBatchDto::from_batch(
+                    batch,
+                    batch.reviewer_id().and_then(|id| names.get(id).cloned()),
+                    batch
+                        .document_ids()
+                        .iter()
+                        .filter(|id| coded.contains(*id))
+                        .count(),
+                )
+            })
+            .collect())
+    }
+
+    async fn coded_documents(&self, project_id: &ProjectId) -> AppResult<HashSet<DocumentId>> {
+        Ok(self
+            .coding_repository
+            .current_decisions(project_id)
+            .await?
+            .iter()
+            .map(|decision| decision.document_id().clone())
+            .collect())
+    }
+
+    async fn active_reviewer_id(&self) -> AppResult<Option<ReviewerId>> {
+        Ok(self
+            .review_repository
+            .active_reviewer()
+            .await?
+            .map(|reviewer| reviewer.id().clone()))
+    }
+
+    async fn load_reviewer(&self, reviewer_id: &str) -> AppResult<Reviewer> {
+        let id = ReviewerId::from_string(reviewer_id.to_string())?;
+        self.review_repository
+            .find_reviewer(&id)
+            .await?
+            .ok_or_else(|| AppError::not_found(format!("Reviewer with ID '{}'", id)))
+    }
+
+    async fn load_batch(&self, batch_id: &str) -> AppResult<ReviewBatch> {
+        let id = ReviewBatchId::from_string(batch_id.to_string())?;
+        self.review_repository
+            .find_batch(&id)
+            .await?
+            .ok_or_else(|| AppError::not_found(format!("Review batch with ID '{}'", id)))
+    }
+}
+
+/// The reviewer to record on a decision or annotation: the profile `given`
+/// by ID or name when it is not blank, otherwise the active reviewer
+///
+/// The profile's own name is returned. Without reviewer profiles `given`
+/// is recorded as it is.
+pub(crate) async fn resolve_reviewer(
+    review_repository: Option<&Arc<dyn ReviewRepository>>,
+    given: Option<String>,
+) -> AppResult<String> {
+    let given = given.filter(|name| !name.trim().is_empty());
+    let Some(repository) = review_repository else {
+        return given.ok_or_else(|| {
+            AppError::validation_error(
+                "No reviewer given",
+                Some("Choose an active reviewer first".to_string()),
+            )
+        });
+    };
+
+    if let Some(given) = given {
+        let wanted = given.trim().to_lowercase();
+        return repository
+            .list_reviewers()
+            .await?
+            .into_iter()
+            .find(|reviewer| {
+                reviewer.id().value() == given.trim() || reviewer.name().to_lowercase() == wanted
+            })
+            .map(|reviewer| reviewer.name().to_string())
+            .ok_or_else(|| AppError::not_found(format!("Reviewer '{}'", given)));
+    }
+    let active = repository.active_reviewer().await?;
+    active
+        .map(|reviewer| reviewer.name().to_string())
+        .ok_or_else(|| {
+            AppError::validation_error(
+                "No reviewer given",
+                Some("Choose an active reviewer first".to_string()),
+            )
+        })
+}
+
+/// Split `items` into `parts` runs whose lengths differ by at most one,
+/// the longer runs first
+fn split_evenly<T>(items: Vec<T>, parts: usize) -> Vec<Vec<T>> {
+    if parts == 0 {
+        return Vec::new();
+    }
+    let (size, extra) = (items.len() / parts, items.len() % parts);
+    let mut items = items.into_iter();
+    (0..parts)
+        .map(|index| {
+            items
+                .by_ref()
+                .take(size + usize::from(index < extra))
+                .collect()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::services::SearchIndexService;
+    use crate::domain::annotation::{Annotation, AnnotationAnchor};
+    use crate::domain::coding::{CodingDecision, CodingValue, CodingValues};
+    use crate::domain::project::repositories::MockProjectRepository;
+    use crate::domain::review::BatchStatus;
+    use crate::domain::workspace::value_objects::{ContentHash, HashAlgorithm};
+    use crate::infrastructure::{
+        DatabaseConnection, FileDetStore, SqliteAnnotationRepository, SqliteCategoryRepository,
+        SqliteCodingRepository, SqliteDerivationRepository, SqliteDocumentRepository,
+        SqliteMediaMetadataRepository, SqliteReportRepository, SqliteReviewRepository,
+        SqliteSearchIndexRepository,
+    };
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_split_rebalance_and_close_batches() {
+        let (database, db_dir) = DatabaseConnection::new_temp().await.unwrap();
+        let source = TempDir::new().unwrap();
+        let project = Project::new(
+            "Case".to_string(),
+            source.path().to_string_lossy().to_string(),
+            None,
+        )
+        .unwrap();
+        let project_repository = Arc::new(MockProjectRepository::new());
+        project_repository.create(&project).await.unwrap();
+        let document_repository = Arc::new(SqliteDocumentRepository::new(database.pool()));
+        let review_repository: Arc<dyn ReviewRepository> =
+            Arc::new(SqliteReviewRepository::new(database.pool()));
+        let coding_repository = Arc::new(SqliteCodingRepository::new(database.pool()));
+        let annotation_repository = Arc::new(SqliteAnnotationRepository::new(database.pool()));
+        let report_repository = Arc::new(SqliteReportRepository::new(database.pool()));
+        let search_index = Arc::new(SearchIndexService::new(
+            document_repository.clone(),
+            report_repository.clone(),
+            Arc::new(SqliteSearchIndexRepository::new(database.pool())),
+            Arc::new(FileDetStore::new()),
+            db_dir.path().join("derivatives"),
+        ));
+        let search_service = Arc::new(SearchService::new(
+            project_repository.clone(),
+            document_repository.clone(),
+            Arc::new(SqliteDerivationRepository::new(database.pool())),
+            report_repository,
+            Arc::new(SqliteMediaMetadataRepository::new(database.pool())),
+            search_index,
+            db_dir.path().join("derivatives"),
+        ));
+        let category_service = Arc::new(CategoryService::new(
+            project_repository.clone(),
+            document_repository.clone(),
+            Arc::new(SqliteCategoryRepository::new(database.pool())),
+        ));
+        let service = ReviewService::new(
+            project_repository,
+            document_repository.clone(),
+            review_repository.clone(),
+            coding_repository.clone(),
+            annotation_repository.clone(),
+            search_service,
+            category_service,
+        );
+        let project_id = project.id().value();
+
+        let documents: Vec<Document> = [
+            "mail/1.eml",
+            "mail/2.eml",
+            "mail/3.eml",
+            "mail/4.eml",
+            "mail/5.eml",
+            "contracts/a.pdf",
+        ]
+        .iter()
+        .map(|name| {
+            Document::new(
+                project.id().clone(),
+                source.path().join(name).to_string_lossy().to_string(),
+                ContentHash::of_bytes(HashAlgorithm::Blake3, name.as_bytes()),
+                1,
+                0,
+            )
+        })
+        .collect();
+        document_repository.save_all(&documents).await.unwrap();
+        std::fs::create_dir(source.path().join("mail")).unwrap();
+
+        let ana = service.create_reviewer("Ana".to_string()).await.unwrap();
+        let ben = service.create_reviewer("Ben".to_string()).await.unwrap();
+        assert!(service.create_reviewer("ANA".to_string()).await.is_err());
+        assert!(resolve_reviewer(Some(&review_repository), None)
+            .await
+            .is_err());
+        service
+            .set_active_reviewer(Some(ben.id.clone()))
+            .await
+            .unwrap();
+        assert_eq!(
+            resolve_reviewer(Some(&review_repository), Some(" ".to_string()))
+                .await
+                .unwrap(),
+            "Ben"
+        );
+        assert_eq!(
+            resolve_reviewer(Some(&review_repository), Some("ana".to_string()))
+                .await
+                .unwrap(),
+            "Ana"
+        );
+        assert_eq!(
+            resolve_reviewer(Some(&review_repository), Some(ana.id.clone()))
+                .await
+                .unwrap(),
+            "Ana"
+        );
+        assert_eq!(
+            resolve_reviewer(Some(&review_repository), Some("Zoe".to_string()))
+                .await
+                .unwrap_err()
+                .code,
+            "NOT_FOUND"
+        );
+
+        let mail = BatchSource::Folder {
+            path: "mail".to_string(),
+        };
+        let batches = service
+            .create_review_batches(
+                project_id,
+                "Mail".to_string(),
+                mail.clone(),
+                vec![ana.id.clone(), ben.id.clone()],
+            )
+            .await
+            .unwrap();
+        assert_eq!(batches.len(), 2);
+        assert_eq!(batches[0].name, "Mail (Ana)");
+        assert_eq!((batches[0].total, batches[1].total), (3, 2));
+        assert_eq!(batches[1].reviewer_name.as_deref(), Some("Ben"));
+        assert_eq!(batches[0].document_ids[0], documents[0].id().value());
+
+        // Documents in open batches are not batched twice
+        assert!(service
+            .create_review_batches(project_id, "Again".to_string(), mail, Vec::new())
+            .await
+            .is_err());
+
+        // Folders must lie inside the source folder
+        for path in ["../", "mail/../.."] {
+            let outside = BatchSource::Folder {
+                path: path.to_string(),
+            };
+            assert_eq!(
+                service
+                    .create_review_batches(project_id, "Out".to_string(), outside, Vec::new())
+                    .await
+                    .unwrap_err()
+                    .code,
+                "VALIDATION_ERROR"
+            );
+        }
+
+        // Ben codes his two documents, so Ana's work is shared out again
+        for id in &batches[1].document_ids {
+            let decision = CodingDecision::new(
+                project.id().clone(),
+                DocumentId::from_string(id.clone()).unwrap(),
+                CodingValues::from([(
+                    "designation".to_string(),
+                    CodingValue::Text("Relevant".to_string()),
+                )]),
+                "Ben".to_string(),
+            )
+            .unwrap();
+            coding_repository.record_decision(&decision).await.unwrap();
+        }
+        annotation_repository
+            .save(
+                &Annotation::new(
+                    project.id().clone(),
+                    documents[0].id().clone(),
+                    AnnotationAnchor::Range { from: 1, to: 4 },
+                    "Re:".to_string(),
+                    "Ana".to_string(),
+                )
+                .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        let rebalanced = service.rebalance_review_batches(project_id).await.unwrap();
+        assert_eq!(rebalanced[0].remaining, 2);
+        assert_eq!(rebalanced[1].remaining, 1);
+        assert_eq!((rebalanced[1].total, rebalanced[1].coded), (3, 2));
+
+        let closed = service.close_review_batch(&rebalanced[0].id).await.unwrap();
+        assert_eq!(closed.status, BatchStatus::Closed);
+        assert!(service.assign_review_batch(&closed.id, None).await.is_err());
+
+        let stats = service.get_reviewer_stats(project_id).await.unwrap();
+        assert_eq!(stats[0].name, "Ana");
+        assert_eq!((stats[0].closed_batches, stats[0].annotations), (1, 1));
+        assert!(stats[1].active);
+        assert_eq!((stats[1].decisions, stats[1].documents_coded), (2, 2));
+        assert_eq!(
+            (
+                stats[1].assigned_documents,
+                stats[1].coded_assigned_documents
+            ),
+            (3, 2)
+        );
+    }
+}
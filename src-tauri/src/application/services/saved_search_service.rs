@@ -0,0 +1,426 @@
+use chrono::Utc;
+use std::collections::HashSet;
+use std::path::Path;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+use crate::application::dtos::{
+    DirectoryListingDto, FileEntryDto, SavedSearchDto, SearchResultsDto,
+};
+use crate::application::services::SearchService;
+use crate::domain::project::{Project, ProjectId};
+use crate::domain::search::{SavedSearch, SavedSearchId, SavedSearchRepository};
+use crate::infrastructure::{AppError, AppResult};
+
+/// Folder name below a project's source folder under which saved searches
+/// appear; the colon keeps it from clashing with real folders on Windows
+pub const SAVED_SEARCHES_FOLDER: &str = ":saved-searches";
+
+/// Most results listed in a saved search folder
+const FOLDER_LIMIT: usize = 500;
+
+/// Application service for saved searches and the virtual folders showing
+/// their results
+///
+/// A saved search folder lists the current results of its query; opening
+/// it marks them as seen. New results are counted incrementally: each check
+/// only evaluates items changed since the previous one. The workspace root
+/// shows the stored counts and refreshes them in the background, so listing
+/// it never waits for the searches to run.
+pub struct SavedSearchService {
+    saved_search_repository: Arc<dyn SavedSearchRepository>,
+    search_service: Arc<SearchService>,
+    /// Projects whose new result counts are being refreshed
+    refreshing: Mutex<HashSet<ProjectId>>,
+    /// Held while a saved search is stored, so a refresh does not overwrite
+    /// a view made while it was searching
+    store_lock: Mutex<()>,
+}
+
+impl SavedSearchService {
+    pub fn new(
+        saved_search_repository: Arc<dyn SavedSearchRepository>,
+        search_service: Arc<SearchService>,
+    ) -> Self {
+        SavedSearchService {
+            saved_search_repository,
+            search_service,
+            refreshing: Mutex::new(HashSet::new()),
+            store_lock: Mutex::new(()),
+        }
+    }
+
+    /// Save a query under a name; its current results count as seen
+    pub async fn create_saved_search(
+        &self,
+        project_id: &str,
+        name: String,
+        query: String,
+        track_new: bool,
+    ) -> AppResult<SavedSearchDto> {
+        let project = self.search_service.load_project(project_id).await?;
+        let mut search = SavedSearch::new(project.id().clone(), name, query, track_new)?;
+
+        self.mark_viewed(&project, &mut search).await?;
+        self.store(&search).await?;
+
+        Ok(Self::dto(&search, &project))
+    }
+
+    /// Change the name, query or tracking of a saved search
+    ///
+    /// A new query starts over: its current results count as seen.
+    pub async fn update_saved_search(
+        &self,
+        saved_search_id: &str,
+        name: Option<String>,
+        query: Option<String>,
+        track_new: Option<bool>,
+    ) -> AppResult<SavedSearchDto> {
+        let mut search = self.load(saved_search_id).await?;
+        let project = self
+            .search_service
+            .load_project(search.project_id().value())
+            .await?;
+
+        if let Some(name) = name {
+            search.rename(name)?;
+        }
+        if let Some(track_new) = track_new {
+            search.set_track_new(track_new);
+        }
+        if let Some(query) = query.filter(|query| query.trim() != search.query()) {
+            search.set_query(query)?;
+            self.mark_viewed(&project, &mut search).await?;
+        }
+        self.store(&search).await?;
+
+        Ok(Self::dto(&search, &project))
+    }
+
+    pub async fn delete_saved_search(&self, saved_search_id: &str) -> AppResult<()> {
+        let search = self.load(saved_search_id).await?;
+        self.saved_search_repository.delete(search.id()).await?;
+        Ok(())
+    }
+
+    /// The saved searches of a project, with up-to-date new result counts
+    pub async fn list_saved_searches(&self, project_id: &str) -> AppResult<Vec<SavedSearchDto>> {
+        let project = self.search_service.load_project(project_id).await?;
+
+        Ok(self
+            .refreshed(&project)
+            .await?
+            .iter()
+            .map(|search| Self::dto(search, &project))
+            .collect())
+    }
+
+    /// Run a saved search and mark its results as seen
+    pub async fn run_saved_search(
+        &self,
+        saved_search_id: &str,
+        limit: Option<usize>,
+    ) -> AppResult<SearchResultsDto> {
+        let mut search = self.load(saved_search_id).await?;
+        let project = self
+            .search_service
+            .load_project(search.project_id().value())
+            .await?;
+
+        let results = self
+            .view(&project, &mut search, limit.unwrap_or(FOLDER_LIMIT))
+            .await?;
+        self.store(&search).await?;
+        Ok(results)
+    }
+
+    /// Virtual folder entries for the saved searches of a project, placed
+    /// below `source_folder`
+    ///
+    /// The new result counts are the stored ones; a background refresh
+    /// brings them up to date for the next listing.
+    pub async fn folder_entries(
+        self: &Arc<Self>,
+        project_id: &str,
+        source_folder: &str,
+    ) -> AppResult<Vec<FileEntryDto>> {
+        let project = self.search_service.load_project(project_id).await?;
+        let searches = self
+            .saved_search_repository
+            .list_by_project(project.id())
+            .await?;
+
+        if searches
+            .iter()
+            .any(|search| search.tracks_new() && search.checked_at().is_some())
+        {
+            self.schedule_refresh(project).await;
+        }
+
+        Ok(searches
+            .iter()
+            .map(|search| {
+                let mut entry = FileEntryDto::directory(
+                    search.name().to_string(),
+                    Self::folder_path(source_folder, search.id()),
+                    search.updated_at().to_rfc3339(),
+                );
+                entry.saved_search_id = Some(search.id().value().to_string());
+                entry.new_results = search.new_result_count();
+                entry
+            })
+            .collect())
+    }
+
+    /// Path of the virtual folder showing a saved search
+    pub fn folder_path(source_folder: &str, id: &SavedSearchId) -> String {
+        Path::new(source_folder)
+            .join(SAVED_SEARCHES_FOLDER)
+            .join(id.value())
+            .to_string_lossy()
+            .to_string()
+    }
+
+    /// The saved search a virtual folder path shows, if it is one
+    pub fn parse_folder_path(source_folder: &str, path: &str) -> Option<SavedSearchId> {
+        let relative = Path::new(path).strip_prefix(source_folder).ok()?;
+        let mut parts = relative.components();
+        if parts.next()?.as_os_str() != SAVED_SEARCHES_FOLDER {
+            return None;
+        }
+        let id = parts.next()?.as_os_str().to_str()?;
+        if parts.next().is_some() {
+            return None;
+        }
+        SavedSearchId::from_string(id.to_string()).ok()
+    }
+
+    /// List the results of a saved search as the contents of its folder,
+    /// marking them as seen
+    pub async fn list_folder(
+        &self,
+        source_folder: &str,
+        saved_search_id: &SavedSearchId,
+    ) -> AppResult<DirectoryListingDto> {
+        let mut search = self.load(saved_search_id.value()).await?;
+        let project = self
+            .search_service
+            .load_project(search.project_id().value())
+            .await?;
+
+        let results = self.view(&project, &mut search, FOLDER_LIMIT).await?;
+        self.store(&search).await?;
+
+        let mut entries = Vec::with_capacity(results.hits.len());
+        for hit in results.hits {
+            let size = tokio::fs::metadata(&hit.path)
+                .await
+                .ok()
+                .map(|metadata| metadata.len());
+            entries.push(FileEntryDto::file(
+                hit.title,
+                hit.path,
+                size,
+                hit.modified_at.unwrap_or_default(),
+            ));
+        }
+
+        Ok(
+            DirectoryListingDto::new(entries, false, Some(source_folder.to_string()), true)
+                .with_saved_search(saved_search_id.value().to_string()),
+        )
+    }
+
+    fn dto(search: &SavedSearch, project: &Project) -> SavedSearchDto {
+        let folder = Self::folder_path(&project.source_folder().as_string(), search.id());
+        SavedSearchDto::from_saved_search(search, folder)
+    }
+
+    async fn load(&self, saved_search_id: &str) -> AppResult<SavedSearch> {
+        let id = SavedSearchId::from_string(saved_search_id.to_string())?;
+        self.saved_search_repository
+            .find_by_id(&id)
+            .await?
+            .ok_or_else(|| AppError::not_found(format!("Saved search with ID '{}'", id)))
+    }
+
+    /// Search and remember every result as seen
+    async fn view(
+        &self,
+        project: &Project,
+        search: &mut SavedSearch,
+        limit: usize,
+    ) -> AppResult<SearchResultsDto> {
+        let viewed_at = Utc::now();
+        let (results, keys) = self
+            .search_service
+            .search_in(
+                project,
+                &search.parsed_query()?,
+                search.query(),
+                limit.max(1),
+            )
+            .await?;
+        search.mark_viewed(keys, viewed_at);
+        Ok(results)
+    }
+
+    async fn mark_viewed(&self, project: &Project, search: &mut SavedSearch) -> AppResult<()> {
+        self.view(project, search, 1).await.map(|_| ())
+    }
+
+    async fn store(&self, search: &SavedSearch) -> AppResult<()> {
+        let _guard = self.store_lock.lock().await;
+        self.saved_search_repository.save(search).await?;
+        Ok(())
+    }
+
+    /// Refresh the new result counts of a project on a background task
+    /// unless one is under way
+    async fn schedule_refresh(self: &Arc<Self>, project: Project) {
+        if !self.refreshing.lock().await.insert(project.id().clone()) {
+            return;
+        }
+
+        let service = Arc::clone(self);
+        tokio::spawn(async move {
+            if let Err(error) = service.refreshed(&project).await {
+                tracing::warn!(
+                    "Refreshing saved searches of project {} failed: {}",
+                    project.id(),
+                    error.user_message()
+                );
+            }
+            service.refreshing.lock().await.remove(project.id());
+        });
+    }
+
+    /// The project's saved searches, with new results found since their
+    /// last check recorded
+    async fn refreshed(&self, project: &Project) -> AppResult<Vec<SavedSearch>> {
+        let mut searches = self
+            .saved_search_repository
+            .list_by_project(project.id())
+            .await?;
+
+        for search in searches.iter_mut().filter(|search| search.tracks_new()) {
+            let Some(checked_at) = search.checked_at() else {
+                continue;
+            };
+            // Items changed while searching are looked at again next time
+            let started = Utc::now();
+            let keys = self
+                .search_service
+                .matching_keys(project, &search.parsed_query()?, Some(checked_at))
+                .await?;
+
+            let _guard = self.store_lock.lock().await;
+            let Some(stored) = self.saved_search_repository.find_by_id(search.id()).await? else {
+                continue;
+            };
+            *search = stored;
+            // A view or refresh since the search started already covers it
+            if search.checked_at() == Some(checked_at) {
+                search.record_changed_matches(keys, started);
+                self.saved_search_repository.save(search).await?;
+            }
+        }
+
+        Ok(searches)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::services::search_service::tests::create_fixture;
+    use crate::application::services::WorkspaceNavigationService;
+    use crate::domain::document::{Document, DocumentRepository};
+    use crate::domain::workspace::value_objects::{ContentHash, HashAlgorithm};
+    use crate::infrastructure::{SqliteDocumentRepository, SqliteSavedSearchRepository};
+
+    #[tokio::test]
+    async fn test_saved_search_folder_counts_new_results() {
+        let fixture = create_fixture().await;
+        let project_id = fixture.project.id().value();
+        let source_folder = fixture.project.source_folder().as_string();
+        let service = Arc::new(SavedSearchService::new(
+            Arc::new(SqliteSavedSearchRepository::new(fixture.database.pool())),
+            Arc::new(fixture.service.clone()),
+        ));
+        let navigation = WorkspaceNavigationService::new().with_saved_searches(service.clone());
+
+        let saved = service
+            .create_saved_search(
+                project_id,
+                "Settlement PDFs".to_string(),
+                "settlement ext:pdf".to_string(),
+                true,
+            )
+            .await
+            .unwrap();
+        assert_eq!(saved.new_results, Some(0));
+
+        // A matching document added later is new; unchanged results are not
+        let letter = Document::new(
+            fixture.project.id().clone(),
+            Path::new(&source_folder)
+                .join("settlement-letter.pdf")
+                .to_string_lossy()
+                .to_string(),
+            ContentHash::of_bytes(HashAlgorithm::Blake3, b"letter"),
+            1,
+            0,
+        );
+        SqliteDocumentRepository::new(fixture.database.pool())
+            .save_all(&[letter])
+            .await
+            .unwrap();
+
+        // The root shows the stored count and refreshes it in the background
+        let root = navigation
+            .list_directory(project_id, "Search", &source_folder, &source_folder)
+            .await
+            .unwrap();
+        assert_eq!(root.entries[0].new_results, Some(0));
+        while !service.refreshing.lock().await.is_empty() {
+            tokio::task::yield_now().await;
+        }
+
+        let root = navigation
+            .list_directory(project_id, "Search", &source_folder, &source_folder)
+            .await
+            .unwrap();
+        let folder = &root.entries[0];
+        assert_eq!(folder.name, "Settlement PDFs");
+        assert!(folder.is_directory());
+        assert_eq!(folder.saved_search_id.as_deref(), Some(saved.id.as_str()));
+        assert_eq!(folder.new_results, Some(1));
+
+        // Opening the folder lists the results and marks them as seen
+        let listing = navigation
+            .list_directory(project_id, "Search", &source_folder, &folder.path)
+            .await
+            .unwrap();
+        assert_eq!(listing.saved_search_id.as_deref(), Some(saved.id.as_str()));
+        assert!(listing.find_entry("settlement-letter.pdf").is_some());
+        // The agreement itself matches through its derivatives
+        assert!(listing.find_entry("summary.det").is_some());
+        assert!(listing.find_entry("agreement.pdf").is_none());
+        assert!(listing
+            .entries
+            .iter()
+            .all(|entry| entry.is_file() && entry.saved_search_id.is_none()));
+
+        let searches = service.list_saved_searches(project_id).await.unwrap();
+        assert_eq!(searches[0].new_results, Some(0));
+        assert_eq!(searches[0].folder_path, folder.path);
+
+        let parent = navigation
+            .navigate_to_parent(project_id, "Search", &source_folder, &folder.path)
+            .await
+            .unwrap();
+        assert_eq!(parent.current_path, source_folder);
+    }
+}
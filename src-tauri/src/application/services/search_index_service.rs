@@ -0,0 +1,280 @@
+use chrono::{DateTime, Utc};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use crate::application::services::DerivationService;
+use crate::domain::det::DetStore;
+use crate::domain::document::{DerivativeFamily, Document, DocumentId, DocumentRepository};
+use crate::domain::project::{Project, ProjectId};
+use crate::domain::report::{Report, ReportRepository};
+use crate::domain::search::{IndexedText, SearchIndexRepository, SourceKind};
+use crate::infrastructure::AppResult;
+
+/// Application service keeping the full-text index of derivatives and
+/// reports that search queries
+///
+/// The text of each `.det` file is indexed together with the file's
+/// modification time. Extraction indexes the files it writes, and
+/// reconciliation refreshes whole projects. Searches refresh the project
+/// too, which only reads the modification times of files indexed before,
+/// so derivatives written by other services or tools are found as well.
+pub struct SearchIndexService {
+    document_repository: Arc<dyn DocumentRepository>,
+    report_repository: Arc<dyn ReportRepository>,
+    index_repository: Arc<dyn SearchIndexRepository>,
+    det_store: Arc<dyn DetStore>,
+    derivatives_root: PathBuf,
+}
+
+impl SearchIndexService {
+    /// Create a new SearchIndexService for derivatives below `derivatives_root`
+    pub fn new(
+        document_repository: Arc<dyn DocumentRepository>,
+        report_repository: Arc<dyn ReportRepository>,
+        index_repository: Arc<dyn SearchIndexRepository>,
+        det_store: Arc<dyn DetStore>,
+        derivatives_root: PathBuf,
+    ) -> Self {
+        SearchIndexService {
+            document_repository,
+            report_repository,
+            index_repository,
+            det_store,
+            derivatives_root,
+        }
+    }
+
+    /// Index a derivative of a document that was just written
+    pub async fn index_derivative(&self, document: &Document, path: &Path) -> AppResult<()> {
+        let Some(written_at) = written_at(path).await else {
+            return Ok(());
+        };
+        self.index_file(
+            document.project_id(),
+            path,
+            SourceKind::Derivative,
+            Some(document.id()),
+            written_at,
+        )
+        .await
+        .map(|_| ())
+    }
+
+    /// Bring a project's index up to date with its derivatives and reports
+    pub async fn refresh_project(&self, project: &Project) -> AppResult<()> {
+        let documents: Vec<Document> = self
+            .document_repository
+            .list_by_project(project.id())
+            .await?
+            .into_iter()
+            .filter(|document| !document.is_missing())
+            .collect();
+        let reports = self.report_repository.list_by_project(project.id()).await?;
+
+        self.refresh(project, &documents, &reports).await?;
+        Ok(())
+    }
+
+    /// Index the changed files of the given documents' derivatives and of
+    /// the given reports, and drop all other files of the project
+    ///
+    /// Returns the project's index entries by path. Files that cannot be
+    /// read are left out.
+    pub(crate) async fn refresh(
+        &self,
+        project: &Project,
+        documents: &[Document],
+        reports: &[Report],
+    ) -> AppResult<HashMap<String, IndexedText>> {
+        let mut previous: HashMap<String, IndexedText> = self
+            .index_repository
+            .list_by_project(project.id())
+            .await?
+            .into_iter()
+            .map(|entry| (entry.path.clone(), entry))
+            .collect();
+
+        let mut files = Vec::new();
+        for document in documents {
+            let family =
+                DerivativeFamily::new(&self.derivatives_root, document.project_id(), document.id());
+            for name in DerivationService::det_files(family.folder()).await {
+                files.push((
+                    family.file(&name),
+                    SourceKind::Derivative,
+                    Some(document.id()),
+                ));
+            }
+        }
+        let reports_folder = project.reports_folder();
+        for report in reports {
+            files.push((reports_folder.join(report.path()), SourceKind::Report, None));
+        }
+
+        let mut current = HashMap::new();
+        for (path, kind, document_id) in files {
+            let Some(written_at) = written_at(&path).await else {
+                continue;
+            };
+            let key = path.to_string_lossy().to_string();
+            let entry = match previous.remove(&key) {
+                Some(entry) if entry.written_at == written_at => entry,
+                _ => match self
+                    .index_file(project.id(), &path, kind, document_id, written_at)
+                    .await
+                {
+                    Ok(entry) => entry,
+                    Err(error) => {
+                        tracing::warn!(
+                            "Skipping {} in search: {}",
+                            path.display(),
+                            error.user_message()
+                        );
+                        continue;
+                    }
+                },
+            };
+            current.insert(key, entry);
+        }
+
+        let stale: Vec<String> = previous.into_keys().collect();
+        self.index_repository.remove(&stale).await?;
+        Ok(current)
+    }
+
+    /// Paths of a project's indexed files whose text contains `words`
+    /// (see `SearchIndexRepository::paths_containing`)
+    pub(crate) async fn paths_containing(
+        &self,
+        project_id: &ProjectId,
+        words: &[String],
+    ) -> AppResult<HashSet<String>> {
+        Ok(self
+            .index_repository
+            .paths_containing(project_id, words)
+            .await?)
+    }
+
+    /// The indexed texts of files by path
+    pub(crate) async fn texts(&self, paths: &[String]) -> AppResult<HashMap<String, String>> {
+        Ok(self.index_repository.texts(paths).await?)
+    }
+
+    async fn index_file(
+        &self,
+        project_id: &ProjectId,
+        path: &Path,
+        kind: SourceKind,
+        document_id: Option<&DocumentId>,
+        written_at: DateTime<Utc>,
+    ) -> AppResult<IndexedText> {
+        let det = self.det_store.load(path).await?;
+        let entry = IndexedText {
+            project_id: project_id.clone(),
+            path: path.to_string_lossy().to_string(),
+            kind,
+            document_id: document_id.cloned(),
+            modified_at: det.processing().updated_at,
+            written_at,
+        };
+        self.index_repository
+            .save(&entry, &det.content().plain_text())
+            .await?;
+        Ok(entry)
+    }
+}
+
+/// Modification time of a file, `None` if it is gone
+async fn written_at(path: &Path) -> Option<DateTime<Utc>> {
+    let metadata = tokio::fs::metadata(path).await.ok()?;
+    metadata.modified().ok().map(DateTime::<Utc>::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::det::{DetDocument, DetKind, PmNode, ProcessingMetadata};
+    use crate::domain::workspace::value_objects::{ContentHash, HashAlgorithm};
+    use crate::infrastructure::{
+        DatabaseConnection, FileDetStore, SqliteDocumentRepository, SqliteReportRepository,
+        SqliteSearchIndexRepository,
+    };
+    use std::time::{Duration, SystemTime};
+    use tempfile::TempDir;
+
+    fn det(text: &str) -> DetDocument {
+        DetDocument::new(
+            DetKind::Extracted,
+            PmNode::doc(vec![PmNode::paragraph(vec![PmNode::text(
+                text,
+                Vec::new(),
+            )])]),
+            ProcessingMetadata::completed("test"),
+        )
+    }
+
+    /// Write a `.det` file and date it `age_secs` seconds back
+    async fn write(path: &Path, text: &str, age_secs: u64) {
+        FileDetStore::new().save(path, &det(text)).await.unwrap();
+        std::fs::File::options()
+            .write(true)
+            .open(path)
+            .unwrap()
+            .set_modified(SystemTime::now() - Duration::from_secs(age_secs))
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_refresh_follows_changed_and_removed_files() {
+        let (database, _db_dir) = DatabaseConnection::new_temp().await.unwrap();
+        let source = TempDir::new().unwrap();
+        let derivatives = TempDir::new().unwrap();
+        let project = Project::new(
+            "Index".to_string(),
+            source.path().to_string_lossy().to_string(),
+            None,
+        )
+        .unwrap();
+        let documents = Arc::new(SqliteDocumentRepository::new(database.pool()));
+        let document = Document::new(
+            project.id().clone(),
+            source.path().join("memo.pdf").to_string_lossy().to_string(),
+            ContentHash::of_bytes(HashAlgorithm::Blake3, b"memo"),
+            4,
+            0,
+        );
+        documents.save(&document).await.unwrap();
+        let service = SearchIndexService::new(
+            documents,
+            Arc::new(SqliteReportRepository::new(database.pool())),
+            Arc::new(SqliteSearchIndexRepository::new(database.pool())),
+            Arc::new(FileDetStore::new()),
+            derivatives.path().to_path_buf(),
+        );
+        let path =
+            DerivativeFamily::new(derivatives.path(), project.id(), document.id()).extracted_path();
+        let found = |word: &'static str| {
+            let (service, project) = (&service, &project);
+            async move {
+                service
+                    .paths_containing(project.id(), &[word.to_string()])
+                    .await
+                    .unwrap()
+            }
+        };
+
+        write(&path, "Quarterly budget", 60).await;
+        service.index_derivative(&document, &path).await.unwrap();
+        assert_eq!(found("budget").await.len(), 1);
+
+        write(&path, "Annual forecast", 30).await;
+        service.refresh_project(&project).await.unwrap();
+        assert!(found("budget").await.is_empty());
+        assert_eq!(found("forecast").await.len(), 1);
+
+        std::fs::remove_file(&path).unwrap();
+        service.refresh_project(&project).await.unwrap();
+        assert!(found("forecast").await.is_empty());
+    }
+}
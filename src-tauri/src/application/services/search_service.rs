@@ -0,0 +1,1561 @@
+use chrono::{DateTime, Utc};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use tokio::task::JoinSet;
+
+use crate::application::dtos::{
+    GlobalSearchResultsDto, ProjectSearchGroupDto, SearchHitDto, SearchRelationDto,
+    SearchResultsDto, SkippedProjectDto,
+};
+use crate::application::services::{
+    project_service, ArchiveService, DerivationService, SearchIndexService,
+};
+use crate::domain::category::{CategoryKind, CategoryRepository};
+use crate::domain::document::{
+    DerivationRepository, DerivativeFamily, Document, DocumentId, DocumentRepository, FamilyMember,
+};
+use crate::domain::email::{EmailMessage, EmailRepository};
+use crate::domain::media::{MediaFilter, MediaMetadataRepository, MediaRecord};
+use crate::domain::project::{Project, ProjectRepository};
+use crate::domain::report::{Report, ReportRepository};
+use crate::domain::search::{IndexedText, SearchItem, SearchMatch, SearchQuery, SourceKind};
+use crate::domain::workspace::repositories::FileCategoryConfigRepository;
+use crate::domain::workspace::value_objects::{
+    ArchiveFormat, ArchiveMember, ArchivePath, FileCategory, FileCategoryConfig,
+};
+use crate::infrastructure::{AppError, AppResult, ProjectDto};
+
+/// Hits returned when the caller sets no limit
+const DEFAULT_LIMIT: usize = 50;
+
+/// Most hits returned for one search
+const MAX_LIMIT: usize = 500;
+
+/// A searchable item with the identities needed to relate it to others
+struct Candidate {
+    item: SearchItem,
+    document_id: Option<DocumentId>,
+    report: Option<Report>,
+    member: Option<FamilyMember>,
+    /// The text is still in the search index
+    indexed: bool,
+}
+
+/// Application service for searching everything in a project at once
+///
+/// Covers source documents by name and path, the text of their `.det`
+/// derivatives, report titles and text, metadata such as embedded media
+/// tags and email headers, and the files inside archives, with one query
+/// language (see `SearchQuery`). Tags assigned to a document apply to all
+/// of its items. Hits from all sources
+/// are ranked together, and each says where it came from and which other
+/// items it is related to.
+///
+/// The text of derivatives and reports comes from the full-text index kept
+/// by `SearchIndexService`; only the texts of items that may match are read
+/// from it.
+#[derive(Clone)]
+pub struct SearchService {
+    project_repository: Arc<dyn ProjectRepository>,
+    document_repository: Arc<dyn DocumentRepository>,
+    derivation_repository: Arc<dyn DerivationRepository>,
+    report_repository: Arc<dyn ReportRepository>,
+    media_repository: Arc<dyn MediaMetadataRepository>,
+    email_repository: Option<Arc<dyn EmailRepository>>,
+    archive_service: Option<Arc<ArchiveService>>,
+    category_config_repository: Option<Arc<dyn FileCategoryConfigRepository>>,
+    category_repository: Option<Arc<dyn CategoryRepository>>,
+    search_index: Arc<SearchIndexService>,
+    derivatives_root: PathBuf,
+}
+
+impl SearchService {
+    /// Create a new SearchService reading derivatives below `derivatives_root`
+    pub fn new(
+        project_repository: Arc<dyn ProjectRepository>,
+        document_repository: Arc<dyn DocumentRepository>,
+        derivation_repository: Arc<dyn DerivationRepository>,
+        report_repository: Arc<dyn ReportRepository>,
+        media_repository: Arc<dyn MediaMetadataRepository>,
+        search_index: Arc<SearchIndexService>,
+        derivatives_root: PathBuf,
+    ) -> Self {
+        SearchService {
+            project_repository,
+            document_repository,
+            derivation_repository,
+            report_repository,
+            media_repository,
+            email_repository: None,
+            archive_service: None,
+            category_config_repository: None,
+            category_repository: None,
+            search_index,
+            derivatives_root,
+        }
+    }
+
+    /// Also search the headers of parsed email messages
+    pub fn with_email_repository(mut self, email_repository: Arc<dyn EmailRepository>) -> Self {
+        self.email_repository = Some(email_repository);
+        self
+    }
+
+    /// Also search the names of files inside archive documents
+    pub fn with_archive_service(mut self, archive_service: Arc<ArchiveService>) -> Self {
+        self.archive_service = Some(archive_service);
+        self
+    }
+
+    /// Classify files for `type:` with each project's category settings
+    /// rather than the built-in extension table
+    pub fn with_category_config_repository(
+        mut self,
+        category_config_repository: Arc<dyn FileCategoryConfigRepository>,
+    ) -> Self {
+        self.category_config_repository = Some(category_config_repository);
+        self
+    }
+
+    /// Let `tag:` find documents by the tags assigned to them
+    pub fn with_category_repository(
+        mut self,
+        category_repository: Arc<dyn CategoryRepository>,
+    ) -> Self {
+        self.category_repository = Some(category_repository);
+        self
+    }
+
+    /// Search a project, best hits first
+    pub async fn search(
+        &self,
+        project_id: &str,
+        query: &str,
+        limit: Option<usize>,
+    ) -> AppResult<SearchResultsDto> {
+        let parsed = SearchQuery::parse(query)?;
+        let project = self.load_project(project_id).await?;
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT);
+
+        let (results, _) = self.search_in(&project, &parsed, query, limit).await?;
+        Ok(results)
+    }
+
+    /// Search every project at once, best hits first, grouped by project
+    ///
+    /// Projects are searched concurrently. Projects whose source folder is
+    /// not accessible, or whose search fails, are skipped and listed with
+    /// the reason. Hits from all projects are ranked together and paged
+    /// with `offset` and `limit` before they are grouped.
+    pub async fn search_all_projects(
+        &self,
+        query: &str,
+        offset: usize,
+        limit: Option<usize>,
+    ) -> AppResult<GlobalSearchResultsDto> {
+        let parsed = Arc::new(SearchQuery::parse(query)?);
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT);
+        let projects = self
+            .project_repository
+            .list_all()
+            .await
+            .map_err(AppError::from)?;
+
+        let mut skipped = Vec::new();
+        let mut searches = JoinSet::new();
+        for project in projects {
+            if !project.is_source_accessible() {
+                skipped.push(SkippedProjectDto {
+                    project: ProjectDto::from_project(&project),
+                    reason: "Project source folder is not accessible".to_string(),
+                });
+                continue;
+            }
+            let service = self.clone();
+            let parsed = parsed.clone();
+            let query = query.to_string();
+            // Every hit of the requested page may come from this project
+            let wanted = offset.saturating_add(limit);
+            searches.spawn(async move {
+                let results = service
+                    .search_in(&project, &parsed, &query, wanted)
+                    .await
+                    .map(|(results, _)| results);
+                (project, results)
+            });
+        }
+
+        let mut found = Vec::new();
+        while let Some(joined) = searches.join_next().await {
+            let (project, results) = joined.map_err(|e| {
+                AppError::internal_error(format!("Project search task failed: {}", e))
+            })?;
+            match results {
+                Ok(results) => found.push((ProjectDto::from_project(&project), results)),
+                Err(error) => {
+                    let reason = error.user_message();
+                    tracing::warn!("Skipping project {} in search: {}", project.id(), reason);
+                    skipped.push(SkippedProjectDto {
+                        project: ProjectDto::from_project(&project),
+                        reason,
+                    });
+                }
+            }
+        }
+        skipped.sort_by(|a, b| a.project.name.cmp(&b.project.name));
+
+        Ok(page_groups(query, found, skipped, offset, limit))
+    }
+
+    /// Search one loaded project, returning at most `limit` hits and the
+    /// keys of all matching items (see `result_key`)
+    pub(crate) async fn search_in(
+        &self,
+        project: &Project,
+        parsed: &SearchQuery,
+        query: &str,
+        limit: usize,
+    ) -> AppResult<(SearchResultsDto, Vec<String>)> {
+        let (documents, reports, candidates) = self.collect(project, parsed, None).await?;
+
+        let mut matches: Vec<(SearchMatch, Candidate)> = candidates
+            .into_iter()
+            .filter_map(|candidate| {
+                parsed
+                    .evaluate(&candidate.item)
+                    .map(|found| (found, candidate))
+            })
+            .collect();
+        matches.sort_by(|(a, a_candidate), (b, b_candidate)| {
+            b.score
+                .total_cmp(&a.score)
+                .then(a_candidate.item.kind.cmp(&b_candidate.item.kind))
+                .then_with(|| a_candidate.item.title.cmp(&b_candidate.item.title))
+        });
+
+        let mut counts = BTreeMap::new();
+        for (_, candidate) in &matches {
+            *counts
+                .entry(candidate.item.kind.as_str().to_string())
+                .or_insert(0) += 1;
+        }
+        let total = matches.len();
+        let keys = matches
+            .iter()
+            .map(|(_, candidate)| result_key(candidate.item.kind, &candidate.item.path))
+            .collect();
+        matches.truncate(limit);
+
+        let documents_by_id: HashMap<&DocumentId, &Document> = documents
+            .iter()
+            .map(|document| (document.id(), document))
+            .collect();
+        let mut hits = Vec::with_capacity(matches.len());
+        for (found, candidate) in matches {
+            let relations = self
+                .relations(project, &candidate, &documents_by_id, &reports)
+                .await;
+            hits.push(hit_dto(found, candidate, relations));
+        }
+
+        Ok((
+            SearchResultsDto {
+                project_id: project.id().value().to_string(),
+                query: query.to_string(),
+                total,
+                counts,
+                hits,
+            },
+            keys,
+        ))
+    }
+
+    /// Keys of the items matching a query (see `result_key`)
+    ///
+    /// With `changed_since`, only items added or changed after that time are
+    /// considered, which avoids reading the texts of unchanged derivatives
+    /// and reports.
+    pub(crate) async fn matching_keys(
+        &self,
+        project: &Project,
+        parsed: &SearchQuery,
+        changed_since: Option<DateTime<Utc>>,
+    ) -> AppResult<Vec<String>> {
+        let (_, _, candidates) = self.collect(project, parsed, changed_since).await?;
+
+        Ok(candidates
+            .iter()
+            .filter(|candidate| parsed.evaluate(&candidate.item).is_some())
+            .map(|candidate| result_key(candidate.item.kind, &candidate.item.path))
+            .collect())
+    }
+
+    /// Documents with at least one item matching a query, however many
+    pub(crate) async fn matching_documents(
+        &self,
+        project: &Project,
+        query: &str,
+    ) -> AppResult<HashSet<DocumentId>> {
+        let parsed = SearchQuery::parse(query)?;
+        let (_, _, candidates) = self.collect(project, &parsed, None).await?;
+
+        Ok(candidates
+            .into_iter()
+            .filter(|candidate| parsed.evaluate(&candidate.item).is_some())
+            .filter_map(|candidate| candidate.document_id)
+            .collect())
+    }
+
+    /// The project's documents and reports, and the items that may match
+    async fn collect(
+        &self,
+        project: &Project,
+        parsed: &SearchQuery,
+        changed_since: Option<DateTime<Utc>>,
+    ) -> AppResult<(Vec<Document>, Vec<Report>, Vec<Candidate>)> {
+        let changed = |at: DateTime<Utc>| changed_since.is_none_or(|since| at > since);
+
+        let documents: Vec<Document> = self
+            .document_repository
+            .list_by_project(project.id())
+            .await?
+            .into_iter()
+            .filter(|document| !document.is_missing())
+            .collect();
+        let reports = self.report_repository.list_by_project(project.id()).await?;
+        let media: HashMap<DocumentId, MediaRecord> = self
+            .media_repository
+            .search(project.id(), &MediaFilter::default())
+            .await?
+            .into_iter()
+            .map(|record| (record.document_id().clone(), record))
+            .collect();
+        let mut emails: HashMap<DocumentId, Vec<EmailMessage>> = HashMap::new();
+        if let Some(email_repository) = self
+            .email_repository
+            .as_ref()
+            .filter(|_| parsed.admits(SourceKind::Metadata))
+        {
+            for message in email_repository.list_by_project(project.id()).await? {
+                emails
+                    .entry(message.document_id().clone())
+                    .or_default()
+                    .push(message);
+            }
+        }
+
+        let indexed = if parsed.admits(SourceKind::Derivative) || parsed.admits(SourceKind::Report)
+        {
+            self.search_index
+                .refresh(project, &documents, &reports)
+                .await?
+        } else {
+            HashMap::new()
+        };
+        let mut derivatives: HashMap<&DocumentId, Vec<&IndexedText>> = HashMap::new();
+        for entry in indexed.values() {
+            if let (SourceKind::Derivative, Some(document_id)) = (entry.kind, &entry.document_id) {
+                derivatives.entry(document_id).or_default().push(entry);
+            }
+        }
+
+        let mut candidates = Vec::new();
+        for document in &documents {
+            let record = media.get(document.id());
+            if parsed.admits(SourceKind::Original) && changed(document.updated_at()) {
+                candidates.push(document_candidate(
+                    document_item(SourceKind::Original, document, record),
+                    document,
+                    None,
+                ));
+            }
+            if parsed.admits(SourceKind::Derivative) {
+                let mut entries = derivatives.remove(document.id()).unwrap_or_default();
+                entries.sort_by(|a, b| a.path.cmp(&b.path));
+                // Files not written since `changed_since` are unchanged
+                candidates.extend(
+                    entries
+                        .into_iter()
+                        .filter(|entry| changed(entry.written_at))
+                        .map(|entry| derivative_candidate(document, record, entry)),
+                );
+            }
+            if let Some(record) = record.filter(|record| {
+                parsed.admits(SourceKind::Metadata)
+                    && changed(record.probed_at().max(document.updated_at()))
+            }) {
+                candidates.push(metadata_candidate(document, record));
+            }
+            for message in emails.get(document.id()).into_iter().flatten() {
+                if changed(message.parsed_at().max(document.updated_at())) {
+                    candidates.push(email_candidate(document, message));
+                }
+            }
+            if parsed.admits(SourceKind::Original) && changed(document.updated_at()) {
+                candidates.extend(self.archive_candidates(document).await);
+            }
+        }
+
+        // Categories follow the project's overrides; search never reads file
+        // contents to sniff them
+        let config = match &self.category_config_repository {
+            Some(repository) => repository.load(project.id()).await?,
+            None => FileCategoryConfig::default(),
+        };
+        let assigned_tags = self.assigned_tags(project).await?;
+        for candidate in &mut candidates {
+            candidate.item.category = candidate.item.extension.as_deref().map(|extension| {
+                config
+                    .classify_extension(extension)
+                    .unwrap_or(FileCategory::Other)
+            });
+            if let Some(tags) = candidate
+                .document_id
+                .as_ref()
+                .and_then(|id| assigned_tags.get(id))
+            {
+                candidate.item.tags.extend(tags.iter().cloned());
+            }
+        }
+
+        if parsed.admits(SourceKind::Report) {
+            let reports_folder = project.reports_folder();
+            for report in reports.iter().filter(|report| changed(report.updated_at())) {
+                let path = reports_folder
+                    .join(report.path())
+                    .to_string_lossy()
+                    .to_string();
+                if indexed.contains_key(&path) {
+                    candidates.push(report_candidate(report, path));
+                }
+            }
+        }
+
+        let candidates = self.load_texts(project, parsed, candidates).await?;
+        Ok((documents, reports, candidates))
+    }
+
+    /// Read the indexed texts of the candidates that may match, dropping
+    /// those that cannot
+    ///
+    /// The index tells which texts contain each term or phrase, so only
+    /// candidates whose text or other fields contain all of them are read.
+    async fn load_texts(
+        &self,
+        project: &Project,
+        parsed: &SearchQuery,
+        candidates: Vec<Candidate>,
+    ) -> AppResult<Vec<Candidate>> {
+        if !candidates.iter().any(|candidate| candidate.indexed) {
+            return Ok(candidates);
+        }
+
+        let mut in_text = Vec::new();
+        for needle in parsed.needles() {
+            in_text.push(
+                self.search_index
+                    .paths_containing(project.id(), needle)
+                    .await?,
+            );
+        }
+        let mut candidates: Vec<Candidate> = candidates
+            .into_iter()
+            .filter(|candidate| {
+                !candidate.indexed
+                    || parsed.may_match(&candidate.item, |index| {
+                        in_text[index].contains(&candidate.item.path)
+                    })
+            })
+            .collect();
+
+        let paths: Vec<String> = candidates
+            .iter()
+            .filter(|candidate| candidate.indexed)
+            .map(|candidate| candidate.item.path.clone())
+            .collect();
+        let mut texts = self.search_index.texts(&paths).await?;
+        for candidate in candidates.iter_mut().filter(|candidate| candidate.indexed) {
+            candidate.item.text = texts.remove(&candidate.item.path).unwrap_or_default();
+            candidate.indexed = false;
+        }
+        Ok(candidates)
+    }
+
+    /// Names of the tags assigned to each document of a project
+    async fn assigned_tags(
+        &self,
+        project: &Project,
+    ) -> AppResult<HashMap<DocumentId, Vec<String>>> {
+        let mut assigned: HashMap<DocumentId, Vec<String>> = HashMap::new();
+        let Some(category_repository) = &self.category_repository else {
+            return Ok(assigned);
+        };
+
+        let tags: HashMap<_, _> = category_repository
+            .list_by_project(project.id())
+            .await?
+            .into_iter()
+            .filter(|category| category.kind() == CategoryKind::Tag)
+            .map(|tag| (tag.id().clone(), tag.name().to_string()))
+            .collect();
+        for assignment in category_repository.list_assignments(project.id()).await? {
+            if let Some(name) = tags.get(&assignment.category_id) {
+                assigned
+                    .entry(assignment.document_id)
+                    .or_default()
+                    .push(name.clone());
+            }
+        }
+
+        Ok(assigned)
+    }
+
+    pub(crate) async fn load_project(&self, project_id: &str) -> AppResult<Project> {
+        project_service::load_project(self.project_repository.as_ref(), project_id).await
+    }
+
+    fn family(&self, document: &Document) -> DerivativeFamily {
+        DerivativeFamily::new(&self.derivatives_root, document.project_id(), document.id())
+    }
+
+    /// Files inside an archive document, titled by their names
+    ///
+    /// Archives that cannot be read are left out.
+    async fn archive_candidates(&self, document: &Document) -> Vec<Candidate> {
+        let Some(archive_service) = self
+            .archive_service
+            .as_ref()
+            .filter(|_| ArchiveFormat::is_archive_name(document.path()))
+        else {
+            return Vec::new();
+        };
+        let files = match archive_service.files(Path::new(document.path())).await {
+            Ok(files) => files,
+            Err(error) => {
+                tracing::warn!(
+                    "Skipping archive {} in search: {}",
+                    document.path(),
+                    error.user_message()
+                );
+                return Vec::new();
+            }
+        };
+
+        files
+            .iter()
+            .map(|(location, member)| archive_member_candidate(document, location, member))
+            .collect()
+    }
+
+    /// Items a hit is related to: its original, derivatives and citing reports
+    async fn relations(
+        &self,
+        project: &Project,
+        candidate: &Candidate,
+        documents: &HashMap<&DocumentId, &Document>,
+        reports: &[Report],
+    ) -> Vec<SearchRelationDto> {
+        let mut relations = Vec::new();
+
+        if let Some(report) = &candidate.report {
+            for document_id in report.source_documents() {
+                if let Some(document) = documents.get(document_id) {
+                    relations.push(document_relation("cites", document));
+                }
+            }
+            for derivative in report.derivatives() {
+                relations.push(SearchRelationDto {
+                    relation: "cites".to_string(),
+                    kind: SourceKind::Derivative,
+                    title: file_name(derivative),
+                    path: derivative.clone(),
+                    document_id: None,
+                    report_id: None,
+                });
+            }
+            return relations;
+        }
+
+        // Files inside archives only relate to the archive
+        if let Some(location) = ArchivePath::parse(&candidate.item.path) {
+            let archive = location.archive().to_string_lossy();
+            if let Some(document) = documents
+                .values()
+                .find(|document| document.path() == archive)
+            {
+                relations.push(document_relation("containedIn", document));
+            }
+            return relations;
+        }
+
+        let Some(document) = candidate
+            .document_id
+            .as_ref()
+            .and_then(|id| documents.get(id))
+        else {
+            return relations;
+        };
+        let family = self.family(document);
+
+        match (candidate.item.kind, &candidate.member) {
+            (SourceKind::Original, _) => {
+                for name in DerivationService::det_files(family.folder()).await {
+                    relations.push(SearchRelationDto {
+                        relation: "hasDerivative".to_string(),
+                        kind: SourceKind::Derivative,
+                        path: family.file(&name).to_string_lossy().to_string(),
+                        title: name,
+                        document_id: Some(document.id().value().to_string()),
+                        report_id: None,
+                    });
+                }
+            }
+            (SourceKind::Derivative, Some(member)) => {
+                let graph = self
+                    .derivation_repository
+                    .find_by_document(document.project_id(), document.id())
+                    .await;
+                // Derivatives without a recorded step come from the original
+                let parent = graph
+                    .ok()
+                    .and_then(|graph| {
+                        graph
+                            .parents_of(member)
+                            .first()
+                            .map(|step| step.parent().clone())
+                    })
+                    .unwrap_or(FamilyMember::Original);
+                relations.push(match parent {
+                    FamilyMember::Original => document_relation("derivedFrom", document),
+                    FamilyMember::Derivative(name) => SearchRelationDto {
+                        relation: "derivedFrom".to_string(),
+                        kind: SourceKind::Derivative,
+                        path: family.file(&name).to_string_lossy().to_string(),
+                        title: name,
+                        document_id: Some(document.id().value().to_string()),
+                        report_id: None,
+                    },
+                });
+            }
+            _ => relations.push(document_relation("describes", document)),
+        }
+
+        let reports_folder = project.reports_folder();
+        for report in reports {
+            let cites = match &candidate.member {
+                Some(FamilyMember::Derivative(_)) => {
+                    report.derivatives().contains(&candidate.item.path)
+                }
+                _ => report.source_documents().contains(document.id()),
+            };
+            if cites {
+                relations.push(SearchRelationDto {
+                    relation: "citedBy".to_string(),
+                    kind: SourceKind::Report,
+                    title: report.title().to_string(),
+                    path: reports_folder
+                        .join(report.path())
+                        .to_string_lossy()
+                        .to_string(),
+                    document_id: None,
+                    report_id: Some(report.id().value().to_string()),
+                });
+            }
+        }
+
+        relations
+    }
+}
+
+/// Identifies a matching item across searches: its kind and path
+pub(crate) fn result_key(kind: SourceKind, path: &str) -> String {
+    format!("{}:{}", kind.as_str(), path)
+}
+
+/// The searchable fields a document lends to all of its items
+fn document_item(
+    kind: SourceKind,
+    document: &Document,
+    record: Option<&MediaRecord>,
+) -> SearchItem {
+    let extension = Path::new(document.path())
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(str::to_lowercase);
+
+    SearchItem {
+        extension,
+        tags: record
+            .map(|record| {
+                record
+                    .metadata()
+                    .tags
+                    .iter()
+                    .map(|(_, value)| value.to_string())
+                    .collect()
+            })
+            .unwrap_or_default(),
+        modified_at: Some(DateTime::from_timestamp_nanos(document.modified_ns())),
+        ..SearchItem::new(
+            kind,
+            document.file_name().unwrap_or(document.path()),
+            document.path(),
+        )
+    }
+}
+
+fn document_candidate(
+    item: SearchItem,
+    document: &Document,
+    member: Option<FamilyMember>,
+) -> Candidate {
+    Candidate {
+        item,
+        document_id: Some(document.id().clone()),
+        report: None,
+        member,
+        indexed: false,
+    }
+}
+
+/// An indexed `.det` file of a document, titled by its file name
+fn derivative_candidate(
+    document: &Document,
+    record: Option<&MediaRecord>,
+    entry: &IndexedText,
+) -> Candidate {
+    let name = file_name(&entry.path);
+    let item = SearchItem {
+        title: name.clone(),
+        path: entry.path.clone(),
+        derivative: Some(name.trim_end_matches(".det").to_string()),
+        modified_at: Some(entry.modified_at),
+        ..document_item(SourceKind::Derivative, document, record)
+    };
+    Candidate {
+        indexed: true,
+        ..document_candidate(item, document, Some(FamilyMember::Derivative(name)))
+    }
+}
+
+/// An indexed report, titled by its title
+fn report_candidate(report: &Report, path: String) -> Candidate {
+    Candidate {
+        item: SearchItem {
+            modified_at: Some(report.updated_at()),
+            ..SearchItem::new(SourceKind::Report, report.title(), path)
+        },
+        document_id: None,
+        report: Some(report.clone()),
+        member: None,
+        indexed: true,
+    }
+}
+
+/// Media metadata, titled by the embedded title where there is one
+fn metadata_candidate(document: &Document, record: &MediaRecord) -> Candidate {
+    let metadata = record.metadata();
+    let description = format!("{} {}", metadata.container, metadata.kind.as_str());
+
+    // Tag values are searched as tags, so the text only describes the streams
+    let mut lines = vec![description.clone()];
+    lines.extend(metadata.audio_codec.clone());
+    lines.extend(metadata.video_codec.clone());
+
+    let item = SearchItem {
+        title: metadata
+            .tags
+            .title()
+            .map_or(description, ToString::to_string),
+        modified_at: Some(record.probed_at()),
+        text: lines.join("\n"),
+        ..document_item(SourceKind::Metadata, document, Some(record))
+    };
+    document_candidate(item, document, None)
+}
+
+/// Email headers, titled by the subject
+///
+/// The bare addresses of sender and recipients are tags, so `tag:` finds
+/// everything a person sent or received.
+fn email_candidate(document: &Document, message: &EmailMessage) -> Candidate {
+    let headers = message.headers();
+    let mut lines = Vec::new();
+    lines.extend(headers.from.iter().map(|from| format!("From: {}", from)));
+    if !headers.to.is_empty() {
+        lines.push(format!("To: {}", headers.to.join(", ")));
+    }
+    if !headers.cc.is_empty() {
+        lines.push(format!("Cc: {}", headers.cc.join(", ")));
+    }
+    lines.extend(headers.subject.clone());
+
+    let item = SearchItem {
+        title: headers
+            .subject
+            .clone()
+            .unwrap_or_else(|| "(no subject)".to_string()),
+        tags: headers
+            .participants()
+            .map(|participant| {
+                participant
+                    .rsplit_once('<')
+                    .map_or(participant, |(_, address)| address.trim_end_matches('>'))
+                    .to_string()
+            })
+            .collect(),
+        modified_at: Some(headers.date.unwrap_or(message.parsed_at())),
+        text: lines.join("\n"),
+        ..document_item(SourceKind::Metadata, document, None)
+    };
+    document_candidate(item, document, None)
+}
+
+/// A file inside an archive; it has no document of its own
+fn archive_member_candidate(
+    archive: &Document,
+    location: &ArchivePath,
+    member: &ArchiveMember,
+) -> Candidate {
+    let extension = Path::new(member.name())
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(str::to_lowercase);
+
+    Candidate {
+        item: SearchItem {
+            extension,
+            modified_at: member
+                .modified
+                .or(Some(DateTime::from_timestamp_nanos(archive.modified_ns()))),
+            ..SearchItem::new(SourceKind::Original, member.name(), location.to_string())
+        },
+        document_id: None,
+        report: None,
+        member: None,
+        indexed: false,
+    }
+}
+
+fn document_relation(relation: &str, document: &Document) -> SearchRelationDto {
+    SearchRelationDto {
+        relation: relation.to_string(),
+        kind: SourceKind::Original,
+        title: document.file_name().unwrap_or(document.path()).to_string(),
+        path: document.path().to_string(),
+        document_id: Some(document.id().value().to_string()),
+        report_id: None,
+    }
+}
+
+fn file_name(path: &str) -> String {
+    Path::new(path).file_name().map_or_else(
+        || path.to_string(),
+        |name| name.to_string_lossy().to_string(),
+    )
+}
+
+/// Rank the hits of all projects together, cut out one page and group it
+///
+/// Groups are ordered by their best hit on the page; projects without hits
+/// on the page are left out.
+fn page_groups(
+    query: &str,
+    found: Vec<(ProjectDto, SearchResultsDto)>,
+    skipped: Vec<SkippedProjectDto>,
+    offset: usize,
+    limit: usize,
+) -> GlobalSearchResultsDto {
+    let projects_searched = found.len();
+    let total = found.iter().map(|(_, results)| results.total).sum();
+
+    let mut groups = Vec::new();
+    let mut ranked = Vec::new();
+    for (index, (project, results)) in found.into_iter().enumerate() {
+        ranked.extend(results.hits.into_iter().map(|hit| (index, hit)));
+        groups.push(ProjectSearchGroupDto {
+            project,
+            total: results.total,
+            counts: results.counts,
+            hits: Vec::new(),
+        });
+    }
+    ranked.sort_by(|(a_index, a), (b_index, b)| {
+        b.score
+            .total_cmp(&a.score)
+            .then(a.kind.cmp(&b.kind))
+            .then_with(|| {
+                groups[*a_index]
+                    .project
+                    .name
+                    .cmp(&groups[*b_index].project.name)
+            })
+            .then_with(|| a.title.cmp(&b.title))
+    });
+
+    let mut order = Vec::new();
+    let mut page_size = 0;
+    for (index, hit) in ranked.into_iter().skip(offset).take(limit) {
+        if groups[index].hits.is_empty() {
+            order.push(index);
+        }
+        groups[index].hits.push(hit);
+        page_size += 1;
+    }
+    let mut groups: Vec<Option<ProjectSearchGroupDto>> = groups.into_iter().map(Some).collect();
+    let groups = order
+        .into_iter()
+        .filter_map(|index| groups[index].take())
+        .collect();
+
+    GlobalSearchResultsDto {
+        query: query.to_string(),
+        total,
+        offset,
+        limit,
+        has_more: offset + page_size < total,
+        projects_searched,
+        groups,
+        skipped,
+    }
+}
+
+fn hit_dto(
+    found: SearchMatch,
+    candidate: Candidate,
+    relations: Vec<SearchRelationDto>,
+) -> SearchHitDto {
+    SearchHitDto {
+        kind: candidate.item.kind,
+        title: candidate.item.title,
+        path: candidate.item.path,
+        document_id: candidate.document_id.map(|id| id.value().to_string()),
+        report_id: candidate
+            .report
+            .map(|report| report.id().value().to_string()),
+        score: found.score,
+        snippet: found.snippet,
+        matched_fields: found
+            .matched_fields
+            .into_iter()
+            .map(ToString::to_string)
+            .collect(),
+        modified_at: candidate.item.modified_at.map(|at| at.to_rfc3339()),
+        relations,
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::domain::category::Category;
+    use crate::domain::det::{DetDocument, DetKind, DetStore, PmNode, ProcessingMetadata};
+    use crate::domain::document::{DerivationGraph, DerivationStep};
+    use crate::domain::email::EmailHeaders;
+    use crate::domain::media::{MediaKind, MediaMetadata, MediaTags};
+    use crate::domain::report::ReportCategory;
+    use crate::domain::workspace::value_objects::{ContentHash, HashAlgorithm};
+    use crate::infrastructure::{
+        DatabaseConnection, FileDetStore, SqliteCategoryRepository, SqliteDerivationRepository,
+        SqliteDocumentRepository, SqliteEmailRepository, SqliteFileCategoryConfigRepository,
+        SqliteMediaMetadataRepository, SqliteProjectRepository, SqliteReportRepository,
+        SqliteSearchIndexRepository,
+    };
+    use serde_json::json;
+    use tempfile::TempDir;
+
+    /// A project with a PDF and its derivatives, a recording with media
+    /// tags and a report, all mentioning "settlement"
+    pub(crate) struct Fixture {
+        pub service: SearchService,
+        pub database: DatabaseConnection,
+        pub project: Project,
+        pub agreement: Document,
+        pub interview: Document,
+        _source: TempDir,
+        _derivatives: TempDir,
+        _db_dir: TempDir,
+    }
+
+    fn det(kind: DetKind, text: &str) -> DetDocument {
+        DetDocument::new(
+            kind,
+            PmNode::doc(vec![PmNode::paragraph(vec![PmNode::text(
+                text,
+                Vec::new(),
+            )])]),
+            ProcessingMetadata::completed("test"),
+        )
+    }
+
+    pub(crate) async fn create_fixture() -> Fixture {
+        let (database, db_dir) = DatabaseConnection::new_temp().await.unwrap();
+        let source = TempDir::new().unwrap();
+        let derivatives = TempDir::new().unwrap();
+        let store = FileDetStore::new();
+
+        let projects = Arc::new(SqliteProjectRepository::new(database.pool()));
+        let project = Project::new(
+            "Search".to_string(),
+            source.path().to_string_lossy().to_string(),
+            None,
+        )
+        .unwrap();
+        projects.create(&project).await.unwrap();
+
+        let documents = Arc::new(SqliteDocumentRepository::new(database.pool()));
+        let add_document = |name: &str| {
+            Document::new(
+                project.id().clone(),
+                source.path().join(name).to_string_lossy().to_string(),
+                ContentHash::of_bytes(HashAlgorithm::Blake3, name.as_bytes()),
+                1,
+                0,
+            )
+        };
+        let agreement = add_document("agreement.pdf");
+        let interview = add_document("interview.mp3");
+        documents
+            .save_all(&[agreement.clone(), interview.clone()])
+            .await
+            .unwrap();
+
+        // The extraction and a summary derived from it
+        let family = DerivativeFamily::new(derivatives.path(), project.id(), agreement.id());
+        store
+            .save(
+                &family.extracted_path(),
+                &det(
+                    DetKind::Extracted,
+                    "The parties agree on a settlement of the invoices.",
+                ),
+            )
+            .await
+            .unwrap();
+        store
+            .save(
+                &family.file("summary.det"),
+                &det(DetKind::Summary, "Short settlement summary."),
+            )
+            .await
+            .unwrap();
+        let derivations = Arc::new(SqliteDerivationRepository::new(database.pool()));
+        let mut graph = DerivationGraph::new(project.id().clone(), agreement.id().clone());
+        graph
+            .record(
+                DerivationStep::new(
+                    FamilyMember::extracted(),
+                    FamilyMember::derivative("summary.det").unwrap(),
+                    "summarize",
+                    json!({}),
+                    "test",
+                )
+                .unwrap(),
+            )
+            .unwrap();
+        derivations.save(&graph).await.unwrap();
+
+        let media = Arc::new(SqliteMediaMetadataRepository::new(database.pool()));
+        let mut metadata = MediaMetadata::new(MediaKind::Audio, "MP3");
+        metadata.tags.set(MediaTags::TITLE, "Settlement hearing");
+        metadata.tags.set(MediaTags::GENRE, "Finance");
+        media
+            .save(&MediaRecord::new(
+                interview.id().clone(),
+                project.id().clone(),
+                "hash".to_string(),
+                "mp3-probe-v1",
+                metadata,
+            ))
+            .await
+            .unwrap();
+
+        let reports = Arc::new(SqliteReportRepository::new(database.pool()));
+        let title = "Settlement findings";
+        let mut report = Report::new(
+            project.id().clone(),
+            title.to_string(),
+            ReportCategory::Findings,
+            Report::path_for(ReportCategory::Findings, title, None),
+        )
+        .unwrap();
+        report.set_source_documents(vec![agreement.id().clone()]);
+        reports.save(&report).await.unwrap();
+        store
+            .save(
+                &project.reports_folder().join(report.path()),
+                &det(DetKind::Report, "We reviewed the settlement terms."),
+            )
+            .await
+            .unwrap();
+
+        let search_index = Arc::new(SearchIndexService::new(
+            documents.clone(),
+            reports.clone(),
+            Arc::new(SqliteSearchIndexRepository::new(database.pool())),
+            Arc::new(store),
+            derivatives.path().to_path_buf(),
+        ));
+
+        Fixture {
+            service: SearchService::new(
+                projects,
+                documents,
+                derivations,
+                reports,
+                media,
+                search_index,
+                derivatives.path().to_path_buf(),
+            )
+            .with_category_config_repository(Arc::new(SqliteFileCategoryConfigRepository::new(
+                database.pool(),
+            )))
+            .with_category_repository(Arc::new(SqliteCategoryRepository::new(database.pool()))),
+            database,
+            project,
+            agreement,
+            interview,
+            _source: source,
+            _derivatives: derivatives,
+            _db_dir: db_dir,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_type_filter_uses_project_categories() {
+        let fixture = create_fixture().await;
+        let project_id = fixture.project.id().value();
+        let audio = fixture
+            .service
+            .search(project_id, "type:audio", None)
+            .await
+            .unwrap();
+        assert_eq!(audio.total, 2);
+
+        SqliteFileCategoryConfigRepository::new(fixture.database.pool())
+            .save(
+                fixture.project.id(),
+                &FileCategoryConfig::default().with_override("mp3", FileCategory::Documents),
+            )
+            .await
+            .unwrap();
+
+        let audio = fixture
+            .service
+            .search(project_id, "type:audio", None)
+            .await
+            .unwrap();
+        assert_eq!(audio.total, 0);
+
+        let documents = fixture
+            .service
+            .search(project_id, "type:documents ext:mp3", None)
+            .await
+            .unwrap();
+        assert_eq!(documents.total, 2);
+    }
+
+    #[tokio::test]
+    async fn test_tag_filter_finds_tagged_documents() {
+        let fixture = create_fixture().await;
+        let project_id = fixture.project.id().value();
+        let categories = SqliteCategoryRepository::new(fixture.database.pool());
+        let tag = Category::new(
+            fixture.project.id().clone(),
+            CategoryKind::Tag,
+            "Privileged".to_string(),
+        )
+        .unwrap();
+        // Categories are not tags
+        let category = Category::new(
+            fixture.project.id().clone(),
+            CategoryKind::Category,
+            "Contracts".to_string(),
+        )
+        .unwrap();
+        categories.save(&tag).await.unwrap();
+        categories.save(&category).await.unwrap();
+        categories
+            .assign(tag.id(), &[fixture.agreement.id().clone()])
+            .await
+            .unwrap();
+        categories
+            .assign(category.id(), &[fixture.interview.id().clone()])
+            .await
+            .unwrap();
+
+        let tagged = fixture
+            .service
+            .search(project_id, "tag:privileged type:original", None)
+            .await
+            .unwrap();
+        assert_eq!(tagged.total, 1);
+        assert_eq!(
+            tagged.hits[0].document_id.as_deref(),
+            Some(fixture.agreement.id().value())
+        );
+
+        // The tag carries over to the document's derivatives
+        let derivatives = fixture
+            .service
+            .search(project_id, "tag:privileged type:derivative", None)
+            .await
+            .unwrap();
+        assert_eq!(derivatives.total, 2);
+
+        let untagged = fixture
+            .service
+            .search(project_id, "tag:contracts", None)
+            .await
+            .unwrap();
+        assert_eq!(untagged.total, 0);
+    }
+
+    fn relation<'a>(hit: &'a SearchHitDto, name: &str) -> Vec<&'a str> {
+        hit.relations
+            .iter()
+            .filter(|relation| relation.relation == name)
+            .map(|relation| relation.title.as_str())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_search_ranks_all_sources_and_relates_hits() {
+        let fixture = create_fixture().await;
+        let project_id = fixture.project.id().value();
+
+        let results = fixture
+            .service
+            .search(project_id, "settlement", None)
+            .await
+            .unwrap();
+        // The recording matches through its embedded title tag
+        assert_eq!(results.total, 5);
+        assert_eq!(results.counts["original"], 1);
+        assert_eq!(results.counts["derivative"], 2);
+        assert_eq!(results.counts["report"], 1);
+        assert_eq!(results.counts["metadata"], 1);
+
+        // Title matches beat tag matches, which beat text-only matches
+        let kinds: Vec<SourceKind> = results.hits.iter().map(|hit| hit.kind).collect();
+        assert_eq!(
+            kinds,
+            [
+                SourceKind::Metadata,
+                SourceKind::Report,
+                SourceKind::Original,
+                SourceKind::Derivative,
+                SourceKind::Derivative
+            ]
+        );
+        let report = &results.hits[1];
+        assert_eq!(report.matched_fields, ["title", "text", "path"]);
+        assert_eq!(relation(report, "cites"), ["agreement.pdf"]);
+
+        let summary = results
+            .hits
+            .iter()
+            .find(|hit| hit.title == "summary.det")
+            .unwrap();
+        assert_eq!(relation(summary, "derivedFrom"), ["extracted.det"]);
+        assert_eq!(
+            summary.snippet.as_deref(),
+            Some("Short settlement summary.")
+        );
+        let extracted = results
+            .hits
+            .iter()
+            .find(|hit| hit.title == "extracted.det")
+            .unwrap();
+        assert_eq!(relation(extracted, "derivedFrom"), ["agreement.pdf"]);
+        assert_eq!(
+            extracted.document_id.as_deref(),
+            Some(fixture.agreement.id().value())
+        );
+
+        let metadata = results
+            .hits
+            .iter()
+            .find(|hit| hit.kind == SourceKind::Metadata)
+            .unwrap();
+        assert_eq!(metadata.title, "Settlement hearing");
+        assert_eq!(relation(metadata, "describes"), ["interview.mp3"]);
+    }
+
+    #[tokio::test]
+    async fn test_search_reads_derivative_text_from_the_index() {
+        let fixture = create_fixture().await;
+        let project_id = fixture.project.id().value();
+        let summaries = |query: &'static str| fixture.service.search(project_id, query, None);
+        assert_eq!(summaries("settlement summary").await.unwrap().total, 1);
+
+        // An unchanged file is not read again
+        let path = DerivativeFamily::new(
+            &fixture.service.derivatives_root,
+            fixture.project.id(),
+            fixture.agreement.id(),
+        )
+        .file("summary.det");
+        let written = std::fs::metadata(&path).unwrap().modified().unwrap();
+        std::fs::write(&path, "not a det file").unwrap();
+        std::fs::File::options()
+            .write(true)
+            .open(&path)
+            .unwrap()
+            .set_modified(written)
+            .unwrap();
+        let results = summaries("settlement summary").await.unwrap();
+        assert_eq!(results.total, 1);
+        assert_eq!(
+            results.hits[0].snippet.as_deref(),
+            Some("Short settlement summary.")
+        );
+
+        // A changed one is, and is left out when it cannot be read
+        std::fs::File::options()
+            .write(true)
+            .open(&path)
+            .unwrap()
+            .set_modified(written + std::time::Duration::from_secs(1))
+            .unwrap();
+        assert_eq!(summaries("settlement summary").await.unwrap().total, 0);
+    }
+
+    #[tokio::test]
+    async fn test_search_filters() {
+        let fixture = create_fixture().await;
+        let project_id = fixture.project.id().value();
+        let search = |query: &'static str| fixture.service.search(project_id, query, None);
+
+        let summaries = search("settlement derivative:summary").await.unwrap();
+        assert_eq!(summaries.total, 1);
+        assert_eq!(summaries.hits[0].title, "summary.det");
+
+        let pdfs = search("type:original ext:pdf").await.unwrap();
+        assert_eq!(pdfs.total, 1);
+        assert_eq!(
+            relation(&pdfs.hits[0], "hasDerivative"),
+            ["extracted.det", "summary.det"]
+        );
+        assert_eq!(relation(&pdfs.hits[0], "citedBy"), ["Settlement findings"]);
+
+        let tagged = search("tag:finance type:original").await.unwrap();
+        assert_eq!(tagged.total, 1);
+        assert_eq!(
+            tagged.hits[0].document_id.as_deref(),
+            Some(fixture.interview.id().value())
+        );
+
+        assert!(search("type:report modified:<2000-01-01")
+            .await
+            .unwrap()
+            .hits
+            .is_empty());
+        assert_eq!(
+            search("modified:>yesterday").await.unwrap_err().code,
+            "VALIDATION_ERROR"
+        );
+        assert!(search("owner:me").await.unwrap().hits.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_search_email_headers() {
+        let fixture = create_fixture().await;
+        let project_id = fixture.project.id().value();
+
+        let email = Document::new(
+            fixture.project.id().clone(),
+            "/corpus/offer.eml".to_string(),
+            ContentHash::of_bytes(HashAlgorithm::Blake3, b"offer"),
+            5,
+            0,
+        );
+        let documents = SqliteDocumentRepository::new(fixture.database.pool());
+        documents.save(&email).await.unwrap();
+        let emails = Arc::new(SqliteEmailRepository::new(fixture.database.pool()));
+        let message = EmailMessage::new(
+            email.id().clone(),
+            fixture.project.id().clone(),
+            0,
+            "blake3:offer".to_string(),
+            "email-parser-v1",
+            EmailHeaders {
+                from: Some("Ana Silva <ana@example.com>".to_string()),
+                to: vec!["ben@example.com".to_string()],
+                subject: Some("Offer for lot 4".to_string()),
+                ..EmailHeaders::default()
+            },
+            Vec::new(),
+        );
+        emails
+            .replace_messages(email.id(), &[message])
+            .await
+            .unwrap();
+        let service = fixture.service.clone().with_email_repository(emails);
+
+        let results = service
+            .search(project_id, "silva type:metadata", None)
+            .await
+            .unwrap();
+        assert_eq!(results.total, 1);
+        assert_eq!(results.hits[0].title, "Offer for lot 4");
+        assert_eq!(relation(&results.hits[0], "describes"), ["offer.eml"]);
+
+        let sent = service
+            .search(project_id, "tag:ANA@example.com", None)
+            .await
+            .unwrap();
+        assert_eq!(sent.counts["metadata"], 1);
+    }
+
+    #[tokio::test]
+    async fn test_search_archive_members() {
+        use std::io::Write;
+
+        let fixture = create_fixture().await;
+        let project_id = fixture.project.id().value();
+        let folder = TempDir::new().unwrap();
+        let path = folder.path().join("records.zip");
+        let mut writer = zip::ZipWriter::new(std::fs::File::create(&path).unwrap());
+        writer
+            .start_file(
+                "2019/board minutes.txt",
+                zip::write::SimpleFileOptions::default(),
+            )
+            .unwrap();
+        writer.write_all(b"minutes").unwrap();
+        writer.finish().unwrap();
+
+        let archive = Document::new(
+            fixture.project.id().clone(),
+            path.to_string_lossy().to_string(),
+            ContentHash::of_bytes(HashAlgorithm::Blake3, b"records"),
+            7,
+            0,
+        );
+        SqliteDocumentRepository::new(fixture.database.pool())
+            .save(&archive)
+            .await
+            .unwrap();
+        let archives = Arc::new(ArchiveService::new(folder.path().join("extracted")));
+
+        let without = fixture
+            .service
+            .search(project_id, "minutes", None)
+            .await
+            .unwrap();
+        assert_eq!(without.total, 0);
+
+        let service = fixture.service.clone().with_archive_service(archives);
+        let results = service
+            .search(project_id, "minutes ext:txt", None)
+            .await
+            .unwrap();
+        assert_eq!(results.total, 1);
+        let hit = &results.hits[0];
+        assert_eq!(hit.title, "board minutes.txt");
+        assert_eq!(
+            hit.path,
+            format!("{}!/2019/board minutes.txt", path.display())
+        );
+        assert_eq!(hit.document_id, None);
+        assert_eq!(relation(hit, "containedIn"), ["records.zip"]);
+    }
+
+    #[tokio::test]
+    async fn test_search_all_projects_groups_pages_and_skips() {
+        let fixture = create_fixture().await;
+
+        // A second project with one matching file, and one whose source is gone
+        let archive_source = TempDir::new().unwrap();
+        let archive = Project::new(
+            "Archive".to_string(),
+            archive_source.path().to_string_lossy().to_string(),
+            None,
+        )
+        .unwrap();
+        let gone_source = TempDir::new().unwrap();
+        let gone = Project::new(
+            "Gone".to_string(),
+            gone_source.path().to_string_lossy().to_string(),
+            None,
+        )
+        .unwrap();
+        for project in [&archive, &gone] {
+            fixture
+                .service
+                .project_repository
+                .create(project)
+                .await
+                .unwrap();
+        }
+        drop(gone_source);
+        let memo = Document::new(
+            archive.id().clone(),
+            archive_source
+                .path()
+                .join("settlement-memo.txt")
+                .to_string_lossy()
+                .to_string(),
+            ContentHash::of_bytes(HashAlgorithm::Blake3, b"memo"),
+            1,
+            0,
+        );
+        fixture
+            .service
+            .document_repository
+            .save_all(&[memo])
+            .await
+            .unwrap();
+
+        let first = fixture
+            .service
+            .search_all_projects("settlement", 0, Some(4))
+            .await
+            .unwrap();
+        assert_eq!(first.total, 6);
+        assert_eq!(first.projects_searched, 2);
+        assert!(first.has_more);
+        assert_eq!(first.skipped.len(), 1);
+        assert_eq!(first.skipped[0].project.name, "Gone");
+
+        let second = fixture
+            .service
+            .search_all_projects("settlement", 4, Some(4))
+            .await
+            .unwrap();
+        assert!(!second.has_more);
+
+        // Every hit appears on exactly one page, under its own project
+        let mut hits: Vec<(String, String)> = Vec::new();
+        for page in [&first, &second] {
+            for group in &page.groups {
+                assert!(!group.hits.is_empty());
+                hits.extend(
+                    group
+                        .hits
+                        .iter()
+                        .map(|hit| (group.project.name.clone(), hit.title.clone())),
+                );
+            }
+        }
+        assert_eq!(hits.len(), 6);
+        assert!(hits.contains(&("Archive".to_string(), "settlement-memo.txt".to_string())));
+        assert_eq!(
+            hits.iter()
+                .filter(|(project, _)| project == "Search")
+                .count(),
+            5
+        );
+        let archive_group = first
+            .groups
+            .iter()
+            .chain(&second.groups)
+            .find(|group| group.project.name == "Archive")
+            .unwrap();
+        assert_eq!(archive_group.total, 1);
+        assert_eq!(archive_group.counts["original"], 1);
+    }
+}
@@ -0,0 +1,482 @@
+use async_trait::async_trait;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, UNIX_EPOCH};
+
+use crate::application::dtos::{SnapshotDiffDto, SnapshotSummaryDto};
+use crate::application::jobs::{JobContext, JobHandler};
+use crate::application::services::hashing_service::{list_files, parse_hash_algorithm};
+use crate::application::services::project_service::{
+    load_accessible_project, output_folders, parse_project_id,
+};
+use crate::application::services::HashingService;
+use crate::domain::project::{ProjectId, ProjectRepository};
+use crate::domain::workspace::entities::{FileEntry, ManifestEntry, ManifestSnapshot};
+use crate::domain::workspace::repositories::ManifestSnapshotRepository;
+use crate::domain::workspace::value_objects::{HashAlgorithm, SnapshotId};
+use crate::infrastructure::{AppError, AppResult};
+
+/// Job kind for taking a manifest snapshot in the background
+pub const CREATE_SNAPSHOT_JOB: &str = "create_snapshot";
+
+/// Application service for manifest snapshots of source folders
+///
+/// A snapshot records path, size, modification time and content hash of
+/// every file in a project's source folder. Comparing two snapshots, or a
+/// snapshot with the live folder, shows what a new production added,
+/// removed, changed or merely renamed.
+pub struct SnapshotService {
+    project_repository: Arc<dyn ProjectRepository>,
+    snapshot_repository: Arc<dyn ManifestSnapshotRepository>,
+    hashing_service: Arc<HashingService>,
+}
+
+impl SnapshotService {
+    /// Create a new SnapshotService
+    pub fn new(
+        project_repository: Arc<dyn ProjectRepository>,
+        snapshot_repository: Arc<dyn ManifestSnapshotRepository>,
+        hashing_service: Arc<HashingService>,
+    ) -> Self {
+        SnapshotService {
+            project_repository,
+            snapshot_repository,
+            hashing_service,
+        }
+    }
+
+    /// Take and store a snapshot of a project's source folder
+    pub async fn create_snapshot(
+        &self,
+        project_id: &str,
+        label: Option<String>,
+        algorithm: HashAlgorithm,
+        context: Option<&JobContext>,
+    ) -> AppResult<SnapshotSummaryDto> {
+        let project = load_accessible_project(self.project_repository.as_ref(), project_id).await?;
+
+        self.snapshot_folder(
+            project.id(),
+            project.source_folder().value(),
+            &output_folders(&project),
+            label,
+            algorithm,
+            context,
+        )
+        .await
+    }
+
+    /// Take and store a snapshot of the files below `folder`, leaving out
+    /// the `excluded` folders
+    pub async fn snapshot_folder(
+        &self,
+        project_id: &ProjectId,
+        folder: &Path,
+        excluded: &[PathBuf],
+        label: Option<String>,
+        algorithm: HashAlgorithm,
+        context: Option<&JobContext>,
+    ) -> AppResult<SnapshotSummaryDto> {
+        let entries = self
+            .build_manifest(folder, excluded, algorithm, context)
+            .await?;
+        let snapshot = ManifestSnapshot::new(project_id.clone(), label, algorithm, entries);
+
+        self.snapshot_repository.save(&snapshot).await?;
+
+        tracing::debug!(
+            "Created snapshot {} of {} with {} files",
+            snapshot.id(),
+            project_id,
+            snapshot.file_count()
+        );
+
+        Ok(SnapshotSummaryDto::from(&snapshot.summary()))
+    }
+
+    /// List the snapshots of a project, newest first
+    pub async fn list_snapshots(&self, project_id: &str) -> AppResult<Vec<SnapshotSummaryDto>> {
+        let id = parse_project_id(project_id)?;
+
+        Ok(self
+            .snapshot_repository
+            .list_by_project(&id)
+            .await?
+            .iter()
+            .map(SnapshotSummaryDto::from)
+            .collect())
+    }
+
+    /// Delete a snapshot and its entries
+    pub async fn delete_snapshot(&self, snapshot_id: &str) -> AppResult<()> {
+        let id = Self::parse_snapshot_id(snapshot_id)?;
+
+        if !self.snapshot_repository.delete(&id).await? {
+            return Err(AppError::not_found(format!(
+                "Snapshot with ID '{}'",
+                snapshot_id
+            )));
+        }
+        Ok(())
+    }
+
+    /// Compare a snapshot with another snapshot or, if `target_snapshot_id`
+    /// is None, with the project's source folder as it is now
+    pub async fn diff_snapshots(
+        &self,
+        project_id: &str,
+        base_snapshot_id: &str,
+        target_snapshot_id: Option<&str>,
+    ) -> AppResult<SnapshotDiffDto> {
+        let id = parse_project_id(project_id)?;
+        let base = self.load_snapshot(&id, base_snapshot_id).await?;
+
+        match target_snapshot_id {
+            Some(target_snapshot_id) => {
+                let target = self.load_snapshot(&id, target_snapshot_id).await?;
+                let diff = base.diff(&target).map_err(|e| {
+                    AppError::validation_error("Snapshots cannot be compared", Some(e.to_string()))
+                })?;
+
+                Ok(SnapshotDiffDto::new(
+                    project_id.to_string(),
+                    base_snapshot_id.to_string(),
+                    Some(target_snapshot_id.to_string()),
+                    &diff,
+                ))
+            }
+            None => {
+                let project =
+                    load_accessible_project(self.project_repository.as_ref(), project_id).await?;
+                self.diff_with_folder(
+                    &base,
+                    project.source_folder().value(),
+                    &output_folders(&project),
+                )
+                .await
+            }
+        }
+    }
+
+    /// Compare a snapshot with the files currently below `folder`, outside
+    /// the `excluded` folders
+    ///
+    /// The live manifest is hashed with the snapshot's algorithm and is not
+    /// stored.
+    pub async fn diff_with_folder(
+        &self,
+        base: &ManifestSnapshot,
+        folder: &Path,
+        excluded: &[PathBuf],
+    ) -> AppResult<SnapshotDiffDto> {
+        let live = ManifestSnapshot::new(
+            base.project_id().clone(),
+            None,
+            base.algorithm(),
+            self.build_manifest(folder, excluded, base.algorithm(), None)
+                .await?,
+        );
+        let diff = base.diff(&live).map_err(|e| {
+            AppError::validation_error("Snapshots cannot be compared", Some(e.to_string()))
+        })?;
+
+        Ok(SnapshotDiffDto::new(
+            base.project_id().value().to_string(),
+            base.id().as_str().to_string(),
+            None,
+            &diff,
+        ))
+    }
+
+    /// Load a stored snapshot by its identifier
+    pub async fn get_snapshot(&self, snapshot_id: &str) -> AppResult<ManifestSnapshot> {
+        let id = Self::parse_snapshot_id(snapshot_id)?;
+
+        self.snapshot_repository
+            .find_by_id(&id)
+            .await?
+            .ok_or_else(|| AppError::not_found(format!("Snapshot with ID '{}'", snapshot_id)))
+    }
+
+    /// Render a diff for export
+    ///
+    /// Supported formats are "csv" (one row per change) and "json" (the
+    /// full `SnapshotDiffDto`).
+    pub fn render_diff(diff: &SnapshotDiffDto, format: &str) -> AppResult<String> {
+        match format.to_ascii_lowercase().as_str() {
+            "csv" => diff
+                .to_csv()
+                .map_err(|e| AppError::internal_error(format!("Failed to write CSV: {}", e))),
+            "json" => serde_json::to_string_pretty(diff)
+                .map_err(|e| AppError::internal_error(format!("Failed to write JSON: {}", e))),
+            other => Err(AppError::validation_error(
+                "Unsupported export format",
+                Some(format!("'{}' is not one of csv, json", other)),
+            )),
+        }
+    }
+
+    /// Record every file below `folder` as a manifest entry
+    async fn build_manifest(
+        &self,
+        folder: &Path,
+        excluded: &[PathBuf],
+        algorithm: HashAlgorithm,
+        context: Option<&JobContext>,
+    ) -> AppResult<Vec<ManifestEntry>> {
+        let files = {
+            let folder = folder.to_path_buf();
+            let excluded = excluded.to_vec();
+            tokio::task::spawn_blocking(move || list_files(&folder, &excluded))
+                .await
+                .map_err(|e| AppError::internal_error(format!("File scan failed: {}", e)))?
+        };
+
+        let total = files.len() as u64;
+        let mut entries = Vec::with_capacity(files.len());
+
+        for (processed, (path, stamp)) in files.into_iter().enumerate() {
+            if let Some(context) = context {
+                context.check_cancelled()?;
+                context
+                    .report_progress(processed as u64, Some(total), Some("Recording manifest"))
+                    .await;
+            }
+
+            let hash = match self.hashing_service.hash_file(&path, algorithm).await {
+                Ok(hash) => hash,
+                Err(error) => {
+                    tracing::warn!(
+                        "Leaving {} out of the manifest: {}",
+                        path.display(),
+                        error.user_message()
+                    );
+                    continue;
+                }
+            };
+
+            let name = path
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_default();
+            let modified = UNIX_EPOCH + Duration::from_nanos(stamp.modified_ns.max(0) as u64);
+            let entry = FileEntry::file(name, &path, Some(stamp.size), modified)
+                .and_then(|file| ManifestEntry::from_file_entry(&file, folder, hash))
+                .map_err(|e| {
+                    AppError::filesystem_error(format!("Cannot record {}: {}", path.display(), e))
+                })?;
+            entries.push(entry);
+        }
+
+        if let Some(context) = context {
+            context.report_progress(total, Some(total), None).await;
+        }
+
+        Ok(entries)
+    }
+
+    /// Load a snapshot and check that it belongs to the project
+    async fn load_snapshot(
+        &self,
+        project_id: &ProjectId,
+        snapshot_id: &str,
+    ) -> AppResult<ManifestSnapshot> {
+        let snapshot = self.get_snapshot(snapshot_id).await?;
+
+        if snapshot.project_id() != project_id {
+            return Err(AppError::validation_error(
+                "Snapshot belongs to a different project",
+                Some(snapshot_id.to_string()),
+            ));
+        }
+        Ok(snapshot)
+    }
+
+    fn parse_snapshot_id(snapshot_id: &str) -> AppResult<SnapshotId> {
+        SnapshotId::from_string(snapshot_id.to_string())
+            .map_err(|e| AppError::validation_error("Invalid snapshot ID format", Some(e)))
+    }
+}
+
+/// Takes a manifest snapshot on the background job pool
+///
+/// Expects `{"label": "...", "algorithm": "blake3" | "sha256"}` as
+/// parameters (both optional) and returns a serialized `SnapshotSummaryDto`.
+pub struct CreateSnapshotJobHandler {
+    service: Arc<SnapshotService>,
+}
+
+impl CreateSnapshotJobHandler {
+    pub fn new(service: Arc<SnapshotService>) -> Self {
+        CreateSnapshotJobHandler { service }
+    }
+}
+
+#[async_trait]
+impl JobHandler for CreateSnapshotJobHandler {
+    fn kind(&self) -> &'static str {
+        CREATE_SNAPSHOT_JOB
+    }
+
+    async fn run(
+        &self,
+        context: JobContext,
+        params: serde_json::Value,
+    ) -> AppResult<serde_json::Value> {
+        let project_id = context
+            .project_id()
+            .ok_or_else(|| AppError::validation_error("A snapshot requires a project", None))?
+            .to_string();
+        let label = params
+            .get("label")
+            .and_then(|v| v.as_str())
+            .map(str::to_string);
+        let algorithm = parse_hash_algorithm(params.get("algorithm").and_then(|v| v.as_str()))?;
+
+        let summary = self
+            .service
+            .create_snapshot(&project_id, label, algorithm, Some(&context))
+            .await?;
+
+        serde_json::to_value(summary)
+            .map_err(|e| AppError::internal_error(format!("Failed to serialize result: {}", e)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::project::repositories::MockProjectRepository;
+    use crate::infrastructure::{
+        DatabaseConnection, SqliteFileHashRepository, SqliteManifestSnapshotRepository,
+    };
+    use std::fs;
+    use tempfile::TempDir;
+
+    async fn create_test_service() -> (SnapshotService, TempDir) {
+        let (database, temp_dir) = DatabaseConnection::new_temp().await.unwrap();
+        let project_repository = Arc::new(MockProjectRepository::new());
+        let hashing_service = Arc::new(HashingService::new(
+            project_repository.clone(),
+            Arc::new(SqliteFileHashRepository::new(database.pool())),
+        ));
+        let service = SnapshotService::new(
+            project_repository,
+            Arc::new(SqliteManifestSnapshotRepository::new(database.pool())),
+            hashing_service,
+        );
+        (service, temp_dir)
+    }
+
+    fn create_test_corpus() -> TempDir {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+
+        fs::write(root.join("offer.pdf"), b"offer letter").unwrap();
+        fs::write(root.join("invoice.pdf"), b"invoice 42").unwrap();
+        fs::write(root.join("notes.txt"), b"notes").unwrap();
+        fs::create_dir(root.join("archive")).unwrap();
+
+        temp_dir
+    }
+
+    #[tokio::test]
+    async fn test_snapshot_and_list() {
+        let (service, _db_dir) = create_test_service().await;
+        let corpus = create_test_corpus();
+        let project_id = ProjectId::new();
+
+        let summary = service
+            .snapshot_folder(
+                &project_id,
+                corpus.path(),
+                &[],
+                Some("Initial".to_string()),
+                HashAlgorithm::Blake3,
+                None,
+            )
+            .await
+            .unwrap();
+        assert_eq!(summary.file_count, 3);
+        assert_eq!(summary.label.as_deref(), Some("Initial"));
+
+        let snapshot = service.get_snapshot(&summary.id).await.unwrap();
+        assert!(snapshot.entries().iter().any(|e| e.path == "offer.pdf"));
+
+        let listed = service.list_snapshots(project_id.value()).await.unwrap();
+        assert_eq!(listed, vec![summary.clone()]);
+
+        service.delete_snapshot(&summary.id).await.unwrap();
+        assert!(service.delete_snapshot(&summary.id).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_diff_between_snapshots_and_live_folder() {
+        let (service, _db_dir) = create_test_service().await;
+        let corpus = create_test_corpus();
+        let root = corpus.path();
+        let project_id = ProjectId::new();
+
+        let base = service
+            .snapshot_folder(&project_id, root, &[], None, HashAlgorithm::Blake3, None)
+            .await
+            .unwrap();
+
+        fs::rename(
+            root.join("offer.pdf"),
+            root.join("archive").join("offer.pdf"),
+        )
+        .unwrap();
+        fs::write(root.join("invoice.pdf"), b"invoice 42, corrected").unwrap();
+        fs::remove_file(root.join("notes.txt")).unwrap();
+        fs::write(root.join("reply.eml"), b"reply").unwrap();
+
+        let base_snapshot = service.get_snapshot(&base.id).await.unwrap();
+        let live = service
+            .diff_with_folder(&base_snapshot, root, &[])
+            .await
+            .unwrap();
+        assert_eq!(live.target_snapshot_id, None);
+        assert_eq!(
+            (live.added, live.removed, live.modified, live.renamed),
+            (1, 1, 1, 1)
+        );
+        let renamed = live.changes.iter().find(|c| c.kind == "renamed").unwrap();
+        assert_eq!(renamed.path, "archive/offer.pdf");
+        assert_eq!(renamed.previous_path.as_deref(), Some("offer.pdf"));
+
+        let target = service
+            .snapshot_folder(&project_id, root, &[], None, HashAlgorithm::Blake3, None)
+            .await
+            .unwrap();
+        let stored = service
+            .diff_snapshots(project_id.value(), &base.id, Some(&target.id))
+            .await
+            .unwrap();
+        assert_eq!(stored.changes, live.changes);
+
+        let other_project = ProjectId::new();
+        assert!(service
+            .diff_snapshots(other_project.value(), &base.id, Some(&target.id))
+            .await
+            .is_err());
+    }
+
+    #[test]
+    fn test_render_diff_formats() {
+        let diff = SnapshotDiffDto::new(
+            "proj".to_string(),
+            "snap".to_string(),
+            None,
+            &Default::default(),
+        );
+
+        assert!(SnapshotService::render_diff(&diff, "CSV")
+            .unwrap()
+            .starts_with("kind,path"));
+        assert!(SnapshotService::render_diff(&diff, "json")
+            .unwrap()
+            .contains("\"baseSnapshotId\": \"snap\""));
+        assert!(SnapshotService::render_diff(&diff, "xml").is_err());
+    }
+}
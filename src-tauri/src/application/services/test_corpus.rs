@@ -0,0 +1,51 @@
+use std::fs;
+use std::sync::Arc;
+use tempfile::TempDir;
+
+use crate::domain::document::{Document, DocumentRepository};
+use crate::domain::project::ProjectId;
+use crate::domain::workspace::value_objects::{ContentHash, HashAlgorithm};
+use crate::infrastructure::{DatabaseConnection, SqliteDocumentRepository};
+
+/// Documents of one project in a temporary folder, for the tests of
+/// services that process documents
+///
+/// Holds a temporary database and a derivatives root for the service under
+/// test; both are removed when the corpus is dropped.
+pub(crate) struct TestCorpus {
+    pub database: DatabaseConnection,
+    pub repository: Arc<SqliteDocumentRepository>,
+    pub project_id: ProjectId,
+    pub folder: TempDir,
+    pub derivatives: TempDir,
+    _db_dir: TempDir,
+}
+
+impl TestCorpus {
+    pub async fn new() -> Self {
+        let (database, db_dir) = DatabaseConnection::new_temp().await.unwrap();
+        TestCorpus {
+            repository: Arc::new(SqliteDocumentRepository::new(database.pool())),
+            database,
+            project_id: ProjectId::new(),
+            folder: TempDir::new().unwrap(),
+            derivatives: TempDir::new().unwrap(),
+            _db_dir: db_dir,
+        }
+    }
+
+    /// Write a file into the corpus folder and record it as a document
+    pub async fn add_document(&self, name: &str, contents: &[u8]) -> Document {
+        let path = self.folder.path().join(name);
+        fs::write(&path, contents).unwrap();
+        let document = Document::new(
+            self.project_id.clone(),
+            path.to_string_lossy().to_string(),
+            ContentHash::of_bytes(HashAlgorithm::Blake3, contents),
+            contents.len() as u64,
+            0,
+        );
+        self.repository.save(&document).await.unwrap();
+        document
+    }
+}
@@ -1,16 +1,55 @@
 use crate::application::dtos::{DirectoryListingDto, FileEntryDto, WorkspaceDto};
+use crate::application::services::{
+    ArchiveService, EmailService, HashingService, SavedSearchService,
+};
+use crate::domain::workspace::value_objects::{ArchiveFormat, ArchivePath, HashAlgorithm};
 use crate::infrastructure::AppError;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
 /// Simplified workspace navigation service for MVP implementation
 ///
 /// This service provides workspace navigation functionality by working
 /// directly with the file system using existing file system operations.
-pub struct WorkspaceNavigationService;
+pub struct WorkspaceNavigationService {
+    hashing_service: Option<Arc<HashingService>>,
+    saved_search_service: Option<Arc<SavedSearchService>>,
+    email_service: Option<Arc<EmailService>>,
+    archive_service: Option<Arc<ArchiveService>>,
+}
 
 impl WorkspaceNavigationService {
     pub fn new() -> Self {
-        Self
+        Self {
+            hashing_service: None,
+            saved_search_service: None,
+            email_service: None,
+            archive_service: None,
+        }
+    }
+
+    /// Include already-computed content hashes in file entries
+    pub fn with_hashing_service(mut self, hashing_service: Arc<HashingService>) -> Self {
+        self.hashing_service = Some(hashing_service);
+        self
+    }
+
+    /// Show saved searches as virtual folders in the workspace root
+    pub fn with_saved_searches(mut self, saved_search_service: Arc<SavedSearchService>) -> Self {
+        self.saved_search_service = Some(saved_search_service);
+        self
+    }
+
+    /// List the attachments of parsed emails as the contents of the email
+    pub fn with_email_service(mut self, email_service: Arc<EmailService>) -> Self {
+        self.email_service = Some(email_service);
+        self
+    }
+
+    /// Browse archive files as read-only folders
+    pub fn with_archive_service(mut self, archive_service: Arc<ArchiveService>) -> Self {
+        self.archive_service = Some(archive_service);
+        self
     }
 
     /// Open a workspace for a project
@@ -21,7 +60,9 @@ impl WorkspaceNavigationService {
         source_folder: &str,
     ) -> Result<WorkspaceDto, AppError> {
         // Create basic workspace DTO with root directory listing
-        let directory_listing = self.list_directory_contents(source_folder).await?;
+        let directory_listing = self
+            .listing(project_id, source_folder, source_folder)
+            .await?;
 
         Ok(WorkspaceDto::new(
             project_id.to_string(),
@@ -40,7 +81,7 @@ impl WorkspaceNavigationService {
         source_folder: &str,
         current_path: &str,
     ) -> Result<DirectoryListingDto, AppError> {
-        self.list_directory_contents(current_path).await
+        self.listing(project_id, source_folder, current_path).await
     }
 
     /// Navigate to a specific folder
@@ -53,7 +94,13 @@ impl WorkspaceNavigationService {
         folder_name: &str,
     ) -> Result<WorkspaceDto, AppError> {
         let new_path = PathBuf::from(current_path).join(folder_name);
-        let new_path_str = new_path.to_string_lossy().to_string();
+        let mut new_path_str = new_path.to_string_lossy().to_string();
+        // Archives open at their root, as `<archive>!`
+        if self.archive_service.is_some() {
+            if let Some(folder) = ArchiveService::folder_location(&new_path_str) {
+                new_path_str = folder.to_string();
+            }
+        }
 
         // Validate that the new path is within the workspace boundaries
         if !new_path_str.starts_with(source_folder) {
@@ -63,7 +110,9 @@ impl WorkspaceNavigationService {
             ));
         }
 
-        let directory_listing = self.list_directory_contents(&new_path_str).await?;
+        let directory_listing = self
+            .listing(project_id, source_folder, &new_path_str)
+            .await?;
 
         Ok(WorkspaceDto::new(
             project_id.to_string(),
@@ -82,12 +131,17 @@ impl WorkspaceNavigationService {
         source_folder: &str,
         current_path: &str,
     ) -> Result<WorkspaceDto, AppError> {
-        let current_path_buf = PathBuf::from(current_path);
-        let parent_path = current_path_buf
-            .parent()
-            .ok_or_else(|| AppError::validation_error("Cannot navigate above root", None))?;
-
-        let parent_path_str = parent_path.to_string_lossy().to_string();
+        // Saved search folders sit directly in the workspace root
+        let parent_path_str =
+            if SavedSearchService::parse_folder_path(source_folder, current_path).is_some() {
+                source_folder.to_string()
+            } else {
+                let current_path_buf = PathBuf::from(current_path);
+                let parent_path = current_path_buf.parent().ok_or_else(|| {
+                    AppError::validation_error("Cannot navigate above root", None)
+                })?;
+                parent_path.to_string_lossy().to_string()
+            };
 
         // Ensure we don't navigate above the source folder
         if !parent_path_str.starts_with(source_folder) {
@@ -97,7 +151,9 @@ impl WorkspaceNavigationService {
             ));
         }
 
-        let directory_listing = self.list_directory_contents(&parent_path_str).await?;
+        let directory_listing = self
+            .listing(project_id, source_folder, &parent_path_str)
+            .await?;
 
         Ok(WorkspaceDto::new(
             project_id.to_string(),
@@ -124,7 +180,7 @@ impl WorkspaceNavigationService {
             ));
         }
 
-        let directory_listing = self.list_directory_contents(target_path).await?;
+        let directory_listing = self.listing(project_id, source_folder, target_path).await?;
 
         Ok(WorkspaceDto::new(
             project_id.to_string(),
@@ -166,10 +222,71 @@ impl WorkspaceNavigationService {
             return Ok(false);
         }
 
+        if let Some(archive_service) = &self.archive_service {
+            if ArchivePath::parse(path).is_some() {
+                return Ok(archive_service.entry(path).await.is_ok());
+            }
+        }
+
         let path_buf = PathBuf::from(path);
         Ok(path_buf.exists() && (path_buf.is_file() || path_buf.is_dir()))
     }
 
+    /// List a directory, the results of a saved search folder, the
+    /// attachments of an email or a folder inside an archive
+    ///
+    /// The workspace root also lists the project's saved searches, as
+    /// virtual folders before the real ones.
+    async fn listing(
+        &self,
+        project_id: &str,
+        source_folder: &str,
+        path: &str,
+    ) -> Result<DirectoryListingDto, AppError> {
+        if let Some(archive_service) = &self.archive_service {
+            if let Some(listing) = archive_service.listing(path).await? {
+                return Ok(listing);
+            }
+        }
+        if let Some(email_service) = &self.email_service {
+            if EmailService::is_email_path(path) {
+                if let Some(listing) = email_service.attachment_listing(project_id, path).await? {
+                    return Ok(listing);
+                }
+            }
+        }
+        let Some(saved_searches) = &self.saved_search_service else {
+            let mut listing = self.list_directory_contents(path).await?;
+            self.attach_attachment_counts(project_id, &mut listing.entries)
+                .await;
+            return Ok(listing);
+        };
+        if let Some(id) = SavedSearchService::parse_folder_path(source_folder, path) {
+            return saved_searches.list_folder(source_folder, &id).await;
+        }
+
+        let mut listing = self.list_directory_contents(path).await?;
+        self.attach_attachment_counts(project_id, &mut listing.entries)
+            .await;
+        if Path::new(path) == Path::new(source_folder) {
+            match saved_searches
+                .folder_entries(project_id, source_folder)
+                .await
+            {
+                Ok(folders) => {
+                    listing.entries.splice(0..0, folders);
+                }
+                // The real contents are still worth showing
+                Err(error) => tracing::warn!(
+                    "Failed to list saved searches of project {}: {}",
+                    project_id,
+                    error.user_message()
+                ),
+            }
+        }
+        Ok(listing)
+    }
+
     /// List directory contents (internal helper)
     async fn list_directory_contents(&self, path: &str) -> Result<DirectoryListingDto, AppError> {
         let path_buf = PathBuf::from(path);
@@ -222,7 +339,7 @@ impl WorkspaceNavigationService {
                                     .unwrap_or_default()
                                     .to_rfc3339();
 
-                            let file_entry = FileEntryDto::new(
+                            let mut file_entry = FileEntryDto::new(
                                 name,
                                 entry_path.to_string_lossy().to_string(),
                                 if is_directory {
@@ -233,6 +350,9 @@ impl WorkspaceNavigationService {
                                 size,
                                 modified_str,
                             );
+                            file_entry.is_archive = self.archive_service.is_some()
+                                && !is_directory
+                                && ArchiveFormat::is_archive_name(&file_entry.name);
 
                             entries.push(file_entry);
                         }
@@ -245,6 +365,8 @@ impl WorkspaceNavigationService {
             }
         }
 
+        self.attach_known_hashes(&mut entries).await;
+
         // Sort entries: directories first, then files, alphabetically
         entries.sort_by(|a, b| {
             use std::cmp::Ordering;
@@ -270,6 +392,12 @@ impl WorkspaceNavigationService {
 
     /// Get file metadata (internal helper)
     async fn get_file_metadata(&self, path: &str) -> Result<FileEntryDto, AppError> {
+        if let Some(archive_service) = &self.archive_service {
+            if let Some(entry) = archive_service.entry(path).await? {
+                return Ok(entry);
+            }
+        }
+
         let path_buf = PathBuf::from(path);
 
         if !path_buf.exists() {
@@ -303,7 +431,7 @@ impl WorkspaceNavigationService {
             .unwrap_or_default()
             .to_rfc3339();
 
-        Ok(FileEntryDto::new(
+        let mut entry = FileEntryDto::new(
             name,
             path.to_string(),
             if is_directory {
@@ -313,7 +441,79 @@ impl WorkspaceNavigationService {
             },
             size,
             modified_str,
-        ))
+        );
+        entry.is_archive = self.archive_service.is_some()
+            && !is_directory
+            && ArchiveFormat::is_archive_name(&entry.name);
+        self.attach_known_hashes(std::slice::from_mut(&mut entry))
+            .await;
+
+        Ok(entry)
+    }
+
+    /// Fill in the number of attachments of parsed emails (internal helper)
+    ///
+    /// Lookup failures leave the counts empty.
+    async fn attach_attachment_counts(&self, project_id: &str, entries: &mut [FileEntryDto]) {
+        let Some(email_service) = &self.email_service else {
+            return;
+        };
+        if !entries
+            .iter()
+            .any(|entry| entry.is_file() && EmailService::is_email_path(&entry.path))
+        {
+            return;
+        }
+
+        match email_service.attachment_counts(project_id).await {
+            Ok(counts) => {
+                for entry in entries.iter_mut() {
+                    entry.attachment_count = counts.get(&entry.path).copied();
+                }
+            }
+            Err(error) => {
+                tracing::warn!(
+                    "Failed to look up email attachments: {}",
+                    error.user_message()
+                );
+            }
+        }
+    }
+
+    /// Fill in cached content hashes for file entries (internal helper)
+    ///
+    /// Only hashes that are already cached and current are attached; files
+    /// are never read here. Lookup failures leave the hashes empty.
+    async fn attach_known_hashes(&self, entries: &mut [FileEntryDto]) {
+        let Some(hashing_service) = &self.hashing_service else {
+            return;
+        };
+
+        let paths: Vec<String> = entries
+            .iter()
+            .filter(|entry| entry.is_file())
+            .map(|entry| entry.path.clone())
+            .collect();
+        if paths.is_empty() {
+            return;
+        }
+
+        match hashing_service
+            .known_hashes(&paths, HashAlgorithm::default())
+            .await
+        {
+            Ok(known) => {
+                for entry in entries.iter_mut() {
+                    entry.content_hash = known.get(&entry.path).map(|hash| hash.to_string());
+                }
+            }
+            Err(error) => {
+                tracing::warn!(
+                    "Failed to look up cached content hashes: {}",
+                    error.user_message()
+                );
+            }
+        }
     }
 }
 
@@ -0,0 +1,105 @@
+use crate::application::dtos::{AnnotationDto, AnnotationReattachmentDto};
+use crate::application::services::AnnotationService;
+use crate::application::AppState;
+use crate::domain::annotation::AnnotationAnchor;
+use crate::infrastructure::AppError;
+use tauri::State;
+
+/// Tauri command to highlight a passage of a document, optionally with a
+/// comment
+///
+/// The anchor is either `{ "type": "page", "page", "start", "end" }` for
+/// character offsets on a page of the original, or
+/// `{ "type": "range", "from", "to" }` for ProseMirror positions in the
+/// document's extraction. Without an author, the active reviewer is
+/// recorded.
+#[tauri::command]
+pub async fn create_annotation(
+    document_id: String,
+    anchor: AnnotationAnchor,
+    quote: Option<String>,
+    color: Option<String>,
+    body: Option<String>,
+    author: Option<String>,
+    app_state: State<'_, AppState>,
+) -> Result<AnnotationDto, AppError> {
+    app_state
+        .annotation_service()
+        .create_annotation(&document_id, anchor, quote, color, body, author)
+        .await
+}
+
+/// Tauri command to change the comment or colour of an annotation
+#[tauri::command]
+pub async fn update_annotation(
+    annotation_id: String,
+    body: Option<String>,
+    color: Option<String>,
+    app_state: State<'_, AppState>,
+) -> Result<AnnotationDto, AppError> {
+    app_state
+        .annotation_service()
+        .update_annotation(&annotation_id, body, color)
+        .await
+}
+
+/// Tauri command to delete an annotation
+#[tauri::command]
+pub async fn delete_annotation(
+    annotation_id: String,
+    app_state: State<'_, AppState>,
+) -> Result<(), AppError> {
+    app_state
+        .annotation_service()
+        .delete_annotation(&annotation_id)
+        .await
+}
+
+/// Tauri command to list the annotations of a document
+#[tauri::command]
+pub async fn list_annotations(
+    document_id: String,
+    app_state: State<'_, AppState>,
+) -> Result<Vec<AnnotationDto>, AppError> {
+    app_state
+        .annotation_service()
+        .list_annotations(&document_id)
+        .await
+}
+
+/// Tauri command to move a document's annotations to their text after its
+/// extraction was corrected
+#[tauri::command]
+pub async fn reattach_annotations(
+    document_id: String,
+    app_state: State<'_, AppState>,
+) -> Result<AnnotationReattachmentDto, AppError> {
+    app_state
+        .annotation_service()
+        .reattach_annotations(&document_id)
+        .await
+}
+
+/// Tauri command to export all annotations of a project as "csv" or "json"
+/// to `destination_path`
+#[tauri::command]
+pub async fn export_annotations(
+    project_id: String,
+    format: String,
+    destination_path: String,
+    app_state: State<'_, AppState>,
+) -> Result<String, AppError> {
+    let export = app_state
+        .annotation_service()
+        .export_annotations(&project_id)
+        .await?;
+    let contents = AnnotationService::render_export(&export, &format)?;
+
+    tokio::fs::write(&destination_path, contents)
+        .await
+        .map_err(|e| {
+            AppError::filesystem_error(format!("Cannot write {}: {}", destination_path, e))
+        })?;
+
+    Ok(destination_path)
+}
@@ -0,0 +1,40 @@
+use crate::application::dtos::{AnonymizationDto, AnonymizationRequest};
+use crate::application::AppState;
+use crate::domain::anonymization::BuiltInDetector;
+use crate::infrastructure::AppError;
+use tauri::State;
+
+/// Tauri command to list the replacements an anonymization would make
+#[tauri::command]
+pub async fn preview_anonymization(
+    document_id: String,
+    request: AnonymizationRequest,
+    app_state: State<'_, AppState>,
+) -> Result<AnonymizationDto, AppError> {
+    app_state
+        .anonymization_service()
+        .preview_anonymization(&document_id, &request)
+        .await
+}
+
+/// Tauri command to write a document's anonymized derivative
+#[tauri::command]
+pub async fn anonymize_document(
+    document_id: String,
+    request: AnonymizationRequest,
+    app_state: State<'_, AppState>,
+) -> Result<AnonymizationDto, AppError> {
+    app_state
+        .anonymization_service()
+        .anonymize_document(&document_id, &request)
+        .await
+}
+
+/// Tauri command to list the built-in entity detectors
+#[tauri::command]
+pub async fn get_anonymization_detectors() -> Result<Vec<String>, AppError> {
+    Ok(BuiltInDetector::ALL
+        .iter()
+        .map(|detector| detector.as_str().to_string())
+        .collect())
+}
@@ -0,0 +1,15 @@
+use crate::application::AppState;
+use crate::infrastructure::AppError;
+use tauri::State;
+
+/// Tauri command to extract one archive member to a temp file for viewing
+///
+/// `path` is a virtual path such as `/corpus/production.zip!/letters/a.pdf`.
+/// Returns the path of a read-only copy; the archive itself is never changed.
+#[tauri::command]
+pub async fn extract_archive_member(
+    path: String,
+    app_state: State<'_, AppState>,
+) -> Result<String, AppError> {
+    app_state.archive_service().extract_member(&path).await
+}
@@ -0,0 +1,121 @@
+use crate::application::dtos::{BulkAssignmentDto, CategoryDto, CategoryTreeDto, DocumentDto};
+use crate::application::AppState;
+use crate::infrastructure::AppError;
+use tauri::State;
+
+/// Tauri command to create a category or tag in a project
+///
+/// `kind` is "category" or "tag". The colour is a hex colour such as
+/// `#3b82f6`; the shortcut is a key with optional modifiers, e.g.
+/// `Ctrl+Shift+1`.
+#[tauri::command]
+pub async fn create_category(
+    project_id: String,
+    kind: String,
+    name: String,
+    parent_id: Option<String>,
+    color: Option<String>,
+    shortcut: Option<String>,
+    app_state: State<'_, AppState>,
+) -> Result<CategoryDto, AppError> {
+    app_state
+        .category_service()
+        .create_category(&project_id, &kind, name, parent_id, color, shortcut)
+        .await
+}
+
+/// Tauri command to rename, move, recolour or rebind a category or tag
+///
+/// An empty parent moves it to the top level; an empty colour or shortcut
+/// removes it.
+#[tauri::command]
+pub async fn update_category(
+    category_id: String,
+    name: Option<String>,
+    parent_id: Option<String>,
+    color: Option<String>,
+    shortcut: Option<String>,
+    app_state: State<'_, AppState>,
+) -> Result<CategoryDto, AppError> {
+    app_state
+        .category_service()
+        .update_category(&category_id, name, parent_id, color, shortcut)
+        .await
+}
+
+/// Tauri command to delete a category or tag with everything below it
+#[tauri::command]
+pub async fn delete_category(
+    category_id: String,
+    app_state: State<'_, AppState>,
+) -> Result<usize, AppError> {
+    app_state
+        .category_service()
+        .delete_category(&category_id)
+        .await
+}
+
+/// Tauri command to get the category tree and tags of a project with
+/// document counts, for the Category Explorer
+#[tauri::command]
+pub async fn list_categories(
+    project_id: String,
+    app_state: State<'_, AppState>,
+) -> Result<CategoryTreeDto, AppError> {
+    app_state
+        .category_service()
+        .list_categories(&project_id)
+        .await
+}
+
+/// Tauri command to assign a category or tag to several documents
+#[tauri::command]
+pub async fn assign_category(
+    category_id: String,
+    document_ids: Vec<String>,
+    app_state: State<'_, AppState>,
+) -> Result<BulkAssignmentDto, AppError> {
+    app_state
+        .category_service()
+        .assign_category(&category_id, document_ids)
+        .await
+}
+
+/// Tauri command to remove a category or tag from several documents
+#[tauri::command]
+pub async fn unassign_category(
+    category_id: String,
+    document_ids: Vec<String>,
+    app_state: State<'_, AppState>,
+) -> Result<BulkAssignmentDto, AppError> {
+    app_state
+        .category_service()
+        .unassign_category(&category_id, document_ids)
+        .await
+}
+
+/// Tauri command to list the documents of a category or tag, optionally
+/// including those of the nodes below it
+#[tauri::command]
+pub async fn list_documents_by_category(
+    category_id: String,
+    include_descendants: Option<bool>,
+    app_state: State<'_, AppState>,
+) -> Result<Vec<DocumentDto>, AppError> {
+    app_state
+        .category_service()
+        .list_documents_by_category(&category_id, include_descendants.unwrap_or(true))
+        .await
+}
+
+/// Tauri command to get the categories and tags assigned to a document
+#[tauri::command]
+pub async fn get_document_categories(
+    document_id: String,
+    app_state: State<'_, AppState>,
+) -> Result<Vec<CategoryDto>, AppError> {
+    app_state
+        .category_service()
+        .categories_of_document(&document_id)
+        .await
+}
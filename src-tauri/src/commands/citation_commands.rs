@@ -0,0 +1,54 @@
+use crate::application::dtos::{CitationCheckDto, DocumentDto, ReportDto, ResolvedCitationDto};
+use crate::application::AppState;
+use crate::infrastructure::AppError;
+use tauri::State;
+
+/// Tauri command to list every report citing a document
+#[tauri::command]
+pub async fn list_citing_reports(
+    document_id: String,
+    app_state: State<'_, AppState>,
+) -> Result<Vec<ReportDto>, AppError> {
+    app_state
+        .citation_service()
+        .list_citing_reports(&document_id)
+        .await
+}
+
+/// Tauri command to list every document a report cites
+#[tauri::command]
+pub async fn list_cited_documents(
+    report_id: String,
+    app_state: State<'_, AppState>,
+) -> Result<Vec<DocumentDto>, AppError> {
+    app_state
+        .citation_service()
+        .list_cited_documents(&report_id)
+        .await
+}
+
+/// Tauri command to resolve a report's citations to current paths and snippets
+#[tauri::command]
+pub async fn resolve_report_citations(
+    report_id: String,
+    app_state: State<'_, AppState>,
+) -> Result<Vec<ResolvedCitationDto>, AppError> {
+    app_state
+        .citation_service()
+        .resolve_report_citations(&report_id)
+        .await
+}
+
+/// Tauri command to find the broken citations in a project's reports
+///
+/// Also runs after document reconciliation whenever files changed.
+#[tauri::command]
+pub async fn check_broken_citations(
+    project_id: String,
+    app_state: State<'_, AppState>,
+) -> Result<CitationCheckDto, AppError> {
+    app_state
+        .citation_service()
+        .check_project(&project_id)
+        .await
+}
@@ -0,0 +1,108 @@
+use crate::application::dtos::{
+    CodingDecisionDto, CodingProgressDto, CodingSchemaDto, DocumentDto,
+};
+use crate::application::AppState;
+use crate::domain::coding::{CodingField, CodingValues};
+use crate::infrastructure::AppError;
+use tauri::State;
+
+/// Tauri command to get the coding schema of a project
+#[tauri::command]
+pub async fn get_coding_schema(
+    project_id: String,
+    app_state: State<'_, AppState>,
+) -> Result<CodingSchemaDto, AppError> {
+    app_state
+        .coding_service()
+        .get_coding_schema(&project_id)
+        .await
+}
+
+/// Tauri command to replace the coding schema of a project
+#[tauri::command]
+pub async fn save_coding_schema(
+    project_id: String,
+    fields: Vec<CodingField>,
+    app_state: State<'_, AppState>,
+) -> Result<CodingSchemaDto, AppError> {
+    app_state
+        .coding_service()
+        .save_coding_schema(&project_id, fields)
+        .await
+}
+
+/// Tauri command to code a document
+///
+/// `values` maps field keys to a string, a list of strings or a boolean,
+/// e.g. `{ "designation": "Privileged", "reason": "Legal advice" }`. Without
+/// a reviewer, the active reviewer is recorded.
+#[tauri::command]
+pub async fn code_document(
+    document_id: String,
+    values: CodingValues,
+    reviewer: Option<String>,
+    app_state: State<'_, AppState>,
+) -> Result<CodingDecisionDto, AppError> {
+    app_state
+        .coding_service()
+        .code_document(&document_id, values, reviewer)
+        .await
+}
+
+/// Tauri command to get the current coding of a document
+#[tauri::command]
+pub async fn get_document_coding(
+    document_id: String,
+    app_state: State<'_, AppState>,
+) -> Result<Option<CodingDecisionDto>, AppError> {
+    app_state
+        .coding_service()
+        .get_document_coding(&document_id)
+        .await
+}
+
+/// Tauri command to list all coding decisions on a document, oldest first
+#[tauri::command]
+pub async fn get_coding_history(
+    document_id: String,
+    app_state: State<'_, AppState>,
+) -> Result<Vec<CodingDecisionDto>, AppError> {
+    app_state
+        .coding_service()
+        .get_coding_history(&document_id)
+        .await
+}
+
+/// Tauri command to find the next document to code after the current one
+#[tauri::command]
+pub async fn next_uncoded_document(
+    project_id: String,
+    after_document_id: Option<String>,
+    app_state: State<'_, AppState>,
+) -> Result<Option<DocumentDto>, AppError> {
+    app_state
+        .coding_service()
+        .next_uncoded_document(&project_id, after_document_id)
+        .await
+}
+
+/// Tauri command to get how many documents of a project are coded
+#[tauri::command]
+pub async fn get_coding_progress(
+    project_id: String,
+    app_state: State<'_, AppState>,
+) -> Result<CodingProgressDto, AppError> {
+    app_state
+        .coding_service()
+        .get_coding_progress(&project_id)
+        .await
+}
+
+/// Tauri command to get the coding progress of every project, for the
+/// project list
+#[tauri::command]
+pub async fn list_coding_progress(
+    app_state: State<'_, AppState>,
+) -> Result<Vec<CodingProgressDto>, AppError> {
+    app_state.coding_service().list_coding_progress().await
+}
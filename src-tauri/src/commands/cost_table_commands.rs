@@ -0,0 +1,75 @@
+use crate::application::dtos::{CostTableDto, CostTableExportDto, SaveCostTableRequest};
+use crate::application::AppState;
+use crate::domain::cost_table::{CostCategory, CostTableFormat};
+use crate::infrastructure::AppError;
+use tauri::State;
+
+/// Tauri command to load a document's cost table with its totals
+///
+/// `purpose` selects one of several tables of the document; None is the
+/// default `cost-table.det`.
+#[tauri::command]
+pub async fn get_cost_table(
+    document_id: String,
+    purpose: Option<String>,
+    app_state: State<'_, AppState>,
+) -> Result<CostTableDto, AppError> {
+    app_state
+        .cost_table_service()
+        .get_cost_table(&document_id, purpose.as_deref())
+        .await
+}
+
+/// Tauri command to create or replace a document's cost table
+#[tauri::command]
+pub async fn save_cost_table(
+    document_id: String,
+    request: SaveCostTableRequest,
+    app_state: State<'_, AppState>,
+) -> Result<CostTableDto, AppError> {
+    app_state
+        .cost_table_service()
+        .save_cost_table(&document_id, request)
+        .await
+}
+
+/// Tauri command to replace a cost table's rows with a CSV or XLSX file
+#[tauri::command]
+pub async fn import_cost_table(
+    document_id: String,
+    purpose: Option<String>,
+    file_path: String,
+    app_state: State<'_, AppState>,
+) -> Result<CostTableDto, AppError> {
+    app_state
+        .cost_table_service()
+        .import_cost_table(&document_id, purpose.as_deref(), &file_path)
+        .await
+}
+
+/// Tauri command to export a cost table into the project's reports folder
+///
+/// `format` is "csv" or "xlsx".
+#[tauri::command]
+pub async fn export_cost_table(
+    document_id: String,
+    purpose: Option<String>,
+    format: String,
+    app_state: State<'_, AppState>,
+) -> Result<CostTableExportDto, AppError> {
+    let format = CostTableFormat::parse(&format)?;
+
+    app_state
+        .cost_table_service()
+        .export_cost_table(&document_id, purpose.as_deref(), format)
+        .await
+}
+
+/// Tauri command to list the standardized cost categories
+#[tauri::command]
+pub async fn get_cost_categories() -> Result<Vec<String>, AppError> {
+    Ok(CostCategory::ALL
+        .iter()
+        .map(|category| category.as_str().to_string())
+        .collect())
+}
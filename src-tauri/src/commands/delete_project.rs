@@ -418,6 +418,8 @@ mod tests {
             note_line_count: None,
             created_at: "2023-12-01T10:30:00Z".to_string(),
             is_accessible: true,
+            reports_folder: None,
+            immutable_source: false,
         };
 
         let deletion_info = DeletionInfo {
@@ -0,0 +1,16 @@
+use crate::application::dtos::DerivationGraphDto;
+use crate::application::AppState;
+use crate::infrastructure::AppError;
+use tauri::State;
+
+/// Tauri command to get a document family's derivation graph for visualization
+#[tauri::command]
+pub async fn get_derivation_graph(
+    document_id: String,
+    app_state: State<'_, AppState>,
+) -> Result<DerivationGraphDto, AppError> {
+    app_state
+        .derivation_service()
+        .get_derivation_graph(&document_id)
+        .await
+}
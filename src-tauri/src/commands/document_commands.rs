@@ -0,0 +1,76 @@
+use crate::application::dtos::{DocumentDto, DocumentReconciliationDto, JobDto};
+use crate::application::services::RECONCILE_DOCUMENTS_JOB;
+use crate::application::AppState;
+use crate::infrastructure::AppError;
+use tauri::State;
+
+/// Tauri command to reconcile a project's documents with its source folder
+///
+/// Registers new files, and re-links renamed or moved files to their
+/// existing document by content hash.
+#[tauri::command]
+pub async fn reconcile_documents(
+    project_id: String,
+    app_state: State<'_, AppState>,
+) -> Result<DocumentReconciliationDto, AppError> {
+    app_state
+        .document_service()
+        .reconcile_project(&project_id, None)
+        .await
+}
+
+/// Tauri command to run document reconciliation as a background job
+#[tauri::command]
+pub async fn start_document_reconciliation(
+    project_id: String,
+    app_state: State<'_, AppState>,
+) -> Result<JobDto, AppError> {
+    app_state
+        .job_manager()
+        .submit(
+            RECONCILE_DOCUMENTS_JOB,
+            Some(project_id),
+            serde_json::Value::Null,
+        )
+        .await
+}
+
+/// Tauri command to list the tracked documents of a project
+#[tauri::command]
+pub async fn list_documents(
+    project_id: String,
+    include_missing: Option<bool>,
+    app_state: State<'_, AppState>,
+) -> Result<Vec<DocumentDto>, AppError> {
+    app_state
+        .document_service()
+        .list_documents(&project_id, include_missing.unwrap_or(false))
+        .await
+}
+
+/// Tauri command to get a document by its identifier
+#[tauri::command]
+pub async fn get_document(
+    document_id: String,
+    app_state: State<'_, AppState>,
+) -> Result<DocumentDto, AppError> {
+    app_state
+        .document_service()
+        .get_document(&document_id)
+        .await
+}
+
+/// Tauri command to find the document for a file path
+///
+/// Returns None if the file has not been reconciled yet.
+#[tauri::command]
+pub async fn find_document_by_path(
+    project_id: String,
+    path: String,
+    app_state: State<'_, AppState>,
+) -> Result<Option<DocumentDto>, AppError> {
+    app_state
+        .document_service()
+        .find_document_by_path(&project_id, &path)
+        .await
+}
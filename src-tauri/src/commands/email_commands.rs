@@ -0,0 +1,75 @@
+use crate::application::dtos::{EmailMessageDto, EmailThreadDto, JobDto};
+use crate::application::services::PARSE_EMAILS_JOB;
+use crate::application::AppState;
+use crate::infrastructure::AppError;
+use tauri::State;
+
+/// Tauri command to parse one email document (.eml, .mbox or .msg)
+///
+/// Always re-reads the file, replacing its stored messages and attachment
+/// documents. Returns the messages of the file and of attached emails.
+#[tauri::command]
+pub async fn parse_email(
+    document_id: String,
+    app_state: State<'_, AppState>,
+) -> Result<Vec<EmailMessageDto>, AppError> {
+    app_state.email_service().parse_document(&document_id).await
+}
+
+/// Tauri command to parse all email documents of a project as a background
+/// job
+///
+/// Files already parsed from their current contents are skipped unless
+/// `force` is set. The finished job's result is a serialized parse batch.
+#[tauri::command]
+pub async fn start_email_parse(
+    project_id: String,
+    force: Option<bool>,
+    app_state: State<'_, AppState>,
+) -> Result<JobDto, AppError> {
+    app_state
+        .job_manager()
+        .submit(
+            PARSE_EMAILS_JOB,
+            Some(project_id),
+            serde_json::json!({ "force": force.unwrap_or(false) }),
+        )
+        .await
+}
+
+/// Tauri command to get the stored messages of an email document
+///
+/// Returns an empty list when the document has not been parsed yet.
+#[tauri::command]
+pub async fn get_email(
+    document_id: String,
+    app_state: State<'_, AppState>,
+) -> Result<Vec<EmailMessageDto>, AppError> {
+    app_state.email_service().get_email(&document_id).await
+}
+
+/// Tauri command to list the email conversations of a project, most
+/// recently active first
+#[tauri::command]
+pub async fn list_email_threads(
+    project_id: String,
+    app_state: State<'_, AppState>,
+) -> Result<Vec<EmailThreadDto>, AppError> {
+    app_state.email_service().list_threads(&project_id).await
+}
+
+/// Tauri command to get the conversation a message belongs to
+///
+/// `message_index` selects the message within a mailbox and defaults to
+/// the first.
+#[tauri::command]
+pub async fn get_email_thread(
+    document_id: String,
+    message_index: Option<u32>,
+    app_state: State<'_, AppState>,
+) -> Result<EmailThreadDto, AppError> {
+    app_state
+        .email_service()
+        .get_thread(&document_id, message_index.unwrap_or(0))
+        .await
+}
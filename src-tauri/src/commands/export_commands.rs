@@ -0,0 +1,44 @@
+use crate::application::dtos::ExportResultDto;
+use crate::application::AppState;
+use crate::domain::export::{ExportFormat, ExportOptions};
+use crate::infrastructure::AppError;
+use tauri::State;
+
+/// Tauri command to export a `.det` document into the project's reports folder
+///
+/// `format` is "markdown", "html", "docx" or "pdf". The file is named
+/// after `file_name`, or the document title, and replaces an earlier
+/// export of the same name.
+#[tauri::command]
+pub async fn export_document(
+    project_id: String,
+    det_path: String,
+    format: String,
+    options: Option<ExportOptions>,
+    file_name: Option<String>,
+    app_state: State<'_, AppState>,
+) -> Result<ExportResultDto, AppError> {
+    let format = ExportFormat::parse(&format)?;
+
+    app_state
+        .export_service()
+        .export_document(
+            &project_id,
+            &det_path,
+            format,
+            options.unwrap_or_default(),
+            file_name,
+        )
+        .await
+}
+
+/// Tauri command to list the formats documents can be exported to
+#[tauri::command]
+pub async fn get_export_formats(app_state: State<'_, AppState>) -> Result<Vec<String>, AppError> {
+    Ok(app_state
+        .export_service()
+        .formats()
+        .iter()
+        .map(|format| format.as_str().to_string())
+        .collect())
+}
@@ -0,0 +1,48 @@
+use crate::application::dtos::{ExtractionResultDto, JobDto};
+use crate::application::services::EXTRACT_DOCUMENTS_JOB;
+use crate::application::AppState;
+use crate::infrastructure::AppError;
+use tauri::State;
+
+/// Tauri command to extract one document into its `extracted.det`
+///
+/// Always re-extracts, replacing an earlier extraction of the document.
+#[tauri::command]
+pub async fn extract_document(
+    document_id: String,
+    app_state: State<'_, AppState>,
+) -> Result<ExtractionResultDto, AppError> {
+    app_state
+        .extraction_service()
+        .extract_document(&document_id)
+        .await
+}
+
+/// Tauri command to extract all documents of a project as a background job
+///
+/// Documents already extracted from their current contents are skipped
+/// unless `force` is set. The finished job's result is a serialized
+/// extraction batch.
+#[tauri::command]
+pub async fn start_project_extraction(
+    project_id: String,
+    force: Option<bool>,
+    app_state: State<'_, AppState>,
+) -> Result<JobDto, AppError> {
+    app_state
+        .job_manager()
+        .submit(
+            EXTRACT_DOCUMENTS_JOB,
+            Some(project_id),
+            serde_json::json!({ "force": force.unwrap_or(false) }),
+        )
+        .await
+}
+
+/// Tauri command to list the file extensions that can be extracted
+#[tauri::command]
+pub async fn get_supported_extraction_formats(
+    app_state: State<'_, AppState>,
+) -> Result<Vec<String>, AppError> {
+    Ok(app_state.extraction_service().supported_extensions())
+}
@@ -0,0 +1,67 @@
+use crate::application::dtos::{DuplicateReportDto, JobDto};
+use crate::application::services::hashing_service::parse_hash_algorithm;
+use crate::application::services::FIND_DUPLICATES_JOB;
+use crate::application::AppState;
+use crate::infrastructure::AppError;
+use tauri::State;
+
+/// Tauri command to find identical files in a project's source folder
+///
+/// `algorithm` is "blake3" (default) or "sha256". Digests are cached, so
+/// repeated scans only hash files that changed. For large corpora prefer
+/// `start_duplicate_scan`, which reports progress and can be cancelled.
+#[tauri::command]
+pub async fn find_duplicates(
+    project_id: String,
+    algorithm: Option<String>,
+    app_state: State<'_, AppState>,
+) -> Result<DuplicateReportDto, AppError> {
+    let algorithm = parse_hash_algorithm(algorithm.as_deref())?;
+
+    app_state
+        .hashing_service()
+        .find_duplicates(&project_id, algorithm, None)
+        .await
+}
+
+/// Tauri command to run a duplicate scan as a background job
+///
+/// The finished job's result is a serialized duplicate report.
+#[tauri::command]
+pub async fn start_duplicate_scan(
+    project_id: String,
+    algorithm: Option<String>,
+    app_state: State<'_, AppState>,
+) -> Result<JobDto, AppError> {
+    let algorithm = parse_hash_algorithm(algorithm.as_deref())?;
+
+    app_state
+        .job_manager()
+        .submit(
+            FIND_DUPLICATES_JOB,
+            Some(project_id),
+            serde_json::json!({ "algorithm": algorithm.as_str() }),
+        )
+        .await
+}
+
+/// Tauri command to get the content hash of a file in a project
+///
+/// `path` is relative to the project's source folder. Returns the hash as
+/// `<algorithm>:<digest>`.
+#[tauri::command]
+pub async fn get_file_hash(
+    project_id: String,
+    path: String,
+    algorithm: Option<String>,
+    app_state: State<'_, AppState>,
+) -> Result<String, AppError> {
+    let algorithm = parse_hash_algorithm(algorithm.as_deref())?;
+
+    let hash = app_state
+        .hashing_service()
+        .hash_project_file(&project_id, &path, algorithm)
+        .await?;
+
+    Ok(hash.to_string())
+}
@@ -0,0 +1,49 @@
+use crate::application::dtos::JobDto;
+use crate::application::jobs::{JobFilter, JobStatus};
+use crate::application::AppState;
+use crate::infrastructure::AppError;
+use tauri::State;
+
+/// Tauri command to list background jobs
+///
+/// Returns jobs newest first, optionally filtered by status
+/// ("queued", "running", "succeeded", "failed", "cancelled") and project.
+#[tauri::command]
+pub async fn list_jobs(
+    status: Option<String>,
+    project_id: Option<String>,
+    limit: Option<usize>,
+    app_state: State<'_, AppState>,
+) -> Result<Vec<JobDto>, AppError> {
+    let status = status
+        .map(|s| JobStatus::from_str(&s))
+        .transpose()
+        .map_err(|e| AppError::validation_error("Invalid job status", Some(e)))?;
+
+    let filter = JobFilter {
+        status,
+        project_id,
+        limit,
+    };
+
+    app_state.job_manager().list_jobs(&filter).await
+}
+
+/// Tauri command to get the current state of a background job
+#[tauri::command]
+pub async fn get_job(job_id: String, app_state: State<'_, AppState>) -> Result<JobDto, AppError> {
+    app_state.job_manager().get_job(&job_id).await
+}
+
+/// Tauri command to cancel a queued or running background job
+///
+/// The job stops at its next cancellation point; a "job-updated" event
+/// with status "cancelled" is emitted once it has stopped.
+#[tauri::command]
+pub async fn cancel_job(
+    job_id: String,
+    app_state: State<'_, AppState>,
+) -> Result<JobDto, AppError> {
+    tracing::info!("Cancel requested for job {}", job_id);
+    app_state.job_manager().cancel_job(&job_id).await
+}
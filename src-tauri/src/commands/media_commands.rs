@@ -0,0 +1,76 @@
+use crate::application::dtos::{JobDto, MediaMetadataDto};
+use crate::application::services::PROBE_MEDIA_JOB;
+use crate::application::AppState;
+use crate::domain::media::MediaFilter;
+use crate::infrastructure::AppError;
+use tauri::State;
+
+/// Tauri command to read the audio or video metadata of one document
+///
+/// Always re-reads the file, replacing earlier metadata of the document.
+#[tauri::command]
+pub async fn probe_media(
+    document_id: String,
+    app_state: State<'_, AppState>,
+) -> Result<MediaMetadataDto, AppError> {
+    app_state.media_service().probe_document(&document_id).await
+}
+
+/// Tauri command to read the metadata of all media documents of a project
+/// as a background job
+///
+/// Files already probed from their current contents are skipped unless
+/// `force` is set. The finished job's result is a serialized probe batch.
+#[tauri::command]
+pub async fn start_media_probe(
+    project_id: String,
+    force: Option<bool>,
+    app_state: State<'_, AppState>,
+) -> Result<JobDto, AppError> {
+    app_state
+        .job_manager()
+        .submit(
+            PROBE_MEDIA_JOB,
+            Some(project_id),
+            serde_json::json!({ "force": force.unwrap_or(false) }),
+        )
+        .await
+}
+
+/// Tauri command to get the stored metadata of a media document
+///
+/// Returns `None` when the document has not been probed yet.
+#[tauri::command]
+pub async fn get_media_metadata(
+    document_id: String,
+    app_state: State<'_, AppState>,
+) -> Result<Option<MediaMetadataDto>, AppError> {
+    app_state
+        .media_service()
+        .get_media_metadata(&document_id)
+        .await
+}
+
+/// Tauri command to find probed media documents of a project, longest first
+///
+/// For example `{ kind: "audio", minDurationMs: 600000 }` finds all
+/// recordings longer than 10 minutes.
+#[tauri::command]
+pub async fn search_media(
+    project_id: String,
+    filter: Option<MediaFilter>,
+    app_state: State<'_, AppState>,
+) -> Result<Vec<MediaMetadataDto>, AppError> {
+    app_state
+        .media_service()
+        .search_media(&project_id, &filter.unwrap_or_default())
+        .await
+}
+
+/// Tauri command to list the file extensions whose metadata can be read
+#[tauri::command]
+pub async fn get_supported_media_formats(
+    app_state: State<'_, AppState>,
+) -> Result<Vec<String>, AppError> {
+    Ok(app_state.media_service().supported_extensions())
+}
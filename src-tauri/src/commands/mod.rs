@@ -1,13 +1,53 @@
+pub mod annotation_commands;
+pub mod anonymization_commands;
+pub mod archive_commands;
+pub mod category_commands;
+pub mod citation_commands;
+pub mod coding_commands;
+pub mod cost_table_commands;
 pub mod create_project;
 pub mod delete_project;
+pub mod derivation_commands;
+pub mod document_commands;
+pub mod email_commands;
+pub mod export_commands;
+pub mod extraction_commands;
 pub mod file_system_commands;
+pub mod hashing_commands;
+pub mod job_commands;
 pub mod list_projects;
+pub mod media_commands;
 pub mod open_project;
+pub mod preview_commands;
+pub mod report_commands;
+pub mod review_commands;
+pub mod search_commands;
+pub mod snapshot_commands;
 pub mod workspace_commands;
 
+pub use annotation_commands::*;
+pub use anonymization_commands::*;
+pub use archive_commands::*;
+pub use category_commands::*;
+pub use citation_commands::*;
+pub use coding_commands::*;
+pub use cost_table_commands::*;
 pub use create_project::*;
 pub use delete_project::*;
+pub use derivation_commands::*;
+pub use document_commands::*;
+pub use email_commands::*;
+pub use export_commands::*;
+pub use extraction_commands::*;
 pub use file_system_commands::*;
+pub use hashing_commands::*;
+pub use job_commands::*;
 pub use list_projects::*;
+pub use media_commands::*;
 pub use open_project::*;
+pub use preview_commands::*;
+pub use report_commands::*;
+pub use review_commands::*;
+pub use search_commands::*;
+pub use snapshot_commands::*;
 pub use workspace_commands::*;
@@ -0,0 +1,22 @@
+use crate::application::dtos::PreviewDto;
+use crate::application::AppState;
+use crate::infrastructure::AppError;
+use tauri::State;
+
+/// Tauri command to get the preview of a file
+///
+/// Returns at once: a preview that is not cached yet comes back as
+/// `pending` while it is generated in the background. With `include_bytes`
+/// a ready image is also returned inline, for when the cache folder cannot
+/// be read by the webview.
+#[tauri::command]
+pub async fn get_preview(
+    path: String,
+    include_bytes: Option<bool>,
+    app_state: State<'_, AppState>,
+) -> Result<PreviewDto, AppError> {
+    app_state
+        .preview_service()
+        .get_preview(&path, include_bytes.unwrap_or(false))
+        .await
+}
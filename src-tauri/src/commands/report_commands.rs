@@ -0,0 +1,88 @@
+use crate::application::dtos::{ReportContentDto, ReportDto, UpdateReportRequest};
+use crate::application::AppState;
+use crate::domain::det::PmNode;
+use crate::domain::report::ReportCategory;
+use crate::infrastructure::AppError;
+use tauri::State;
+
+/// Tauri command to create a draft report in the project's reports folder
+///
+/// `category` is "findings", "analysis_summary", "deliverable" or
+/// "client_report". The report is empty unless `content` is given.
+#[tauri::command]
+pub async fn create_report(
+    project_id: String,
+    title: String,
+    category: String,
+    content: Option<PmNode>,
+    app_state: State<'_, AppState>,
+) -> Result<ReportDto, AppError> {
+    let category = ReportCategory::parse(&category)?;
+
+    app_state
+        .report_service()
+        .create_report(&project_id, title, category, content)
+        .await
+}
+
+/// Tauri command to get a report with its content
+#[tauri::command]
+pub async fn get_report(
+    report_id: String,
+    app_state: State<'_, AppState>,
+) -> Result<ReportContentDto, AppError> {
+    app_state.report_service().get_report(&report_id).await
+}
+
+/// Tauri command to list a project's reports, optionally of one category
+#[tauri::command]
+pub async fn list_reports(
+    project_id: String,
+    category: Option<String>,
+    app_state: State<'_, AppState>,
+) -> Result<Vec<ReportDto>, AppError> {
+    let category = category.as_deref().map(ReportCategory::parse).transpose()?;
+
+    app_state
+        .report_service()
+        .list_reports(&project_id, category)
+        .await
+}
+
+/// Tauri command to update a report's title, category, status, content or links
+///
+/// A final report must be set back to draft before its title or content
+/// can change.
+#[tauri::command]
+pub async fn update_report(
+    report_id: String,
+    request: UpdateReportRequest,
+    app_state: State<'_, AppState>,
+) -> Result<ReportDto, AppError> {
+    app_state
+        .report_service()
+        .update_report(&report_id, request)
+        .await
+}
+
+/// Tauri command to delete a report, keeping its file if `keep_file` is set
+#[tauri::command]
+pub async fn delete_report(
+    report_id: String,
+    keep_file: Option<bool>,
+    app_state: State<'_, AppState>,
+) -> Result<(), AppError> {
+    app_state
+        .report_service()
+        .delete_report(&report_id, keep_file.unwrap_or(false))
+        .await
+}
+
+/// Tauri command to list the report categories
+#[tauri::command]
+pub async fn get_report_categories() -> Result<Vec<String>, AppError> {
+    Ok(ReportCategory::ALL
+        .iter()
+        .map(|category| category.as_str().to_string())
+        .collect())
+}
@@ -0,0 +1,124 @@
+use crate::application::dtos::{ReviewBatchDto, ReviewerDto, ReviewerStatsDto};
+use crate::application::AppState;
+use crate::domain::review::BatchSource;
+use crate::infrastructure::AppError;
+use tauri::State;
+
+/// Tauri command to add a reviewer profile
+#[tauri::command]
+pub async fn create_reviewer(
+    name: String,
+    app_state: State<'_, AppState>,
+) -> Result<ReviewerDto, AppError> {
+    app_state.review_service().create_reviewer(name).await
+}
+
+/// Tauri command to list the reviewer profiles on this machine
+#[tauri::command]
+pub async fn list_reviewers(app_state: State<'_, AppState>) -> Result<Vec<ReviewerDto>, AppError> {
+    app_state.review_service().list_reviewers().await
+}
+
+/// Tauri command to switch the active reviewer, or clear it when no id is
+/// given
+#[tauri::command]
+pub async fn set_active_reviewer(
+    reviewer_id: Option<String>,
+    app_state: State<'_, AppState>,
+) -> Result<Option<ReviewerDto>, AppError> {
+    app_state
+        .review_service()
+        .set_active_reviewer(reviewer_id)
+        .await
+}
+
+/// Tauri command to get the active reviewer
+#[tauri::command]
+pub async fn get_active_reviewer(
+    app_state: State<'_, AppState>,
+) -> Result<Option<ReviewerDto>, AppError> {
+    app_state.review_service().get_active_reviewer().await
+}
+
+/// Tauri command to create review batches from a folder, search or
+/// category, split evenly between the given reviewers
+///
+/// The source is `{ "type": "folder", "path" }` with a path relative to
+/// the project's source folder, `{ "type": "search", "query" }` or
+/// `{ "type": "category", "categoryId", "includeDescendants" }`.
+#[tauri::command]
+pub async fn create_review_batches(
+    project_id: String,
+    name: String,
+    source: BatchSource,
+    reviewer_ids: Vec<String>,
+    app_state: State<'_, AppState>,
+) -> Result<Vec<ReviewBatchDto>, AppError> {
+    app_state
+        .review_service()
+        .create_review_batches(&project_id, name, source, reviewer_ids)
+        .await
+}
+
+/// Tauri command to assign a review batch to a reviewer, or unassign it
+/// when no id is given
+#[tauri::command]
+pub async fn assign_review_batch(
+    batch_id: String,
+    reviewer_id: Option<String>,
+    app_state: State<'_, AppState>,
+) -> Result<ReviewBatchDto, AppError> {
+    app_state
+        .review_service()
+        .assign_review_batch(&batch_id, reviewer_id)
+        .await
+}
+
+/// Tauri command to list the review batches of a project
+#[tauri::command]
+pub async fn list_review_batches(
+    project_id: String,
+    app_state: State<'_, AppState>,
+) -> Result<Vec<ReviewBatchDto>, AppError> {
+    app_state
+        .review_service()
+        .list_review_batches(&project_id)
+        .await
+}
+
+/// Tauri command to even out the uncoded documents of a project's open
+/// review batches
+#[tauri::command]
+pub async fn rebalance_review_batches(
+    project_id: String,
+    app_state: State<'_, AppState>,
+) -> Result<Vec<ReviewBatchDto>, AppError> {
+    app_state
+        .review_service()
+        .rebalance_review_batches(&project_id)
+        .await
+}
+
+/// Tauri command to close a review batch
+#[tauri::command]
+pub async fn close_review_batch(
+    batch_id: String,
+    app_state: State<'_, AppState>,
+) -> Result<ReviewBatchDto, AppError> {
+    app_state
+        .review_service()
+        .close_review_batch(&batch_id)
+        .await
+}
+
+/// Tauri command to get the throughput of each reviewer in a project
+#[tauri::command]
+pub async fn get_reviewer_stats(
+    project_id: String,
+    app_state: State<'_, AppState>,
+) -> Result<Vec<ReviewerStatsDto>, AppError> {
+    app_state
+        .review_service()
+        .get_reviewer_stats(&project_id)
+        .await
+}
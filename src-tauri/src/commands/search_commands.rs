@@ -0,0 +1,115 @@
+use crate::application::dtos::{GlobalSearchResultsDto, SavedSearchDto, SearchResultsDto};
+use crate::application::AppState;
+use crate::infrastructure::AppError;
+use tauri::State;
+
+/// Tauri command to search originals, derivatives, reports and metadata of
+/// a project at once
+///
+/// The query combines terms, quoted phrases and filters, e.g.
+/// `"board minutes" budget type:report ext:pdf tag:finance
+/// modified:>2024-01-01 derivative:summary`. At most `limit` hits are
+/// returned (50 by default), best first.
+#[tauri::command]
+pub async fn search_project(
+    project_id: String,
+    query: String,
+    limit: Option<usize>,
+    app_state: State<'_, AppState>,
+) -> Result<SearchResultsDto, AppError> {
+    app_state
+        .search_service()
+        .search(&project_id, &query, limit)
+        .await
+}
+
+/// Tauri command to search all accessible projects at once
+///
+/// Uses the same query language as `search_project`. Hits from all
+/// projects are ranked together; one page of `limit` hits (50 by default)
+/// starting at `offset` is returned, grouped by project. Projects whose
+/// source folder is not accessible are skipped and listed.
+#[tauri::command]
+pub async fn search_all_projects(
+    query: String,
+    offset: Option<usize>,
+    limit: Option<usize>,
+    app_state: State<'_, AppState>,
+) -> Result<GlobalSearchResultsDto, AppError> {
+    app_state
+        .search_service()
+        .search_all_projects(&query, offset.unwrap_or(0), limit)
+        .await
+}
+
+/// Tauri command to save a query under a name
+///
+/// The saved search appears as a virtual folder in the workspace root.
+/// With `track_new`, results found after it was last opened are counted.
+#[tauri::command]
+pub async fn create_saved_search(
+    project_id: String,
+    name: String,
+    query: String,
+    track_new: Option<bool>,
+    app_state: State<'_, AppState>,
+) -> Result<SavedSearchDto, AppError> {
+    app_state
+        .saved_search_service()
+        .create_saved_search(&project_id, name, query, track_new.unwrap_or(false))
+        .await
+}
+
+/// Tauri command to rename a saved search, change its query or turn
+/// counting of new results on or off
+#[tauri::command]
+pub async fn update_saved_search(
+    saved_search_id: String,
+    name: Option<String>,
+    query: Option<String>,
+    track_new: Option<bool>,
+    app_state: State<'_, AppState>,
+) -> Result<SavedSearchDto, AppError> {
+    app_state
+        .saved_search_service()
+        .update_saved_search(&saved_search_id, name, query, track_new)
+        .await
+}
+
+/// Tauri command to delete a saved search
+#[tauri::command]
+pub async fn delete_saved_search(
+    saved_search_id: String,
+    app_state: State<'_, AppState>,
+) -> Result<(), AppError> {
+    app_state
+        .saved_search_service()
+        .delete_saved_search(&saved_search_id)
+        .await
+}
+
+/// Tauri command to list the saved searches of a project with their counts
+/// of new results
+#[tauri::command]
+pub async fn list_saved_searches(
+    project_id: String,
+    app_state: State<'_, AppState>,
+) -> Result<Vec<SavedSearchDto>, AppError> {
+    app_state
+        .saved_search_service()
+        .list_saved_searches(&project_id)
+        .await
+}
+
+/// Tauri command to run a saved search, marking its results as seen
+#[tauri::command]
+pub async fn run_saved_search(
+    saved_search_id: String,
+    limit: Option<usize>,
+    app_state: State<'_, AppState>,
+) -> Result<SearchResultsDto, AppError> {
+    app_state
+        .saved_search_service()
+        .run_saved_search(&saved_search_id, limit)
+        .await
+}
@@ -0,0 +1,123 @@
+use crate::application::dtos::{JobDto, SnapshotDiffDto, SnapshotSummaryDto};
+use crate::application::services::hashing_service::parse_hash_algorithm;
+use crate::application::services::{SnapshotService, CREATE_SNAPSHOT_JOB};
+use crate::application::AppState;
+use crate::infrastructure::AppError;
+use tauri::State;
+
+/// Tauri command to take a manifest snapshot of a project's source folder
+///
+/// Records path, size, modification time and content hash of every file.
+/// `algorithm` is "blake3" (default) or "sha256".
+#[tauri::command]
+pub async fn create_snapshot(
+    project_id: String,
+    label: Option<String>,
+    algorithm: Option<String>,
+    app_state: State<'_, AppState>,
+) -> Result<SnapshotSummaryDto, AppError> {
+    let algorithm = parse_hash_algorithm(algorithm.as_deref())?;
+
+    app_state
+        .snapshot_service()
+        .create_snapshot(&project_id, label, algorithm, None)
+        .await
+}
+
+/// Tauri command to take a manifest snapshot as a background job
+///
+/// The finished job's result is a serialized snapshot summary.
+#[tauri::command]
+pub async fn start_snapshot_job(
+    project_id: String,
+    label: Option<String>,
+    algorithm: Option<String>,
+    app_state: State<'_, AppState>,
+) -> Result<JobDto, AppError> {
+    let algorithm = parse_hash_algorithm(algorithm.as_deref())?;
+
+    app_state
+        .job_manager()
+        .submit(
+            CREATE_SNAPSHOT_JOB,
+            Some(project_id),
+            serde_json::json!({ "label": label, "algorithm": algorithm.as_str() }),
+        )
+        .await
+}
+
+/// Tauri command to list the snapshots of a project, newest first
+#[tauri::command]
+pub async fn list_snapshots(
+    project_id: String,
+    app_state: State<'_, AppState>,
+) -> Result<Vec<SnapshotSummaryDto>, AppError> {
+    app_state
+        .snapshot_service()
+        .list_snapshots(&project_id)
+        .await
+}
+
+/// Tauri command to delete a snapshot
+#[tauri::command]
+pub async fn delete_snapshot(
+    snapshot_id: String,
+    app_state: State<'_, AppState>,
+) -> Result<(), AppError> {
+    app_state
+        .snapshot_service()
+        .delete_snapshot(&snapshot_id)
+        .await
+}
+
+/// Tauri command to compare two snapshots
+///
+/// Without `target_snapshot_id` the base snapshot is compared with the
+/// source folder as it is now.
+#[tauri::command]
+pub async fn diff_snapshots(
+    project_id: String,
+    base_snapshot_id: String,
+    target_snapshot_id: Option<String>,
+    app_state: State<'_, AppState>,
+) -> Result<SnapshotDiffDto, AppError> {
+    app_state
+        .snapshot_service()
+        .diff_snapshots(
+            &project_id,
+            &base_snapshot_id,
+            target_snapshot_id.as_deref(),
+        )
+        .await
+}
+
+/// Tauri command to export a snapshot diff as CSV or JSON
+///
+/// Writes the export to `destination_path` and returns that path.
+#[tauri::command]
+pub async fn export_snapshot_diff(
+    project_id: String,
+    base_snapshot_id: String,
+    target_snapshot_id: Option<String>,
+    format: String,
+    destination_path: String,
+    app_state: State<'_, AppState>,
+) -> Result<String, AppError> {
+    let diff = app_state
+        .snapshot_service()
+        .diff_snapshots(
+            &project_id,
+            &base_snapshot_id,
+            target_snapshot_id.as_deref(),
+        )
+        .await?;
+    let contents = SnapshotService::render_diff(&diff, &format)?;
+
+    tokio::fs::write(&destination_path, contents)
+        .await
+        .map_err(|e| {
+            AppError::filesystem_error(format!("Cannot write {}: {}", destination_path, e))
+        })?;
+
+    Ok(destination_path)
+}
@@ -1,8 +1,9 @@
 use crate::application::{
     // workspace_service::{WorkspaceService as LegacyWorkspaceService, WorkspaceServiceError},
-    dtos::{DirectoryListingDto, WorkspaceDto},
+    dtos::{DirectoryListingDto, ProjectFileSummaryDto, WorkspaceDto},
     AppState,
 };
+use crate::domain::workspace::value_objects::FileCategoryConfig;
 use crate::infrastructure::AppError;
 use tauri::State;
 
@@ -199,3 +200,50 @@ pub async fn navigate_to_parent(
         .await
         .map_err(AppError::from)
 }
+
+// ============================================================================
+// File Classification Commands
+// ============================================================================
+
+/// Tauri command to get file type counts for a project
+///
+/// Returns the number of documents, audio, video, image and other files in
+/// the project's source folder. Counts are cached and recomputed only when
+/// the folder contents or the project's classification settings change.
+#[tauri::command]
+pub async fn get_project_file_summary(
+    project_id: String,
+    app_state: State<'_, AppState>,
+) -> Result<ProjectFileSummaryDto, AppError> {
+    app_state
+        .file_summary_service()
+        .get_project_file_summary(&project_id)
+        .await
+}
+
+/// Tauri command to get the file classification settings for a project
+#[tauri::command]
+pub async fn get_file_category_config(
+    project_id: String,
+    app_state: State<'_, AppState>,
+) -> Result<FileCategoryConfig, AppError> {
+    app_state
+        .file_summary_service()
+        .get_category_config(&project_id)
+        .await
+}
+
+/// Tauri command to update the file classification settings for a project
+///
+/// Saving new settings invalidates the project's cached file summary.
+#[tauri::command]
+pub async fn update_file_category_config(
+    project_id: String,
+    config: FileCategoryConfig,
+    app_state: State<'_, AppState>,
+) -> Result<FileCategoryConfig, AppError> {
+    app_state
+        .file_summary_service()
+        .update_category_config(&project_id, config)
+        .await
+}
@@ -0,0 +1,308 @@
+use chrono::{DateTime, Utc};
+
+use crate::domain::annotation::errors::AnnotationError;
+use crate::domain::annotation::value_objects::{AnchorStatus, AnnotationAnchor, AnnotationId};
+use crate::domain::category::CategoryColor;
+use crate::domain::det::PmNode;
+use crate::domain::document::DocumentId;
+use crate::domain::project::ProjectId;
+
+/// Longest author name accepted
+const MAX_AUTHOR_LENGTH: usize = 100;
+
+/// Longest comment accepted, in characters
+const MAX_BODY_LENGTH: usize = 10_000;
+
+/// Annotation aggregate root: a highlight on a passage of a document, with
+/// an optional comment
+///
+/// Business Rules:
+/// - An annotation belongs to one document, identified by its stable id
+/// - The highlighted text is kept as a quote, so the anchor can follow the
+///   text when the extraction it points into changes
+/// - Every annotation has an author; the comment may be empty
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Annotation {
+    id: AnnotationId,
+    project_id: ProjectId,
+    document_id: DocumentId,
+    anchor: AnnotationAnchor,
+    quote: String,
+    color: Option<CategoryColor>,
+    body: String,
+    author: String,
+    status: AnchorStatus,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+}
+
+impl Annotation {
+    /// Create an attached annotation on `quote`
+    pub fn new(
+        project_id: ProjectId,
+        document_id: DocumentId,
+        anchor: AnnotationAnchor,
+        quote: String,
+        author: String,
+    ) -> Result<Self, AnnotationError> {
+        anchor.validate()?;
+        let now = Utc::now();
+        Ok(Annotation {
+            id: AnnotationId::new(),
+            project_id,
+            document_id,
+            anchor,
+            quote,
+            color: None,
+            body: String::new(),
+            author: validate_author(author)?,
+            status: AnchorStatus::Attached,
+            created_at: now,
+            updated_at: now,
+        })
+    }
+
+    /// Create an Annotation from existing data (for repository reconstruction)
+    #[allow(clippy::too_many_arguments)]
+    pub fn from_data(
+        id: AnnotationId,
+        project_id: ProjectId,
+        document_id: DocumentId,
+        anchor: AnnotationAnchor,
+        quote: String,
+        color: Option<CategoryColor>,
+        body: String,
+        author: String,
+        status: AnchorStatus,
+        created_at: DateTime<Utc>,
+        updated_at: DateTime<Utc>,
+    ) -> Self {
+        Annotation {
+            id,
+            project_id,
+            document_id,
+            anchor,
+            quote,
+            color,
+            body,
+            author,
+            status,
+            created_at,
+            updated_at,
+        }
+    }
+
+    pub fn id(&self) -> &AnnotationId {
+        &self.id
+    }
+
+    pub fn project_id(&self) -> &ProjectId {
+        &self.project_id
+    }
+
+    pub fn document_id(&self) -> &DocumentId {
+        &self.document_id
+    }
+
+    pub fn anchor(&self) -> &AnnotationAnchor {
+        &self.anchor
+    }
+
+    /// The highlighted text
+    pub fn quote(&self) -> &str {
+        &self.quote
+    }
+
+    pub fn color(&self) -> Option<&CategoryColor> {
+        self.color.as_ref()
+    }
+
+    /// The comment; empty for a plain highlight
+    pub fn body(&self) -> &str {
+        &self.body
+    }
+
+    pub fn author(&self) -> &str {
+        &self.author
+    }
+
+    pub fn status(&self) -> AnchorStatus {
+        self.status
+    }
+
+    pub fn created_at(&self) -> DateTime<Utc> {
+        self.created_at
+    }
+
+    pub fn updated_at(&self) -> DateTime<Utc> {
+        self.updated_at
+    }
+
+    pub fn set_body(&mut self, body: String) -> Result<(), AnnotationError> {
+        if body.chars().count() > MAX_BODY_LENGTH {
+            return Err(AnnotationError::InvalidBody(format!(
+                "the comment cannot be longer than {} characters",
+                MAX_BODY_LENGTH
+            )));
+        }
+        self.body = body;
+        self.touch();
+        Ok(())
+    }
+
+    pub fn set_color(&mut self, color: Option<CategoryColor>) {
+        self.color = color;
+        self.touch();
+    }
+
+    /// Follow the quoted text into a changed extraction
+    ///
+    /// Only ProseMirror range anchors are affected. If the quote is no
+    /// longer at the anchor, the occurrence nearest to it is used; if there
+    /// is none, the annotation is orphaned and keeps its old anchor. Returns
+    /// what happened in this pass.
+    pub fn reattach(&mut self, content: &PmNode) -> AnchorStatus {
+        let AnnotationAnchor::Range { from, to } = self.anchor else {
+            return AnchorStatus::Attached;
+        };
+
+        if content.text_between(from, to).as_deref() == Some(self.quote.as_str()) {
+            if self.status == AnchorStatus::Orphaned {
+                self.status = AnchorStatus::Attached;
+                self.touch();
+            }
+            return AnchorStatus::Attached;
+        }
+
+        let nearest = content
+            .find_text(&self.quote)
+            .into_iter()
+            .min_by_key(|(start, _)| start.abs_diff(from));
+        match nearest {
+            Some((from, to)) => {
+                self.anchor = AnnotationAnchor::Range { from, to };
+                self.status = AnchorStatus::Reattached;
+            }
+            None => self.status = AnchorStatus::Orphaned,
+        }
+        self.touch();
+        self.status
+    }
+
+    fn touch(&mut self) {
+        self.updated_at = Utc::now();
+    }
+}
+
+fn validate_author(author: String) -> Result<String, AnnotationError> {
+    let author = author.trim().to_string();
+    if author.is_empty() {
+        return Err(AnnotationError::InvalidAuthor(
+            "the author cannot be empty".to_string(),
+        ));
+    }
+    if author.chars().count() > MAX_AUTHOR_LENGTH {
+        return Err(AnnotationError::InvalidAuthor(format!(
+            "the author cannot be longer than {} characters",
+            MAX_AUTHOR_LENGTH
+        )));
+    }
+    Ok(author)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn annotation(anchor: AnnotationAnchor, quote: &str) -> Annotation {
+        Annotation::new(
+            ProjectId::new(),
+            DocumentId::new(),
+            anchor,
+            quote.to_string(),
+            "Reviewer".to_string(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_reattach_follows_moved_text() {
+        let original = PmNode::doc(vec![PmNode::paragraph(PmNode::text_nodes(
+            "The fee is due in May.",
+            Vec::new(),
+        ))]);
+        // "fee" starts at position 1 + 4
+        let mut highlight = annotation(AnnotationAnchor::Range { from: 5, to: 8 }, "fee");
+        assert_eq!(original.text_between(5, 8).as_deref(), Some("fee"));
+        assert_eq!(highlight.reattach(&original), AnchorStatus::Attached);
+
+        let corrected = PmNode::doc(vec![
+            PmNode::heading(1, PmNode::text_nodes("Terms", Vec::new())),
+            PmNode::paragraph(PmNode::text_nodes("The fee is due in June.", Vec::new())),
+        ]);
+        assert_eq!(highlight.reattach(&corrected), AnchorStatus::Reattached);
+        let AnnotationAnchor::Range { from, to } = *highlight.anchor() else {
+            panic!("anchor type changed");
+        };
+        assert_eq!(corrected.text_between(from, to).as_deref(), Some("fee"));
+
+        let rewritten = PmNode::doc(vec![PmNode::paragraph(PmNode::text_nodes(
+            "Payment terms changed.",
+            Vec::new(),
+        ))]);
+        assert_eq!(highlight.reattach(&rewritten), AnchorStatus::Orphaned);
+        assert_eq!(highlight.anchor(), &AnnotationAnchor::Range { from, to });
+
+        // Page anchors point into the original and are left alone
+        let mut page = annotation(
+            AnnotationAnchor::Page {
+                page: 2,
+                start: 10,
+                end: 20,
+            },
+            "",
+        );
+        assert_eq!(page.reattach(&rewritten), AnchorStatus::Attached);
+        assert_eq!(page.status(), AnchorStatus::Attached);
+    }
+
+    #[test]
+    fn test_validation() {
+        let create = |anchor: AnnotationAnchor, author: &str| {
+            Annotation::new(
+                ProjectId::new(),
+                DocumentId::new(),
+                anchor,
+                String::new(),
+                author.to_string(),
+            )
+        };
+        let range = AnnotationAnchor::Range { from: 1, to: 4 };
+
+        assert!(matches!(
+            create(AnnotationAnchor::Range { from: 4, to: 4 }, "A"),
+            Err(AnnotationError::InvalidAnchor(_))
+        ));
+        assert!(matches!(
+            create(
+                AnnotationAnchor::Page {
+                    page: 0,
+                    start: 0,
+                    end: 1
+                },
+                "A"
+            ),
+            Err(AnnotationError::InvalidAnchor(_))
+        ));
+        assert!(matches!(
+            create(range.clone(), "  "),
+            Err(AnnotationError::InvalidAuthor(_))
+        ));
+
+        let mut annotation = create(range, " Ana ").unwrap();
+        assert_eq!(annotation.author(), "Ana");
+        assert!(annotation
+            .set_body("x".repeat(MAX_BODY_LENGTH + 1))
+            .is_err());
+    }
+}
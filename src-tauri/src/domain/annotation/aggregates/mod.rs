@@ -0,0 +1,3 @@
+pub mod annotation;
+
+pub use annotation::Annotation;
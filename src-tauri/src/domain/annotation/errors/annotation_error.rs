@@ -0,0 +1,17 @@
+use thiserror::Error;
+
+/// Errors raised by document annotations
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum AnnotationError {
+    #[error("Invalid annotation ID: {0}")]
+    InvalidId(String),
+
+    #[error("Invalid anchor: {0}")]
+    InvalidAnchor(String),
+
+    #[error("Invalid author: {0}")]
+    InvalidAuthor(String),
+
+    #[error("Invalid comment: {0}")]
+    InvalidBody(String),
+}
@@ -0,0 +1,3 @@
+pub mod annotation_error;
+
+pub use annotation_error::AnnotationError;
@@ -0,0 +1,10 @@
+pub mod aggregates;
+pub mod errors;
+pub mod repositories;
+pub mod value_objects;
+
+// Re-export commonly used types
+pub use aggregates::Annotation;
+pub use errors::AnnotationError;
+pub use repositories::AnnotationRepository;
+pub use value_objects::{AnchorStatus, AnnotationAnchor, AnnotationId};
@@ -0,0 +1,30 @@
+use async_trait::async_trait;
+
+use crate::domain::annotation::aggregates::Annotation;
+use crate::domain::annotation::value_objects::AnnotationId;
+use crate::domain::document::DocumentId;
+use crate::domain::project::ProjectId;
+use crate::domain::workspace::repositories::RepositoryError;
+
+/// Repository trait for Annotation persistence
+#[async_trait]
+pub trait AnnotationRepository: Send + Sync {
+    /// Insert or update an annotation
+    async fn save(&self, annotation: &Annotation) -> Result<(), RepositoryError>;
+
+    async fn find_by_id(&self, id: &AnnotationId) -> Result<Option<Annotation>, RepositoryError>;
+
+    /// The annotations of a document, oldest first
+    async fn list_by_document(
+        &self,
+        document_id: &DocumentId,
+    ) -> Result<Vec<Annotation>, RepositoryError>;
+
+    /// The annotations of all documents of a project, oldest first
+    async fn list_by_project(
+        &self,
+        project_id: &ProjectId,
+    ) -> Result<Vec<Annotation>, RepositoryError>;
+
+    async fn delete(&self, id: &AnnotationId) -> Result<(), RepositoryError>;
+}
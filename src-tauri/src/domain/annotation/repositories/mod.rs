@@ -0,0 +1,3 @@
+pub mod annotation_repository;
+
+pub use annotation_repository::AnnotationRepository;
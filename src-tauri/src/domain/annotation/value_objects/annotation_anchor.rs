@@ -0,0 +1,76 @@
+use serde::{Deserialize, Serialize};
+
+use crate::domain::annotation::errors::AnnotationError;
+
+/// Where an annotation is attached in a document
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum AnnotationAnchor {
+    /// Character offsets in the text of a page of the original (1-based
+    /// page number, end exclusive)
+    Page { page: u32, start: usize, end: usize },
+
+    /// ProseMirror positions in the document's `extracted.det` (end
+    /// exclusive)
+    Range { from: usize, to: usize },
+}
+
+impl AnnotationAnchor {
+    /// Check that the anchor covers at least one character
+    pub fn validate(&self) -> Result<(), AnnotationError> {
+        match *self {
+            AnnotationAnchor::Page { page: 0, .. } => Err(AnnotationError::InvalidAnchor(
+                "pages are numbered from 1".to_string(),
+            )),
+            AnnotationAnchor::Page { start, end, .. } if start >= end => Err(
+                AnnotationError::InvalidAnchor("the start must come before the end".to_string()),
+            ),
+            AnnotationAnchor::Range { from, to } if from >= to => Err(
+                AnnotationError::InvalidAnchor("the start must come before the end".to_string()),
+            ),
+            _ => Ok(()),
+        }
+    }
+
+    /// Short name of the anchor type: "page" or "range"
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            AnnotationAnchor::Page { .. } => "page",
+            AnnotationAnchor::Range { .. } => "range",
+        }
+    }
+}
+
+/// Whether an annotation still points at the text it was made on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AnchorStatus {
+    /// The anchor points at the quoted text
+    Attached,
+
+    /// The text moved when the extraction changed and the anchor followed it
+    Reattached,
+
+    /// The quoted text is no longer in the extraction; the old anchor is
+    /// kept
+    Orphaned,
+}
+
+impl AnchorStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AnchorStatus::Attached => "attached",
+            AnchorStatus::Reattached => "reattached",
+            AnchorStatus::Orphaned => "orphaned",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "attached" => Some(AnchorStatus::Attached),
+            "reattached" => Some(AnchorStatus::Reattached),
+            "orphaned" => Some(AnchorStatus::Orphaned),
+            _ => None,
+        }
+    }
+}
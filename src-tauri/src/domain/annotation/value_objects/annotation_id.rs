@@ -0,0 +1,44 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use uuid::Uuid;
+
+use crate::domain::annotation::errors::AnnotationError;
+
+/// AnnotationId value object identifying a highlight or comment
+///
+/// All annotation identifiers use the format: annotation_xxxxxxxx-xxxx-xxxx-xxxx-xxxxxxxxxxxx
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct AnnotationId(String);
+
+impl AnnotationId {
+    const PREFIX: &'static str = "annotation_";
+
+    /// Create a new AnnotationId with a generated UUID
+    pub fn new() -> Self {
+        AnnotationId(format!("{}{}", Self::PREFIX, Uuid::new_v4()))
+    }
+
+    /// Create an AnnotationId from an existing string, validating the prefix format
+    pub fn from_string(value: String) -> Result<Self, AnnotationError> {
+        match value.strip_prefix(Self::PREFIX) {
+            Some(uuid_part) if uuid_part.parse::<Uuid>().is_ok() => Ok(AnnotationId(value)),
+            _ => Err(AnnotationError::InvalidId(value)),
+        }
+    }
+
+    pub fn value(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Default for AnnotationId {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Display for AnnotationId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
@@ -0,0 +1,5 @@
+pub mod annotation_anchor;
+pub mod annotation_id;
+
+pub use annotation_anchor::{AnchorStatus, AnnotationAnchor};
+pub use annotation_id::AnnotationId;
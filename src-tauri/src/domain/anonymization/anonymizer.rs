@@ -0,0 +1,498 @@
+use regex::{Regex, RegexBuilder};
+use std::cmp::Reverse;
+use std::collections::HashSet;
+
+use super::errors::AnonymizationError;
+use super::value_objects::{
+    AnonymizationRule, BuiltInDetector, PseudonymMap, Replacement, RuleMatcher,
+};
+use crate::domain::det::PmNode;
+
+/// Characters of surrounding text kept on each side in review contexts
+const CONTEXT_CHARS: usize = 40;
+
+/// Upper bound on the compiled size of user-supplied patterns
+const PATTERN_SIZE_LIMIT: usize = 1 << 20;
+
+/// An entity found in text, by byte offsets
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EntityMatch {
+    pub start: usize,
+    pub end: usize,
+    pub label: String,
+}
+
+/// Result of anonymizing a document's content
+#[derive(Debug, Clone, PartialEq)]
+pub struct AnonymizedContent {
+    pub content: PmNode,
+    pub title: Option<String>,
+    /// Every replacement made, in reading order
+    pub replacements: Vec<Replacement>,
+}
+
+struct CompiledRule {
+    label: String,
+    regex: Regex,
+    detector: Option<BuiltInDetector>,
+}
+
+/// Rule-based replacement of entities with pseudonyms
+///
+/// Runs entirely locally. Text blocks are matched as a whole, so an entity
+/// split over differently formatted text still matches; its pseudonym takes
+/// the formatting of the entity's first character. Where rules overlap the
+/// earliest match wins, then the longest, then the rule listed first.
+pub struct Anonymizer {
+    rules: Vec<CompiledRule>,
+    excluded: HashSet<String>,
+}
+
+impl Anonymizer {
+    /// Compile rules, rejecting invalid patterns and empty dictionaries
+    pub fn new(rules: &[AnonymizationRule]) -> Result<Self, AnonymizationError> {
+        if rules.is_empty() {
+            return Err(AnonymizationError::NoRules);
+        }
+
+        let rules = rules
+            .iter()
+            .map(Self::compile)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Anonymizer {
+            rules,
+            excluded: HashSet::new(),
+        })
+    }
+
+    /// Leave these values untouched, e.g. false positives found in review
+    pub fn with_exclusions<I, S>(mut self, values: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        self.excluded.extend(
+            values
+                .into_iter()
+                .map(|v| PseudonymMap::normalize(v.as_ref())),
+        );
+        self
+    }
+
+    fn compile(rule: &AnonymizationRule) -> Result<CompiledRule, AnonymizationError> {
+        let invalid = |reason: String| AnonymizationError::invalid_rule(rule.label(), reason);
+
+        if rule.label().is_empty() {
+            return Err(invalid("the label is empty".to_string()));
+        }
+
+        let (pattern, case_insensitive, detector) = match rule.matcher() {
+            RuleMatcher::Regex {
+                pattern,
+                case_insensitive,
+            } => (pattern.clone(), *case_insensitive, None),
+            RuleMatcher::Dictionary {
+                terms,
+                case_insensitive,
+            } => (
+                Self::dictionary_pattern(terms)
+                    .ok_or_else(|| invalid("the dictionary has no terms".to_string()))?,
+                *case_insensitive,
+                None,
+            ),
+            RuleMatcher::Detector { detector } => {
+                (detector.pattern().to_string(), false, Some(*detector))
+            }
+        };
+
+        let regex = RegexBuilder::new(&pattern)
+            .case_insensitive(case_insensitive)
+            .size_limit(PATTERN_SIZE_LIMIT)
+            .build()
+            .map_err(|e| invalid(e.to_string()))?;
+        if regex.is_match("") {
+            return Err(invalid("the pattern matches empty text".to_string()));
+        }
+
+        Ok(CompiledRule {
+            label: rule.label().to_string(),
+            regex,
+            detector,
+        })
+    }
+
+    /// Alternation of the terms, longest first so "Jane Doe" beats "Jane"
+    fn dictionary_pattern(terms: &[String]) -> Option<String> {
+        let mut terms: Vec<&str> = terms
+            .iter()
+            .map(|t| t.trim())
+            .filter(|t| !t.is_empty())
+            .collect();
+        if terms.is_empty() {
+            return None;
+        }
+        terms.sort_by_key(|t| Reverse(t.chars().count()));
+        terms.dedup();
+
+        let boundary = |c: Option<char>| {
+            if c.is_some_and(char::is_alphanumeric) {
+                r"\b"
+            } else {
+                ""
+            }
+        };
+        let alternatives: Vec<String> = terms
+            .iter()
+            .map(|t| {
+                format!(
+                    "{}{}{}",
+                    boundary(t.chars().next()),
+                    regex::escape(t),
+                    boundary(t.chars().last())
+                )
+            })
+            .collect();
+        Some(format!("(?:{})", alternatives.join("|")))
+    }
+
+    /// Entities in a text, without overlaps, in order
+    pub fn find(&self, text: &str) -> Vec<EntityMatch> {
+        let mut candidates = Vec::new();
+        for (priority, rule) in self.rules.iter().enumerate() {
+            for found in rule.regex.find_iter(text) {
+                let value = found.as_str();
+                if value.trim().is_empty()
+                    || rule.detector.is_some_and(|d| !d.accepts(value))
+                    || self.excluded.contains(&PseudonymMap::normalize(value))
+                {
+                    continue;
+                }
+                candidates.push((found.start(), found.end(), priority));
+            }
+        }
+        candidates.sort_by_key(|&(start, end, priority)| (start, Reverse(end), priority));
+
+        let mut matches = Vec::new();
+        let mut covered = 0;
+        for (start, end, priority) in candidates {
+            if start < covered {
+                continue;
+            }
+            covered = end;
+            matches.push(EntityMatch {
+                start,
+                end,
+                label: self.rules[priority].label.clone(),
+            });
+        }
+        matches
+    }
+
+    /// Replace entities in a document's content and title
+    ///
+    /// New values get pseudonyms from `map`; pass a copy to preview the
+    /// replacements without committing them.
+    pub fn anonymize(
+        &self,
+        content: &PmNode,
+        title: Option<&str>,
+        map: &mut PseudonymMap,
+    ) -> AnonymizedContent {
+        let mut replacements = Vec::new();
+        let title = title.map(|t| self.anonymize_text(t, None, map, &mut replacements));
+
+        let mut block = 0;
+        let content = self.rewrite(content, map, &mut block, &mut replacements);
+
+        AnonymizedContent {
+            content,
+            title,
+            replacements,
+        }
+    }
+
+    fn rewrite(
+        &self,
+        node: &PmNode,
+        map: &mut PseudonymMap,
+        block: &mut usize,
+        replacements: &mut Vec<Replacement>,
+    ) -> PmNode {
+        if node.is_textblock() {
+            let rewritten = self.rewrite_block(node, map, *block, replacements);
+            *block += 1;
+            return rewritten;
+        }
+
+        let mut rewritten = node.clone();
+        rewritten.content = node
+            .content
+            .iter()
+            .map(|child| self.rewrite(child, map, block, replacements))
+            .collect();
+        rewritten
+    }
+
+    /// Match over the block's joined text, then cut the matches out of the
+    /// text nodes they span
+    fn rewrite_block(
+        &self,
+        node: &PmNode,
+        map: &mut PseudonymMap,
+        block: usize,
+        replacements: &mut Vec<Replacement>,
+    ) -> PmNode {
+        let mut joined = String::new();
+        let spans: Vec<Option<(usize, usize)>> = node
+            .content
+            .iter()
+            .map(|child| match &child.text {
+                Some(text) => {
+                    let start = joined.len();
+                    joined.push_str(text);
+                    Some((start, joined.len()))
+                }
+                None => {
+                    // Keeps entities from matching across hard breaks
+                    joined.push('\n');
+                    None
+                }
+            })
+            .collect();
+
+        // Pseudonyms are handed out in reading order: the text up to the end
+        // of each child, then the links on that child
+        let matches = self.find(&joined);
+        let mut pseudonyms = Vec::with_capacity(matches.len());
+        let mut rewritten = node.clone();
+        let mut child_end = 0;
+        for (child, span) in rewritten.content.iter_mut().zip(&spans) {
+            child_end = span.map_or(child_end + 1, |(_, end)| end);
+            while let Some(m) = matches
+                .get(pseudonyms.len())
+                .filter(|m| m.start < child_end)
+            {
+                pseudonyms.push(replace(&joined, m, Some(block), map, replacements));
+            }
+
+            for mark in &mut child.marks {
+                if let Some(href) = mark.attrs.as_mut().and_then(|a| a.get_mut("href")) {
+                    if let Some(value) = href.as_str() {
+                        *href = self
+                            .anonymize_text(value, Some(block), map, replacements)
+                            .into();
+                    }
+                }
+            }
+        }
+        if matches.is_empty() {
+            return rewritten;
+        }
+
+        let children = std::mem::take(&mut rewritten.content);
+        rewritten.content = children
+            .into_iter()
+            .zip(spans)
+            .filter_map(|(mut child, span)| {
+                let Some((start, end)) = span else {
+                    return Some(child);
+                };
+
+                let mut text = String::new();
+                let mut cursor = start;
+                for (m, pseudonym) in matches.iter().zip(&pseudonyms) {
+                    if m.end <= start || m.start >= end {
+                        continue;
+                    }
+                    if m.start > cursor {
+                        text.push_str(&joined[cursor..m.start]);
+                    }
+                    if m.start >= start {
+                        text.push_str(pseudonym);
+                    }
+                    cursor = m.end.min(end);
+                }
+                text.push_str(&joined[cursor..end]);
+
+                if text.is_empty() {
+                    return None;
+                }
+                child.text = Some(text);
+                Some(child)
+            })
+            .collect();
+        rewritten
+    }
+
+    fn anonymize_text(
+        &self,
+        text: &str,
+        block: Option<usize>,
+        map: &mut PseudonymMap,
+        replacements: &mut Vec<Replacement>,
+    ) -> String {
+        let mut output = String::new();
+        let mut cursor = 0;
+        for m in self.find(text) {
+            output.push_str(&text[cursor..m.start]);
+            output.push_str(&replace(text, &m, block, map, replacements));
+            cursor = m.end;
+        }
+        output.push_str(&text[cursor..]);
+        output
+    }
+}
+
+/// Look up the pseudonym for a match and record the replacement
+fn replace(
+    text: &str,
+    m: &EntityMatch,
+    block: Option<usize>,
+    map: &mut PseudonymMap,
+    replacements: &mut Vec<Replacement>,
+) -> String {
+    let original = &text[m.start..m.end];
+    let pseudonym = map.pseudonym_for(&m.label, original);
+    replacements.push(Replacement {
+        label: m.label.clone(),
+        original: original.to_string(),
+        pseudonym: pseudonym.clone(),
+        block,
+        offset: text[..m.start].chars().count(),
+        context: context(text, m.start, m.end),
+    });
+    pseudonym
+}
+
+/// The match with up to `CONTEXT_CHARS` characters on either side
+fn context(text: &str, start: usize, end: usize) -> String {
+    let before: Vec<char> = text[..start].chars().collect();
+    let skipped = before.len().saturating_sub(CONTEXT_CHARS);
+    let mut context: String = before[skipped..].iter().collect();
+    if skipped > 0 {
+        context.insert(0, '…');
+    }
+
+    context.push_str(&text[start..end]);
+    let after = &text[end..];
+    context.extend(after.chars().take(CONTEXT_CHARS));
+    if after.chars().count() > CONTEXT_CHARS {
+        context.push('…');
+    }
+    context.replace('\n', " ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::det::PmMark;
+
+    fn rules() -> Vec<AnonymizationRule> {
+        let mut rules = vec![AnonymizationRule::dictionary(
+            "person",
+            vec!["Jane Doe".to_string(), "Jane".to_string()],
+        )
+        .case_insensitive()];
+        rules.extend(BuiltInDetector::ALL.map(AnonymizationRule::detector));
+        rules
+    }
+
+    #[test]
+    fn test_replaces_entities_consistently() {
+        let anonymizer = Anonymizer::new(&rules()).unwrap();
+        let content = PmNode::doc(vec![
+            PmNode::paragraph(vec![
+                PmNode::text("Call Ja", Vec::new()),
+                PmNode::text("ne Doe on ", vec![PmMark::bold()]),
+                PmNode::text("+31 6 1234 5678", vec![PmMark::italic()]),
+                PmNode::text(" or mail ", Vec::new()),
+                PmNode::text(
+                    "jane@example.com",
+                    vec![PmMark::link("mailto:jane@example.com")],
+                ),
+            ]),
+            PmNode::paragraph(PmNode::text_nodes(
+                "JANE DOE paid from NL91 ABNA 0417 1643 00 in 2019 - 2020.",
+                Vec::new(),
+            )),
+        ]);
+
+        let mut map = PseudonymMap::new();
+        let result = anonymizer.anonymize(&content, Some("Letter to Jane"), &mut map);
+
+        assert_eq!(result.title.as_deref(), Some("Letter to [PERSON-1]"));
+        let first = &result.content.content[0];
+        assert_eq!(first.content[0].text.as_deref(), Some("Call [PERSON-2]"));
+        assert_eq!(first.content[1].text.as_deref(), Some(" on "));
+        assert_eq!(first.content[1].marks, vec![PmMark::bold()]);
+        assert_eq!(first.content[2].text.as_deref(), Some("[PHONE-1]"));
+        assert_eq!(
+            first.content[4].marks,
+            vec![PmMark::link("mailto:[EMAIL-1]")]
+        );
+        assert_eq!(
+            result.content.content[1].plain_text(),
+            "[PERSON-2] paid from [IBAN-1] in 2019 - 2020."
+        );
+        assert!(!result.content.plain_text().contains("jane"));
+
+        let labels: Vec<&str> = result
+            .replacements
+            .iter()
+            .map(|r| r.label.as_str())
+            .collect();
+        assert_eq!(
+            labels,
+            vec!["PERSON", "PERSON", "PHONE", "EMAIL", "EMAIL", "PERSON", "IBAN"]
+        );
+        assert_eq!(result.replacements[1].original, "Jane Doe");
+        assert_eq!(result.replacements[1].offset, 5);
+    }
+
+    #[test]
+    fn test_pseudonyms_follow_reading_order() {
+        let anonymizer = Anonymizer::new(&rules()).unwrap();
+        let content = PmNode::doc(vec![PmNode::paragraph(vec![
+            PmNode::text("Ask bob@example.com or ", Vec::new()),
+            PmNode::text(
+                "the office",
+                vec![PmMark::link("mailto:office@example.com")],
+            ),
+            PmNode::text(" and Jane", Vec::new()),
+        ])]);
+
+        let mut map = PseudonymMap::new();
+        let result = anonymizer.anonymize(&content, None, &mut map);
+
+        let order: Vec<(&str, &str)> = result
+            .replacements
+            .iter()
+            .map(|r| (r.original.as_str(), r.pseudonym.as_str()))
+            .collect();
+        assert_eq!(
+            order,
+            vec![
+                ("bob@example.com", "[EMAIL-1]"),
+                ("office@example.com", "[EMAIL-2]"),
+                ("Jane", "[PERSON-1]"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_exclusions_and_invalid_rules() {
+        let anonymizer = Anonymizer::new(&rules()).unwrap().with_exclusions(["jane"]);
+        let matches = anonymizer.find("Jane and Jane Doe");
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].start, 9);
+
+        assert!(matches!(
+            Anonymizer::new(&[]),
+            Err(AnonymizationError::NoRules)
+        ));
+        assert!(Anonymizer::new(&[AnonymizationRule::regex("x", "(")]).is_err());
+        assert!(Anonymizer::new(&[AnonymizationRule::regex("x", "a*")]).is_err());
+        assert!(Anonymizer::new(&[AnonymizationRule::dictionary("x", vec![" ".into()])]).is_err());
+    }
+}
@@ -0,0 +1,24 @@
+use thiserror::Error;
+
+/// Problems with the rules an anonymization runs with
+#[derive(Debug, Error)]
+pub enum AnonymizationError {
+    #[error("Invalid anonymization rule '{label}': {reason}")]
+    InvalidRule { label: String, reason: String },
+
+    #[error("Unknown detector '{0}'")]
+    UnknownDetector(String),
+
+    #[error("No anonymization rules or detectors were given")]
+    NoRules,
+}
+
+impl AnonymizationError {
+    /// Create an InvalidRule error for a rule label
+    pub fn invalid_rule(label: impl Into<String>, reason: impl ToString) -> Self {
+        AnonymizationError::InvalidRule {
+            label: label.into(),
+            reason: reason.to_string(),
+        }
+    }
+}
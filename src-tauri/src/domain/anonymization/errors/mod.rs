@@ -0,0 +1,3 @@
+pub mod anonymization_error;
+
+pub use anonymization_error::AnonymizationError;
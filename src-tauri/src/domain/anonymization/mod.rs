@@ -0,0 +1,12 @@
+pub mod anonymizer;
+pub mod errors;
+pub mod repositories;
+pub mod value_objects;
+
+// Re-export commonly used types
+pub use anonymizer::{AnonymizedContent, Anonymizer, EntityMatch};
+pub use errors::AnonymizationError;
+pub use repositories::PseudonymMapRepository;
+pub use value_objects::{
+    AnonymizationRule, BuiltInDetector, PseudonymMap, Replacement, RuleMatcher,
+};
@@ -0,0 +1,3 @@
+pub mod pseudonym_map_repository;
+
+pub use pseudonym_map_repository::PseudonymMapRepository;
@@ -0,0 +1,20 @@
+use async_trait::async_trait;
+
+use super::super::value_objects::PseudonymMap;
+use crate::domain::project::ProjectId;
+use crate::domain::workspace::repositories::RepositoryError;
+
+/// Storage of a project's pseudonym map
+///
+/// The map links pseudonyms back to the real values, so implementations
+/// must keep it inside the project folder, outside the document families
+/// whose derivatives get exported or shared.
+#[async_trait]
+pub trait PseudonymMapRepository: Send + Sync {
+    /// Load the map of a project; a project without one gets an empty map
+    async fn load(&self, project_id: &ProjectId) -> Result<PseudonymMap, RepositoryError>;
+
+    /// Replace the stored map of a project
+    async fn save(&self, project_id: &ProjectId, map: &PseudonymMap)
+        -> Result<(), RepositoryError>;
+}
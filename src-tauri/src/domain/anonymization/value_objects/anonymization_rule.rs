@@ -0,0 +1,97 @@
+use serde::{Deserialize, Serialize};
+
+use super::built_in_detector::BuiltInDetector;
+
+/// How a rule recognises entities in text
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum RuleMatcher {
+    /// A regular expression; the whole match is replaced
+    #[serde(rename_all = "camelCase")]
+    Regex {
+        pattern: String,
+        #[serde(default)]
+        case_insensitive: bool,
+    },
+    /// A list of names or terms, matched as whole words
+    #[serde(rename_all = "camelCase")]
+    Dictionary {
+        terms: Vec<String>,
+        #[serde(default)]
+        case_insensitive: bool,
+    },
+    /// One of the built-in detectors
+    Detector { detector: BuiltInDetector },
+}
+
+/// A rule replacing one kind of entity with pseudonyms
+///
+/// The label names the entity type in pseudonyms, e.g. `PERSON` gives
+/// `[PERSON-1]`, `[PERSON-2]`, ... Labels are upper-cased with spaces
+/// turned into underscores.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AnonymizationRule {
+    label: String,
+    #[serde(flatten)]
+    matcher: RuleMatcher,
+}
+
+impl AnonymizationRule {
+    pub fn new(label: &str, matcher: RuleMatcher) -> Self {
+        AnonymizationRule {
+            label: label
+                .trim()
+                .to_uppercase()
+                .replace(char::is_whitespace, "_"),
+            matcher,
+        }
+    }
+
+    /// Rule for a built-in detector, labelled after it
+    pub fn detector(detector: BuiltInDetector) -> Self {
+        Self::new(detector.label(), RuleMatcher::Detector { detector })
+    }
+
+    pub fn regex(label: &str, pattern: impl Into<String>) -> Self {
+        Self::new(
+            label,
+            RuleMatcher::Regex {
+                pattern: pattern.into(),
+                case_insensitive: false,
+            },
+        )
+    }
+
+    pub fn dictionary(label: &str, terms: Vec<String>) -> Self {
+        Self::new(
+            label,
+            RuleMatcher::Dictionary {
+                terms,
+                case_insensitive: false,
+            },
+        )
+    }
+
+    /// Match regardless of case; no effect on detectors
+    pub fn case_insensitive(mut self) -> Self {
+        match &mut self.matcher {
+            RuleMatcher::Regex {
+                case_insensitive, ..
+            }
+            | RuleMatcher::Dictionary {
+                case_insensitive, ..
+            } => *case_insensitive = true,
+            RuleMatcher::Detector { .. } => {}
+        }
+        self
+    }
+
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+
+    pub fn matcher(&self) -> &RuleMatcher {
+        &self.matcher
+    }
+}
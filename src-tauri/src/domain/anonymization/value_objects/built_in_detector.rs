@@ -0,0 +1,157 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use crate::domain::anonymization::errors::AnonymizationError;
+
+/// Entity types recognised without configuration
+///
+/// Each detector pairs a pattern with a check of the matched text, so
+/// checksummed identifiers (IBANs, Dutch BSNs) only match when valid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BuiltInDetector {
+    Email,
+    Phone,
+    Iban,
+    /// US social security numbers, UK national insurance numbers and
+    /// Dutch citizen service numbers (BSN)
+    NationalId,
+}
+
+impl BuiltInDetector {
+    /// All detectors, in the order they win ties for the same text
+    pub const ALL: [BuiltInDetector; 4] = [
+        BuiltInDetector::Email,
+        BuiltInDetector::Iban,
+        BuiltInDetector::NationalId,
+        BuiltInDetector::Phone,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BuiltInDetector::Email => "email",
+            BuiltInDetector::Phone => "phone",
+            BuiltInDetector::Iban => "iban",
+            BuiltInDetector::NationalId => "national_id",
+        }
+    }
+
+    pub fn parse(value: &str) -> Result<Self, AnonymizationError> {
+        Self::ALL
+            .into_iter()
+            .find(|detector| detector.as_str() == value)
+            .ok_or_else(|| AnonymizationError::UnknownDetector(value.to_string()))
+    }
+
+    /// Entity label used in pseudonyms
+    pub fn label(&self) -> &'static str {
+        match self {
+            BuiltInDetector::Email => "EMAIL",
+            BuiltInDetector::Phone => "PHONE",
+            BuiltInDetector::Iban => "IBAN",
+            BuiltInDetector::NationalId => "NATIONAL_ID",
+        }
+    }
+
+    /// Regular expression finding candidates
+    pub fn pattern(&self) -> &'static str {
+        match self {
+            BuiltInDetector::Email => r"(?i)\b[a-z0-9._%+-]+@[a-z0-9.-]+\.[a-z]{2,}\b",
+            BuiltInDetector::Phone => r"(?:\+\d|\(\d|\b\d)[\d ().-]{6,}\d\b",
+            BuiltInDetector::Iban => r"\b[A-Z]{2}\d{2}(?: ?[A-Z0-9]{4}){2,7}(?: ?[A-Z0-9]{1,3})?\b",
+            BuiltInDetector::NationalId => {
+                r"\b(?:\d{3}-\d{2}-\d{4}|[A-CEGHJ-PR-TW-Z]{2} ?\d{2} ?\d{2} ?\d{2} ?[A-D]|\d{9})\b"
+            }
+        }
+    }
+
+    /// Whether a candidate found by `pattern` really is such an entity
+    pub fn accepts(&self, candidate: &str) -> bool {
+        match self {
+            BuiltInDetector::Email => true,
+            BuiltInDetector::Phone => is_phone_number(candidate),
+            BuiltInDetector::Iban => is_valid_iban(candidate),
+            BuiltInDetector::NationalId => is_national_id(candidate),
+        }
+    }
+}
+
+impl fmt::Display for BuiltInDetector {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// 8 to 15 digits, and not a range of numbers such as "2019 - 2020"
+fn is_phone_number(candidate: &str) -> bool {
+    let digits = candidate.chars().filter(char::is_ascii_digit).count();
+    (8..=15).contains(&digits) && !candidate.contains(" - ") && !candidate.contains(" . ")
+}
+
+/// ISO 13616 check: the rearranged number modulo 97 must be 1
+fn is_valid_iban(candidate: &str) -> bool {
+    let iban: String = candidate.chars().filter(|c| !c.is_whitespace()).collect();
+    if !(15..=34).contains(&iban.len()) {
+        return false;
+    }
+
+    let (head, tail) = iban.split_at(4);
+    let mut remainder = 0u32;
+    for c in tail.chars().chain(head.chars()) {
+        let Some(value) = c.to_digit(36) else {
+            return false;
+        };
+        remainder = if value < 10 {
+            (remainder * 10 + value) % 97
+        } else {
+            (remainder * 100 + value) % 97
+        };
+    }
+    remainder == 1
+}
+
+fn is_national_id(candidate: &str) -> bool {
+    let digits: Vec<u32> = candidate.chars().filter_map(|c| c.to_digit(10)).collect();
+
+    if candidate.len() == 9 && digits.len() == 9 {
+        // Dutch BSN: the "elfproef" weighs the digits 9..2 and the last by -1
+        let sum: i64 = digits
+            .iter()
+            .enumerate()
+            .map(|(i, d)| {
+                let weight = if i == 8 { -1 } else { 9 - i as i64 };
+                weight * i64::from(*d)
+            })
+            .sum();
+        return sum != 0 && sum % 11 == 0;
+    }
+    if candidate.contains('-') {
+        // US SSN: area 000, 666 and 900-999 are never issued
+        let area = &candidate[..3];
+        return area != "000" && area != "666" && !area.starts_with('9');
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_checksums_and_heuristics() {
+        assert!(is_valid_iban("NL91 ABNA 0417 1643 00"));
+        assert!(is_valid_iban("DE89370400440532013000"));
+        assert!(!is_valid_iban("NL91 ABNA 0417 1643 01"));
+
+        assert!(is_national_id("111222333"));
+        assert!(!is_national_id("111222334"));
+        assert!(is_national_id("123-45-6789"));
+        assert!(!is_national_id("666-45-6789"));
+        assert!(is_national_id("AB 12 34 56 C"));
+
+        assert!(is_phone_number("+31 6 1234 5678"));
+        assert!(is_phone_number("(555) 123-4567"));
+        assert!(!is_phone_number("2019 - 2020"));
+        assert!(!is_phone_number("123 4567"));
+    }
+}
@@ -0,0 +1,9 @@
+pub mod anonymization_rule;
+pub mod built_in_detector;
+pub mod pseudonym_map;
+pub mod replacement;
+
+pub use anonymization_rule::{AnonymizationRule, RuleMatcher};
+pub use built_in_detector::BuiltInDetector;
+pub use pseudonym_map::PseudonymMap;
+pub use replacement::Replacement;
@@ -0,0 +1,92 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Project-wide mapping from real values to pseudonyms
+///
+/// The same value gets the same pseudonym in every document of a project,
+/// so anonymized documents can still be read side by side. Values are
+/// compared ignoring case and runs of whitespace; pseudonyms are numbered
+/// per label in order of first appearance and are never reused.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PseudonymMap {
+    #[serde(default)]
+    labels: BTreeMap<String, LabelPseudonyms>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LabelPseudonyms {
+    /// Number of pseudonyms handed out so far
+    issued: u32,
+    /// Normalized real value to pseudonym
+    pseudonyms: BTreeMap<String, String>,
+}
+
+impl PseudonymMap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Pseudonym of a value, handing out the next one for new values
+    pub fn pseudonym_for(&mut self, label: &str, value: &str) -> String {
+        let entry = self.labels.entry(label.to_string()).or_default();
+        let key = Self::normalize(value);
+        if let Some(pseudonym) = entry.pseudonyms.get(&key) {
+            return pseudonym.clone();
+        }
+
+        entry.issued += 1;
+        let pseudonym = format!("[{}-{}]", label, entry.issued);
+        entry.pseudonyms.insert(key, pseudonym.clone());
+        pseudonym
+    }
+
+    /// Pseudonym already given to a value, if any
+    pub fn get(&self, label: &str, value: &str) -> Option<&str> {
+        self.labels
+            .get(label)
+            .and_then(|entry| entry.pseudonyms.get(&Self::normalize(value)))
+            .map(String::as_str)
+    }
+
+    /// Number of values with a pseudonym
+    pub fn len(&self) -> usize {
+        self.labels
+            .values()
+            .map(|entry| entry.pseudonyms.len())
+            .sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Form values are compared in
+    pub fn normalize(value: &str) -> String {
+        value
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ")
+            .to_lowercase()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pseudonyms_are_stable_per_label() {
+        let mut map = PseudonymMap::new();
+        assert_eq!(map.pseudonym_for("PERSON", "Jane Doe"), "[PERSON-1]");
+        assert_eq!(map.pseudonym_for("PERSON", "John Roe"), "[PERSON-2]");
+        assert_eq!(map.pseudonym_for("PERSON", "jane  DOE"), "[PERSON-1]");
+        assert_eq!(map.pseudonym_for("EMAIL", "jane@example.com"), "[EMAIL-1]");
+        assert_eq!(map.len(), 3);
+
+        let json = serde_json::to_string(&map).unwrap();
+        let restored: PseudonymMap = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.get("PERSON", "John Roe"), Some("[PERSON-2]"));
+    }
+}
@@ -0,0 +1,16 @@
+/// One value replaced by a pseudonym, as listed for review
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Replacement {
+    /// Label of the rule that matched
+    pub label: String,
+    /// Text as it appears in the document
+    pub original: String,
+    pub pseudonym: String,
+    /// Index of the text block, counting paragraphs, headings and code
+    /// blocks in reading order; `None` for the document title
+    pub block: Option<usize>,
+    /// Character offset of the value within the block
+    pub offset: usize,
+    /// The surrounding text of the block
+    pub context: String,
+}
@@ -0,0 +1,226 @@
+use chrono::{DateTime, Utc};
+
+use crate::domain::category::errors::CategoryError;
+use crate::domain::category::value_objects::{CategoryColor, CategoryId, CategoryKind, Shortcut};
+use crate::domain::project::ProjectId;
+
+/// Longest name accepted for a category or tag
+const MAX_NAME_LENGTH: usize = 100;
+
+/// Category aggregate root: a node of a project's category tree, or a tag
+///
+/// Business Rules:
+/// - Categories and tags can be nested below a parent of the same kind
+/// - A node can never be its own ancestor (checked when moving it)
+/// - Names are unique among siblings of the same kind
+/// - Shortcuts are unique within a project
+/// - Documents are assigned by their stable id, so assignments survive
+///   renames and moves of the files
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Category {
+    id: CategoryId,
+    project_id: ProjectId,
+    kind: CategoryKind,
+    name: String,
+    parent_id: Option<CategoryId>,
+    color: Option<CategoryColor>,
+    shortcut: Option<Shortcut>,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+}
+
+impl Category {
+    /// Create a category or tag at the top level
+    pub fn new(
+        project_id: ProjectId,
+        kind: CategoryKind,
+        name: String,
+    ) -> Result<Self, CategoryError> {
+        let now = Utc::now();
+        Ok(Category {
+            id: CategoryId::new(),
+            project_id,
+            kind,
+            name: validate_name(name)?,
+            parent_id: None,
+            color: None,
+            shortcut: None,
+            created_at: now,
+            updated_at: now,
+        })
+    }
+
+    /// Create a Category from existing data (for repository reconstruction)
+    #[allow(clippy::too_many_arguments)]
+    pub fn from_data(
+        id: CategoryId,
+        project_id: ProjectId,
+        kind: CategoryKind,
+        name: String,
+        parent_id: Option<CategoryId>,
+        color: Option<CategoryColor>,
+        shortcut: Option<Shortcut>,
+        created_at: DateTime<Utc>,
+        updated_at: DateTime<Utc>,
+    ) -> Self {
+        Category {
+            id,
+            project_id,
+            kind,
+            name,
+            parent_id,
+            color,
+            shortcut,
+            created_at,
+            updated_at,
+        }
+    }
+
+    pub fn id(&self) -> &CategoryId {
+        &self.id
+    }
+
+    pub fn project_id(&self) -> &ProjectId {
+        &self.project_id
+    }
+
+    pub fn kind(&self) -> CategoryKind {
+        self.kind
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn parent_id(&self) -> Option<&CategoryId> {
+        self.parent_id.as_ref()
+    }
+
+    pub fn color(&self) -> Option<&CategoryColor> {
+        self.color.as_ref()
+    }
+
+    pub fn shortcut(&self) -> Option<&Shortcut> {
+        self.shortcut.as_ref()
+    }
+
+    pub fn created_at(&self) -> DateTime<Utc> {
+        self.created_at
+    }
+
+    pub fn updated_at(&self) -> DateTime<Utc> {
+        self.updated_at
+    }
+
+    pub fn rename(&mut self, name: String) -> Result<(), CategoryError> {
+        self.name = validate_name(name)?;
+        self.touch();
+        Ok(())
+    }
+
+    /// Move below another node of the same kind, or to the top level
+    ///
+    /// `ancestors` are the ids from the new parent up to the root; moving a
+    /// node below itself or one of its descendants is refused.
+    pub fn move_to(
+        &mut self,
+        parent: Option<&Category>,
+        ancestors: &[CategoryId],
+    ) -> Result<(), CategoryError> {
+        if let Some(parent) = parent {
+            if parent.project_id != self.project_id {
+                return Err(CategoryError::InvalidParent(
+                    "the parent belongs to another project".to_string(),
+                ));
+            }
+            if parent.kind != self.kind {
+                return Err(CategoryError::InvalidParent(format!(
+                    "a {} cannot be placed below a {}",
+                    self.kind.as_str(),
+                    parent.kind.as_str()
+                )));
+            }
+            if parent.id == self.id || ancestors.contains(&self.id) {
+                return Err(CategoryError::InvalidParent(format!(
+                    "'{}' cannot be placed below itself",
+                    self.name
+                )));
+            }
+        }
+        self.parent_id = parent.map(|parent| parent.id.clone());
+        self.touch();
+        Ok(())
+    }
+
+    pub fn set_color(&mut self, color: Option<CategoryColor>) {
+        self.color = color;
+        self.touch();
+    }
+
+    pub fn set_shortcut(&mut self, shortcut: Option<Shortcut>) {
+        self.shortcut = shortcut;
+        self.touch();
+    }
+
+    fn touch(&mut self) {
+        self.updated_at = Utc::now();
+    }
+}
+
+fn validate_name(name: String) -> Result<String, CategoryError> {
+    let name = name.trim().to_string();
+    if name.is_empty() {
+        return Err(CategoryError::InvalidName(
+            "the name cannot be empty".to_string(),
+        ));
+    }
+    if name.chars().count() > MAX_NAME_LENGTH {
+        return Err(CategoryError::InvalidName(format!(
+            "the name cannot be longer than {} characters",
+            MAX_NAME_LENGTH
+        )));
+    }
+    Ok(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_move_refuses_cycles_and_mixed_kinds() {
+        let project_id = ProjectId::new();
+        let category = |name: &str| {
+            Category::new(project_id.clone(), CategoryKind::Category, name.to_string()).unwrap()
+        };
+        let mut finance = category("Finance");
+        let mut invoices = category("Invoices");
+        let tag =
+            Category::new(project_id.clone(), CategoryKind::Tag, "Urgent".to_string()).unwrap();
+
+        invoices.move_to(Some(&finance), &[]).unwrap();
+        assert_eq!(invoices.parent_id(), Some(finance.id()));
+
+        // Finance below Invoices would make Finance its own ancestor
+        let ancestors = vec![invoices.id().clone(), finance.id().clone()];
+        assert!(matches!(
+            finance.move_to(Some(&invoices), &ancestors),
+            Err(CategoryError::InvalidParent(_))
+        ));
+        assert!(finance.move_to(Some(&finance.clone()), &[]).is_err());
+        assert!(invoices.move_to(Some(&tag), &[]).is_err());
+
+        invoices.move_to(None, &[]).unwrap();
+        assert_eq!(invoices.parent_id(), None);
+    }
+
+    #[test]
+    fn test_name_validation() {
+        let create =
+            |name: &str| Category::new(ProjectId::new(), CategoryKind::Tag, name.to_string());
+
+        assert_eq!(create("  Urgent ").unwrap().name(), "Urgent");
+        assert!(create(" ").is_err());
+        assert!(create(&"x".repeat(101)).is_err());
+    }
+}
@@ -0,0 +1,3 @@
+pub mod category;
+
+pub use category::Category;
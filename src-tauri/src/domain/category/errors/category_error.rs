@@ -0,0 +1,33 @@
+use thiserror::Error;
+
+/// Errors raised by the categories and tags of a project
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum CategoryError {
+    #[error("Invalid category ID: {0}")]
+    InvalidId(String),
+
+    #[error("Unknown category kind '{0}'; use category or tag")]
+    InvalidKind(String),
+
+    #[error("Invalid category name: {0}")]
+    InvalidName(String),
+
+    #[error("Invalid colour '{0}'; use a hex colour such as #3b82f6")]
+    InvalidColor(String),
+
+    #[error("Invalid shortcut '{value}': {reason}")]
+    InvalidShortcut { value: String, reason: String },
+
+    #[error("Invalid parent: {0}")]
+    InvalidParent(String),
+}
+
+impl CategoryError {
+    /// Create an InvalidShortcut error
+    pub fn invalid_shortcut(value: impl Into<String>, reason: impl ToString) -> Self {
+        CategoryError::InvalidShortcut {
+            value: value.into(),
+            reason: reason.to_string(),
+        }
+    }
+}
@@ -0,0 +1,3 @@
+pub mod category_error;
+
+pub use category_error::CategoryError;
@@ -0,0 +1,10 @@
+pub mod aggregates;
+pub mod errors;
+pub mod repositories;
+pub mod value_objects;
+
+// Re-export commonly used types
+pub use aggregates::Category;
+pub use errors::CategoryError;
+pub use repositories::{CategoryAssignment, CategoryRepository};
+pub use value_objects::{CategoryColor, CategoryId, CategoryKind, Shortcut};
@@ -0,0 +1,61 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+
+use crate::domain::category::aggregates::Category;
+use crate::domain::category::value_objects::CategoryId;
+use crate::domain::document::DocumentId;
+use crate::domain::project::ProjectId;
+use crate::domain::workspace::repositories::RepositoryError;
+
+/// A category or tag assigned to a document
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CategoryAssignment {
+    pub document_id: DocumentId,
+    pub category_id: CategoryId,
+    pub assigned_at: DateTime<Utc>,
+}
+
+/// Repository trait for the categories and tags of projects and their
+/// assignments to documents
+#[async_trait]
+pub trait CategoryRepository: Send + Sync {
+    /// Insert or update a category
+    async fn save(&self, category: &Category) -> Result<(), RepositoryError>;
+
+    async fn find_by_id(&self, id: &CategoryId) -> Result<Option<Category>, RepositoryError>;
+
+    /// List the categories and tags of a project by name
+    async fn list_by_project(
+        &self,
+        project_id: &ProjectId,
+    ) -> Result<Vec<Category>, RepositoryError>;
+
+    /// Delete categories together with their assignments
+    async fn delete(&self, ids: &[CategoryId]) -> Result<(), RepositoryError>;
+
+    /// Assign a category to documents; returns how many were not assigned yet
+    async fn assign(
+        &self,
+        category_id: &CategoryId,
+        document_ids: &[DocumentId],
+    ) -> Result<usize, RepositoryError>;
+
+    /// Remove a category from documents; returns how many had it
+    async fn unassign(
+        &self,
+        category_id: &CategoryId,
+        document_ids: &[DocumentId],
+    ) -> Result<usize, RepositoryError>;
+
+    /// All assignments of a project's categories
+    async fn list_assignments(
+        &self,
+        project_id: &ProjectId,
+    ) -> Result<Vec<CategoryAssignment>, RepositoryError>;
+
+    /// The categories assigned to a document
+    async fn categories_of(
+        &self,
+        document_id: &DocumentId,
+    ) -> Result<Vec<CategoryId>, RepositoryError>;
+}
@@ -0,0 +1,3 @@
+pub mod category_repository;
+
+pub use category_repository::{CategoryAssignment, CategoryRepository};
@@ -0,0 +1,50 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use crate::domain::category::errors::CategoryError;
+
+/// Display colour of a category, as a lower-case `#rrggbb` hex string
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct CategoryColor(String);
+
+impl CategoryColor {
+    /// Parse `#rrggbb` or the short form `#rgb`
+    pub fn parse(value: &str) -> Result<Self, CategoryError> {
+        let invalid = || CategoryError::InvalidColor(value.to_string());
+        let digits = value.trim().strip_prefix('#').ok_or_else(invalid)?;
+        if !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(invalid());
+        }
+
+        let digits = match digits.len() {
+            3 => digits.chars().flat_map(|c| [c, c]).collect(),
+            6 => digits.to_string(),
+            _ => return Err(invalid()),
+        };
+        Ok(CategoryColor(format!("#{}", digits.to_lowercase())))
+    }
+
+    pub fn value(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for CategoryColor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(CategoryColor::parse("#3B82F6").unwrap().value(), "#3b82f6");
+        assert_eq!(CategoryColor::parse(" #f0a ").unwrap().value(), "#ff00aa");
+        assert!(CategoryColor::parse("3b82f6").is_err());
+        assert!(CategoryColor::parse("#3b82f").is_err());
+        assert!(CategoryColor::parse("#gggggg").is_err());
+    }
+}
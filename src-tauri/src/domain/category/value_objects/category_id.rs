@@ -0,0 +1,44 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use uuid::Uuid;
+
+use crate::domain::category::errors::CategoryError;
+
+/// CategoryId value object identifying a category or tag
+///
+/// All category identifiers use the format: category_xxxxxxxx-xxxx-xxxx-xxxx-xxxxxxxxxxxx
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct CategoryId(String);
+
+impl CategoryId {
+    const PREFIX: &'static str = "category_";
+
+    /// Create a new CategoryId with a generated UUID
+    pub fn new() -> Self {
+        CategoryId(format!("{}{}", Self::PREFIX, Uuid::new_v4()))
+    }
+
+    /// Create a CategoryId from an existing string, validating the prefix format
+    pub fn from_string(value: String) -> Result<Self, CategoryError> {
+        match value.strip_prefix(Self::PREFIX) {
+            Some(uuid_part) if uuid_part.parse::<Uuid>().is_ok() => Ok(CategoryId(value)),
+            _ => Err(CategoryError::InvalidId(value)),
+        }
+    }
+
+    pub fn value(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Default for CategoryId {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Display for CategoryId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
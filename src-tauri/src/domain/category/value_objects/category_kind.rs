@@ -0,0 +1,31 @@
+use serde::{Deserialize, Serialize};
+
+use crate::domain::category::errors::CategoryError;
+
+/// Whether a label is a node of the category tree or a free tag
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CategoryKind {
+    /// A node of the category hierarchy
+    Category,
+
+    /// A tag; tags may also be nested to group them
+    Tag,
+}
+
+impl CategoryKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CategoryKind::Category => "category",
+            CategoryKind::Tag => "tag",
+        }
+    }
+
+    pub fn parse(value: &str) -> Result<Self, CategoryError> {
+        match value.trim().to_lowercase().as_str() {
+            "category" => Ok(CategoryKind::Category),
+            "tag" => Ok(CategoryKind::Tag),
+            _ => Err(CategoryError::InvalidKind(value.to_string())),
+        }
+    }
+}
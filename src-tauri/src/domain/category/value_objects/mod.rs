@@ -0,0 +1,9 @@
+pub mod category_color;
+pub mod category_id;
+pub mod category_kind;
+pub mod shortcut;
+
+pub use category_color::CategoryColor;
+pub use category_id::CategoryId;
+pub use category_kind::CategoryKind;
+pub use shortcut::Shortcut;
@@ -0,0 +1,97 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use crate::domain::category::errors::CategoryError;
+
+/// Modifier keys in the order they are written
+const MODIFIERS: [&str; 4] = ["Ctrl", "Alt", "Shift", "Meta"];
+
+/// Keyboard shortcut assigning a category, such as `1` or `Ctrl+Shift+K`
+///
+/// Stored in a canonical form: modifiers in a fixed order, then a letter,
+/// digit or function key, so equal shortcuts compare equal.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Shortcut(String);
+
+impl Shortcut {
+    pub fn parse(value: &str) -> Result<Self, CategoryError> {
+        let invalid = |reason: &str| CategoryError::invalid_shortcut(value, reason);
+
+        let mut parts: Vec<&str> = value.split('+').map(str::trim).collect();
+        let key = parts
+            .pop()
+            .filter(|key| !key.is_empty())
+            .ok_or_else(|| invalid("the key is missing"))?;
+
+        let mut modifiers = [false; MODIFIERS.len()];
+        for part in parts {
+            let index = match part.to_lowercase().as_str() {
+                "ctrl" | "control" => 0,
+                "alt" | "option" => 1,
+                "shift" => 2,
+                "meta" | "cmd" | "command" => 3,
+                _ => return Err(invalid("use Ctrl, Alt, Shift or Meta as modifiers")),
+            };
+            if modifiers[index] {
+                return Err(invalid("a modifier is repeated"));
+            }
+            modifiers[index] = true;
+        }
+
+        let key = key.to_uppercase();
+        let is_function_key = key
+            .strip_prefix('F')
+            .and_then(|number| number.parse::<u8>().ok())
+            .is_some_and(|number| (1..=24).contains(&number));
+        let is_character =
+            key.chars().count() == 1 && key.chars().all(|c| c.is_ascii_alphanumeric());
+        if !is_function_key && !is_character {
+            return Err(invalid("use a letter, a digit or F1 to F24 as the key"));
+        }
+
+        let mut canonical: Vec<&str> = MODIFIERS
+            .iter()
+            .zip(modifiers)
+            .filter(|(_, used)| *used)
+            .map(|(name, _)| *name)
+            .collect();
+        canonical.push(&key);
+        Ok(Shortcut(canonical.join("+")))
+    }
+
+    pub fn value(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for Shortcut {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_canonical_form() {
+        assert_eq!(Shortcut::parse("1").unwrap().value(), "1");
+        assert_eq!(
+            Shortcut::parse("shift + ctrl + k").unwrap().value(),
+            "Ctrl+Shift+K"
+        );
+        assert_eq!(Shortcut::parse("Cmd+f5").unwrap().value(), "Meta+F5");
+        assert_eq!(
+            Shortcut::parse("Alt+Shift+x").unwrap(),
+            Shortcut::parse("Shift+Alt+X").unwrap()
+        );
+
+        assert!(Shortcut::parse("").is_err());
+        assert!(Shortcut::parse("Ctrl+").is_err());
+        assert!(Shortcut::parse("Ctrl+Ctrl+K").is_err());
+        assert!(Shortcut::parse("Hyper+K").is_err());
+        assert!(Shortcut::parse("Ctrl+Enter").is_err());
+        assert!(Shortcut::parse("F25").is_err());
+    }
+}
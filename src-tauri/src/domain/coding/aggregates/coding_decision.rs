@@ -0,0 +1,105 @@
+use chrono::{DateTime, Utc};
+
+use crate::domain::coding::errors::CodingError;
+use crate::domain::coding::value_objects::{CodingDecisionId, CodingValues};
+use crate::domain::document::DocumentId;
+use crate::domain::project::ProjectId;
+
+/// Longest reviewer name accepted
+const MAX_REVIEWER_LENGTH: usize = 100;
+
+/// CodingDecision aggregate root: how a reviewer coded a document
+///
+/// Business Rules:
+/// - Decisions are checked against the project's coding schema before
+///   they are made
+/// - A decision is never changed; recoding a document makes a new one, so
+///   the document keeps its history
+/// - Every decision has a reviewer
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CodingDecision {
+    id: CodingDecisionId,
+    project_id: ProjectId,
+    document_id: DocumentId,
+    values: CodingValues,
+    reviewer: String,
+    decided_at: DateTime<Utc>,
+}
+
+impl CodingDecision {
+    /// Record `values`, already validated against the schema, for a document
+    pub fn new(
+        project_id: ProjectId,
+        document_id: DocumentId,
+        values: CodingValues,
+        reviewer: String,
+    ) -> Result<Self, CodingError> {
+        Ok(CodingDecision {
+            id: CodingDecisionId::new(),
+            project_id,
+            document_id,
+            values,
+            reviewer: validate_reviewer(reviewer)?,
+            decided_at: Utc::now(),
+        })
+    }
+
+    /// Create a CodingDecision from existing data (for repository reconstruction)
+    pub fn from_data(
+        id: CodingDecisionId,
+        project_id: ProjectId,
+        document_id: DocumentId,
+        values: CodingValues,
+        reviewer: String,
+        decided_at: DateTime<Utc>,
+    ) -> Self {
+        CodingDecision {
+            id,
+            project_id,
+            document_id,
+            values,
+            reviewer,
+            decided_at,
+        }
+    }
+
+    pub fn id(&self) -> &CodingDecisionId {
+        &self.id
+    }
+
+    pub fn project_id(&self) -> &ProjectId {
+        &self.project_id
+    }
+
+    pub fn document_id(&self) -> &DocumentId {
+        &self.document_id
+    }
+
+    pub fn values(&self) -> &CodingValues {
+        &self.values
+    }
+
+    pub fn reviewer(&self) -> &str {
+        &self.reviewer
+    }
+
+    pub fn decided_at(&self) -> DateTime<Utc> {
+        self.decided_at
+    }
+}
+
+fn validate_reviewer(reviewer: String) -> Result<String, CodingError> {
+    let reviewer = reviewer.trim().to_string();
+    if reviewer.is_empty() {
+        return Err(CodingError::InvalidReviewer(
+            "the reviewer cannot be empty".to_string(),
+        ));
+    }
+    if reviewer.chars().count() > MAX_REVIEWER_LENGTH {
+        return Err(CodingError::InvalidReviewer(format!(
+            "the reviewer cannot be longer than {} characters",
+            MAX_REVIEWER_LENGTH
+        )));
+    }
+    Ok(reviewer)
+}
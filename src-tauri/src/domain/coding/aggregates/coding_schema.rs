@@ -0,0 +1,358 @@
+use chrono::{DateTime, Utc};
+use std::collections::HashSet;
+
+use crate::domain::coding::errors::CodingError;
+use crate::domain::coding::value_objects::{
+    CodingField, CodingValue, CodingValues, FieldCondition, FieldKind,
+};
+use crate::domain::project::ProjectId;
+
+/// Key of the designation field of the default schema
+pub const DESIGNATION_FIELD: &str = "designation";
+
+/// Key of the reason field of the default schema
+pub const REASON_FIELD: &str = "reason";
+
+/// CodingSchema aggregate root: the fields reviewers fill in when coding
+/// the documents of a project
+///
+/// Business Rules:
+/// - Field keys are unique; choice fields list their allowed values
+/// - A field can be required always, or only when another choice or yes/no
+///   field has a given value
+/// - Projects without a saved schema use the default one: a required
+///   designation (Relevant, Not Relevant or Privileged) and a reason,
+///   required for privileged documents
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CodingSchema {
+    project_id: ProjectId,
+    fields: Vec<CodingField>,
+    updated_at: Option<DateTime<Utc>>,
+}
+
+impl CodingSchema {
+    /// Create a schema with the given fields
+    pub fn new(project_id: ProjectId, fields: Vec<CodingField>) -> Result<Self, CodingError> {
+        Ok(CodingSchema {
+            project_id,
+            fields: validate_fields(fields)?,
+            updated_at: Some(Utc::now()),
+        })
+    }
+
+    /// The schema used until a project saves its own
+    pub fn default_for(project_id: ProjectId) -> Self {
+        let designation = |value: &str| value.to_string();
+        CodingSchema {
+            project_id,
+            fields: vec![
+                CodingField {
+                    key: DESIGNATION_FIELD.to_string(),
+                    label: "Designation".to_string(),
+                    kind: FieldKind::SingleChoice,
+                    allowed_values: vec![
+                        designation("Relevant"),
+                        designation("Not Relevant"),
+                        designation("Privileged"),
+                    ],
+                    required: true,
+                    required_when: None,
+                },
+                CodingField {
+                    key: REASON_FIELD.to_string(),
+                    label: "Reason".to_string(),
+                    kind: FieldKind::Text,
+                    allowed_values: Vec::new(),
+                    required: false,
+                    required_when: Some(FieldCondition {
+                        field: DESIGNATION_FIELD.to_string(),
+                        equals: designation("Privileged"),
+                    }),
+                },
+            ],
+            updated_at: None,
+        }
+    }
+
+    /// Create a CodingSchema from existing data (for repository reconstruction)
+    pub fn from_data(
+        project_id: ProjectId,
+        fields: Vec<CodingField>,
+        updated_at: DateTime<Utc>,
+    ) -> Self {
+        CodingSchema {
+            project_id,
+            fields,
+            updated_at: Some(updated_at),
+        }
+    }
+
+    pub fn project_id(&self) -> &ProjectId {
+        &self.project_id
+    }
+
+    pub fn fields(&self) -> &[CodingField] {
+        &self.fields
+    }
+
+    pub fn field(&self, key: &str) -> Option<&CodingField> {
+        self.fields.iter().find(|field| field.key == key)
+    }
+
+    /// When the schema was saved; `None` for the default schema
+    pub fn updated_at(&self) -> Option<DateTime<Utc>> {
+        self.updated_at
+    }
+
+    /// Whether this is the default schema, not saved by the project
+    pub fn is_default(&self) -> bool {
+        self.updated_at.is_none()
+    }
+
+    /// Check values against the schema and normalize them
+    ///
+    /// Blank values are dropped and text is trimmed, so a blank required
+    /// field counts as missing.
+    pub fn validate(&self, values: CodingValues) -> Result<CodingValues, CodingError> {
+        let invalid = |message: String| Err(CodingError::InvalidDecision(message));
+
+        let mut normalized = CodingValues::new();
+        for (key, value) in values {
+            let Some(field) = self.field(&key) else {
+                return invalid(format!("'{}' is not a field of the schema", key));
+            };
+            if value.is_empty() {
+                continue;
+            }
+
+            let value = match (field.kind, value) {
+                (FieldKind::Boolean, value @ CodingValue::Flag(_)) => value,
+                (FieldKind::Text, CodingValue::Text(text)) => {
+                    CodingValue::Text(text.trim().to_string())
+                }
+                (FieldKind::SingleChoice, CodingValue::Text(choice)) => {
+                    if !field.allowed_values.contains(&choice) {
+                        return invalid(format!("'{}' is not allowed for {}", choice, field.label));
+                    }
+                    CodingValue::Text(choice)
+                }
+                (FieldKind::MultipleChoice, CodingValue::Choices(choices)) => {
+                    if let Some(choice) = choices
+                        .iter()
+                        .find(|choice| !field.allowed_values.contains(choice))
+                    {
+                        return invalid(format!("'{}' is not allowed for {}", choice, field.label));
+                    }
+                    // Keep the order of the allowed values, without repeats
+                    CodingValue::Choices(
+                        field
+                            .allowed_values
+                            .iter()
+                            .filter(|allowed| choices.contains(allowed))
+                            .cloned()
+                            .collect(),
+                    )
+                }
+                _ => return invalid(format!("{} takes a different kind of value", field.label)),
+            };
+            normalized.insert(key, value);
+        }
+
+        for field in &self.fields {
+            if !normalized.contains_key(&field.key) && self.is_required(field, &normalized) {
+                return invalid(format!("{} is required", field.label));
+            }
+        }
+
+        Ok(normalized)
+    }
+
+    fn is_required(&self, field: &CodingField, values: &CodingValues) -> bool {
+        field.required
+            || field.required_when.as_ref().is_some_and(|condition| {
+                values
+                    .get(&condition.field)
+                    .is_some_and(|value| value.matches(&condition.equals))
+            })
+    }
+}
+
+fn validate_fields(fields: Vec<CodingField>) -> Result<Vec<CodingField>, CodingError> {
+    let invalid = |message: String| Err(CodingError::InvalidSchema(message));
+
+    if fields.is_empty() {
+        return invalid("a schema needs at least one field".to_string());
+    }
+
+    let mut keys = HashSet::new();
+    let mut validated = Vec::with_capacity(fields.len());
+    for mut field in fields {
+        field.key = field.key.trim().to_string();
+        field.label = field.label.trim().to_string();
+        let key_is_valid = !field.key.is_empty()
+            && field
+                .key
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_');
+        if !key_is_valid {
+            return invalid(format!(
+                "'{}' is not a valid key; use lowercase letters, digits and underscores",
+                field.key
+            ));
+        }
+        if !keys.insert(field.key.clone()) {
+            return invalid(format!("the key '{}' is used twice", field.key));
+        }
+        if field.label.is_empty() {
+            field.label = field.key.clone();
+        }
+
+        field.allowed_values = field
+            .allowed_values
+            .iter()
+            .map(|value| value.trim().to_string())
+            .filter(|value| !value.is_empty())
+            .collect();
+        let distinct: HashSet<&String> = field.allowed_values.iter().collect();
+        if distinct.len() != field.allowed_values.len() {
+            return invalid(format!("{} lists a value twice", field.label));
+        }
+        match (field.kind.is_choice(), field.allowed_values.is_empty()) {
+            (true, true) => return invalid(format!("{} needs allowed values", field.label)),
+            (false, false) => {
+                return invalid(format!(
+                    "only choice fields take allowed values, not {}",
+                    field.label
+                ))
+            }
+            _ => {}
+        }
+        validated.push(field);
+    }
+
+    for field in &validated {
+        let Some(condition) = &field.required_when else {
+            continue;
+        };
+        let Some(other) = validated
+            .iter()
+            .find(|other| other.key == condition.field && other.key != field.key)
+        else {
+            return invalid(format!(
+                "{} depends on '{}', which is not another field",
+                field.label, condition.field
+            ));
+        };
+        let value_exists = match other.kind {
+            FieldKind::Boolean => matches!(condition.equals.as_str(), "true" | "false"),
+            FieldKind::Text => false,
+            _ => other.allowed_values.contains(&condition.equals),
+        };
+        if !value_exists {
+            return invalid(format!(
+                "{} depends on {} being '{}', which it cannot be",
+                field.label, other.label, condition.equals
+            ));
+        }
+    }
+
+    Ok(validated)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn values(entries: &[(&str, CodingValue)]) -> CodingValues {
+        entries
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.clone()))
+            .collect()
+    }
+
+    fn text(value: &str) -> CodingValue {
+        CodingValue::Text(value.to_string())
+    }
+
+    #[test]
+    fn test_default_schema_requires_reason_for_privileged() {
+        let schema = CodingSchema::default_for(ProjectId::new());
+        assert!(schema.is_default());
+
+        assert!(schema
+            .validate(values(&[(DESIGNATION_FIELD, text("Relevant"))]))
+            .is_ok());
+        assert!(schema.validate(CodingValues::new()).is_err());
+        assert!(schema
+            .validate(values(&[(DESIGNATION_FIELD, text("Maybe"))]))
+            .is_err());
+        assert!(schema
+            .validate(values(&[
+                (DESIGNATION_FIELD, text("Privileged")),
+                (REASON_FIELD, text("   ")),
+            ]))
+            .is_err());
+
+        let coded = schema
+            .validate(values(&[
+                (DESIGNATION_FIELD, text("Privileged")),
+                (REASON_FIELD, text(" Attorney-client advice ")),
+            ]))
+            .unwrap();
+        assert_eq!(coded[REASON_FIELD], text("Attorney-client advice"));
+    }
+
+    #[test]
+    fn test_custom_schema_validation() {
+        let field = |key: &str, kind: FieldKind, allowed: &[&str]| CodingField {
+            key: key.to_string(),
+            label: String::new(),
+            kind,
+            allowed_values: allowed.iter().map(ToString::to_string).collect(),
+            required: false,
+            required_when: None,
+        };
+
+        assert!(CodingSchema::new(ProjectId::new(), Vec::new()).is_err());
+        assert!(
+            CodingSchema::new(ProjectId::new(), vec![field("Issue", FieldKind::Text, &[])])
+                .is_err()
+        );
+        assert!(CodingSchema::new(
+            ProjectId::new(),
+            vec![field("issues", FieldKind::MultipleChoice, &[])]
+        )
+        .is_err());
+
+        let mut notes = field("notes", FieldKind::Text, &[]);
+        notes.required_when = Some(FieldCondition {
+            field: "hot".to_string(),
+            equals: "true".to_string(),
+        });
+        let schema = CodingSchema::new(
+            ProjectId::new(),
+            vec![
+                field("issues", FieldKind::MultipleChoice, &["Fraud", "Bribery"]),
+                field("hot", FieldKind::Boolean, &[]),
+                notes,
+            ],
+        )
+        .unwrap();
+        assert_eq!(schema.field("issues").unwrap().label, "issues");
+
+        let coded = schema
+            .validate(values(&[(
+                "issues",
+                CodingValue::Choices(vec!["Bribery".to_string(), "Fraud".to_string()]),
+            )]))
+            .unwrap();
+        assert_eq!(
+            coded["issues"],
+            CodingValue::Choices(vec!["Fraud".to_string(), "Bribery".to_string()])
+        );
+        assert!(schema
+            .validate(values(&[("hot", CodingValue::Flag(true))]))
+            .is_err());
+        assert!(schema.validate(values(&[("hot", text("yes"))])).is_err());
+    }
+}
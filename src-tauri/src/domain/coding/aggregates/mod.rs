@@ -0,0 +1,5 @@
+pub mod coding_decision;
+pub mod coding_schema;
+
+pub use coding_decision::CodingDecision;
+pub use coding_schema::CodingSchema;
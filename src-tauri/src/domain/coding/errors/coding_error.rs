@@ -0,0 +1,17 @@
+use thiserror::Error;
+
+/// Errors raised by coding schemas and decisions
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum CodingError {
+    #[error("Invalid coding decision ID: {0}")]
+    InvalidId(String),
+
+    #[error("Invalid coding schema: {0}")]
+    InvalidSchema(String),
+
+    #[error("Invalid coding: {0}")]
+    InvalidDecision(String),
+
+    #[error("Invalid reviewer: {0}")]
+    InvalidReviewer(String),
+}
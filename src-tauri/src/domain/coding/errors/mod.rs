@@ -0,0 +1,3 @@
+pub mod coding_error;
+
+pub use coding_error::CodingError;
@@ -0,0 +1,12 @@
+pub mod aggregates;
+pub mod errors;
+pub mod repositories;
+pub mod value_objects;
+
+// Re-export commonly used types
+pub use aggregates::{CodingDecision, CodingSchema};
+pub use errors::CodingError;
+pub use repositories::CodingRepository;
+pub use value_objects::{
+    CodingDecisionId, CodingField, CodingValue, CodingValues, FieldCondition, FieldKind,
+};
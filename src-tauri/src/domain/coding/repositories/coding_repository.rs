@@ -0,0 +1,43 @@
+use async_trait::async_trait;
+
+use crate::domain::coding::aggregates::{CodingDecision, CodingSchema};
+use crate::domain::document::DocumentId;
+use crate::domain::project::ProjectId;
+use crate::domain::workspace::repositories::RepositoryError;
+
+/// Repository trait for coding schemas and the decisions made with them
+///
+/// Decisions are never changed: recoding a document adds a decision, and
+/// the latest one is the document's current coding.
+#[async_trait]
+pub trait CodingRepository: Send + Sync {
+    /// The schema of a project, if one was saved
+    async fn find_schema(
+        &self,
+        project_id: &ProjectId,
+    ) -> Result<Option<CodingSchema>, RepositoryError>;
+
+    /// Insert or replace the schema of a project
+    async fn save_schema(&self, schema: &CodingSchema) -> Result<(), RepositoryError>;
+
+    /// Add a decision to the history of its document
+    async fn record_decision(&self, decision: &CodingDecision) -> Result<(), RepositoryError>;
+
+    /// All decisions on a document, oldest first
+    async fn history(
+        &self,
+        document_id: &DocumentId,
+    ) -> Result<Vec<CodingDecision>, RepositoryError>;
+
+    /// All decisions on the documents of a project, oldest first
+    async fn decisions(
+        &self,
+        project_id: &ProjectId,
+    ) -> Result<Vec<CodingDecision>, RepositoryError>;
+
+    /// The latest decision on each coded document of a project
+    async fn current_decisions(
+        &self,
+        project_id: &ProjectId,
+    ) -> Result<Vec<CodingDecision>, RepositoryError>;
+}
@@ -0,0 +1,3 @@
+pub mod coding_repository;
+
+pub use coding_repository::CodingRepository;
@@ -0,0 +1,44 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use uuid::Uuid;
+
+use crate::domain::coding::errors::CodingError;
+
+/// CodingDecisionId value object identifying one coding decision
+///
+/// All coding decision identifiers use the format: decision_xxxxxxxx-xxxx-xxxx-xxxx-xxxxxxxxxxxx
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct CodingDecisionId(String);
+
+impl CodingDecisionId {
+    const PREFIX: &'static str = "decision_";
+
+    /// Create a new CodingDecisionId with a generated UUID
+    pub fn new() -> Self {
+        CodingDecisionId(format!("{}{}", Self::PREFIX, Uuid::new_v4()))
+    }
+
+    /// Create a CodingDecisionId from an existing string, validating the prefix format
+    pub fn from_string(value: String) -> Result<Self, CodingError> {
+        match value.strip_prefix(Self::PREFIX) {
+            Some(uuid_part) if uuid_part.parse::<Uuid>().is_ok() => Ok(CodingDecisionId(value)),
+            _ => Err(CodingError::InvalidId(value)),
+        }
+    }
+
+    pub fn value(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Default for CodingDecisionId {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Display for CodingDecisionId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
@@ -0,0 +1,63 @@
+use serde::{Deserialize, Serialize};
+
+/// What kind of answer a coding field takes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum FieldKind {
+    /// Exactly one of the allowed values
+    SingleChoice,
+
+    /// Any number of the allowed values
+    MultipleChoice,
+
+    /// Free text, such as a reason
+    Text,
+
+    /// Yes or no
+    Boolean,
+}
+
+impl FieldKind {
+    /// Whether the field takes values from a fixed list
+    pub fn is_choice(&self) -> bool {
+        matches!(self, FieldKind::SingleChoice | FieldKind::MultipleChoice)
+    }
+}
+
+/// Condition on another field: it has, or includes, a value
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FieldCondition {
+    pub field: String,
+
+    /// The value, or "true"/"false" for a yes/no field
+    pub equals: String,
+}
+
+/// One field of a coding schema, such as the designation of a document or
+/// the reason for it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CodingField {
+    /// Identifier used in decisions; lowercase letters, digits and
+    /// underscores
+    pub key: String,
+
+    /// Name shown to reviewers
+    pub label: String,
+
+    pub kind: FieldKind,
+
+    /// Values a choice field accepts, in display order
+    #[serde(default)]
+    pub allowed_values: Vec<String>,
+
+    /// Whether every decision must give the field
+    #[serde(default)]
+    pub required: bool,
+
+    /// Makes the field required when another field has a value, e.g. a
+    /// reason when the designation is Privileged
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub required_when: Option<FieldCondition>,
+}
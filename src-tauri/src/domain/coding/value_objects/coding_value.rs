@@ -0,0 +1,39 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Value given to one field of a coding schema
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum CodingValue {
+    /// Answer to a yes/no field
+    Flag(bool),
+
+    /// Chosen value of a single-choice field, or free text
+    Text(String),
+
+    /// Chosen values of a multiple-choice field
+    Choices(Vec<String>),
+}
+
+impl CodingValue {
+    /// Whether the value counts as not given: blank text or no choices
+    pub fn is_empty(&self) -> bool {
+        match self {
+            CodingValue::Flag(_) => false,
+            CodingValue::Text(text) => text.trim().is_empty(),
+            CodingValue::Choices(choices) => choices.is_empty(),
+        }
+    }
+
+    /// Whether the value is, or includes, `expected`
+    pub fn matches(&self, expected: &str) -> bool {
+        match self {
+            CodingValue::Flag(flag) => flag.to_string() == expected,
+            CodingValue::Text(text) => text == expected,
+            CodingValue::Choices(choices) => choices.iter().any(|choice| choice == expected),
+        }
+    }
+}
+
+/// Values of a coding decision by field key
+pub type CodingValues = BTreeMap<String, CodingValue>;
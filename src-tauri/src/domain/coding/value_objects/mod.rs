@@ -0,0 +1,7 @@
+pub mod coding_decision_id;
+pub mod coding_field;
+pub mod coding_value;
+
+pub use coding_decision_id::CodingDecisionId;
+pub use coding_field::{CodingField, FieldCondition, FieldKind};
+pub use coding_value::{CodingValue, CodingValues};
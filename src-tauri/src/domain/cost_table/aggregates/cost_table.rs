@@ -0,0 +1,321 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+use super::super::errors::CostTableError;
+use super::super::value_objects::{Amount, CostCategory, CostRow, Currency};
+use crate::domain::det::PmNode;
+use crate::domain::export::CitedSource;
+use crate::domain::report::CitationLocator;
+
+/// Structured cost analysis stored as a `cost-table.det` derivative
+///
+/// Business Rules:
+/// - A table has a title and optionally a purpose, which names the file
+///   `cost-table-<purpose>.det` so one document can have several tables
+/// - Every row has a date, description, standardized category, amount and
+///   currency, and may cite the document it was taken from
+/// - Amounts in different currencies are never added together; totals
+///   are kept per category and currency
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CostTable {
+    title: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    purpose: Option<String>,
+    #[serde(default)]
+    rows: Vec<CostRow>,
+}
+
+/// Sum of the costs of one category (or of all, for grand totals) in one
+/// currency
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CostTotal {
+    /// None for a grand total over all categories
+    pub category: Option<CostCategory>,
+    pub currency: Currency,
+    pub amount: Amount,
+    pub row_count: usize,
+}
+
+impl CostTable {
+    /// File name prefix of cost table derivatives
+    pub const FILE_PREFIX: &'static str = "cost-table";
+
+    pub fn new(title: &str, purpose: Option<&str>) -> Result<Self, CostTableError> {
+        let table = CostTable {
+            title: title.trim().to_string(),
+            purpose: purpose
+                .map(|p| p.trim().to_string())
+                .filter(|p| !p.is_empty()),
+            rows: Vec::new(),
+        };
+        table.validate()?;
+        Ok(table)
+    }
+
+    pub fn with_rows(mut self, rows: Vec<CostRow>) -> Self {
+        self.rows = rows;
+        self
+    }
+
+    /// Read the structured payload of a cost table `.det`
+    pub fn from_value(value: Value) -> Result<Self, CostTableError> {
+        let table: CostTable =
+            serde_json::from_value(value).map_err(|e| CostTableError::Invalid(e.to_string()))?;
+        table.validate()?;
+        Ok(table)
+    }
+
+    pub fn to_value(&self) -> Value {
+        serde_json::to_value(self).unwrap_or(Value::Null)
+    }
+
+    /// Check the title and every row, naming the first bad row
+    pub fn validate(&self) -> Result<(), CostTableError> {
+        if self.title.trim().is_empty() {
+            return Err(CostTableError::Invalid("the title is empty".to_string()));
+        }
+        if self
+            .purpose
+            .as_deref()
+            .is_some_and(|p| Self::slug(p).is_empty())
+        {
+            return Err(CostTableError::Invalid(
+                "the purpose needs at least one letter or digit".to_string(),
+            ));
+        }
+        for (index, row) in self.rows.iter().enumerate() {
+            row.validate()
+                .map_err(|e| CostTableError::row(index + 1, e))?;
+        }
+        Ok(())
+    }
+
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+
+    pub fn purpose(&self) -> Option<&str> {
+        self.purpose.as_deref()
+    }
+
+    pub fn rows(&self) -> &[CostRow] {
+        &self.rows
+    }
+
+    pub fn add_row(&mut self, row: CostRow) {
+        self.rows.push(row);
+    }
+
+    /// File name in the document family: `cost-table.det`, or
+    /// `cost-table-<purpose>.det` when a purpose is set
+    pub fn file_name(&self) -> String {
+        Self::file_name_for(self.purpose())
+    }
+
+    /// File name of the table with the given purpose
+    pub fn file_name_for(purpose: Option<&str>) -> String {
+        match purpose.map(Self::slug).filter(|slug| !slug.is_empty()) {
+            Some(slug) => format!("{}-{}.det", Self::FILE_PREFIX, slug),
+            None => format!("{}.det", Self::FILE_PREFIX),
+        }
+    }
+
+    fn slug(value: &str) -> String {
+        value
+            .to_lowercase()
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|part| !part.is_empty())
+            .collect::<Vec<_>>()
+            .join("-")
+    }
+
+    /// Totals per category and currency, in category order
+    pub fn totals(&self) -> Vec<CostTotal> {
+        self.sum_by(|row| Some(row.category()))
+    }
+
+    /// Totals over all categories, one per currency
+    pub fn grand_totals(&self) -> Vec<CostTotal> {
+        self.sum_by(|_| None)
+    }
+
+    fn sum_by(&self, key: impl Fn(&CostRow) -> Option<CostCategory>) -> Vec<CostTotal> {
+        let mut sums: BTreeMap<(Option<CostCategory>, Currency), (Amount, usize)> = BTreeMap::new();
+        for row in &self.rows {
+            let entry = sums
+                .entry((key(row), row.currency().clone()))
+                .or_insert((Amount::ZERO, 0));
+            entry.0 = entry.0 + row.amount();
+            entry.1 += 1;
+        }
+
+        sums.into_iter()
+            .map(|((category, currency), (amount, row_count))| CostTotal {
+                category,
+                currency,
+                amount,
+                row_count,
+            })
+            .collect()
+    }
+
+    /// Editor content showing the table and its totals
+    ///
+    /// The structured rows remain the source of truth; this is regenerated
+    /// on every save. Cited sources carry citation marks so exports list them.
+    pub fn render(&self) -> PmNode {
+        let cell = |text: String| {
+            PmNode::table_cell(vec![PmNode::paragraph(PmNode::text_nodes(
+                &text,
+                Vec::new(),
+            ))])
+        };
+        let header = |labels: &[&str]| {
+            PmNode::table_row(
+                labels
+                    .iter()
+                    .map(|label| {
+                        PmNode::table_header(vec![PmNode::paragraph(PmNode::text_nodes(
+                            label,
+                            Vec::new(),
+                        ))])
+                    })
+                    .collect(),
+            )
+        };
+
+        let mut rows = vec![header(&[
+            "Date",
+            "Description",
+            "Category",
+            "Amount",
+            "Currency",
+            "Source",
+        ])];
+        for row in &self.rows {
+            let source = row.source().map(|citation| {
+                let mut cited = CitedSource::new(citation.path());
+                if let Some(id) = citation.document_id() {
+                    cited = cited.with_document_id(id.value());
+                }
+                if let CitationLocator::Page { page } = citation.locator() {
+                    cited = cited.with_page(page);
+                }
+                PmNode::text(cited.display_label(), vec![cited.to_mark()])
+            });
+
+            rows.push(PmNode::table_row(vec![
+                cell(row.date().to_string()),
+                cell(row.description().to_string()),
+                cell(row.category().label().to_string()),
+                cell(row.amount().to_string()),
+                cell(row.currency().to_string()),
+                PmNode::table_cell(vec![PmNode::paragraph(source.into_iter().collect())]),
+            ]));
+        }
+
+        let mut summary = vec![header(&["Category", "Currency", "Total"])];
+        for total in self.totals().iter().chain(self.grand_totals().iter()) {
+            let label = total.category.map_or("All categories", |c| c.label());
+            summary.push(PmNode::table_row(vec![
+                cell(label.to_string()),
+                cell(total.currency.to_string()),
+                cell(total.amount.to_string()),
+            ]));
+        }
+
+        PmNode::doc(vec![
+            PmNode::heading(
+                1,
+                PmNode::text_nodes(&format!("Cost Analysis: {}", self.title), Vec::new()),
+            ),
+            PmNode::table(rows),
+            PmNode::heading(2, PmNode::text_nodes("Summary", Vec::new())),
+            PmNode::table(summary),
+        ])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::det::DetSchema;
+    use crate::domain::report::Citation;
+    use chrono::NaiveDate;
+
+    fn row(category: CostCategory, amount: &str, currency: &str) -> CostRow {
+        CostRow::new(
+            NaiveDate::from_ymd_opt(2024, 3, 1).unwrap(),
+            "Expert fee",
+            category,
+            Amount::parse(amount).unwrap(),
+            Currency::parse(currency).unwrap(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_totals_by_category_and_currency() {
+        let table = CostTable::new("Damages", Some("Damages 2024"))
+            .unwrap()
+            .with_rows(vec![
+                row(CostCategory::Direct, "100.10", "EUR"),
+                row(CostCategory::Indirect, "50", "EUR"),
+                row(CostCategory::Direct, "0.20", "eur"),
+                row(CostCategory::Direct, "10", "USD"),
+            ]);
+
+        assert_eq!(table.file_name(), "cost-table-damages-2024.det");
+        let totals: Vec<(Option<CostCategory>, String, String)> = table
+            .totals()
+            .into_iter()
+            .map(|t| (t.category, t.currency.to_string(), t.amount.to_string()))
+            .collect();
+        assert_eq!(
+            totals,
+            vec![
+                (Some(CostCategory::Direct), "EUR".into(), "100.30".into()),
+                (Some(CostCategory::Direct), "USD".into(), "10.00".into()),
+                (Some(CostCategory::Indirect), "EUR".into(), "50.00".into()),
+            ]
+        );
+        assert_eq!(table.grand_totals()[0].amount, Amount::from_cents(15_030));
+        assert_eq!(table.grand_totals()[0].row_count, 3);
+    }
+
+    #[test]
+    fn test_round_trips_and_renders_valid_content() {
+        let source = Citation::new(
+            None,
+            "/corpus/invoice.pdf".to_string(),
+            CitationLocator::Page { page: 2 },
+        )
+        .unwrap();
+        let table = CostTable::new("Litigation", None)
+            .unwrap()
+            .with_rows(vec![
+                row(CostCategory::Contingent, "12.5", "GBP").with_source(source)
+            ]);
+        assert_eq!(table.file_name(), "cost-table.det");
+
+        let value = table.to_value();
+        assert_eq!(value["rows"][0]["amount"], "12.50");
+        assert_eq!(value["rows"][0]["source"]["locator"]["page"], 2);
+        assert_eq!(CostTable::from_value(value).unwrap(), table);
+
+        let content = table.render();
+        DetSchema::tiptap().validate(&content).unwrap();
+        assert!(content.plain_text().contains("invoice.pdf, p. 2"));
+
+        let mut broken = table.to_value();
+        broken["rows"][0]["description"] = " ".into();
+        assert!(matches!(
+            CostTable::from_value(broken),
+            Err(CostTableError::InvalidRow { row: 1, .. })
+        ));
+        assert!(CostTable::new(" ", None).is_err());
+    }
+}
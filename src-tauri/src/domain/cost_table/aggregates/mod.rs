@@ -0,0 +1,3 @@
+pub mod cost_table;
+
+pub use cost_table::{CostTable, CostTotal};
@@ -0,0 +1,155 @@
+use super::aggregates::CostTable;
+use super::errors::CostTableError;
+use super::value_objects::{Amount, CostCategory, CostRow, CostTableFormat, Currency};
+use crate::domain::document::DocumentId;
+use crate::domain::report::{Citation, CitationLocator};
+
+/// Column headers of imported and exported spreadsheets, in order
+pub const COST_TABLE_COLUMNS: [&str; 8] = [
+    "date",
+    "description",
+    "category",
+    "amount",
+    "currency",
+    "source_path",
+    "source_document_id",
+    "source_page",
+];
+
+/// Columns every imported file must have
+const REQUIRED_COLUMNS: usize = 5;
+
+/// Reads and writes cost table rows in one spreadsheet format
+///
+/// Implementations work on bytes only. Columns are those of
+/// `COST_TABLE_COLUMNS`, matched by header name on import so accountants
+/// may reorder them or add columns of their own.
+pub trait CostTableCodec: Send + Sync {
+    fn format(&self) -> CostTableFormat;
+
+    /// Write the rows, followed by totals where the format allows
+    fn encode(&self, table: &CostTable) -> Result<Vec<u8>, CostTableError>;
+
+    /// Read the rows of a file
+    fn decode(&self, bytes: &[u8]) -> Result<Vec<CostRow>, CostTableError>;
+}
+
+/// Cells of a row, in `COST_TABLE_COLUMNS` order
+pub fn row_to_record(row: &CostRow) -> Vec<String> {
+    let source = row.source();
+    let page = source.and_then(|citation| match citation.locator() {
+        CitationLocator::Page { page } => Some(page.to_string()),
+        _ => None,
+    });
+
+    vec![
+        row.date().to_string(),
+        row.description().to_string(),
+        row.category().as_str().to_string(),
+        row.amount().to_string(),
+        row.currency().to_string(),
+        source.map(|c| c.path().to_string()).unwrap_or_default(),
+        source
+            .and_then(|c| c.document_id())
+            .map(|id| id.value().to_string())
+            .unwrap_or_default(),
+        page.unwrap_or_default(),
+    ]
+}
+
+/// Where each known column is in an imported file
+#[derive(Debug, Clone)]
+pub struct ColumnLayout {
+    indices: [Option<usize>; 8],
+}
+
+impl ColumnLayout {
+    /// Match a header row against `COST_TABLE_COLUMNS`
+    ///
+    /// Headers are compared case-insensitively with spaces read as
+    /// underscores, so "Source Path" matches `source_path`.
+    pub fn from_header(header: &[String], format: CostTableFormat) -> Result<Self, CostTableError> {
+        let normalized: Vec<String> = header
+            .iter()
+            .map(|h| h.trim().to_lowercase().replace([' ', '-'], "_"))
+            .collect();
+        let mut indices = [None; 8];
+        for (slot, column) in indices.iter_mut().zip(COST_TABLE_COLUMNS) {
+            *slot = normalized.iter().position(|h| h == column);
+        }
+
+        let missing: Vec<&str> = COST_TABLE_COLUMNS[..REQUIRED_COLUMNS]
+            .iter()
+            .zip(&indices)
+            .filter(|(_, index)| index.is_none())
+            .map(|(column, _)| *column)
+            .collect();
+        if !missing.is_empty() {
+            return Err(CostTableError::invalid_file(
+                format.as_str(),
+                format!("missing column(s) {}", missing.join(", ")),
+            ));
+        }
+        Ok(ColumnLayout { indices })
+    }
+
+    /// Position of a column of `COST_TABLE_COLUMNS` in the file
+    pub fn index_of(&self, column: &str) -> Option<usize> {
+        COST_TABLE_COLUMNS
+            .iter()
+            .position(|c| *c == column)
+            .and_then(|i| self.indices[i])
+    }
+
+    /// Read one data row; blank rows give `None`
+    ///
+    /// `row` is the 1-based row number in the file, for error messages.
+    pub fn read_row(
+        &self,
+        cells: &[String],
+        row: usize,
+    ) -> Result<Option<CostRow>, CostTableError> {
+        if cells.iter().all(|cell| cell.trim().is_empty()) {
+            return Ok(None);
+        }
+        let cell = |column: &str| {
+            self.index_of(column)
+                .and_then(|i| cells.get(i))
+                .map(|value| value.trim())
+                .unwrap_or("")
+        };
+        let at_row = |e: CostTableError| CostTableError::row(row, e);
+
+        let mut cost = CostRow::new(
+            CostRow::parse_date(cell("date")).map_err(at_row)?,
+            cell("description"),
+            CostCategory::parse(cell("category")).map_err(at_row)?,
+            Amount::parse(cell("amount")).map_err(at_row)?,
+            Currency::parse(cell("currency")).map_err(at_row)?,
+        )
+        .map_err(at_row)?;
+
+        let path = cell("source_path");
+        if !path.is_empty() {
+            let document_id = match cell("source_document_id") {
+                "" => None,
+                id => Some(
+                    DocumentId::from_string(id.to_string())
+                        .map_err(|e| CostTableError::row(row, e))?,
+                ),
+            };
+            let locator = match cell("source_page") {
+                "" => CitationLocator::File,
+                page => CitationLocator::Page {
+                    page: page.parse().map_err(|_| {
+                        CostTableError::row(row, format!("invalid source page '{}'", page))
+                    })?,
+                },
+            };
+            let citation = Citation::new(document_id, path.to_string(), locator)
+                .map_err(|e| CostTableError::row(row, e))?;
+            cost = cost.with_source(citation);
+        }
+        Ok(Some(cost))
+    }
+}
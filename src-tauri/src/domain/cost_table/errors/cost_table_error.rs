@@ -0,0 +1,47 @@
+use thiserror::Error;
+
+/// Invalid cost table data, or a file that cannot be read as one
+#[derive(Debug, Error)]
+pub enum CostTableError {
+    #[error("Invalid amount '{0}'")]
+    InvalidAmount(String),
+
+    #[error("Invalid currency '{0}'; use a three-letter ISO 4217 code")]
+    InvalidCurrency(String),
+
+    #[error("Unknown cost category '{0}'")]
+    UnknownCategory(String),
+
+    #[error("Invalid date '{0}'; use YYYY-MM-DD")]
+    InvalidDate(String),
+
+    #[error("Invalid cost table: {0}")]
+    Invalid(String),
+
+    #[error("Row {row}: {reason}")]
+    InvalidRow { row: usize, reason: String },
+
+    #[error("Unsupported cost table format '{0}'")]
+    UnsupportedFormat(String),
+
+    #[error("The {format} file cannot be read: {reason}")]
+    InvalidFile { format: String, reason: String },
+}
+
+impl CostTableError {
+    /// Create an InvalidRow error, counting rows from 1
+    pub fn row(row: usize, reason: impl ToString) -> Self {
+        CostTableError::InvalidRow {
+            row,
+            reason: reason.to_string(),
+        }
+    }
+
+    /// Create an InvalidFile error for a format
+    pub fn invalid_file(format: impl Into<String>, reason: impl ToString) -> Self {
+        CostTableError::InvalidFile {
+            format: format.into(),
+            reason: reason.to_string(),
+        }
+    }
+}
@@ -0,0 +1,3 @@
+pub mod cost_table_error;
+
+pub use cost_table_error::CostTableError;
@@ -0,0 +1,10 @@
+pub mod aggregates;
+pub mod codec;
+pub mod errors;
+pub mod value_objects;
+
+// Re-export commonly used types
+pub use aggregates::{CostTable, CostTotal};
+pub use codec::{row_to_record, ColumnLayout, CostTableCodec, COST_TABLE_COLUMNS};
+pub use errors::CostTableError;
+pub use value_objects::{Amount, CostCategory, CostRow, CostTableFormat, Currency};
@@ -0,0 +1,169 @@
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::ops::Add;
+
+use crate::domain::cost_table::errors::CostTableError;
+
+/// An exact money amount in hundredths of the currency unit
+///
+/// Amounts are kept as whole cents so totals never pick up floating point
+/// error; more than two decimals is rejected rather than rounded.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Amount(i64);
+
+impl Amount {
+    pub const ZERO: Amount = Amount(0);
+
+    pub fn from_cents(cents: i64) -> Self {
+        Amount(cents)
+    }
+
+    pub fn cents(&self) -> i64 {
+        self.0
+    }
+
+    /// Parse an amount as accountants write it
+    ///
+    /// Accepts "1234.5", "-12.30", "1,234.56", "1.234,56", "12,50" and
+    /// "(12.50)" for negatives. With both separators present the last one
+    /// is the decimal separator; a lone comma is decimal when followed by
+    /// one or two digits.
+    pub fn parse(value: &str) -> Result<Self, CostTableError> {
+        let invalid = || CostTableError::InvalidAmount(value.trim().to_string());
+
+        let mut text: String = value
+            .chars()
+            .filter(|c| !c.is_whitespace() && *c != '\'')
+            .collect();
+        let mut negative = false;
+        if text.starts_with('(') && text.ends_with(')') {
+            negative = true;
+            text = text[1..text.len() - 1].to_string();
+        }
+        if let Some(rest) = text.strip_prefix('-') {
+            negative = !negative;
+            text = rest.to_string();
+        } else if let Some(rest) = text.strip_prefix('+') {
+            text = rest.to_string();
+        }
+
+        let decimal_separator = match (text.rfind('.'), text.rfind(',')) {
+            (Some(dot), Some(comma)) => Some(dot.max(comma)),
+            (Some(dot), None) if text.matches('.').count() == 1 => Some(dot),
+            (None, Some(comma)) if text.matches(',').count() == 1 && text.len() - comma <= 3 => {
+                Some(comma)
+            }
+            _ => None,
+        };
+        let (whole, fraction) = match decimal_separator {
+            Some(index) => (&text[..index], &text[index + 1..]),
+            None => (text.as_str(), ""),
+        };
+        let whole: String = whole.chars().filter(|c| *c != ',' && *c != '.').collect();
+
+        if whole.is_empty() && fraction.is_empty()
+            || !whole.chars().all(|c| c.is_ascii_digit())
+            || !fraction.chars().all(|c| c.is_ascii_digit())
+            || fraction.len() > 2
+        {
+            return Err(invalid());
+        }
+
+        let units: i64 = if whole.is_empty() {
+            0
+        } else {
+            whole.parse().map_err(|_| invalid())?
+        };
+        let cents: i64 = format!("{:0<2}", fraction).parse().map_err(|_| invalid())?;
+        let total = units
+            .checked_mul(100)
+            .and_then(|u| u.checked_add(cents))
+            .ok_or_else(invalid)?;
+
+        Ok(Amount(if negative { -total } else { total }))
+    }
+
+    /// Amount of a spreadsheet number, rounded to cents
+    pub fn from_f64(value: f64) -> Result<Self, CostTableError> {
+        let cents = (value * 100.0).round();
+        if !cents.is_finite() || cents.abs() > i64::MAX as f64 {
+            return Err(CostTableError::InvalidAmount(value.to_string()));
+        }
+        Ok(Amount(cents as i64))
+    }
+
+    /// Amount as a spreadsheet number
+    pub fn to_f64(&self) -> f64 {
+        self.0 as f64 / 100.0
+    }
+}
+
+impl Add for Amount {
+    type Output = Amount;
+
+    fn add(self, other: Amount) -> Amount {
+        Amount(self.0.saturating_add(other.0))
+    }
+}
+
+impl fmt::Display for Amount {
+    /// Plain decimal with two places, e.g. "-1234.50"
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let sign = if self.0 < 0 { "-" } else { "" };
+        let cents = self.0.unsigned_abs();
+        write!(f, "{}{}.{:02}", sign, cents / 100, cents % 100)
+    }
+}
+
+impl Serialize for Amount {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for Amount {
+    /// Reads the string form, or a JSON number as the editor may send
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Raw {
+            Text(String),
+            Number(f64),
+        }
+
+        match Raw::deserialize(deserializer)? {
+            Raw::Text(text) => Amount::parse(&text),
+            Raw::Number(number) => Amount::from_f64(number),
+        }
+        .map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parses_common_notations() {
+        let cents = |value: &str| Amount::parse(value).unwrap().cents();
+        assert_eq!(cents("1234.5"), 123_450);
+        assert_eq!(cents("-12.30"), -1_230);
+        assert_eq!(cents("1,234.56"), 123_456);
+        assert_eq!(cents("1.234,56"), 123_456);
+        assert_eq!(cents("12,50"), 1_250);
+        assert_eq!(cents("1,234"), 123_400);
+        assert_eq!(cents("1.234.567"), 123_456_700);
+        assert_eq!(cents("(12.50)"), -1_250);
+        assert_eq!(cents(".5"), 50);
+
+        assert!(Amount::parse("12.345").is_err());
+        assert!(Amount::parse("abc").is_err());
+        assert!(Amount::parse("").is_err());
+
+        assert_eq!(Amount::from_cents(-5).to_string(), "-0.05");
+        assert_eq!(
+            serde_json::from_str::<Amount>("19.99").unwrap(),
+            Amount::from_cents(1_999)
+        );
+    }
+}
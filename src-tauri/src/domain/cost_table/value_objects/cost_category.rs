@@ -0,0 +1,61 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use crate::domain::cost_table::errors::CostTableError;
+
+/// Standardized cost category of a cost table row
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CostCategory {
+    /// Costs attributable to the matter itself
+    Direct,
+    /// Overheads and shared costs allocated to the matter
+    Indirect,
+    /// Costs that depend on a future event
+    Contingent,
+    Other,
+}
+
+impl CostCategory {
+    pub const ALL: [CostCategory; 4] = [
+        CostCategory::Direct,
+        CostCategory::Indirect,
+        CostCategory::Contingent,
+        CostCategory::Other,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CostCategory::Direct => "direct",
+            CostCategory::Indirect => "indirect",
+            CostCategory::Contingent => "contingent",
+            CostCategory::Other => "other",
+        }
+    }
+
+    /// Heading used in rendered tables and exports
+    pub fn label(&self) -> &'static str {
+        match self {
+            CostCategory::Direct => "Direct costs",
+            CostCategory::Indirect => "Indirect costs",
+            CostCategory::Contingent => "Contingent costs",
+            CostCategory::Other => "Other costs",
+        }
+    }
+
+    /// Parse a category name or label, e.g. "direct" or "Direct costs"
+    pub fn parse(value: &str) -> Result<Self, CostTableError> {
+        let normalized = value.trim().to_lowercase().replace(['_', '-'], " ");
+        let name = normalized.strip_suffix(" costs").unwrap_or(&normalized);
+        CostCategory::ALL
+            .into_iter()
+            .find(|category| category.as_str() == name.trim())
+            .ok_or_else(|| CostTableError::UnknownCategory(value.trim().to_string()))
+    }
+}
+
+impl fmt::Display for CostCategory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
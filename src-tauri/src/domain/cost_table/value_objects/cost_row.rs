@@ -0,0 +1,92 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+use super::amount::Amount;
+use super::cost_category::CostCategory;
+use super::currency::Currency;
+use crate::domain::cost_table::errors::CostTableError;
+use crate::domain::report::Citation;
+
+/// One cost: what was spent, when, on what, and where it is evidenced
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CostRow {
+    date: NaiveDate,
+    description: String,
+    category: CostCategory,
+    amount: Amount,
+    currency: Currency,
+    /// The invoice, receipt or statement the cost is taken from
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    source: Option<Citation>,
+}
+
+impl CostRow {
+    pub fn new(
+        date: NaiveDate,
+        description: impl Into<String>,
+        category: CostCategory,
+        amount: Amount,
+        currency: Currency,
+    ) -> Result<Self, CostTableError> {
+        let row = CostRow {
+            date,
+            description: description.into().trim().to_string(),
+            category,
+            amount,
+            currency,
+            source: None,
+        };
+        row.validate()?;
+        Ok(row)
+    }
+
+    pub fn with_source(mut self, source: Citation) -> Self {
+        self.source = Some(source);
+        self
+    }
+
+    /// Parse a date written as YYYY-MM-DD, ignoring any time part
+    pub fn parse_date(value: &str) -> Result<NaiveDate, CostTableError> {
+        let value = value.trim();
+        value
+            .get(..10)
+            .and_then(|day| NaiveDate::parse_from_str(day, "%Y-%m-%d").ok())
+            .filter(|_| value.len() == 10 || value[10..].starts_with(['T', ' ']))
+            .ok_or_else(|| CostTableError::InvalidDate(value.to_string()))
+    }
+
+    /// Check rules that deserialized rows bypass
+    pub fn validate(&self) -> Result<(), CostTableError> {
+        if self.description.trim().is_empty() {
+            return Err(CostTableError::Invalid(
+                "every cost needs a description".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    pub fn date(&self) -> NaiveDate {
+        self.date
+    }
+
+    pub fn description(&self) -> &str {
+        &self.description
+    }
+
+    pub fn category(&self) -> CostCategory {
+        self.category
+    }
+
+    pub fn amount(&self) -> Amount {
+        self.amount
+    }
+
+    pub fn currency(&self) -> &Currency {
+        &self.currency
+    }
+
+    pub fn source(&self) -> Option<&Citation> {
+        self.source.as_ref()
+    }
+}
@@ -0,0 +1,43 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use crate::domain::cost_table::errors::CostTableError;
+
+/// Spreadsheet format cost tables are imported from and exported to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CostTableFormat {
+    Csv,
+    Xlsx,
+}
+
+impl CostTableFormat {
+    pub const ALL: [CostTableFormat; 2] = [CostTableFormat::Csv, CostTableFormat::Xlsx];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CostTableFormat::Csv => "csv",
+            CostTableFormat::Xlsx => "xlsx",
+        }
+    }
+
+    /// File extension, without the dot
+    pub fn extension(&self) -> &'static str {
+        self.as_str()
+    }
+
+    /// Parse a format name or file extension
+    pub fn parse(value: &str) -> Result<Self, CostTableError> {
+        match value.trim().trim_start_matches('.').to_lowercase().as_str() {
+            "csv" => Ok(CostTableFormat::Csv),
+            "xlsx" | "excel" => Ok(CostTableFormat::Xlsx),
+            other => Err(CostTableError::UnsupportedFormat(other.to_string())),
+        }
+    }
+}
+
+impl fmt::Display for CostTableFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
@@ -0,0 +1,11 @@
+pub mod amount;
+pub mod cost_category;
+pub mod cost_row;
+pub mod cost_table_format;
+pub mod currency;
+
+pub use amount::Amount;
+pub use cost_category::CostCategory;
+pub use cost_row::CostRow;
+pub use cost_table_format::CostTableFormat;
+pub use currency::Currency;
@@ -0,0 +1,246 @@
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::domain::det::errors::DetError;
+use crate::domain::det::schema::DetSchema;
+use crate::domain::det::value_objects::{PmNode, ProcessingMetadata, SourceReference};
+
+/// Schema version written by this build
+pub const CURRENT_SCHEMA_VERSION: u32 = 1;
+
+/// Value of the `format` field that marks a `.det` envelope
+const DET_FORMAT: &str = "det";
+
+/// What a `.det` document is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum DetKind {
+    /// Base extraction of an original (`extracted.det`)
+    Extracted,
+    Summary,
+    Anonymized,
+    CostTable,
+    Explanatory,
+    Report,
+    Custom,
+}
+
+impl DetKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DetKind::Extracted => "extracted",
+            DetKind::Summary => "summary",
+            DetKind::Anonymized => "anonymized",
+            DetKind::CostTable => "cost-table",
+            DetKind::Explanatory => "explanatory",
+            DetKind::Report => "report",
+            DetKind::Custom => "custom",
+        }
+    }
+}
+
+/// A `.det` file: editor content in a versioned envelope
+///
+/// The envelope records what the document is, which files it was made
+/// from and how it was produced. Fields written by newer builds or other
+/// tools are kept in `extra` so a load/save round trip does not lose them.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DetDocument {
+    format: String,
+    schema_version: u32,
+    kind: DetKind,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    title: Option<String>,
+
+    #[serde(default)]
+    sources: Vec<SourceReference>,
+
+    processing: ProcessingMetadata,
+
+    /// The `doc` node edited in TipTap
+    content: PmNode,
+
+    /// Structured payload of typed derivatives such as cost tables
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    data: Option<Value>,
+
+    #[serde(flatten)]
+    extra: Map<String, Value>,
+}
+
+impl DetDocument {
+    /// Create a document of the current schema version
+    pub fn new(kind: DetKind, content: PmNode, processing: ProcessingMetadata) -> Self {
+        DetDocument {
+            format: DET_FORMAT.to_string(),
+            schema_version: CURRENT_SCHEMA_VERSION,
+            kind,
+            title: None,
+            sources: Vec::new(),
+            processing,
+            content,
+            data: None,
+            extra: Map::new(),
+        }
+    }
+
+    pub fn with_title(mut self, title: impl Into<String>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    pub fn with_source(mut self, source: SourceReference) -> Self {
+        self.sources.push(source);
+        self
+    }
+
+    pub fn with_data(mut self, data: Value) -> Self {
+        self.data = Some(data);
+        self
+    }
+
+    /// Read a document from envelope JSON of the current schema version
+    ///
+    /// Older versions must be upgraded with `DetMigrator` first.
+    pub fn from_json(value: Value) -> Result<Self, DetError> {
+        if value.get("format").and_then(Value::as_str) != Some(DET_FORMAT) {
+            return Err(DetError::InvalidEnvelope(
+                "missing \"format\": \"det\"".to_string(),
+            ));
+        }
+        let document: DetDocument =
+            serde_json::from_value(value).map_err(|e| DetError::InvalidEnvelope(e.to_string()))?;
+        if document.schema_version != CURRENT_SCHEMA_VERSION {
+            return Err(DetError::UnsupportedVersion {
+                found: document.schema_version,
+                supported: CURRENT_SCHEMA_VERSION,
+            });
+        }
+        Ok(document)
+    }
+
+    pub fn to_json(&self) -> Result<Value, DetError> {
+        serde_json::to_value(self).map_err(|e| DetError::InvalidEnvelope(e.to_string()))
+    }
+
+    pub fn schema_version(&self) -> u32 {
+        self.schema_version
+    }
+
+    pub fn kind(&self) -> DetKind {
+        self.kind
+    }
+
+    pub fn title(&self) -> Option<&str> {
+        self.title.as_deref()
+    }
+
+    pub fn sources(&self) -> &[SourceReference] {
+        &self.sources
+    }
+
+    /// The first source, which for derivatives is the original
+    pub fn primary_source(&self) -> Option<&SourceReference> {
+        self.sources.first()
+    }
+
+    pub fn processing(&self) -> &ProcessingMetadata {
+        &self.processing
+    }
+
+    pub fn content(&self) -> &PmNode {
+        &self.content
+    }
+
+    pub fn data(&self) -> Option<&Value> {
+        self.data.as_ref()
+    }
+
+    /// Envelope fields this build does not know about
+    pub fn extra(&self) -> &Map<String, Value> {
+        &self.extra
+    }
+
+    /// Replace the content after an edit
+    pub fn set_content(&mut self, content: PmNode) {
+        self.content = content;
+        self.processing.updated_at = Utc::now();
+    }
+
+    /// Replace the structured payload after an edit
+    pub fn set_data(&mut self, data: Option<Value>) {
+        self.data = data;
+        self.processing.updated_at = Utc::now();
+    }
+
+    pub fn set_title(&mut self, title: Option<String>) {
+        self.title = title;
+        self.processing.updated_at = Utc::now();
+    }
+
+    /// Check the content against an editor schema
+    pub fn validate(&self, schema: &DetSchema) -> Result<(), DetError> {
+        schema.validate(&self.content)
+    }
+
+    /// Plain text of the content, for search and snippets
+    pub fn plain_text(&self) -> String {
+        self.content.plain_text()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_test_document() -> DetDocument {
+        DetDocument::new(
+            DetKind::Extracted,
+            PmNode::doc(vec![PmNode::paragraph(PmNode::text_nodes(
+                "Invoice",
+                Vec::new(),
+            ))]),
+            ProcessingMetadata::completed("pdf-text-v1").with_quality(0.95, "good"),
+        )
+        .with_source(
+            SourceReference::new("/corpus/invoice.pdf")
+                .with_document_id("document_1")
+                .with_content_hash("blake3:abc"),
+        )
+    }
+
+    #[test]
+    fn test_json_round_trip_keeps_unknown_fields() {
+        let document = create_test_document();
+        let mut json = document.to_json().unwrap();
+        assert_eq!(json["format"], "det");
+        assert_eq!(json["schemaVersion"], 1);
+        assert_eq!(json["kind"], "extracted");
+        assert_eq!(json["content"]["type"], "doc");
+
+        json["reviewState"] = serde_json::json!({ "reviewer": "kim" });
+        let loaded = DetDocument::from_json(json.clone()).unwrap();
+        assert_eq!(loaded.extra()["reviewState"]["reviewer"], "kim");
+        assert_eq!(loaded.to_json().unwrap(), json);
+        assert_eq!(loaded.primary_source().unwrap().path, "/corpus/invoice.pdf");
+    }
+
+    #[test]
+    fn test_from_json_rejects_other_versions_and_bare_docs() {
+        let mut json = create_test_document().to_json().unwrap();
+        json["schemaVersion"] = 2.into();
+        assert!(matches!(
+            DetDocument::from_json(json),
+            Err(DetError::UnsupportedVersion { found: 2, .. })
+        ));
+
+        let bare = serde_json::json!({ "type": "doc", "content": [] });
+        assert!(matches!(
+            DetDocument::from_json(bare),
+            Err(DetError::InvalidEnvelope(_))
+        ));
+    }
+}
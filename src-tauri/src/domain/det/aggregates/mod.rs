@@ -0,0 +1,3 @@
+pub mod det_document;
+
+pub use det_document::{DetDocument, DetKind, CURRENT_SCHEMA_VERSION};
@@ -0,0 +1,49 @@
+use std::fmt;
+use thiserror::Error;
+
+/// One place where a `.det` document breaks the editor schema
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SchemaViolation {
+    /// Location of the node, e.g. `doc.content[2].content[0]`
+    pub path: String,
+    pub message: String,
+}
+
+impl SchemaViolation {
+    pub fn new(path: impl Into<String>, message: impl Into<String>) -> Self {
+        SchemaViolation {
+            path: path.into(),
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for SchemaViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.path, self.message)
+    }
+}
+
+/// Errors raised while reading, validating or writing `.det` files
+#[derive(Debug, Error)]
+pub enum DetError {
+    #[error("Cannot access .det file: {0}")]
+    Io(String),
+
+    #[error("The .det file is not valid JSON: {0}")]
+    Parse(String),
+
+    #[error("The .det file has an invalid envelope: {0}")]
+    InvalidEnvelope(String),
+
+    #[error(
+        "The .det file uses schema version {found}, but at most version {supported} is supported"
+    )]
+    UnsupportedVersion { found: u32, supported: u32 },
+
+    #[error("Migrating the .det file from schema version {from} failed: {reason}")]
+    Migration { from: u32, reason: String },
+
+    #[error("The document does not match the editor schema ({} problems, first: {})", .0.len(), .0.first().map(ToString::to_string).unwrap_or_default())]
+    SchemaViolations(Vec<SchemaViolation>),
+}
@@ -0,0 +1,3 @@
+pub mod det_error;
+
+pub use det_error::{DetError, SchemaViolation};
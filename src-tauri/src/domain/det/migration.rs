@@ -0,0 +1,290 @@
+//! Upgrades of `.det` files written with older schema versions
+//!
+//! Each migration takes the raw JSON of one schema version and returns the
+//! JSON of the next. `DetMigrator` chains them until the current version is
+//! reached, so a file from any older build can be loaded. When the envelope
+//! or content schema changes, bump `CURRENT_SCHEMA_VERSION` and register a
+//! migration from the previous version.
+
+use std::sync::Arc;
+
+use chrono::Utc;
+use serde_json::{json, Map, Value};
+
+use super::aggregates::{DetKind, CURRENT_SCHEMA_VERSION};
+use super::errors::DetError;
+
+/// Upgrade from one schema version to the next
+pub trait DetMigration: Send + Sync {
+    /// Version this migration reads; it produces `source_version() + 1`
+    fn source_version(&self) -> u32;
+
+    fn migrate(&self, value: Value) -> Result<Value, DetError>;
+}
+
+/// Applies registered migrations in order
+#[derive(Clone)]
+pub struct DetMigrator {
+    migrations: Vec<Arc<dyn DetMigration>>,
+}
+
+impl Default for DetMigrator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DetMigrator {
+    /// Create a migrator with the built-in migrations
+    pub fn new() -> Self {
+        DetMigrator {
+            migrations: vec![Arc::new(BareDocMigration)],
+        }
+    }
+
+    /// Add a migration; it replaces any migration for the same version
+    pub fn register(&mut self, migration: Arc<dyn DetMigration>) {
+        let version = migration.source_version();
+        self.migrations.retain(|m| m.source_version() != version);
+        self.migrations.push(migration);
+    }
+
+    /// Schema version of raw `.det` JSON
+    ///
+    /// Files from before the envelope was introduced are a bare `doc` node
+    /// and count as version 0.
+    pub fn schema_version(value: &Value) -> Result<u32, DetError> {
+        match value.get("schemaVersion") {
+            Some(version) => version
+                .as_u64()
+                .and_then(|v| u32::try_from(v).ok())
+                .ok_or_else(|| DetError::InvalidEnvelope("invalid schemaVersion".to_string())),
+            None if value.get("type").and_then(Value::as_str) == Some("doc") => Ok(0),
+            None => Err(DetError::InvalidEnvelope(
+                "neither a .det envelope nor a ProseMirror document".to_string(),
+            )),
+        }
+    }
+
+    /// Upgrade raw JSON to the current schema version
+    ///
+    /// Returns the upgraded JSON and the version the input had.
+    pub fn upgrade(&self, mut value: Value) -> Result<(Value, u32), DetError> {
+        let original = Self::schema_version(&value)?;
+        if original > CURRENT_SCHEMA_VERSION {
+            return Err(DetError::UnsupportedVersion {
+                found: original,
+                supported: CURRENT_SCHEMA_VERSION,
+            });
+        }
+
+        let mut version = original;
+        while version < CURRENT_SCHEMA_VERSION {
+            let migration = self
+                .migrations
+                .iter()
+                .find(|m| m.source_version() == version)
+                .ok_or_else(|| DetError::Migration {
+                    from: version,
+                    reason: "no migration registered".to_string(),
+                })?;
+
+            value = migration.migrate(value)?;
+            let next = Self::schema_version(&value)?;
+            if next != version + 1 {
+                return Err(DetError::Migration {
+                    from: version,
+                    reason: format!("migration produced version {}", next),
+                });
+            }
+            version = next;
+        }
+
+        Ok((value, original))
+    }
+}
+
+/// Wraps a bare `doc` node (version 0) in the version 1 envelope
+///
+/// Version 0 files put their metadata in a sibling object: `metadata`
+/// for extractions, `derivativeMetadata` or `reportMetadata` for documents
+/// made by hand. These objects are kept as extra envelope fields.
+struct BareDocMigration;
+
+impl DetMigration for BareDocMigration {
+    fn source_version(&self) -> u32 {
+        0
+    }
+
+    fn migrate(&self, value: Value) -> Result<Value, DetError> {
+        let Value::Object(mut legacy) = value else {
+            return Err(DetError::Migration {
+                from: 0,
+                reason: "document is not a JSON object".to_string(),
+            });
+        };
+
+        let content = json!({
+            "type": "doc",
+            "content": legacy.remove("content").unwrap_or_else(|| json!([])),
+        });
+        legacy.remove("type");
+
+        let metadata = legacy
+            .get("metadata")
+            .and_then(Value::as_object)
+            .cloned()
+            .unwrap_or_default();
+        let text = |key: &str| metadata.get(key).and_then(Value::as_str);
+
+        let kind = if legacy.contains_key("reportMetadata") {
+            DetKind::Report
+        } else if let Some(kind) = legacy
+            .get("derivativeMetadata")
+            .and_then(|d| d.get("type"))
+            .and_then(|t| serde_json::from_value::<DetKind>(t.clone()).ok())
+        {
+            kind
+        } else if text("extractionMethod").is_some() {
+            DetKind::Extracted
+        } else {
+            DetKind::Custom
+        };
+
+        let now = Utc::now().to_rfc3339();
+        let created_at = text("extractedAt").unwrap_or(&now).to_string();
+        let warnings: Vec<String> = metadata
+            .get("warnings")
+            .and_then(Value::as_array)
+            .map(|warnings| {
+                warnings
+                    .iter()
+                    .filter_map(|w| w.as_str().or_else(|| w.get("message")?.as_str()))
+                    .map(str::to_string)
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let mut processing = Map::new();
+        processing.insert(
+            "method".to_string(),
+            json!(text("extractionMethod").unwrap_or("unknown")),
+        );
+        processing.insert(
+            "status".to_string(),
+            json!(text("status").unwrap_or("completed")),
+        );
+        processing.insert("createdAt".to_string(), json!(created_at));
+        processing.insert("updatedAt".to_string(), json!(created_at));
+        if let Some(quality) = metadata.get("quality").filter(|q| q.is_object()) {
+            processing.insert("quality".to_string(), quality.clone());
+        }
+        if !warnings.is_empty() {
+            processing.insert("warnings".to_string(), json!(warnings));
+        }
+        if let Some(pages) = metadata.get("pageCount").filter(|p| p.is_u64()) {
+            processing.insert("pageCount".to_string(), pages.clone());
+        }
+
+        let mut sources = Vec::new();
+        if let Some(path) = text("sourcePath") {
+            let mut source = Map::new();
+            source.insert("path".to_string(), json!(path));
+            if let Some(id) = text("documentId") {
+                source.insert("documentId".to_string(), json!(id));
+            }
+            if let Some(hash) = text("contentHash") {
+                source.insert("contentHash".to_string(), json!(hash));
+            }
+            sources.push(Value::Object(source));
+        }
+
+        let mut envelope = legacy;
+        envelope.insert("format".to_string(), json!("det"));
+        envelope.insert("schemaVersion".to_string(), json!(1));
+        envelope.insert("kind".to_string(), json!(kind.as_str()));
+        envelope.insert("sources".to_string(), Value::Array(sources));
+        envelope.insert("processing".to_string(), Value::Object(processing));
+        envelope.insert("content".to_string(), content);
+        Ok(Value::Object(envelope))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::det::DetDocument;
+
+    #[test]
+    fn test_upgrades_bare_extraction() {
+        let legacy = json!({
+            "type": "doc",
+            "content": [{ "type": "paragraph", "content": [{ "type": "text", "text": "Hi" }] }],
+            "metadata": {
+                "originalFile": "letter.pdf",
+                "sourcePath": "/corpus/letter.pdf",
+                "documentId": "document_1",
+                "contentHash": "blake3:abc",
+                "extractedAt": "2024-09-24T10:30:00+00:00",
+                "extractionMethod": "pdf-text-v1",
+                "status": "completed",
+                "quality": { "score": 0.8, "level": "fair" },
+                "warnings": [{ "location": "page", "page": 2, "message": "No text layer" }],
+                "pageCount": 2
+            }
+        });
+
+        let (upgraded, from) = DetMigrator::new().upgrade(legacy).unwrap();
+        assert_eq!(from, 0);
+
+        let document = DetDocument::from_json(upgraded).unwrap();
+        assert_eq!(document.kind(), DetKind::Extracted);
+        assert_eq!(document.plain_text(), "Hi");
+        assert_eq!(document.processing().method, "pdf-text-v1");
+        assert_eq!(document.processing().warnings, vec!["No text layer"]);
+        assert_eq!(document.processing().page_count, Some(2));
+        let source = document.primary_source().unwrap();
+        assert_eq!(source.content_hash.as_deref(), Some("blake3:abc"));
+        assert!(document.extra().contains_key("metadata"));
+    }
+
+    #[test]
+    fn test_upgrades_report_and_rejects_newer_versions() {
+        let report = json!({
+            "type": "doc",
+            "content": [],
+            "reportMetadata": { "reportType": "analysis" }
+        });
+        let (upgraded, _) = DetMigrator::new().upgrade(report).unwrap();
+        assert_eq!(upgraded["kind"], "report");
+        assert_eq!(upgraded["reportMetadata"]["reportType"], "analysis");
+
+        let newer = json!({ "format": "det", "schemaVersion": CURRENT_SCHEMA_VERSION + 1 });
+        assert!(matches!(
+            DetMigrator::new().upgrade(newer),
+            Err(DetError::UnsupportedVersion { .. })
+        ));
+        assert!(DetMigrator::new().upgrade(json!({ "foo": 1 })).is_err());
+    }
+
+    struct RenameKind;
+
+    impl DetMigration for RenameKind {
+        fn source_version(&self) -> u32 {
+            0
+        }
+
+        fn migrate(&self, _value: Value) -> Result<Value, DetError> {
+            Ok(json!({ "schemaVersion": 3 }))
+        }
+    }
+
+    #[test]
+    fn test_rejects_migrations_that_skip_versions() {
+        let mut migrator = DetMigrator::new();
+        migrator.register(Arc::new(RenameKind));
+
+        let result = migrator.upgrade(json!({ "type": "doc", "content": [] }));
+        assert!(matches!(result, Err(DetError::Migration { from: 0, .. })));
+    }
+}
@@ -0,0 +1,16 @@
+pub mod aggregates;
+pub mod errors;
+pub mod migration;
+pub mod repositories;
+pub mod schema;
+pub mod value_objects;
+
+// Re-export commonly used types
+pub use aggregates::{DetDocument, DetKind, CURRENT_SCHEMA_VERSION};
+pub use errors::{DetError, SchemaViolation};
+pub use migration::{DetMigration, DetMigrator};
+pub use repositories::DetStore;
+pub use schema::{AttrKind, AttrSpec, Content, DetSchema, MarkSpec, NodeGroup, NodeSpec};
+pub use value_objects::{
+    PmMark, PmNode, ProcessingMetadata, ProcessingQuality, ProcessingStatus, SourceReference,
+};
@@ -0,0 +1,19 @@
+use async_trait::async_trait;
+use std::path::Path;
+
+use super::super::aggregates::DetDocument;
+use super::super::errors::DetError;
+
+/// Storage of `.det` files
+///
+/// Implementations upgrade older schema versions on load, validate content
+/// against the editor schema, and replace files atomically on save so a
+/// crash never leaves a half-written document behind.
+#[async_trait]
+pub trait DetStore: Send + Sync {
+    /// Read, upgrade and validate the document at `path`
+    async fn load(&self, path: &Path) -> Result<DetDocument, DetError>;
+
+    /// Validate and write a document to `path`, replacing any existing file
+    async fn save(&self, path: &Path, document: &DetDocument) -> Result<(), DetError>;
+}
@@ -0,0 +1,3 @@
+pub mod det_store;
+
+pub use det_store::DetStore;
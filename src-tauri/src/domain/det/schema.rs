@@ -0,0 +1,565 @@
+//! Editor schema for `.det` content
+//!
+//! Mirrors the TipTap schema of the frontend editor (StarterKit with the
+//! Table, Link, Underline and Highlight extensions, plus the `citation`
+//! mark linking passages to source documents), so documents written
+//! by the backend open without ProseMirror rejecting or silently dropping
+//! content. Features that add their own node or mark types register them
+//! with `with_node` and `with_mark`.
+
+use std::collections::HashMap;
+
+use serde_json::Value;
+
+use super::errors::{DetError, SchemaViolation};
+use super::value_objects::PmNode;
+
+/// Value type of a node or mark attribute
+#[derive(Debug, Clone, PartialEq)]
+pub enum AttrKind {
+    Integer {
+        min: i64,
+        max: i64,
+    },
+    Text,
+    /// A string or null
+    OptionalText,
+    /// Any JSON value
+    Any,
+}
+
+/// An attribute a node or mark accepts
+///
+/// Attributes without a default in the editor schema are `required`.
+/// Attributes not listed are ignored, as ProseMirror ignores them.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AttrSpec {
+    pub name: String,
+    pub kind: AttrKind,
+    pub required: bool,
+}
+
+impl AttrSpec {
+    pub fn optional(name: &str, kind: AttrKind) -> Self {
+        AttrSpec {
+            name: name.to_string(),
+            kind,
+            required: false,
+        }
+    }
+
+    pub fn required(name: &str, kind: AttrKind) -> Self {
+        AttrSpec {
+            name: name.to_string(),
+            kind,
+            required: true,
+        }
+    }
+
+    fn check(&self, value: Option<&Value>) -> Option<String> {
+        let Some(value) = value else {
+            return self
+                .required
+                .then(|| format!("attribute '{}' is required", self.name));
+        };
+
+        let valid = match &self.kind {
+            AttrKind::Integer { min, max } => value
+                .as_i64()
+                .is_some_and(|number| (*min..=*max).contains(&number)),
+            AttrKind::Text => value.is_string(),
+            AttrKind::OptionalText => value.is_string() || value.is_null(),
+            AttrKind::Any => true,
+        };
+        (!valid).then(|| {
+            format!(
+                "attribute '{}' has invalid value {} (expected {:?})",
+                self.name, value, self.kind
+            )
+        })
+    }
+}
+
+/// Which children a node accepts
+#[derive(Debug, Clone, PartialEq)]
+pub enum Content {
+    /// A leaf node
+    Empty,
+    /// Block nodes, at least `min` of them
+    Blocks { min: usize },
+    /// Inline nodes
+    Inline,
+    /// Unmarked text only
+    Text,
+    /// Nodes of the listed types, at least `min` of them
+    Nodes { allowed: Vec<String>, min: usize },
+    /// A node of type `first`, followed by any blocks ("paragraph block*")
+    FirstThenBlocks { first: String },
+}
+
+/// Whether a node is used in block or inline positions
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NodeGroup {
+    Block,
+    Inline,
+    /// Only allowed where a parent names it (list items, table rows)
+    None,
+}
+
+/// Definition of a node type
+#[derive(Debug, Clone, PartialEq)]
+pub struct NodeSpec {
+    pub name: String,
+    pub group: NodeGroup,
+    pub content: Content,
+    pub attrs: Vec<AttrSpec>,
+}
+
+impl NodeSpec {
+    pub fn new(name: &str, group: NodeGroup, content: Content) -> Self {
+        NodeSpec {
+            name: name.to_string(),
+            group,
+            content,
+            attrs: Vec::new(),
+        }
+    }
+
+    pub fn with_attr(mut self, attr: AttrSpec) -> Self {
+        self.attrs.push(attr);
+        self
+    }
+}
+
+/// Definition of a mark type
+#[derive(Debug, Clone, PartialEq)]
+pub struct MarkSpec {
+    pub name: String,
+    pub attrs: Vec<AttrSpec>,
+}
+
+impl MarkSpec {
+    pub fn new(name: &str) -> Self {
+        MarkSpec {
+            name: name.to_string(),
+            attrs: Vec::new(),
+        }
+    }
+
+    pub fn with_attr(mut self, attr: AttrSpec) -> Self {
+        self.attrs.push(attr);
+        self
+    }
+}
+
+/// The set of node and mark types `.det` content may use
+#[derive(Debug, Clone)]
+pub struct DetSchema {
+    nodes: HashMap<String, NodeSpec>,
+    marks: HashMap<String, MarkSpec>,
+}
+
+impl Default for DetSchema {
+    fn default() -> Self {
+        Self::tiptap()
+    }
+}
+
+impl DetSchema {
+    /// The schema of the frontend editor
+    pub fn tiptap() -> Self {
+        use AttrKind::{Any, Integer, OptionalText, Text};
+        use NodeGroup::{Block, Inline};
+
+        let cell = |name: &str| {
+            NodeSpec::new(name, NodeGroup::None, Content::Blocks { min: 1 })
+                .with_attr(AttrSpec::optional(
+                    "colspan",
+                    Integer {
+                        min: 1,
+                        max: i64::MAX,
+                    },
+                ))
+                .with_attr(AttrSpec::optional(
+                    "rowspan",
+                    Integer {
+                        min: 1,
+                        max: i64::MAX,
+                    },
+                ))
+                .with_attr(AttrSpec::optional("colwidth", Any))
+        };
+
+        // Character offset or ProseMirror position in a cited document
+        let offset = Integer {
+            min: 0,
+            max: i64::from(u32::MAX),
+        };
+
+        let schema = DetSchema {
+            nodes: HashMap::new(),
+            marks: HashMap::new(),
+        };
+
+        schema
+            .with_node(NodeSpec::new(
+                "doc",
+                NodeGroup::None,
+                Content::Blocks { min: 1 },
+            ))
+            .with_node(NodeSpec::new("text", Inline, Content::Empty))
+            .with_node(NodeSpec::new("paragraph", Block, Content::Inline))
+            .with_node(
+                NodeSpec::new("heading", Block, Content::Inline)
+                    .with_attr(AttrSpec::optional("level", Integer { min: 1, max: 6 })),
+            )
+            .with_node(NodeSpec::new(
+                "blockquote",
+                Block,
+                Content::Blocks { min: 1 },
+            ))
+            .with_node(
+                NodeSpec::new("codeBlock", Block, Content::Text)
+                    .with_attr(AttrSpec::optional("language", OptionalText)),
+            )
+            .with_node(NodeSpec::new("horizontalRule", Block, Content::Empty))
+            .with_node(NodeSpec::new("hardBreak", Inline, Content::Empty))
+            .with_node(NodeSpec::new(
+                "bulletList",
+                Block,
+                Content::Nodes {
+                    allowed: vec!["listItem".to_string()],
+                    min: 1,
+                },
+            ))
+            .with_node(
+                NodeSpec::new(
+                    "orderedList",
+                    Block,
+                    Content::Nodes {
+                        allowed: vec!["listItem".to_string()],
+                        min: 1,
+                    },
+                )
+                .with_attr(AttrSpec::optional(
+                    "start",
+                    Integer {
+                        min: 0,
+                        max: i64::MAX,
+                    },
+                )),
+            )
+            .with_node(NodeSpec::new(
+                "listItem",
+                NodeGroup::None,
+                Content::FirstThenBlocks {
+                    first: "paragraph".to_string(),
+                },
+            ))
+            .with_node(NodeSpec::new(
+                "table",
+                Block,
+                Content::Nodes {
+                    allowed: vec!["tableRow".to_string()],
+                    min: 1,
+                },
+            ))
+            .with_node(NodeSpec::new(
+                "tableRow",
+                NodeGroup::None,
+                Content::Nodes {
+                    allowed: vec!["tableCell".to_string(), "tableHeader".to_string()],
+                    min: 0,
+                },
+            ))
+            .with_node(cell("tableCell"))
+            .with_node(cell("tableHeader"))
+            .with_mark(MarkSpec::new("bold"))
+            .with_mark(MarkSpec::new("italic"))
+            .with_mark(MarkSpec::new("underline"))
+            .with_mark(MarkSpec::new("strike"))
+            .with_mark(MarkSpec::new("code"))
+            .with_mark(
+                MarkSpec::new("highlight").with_attr(AttrSpec::optional("color", OptionalText)),
+            )
+            .with_mark(
+                MarkSpec::new("link")
+                    .with_attr(AttrSpec::required("href", Text))
+                    .with_attr(AttrSpec::optional("target", OptionalText))
+                    .with_attr(AttrSpec::optional("rel", OptionalText))
+                    .with_attr(AttrSpec::optional("class", OptionalText)),
+            )
+            .with_mark(
+                MarkSpec::new("citation")
+                    .with_attr(AttrSpec::required("path", Text))
+                    .with_attr(AttrSpec::optional("documentId", OptionalText))
+                    .with_attr(AttrSpec::optional(
+                        "page",
+                        Integer {
+                            min: 1,
+                            max: i64::from(u32::MAX),
+                        },
+                    ))
+                    .with_attr(AttrSpec::optional("label", OptionalText))
+                    .with_attr(AttrSpec::optional("charStart", offset.clone()))
+                    .with_attr(AttrSpec::optional("charEnd", offset.clone()))
+                    .with_attr(AttrSpec::optional("pmFrom", offset.clone()))
+                    .with_attr(AttrSpec::optional("pmTo", offset)),
+            )
+    }
+
+    /// Add or replace a node type
+    pub fn with_node(mut self, spec: NodeSpec) -> Self {
+        self.nodes.insert(spec.name.clone(), spec);
+        self
+    }
+
+    /// Add or replace a mark type
+    pub fn with_mark(mut self, spec: MarkSpec) -> Self {
+        self.marks.insert(spec.name.clone(), spec);
+        self
+    }
+
+    pub fn node(&self, name: &str) -> Option<&NodeSpec> {
+        self.nodes.get(name)
+    }
+
+    pub fn mark(&self, name: &str) -> Option<&MarkSpec> {
+        self.marks.get(name)
+    }
+
+    /// Check that `doc` is a `doc` node that the editor accepts
+    pub fn validate(&self, doc: &PmNode) -> Result<(), DetError> {
+        let violations = self.violations(doc);
+        if violations.is_empty() {
+            Ok(())
+        } else {
+            Err(DetError::SchemaViolations(violations))
+        }
+    }
+
+    /// All schema problems in a document, in document order
+    pub fn violations(&self, doc: &PmNode) -> Vec<SchemaViolation> {
+        let mut violations = Vec::new();
+        if doc.node_type != "doc" {
+            violations.push(SchemaViolation::new(
+                "doc",
+                format!("root node must be 'doc', not '{}'", doc.node_type),
+            ));
+        }
+        self.check_node(doc, "doc", &mut violations);
+        violations
+    }
+
+    fn check_node(&self, node: &PmNode, path: &str, violations: &mut Vec<SchemaViolation>) {
+        let Some(spec) = self.node(&node.node_type) else {
+            violations.push(SchemaViolation::new(
+                path,
+                format!("unknown node type '{}'", node.node_type),
+            ));
+            return;
+        };
+
+        for attr in &spec.attrs {
+            if let Some(message) = attr.check(node.attr(&attr.name)) {
+                violations.push(SchemaViolation::new(path, message));
+            }
+        }
+
+        if node.is_text() {
+            if node.text.as_deref().unwrap_or_default().is_empty() {
+                violations.push(SchemaViolation::new(path, "text nodes must not be empty"));
+            }
+        } else if node.text.is_some() {
+            violations.push(SchemaViolation::new(path, "only text nodes can hold text"));
+        }
+
+        if !node.marks.is_empty() && spec.group != NodeGroup::Inline {
+            violations.push(SchemaViolation::new(
+                path,
+                "only inline nodes can have marks",
+            ));
+        }
+        for mark in &node.marks {
+            match self.mark(&mark.mark_type) {
+                Some(mark_spec) => {
+                    for attr in &mark_spec.attrs {
+                        let value = mark.attrs.as_ref().and_then(|attrs| attrs.get(&attr.name));
+                        if let Some(message) = attr.check(value) {
+                            violations.push(SchemaViolation::new(
+                                path,
+                                format!("mark '{}': {}", mark.mark_type, message),
+                            ));
+                        }
+                    }
+                }
+                None => violations.push(SchemaViolation::new(
+                    path,
+                    format!("unknown mark type '{}'", mark.mark_type),
+                )),
+            }
+        }
+
+        self.check_content(node, spec, path, violations);
+
+        for (index, child) in node.content.iter().enumerate() {
+            self.check_node(child, &format!("{}.content[{}]", path, index), violations);
+        }
+    }
+
+    fn check_content(
+        &self,
+        node: &PmNode,
+        spec: &NodeSpec,
+        path: &str,
+        violations: &mut Vec<SchemaViolation>,
+    ) {
+        let group_of = |child: &PmNode| self.node(&child.node_type).map(|s| s.group);
+        let mut complain = |message: String| violations.push(SchemaViolation::new(path, message));
+        let children = &node.content;
+
+        match &spec.content {
+            Content::Empty => {
+                if !children.is_empty() {
+                    complain(format!("'{}' cannot have content", spec.name));
+                }
+            }
+            Content::Blocks { min } => {
+                if children.len() < *min {
+                    complain(format!("'{}' needs at least {} block(s)", spec.name, min));
+                }
+                for child in children {
+                    if group_of(child).is_some_and(|group| group != NodeGroup::Block) {
+                        complain(format!(
+                            "'{}' is not allowed in '{}'",
+                            child.node_type, spec.name
+                        ));
+                    }
+                }
+            }
+            Content::Inline => {
+                for child in children {
+                    if group_of(child).is_some_and(|group| group != NodeGroup::Inline) {
+                        complain(format!(
+                            "'{}' is not allowed in '{}'",
+                            child.node_type, spec.name
+                        ));
+                    }
+                }
+            }
+            Content::Text => {
+                for child in children {
+                    if !child.is_text() {
+                        complain(format!("'{}' can only contain text", spec.name));
+                    } else if !child.marks.is_empty() {
+                        complain(format!("text in '{}' cannot have marks", spec.name));
+                    }
+                }
+            }
+            Content::Nodes { allowed, min } => {
+                if children.len() < *min {
+                    complain(format!(
+                        "'{}' needs at least {} of {}",
+                        spec.name,
+                        min,
+                        allowed.join("/")
+                    ));
+                }
+                for child in children {
+                    if !allowed.contains(&child.node_type) {
+                        complain(format!(
+                            "'{}' is not allowed in '{}'",
+                            child.node_type, spec.name
+                        ));
+                    }
+                }
+            }
+            Content::FirstThenBlocks { first } => {
+                match children.first() {
+                    Some(child) if &child.node_type == first => {}
+                    _ => complain(format!("'{}' must start with a '{}'", spec.name, first)),
+                }
+                for child in children.iter().skip(1) {
+                    if group_of(child).is_some_and(|group| group != NodeGroup::Block) {
+                        complain(format!(
+                            "'{}' is not allowed in '{}'",
+                            child.node_type, spec.name
+                        ));
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::det::PmMark;
+
+    #[test]
+    fn test_accepts_editor_content() {
+        let doc = PmNode::doc(vec![
+            PmNode::heading(2, PmNode::text_nodes("Costs", vec![PmMark::bold()])),
+            PmNode::bullet_list(vec![PmNode::list_item(vec![
+                PmNode::paragraph(PmNode::text_nodes("item", vec![PmMark::link("https://a")])),
+                PmNode::ordered_list(
+                    vec![PmNode::list_item(vec![PmNode::paragraph(Vec::new())])],
+                    3,
+                ),
+            ])]),
+            PmNode::table(vec![PmNode::table_row(vec![
+                PmNode::table_header(Vec::new()),
+                PmNode::table_cell(vec![PmNode::paragraph(vec![PmNode::hard_break()])]),
+            ])]),
+            PmNode::code_block("fn main() {}"),
+        ]);
+
+        assert_eq!(DetSchema::tiptap().violations(&doc), Vec::new());
+    }
+
+    #[test]
+    fn test_reports_violations_with_paths() {
+        let doc = PmNode::doc(vec![
+            PmNode::paragraph(vec![PmNode::paragraph(Vec::new())]),
+            PmNode::element("heading", Vec::new()).with_attr("level", 9),
+            PmNode::bullet_list(Vec::new()),
+            PmNode::paragraph(vec![
+                PmNode::text("", Vec::new()),
+                PmNode::text("x", vec![PmMark::new("comment")]),
+            ]),
+            PmNode::element("video", Vec::new()),
+        ]);
+
+        let violations = DetSchema::tiptap().violations(&doc);
+        let paths: Vec<&str> = violations.iter().map(|v| v.path.as_str()).collect();
+        assert_eq!(
+            paths,
+            vec![
+                "doc.content[0]",
+                "doc.content[1]",
+                "doc.content[2]",
+                "doc.content[3].content[0]",
+                "doc.content[3].content[1]",
+                "doc.content[4]",
+            ]
+        );
+        assert!(violations[4]
+            .message
+            .contains("unknown mark type 'comment'"));
+        assert!(DetSchema::tiptap().validate(&doc).is_err());
+    }
+
+    #[test]
+    fn test_custom_nodes_can_be_registered() {
+        let doc = PmNode::doc(vec![PmNode::element("costTable", Vec::new())]);
+        assert!(DetSchema::tiptap().validate(&doc).is_err());
+
+        let schema = DetSchema::tiptap().with_node(NodeSpec::new(
+            "costTable",
+            NodeGroup::Block,
+            Content::Empty,
+        ));
+        assert!(schema.validate(&doc).is_ok());
+    }
+}
@@ -0,0 +1,7 @@
+pub mod processing_metadata;
+pub mod prosemirror;
+pub mod source_reference;
+
+pub use processing_metadata::{ProcessingMetadata, ProcessingQuality, ProcessingStatus};
+pub use prosemirror::{PmMark, PmNode};
+pub use source_reference::SourceReference;
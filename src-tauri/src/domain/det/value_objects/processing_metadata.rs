@@ -0,0 +1,91 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+/// State of the processing that produced a `.det` document
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ProcessingStatus {
+    Pending,
+    Completed,
+    Failed,
+}
+
+/// Quality score recorded by the producing step
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProcessingQuality {
+    /// Between 0 and 1
+    pub score: f64,
+
+    /// "good", "fair" or "poor"
+    pub level: String,
+}
+
+/// How a `.det` document was produced
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProcessingMetadata {
+    /// Versioned method name, e.g. "pdf-text-v1" or "manual"
+    pub method: String,
+
+    pub status: ProcessingStatus,
+
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quality: Option<ProcessingQuality>,
+
+    /// Human-readable problems found while processing
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+
+    /// Number of pages of the source, for paginated formats
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub page_count: Option<u32>,
+
+    /// Method-specific values
+    #[serde(default, skip_serializing_if = "Map::is_empty")]
+    pub properties: Map<String, Value>,
+}
+
+impl ProcessingMetadata {
+    /// Metadata for a completed processing step, timestamped now
+    pub fn completed(method: impl Into<String>) -> Self {
+        let now = Utc::now();
+        ProcessingMetadata {
+            method: method.into(),
+            status: ProcessingStatus::Completed,
+            created_at: now,
+            updated_at: now,
+            quality: None,
+            warnings: Vec::new(),
+            page_count: None,
+            properties: Map::new(),
+        }
+    }
+
+    pub fn with_quality(mut self, score: f64, level: impl Into<String>) -> Self {
+        self.quality = Some(ProcessingQuality {
+            score,
+            level: level.into(),
+        });
+        self
+    }
+
+    pub fn with_warnings(mut self, warnings: Vec<String>) -> Self {
+        self.warnings = warnings;
+        self
+    }
+
+    pub fn with_page_count(mut self, page_count: Option<u32>) -> Self {
+        self.page_count = page_count;
+        self
+    }
+
+    pub fn with_property(mut self, key: &str, value: impl Into<Value>) -> Self {
+        self.properties.insert(key.to_string(), value.into());
+        self
+    }
+}
@@ -0,0 +1,415 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+/// A ProseMirror node as stored in `.det` files
+///
+/// Mirrors the JSON shape TipTap reads and writes: `type`, optional
+/// `attrs`, child `content`, and for text nodes `text` plus `marks`. Node
+/// and mark names follow TipTap's StarterKit and Table extensions.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PmNode {
+    #[serde(rename = "type")]
+    pub node_type: String,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub attrs: Option<Map<String, Value>>,
+
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub content: Vec<PmNode>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub marks: Vec<PmMark>,
+}
+
+/// A mark (bold, italic, link, ...) applied to a text node
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PmMark {
+    #[serde(rename = "type")]
+    pub mark_type: String,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub attrs: Option<Map<String, Value>>,
+}
+
+impl PmNode {
+    /// Create an element node with the given children
+    pub fn element(node_type: impl Into<String>, content: Vec<PmNode>) -> Self {
+        PmNode {
+            node_type: node_type.into(),
+            attrs: None,
+            content,
+            text: None,
+            marks: Vec::new(),
+        }
+    }
+
+    /// Set an attribute, returning the node
+    pub fn with_attr(mut self, key: &str, value: impl Into<Value>) -> Self {
+        self.attrs
+            .get_or_insert_with(Map::new)
+            .insert(key.to_string(), value.into());
+        self
+    }
+
+    pub fn doc(content: Vec<PmNode>) -> Self {
+        Self::element("doc", content)
+    }
+
+    pub fn paragraph(inlines: Vec<PmNode>) -> Self {
+        Self::element("paragraph", inlines)
+    }
+
+    /// Heading of level 1 to 6; other levels are clamped
+    pub fn heading(level: u8, inlines: Vec<PmNode>) -> Self {
+        Self::element("heading", inlines).with_attr("level", level.clamp(1, 6))
+    }
+
+    pub fn blockquote(blocks: Vec<PmNode>) -> Self {
+        Self::element("blockquote", blocks)
+    }
+
+    pub fn code_block(code: &str) -> Self {
+        Self::element("codeBlock", Self::text_nodes(code, Vec::new()))
+    }
+
+    pub fn horizontal_rule() -> Self {
+        Self::element("horizontalRule", Vec::new())
+    }
+
+    pub fn bullet_list(items: Vec<PmNode>) -> Self {
+        Self::element("bulletList", items)
+    }
+
+    pub fn ordered_list(items: Vec<PmNode>, start: u64) -> Self {
+        Self::element("orderedList", items).with_attr("start", start)
+    }
+
+    pub fn list_item(blocks: Vec<PmNode>) -> Self {
+        Self::element("listItem", blocks)
+    }
+
+    pub fn table(rows: Vec<PmNode>) -> Self {
+        Self::element("table", rows)
+    }
+
+    pub fn table_row(cells: Vec<PmNode>) -> Self {
+        Self::element("tableRow", cells)
+    }
+
+    pub fn table_cell(blocks: Vec<PmNode>) -> Self {
+        Self::element("tableCell", Self::ensure_block_content(blocks))
+    }
+
+    pub fn table_header(blocks: Vec<PmNode>) -> Self {
+        Self::element("tableHeader", Self::ensure_block_content(blocks))
+    }
+
+    pub fn hard_break() -> Self {
+        Self::element("hardBreak", Vec::new())
+    }
+
+    /// Text node with marks
+    ///
+    /// ProseMirror rejects empty text nodes, so callers should skip empty
+    /// strings; see `text_nodes`.
+    pub fn text(text: impl Into<String>, marks: Vec<PmMark>) -> Self {
+        PmNode {
+            node_type: "text".to_string(),
+            attrs: None,
+            content: Vec::new(),
+            text: Some(text.into()),
+            marks,
+        }
+    }
+
+    /// Text nodes for a string, empty if the string is empty
+    pub fn text_nodes(text: &str, marks: Vec<PmMark>) -> Vec<PmNode> {
+        if text.is_empty() {
+            Vec::new()
+        } else {
+            vec![Self::text(text, marks)]
+        }
+    }
+
+    pub fn is_text(&self) -> bool {
+        self.node_type == "text"
+    }
+
+    /// Whether the node holds inline content (text and hard breaks)
+    pub fn is_textblock(&self) -> bool {
+        matches!(
+            self.node_type.as_str(),
+            "paragraph" | "heading" | "codeBlock"
+        )
+    }
+
+    /// Attribute value, if set
+    pub fn attr(&self, key: &str) -> Option<&Value> {
+        self.attrs.as_ref().and_then(|attrs| attrs.get(key))
+    }
+
+    /// Concatenated text of this node and its descendants
+    ///
+    /// Blocks are separated by newlines, so the result is suitable for
+    /// search indexing and snippets.
+    pub fn plain_text(&self) -> String {
+        let mut output = String::new();
+        self.collect_text(&mut output);
+        output.trim_end().to_string()
+    }
+
+    fn collect_text(&self, output: &mut String) {
+        if let Some(text) = &self.text {
+            output.push_str(text);
+            return;
+        }
+        if self.node_type == "hardBreak" {
+            output.push('\n');
+            return;
+        }
+
+        for child in &self.content {
+            child.collect_text(output);
+        }
+        if self.is_textblock() && !output.ends_with('\n') {
+            output.push('\n');
+        }
+    }
+
+    /// Text between two ProseMirror positions in this node's content
+    ///
+    /// Positions count as in ProseMirror: each character is one step, a
+    /// leaf node such as a hard break is one step, and every other node
+    /// adds one step on entry and one on exit. Returns `None` if the range
+    /// does not fit the content.
+    pub fn text_between(&self, from: usize, to: usize) -> Option<String> {
+        let size: usize = self.content.iter().map(PmNode::node_size).sum();
+        if from > to || to > size {
+            return None;
+        }
+
+        let mut output = String::new();
+        let mut position = 0;
+        for child in &self.content {
+            child.collect_between(&mut position, from, to, &mut output);
+        }
+        Some(output.trim_end().to_string())
+    }
+
+    /// Positions of every occurrence of `needle` within a single text block
+    ///
+    /// Ranges use the positions of `text_between`; a hard break matches a
+    /// newline. Occurrences spanning several blocks are not found.
+    pub fn find_text(&self, needle: &str) -> Vec<(usize, usize)> {
+        let needle: Vec<char> = needle.chars().collect();
+        let mut ranges = Vec::new();
+        if needle.is_empty() {
+            return ranges;
+        }
+
+        let mut position = 0;
+        for child in &self.content {
+            child.find_in(&mut position, &needle, &mut ranges);
+        }
+        ranges
+    }
+
+    fn find_in(&self, position: &mut usize, needle: &[char], ranges: &mut Vec<(usize, usize)>) {
+        let start = *position;
+        *position = start + self.node_size();
+        if self.text.is_some() || self.is_leaf() {
+            return;
+        }
+
+        let mut inner = start + 1;
+        if !self.is_textblock() {
+            for child in &self.content {
+                child.find_in(&mut inner, needle, ranges);
+            }
+            return;
+        }
+
+        // Characters of the block with the position before each
+        let mut chars = Vec::new();
+        for child in &self.content {
+            match &child.text {
+                Some(text) => chars.extend(text.chars().zip(inner..)),
+                None if child.node_type == "hardBreak" => chars.push(('\n', inner)),
+                None => {}
+            }
+            inner += child.node_size();
+        }
+        for window in chars.windows(needle.len()) {
+            if window.iter().map(|(c, _)| c).eq(needle) {
+                ranges.push((window[0].1, window[window.len() - 1].1 + 1));
+            }
+        }
+    }
+
+    fn is_leaf(&self) -> bool {
+        matches!(self.node_type.as_str(), "hardBreak" | "horizontalRule")
+    }
+
+    fn node_size(&self) -> usize {
+        match &self.text {
+            Some(text) => text.chars().count(),
+            None if self.is_leaf() => 1,
+            None => 2 + self.content.iter().map(PmNode::node_size).sum::<usize>(),
+        }
+    }
+
+    fn collect_between(&self, position: &mut usize, from: usize, to: usize, output: &mut String) {
+        let start = *position;
+        let end = start + self.node_size();
+        *position = end;
+        if end <= from || start >= to {
+            return;
+        }
+
+        if let Some(text) = &self.text {
+            let skip = from.saturating_sub(start);
+            output.extend(text.chars().skip(skip).take(to.min(end) - start - skip));
+            return;
+        }
+        if self.is_leaf() {
+            if self.node_type == "hardBreak" {
+                output.push('\n');
+            }
+            return;
+        }
+
+        let mut inner = start + 1;
+        for child in &self.content {
+            child.collect_between(&mut inner, from, to, output);
+        }
+        if self.is_textblock() && end <= to && !output.is_empty() && !output.ends_with('\n') {
+            output.push('\n');
+        }
+    }
+
+    /// Number of text blocks (paragraphs, headings, code blocks) below
+    /// this node
+    pub fn block_count(&self) -> usize {
+        let own = usize::from(self.is_textblock());
+        own + self.content.iter().map(PmNode::block_count).sum::<usize>()
+    }
+
+    /// Cells must contain at least one block
+    fn ensure_block_content(blocks: Vec<PmNode>) -> Vec<PmNode> {
+        if blocks.is_empty() {
+            vec![Self::paragraph(Vec::new())]
+        } else {
+            blocks
+        }
+    }
+}
+
+impl PmMark {
+    pub fn new(mark_type: impl Into<String>) -> Self {
+        PmMark {
+            mark_type: mark_type.into(),
+            attrs: None,
+        }
+    }
+
+    pub fn bold() -> Self {
+        Self::new("bold")
+    }
+
+    pub fn italic() -> Self {
+        Self::new("italic")
+    }
+
+    pub fn underline() -> Self {
+        Self::new("underline")
+    }
+
+    pub fn strike() -> Self {
+        Self::new("strike")
+    }
+
+    pub fn code() -> Self {
+        Self::new("code")
+    }
+
+    pub fn link(href: impl Into<String>) -> Self {
+        let mut attrs = Map::new();
+        attrs.insert("href".to_string(), Value::String(href.into()));
+        PmMark {
+            mark_type: "link".to_string(),
+            attrs: Some(attrs),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_serializes_as_prosemirror_json() {
+        let doc = PmNode::doc(vec![
+            PmNode::heading(1, PmNode::text_nodes("Title", Vec::new())),
+            PmNode::paragraph(vec![
+                PmNode::text("plain ", Vec::new()),
+                PmNode::text("bold", vec![PmMark::bold()]),
+            ]),
+        ]);
+
+        let json = serde_json::to_value(&doc).unwrap();
+        assert_eq!(json["type"], "doc");
+        assert_eq!(json["content"][0]["attrs"]["level"], 1);
+        assert_eq!(json["content"][1]["content"][1]["marks"][0]["type"], "bold");
+        assert!(json["content"][1]["content"][0].get("marks").is_none());
+
+        let parsed: PmNode = serde_json::from_value(json).unwrap();
+        assert_eq!(parsed, doc);
+    }
+
+    #[test]
+    fn test_plain_text_and_block_count() {
+        let doc = PmNode::doc(vec![
+            PmNode::paragraph(vec![
+                PmNode::text("line one", Vec::new()),
+                PmNode::hard_break(),
+                PmNode::text("line two", Vec::new()),
+            ]),
+            PmNode::bullet_list(vec![PmNode::list_item(vec![PmNode::paragraph(
+                PmNode::text_nodes("item", Vec::new()),
+            )])]),
+            PmNode::table(vec![PmNode::table_row(vec![
+                PmNode::table_cell(Vec::new()),
+            ])]),
+        ]);
+
+        assert_eq!(doc.plain_text(), "line one\nline two\nitem");
+        assert_eq!(doc.block_count(), 3);
+    }
+
+    #[test]
+    fn test_text_between_positions() {
+        let doc = PmNode::doc(vec![
+            PmNode::paragraph(vec![
+                PmNode::text("ab", Vec::new()),
+                PmNode::hard_break(),
+                PmNode::text("cd", Vec::new()),
+            ]),
+            PmNode::paragraph(PmNode::text_nodes("efg", Vec::new())),
+        ]);
+
+        // <p>ab<br>cd</p> spans 0..7, <p>efg</p> spans 7..12
+        assert_eq!(doc.text_between(1, 3).unwrap(), "ab");
+        assert_eq!(doc.text_between(2, 5).unwrap(), "b\nc");
+        assert_eq!(doc.text_between(0, 12).unwrap(), "ab\ncd\nefg");
+        assert_eq!(doc.text_between(9, 11).unwrap(), "fg");
+        assert_eq!(doc.text_between(0, 13), None);
+        assert_eq!(doc.text_between(5, 4), None);
+
+        assert_eq!(doc.find_text("b\nc"), [(2, 5)]);
+        assert_eq!(doc.find_text("fg"), [(9, 11)]);
+        assert!(doc.find_text("cde").is_empty());
+    }
+}
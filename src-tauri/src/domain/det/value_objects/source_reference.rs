@@ -0,0 +1,51 @@
+use serde::{Deserialize, Serialize};
+
+/// A file a `.det` document was made from
+///
+/// Records the path and, when known, the stable document identifier and
+/// content hash at the time of processing, so the source can be found again
+/// after a rename and changes to it can be detected.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SourceReference {
+    /// Path of the source when the document was made; empty when withheld
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub path: String,
+
+    /// Stable identifier of the source document ("document_<uuid>")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub document_id: Option<String>,
+
+    /// Content hash of the source as `<algorithm>:<digest>`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content_hash: Option<String>,
+}
+
+impl SourceReference {
+    pub fn new(path: impl Into<String>) -> Self {
+        SourceReference {
+            path: path.into(),
+            document_id: None,
+            content_hash: None,
+        }
+    }
+
+    pub fn with_document_id(mut self, document_id: impl Into<String>) -> Self {
+        self.document_id = Some(document_id.into());
+        self
+    }
+
+    pub fn with_content_hash(mut self, content_hash: impl Into<String>) -> Self {
+        self.content_hash = Some(content_hash.into());
+        self
+    }
+
+    /// The same reference without its path, for documents that must not
+    /// reveal where the source is stored
+    pub fn without_path(&self) -> Self {
+        SourceReference {
+            path: String::new(),
+            ..self.clone()
+        }
+    }
+}
@@ -0,0 +1,289 @@
+use chrono::{DateTime, Utc};
+use std::collections::{HashMap, HashSet};
+
+use super::super::errors::DerivationError;
+use super::super::value_objects::{DerivationStep, DocumentId, FamilyMember};
+use crate::domain::project::ProjectId;
+
+/// How the members of one document family were derived from each other
+///
+/// Business Rules:
+/// - Every step produces a derivative from the original or from another
+///   derivative of the same family
+/// - The graph stays acyclic: a step whose parent descends from its child
+///   is rejected
+/// - Recording a step that already exists replaces it, so re-running an
+///   operation refreshes its parameters and timestamp
+/// - A derivative is stale when any file it was derived from, directly or
+///   through other derivatives, changed after it was produced
+#[derive(Debug, Clone, PartialEq)]
+pub struct DerivationGraph {
+    project_id: ProjectId,
+    document_id: DocumentId,
+    steps: Vec<DerivationStep>,
+}
+
+impl DerivationGraph {
+    /// Graph of a family nothing has been derived in yet
+    pub fn new(project_id: ProjectId, document_id: DocumentId) -> Self {
+        DerivationGraph {
+            project_id,
+            document_id,
+            steps: Vec::new(),
+        }
+    }
+
+    /// Create a DerivationGraph from existing data (for repository reconstruction)
+    pub fn from_data(
+        project_id: ProjectId,
+        document_id: DocumentId,
+        steps: Vec<DerivationStep>,
+    ) -> Self {
+        DerivationGraph {
+            project_id,
+            document_id,
+            steps,
+        }
+    }
+
+    pub fn project_id(&self) -> &ProjectId {
+        &self.project_id
+    }
+
+    pub fn document_id(&self) -> &DocumentId {
+        &self.document_id
+    }
+
+    /// Steps in the order they were first recorded
+    pub fn steps(&self) -> &[DerivationStep] {
+        &self.steps
+    }
+
+    /// The original followed by every member the steps mention
+    pub fn members(&self) -> Vec<FamilyMember> {
+        let mut members = vec![FamilyMember::Original];
+        for step in &self.steps {
+            for member in [step.parent(), step.child()] {
+                if !members.contains(member) {
+                    members.push(member.clone());
+                }
+            }
+        }
+        members
+    }
+
+    /// Add a step, or replace the step between the same two members
+    pub fn record(&mut self, step: DerivationStep) -> Result<(), DerivationError> {
+        if let Some(existing) = self
+            .steps
+            .iter_mut()
+            .find(|s| s.parent() == step.parent() && s.child() == step.child())
+        {
+            *existing = step;
+            return Ok(());
+        }
+
+        if let Some(mut path) = self.path_between(step.child(), step.parent()) {
+            path.insert(0, step.parent().clone());
+            return Err(DerivationError::Cycle {
+                parent: step.parent().to_string(),
+                child: step.child().to_string(),
+                path: path.iter().map(ToString::to_string).collect(),
+            });
+        }
+
+        self.steps.push(step);
+        Ok(())
+    }
+
+    /// Steps producing a member
+    pub fn parents_of(&self, member: &FamilyMember) -> Vec<&DerivationStep> {
+        self.steps.iter().filter(|s| s.child() == member).collect()
+    }
+
+    /// Steps taking a member as input
+    pub fn children_of(&self, member: &FamilyMember) -> Vec<&DerivationStep> {
+        self.steps.iter().filter(|s| s.parent() == member).collect()
+    }
+
+    /// Processing history of a member: every step leading to it, each
+    /// after the steps that produced its input
+    pub fn chain_to(&self, member: &FamilyMember) -> Vec<&DerivationStep> {
+        fn visit<'a>(
+            graph: &'a DerivationGraph,
+            member: &FamilyMember,
+            chain: &mut Vec<&'a DerivationStep>,
+        ) {
+            for step in graph.parents_of(member) {
+                if !chain.iter().any(|s| std::ptr::eq(*s, step)) {
+                    visit(graph, step.parent(), chain);
+                    chain.push(step);
+                }
+            }
+        }
+
+        let mut chain = Vec::new();
+        visit(self, member, &mut chain);
+        chain
+    }
+
+    /// Number of steps on the longest path from a member without parents
+    pub fn generation(&self, member: &FamilyMember) -> usize {
+        self.parents_of(member)
+            .iter()
+            .map(|step| self.generation(step.parent()) + 1)
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Derivatives produced before one of their inputs last changed
+    ///
+    /// `changed_at` holds when each member's file was last modified;
+    /// members without an entry are taken as unchanged.
+    pub fn stale_members(
+        &self,
+        changed_at: &HashMap<FamilyMember, DateTime<Utc>>,
+    ) -> Vec<FamilyMember> {
+        fn is_stale(
+            graph: &DerivationGraph,
+            member: &FamilyMember,
+            changed_at: &HashMap<FamilyMember, DateTime<Utc>>,
+            known: &mut HashMap<FamilyMember, bool>,
+        ) -> bool {
+            if let Some(stale) = known.get(member) {
+                return *stale;
+            }
+            let stale = graph.parents_of(member).iter().any(|step| {
+                changed_at
+                    .get(step.parent())
+                    .is_some_and(|changed| *changed > step.produced_at())
+                    || is_stale(graph, step.parent(), changed_at, known)
+            });
+            known.insert(member.clone(), stale);
+            stale
+        }
+
+        let mut known = HashMap::new();
+        self.members()
+            .into_iter()
+            .filter(|member| is_stale(self, member, changed_at, &mut known))
+            .collect()
+    }
+
+    /// Members on a path of steps from `from` to `to`, both included
+    fn path_between(&self, from: &FamilyMember, to: &FamilyMember) -> Option<Vec<FamilyMember>> {
+        fn search(
+            graph: &DerivationGraph,
+            current: &FamilyMember,
+            to: &FamilyMember,
+            visited: &mut HashSet<FamilyMember>,
+            path: &mut Vec<FamilyMember>,
+        ) -> bool {
+            path.push(current.clone());
+            if current == to {
+                return true;
+            }
+            if visited.insert(current.clone()) {
+                for step in graph.children_of(current) {
+                    if search(graph, step.child(), to, visited, path) {
+                        return true;
+                    }
+                }
+            }
+            path.pop();
+            false
+        }
+
+        let mut path = Vec::new();
+        search(self, from, to, &mut HashSet::new(), &mut path).then_some(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+    use serde_json::json;
+
+    fn derivative(name: &str) -> FamilyMember {
+        FamilyMember::derivative(name).unwrap()
+    }
+
+    fn step(parent: &FamilyMember, child: &FamilyMember) -> DerivationStep {
+        DerivationStep::new(parent.clone(), child.clone(), "test", json!({}), "1.0").unwrap()
+    }
+
+    #[test]
+    fn test_rejects_cycles() {
+        let extracted = FamilyMember::extracted();
+        let summary = derivative("summary.det");
+        let anonymized = derivative("anonymized.det");
+
+        let mut graph = DerivationGraph::new(ProjectId::new(), DocumentId::new());
+        graph
+            .record(step(&FamilyMember::Original, &extracted))
+            .unwrap();
+        graph.record(step(&extracted, &summary)).unwrap();
+        graph.record(step(&summary, &anonymized)).unwrap();
+
+        match graph.record(step(&anonymized, &extracted)) {
+            Err(DerivationError::Cycle { path, .. }) => assert_eq!(
+                path,
+                vec![
+                    "anonymized.det",
+                    "extracted.det",
+                    "summary.det",
+                    "anonymized.det"
+                ]
+            ),
+            other => panic!("expected a cycle, got {:?}", other),
+        }
+        assert!(
+            DerivationStep::new(summary.clone(), summary.clone(), "x", json!({}), "1").is_err()
+        );
+        assert!(DerivationStep::new(summary, FamilyMember::Original, "x", json!({}), "1").is_err());
+
+        // Re-recording an edge replaces it instead of duplicating it
+        graph
+            .record(step(&extracted, &derivative("summary.det")))
+            .unwrap();
+        assert_eq!(graph.steps().len(), 3);
+        assert_eq!(graph.generation(&anonymized), 3);
+        assert_eq!(
+            graph
+                .chain_to(&anonymized)
+                .iter()
+                .map(|s| s.child().key())
+                .collect::<Vec<_>>(),
+            vec!["extracted.det", "summary.det", "anonymized.det"]
+        );
+    }
+
+    #[test]
+    fn test_stale_members_follow_the_chain() {
+        let extracted = FamilyMember::extracted();
+        let summary = derivative("summary.det");
+        let table = derivative("costs.det");
+
+        let mut graph = DerivationGraph::new(ProjectId::new(), DocumentId::new());
+        graph
+            .record(step(&FamilyMember::Original, &extracted))
+            .unwrap();
+        graph.record(step(&extracted, &summary)).unwrap();
+        graph.record(step(&FamilyMember::Original, &table)).unwrap();
+
+        let produced = graph.steps()[0].produced_at();
+        let mut changed_at = HashMap::new();
+        changed_at.insert(FamilyMember::Original, produced - Duration::hours(1));
+        assert!(graph.stale_members(&changed_at).is_empty());
+
+        changed_at.insert(extracted.clone(), produced + Duration::hours(1));
+        assert_eq!(graph.stale_members(&changed_at), vec![summary.clone()]);
+
+        changed_at.insert(FamilyMember::Original, produced + Duration::hours(1));
+        assert_eq!(
+            graph.stale_members(&changed_at),
+            vec![extracted, summary, table]
+        );
+    }
+}
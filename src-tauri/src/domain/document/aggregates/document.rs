@@ -0,0 +1,253 @@
+use chrono::{DateTime, Utc};
+use std::path::Path;
+
+use super::super::value_objects::document_id::DocumentId;
+use crate::domain::project::ProjectId;
+use crate::domain::workspace::value_objects::ContentHash;
+
+/// Document aggregate root representing one source file of a project
+///
+/// Business Rules:
+/// - A document keeps its identifier for its whole lifetime
+/// - The path is the file's current location and changes on rename or move
+/// - The content hash, size and mtime describe the version last seen
+/// - A document whose file disappears is marked missing rather than deleted,
+///   so anything attached to it survives until the file reappears
+/// - A child document (an email attachment) is stored with the derivatives
+///   of its parent and is not part of the source folder
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Document {
+    id: DocumentId,
+    project_id: ProjectId,
+    path: String,
+    content_hash: ContentHash,
+    size: u64,
+    modified_ns: i64,
+    missing: bool,
+    parent_id: Option<DocumentId>,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+}
+
+impl Document {
+    /// Register a newly discovered file
+    pub fn new(
+        project_id: ProjectId,
+        path: String,
+        content_hash: ContentHash,
+        size: u64,
+        modified_ns: i64,
+    ) -> Self {
+        let now = Utc::now();
+        Document {
+            id: DocumentId::new(),
+            project_id,
+            path,
+            content_hash,
+            size,
+            modified_ns,
+            missing: false,
+            parent_id: None,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    /// Register a file taken out of another document, such as an email
+    /// attachment
+    pub fn child(
+        parent: &Document,
+        path: String,
+        content_hash: ContentHash,
+        size: u64,
+        modified_ns: i64,
+    ) -> Self {
+        Document {
+            parent_id: Some(parent.id.clone()),
+            ..Document::new(
+                parent.project_id.clone(),
+                path,
+                content_hash,
+                size,
+                modified_ns,
+            )
+        }
+    }
+
+    /// Create a Document from existing data (for repository reconstruction)
+    #[allow(clippy::too_many_arguments)]
+    pub fn from_data(
+        id: DocumentId,
+        project_id: ProjectId,
+        path: String,
+        content_hash: ContentHash,
+        size: u64,
+        modified_ns: i64,
+        missing: bool,
+        parent_id: Option<DocumentId>,
+        created_at: DateTime<Utc>,
+        updated_at: DateTime<Utc>,
+    ) -> Self {
+        Document {
+            id,
+            project_id,
+            path,
+            content_hash,
+            size,
+            modified_ns,
+            missing,
+            parent_id,
+            created_at,
+            updated_at,
+        }
+    }
+
+    pub fn id(&self) -> &DocumentId {
+        &self.id
+    }
+
+    pub fn project_id(&self) -> &ProjectId {
+        &self.project_id
+    }
+
+    /// Current absolute path of the file
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// File name component of the current path
+    pub fn file_name(&self) -> Option<&str> {
+        Path::new(&self.path).file_name().and_then(|n| n.to_str())
+    }
+
+    pub fn content_hash(&self) -> &ContentHash {
+        &self.content_hash
+    }
+
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+
+    pub fn modified_ns(&self) -> i64 {
+        self.modified_ns
+    }
+
+    /// Whether the file could not be found during the last reconciliation
+    pub fn is_missing(&self) -> bool {
+        self.missing
+    }
+
+    /// The document this one was taken out of, for child documents
+    pub fn parent_id(&self) -> Option<&DocumentId> {
+        self.parent_id.as_ref()
+    }
+
+    pub fn created_at(&self) -> DateTime<Utc> {
+        self.created_at
+    }
+
+    pub fn updated_at(&self) -> DateTime<Utc> {
+        self.updated_at
+    }
+
+    /// Whether the recorded version matches a file with this size and mtime
+    pub fn is_current(&self, size: u64, modified_ns: i64) -> bool {
+        !self.missing && self.size == size && self.modified_ns == modified_ns
+    }
+
+    /// Record that the file was edited in place
+    pub fn update_contents(&mut self, content_hash: ContentHash, size: u64, modified_ns: i64) {
+        self.content_hash = content_hash;
+        self.size = size;
+        self.modified_ns = modified_ns;
+        self.missing = false;
+        self.updated_at = Utc::now();
+    }
+
+    /// Record that the file now lives at a different path
+    pub fn relink(&mut self, path: String, size: u64, modified_ns: i64) {
+        self.path = path;
+        self.size = size;
+        self.modified_ns = modified_ns;
+        self.missing = false;
+        self.updated_at = Utc::now();
+    }
+
+    /// Record that the file can no longer be found
+    pub fn mark_missing(&mut self) {
+        if !self.missing {
+            self.missing = true;
+            self.updated_at = Utc::now();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::workspace::value_objects::HashAlgorithm;
+
+    fn create_test_document() -> Document {
+        Document::new(
+            ProjectId::new(),
+            "/corpus/letters/offer.pdf".to_string(),
+            ContentHash::of_bytes(HashAlgorithm::Blake3, b"offer"),
+            5,
+            100,
+        )
+    }
+
+    #[test]
+    fn test_new_document() {
+        let document = create_test_document();
+        assert!(document.id().value().starts_with("document_"));
+        assert_eq!(document.file_name(), Some("offer.pdf"));
+        assert!(!document.is_missing());
+        assert!(document.is_current(5, 100));
+        assert!(!document.is_current(5, 101));
+    }
+
+    #[test]
+    fn test_relink_keeps_identity() {
+        let mut document = create_test_document();
+        let id = document.id().clone();
+        let hash = document.content_hash().clone();
+
+        document.mark_missing();
+        assert!(document.is_missing());
+        assert!(!document.is_current(5, 100));
+
+        document.relink("/corpus/archive/offer-final.pdf".to_string(), 5, 200);
+        assert_eq!(document.id(), &id);
+        assert_eq!(document.content_hash(), &hash);
+        assert_eq!(document.path(), "/corpus/archive/offer-final.pdf");
+        assert!(!document.is_missing());
+    }
+
+    #[test]
+    fn test_update_contents() {
+        let mut document = create_test_document();
+        let new_hash = ContentHash::of_bytes(HashAlgorithm::Blake3, b"revised offer");
+
+        document.update_contents(new_hash.clone(), 13, 300);
+        assert_eq!(document.content_hash(), &new_hash);
+        assert!(document.is_current(13, 300));
+    }
+
+    #[test]
+    fn test_child_document() {
+        let email = create_test_document();
+        let attachment = Document::child(
+            &email,
+            "/derivatives/attachments/0-0/invoice.pdf".to_string(),
+            ContentHash::of_bytes(HashAlgorithm::Blake3, b"invoice"),
+            7,
+            100,
+        );
+
+        assert_ne!(attachment.id(), email.id());
+        assert_eq!(attachment.parent_id(), Some(email.id()));
+        assert_eq!(attachment.project_id(), email.project_id());
+        assert_eq!(email.parent_id(), None);
+    }
+}
@@ -0,0 +1,5 @@
+pub mod derivation_graph;
+pub mod document;
+
+pub use derivation_graph::DerivationGraph;
+pub use document::Document;
@@ -0,0 +1,18 @@
+use thiserror::Error;
+
+/// Business rule violations of a document's derivation graph
+#[derive(Debug, Error)]
+pub enum DerivationError {
+    #[error("Invalid family member '{0}'")]
+    InvalidMember(String),
+
+    #[error("Invalid derivation step: {0}")]
+    InvalidStep(String),
+
+    #[error("Deriving '{child}' from '{parent}' would create a cycle: {}", path.join(" -> "))]
+    Cycle {
+        parent: String,
+        child: String,
+        path: Vec<String>,
+    },
+}
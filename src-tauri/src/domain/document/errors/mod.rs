@@ -0,0 +1,3 @@
+pub mod derivation_error;
+
+pub use derivation_error::DerivationError;
@@ -0,0 +1,12 @@
+pub mod aggregates;
+pub mod errors;
+pub mod repositories;
+pub mod value_objects;
+
+// Re-export commonly used types
+pub use aggregates::{DerivationGraph, Document};
+pub use errors::DerivationError;
+pub use repositories::{DerivationRepository, DocumentRepository};
+pub use value_objects::{
+    DerivationStep, DerivativeFamily, DocumentId, DocumentIdError, FamilyMember,
+};
@@ -0,0 +1,20 @@
+use async_trait::async_trait;
+
+use super::super::aggregates::DerivationGraph;
+use super::super::value_objects::DocumentId;
+use crate::domain::project::ProjectId;
+use crate::domain::workspace::repositories::RepositoryError;
+
+/// Repository trait for the derivation graphs of document families
+#[async_trait]
+pub trait DerivationRepository: Send + Sync {
+    /// Load the graph of a document; a family without steps gives an empty graph
+    async fn find_by_document(
+        &self,
+        project_id: &ProjectId,
+        document_id: &DocumentId,
+    ) -> Result<DerivationGraph, RepositoryError>;
+
+    /// Save a graph, replacing the steps stored for its document
+    async fn save(&self, graph: &DerivationGraph) -> Result<(), RepositoryError>;
+}
@@ -0,0 +1,32 @@
+use async_trait::async_trait;
+
+use super::super::aggregates::document::Document;
+use super::super::value_objects::document_id::DocumentId;
+use crate::domain::project::ProjectId;
+use crate::domain::workspace::repositories::RepositoryError;
+
+/// Repository trait for Document aggregate persistence
+#[async_trait]
+pub trait DocumentRepository: Send + Sync {
+    /// Insert or update a document
+    async fn save(&self, document: &Document) -> Result<(), RepositoryError>;
+
+    /// Insert or update several documents in one transaction
+    async fn save_all(&self, documents: &[Document]) -> Result<(), RepositoryError>;
+
+    /// Find a document by its identifier
+    async fn find_by_id(&self, id: &DocumentId) -> Result<Option<Document>, RepositoryError>;
+
+    /// Find the document currently at a path within a project
+    async fn find_by_path(
+        &self,
+        project_id: &ProjectId,
+        path: &str,
+    ) -> Result<Option<Document>, RepositoryError>;
+
+    /// List all documents of a project, including missing ones
+    async fn list_by_project(
+        &self,
+        project_id: &ProjectId,
+    ) -> Result<Vec<Document>, RepositoryError>;
+}
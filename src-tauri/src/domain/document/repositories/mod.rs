@@ -0,0 +1,5 @@
+pub mod derivation_repository;
+pub mod document_repository;
+
+pub use derivation_repository::DerivationRepository;
+pub use document_repository::DocumentRepository;
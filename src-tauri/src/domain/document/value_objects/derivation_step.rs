@@ -0,0 +1,101 @@
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+
+use super::family_member::FamilyMember;
+use crate::domain::document::errors::DerivationError;
+
+/// One edge of a derivation graph: a family member produced from another
+///
+/// Records how the child was made — the operation, its parameters and the
+/// version of the tool that ran it — and when, so the child can be flagged
+/// once its parent changes afterwards.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DerivationStep {
+    parent: FamilyMember,
+    child: FamilyMember,
+    operation: String,
+    parameters: Value,
+    tool_version: String,
+    produced_at: DateTime<Utc>,
+}
+
+impl DerivationStep {
+    /// Record a step that has just been performed
+    pub fn new(
+        parent: FamilyMember,
+        child: FamilyMember,
+        operation: impl Into<String>,
+        parameters: Value,
+        tool_version: impl Into<String>,
+    ) -> Result<Self, DerivationError> {
+        Self::from_data(
+            parent,
+            child,
+            operation.into(),
+            parameters,
+            tool_version.into(),
+            Utc::now(),
+        )
+    }
+
+    /// Create a DerivationStep from existing data (for repository reconstruction)
+    pub fn from_data(
+        parent: FamilyMember,
+        child: FamilyMember,
+        operation: String,
+        parameters: Value,
+        tool_version: String,
+        produced_at: DateTime<Utc>,
+    ) -> Result<Self, DerivationError> {
+        if child.is_original() {
+            return Err(DerivationError::InvalidStep(
+                "the original cannot be derived from another file".to_string(),
+            ));
+        }
+        if parent == child {
+            return Err(DerivationError::Cycle {
+                parent: parent.to_string(),
+                child: child.to_string(),
+                path: vec![parent.to_string(), child.to_string()],
+            });
+        }
+        if operation.trim().is_empty() {
+            return Err(DerivationError::InvalidStep(
+                "the operation is empty".to_string(),
+            ));
+        }
+
+        Ok(DerivationStep {
+            parent,
+            child,
+            operation: operation.trim().to_string(),
+            parameters,
+            tool_version,
+            produced_at,
+        })
+    }
+
+    pub fn parent(&self) -> &FamilyMember {
+        &self.parent
+    }
+
+    pub fn child(&self) -> &FamilyMember {
+        &self.child
+    }
+
+    pub fn operation(&self) -> &str {
+        &self.operation
+    }
+
+    pub fn parameters(&self) -> &Value {
+        &self.parameters
+    }
+
+    pub fn tool_version(&self) -> &str {
+        &self.tool_version
+    }
+
+    pub fn produced_at(&self) -> DateTime<Utc> {
+        self.produced_at
+    }
+}
@@ -0,0 +1,69 @@
+use std::path::{Path, PathBuf};
+
+use super::document_id::DocumentId;
+use crate::domain::project::ProjectId;
+
+/// Folder holding everything derived from one source document
+///
+/// Laid out as `<derivatives root>/<project id>/<document id>/`, keyed by the
+/// stable document identifier so the family survives renames of the
+/// original. Source folders are never written to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DerivativeFamily {
+    folder: PathBuf,
+}
+
+impl DerivativeFamily {
+    /// Base extraction of the original
+    pub const EXTRACTED_FILE: &'static str = "extracted.det";
+
+    /// Anonymized copy of the base extraction
+    pub const ANONYMIZED_FILE: &'static str = "anonymized.det";
+
+    /// Folder holding the files taken out of the original, such as email
+    /// attachments
+    pub const ATTACHMENTS_FOLDER: &'static str = "attachments";
+
+    /// Family folder of a document below the derivatives root
+    pub fn new(derivatives_root: &Path, project_id: &ProjectId, document_id: &DocumentId) -> Self {
+        DerivativeFamily {
+            folder: derivatives_root
+                .join(project_id.value())
+                .join(document_id.value()),
+        }
+    }
+
+    pub fn folder(&self) -> &Path {
+        &self.folder
+    }
+
+    /// Path of a derivative file in this family
+    pub fn file(&self, file_name: &str) -> PathBuf {
+        self.folder.join(file_name)
+    }
+
+    /// Path of the base extraction
+    pub fn extracted_path(&self) -> PathBuf {
+        self.file(Self::EXTRACTED_FILE)
+    }
+
+    /// Path of the anonymized derivative
+    pub fn anonymized_path(&self) -> PathBuf {
+        self.file(Self::ANONYMIZED_FILE)
+    }
+
+    /// Folder of the extracted attachments
+    pub fn attachments_folder(&self) -> PathBuf {
+        self.folder.join(Self::ATTACHMENTS_FOLDER)
+    }
+
+    /// Path of one attachment of one message
+    ///
+    /// Each attachment gets its own folder, so attachments with the same
+    /// name keep their names.
+    pub fn attachment_path(&self, message: u32, attachment: usize, file_name: &str) -> PathBuf {
+        self.attachments_folder()
+            .join(format!("{}-{}", message + 1, attachment + 1))
+            .join(file_name)
+    }
+}
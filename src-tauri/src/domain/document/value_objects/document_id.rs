@@ -0,0 +1,108 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use uuid::Uuid;
+
+/// DocumentId value object identifying a source document independent of its path
+///
+/// All document identifiers use the format: document_xxxxxxxx-xxxx-xxxx-xxxx-xxxxxxxxxxxx
+/// The identifier is assigned when a file is first seen and kept when the
+/// file is renamed or moved, so it is safe to use as a folder name under
+/// `/derivatives/` and as the anchor for tags, annotations and caddies.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct DocumentId(String);
+
+impl DocumentId {
+    const PREFIX: &'static str = "document_";
+
+    /// Create a new DocumentId with a generated UUID
+    pub fn new() -> Self {
+        let uuid = Uuid::new_v4();
+        DocumentId(format!("{}{}", Self::PREFIX, uuid))
+    }
+
+    /// Create a DocumentId from an existing string, validating the prefix format
+    pub fn from_string(value: String) -> Result<Self, DocumentIdError> {
+        let uuid_part = value
+            .strip_prefix(Self::PREFIX)
+            .ok_or(DocumentIdError::InvalidFormat)?;
+
+        if uuid_part.parse::<Uuid>().is_err() {
+            return Err(DocumentIdError::InvalidUuid);
+        }
+
+        Ok(DocumentId(value))
+    }
+
+    /// Get the string value of this DocumentId
+    pub fn value(&self) -> &str {
+        &self.0
+    }
+
+    /// Extract just the UUID part (without the document_ prefix)
+    pub fn uuid_part(&self) -> &str {
+        self.0.strip_prefix(Self::PREFIX).unwrap_or(&self.0)
+    }
+}
+
+impl Default for DocumentId {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Display for DocumentId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl From<DocumentId> for String {
+    fn from(id: DocumentId) -> Self {
+        id.0
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum DocumentIdError {
+    #[error("Invalid document ID format - must start with 'document_'")]
+    InvalidFormat,
+    #[error("Invalid UUID in document ID")]
+    InvalidUuid,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new_document_id_has_correct_format() {
+        let id = DocumentId::new();
+        assert!(id.value().starts_with("document_"));
+        assert_eq!(id.value().len(), 45); // "document_" (9) + UUID (36) = 45
+    }
+
+    #[test]
+    fn test_from_string_validation() {
+        let uuid = Uuid::new_v4();
+        let id = DocumentId::from_string(format!("document_{}", uuid)).unwrap();
+        assert_eq!(id.uuid_part(), uuid.to_string());
+
+        assert!(matches!(
+            DocumentId::from_string(format!("doc_{}", uuid)),
+            Err(DocumentIdError::InvalidFormat)
+        ));
+        assert!(matches!(
+            DocumentId::from_string("document_not-a-uuid".to_string()),
+            Err(DocumentIdError::InvalidUuid)
+        ));
+    }
+
+    #[test]
+    fn test_document_id_serialization() {
+        let id = DocumentId::new();
+        let serialized = serde_json::to_string(&id).unwrap();
+        let deserialized: DocumentId = serde_json::from_str(&serialized).unwrap();
+
+        assert_eq!(id, deserialized);
+    }
+}
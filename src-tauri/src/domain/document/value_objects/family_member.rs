@@ -0,0 +1,63 @@
+use std::fmt;
+
+use super::derivative_family::DerivativeFamily;
+use crate::domain::document::errors::DerivationError;
+
+/// A file of a document family: the original or one of its derivatives
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum FamilyMember {
+    /// The source file itself
+    Original,
+    /// A derivative, by its file name in the family folder
+    Derivative(String),
+}
+
+impl FamilyMember {
+    /// Key of the original in storage and in graph views
+    pub const ORIGINAL_KEY: &'static str = "original";
+
+    /// Derivative file of the family; the name must be a plain file name
+    pub fn derivative(file_name: impl Into<String>) -> Result<Self, DerivationError> {
+        let file_name = file_name.into();
+        if file_name.trim().is_empty()
+            || file_name == Self::ORIGINAL_KEY
+            || file_name.contains(['/', '\\'])
+            || file_name == "."
+            || file_name == ".."
+        {
+            return Err(DerivationError::InvalidMember(file_name));
+        }
+        Ok(FamilyMember::Derivative(file_name))
+    }
+
+    /// The base extraction of the original
+    pub fn extracted() -> Self {
+        FamilyMember::Derivative(DerivativeFamily::EXTRACTED_FILE.to_string())
+    }
+
+    /// Parse a key produced by `key`
+    pub fn parse(key: &str) -> Result<Self, DerivationError> {
+        if key == Self::ORIGINAL_KEY {
+            Ok(FamilyMember::Original)
+        } else {
+            Self::derivative(key)
+        }
+    }
+
+    pub fn key(&self) -> &str {
+        match self {
+            FamilyMember::Original => Self::ORIGINAL_KEY,
+            FamilyMember::Derivative(file_name) => file_name,
+        }
+    }
+
+    pub fn is_original(&self) -> bool {
+        matches!(self, FamilyMember::Original)
+    }
+}
+
+impl fmt::Display for FamilyMember {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.key())
+    }
+}
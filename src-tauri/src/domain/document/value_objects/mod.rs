@@ -0,0 +1,9 @@
+pub mod derivation_step;
+pub mod derivative_family;
+pub mod document_id;
+pub mod family_member;
+
+pub use derivation_step::DerivationStep;
+pub use derivative_family::DerivativeFamily;
+pub use document_id::{DocumentId, DocumentIdError};
+pub use family_member::FamilyMember;
@@ -0,0 +1,117 @@
+use chrono::{DateTime, Utc};
+
+use crate::domain::document::DocumentId;
+use crate::domain::email::value_objects::EmailHeaders;
+use crate::domain::project::ProjectId;
+
+/// Stored headers of one message of a tracked email document
+///
+/// EML and MSG files hold one message, MBOX files any number; `index` is
+/// the message's position in its file. Attachments are tracked as child
+/// documents of the email document. Records keep the content hash they
+/// were read from, so a project run only parses files whose contents
+/// changed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EmailMessage {
+    document_id: DocumentId,
+    project_id: ProjectId,
+    index: u32,
+    content_hash: String,
+    method: String,
+    headers: EmailHeaders,
+    attachment_ids: Vec<DocumentId>,
+    parsed_at: DateTime<Utc>,
+}
+
+impl EmailMessage {
+    /// Record a message parsed just now
+    pub fn new(
+        document_id: DocumentId,
+        project_id: ProjectId,
+        index: u32,
+        content_hash: String,
+        method: impl Into<String>,
+        headers: EmailHeaders,
+        attachment_ids: Vec<DocumentId>,
+    ) -> Self {
+        Self::from_data(
+            document_id,
+            project_id,
+            index,
+            content_hash,
+            method.into(),
+            headers,
+            attachment_ids,
+            Utc::now(),
+        )
+    }
+
+    /// Reconstruct a message from storage
+    #[allow(clippy::too_many_arguments)]
+    pub fn from_data(
+        document_id: DocumentId,
+        project_id: ProjectId,
+        index: u32,
+        content_hash: String,
+        method: String,
+        headers: EmailHeaders,
+        attachment_ids: Vec<DocumentId>,
+        parsed_at: DateTime<Utc>,
+    ) -> Self {
+        EmailMessage {
+            document_id,
+            project_id,
+            index,
+            content_hash,
+            method,
+            headers,
+            attachment_ids,
+            parsed_at,
+        }
+    }
+
+    pub fn document_id(&self) -> &DocumentId {
+        &self.document_id
+    }
+
+    pub fn project_id(&self) -> &ProjectId {
+        &self.project_id
+    }
+
+    /// Position of the message in its file, from 0
+    pub fn index(&self) -> u32 {
+        self.index
+    }
+
+    /// Identifies the message among all stored ones, as
+    /// `<document id>#<index>`
+    pub fn key(&self) -> String {
+        format!("{}#{}", self.document_id, self.index)
+    }
+
+    pub fn content_hash(&self) -> &str {
+        &self.content_hash
+    }
+
+    pub fn method(&self) -> &str {
+        &self.method
+    }
+
+    pub fn headers(&self) -> &EmailHeaders {
+        &self.headers
+    }
+
+    /// Child documents holding the message's attachments, in order
+    pub fn attachment_ids(&self) -> &[DocumentId] {
+        &self.attachment_ids
+    }
+
+    pub fn parsed_at(&self) -> DateTime<Utc> {
+        self.parsed_at
+    }
+
+    /// Whether the message was read from contents with this hash
+    pub fn is_current(&self, content_hash: &str) -> bool {
+        self.content_hash == content_hash
+    }
+}
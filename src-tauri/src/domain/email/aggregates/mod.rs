@@ -0,0 +1,3 @@
+pub mod email_message;
+
+pub use email_message::EmailMessage;
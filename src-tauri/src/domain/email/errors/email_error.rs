@@ -0,0 +1,31 @@
+use thiserror::Error;
+
+/// Errors raised while reading an email file
+///
+/// Missing headers are not errors; these mean the file is not a readable
+/// message or mailbox of its format.
+#[derive(Debug, Error)]
+pub enum EmailError {
+    #[error("'.{extension}' files are not email files")]
+    UnsupportedFormat { extension: String },
+
+    #[error("The {format} file is damaged or not a valid {format} file: {reason}")]
+    InvalidFile { format: String, reason: String },
+}
+
+impl EmailError {
+    /// Create an UnsupportedFormat error for a file extension
+    pub fn unsupported(extension: impl Into<String>) -> Self {
+        EmailError::UnsupportedFormat {
+            extension: extension.into(),
+        }
+    }
+
+    /// Create an InvalidFile error for a format
+    pub fn invalid(format: impl Into<String>, reason: impl ToString) -> Self {
+        EmailError::InvalidFile {
+            format: format.into(),
+            reason: reason.to_string(),
+        }
+    }
+}
@@ -0,0 +1,3 @@
+pub mod email_error;
+
+pub use email_error::EmailError;
@@ -0,0 +1,12 @@
+pub mod aggregates;
+pub mod errors;
+pub mod repositories;
+pub mod threading;
+pub mod value_objects;
+
+// Re-export commonly used types
+pub use aggregates::EmailMessage;
+pub use errors::EmailError;
+pub use repositories::EmailRepository;
+pub use threading::reconstruct_threads;
+pub use value_objects::{EmailAttachment, EmailHeaders, EmailThread, ParsedEmail};
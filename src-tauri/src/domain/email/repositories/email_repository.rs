@@ -0,0 +1,29 @@
+use async_trait::async_trait;
+
+use crate::domain::document::DocumentId;
+use crate::domain::email::aggregates::EmailMessage;
+use crate::domain::project::ProjectId;
+use crate::domain::workspace::repositories::RepositoryError;
+
+/// Repository trait for the parsed messages of email documents
+#[async_trait]
+pub trait EmailRepository: Send + Sync {
+    /// Replace all messages of a document with the given ones
+    async fn replace_messages(
+        &self,
+        document_id: &DocumentId,
+        messages: &[EmailMessage],
+    ) -> Result<(), RepositoryError>;
+
+    /// Messages of a document, in file order
+    async fn find_by_document(
+        &self,
+        document_id: &DocumentId,
+    ) -> Result<Vec<EmailMessage>, RepositoryError>;
+
+    /// All messages of a project, oldest first
+    async fn list_by_project(
+        &self,
+        project_id: &ProjectId,
+    ) -> Result<Vec<EmailMessage>, RepositoryError>;
+}
@@ -0,0 +1,3 @@
+pub mod email_repository;
+
+pub use email_repository::EmailRepository;
@@ -0,0 +1,189 @@
+//! Groups email messages into conversations.
+//!
+//! Messages are linked through their References and In-Reply-To headers,
+//! also when the message they point to is not in the corpus, so replies to
+//! a missing original still end up together. Copies of the same message
+//! (one per custodian mailbox) share a thread. Replies whose client dropped
+//! the headers are joined by subject as a last resort, but only when their
+//! subject carries a reply prefix.
+
+use std::collections::HashMap;
+
+use super::aggregates::EmailMessage;
+use super::value_objects::EmailThread;
+
+/// Union-find over message identifiers
+#[derive(Default)]
+struct Links {
+    parent: HashMap<String, String>,
+}
+
+impl Links {
+    fn find(&mut self, id: &str) -> String {
+        let mut root = id.to_string();
+        while let Some(parent) = self.parent.get(&root).filter(|p| **p != root) {
+            root = parent.clone();
+        }
+        // Point everything on the way straight at the root
+        let mut current = id.to_string();
+        while current != root {
+            let next = self
+                .parent
+                .insert(current, root.clone())
+                .unwrap_or_default();
+            current = next;
+        }
+        root
+    }
+
+    fn union(&mut self, a: &str, b: &str) {
+        let (a, b) = (self.find(a), self.find(b));
+        if a != b {
+            self.parent.insert(b, a);
+        }
+    }
+}
+
+/// Reconstruct the threads of a set of messages, most recently active first
+pub fn reconstruct_threads(messages: Vec<EmailMessage>) -> Vec<EmailThread> {
+    let node = |message: &EmailMessage| {
+        message
+            .headers()
+            .message_id
+            .clone()
+            .unwrap_or_else(|| message.key())
+    };
+
+    let mut links = Links::default();
+    for message in &messages {
+        let id = node(message);
+        for parent in message.headers().parent_ids() {
+            links.union(parent, &id);
+        }
+    }
+
+    // Subject fallback for replies without reply headers
+    let mut by_subject: HashMap<String, String> = HashMap::new();
+    let mut orphans = Vec::new();
+    for message in sorted_by_date(messages.iter()) {
+        let headers = message.headers();
+        let Some(base) = headers.base_subject() else {
+            continue;
+        };
+        let is_reply = headers
+            .subject
+            .as_deref()
+            .is_some_and(|subject| subject.trim().to_lowercase() != base);
+        if is_reply && headers.parent_ids().is_empty() {
+            orphans.push((base, node(message)));
+        } else {
+            by_subject.entry(base).or_insert_with(|| node(message));
+        }
+    }
+    for (base, id) in orphans {
+        if let Some(original) = by_subject.get(&base) {
+            links.union(original, &id);
+        }
+    }
+
+    let mut groups: HashMap<String, Vec<EmailMessage>> = HashMap::new();
+    for message in messages {
+        let root = links.find(&node(&message));
+        groups.entry(root).or_default().push(message);
+    }
+
+    let mut threads: Vec<EmailThread> = groups
+        .into_values()
+        .map(|messages| {
+            let messages: Vec<EmailMessage> = sorted_by_date(messages.iter())
+                .into_iter()
+                .cloned()
+                .collect();
+            let first = &messages[0];
+            EmailThread {
+                id: node(first),
+                subject: first.headers().subject.clone(),
+                messages,
+            }
+        })
+        .collect();
+    threads.sort_by(|a, b| {
+        b.last_message_at()
+            .cmp(&a.last_message_at())
+            .then_with(|| a.id.cmp(&b.id))
+    });
+    threads
+}
+
+/// Messages by sending date, undated ones last, then by storage key
+fn sorted_by_date<'m>(messages: impl Iterator<Item = &'m EmailMessage>) -> Vec<&'m EmailMessage> {
+    let mut sorted: Vec<&EmailMessage> = messages.collect();
+    sorted.sort_by(|a, b| {
+        let (a_date, b_date) = (a.headers().date, b.headers().date);
+        a_date
+            .is_none()
+            .cmp(&b_date.is_none())
+            .then(a_date.cmp(&b_date))
+            .then_with(|| a.key().cmp(&b.key()))
+    });
+    sorted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::document::DocumentId;
+    use crate::domain::email::EmailHeaders;
+    use crate::domain::project::ProjectId;
+    use chrono::{TimeZone, Utc};
+
+    fn message(id: Option<&str>, subject: &str, day: u32, references: &[&str]) -> EmailMessage {
+        EmailMessage::new(
+            DocumentId::new(),
+            ProjectId::new(),
+            0,
+            "blake3:abc".to_string(),
+            "test",
+            EmailHeaders {
+                message_id: id.map(str::to_string),
+                subject: Some(subject.to_string()),
+                date: Some(Utc.with_ymd_and_hms(2024, 3, day, 9, 0, 0).unwrap()),
+                references: references.iter().map(|r| r.to_string()).collect(),
+                ..EmailHeaders::default()
+            },
+            Vec::new(),
+        )
+    }
+
+    #[test]
+    fn test_threads_follow_references_and_reply_subjects() {
+        let offer = message(Some("offer@a"), "Offer", 1, &[]);
+        let reply = message(Some("reply@b"), "Re: Offer", 2, &["offer@a"]);
+        // Replies to a message outside the corpus still share a thread
+        let late = message(Some("late@c"), "Re: Offer", 5, &["offer@a", "missing@x"]);
+        let copy = message(Some("reply@b"), "Re: Offer", 2, &["offer@a"]);
+        let bare_reply = message(None, "AW: Invoice", 4, &[]);
+        let invoice = message(Some("invoice@a"), "Invoice", 3, &[]);
+        let unrelated = message(Some("lunch@d"), "Lunch", 6, &[]);
+
+        let threads = reconstruct_threads(vec![
+            late.clone(),
+            bare_reply.clone(),
+            reply,
+            unrelated,
+            copy,
+            invoice.clone(),
+            offer.clone(),
+        ]);
+
+        assert_eq!(threads.len(), 3);
+        assert_eq!(threads[0].id, "lunch@d");
+        assert_eq!(threads[1].id, "offer@a");
+        assert_eq!(threads[1].subject.as_deref(), Some("Offer"));
+        assert_eq!(threads[1].messages.len(), 4);
+        assert_eq!(threads[1].messages[0], offer);
+        assert_eq!(threads[1].messages[3], late);
+        assert_eq!(threads[2].id, "invoice@a");
+        assert_eq!(threads[2].messages, [invoice, bare_reply]);
+    }
+}
@@ -0,0 +1,113 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Envelope headers of one email message
+///
+/// Addresses are kept as written, `Name <address>` or a bare address.
+/// Message identifiers are stored without angle brackets, so they compare
+/// equal however the sending client quoted them.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EmailHeaders {
+    pub from: Option<String>,
+
+    #[serde(default)]
+    pub to: Vec<String>,
+
+    #[serde(default)]
+    pub cc: Vec<String>,
+
+    /// When the message was sent, from the Date header
+    pub date: Option<DateTime<Utc>>,
+
+    pub subject: Option<String>,
+
+    pub message_id: Option<String>,
+
+    /// Messages this one replies to
+    #[serde(default)]
+    pub in_reply_to: Vec<String>,
+
+    /// Earlier messages of the conversation, oldest first
+    #[serde(default)]
+    pub references: Vec<String>,
+}
+
+impl EmailHeaders {
+    /// Strip the angle brackets and whitespace around a message identifier
+    pub fn normalize_id(id: &str) -> Option<String> {
+        let id = id
+            .trim()
+            .trim_start_matches('<')
+            .trim_end_matches('>')
+            .trim();
+        (!id.is_empty()).then(|| id.to_string())
+    }
+
+    /// Split a References or In-Reply-To value into message identifiers
+    pub fn parse_ids(value: &str) -> Vec<String> {
+        value
+            .split(|c: char| c.is_whitespace() || c == ',')
+            .filter_map(Self::normalize_id)
+            .collect()
+    }
+
+    /// Messages this one follows in its conversation: the references,
+    /// then the direct reply target if the references lack it
+    pub fn parent_ids(&self) -> Vec<&str> {
+        let mut ids: Vec<&str> = self.references.iter().map(String::as_str).collect();
+        for id in &self.in_reply_to {
+            if !ids.contains(&id.as_str()) {
+                ids.push(id);
+            }
+        }
+        ids
+    }
+
+    /// Sender and recipients, for matching by address
+    pub fn participants(&self) -> impl Iterator<Item = &str> {
+        self.from
+            .iter()
+            .chain(&self.to)
+            .chain(&self.cc)
+            .map(String::as_str)
+    }
+
+    /// The subject without reply and forward prefixes, for grouping
+    /// messages whose clients dropped the references
+    pub fn base_subject(&self) -> Option<String> {
+        let mut subject = self.subject.as_deref()?.trim();
+        while let Some((prefix, rest)) = subject.split_once(':') {
+            let prefix = prefix.trim().to_lowercase();
+            let prefix =
+                prefix.trim_end_matches(|c: char| c.is_ascii_digit() || "[]()".contains(c));
+            if !matches!(prefix, "re" | "fw" | "fwd" | "aw" | "wg" | "sv" | "vs") {
+                break;
+            }
+            subject = rest.trim();
+        }
+        (!subject.is_empty()).then(|| subject.to_lowercase())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ids_and_subjects() {
+        assert_eq!(
+            EmailHeaders::parse_ids(" <a@example.com>\r\n\t<b@example.com>,c@x "),
+            ["a@example.com", "b@example.com", "c@x"]
+        );
+
+        let headers = EmailHeaders {
+            subject: Some("RE: Fwd: Re[2]: Offer for lot 4".to_string()),
+            in_reply_to: vec!["b".to_string(), "c".to_string()],
+            references: vec!["a".to_string(), "b".to_string()],
+            ..EmailHeaders::default()
+        };
+        assert_eq!(headers.parent_ids(), ["a", "b", "c"]);
+        assert_eq!(headers.base_subject().as_deref(), Some("offer for lot 4"));
+    }
+}
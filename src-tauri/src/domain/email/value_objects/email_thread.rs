@@ -0,0 +1,44 @@
+use chrono::{DateTime, Utc};
+
+use crate::domain::email::aggregates::EmailMessage;
+
+/// A conversation: messages linked by their reply headers, oldest first
+#[derive(Debug, Clone, PartialEq)]
+pub struct EmailThread {
+    /// Message identifier of the oldest message, or its storage key if it
+    /// has none
+    pub id: String,
+
+    pub subject: Option<String>,
+
+    pub messages: Vec<EmailMessage>,
+}
+
+impl EmailThread {
+    /// When the first message was sent
+    pub fn started_at(&self) -> Option<DateTime<Utc>> {
+        self.messages.iter().filter_map(|m| m.headers().date).min()
+    }
+
+    /// When the last message was sent
+    pub fn last_message_at(&self) -> Option<DateTime<Utc>> {
+        self.messages.iter().filter_map(|m| m.headers().date).max()
+    }
+
+    /// Distinct senders and recipients, in order of first appearance
+    pub fn participants(&self) -> Vec<String> {
+        let mut participants: Vec<String> = Vec::new();
+        for message in &self.messages {
+            for address in message.headers().participants() {
+                if !participants.iter().any(|p| p.eq_ignore_ascii_case(address)) {
+                    participants.push(address.to_string());
+                }
+            }
+        }
+        participants
+    }
+
+    pub fn contains(&self, message: &EmailMessage) -> bool {
+        self.messages.iter().any(|m| m.key() == message.key())
+    }
+}
@@ -0,0 +1,7 @@
+pub mod email_headers;
+pub mod email_thread;
+pub mod parsed_email;
+
+pub use email_headers::EmailHeaders;
+pub use email_thread::EmailThread;
+pub use parsed_email::{EmailAttachment, ParsedEmail};
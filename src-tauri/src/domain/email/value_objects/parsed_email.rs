@@ -0,0 +1,51 @@
+use super::email_headers::EmailHeaders;
+
+/// One message read from an EML, MBOX or MSG file
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ParsedEmail {
+    pub headers: EmailHeaders,
+
+    /// Plain text body; HTML-only messages are converted to text
+    pub body: String,
+
+    pub attachments: Vec<EmailAttachment>,
+}
+
+/// A file attached to a message
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EmailAttachment {
+    /// File name as given by the sender, or a generated one
+    pub file_name: String,
+
+    /// MIME type, e.g. "application/pdf"
+    pub content_type: Option<String>,
+
+    pub data: Vec<u8>,
+}
+
+impl EmailAttachment {
+    /// The file name made safe to store: no directories, no characters
+    /// Windows rejects, never empty
+    pub fn safe_file_name(&self) -> String {
+        let name = self
+            .file_name
+            .rsplit(['/', '\\'])
+            .next()
+            .unwrap_or_default()
+            .chars()
+            .map(|c| {
+                if c.is_control() || "<>:\"|?*".contains(c) {
+                    '_'
+                } else {
+                    c
+                }
+            })
+            .collect::<String>();
+        let name = name.trim().trim_matches('.');
+        if name.is_empty() {
+            "attachment".to_string()
+        } else {
+            name.to_string()
+        }
+    }
+}
@@ -0,0 +1,14 @@
+use thiserror::Error;
+
+/// Errors raised while rendering a `.det` document to a deliverable format
+#[derive(Debug, Error)]
+pub enum ExportError {
+    #[error("Unsupported export format '{0}'")]
+    UnsupportedFormat(String),
+
+    #[error("Invalid export options: {0}")]
+    InvalidOptions(String),
+
+    #[error("Export failed: {0}")]
+    Failed(String),
+}
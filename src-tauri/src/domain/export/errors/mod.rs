@@ -0,0 +1,3 @@
+pub mod export_error;
+
+pub use export_error::ExportError;
@@ -0,0 +1,246 @@
+use std::collections::HashSet;
+
+use super::errors::ExportError;
+use super::value_objects::{CitedSource, ExportFormat, ExportOptions, PageFields};
+use crate::domain::det::{DetDocument, PmNode};
+
+/// Renders a prepared `.det` document to one file format
+///
+/// Implementations must be pure Rust and produce the file in memory, so
+/// deliverables are generated locally and can be written atomically.
+pub trait DocumentExporter: Send + Sync {
+    fn format(&self) -> ExportFormat;
+
+    /// Render the document to the bytes of the output file
+    fn export(&self, document: &ExportDocument) -> Result<Vec<u8>, ExportError>;
+}
+
+/// A heading of the document, in document order
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TocEntry {
+    pub level: u8,
+    pub text: String,
+    /// Unique id for links to the heading
+    pub anchor: String,
+}
+
+/// A `.det` document prepared for rendering
+///
+/// Collects what every format needs before walking the content: heading
+/// anchors for the table of contents and the numbered list of cited
+/// sources. Exporters visit headings depth-first, so the n-th heading they
+/// meet has anchor `headings[n]`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExportDocument {
+    pub title: String,
+    pub content: PmNode,
+    pub options: ExportOptions,
+    /// Date shown in page templates, e.g. "2024-09-24"
+    pub date: String,
+    pub headings: Vec<TocEntry>,
+    /// Distinct cited sources in order of first citation
+    pub sources: Vec<CitedSource>,
+}
+
+impl ExportDocument {
+    /// Prepare a document; `fallback_title` is used when it has no title
+    pub fn new(
+        document: &DetDocument,
+        fallback_title: &str,
+        options: ExportOptions,
+        date: impl Into<String>,
+    ) -> Self {
+        let mut headings = Vec::new();
+        let mut sources = Vec::new();
+        let mut anchors = HashSet::new();
+        collect(
+            document.content(),
+            &mut headings,
+            &mut sources,
+            &mut anchors,
+        );
+
+        ExportDocument {
+            title: document
+                .title()
+                .filter(|t| !t.trim().is_empty())
+                .unwrap_or(fallback_title)
+                .to_string(),
+            content: document.content().clone(),
+            options,
+            date: date.into(),
+            headings,
+            sources,
+        }
+    }
+
+    /// Entries of the table of contents; empty if it is turned off
+    pub fn toc(&self) -> Vec<&TocEntry> {
+        if !self.options.include_toc {
+            return Vec::new();
+        }
+        self.headings
+            .iter()
+            .filter(|entry| entry.level <= self.options.toc_depth)
+            .collect()
+    }
+
+    /// Anchor of the n-th heading in document order
+    pub fn heading_anchor(&self, index: usize) -> Option<&str> {
+        self.headings.get(index).map(|entry| entry.anchor.as_str())
+    }
+
+    /// Sources to list after the content; empty if the list is turned off
+    pub fn listed_sources(&self) -> &[CitedSource] {
+        if self.options.include_sources {
+            &self.sources
+        } else {
+            &[]
+        }
+    }
+
+    /// 1-based number of a cited source
+    pub fn source_number(&self, source: &CitedSource) -> Option<usize> {
+        self.sources
+            .iter()
+            .position(|s| s == source)
+            .map(|index| index + 1)
+    }
+
+    /// Citation numbers to print after `inlines[index]`
+    ///
+    /// A citation spanning several text nodes (say, with a bold word
+    /// inside) gets one marker, after its last node. Without a source list
+    /// there is nothing to refer to, so no markers are printed.
+    pub fn citations_after(&self, inlines: &[PmNode], index: usize) -> Vec<usize> {
+        if !self.options.include_sources {
+            return Vec::new();
+        }
+        let next = inlines.get(index + 1);
+        inlines[index]
+            .marks
+            .iter()
+            .filter_map(CitedSource::from_mark)
+            .filter(|source| {
+                !next.is_some_and(|n| {
+                    n.marks
+                        .iter()
+                        .filter_map(CitedSource::from_mark)
+                        .any(|s| &s == source)
+                })
+            })
+            .filter_map(|source| self.source_number(&source))
+            .collect()
+    }
+
+    pub fn page_fields(&self, page: Option<(u32, u32)>) -> PageFields<'_> {
+        PageFields {
+            title: &self.title,
+            date: &self.date,
+            page,
+        }
+    }
+}
+
+fn collect(
+    node: &PmNode,
+    headings: &mut Vec<TocEntry>,
+    sources: &mut Vec<CitedSource>,
+    anchors: &mut HashSet<String>,
+) {
+    if node.node_type == "heading" {
+        let text = node.plain_text();
+        let base = slug(&text);
+        let mut anchor = base.clone();
+        let mut suffix = 2;
+        while !anchors.insert(anchor.clone()) {
+            anchor = format!("{}-{}", base, suffix);
+            suffix += 1;
+        }
+        let level = node
+            .attr("level")
+            .and_then(|l| l.as_u64())
+            .map_or(1, |l| l.clamp(1, 6) as u8);
+        headings.push(TocEntry {
+            level,
+            text,
+            anchor,
+        });
+    }
+
+    for source in node.marks.iter().filter_map(CitedSource::from_mark) {
+        if !sources.contains(&source) {
+            sources.push(source);
+        }
+    }
+    for child in &node.content {
+        collect(child, headings, sources, anchors);
+    }
+}
+
+/// Lower-case, dash-separated anchor for a heading
+fn slug(text: &str) -> String {
+    let mut slug = String::new();
+    for c in text.chars() {
+        if c.is_alphanumeric() {
+            slug.extend(c.to_lowercase());
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    let slug = slug.trim_end_matches('-');
+    if slug.is_empty() {
+        "section".to_string()
+    } else {
+        slug.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::det::{DetKind, PmMark, ProcessingMetadata};
+
+    #[test]
+    fn test_prepares_anchors_toc_and_citation_numbers() {
+        let invoice = CitedSource::new("/corpus/invoice.pdf").with_page(2);
+        let letter = CitedSource::new("/corpus/letter.docx");
+        let cited = vec![
+            PmNode::text("Costs rose ", vec![invoice.to_mark()]),
+            PmNode::text("sharply", vec![invoice.to_mark(), PmMark::bold()]),
+            PmNode::text(" as agreed", vec![letter.to_mark()]),
+            PmNode::text(".", Vec::new()),
+        ];
+        let content = PmNode::doc(vec![
+            PmNode::heading(1, PmNode::text_nodes("Findings", Vec::new())),
+            PmNode::paragraph(cited.clone()),
+            PmNode::heading(2, PmNode::text_nodes("Costs & Fees", Vec::new())),
+            PmNode::heading(4, PmNode::text_nodes("Findings", Vec::new())),
+        ]);
+        let det = DetDocument::new(
+            DetKind::Report,
+            content,
+            ProcessingMetadata::completed("manual"),
+        );
+        let options = ExportOptions {
+            include_toc: true,
+            ..ExportOptions::default()
+        };
+
+        let document = ExportDocument::new(&det, "report", options, "2024-09-24");
+        assert_eq!(document.title, "report");
+        let anchors: Vec<&str> = document
+            .headings
+            .iter()
+            .map(|h| h.anchor.as_str())
+            .collect();
+        assert_eq!(anchors, vec!["findings", "costs-fees", "findings-2"]);
+        assert_eq!(document.toc().len(), 2);
+
+        assert_eq!(document.sources, vec![invoice, letter]);
+        assert!(document.citations_after(&cited, 0).is_empty());
+        assert_eq!(document.citations_after(&cited, 1), vec![1]);
+        assert_eq!(document.citations_after(&cited, 2), vec![2]);
+        assert!(document.citations_after(&cited, 3).is_empty());
+    }
+}
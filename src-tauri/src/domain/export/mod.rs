@@ -0,0 +1,11 @@
+pub mod errors;
+pub mod exporter;
+pub mod value_objects;
+
+// Re-export commonly used types
+pub use errors::ExportError;
+pub use exporter::{DocumentExporter, ExportDocument, TocEntry};
+pub use value_objects::{
+    CitedSource, ExportFormat, ExportOptions, PageFields, PageTemplate, CITATION_MARK,
+    TEMPLATE_PLACEHOLDERS,
+};
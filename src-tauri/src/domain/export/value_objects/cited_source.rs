@@ -0,0 +1,147 @@
+use serde_json::{Map, Value};
+use std::path::Path;
+
+use crate::domain::det::PmMark;
+
+/// Name of the mark that ties a passage to the source it came from
+pub const CITATION_MARK: &str = "citation";
+
+/// A source document cited by a passage of a `.det` document
+///
+/// Read from `citation` marks. Exports number each distinct source once
+/// and link the marker back to the file.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CitedSource {
+    pub path: String,
+    pub document_id: Option<String>,
+    pub page: Option<u32>,
+    /// Text shown in the source list instead of the file name
+    pub label: Option<String>,
+}
+
+impl CitedSource {
+    pub fn new(path: impl Into<String>) -> Self {
+        CitedSource {
+            path: path.into(),
+            document_id: None,
+            page: None,
+            label: None,
+        }
+    }
+
+    pub fn with_document_id(mut self, document_id: impl Into<String>) -> Self {
+        self.document_id = Some(document_id.into());
+        self
+    }
+
+    pub fn with_page(mut self, page: u32) -> Self {
+        self.page = Some(page);
+        self
+    }
+
+    pub fn with_label(mut self, label: impl Into<String>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+
+    /// Read a `citation` mark; other marks give `None`
+    pub fn from_mark(mark: &PmMark) -> Option<Self> {
+        if mark.mark_type != CITATION_MARK {
+            return None;
+        }
+        let attrs = mark.attrs.as_ref()?;
+        let text = |key: &str| attrs.get(key).and_then(Value::as_str).map(str::to_string);
+
+        Some(CitedSource {
+            path: text("path")?,
+            document_id: text("documentId"),
+            page: attrs
+                .get("page")
+                .and_then(Value::as_u64)
+                .and_then(|page| u32::try_from(page).ok()),
+            label: text("label"),
+        })
+    }
+
+    pub fn to_mark(&self) -> PmMark {
+        let mut attrs = Map::new();
+        attrs.insert("path".to_string(), Value::String(self.path.clone()));
+        if let Some(id) = &self.document_id {
+            attrs.insert("documentId".to_string(), Value::String(id.clone()));
+        }
+        if let Some(page) = self.page {
+            attrs.insert("page".to_string(), Value::from(page));
+        }
+        if let Some(label) = &self.label {
+            attrs.insert("label".to_string(), Value::String(label.clone()));
+        }
+        PmMark {
+            mark_type: CITATION_MARK.to_string(),
+            attrs: Some(attrs),
+        }
+    }
+
+    /// Label and page as shown in a source list, e.g. "invoice.pdf, p. 3"
+    pub fn display_label(&self) -> String {
+        let name = self.label.clone().unwrap_or_else(|| {
+            Path::new(&self.path)
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_else(|| self.path.clone())
+        });
+        match self.page {
+            Some(page) => format!("{}, p. {}", name, page),
+            None => name,
+        }
+    }
+
+    /// `file://` URL of the source
+    pub fn file_url(&self) -> String {
+        let path = self.path.replace('\\', "/");
+        let mut url = String::from("file://");
+        if !path.starts_with('/') {
+            url.push('/');
+        }
+        for (index, byte) in path.bytes().enumerate() {
+            match byte {
+                b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'/' | b'-' | b'_' | b'.' | b'~' => {
+                    url.push(byte as char)
+                }
+                // Drive letter of a Windows path
+                b':' if index == 1 => url.push(':'),
+                _ => url.push_str(&format!("%{:02X}", byte)),
+            }
+        }
+        if let Some(page) = self.page {
+            url.push_str(&format!("#page={}", page));
+        }
+        url
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mark_round_trip_and_display() {
+        let source = CitedSource::new("/corpus/Letters/Invoice 3.pdf")
+            .with_document_id("document_1")
+            .with_page(3);
+
+        let mark = source.to_mark();
+        assert_eq!(mark.mark_type, "citation");
+        assert_eq!(CitedSource::from_mark(&mark).unwrap(), source);
+        assert_eq!(CitedSource::from_mark(&PmMark::bold()), None);
+
+        assert_eq!(source.display_label(), "Invoice 3.pdf, p. 3");
+        assert_eq!(
+            source.file_url(),
+            "file:///corpus/Letters/Invoice%203.pdf#page=3"
+        );
+        assert_eq!(
+            CitedSource::new(r"C:\corpus\a.pdf").file_url(),
+            "file:///C:/corpus/a.pdf"
+        );
+    }
+}
@@ -0,0 +1,59 @@
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Display};
+
+use crate::domain::export::errors::ExportError;
+
+/// File format of an exported document
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    Markdown,
+    Html,
+    Docx,
+    Pdf,
+}
+
+impl ExportFormat {
+    pub const ALL: [ExportFormat; 4] = [
+        ExportFormat::Markdown,
+        ExportFormat::Html,
+        ExportFormat::Docx,
+        ExportFormat::Pdf,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ExportFormat::Markdown => "markdown",
+            ExportFormat::Html => "html",
+            ExportFormat::Docx => "docx",
+            ExportFormat::Pdf => "pdf",
+        }
+    }
+
+    /// File extension of exported files, without the dot
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Markdown => "md",
+            ExportFormat::Html => "html",
+            ExportFormat::Docx => "docx",
+            ExportFormat::Pdf => "pdf",
+        }
+    }
+
+    /// Parse a format name or file extension
+    pub fn parse(value: &str) -> Result<Self, ExportError> {
+        match value.trim().trim_start_matches('.').to_lowercase().as_str() {
+            "markdown" | "md" => Ok(ExportFormat::Markdown),
+            "html" | "htm" => Ok(ExportFormat::Html),
+            "docx" | "word" => Ok(ExportFormat::Docx),
+            "pdf" => Ok(ExportFormat::Pdf),
+            other => Err(ExportError::UnsupportedFormat(other.to_string())),
+        }
+    }
+}
+
+impl Display for ExportFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
@@ -0,0 +1,187 @@
+use serde::{Deserialize, Serialize};
+
+use crate::domain::export::errors::ExportError;
+
+/// Placeholders a page header or footer may contain
+pub const TEMPLATE_PLACEHOLDERS: [&str; 4] = ["{title}", "{date}", "{page}", "{pages}"];
+
+/// Values substituted into a page template
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PageFields<'a> {
+    pub title: &'a str,
+    pub date: &'a str,
+    /// Current and total page, for paginated formats
+    pub page: Option<(u32, u32)>,
+}
+
+/// Text printed at the top and bottom of every page
+///
+/// Lines may use `{title}`, `{date}`, `{page}` and `{pages}`. Formats
+/// without pages (Markdown, HTML) print the header once at the start and
+/// the footer once at the end, leaving out lines with page numbers.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PageTemplate {
+    #[serde(default)]
+    pub header: Option<String>,
+
+    #[serde(default)]
+    pub footer: Option<String>,
+}
+
+impl PageTemplate {
+    pub fn is_empty(&self) -> bool {
+        self.header.is_none() && self.footer.is_none()
+    }
+
+    /// Check that only known placeholders are used
+    pub fn validate(&self) -> Result<(), ExportError> {
+        for text in self.header.iter().chain(self.footer.iter()) {
+            let mut rest = text.as_str();
+            while let Some(start) = rest.find('{') {
+                let Some(end) = rest[start..].find('}') else {
+                    return Err(ExportError::InvalidOptions(format!(
+                        "unclosed placeholder in page template \"{}\"",
+                        text
+                    )));
+                };
+                let placeholder = &rest[start..start + end + 1];
+                if !TEMPLATE_PLACEHOLDERS.contains(&placeholder) {
+                    return Err(ExportError::InvalidOptions(format!(
+                        "unknown placeholder {} in page template; use {}",
+                        placeholder,
+                        TEMPLATE_PLACEHOLDERS.join(", ")
+                    )));
+                }
+                rest = &rest[start + end + 1..];
+            }
+        }
+        Ok(())
+    }
+
+    pub fn header(&self, fields: &PageFields<'_>) -> Option<String> {
+        self.header
+            .as_deref()
+            .and_then(|text| Self::fill(text, fields))
+    }
+
+    pub fn footer(&self, fields: &PageFields<'_>) -> Option<String> {
+        self.footer
+            .as_deref()
+            .and_then(|text| Self::fill(text, fields))
+    }
+
+    /// Substitute the placeholders of a template
+    ///
+    /// Without page numbers, lines that show them are left out. Returns
+    /// `None` if nothing is left.
+    pub fn fill(text: &str, fields: &PageFields<'_>) -> Option<String> {
+        let (page, pages) = match fields.page {
+            Some((page, pages)) => (page.to_string(), pages.to_string()),
+            None => (String::new(), String::new()),
+        };
+
+        let lines: Vec<String> = text
+            .lines()
+            .filter(|line| {
+                fields.page.is_some() || !(line.contains("{page}") || line.contains("{pages}"))
+            })
+            .map(|line| {
+                line.replace("{title}", fields.title)
+                    .replace("{date}", fields.date)
+                    .replace("{page}", &page)
+                    .replace("{pages}", &pages)
+            })
+            .collect();
+
+        let filled = lines.join("\n");
+        if filled.trim().is_empty() {
+            None
+        } else {
+            Some(filled)
+        }
+    }
+}
+
+/// How a `.det` document is rendered
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ExportOptions {
+    /// Insert a table of contents before the content
+    pub include_toc: bool,
+
+    /// Deepest heading level listed in the table of contents
+    pub toc_depth: u8,
+
+    /// List cited sources after the content
+    pub include_sources: bool,
+
+    pub template: PageTemplate,
+}
+
+impl Default for ExportOptions {
+    fn default() -> Self {
+        ExportOptions {
+            include_toc: false,
+            toc_depth: 3,
+            include_sources: true,
+            template: PageTemplate::default(),
+        }
+    }
+}
+
+impl ExportOptions {
+    pub fn validate(&self) -> Result<(), ExportError> {
+        if !(1..=6).contains(&self.toc_depth) {
+            return Err(ExportError::InvalidOptions(format!(
+                "table of contents depth must be between 1 and 6, got {}",
+                self.toc_depth
+            )));
+        }
+        self.template.validate()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fill_page_template() {
+        let template = PageTemplate {
+            header: Some("{title} ({date})".to_string()),
+            footer: Some("Page {page} of {pages}".to_string()),
+        };
+        template.validate().unwrap();
+
+        let mut fields = PageFields {
+            title: "Findings",
+            date: "2024-09-24",
+            page: Some((2, 5)),
+        };
+        assert_eq!(template.header(&fields).unwrap(), "Findings (2024-09-24)");
+        assert_eq!(template.footer(&fields).unwrap(), "Page 2 of 5");
+
+        fields.page = None;
+        assert_eq!(template.header(&fields).unwrap(), "Findings (2024-09-24)");
+        assert_eq!(template.footer(&fields), None);
+    }
+
+    #[test]
+    fn test_validate_rejects_unknown_placeholders() {
+        let template = PageTemplate {
+            header: Some("{client}".to_string()),
+            footer: None,
+        };
+        assert!(matches!(
+            template.validate(),
+            Err(ExportError::InvalidOptions(_))
+        ));
+
+        let options = ExportOptions {
+            toc_depth: 7,
+            ..ExportOptions::default()
+        };
+        assert!(options.validate().is_err());
+    }
+}
@@ -0,0 +1,7 @@
+pub mod cited_source;
+pub mod export_format;
+pub mod export_options;
+
+pub use cited_source::{CitedSource, CITATION_MARK};
+pub use export_format::ExportFormat;
+pub use export_options::{ExportOptions, PageFields, PageTemplate, TEMPLATE_PLACEHOLDERS};
@@ -0,0 +1,38 @@
+use thiserror::Error;
+
+/// Errors raised while converting a source document to ProseMirror
+///
+/// Partial problems (an unreadable page, a skipped image) are reported as
+/// warnings on the result instead; these errors mean nothing usable could
+/// be produced.
+#[derive(Debug, Error)]
+pub enum ExtractionError {
+    #[error("No extractor supports '.{extension}' files")]
+    UnsupportedFormat { extension: String },
+
+    #[error("The {format} file is damaged or not a valid {format} file: {reason}")]
+    InvalidDocument { format: String, reason: String },
+
+    #[error("The {format} file is encrypted")]
+    Encrypted { format: String },
+
+    #[error("Extraction failed: {0}")]
+    Failed(String),
+}
+
+impl ExtractionError {
+    /// Create an UnsupportedFormat error for a file extension
+    pub fn unsupported(extension: impl Into<String>) -> Self {
+        ExtractionError::UnsupportedFormat {
+            extension: extension.into(),
+        }
+    }
+
+    /// Create an InvalidDocument error for a format
+    pub fn invalid(format: impl Into<String>, reason: impl ToString) -> Self {
+        ExtractionError::InvalidDocument {
+            format: format.into(),
+            reason: reason.to_string(),
+        }
+    }
+}
@@ -0,0 +1,3 @@
+pub mod extraction_error;
+
+pub use extraction_error::ExtractionError;
@@ -0,0 +1,90 @@
+use super::errors::ExtractionError;
+use super::ocr::OcrPage;
+use super::value_objects::{ExtractionQuality, ExtractionWarning, QualityStats};
+use crate::domain::det::PmNode;
+
+/// Converts one source format into a ProseMirror document
+///
+/// Implementations must be pure Rust and work on the file contents only, so
+/// extraction never sends data anywhere and can run on the blocking pool.
+pub trait DocumentExtractor: Send + Sync {
+    /// Versioned method name recorded in the `.det` metadata,
+    /// e.g. "pdf-text-v1"
+    fn method(&self) -> &'static str;
+
+    /// Lower-case file extensions this extractor handles
+    fn extensions(&self) -> &'static [&'static str];
+
+    /// Convert the file contents
+    fn extract(&self, bytes: &[u8]) -> Result<ExtractedDocument, ExtractionError>;
+
+    /// Whether this extractor handles files with the given extension
+    fn supports(&self, extension: &str) -> bool {
+        self.extensions()
+            .iter()
+            .any(|e| e.eq_ignore_ascii_case(extension))
+    }
+}
+
+/// Output of a successful extraction
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExtractedDocument {
+    /// The `doc` node with the extracted structure
+    pub content: PmNode,
+
+    /// Problems with individual pages or sections
+    pub warnings: Vec<ExtractionWarning>,
+
+    pub stats: QualityStats,
+
+    /// Number of pages, for paginated formats
+    pub page_count: Option<u32>,
+
+    /// Engine that recognized the text of scanned pages, if OCR ran
+    pub ocr_engine: Option<String>,
+
+    /// Words, confidences and positions of the pages read by OCR
+    pub ocr_pages: Vec<OcrPage>,
+}
+
+impl ExtractedDocument {
+    /// Create a result from top-level blocks
+    ///
+    /// A document needs at least one block, so an empty extraction gets an
+    /// empty paragraph.
+    pub fn new(
+        mut blocks: Vec<PmNode>,
+        warnings: Vec<ExtractionWarning>,
+        stats: QualityStats,
+    ) -> Self {
+        if blocks.is_empty() {
+            blocks.push(PmNode::paragraph(Vec::new()));
+        }
+        ExtractedDocument {
+            content: PmNode::doc(blocks),
+            warnings,
+            stats,
+            page_count: None,
+            ocr_engine: None,
+            ocr_pages: Vec::new(),
+        }
+    }
+
+    pub fn with_page_count(mut self, page_count: u32) -> Self {
+        self.page_count = Some(page_count);
+        self
+    }
+
+    /// Attach the pages that were read by OCR
+    pub fn with_ocr(mut self, engine: impl Into<String>, pages: Vec<OcrPage>) -> Self {
+        if !pages.is_empty() {
+            self.ocr_engine = Some(engine.into());
+            self.ocr_pages = pages;
+        }
+        self
+    }
+
+    pub fn quality(&self) -> ExtractionQuality {
+        ExtractionQuality::from_stats(&self.stats)
+    }
+}
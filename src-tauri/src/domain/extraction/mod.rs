@@ -0,0 +1,15 @@
+pub mod errors;
+pub mod extractor;
+pub mod ocr;
+pub mod value_objects;
+
+// Re-export commonly used types
+pub use errors::ExtractionError;
+pub use extractor::{DocumentExtractor, ExtractedDocument};
+pub use ocr::{BoundingBox, OcrEngine, OcrPage, OcrWord, PageImage};
+pub use value_objects::{
+    is_unreadable, ExtractionQuality, ExtractionWarning, QualityStats, WarningLocation,
+};
+
+// Extractors build `.det` content nodes
+pub use crate::domain::det::{PmMark, PmNode};
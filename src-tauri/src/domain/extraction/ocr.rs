@@ -0,0 +1,154 @@
+use serde::{Deserialize, Serialize};
+
+use super::errors::ExtractionError;
+
+/// Recognizes text in the image of a page
+///
+/// Engines must run locally; page images never leave the machine.
+pub trait OcrEngine: Send + Sync {
+    /// Name and version recorded with the results, e.g. "tesseract-5"
+    fn name(&self) -> &str;
+
+    /// Recognize the words on one page image
+    fn recognize(&self, image: &PageImage) -> Result<OcrPage, ExtractionError>;
+}
+
+/// The image of one page handed to an OCR engine
+#[derive(Debug, Clone, PartialEq)]
+pub enum PageImage {
+    /// A complete image file (JPEG, PNG, TIFF, JPEG 2000) as stored in the
+    /// source
+    Encoded(Vec<u8>),
+
+    /// Uncompressed 8-bit samples, `channels` per pixel, rows top to bottom
+    Pixels {
+        width: u32,
+        height: u32,
+        channels: u8,
+        data: Vec<u8>,
+    },
+}
+
+/// Position of a word on the page image, in pixels from the top left
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BoundingBox {
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
+/// One recognized word
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OcrWord {
+    pub text: String,
+
+    /// Engine confidence between 0 and 1
+    pub confidence: f32,
+
+    pub bbox: BoundingBox,
+
+    /// Index of the paragraph on the page
+    pub paragraph: u32,
+
+    /// Index of the line on the page
+    pub line: u32,
+}
+
+/// The words recognized on one page, in reading order
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OcrPage {
+    /// 1-based page number; images are page 1
+    pub page: u32,
+
+    pub words: Vec<OcrWord>,
+}
+
+impl OcrPage {
+    /// Pages recognized with less confidence need manual correction
+    pub const REVIEW_CONFIDENCE: f32 = 0.8;
+
+    /// Whether the recognized text should be checked and corrected by hand
+    pub fn needs_correction(&self) -> bool {
+        self.confidence()
+            .is_some_and(|confidence| confidence < Self::REVIEW_CONFIDENCE)
+    }
+
+    /// Mean word confidence, weighted by word length
+    ///
+    /// `None` when no words were recognized.
+    pub fn confidence(&self) -> Option<f32> {
+        let (weighted, characters) =
+            self.words
+                .iter()
+                .fold((0.0f64, 0usize), |(weighted, characters), word| {
+                    let length = word.text.chars().count();
+                    (
+                        weighted + f64::from(word.confidence) * length as f64,
+                        characters + length,
+                    )
+                });
+        (characters > 0).then(|| (weighted / characters as f64) as f32)
+    }
+
+    /// The recognized text, with lines on their own line and a blank line
+    /// between paragraphs
+    pub fn text(&self) -> String {
+        let mut text = String::new();
+        let mut previous: Option<&OcrWord> = None;
+        for word in &self.words {
+            if let Some(previous) = previous {
+                if previous.paragraph != word.paragraph {
+                    text.push_str("\n\n");
+                } else if previous.line != word.line {
+                    text.push('\n');
+                } else {
+                    text.push(' ');
+                }
+            }
+            text.push_str(&word.text);
+            previous = Some(word);
+        }
+        text
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn word(text: &str, confidence: f32, paragraph: u32, line: u32) -> OcrWord {
+        OcrWord {
+            text: text.to_string(),
+            confidence,
+            bbox: BoundingBox {
+                x: 0,
+                y: 0,
+                width: 10,
+                height: 10,
+            },
+            paragraph,
+            line,
+        }
+    }
+
+    #[test]
+    fn test_text_and_weighted_confidence() {
+        let page = OcrPage {
+            page: 1,
+            words: vec![
+                word("Dear", 0.9, 0, 0),
+                word("Sir,", 0.5, 0, 0),
+                word("thanks", 0.9, 0, 1),
+                word("Regards", 0.7, 1, 2),
+            ],
+        };
+        assert_eq!(page.text(), "Dear Sir,\nthanks\n\nRegards");
+        let confidence = page.confidence().unwrap();
+        assert!((confidence - 15.9 / 21.0).abs() < 1e-6);
+        assert_eq!(OcrPage::default().confidence(), None);
+        assert!(!OcrPage::default().needs_correction());
+    }
+}
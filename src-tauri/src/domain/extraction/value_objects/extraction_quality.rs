@@ -0,0 +1,162 @@
+use serde::{Deserialize, Serialize};
+
+/// Raw measurements an extractor collects while converting a document
+///
+/// A "unit" is whatever the format is divided into: pages for PDF,
+/// sections for DOCX and RTF, paragraphs for Markdown and plain text.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct QualityStats {
+    pub units: usize,
+    pub empty_units: usize,
+    pub characters: usize,
+    pub unreadable_characters: usize,
+    pub skipped_elements: usize,
+
+    /// Characters recognized by OCR on pages that need correction
+    pub uncertain_characters: usize,
+}
+
+impl QualityStats {
+    /// Count the characters of extracted text
+    pub fn record_text(&mut self, text: &str) {
+        for c in text.chars() {
+            if c.is_whitespace() {
+                continue;
+            }
+            self.characters += 1;
+            if is_unreadable(c) {
+                self.unreadable_characters += 1;
+            }
+        }
+    }
+
+    /// Record one unit and whether any text was found in it
+    pub fn record_unit(&mut self, has_text: bool) {
+        self.units += 1;
+        if !has_text {
+            self.empty_units += 1;
+        }
+    }
+}
+
+/// Characters that indicate a failed decode rather than real content
+pub fn is_unreadable(c: char) -> bool {
+    c == '\u{FFFD}'
+        || ('\u{E000}'..='\u{F8FF}').contains(&c)
+        || (c.is_control() && !matches!(c, '\n' | '\r' | '\t'))
+}
+
+/// How much of a document survived extraction, from 0.0 to 1.0
+///
+/// The score multiplies coverage (units that yielded text), readability
+/// (characters that decoded cleanly, with OCR text of doubtful pages
+/// counting half) and a small penalty per element that had to be skipped,
+/// so a scanned PDF without a text layer scores close to zero while a clean
+/// Markdown file scores 1.0.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ExtractionQuality(f64);
+
+impl ExtractionQuality {
+    /// Scores at or above this need no manual review
+    pub const GOOD_THRESHOLD: f64 = 0.9;
+
+    /// Scores below this usually mean the text is unusable
+    pub const POOR_THRESHOLD: f64 = 0.6;
+
+    /// Create a quality score, clamped to 0.0..=1.0
+    pub fn new(score: f64) -> Self {
+        ExtractionQuality(if score.is_nan() {
+            0.0
+        } else {
+            score.clamp(0.0, 1.0)
+        })
+    }
+
+    /// Compute the score from extraction measurements
+    pub fn from_stats(stats: &QualityStats) -> Self {
+        if stats.characters == 0 {
+            return ExtractionQuality(0.0);
+        }
+
+        let coverage = if stats.units == 0 {
+            1.0
+        } else {
+            1.0 - stats.empty_units as f64 / stats.units as f64
+        };
+        let doubtful = stats.unreadable_characters as f64 + 0.5 * stats.uncertain_characters as f64;
+        let readability = 1.0 - doubtful / stats.characters as f64;
+        let completeness = (1.0 - 0.02 * stats.skipped_elements as f64).max(0.5);
+
+        Self::new(coverage * readability * completeness)
+    }
+
+    pub fn score(&self) -> f64 {
+        self.0
+    }
+
+    /// "good", "fair" or "poor"
+    pub fn level(&self) -> &'static str {
+        if self.0 >= Self::GOOD_THRESHOLD {
+            "good"
+        } else if self.0 >= Self::POOR_THRESHOLD {
+            "fair"
+        } else {
+            "poor"
+        }
+    }
+
+    /// Whether a person should check the extracted text
+    pub fn needs_review(&self) -> bool {
+        self.0 < Self::GOOD_THRESHOLD
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clean_text_scores_full() {
+        let mut stats = QualityStats::default();
+        stats.record_text("Hello world");
+        stats.record_unit(true);
+
+        let quality = ExtractionQuality::from_stats(&stats);
+        assert_eq!(quality.score(), 1.0);
+        assert_eq!(quality.level(), "good");
+        assert!(!quality.needs_review());
+    }
+
+    #[test]
+    fn test_empty_pages_and_garbage_lower_the_score() {
+        let mut stats = QualityStats::default();
+        stats.record_text("ab\u{FFFD}\u{E001}");
+        stats.record_unit(true);
+        stats.record_unit(false);
+
+        let quality = ExtractionQuality::from_stats(&stats);
+        assert!((quality.score() - 0.25).abs() < 1e-9);
+        assert_eq!(quality.level(), "poor");
+    }
+
+    #[test]
+    fn test_no_text_scores_zero() {
+        let mut stats = QualityStats::default();
+        stats.record_unit(false);
+
+        assert_eq!(ExtractionQuality::from_stats(&stats).score(), 0.0);
+        assert_eq!(ExtractionQuality::new(1.7).score(), 1.0);
+    }
+
+    #[test]
+    fn test_uncertain_ocr_text_counts_half() {
+        let mut stats = QualityStats::default();
+        stats.record_text("abcd");
+        stats.uncertain_characters = 4;
+        stats.record_unit(true);
+
+        let quality = ExtractionQuality::from_stats(&stats);
+        assert!((quality.score() - 0.5).abs() < 1e-9);
+        assert!(quality.needs_review());
+    }
+}
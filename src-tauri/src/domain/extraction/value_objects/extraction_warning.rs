@@ -0,0 +1,62 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Where in the source document a problem was found
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", content = "value", rename_all = "camelCase")]
+pub enum WarningLocation {
+    /// The document as a whole
+    Document,
+
+    /// A 1-based page number (PDF)
+    Page(u32),
+
+    /// A section, named after its heading where there is one
+    Section(String),
+}
+
+impl fmt::Display for WarningLocation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WarningLocation::Document => write!(f, "document"),
+            WarningLocation::Page(page) => write!(f, "page {}", page),
+            WarningLocation::Section(section) => write!(f, "section \"{}\"", section),
+        }
+    }
+}
+
+/// A part of the source that could not be extracted faithfully
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExtractionWarning {
+    pub location: WarningLocation,
+    pub message: String,
+}
+
+impl ExtractionWarning {
+    pub fn document(message: impl Into<String>) -> Self {
+        ExtractionWarning {
+            location: WarningLocation::Document,
+            message: message.into(),
+        }
+    }
+
+    pub fn page(page: u32, message: impl Into<String>) -> Self {
+        ExtractionWarning {
+            location: WarningLocation::Page(page),
+            message: message.into(),
+        }
+    }
+
+    pub fn section(section: impl Into<String>, message: impl Into<String>) -> Self {
+        ExtractionWarning {
+            location: WarningLocation::Section(section.into()),
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for ExtractionWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.location, self.message)
+    }
+}
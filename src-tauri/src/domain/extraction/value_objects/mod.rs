@@ -0,0 +1,5 @@
+pub mod extraction_quality;
+pub mod extraction_warning;
+
+pub use extraction_quality::{is_unreadable, ExtractionQuality, QualityStats};
+pub use extraction_warning::{ExtractionWarning, WarningLocation};
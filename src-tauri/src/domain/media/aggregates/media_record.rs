@@ -0,0 +1,87 @@
+use chrono::{DateTime, Utc};
+
+use crate::domain::document::DocumentId;
+use crate::domain::media::value_objects::MediaMetadata;
+use crate::domain::project::ProjectId;
+
+/// Probed metadata of one tracked media document
+///
+/// Records the content hash the metadata was read from, so a project run
+/// only probes files whose contents changed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MediaRecord {
+    document_id: DocumentId,
+    project_id: ProjectId,
+    content_hash: String,
+    method: String,
+    metadata: MediaMetadata,
+    probed_at: DateTime<Utc>,
+}
+
+impl MediaRecord {
+    /// Record metadata probed just now
+    pub fn new(
+        document_id: DocumentId,
+        project_id: ProjectId,
+        content_hash: String,
+        method: impl Into<String>,
+        metadata: MediaMetadata,
+    ) -> Self {
+        Self::from_data(
+            document_id,
+            project_id,
+            content_hash,
+            method.into(),
+            metadata,
+            Utc::now(),
+        )
+    }
+
+    /// Reconstruct a record from storage
+    pub fn from_data(
+        document_id: DocumentId,
+        project_id: ProjectId,
+        content_hash: String,
+        method: String,
+        metadata: MediaMetadata,
+        probed_at: DateTime<Utc>,
+    ) -> Self {
+        MediaRecord {
+            document_id,
+            project_id,
+            content_hash,
+            method,
+            metadata,
+            probed_at,
+        }
+    }
+
+    pub fn document_id(&self) -> &DocumentId {
+        &self.document_id
+    }
+
+    pub fn project_id(&self) -> &ProjectId {
+        &self.project_id
+    }
+
+    pub fn content_hash(&self) -> &str {
+        &self.content_hash
+    }
+
+    pub fn method(&self) -> &str {
+        &self.method
+    }
+
+    pub fn metadata(&self) -> &MediaMetadata {
+        &self.metadata
+    }
+
+    pub fn probed_at(&self) -> DateTime<Utc> {
+        self.probed_at
+    }
+
+    /// Whether the metadata was read from contents with this hash
+    pub fn is_current(&self, content_hash: &str) -> bool {
+        self.content_hash == content_hash
+    }
+}
@@ -0,0 +1,3 @@
+pub mod media_record;
+
+pub use media_record::MediaRecord;
@@ -0,0 +1,40 @@
+use thiserror::Error;
+
+/// Errors raised while reading the technical metadata of a media file
+///
+/// Missing tags are not errors; these mean the file is not a readable
+/// audio or video file of its format.
+#[derive(Debug, Error)]
+pub enum MediaProbeError {
+    #[error("No media probe supports '.{extension}' files")]
+    UnsupportedFormat { extension: String },
+
+    #[error("The {format} file is damaged or not a valid {format} file: {reason}")]
+    InvalidFile { format: String, reason: String },
+
+    #[error("Cannot read the media file: {0}")]
+    Io(String),
+}
+
+impl MediaProbeError {
+    /// Create an UnsupportedFormat error for a file extension
+    pub fn unsupported(extension: impl Into<String>) -> Self {
+        MediaProbeError::UnsupportedFormat {
+            extension: extension.into(),
+        }
+    }
+
+    /// Create an InvalidFile error for a format
+    pub fn invalid(format: impl Into<String>, reason: impl ToString) -> Self {
+        MediaProbeError::InvalidFile {
+            format: format.into(),
+            reason: reason.to_string(),
+        }
+    }
+}
+
+impl From<std::io::Error> for MediaProbeError {
+    fn from(error: std::io::Error) -> Self {
+        MediaProbeError::Io(error.to_string())
+    }
+}
@@ -0,0 +1,3 @@
+pub mod media_probe_error;
+
+pub use media_probe_error::MediaProbeError;
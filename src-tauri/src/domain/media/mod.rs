@@ -0,0 +1,12 @@
+pub mod aggregates;
+pub mod errors;
+pub mod probe;
+pub mod repositories;
+pub mod value_objects;
+
+// Re-export commonly used types
+pub use aggregates::MediaRecord;
+pub use errors::MediaProbeError;
+pub use probe::{MediaProbe, MediaSource};
+pub use repositories::MediaMetadataRepository;
+pub use value_objects::{MediaFilter, MediaKind, MediaMetadata, MediaTags};
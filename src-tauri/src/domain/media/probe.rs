@@ -0,0 +1,36 @@
+use std::io::{Read, Seek};
+
+use super::errors::MediaProbeError;
+use super::value_objects::MediaMetadata;
+
+/// A seekable media file; probes read headers and skip over the payload
+pub trait MediaSource: Read + Seek + Send {}
+
+impl<T: Read + Seek + Send> MediaSource for T {}
+
+/// Reads duration, codecs, stream properties and tags of one media format
+///
+/// Implementations must be pure Rust and read only what they need, so
+/// probing a multi-gigabyte recording costs a few header reads and never
+/// starts an external process.
+pub trait MediaProbe: Send + Sync {
+    /// Versioned method name stored with the metadata, e.g. "mp3-probe-v1"
+    fn method(&self) -> &'static str;
+
+    /// Lower-case file extensions this probe handles
+    fn extensions(&self) -> &'static [&'static str];
+
+    /// Read the metadata of a file of `size` bytes
+    fn probe(
+        &self,
+        source: &mut dyn MediaSource,
+        size: u64,
+    ) -> Result<MediaMetadata, MediaProbeError>;
+
+    /// Whether this probe handles files with the given extension
+    fn supports(&self, extension: &str) -> bool {
+        self.extensions()
+            .iter()
+            .any(|e| e.eq_ignore_ascii_case(extension))
+    }
+}
@@ -0,0 +1,28 @@
+use async_trait::async_trait;
+
+use crate::domain::document::DocumentId;
+use crate::domain::media::aggregates::MediaRecord;
+use crate::domain::media::value_objects::MediaFilter;
+use crate::domain::project::ProjectId;
+use crate::domain::workspace::repositories::RepositoryError;
+
+/// Repository trait for probed media metadata
+#[async_trait]
+pub trait MediaMetadataRepository: Send + Sync {
+    /// Save a record, replacing the document's earlier one
+    async fn save(&self, record: &MediaRecord) -> Result<(), RepositoryError>;
+
+    async fn find_by_document(
+        &self,
+        document_id: &DocumentId,
+    ) -> Result<Option<MediaRecord>, RepositoryError>;
+
+    /// Records of a project matching a filter, longest first
+    async fn search(
+        &self,
+        project_id: &ProjectId,
+        filter: &MediaFilter,
+    ) -> Result<Vec<MediaRecord>, RepositoryError>;
+
+    async fn delete(&self, document_id: &DocumentId) -> Result<(), RepositoryError>;
+}
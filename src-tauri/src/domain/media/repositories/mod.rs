@@ -0,0 +1,3 @@
+pub mod media_metadata_repository;
+
+pub use media_metadata_repository::MediaMetadataRepository;
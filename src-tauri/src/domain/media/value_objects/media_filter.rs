@@ -0,0 +1,105 @@
+use serde::{Deserialize, Serialize};
+
+use super::media_kind::MediaKind;
+use super::media_metadata::MediaMetadata;
+
+/// Criteria for finding media files by their metadata
+///
+/// All given criteria must match; an empty filter matches every probed
+/// file. "All recordings longer than 10 minutes" is
+/// `{ kind: audio, minDurationMs: 600000 }`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct MediaFilter {
+    pub kind: Option<MediaKind>,
+
+    pub min_duration_ms: Option<u64>,
+    pub max_duration_ms: Option<u64>,
+
+    /// Container format, case-insensitive
+    pub container: Option<String>,
+
+    /// Audio or video codec, case-insensitive
+    pub codec: Option<String>,
+
+    pub min_width: Option<u32>,
+    pub min_height: Option<u32>,
+
+    /// Text contained in the title, artist or album, case-insensitive
+    pub text: Option<String>,
+}
+
+impl MediaFilter {
+    /// Whether metadata meets every criterion
+    pub fn matches(&self, metadata: &MediaMetadata) -> bool {
+        let equal = |wanted: &Option<String>, actual: Option<&str>| {
+            wanted
+                .as_deref()
+                .is_none_or(|w| actual.is_some_and(|a| a.eq_ignore_ascii_case(w)))
+        };
+        let at_least = |minimum: Option<u32>, actual: Option<u32>| {
+            minimum.is_none_or(|m| actual.is_some_and(|a| a >= m))
+        };
+        let duration = metadata.duration_ms;
+
+        self.kind.is_none_or(|kind| kind == metadata.kind)
+            && self
+                .min_duration_ms
+                .is_none_or(|m| duration.is_some_and(|d| d >= m))
+            && self
+                .max_duration_ms
+                .is_none_or(|m| duration.is_some_and(|d| d <= m))
+            && equal(&self.container, Some(&metadata.container))
+            && (equal(&self.codec, metadata.audio_codec.as_deref())
+                || equal(&self.codec, metadata.video_codec.as_deref()))
+            && at_least(self.min_width, metadata.width)
+            && at_least(self.min_height, metadata.height)
+            && self.text.as_deref().is_none_or(|text| {
+                let text = text.to_lowercase();
+                [
+                    metadata.tags.title(),
+                    metadata.tags.artist(),
+                    metadata.tags.album(),
+                ]
+                .into_iter()
+                .flatten()
+                .any(|value| value.to_lowercase().contains(&text))
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_matches_duration_codec_and_tags() {
+        let mut recording = MediaMetadata::new(MediaKind::Audio, "MP3");
+        recording.duration_ms = Some(754_000);
+        recording.audio_codec = Some("MP3".to_string());
+        recording.tags.set("artist", "Board Meeting");
+
+        let long_recordings = MediaFilter {
+            kind: Some(MediaKind::Audio),
+            min_duration_ms: Some(600_000),
+            ..MediaFilter::default()
+        };
+        assert!(long_recordings.matches(&recording));
+        assert!(MediaFilter::default().matches(&recording));
+
+        let filter = MediaFilter {
+            codec: Some("mp3".to_string()),
+            text: Some("meeting".to_string()),
+            ..MediaFilter::default()
+        };
+        assert!(filter.matches(&recording));
+
+        recording.duration_ms = None;
+        assert!(!long_recordings.matches(&recording));
+        let videos = MediaFilter {
+            min_width: Some(1280),
+            ..MediaFilter::default()
+        };
+        assert!(!videos.matches(&recording));
+    }
+}
@@ -0,0 +1,86 @@
+use serde::{Deserialize, Serialize};
+
+use super::media_kind::MediaKind;
+use super::media_tags::MediaTags;
+
+/// Technical properties and tags of an audio or video file
+///
+/// Every property is optional: a probe fills in what the container
+/// declares and leaves the rest empty rather than guessing.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MediaMetadata {
+    pub kind: MediaKind,
+
+    /// Container format, e.g. "MP3", "MP4", "Matroska"
+    pub container: String,
+
+    pub duration_ms: Option<u64>,
+
+    /// First audio stream's codec, e.g. "MP3", "AAC", "Opus"
+    pub audio_codec: Option<String>,
+
+    /// First video stream's codec, e.g. "H.264", "VP9"
+    pub video_codec: Option<String>,
+
+    /// Average bitrate of the whole file in kbit/s
+    pub bitrate_kbps: Option<u32>,
+
+    /// Audio samples per second
+    pub sample_rate: Option<u32>,
+
+    pub channels: Option<u16>,
+
+    /// Picture size in pixels
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+
+    /// Frames per second
+    pub frame_rate: Option<f64>,
+
+    #[serde(default)]
+    pub tags: MediaTags,
+}
+
+impl MediaMetadata {
+    /// Create metadata with no properties known yet
+    pub fn new(kind: MediaKind, container: impl Into<String>) -> Self {
+        MediaMetadata {
+            kind,
+            container: container.into(),
+            duration_ms: None,
+            audio_codec: None,
+            video_codec: None,
+            bitrate_kbps: None,
+            sample_rate: None,
+            channels: None,
+            width: None,
+            height: None,
+            frame_rate: None,
+            tags: MediaTags::default(),
+        }
+    }
+
+    /// Set the duration from a count of units at `rate` units per second
+    pub fn set_duration(&mut self, units: u64, rate: u64) {
+        if rate > 0 && units > 0 {
+            self.duration_ms = Some((u128::from(units) * 1000 / u128::from(rate)) as u64);
+        }
+    }
+
+    /// Derive the average bitrate from the file size when the container
+    /// does not declare one
+    pub fn fill_bitrate(&mut self, size: u64) {
+        if self.bitrate_kbps.is_some() {
+            return;
+        }
+        if let Some(duration_ms) = self.duration_ms.filter(|ms| *ms > 0) {
+            let kbps = u128::from(size) * 8 / u128::from(duration_ms);
+            self.bitrate_kbps = u32::try_from(kbps).ok();
+        }
+    }
+
+    pub fn duration_seconds(&self) -> Option<f64> {
+        self.duration_ms.map(|ms| ms as f64 / 1000.0)
+    }
+}
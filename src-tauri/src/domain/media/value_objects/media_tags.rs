@@ -0,0 +1,78 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Descriptive tags of a media file under format-independent names
+///
+/// Probes map ID3 frames, Vorbis comments, RIFF INFO chunks, iTunes atoms
+/// and Matroska tags onto the same lower-case names ("title", "artist",
+/// "album", "genre", "year", "track", "comment", ...), so a search for an
+/// artist finds MP3s and M4As alike. The first non-empty value wins.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct MediaTags(BTreeMap<String, String>);
+
+impl MediaTags {
+    pub const TITLE: &'static str = "title";
+    pub const ARTIST: &'static str = "artist";
+    pub const ALBUM: &'static str = "album";
+    pub const GENRE: &'static str = "genre";
+    pub const YEAR: &'static str = "year";
+    pub const TRACK: &'static str = "track";
+    pub const COMMENT: &'static str = "comment";
+
+    /// Set a tag unless it already has a value
+    ///
+    /// Values are trimmed of whitespace and NUL padding; a year keeps only
+    /// its leading four digits, so "2024-05-01" is stored as "2024".
+    pub fn set(&mut self, name: &str, value: &str) {
+        let value = value.trim_matches(|c: char| c == '\0' || c.is_whitespace());
+        if value.is_empty() {
+            return;
+        }
+        let name = name.to_lowercase();
+        let value = if name == Self::YEAR {
+            match value
+                .get(..4)
+                .filter(|y| y.bytes().all(|b| b.is_ascii_digit()))
+            {
+                Some(year) => year.to_string(),
+                None => return,
+            }
+        } else {
+            value.to_string()
+        };
+        self.0.entry(name).or_insert(value);
+    }
+
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.0.get(name).map(String::as_str)
+    }
+
+    pub fn title(&self) -> Option<&str> {
+        self.get(Self::TITLE)
+    }
+
+    pub fn artist(&self) -> Option<&str> {
+        self.get(Self::ARTIST)
+    }
+
+    pub fn album(&self) -> Option<&str> {
+        self.get(Self::ALBUM)
+    }
+
+    pub fn genre(&self) -> Option<&str> {
+        self.get(Self::GENRE)
+    }
+
+    pub fn year(&self) -> Option<&str> {
+        self.get(Self::YEAR)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.0.iter().map(|(k, v)| (k.as_str(), v.as_str()))
+    }
+}
@@ -0,0 +1,9 @@
+pub mod media_filter;
+pub mod media_kind;
+pub mod media_metadata;
+pub mod media_tags;
+
+pub use media_filter::MediaFilter;
+pub use media_kind::MediaKind;
+pub use media_metadata::MediaMetadata;
+pub use media_tags::MediaTags;
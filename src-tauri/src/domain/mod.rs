@@ -1,2 +1,17 @@
+pub mod annotation;
+pub mod anonymization;
+pub mod category;
+pub mod coding;
+pub mod cost_table;
+pub mod det;
+pub mod document;
+pub mod email;
+pub mod export;
+pub mod extraction;
+pub mod media;
+pub mod preview;
 pub mod project;
+pub mod report;
+pub mod review;
+pub mod search;
 pub mod workspace;
@@ -0,0 +1,3 @@
+pub mod preview_error;
+
+pub use preview_error::PreviewError;
@@ -0,0 +1,45 @@
+use thiserror::Error;
+
+/// Errors raised while generating the preview of a file
+#[derive(Debug, Error)]
+pub enum PreviewError {
+    #[error("No preview generator supports '.{extension}' files")]
+    UnsupportedFormat { extension: String },
+
+    #[error("The {format} file is damaged or not a valid {format} file: {reason}")]
+    InvalidFile { format: String, reason: String },
+
+    #[error("No preview can be shown: {0}")]
+    NotAvailable(String),
+
+    #[error("Cannot read the file: {0}")]
+    Io(String),
+}
+
+impl PreviewError {
+    /// Create an UnsupportedFormat error for a file extension
+    pub fn unsupported(extension: impl Into<String>) -> Self {
+        PreviewError::UnsupportedFormat {
+            extension: extension.into(),
+        }
+    }
+
+    /// Create an InvalidFile error for a format
+    pub fn invalid(format: impl Into<String>, reason: impl ToString) -> Self {
+        PreviewError::InvalidFile {
+            format: format.into(),
+            reason: reason.to_string(),
+        }
+    }
+
+    /// Create a NotAvailable error for a readable file without a preview
+    pub fn not_available(reason: impl Into<String>) -> Self {
+        PreviewError::NotAvailable(reason.into())
+    }
+}
+
+impl From<std::io::Error> for PreviewError {
+    fn from(error: std::io::Error) -> Self {
+        PreviewError::Io(error.to_string())
+    }
+}
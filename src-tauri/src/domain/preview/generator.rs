@@ -0,0 +1,26 @@
+use std::io::Read;
+
+use super::errors::PreviewError;
+use super::value_objects::Preview;
+
+/// Generates the preview of one kind of file
+///
+/// Implementations must be pure Rust. They read only as much of the file as
+/// the preview needs, so a snippet of a large log costs one small read.
+pub trait PreviewGenerator: Send + Sync {
+    /// Versioned method name, part of the cache key, e.g. "png-thumbnail-v1"
+    fn method(&self) -> &'static str;
+
+    /// Lower-case file extensions this generator handles
+    fn extensions(&self) -> &'static [&'static str];
+
+    /// Generate the preview of a file of `size` bytes
+    fn generate(&self, source: &mut dyn Read, size: u64) -> Result<Preview, PreviewError>;
+
+    /// Whether this generator handles files with the given extension
+    fn supports(&self, extension: &str) -> bool {
+        self.extensions()
+            .iter()
+            .any(|e| e.eq_ignore_ascii_case(extension))
+    }
+}
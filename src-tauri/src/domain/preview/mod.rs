@@ -0,0 +1,10 @@
+pub mod errors;
+pub mod generator;
+pub mod value_objects;
+
+// Re-export commonly used types
+pub use errors::PreviewError;
+pub use generator::PreviewGenerator;
+pub use value_objects::{
+    Preview, PreviewFormat, SNIPPET_MAX_CHARS, SNIPPET_MAX_LINES, THUMBNAIL_MAX_EDGE,
+};
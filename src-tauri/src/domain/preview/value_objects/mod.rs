@@ -0,0 +1,5 @@
+pub mod preview;
+
+pub use preview::{
+    Preview, PreviewFormat, SNIPPET_MAX_CHARS, SNIPPET_MAX_LINES, THUMBNAIL_MAX_EDGE,
+};
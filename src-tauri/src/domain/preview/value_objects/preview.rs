@@ -0,0 +1,199 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Longest edge of a generated thumbnail, in pixels
+pub const THUMBNAIL_MAX_EDGE: u32 = 256;
+
+/// Most characters kept in a text snippet
+pub const SNIPPET_MAX_CHARS: usize = 600;
+
+/// Most lines kept in a text snippet
+pub const SNIPPET_MAX_LINES: usize = 12;
+
+/// How the bytes of a preview are encoded
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PreviewFormat {
+    Png,
+    Jpeg,
+    Gif,
+    Webp,
+    Bmp,
+    /// A UTF-8 text snippet
+    Text,
+}
+
+impl PreviewFormat {
+    /// File extension used for cached previews of this format
+    pub fn extension(&self) -> &'static str {
+        match self {
+            PreviewFormat::Png => "png",
+            PreviewFormat::Jpeg => "jpg",
+            PreviewFormat::Gif => "gif",
+            PreviewFormat::Webp => "webp",
+            PreviewFormat::Bmp => "bmp",
+            PreviewFormat::Text => "txt",
+        }
+    }
+
+    pub fn from_extension(extension: &str) -> Option<Self> {
+        match extension.to_ascii_lowercase().as_str() {
+            "png" => Some(PreviewFormat::Png),
+            "jpg" | "jpeg" => Some(PreviewFormat::Jpeg),
+            "gif" => Some(PreviewFormat::Gif),
+            "webp" => Some(PreviewFormat::Webp),
+            "bmp" => Some(PreviewFormat::Bmp),
+            "txt" => Some(PreviewFormat::Text),
+            _ => None,
+        }
+    }
+
+    pub fn media_type(&self) -> &'static str {
+        match self {
+            PreviewFormat::Png => "image/png",
+            PreviewFormat::Jpeg => "image/jpeg",
+            PreviewFormat::Gif => "image/gif",
+            PreviewFormat::Webp => "image/webp",
+            PreviewFormat::Bmp => "image/bmp",
+            PreviewFormat::Text => "text/plain; charset=utf-8",
+        }
+    }
+
+    pub fn is_image(&self) -> bool {
+        *self != PreviewFormat::Text
+    }
+}
+
+impl fmt::Display for PreviewFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.extension())
+    }
+}
+
+/// A small stand-in for a file shown in the explorer: a thumbnail, a
+/// first-page image or the opening lines of a text
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Preview {
+    format: PreviewFormat,
+    bytes: Vec<u8>,
+}
+
+impl Preview {
+    /// Create an image preview from encoded image bytes
+    pub fn image(format: PreviewFormat, bytes: Vec<u8>) -> Self {
+        Preview { format, bytes }
+    }
+
+    /// Create a text snippet from the start of a text
+    ///
+    /// Keeps at most `SNIPPET_MAX_LINES` lines and `SNIPPET_MAX_CHARS`
+    /// characters, drops control characters other than tabs and marks a
+    /// cut-off text with an ellipsis. `None` when nothing readable is left.
+    pub fn snippet(text: &str) -> Option<Self> {
+        let text = text.trim_start_matches('\u{feff}').replace("\r\n", "\n");
+        let mut snippet = String::new();
+        let mut truncated = false;
+
+        for (index, line) in text.trim().lines().enumerate() {
+            if index == SNIPPET_MAX_LINES {
+                truncated = true;
+                break;
+            }
+            if index > 0 {
+                snippet.push('\n');
+            }
+            snippet.extend(
+                line.trim_end()
+                    .chars()
+                    .filter(|c| *c == '\t' || !c.is_control()),
+            );
+        }
+
+        if let Some((cut, _)) = snippet.char_indices().nth(SNIPPET_MAX_CHARS) {
+            snippet.truncate(cut);
+            truncated = true;
+        }
+
+        let mut snippet = snippet.trim_end().to_string();
+        if snippet.is_empty() {
+            return None;
+        }
+        if truncated {
+            snippet.push('…');
+        }
+
+        Some(Preview {
+            format: PreviewFormat::Text,
+            bytes: snippet.into_bytes(),
+        })
+    }
+
+    pub fn format(&self) -> PreviewFormat {
+        self.format
+    }
+
+    pub fn bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.bytes
+    }
+
+    /// The snippet text of a text preview
+    pub fn text(&self) -> Option<&str> {
+        (self.format == PreviewFormat::Text)
+            .then(|| std::str::from_utf8(&self.bytes).ok())
+            .flatten()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_snippet_keeps_opening_lines() {
+        let preview = Preview::snippet("\u{feff}Minutes\r\n\r\nItem 1:\tbudget\u{7}  \n").unwrap();
+
+        assert_eq!(preview.format(), PreviewFormat::Text);
+        assert_eq!(preview.text(), Some("Minutes\n\nItem 1:\tbudget"));
+    }
+
+    #[test]
+    fn test_snippet_is_cut_to_limits() {
+        let lines: Vec<String> = (1..=40).map(|n| format!("line {}", n)).collect();
+        let preview = Preview::snippet(&lines.join("\n")).unwrap();
+        let text = preview.text().unwrap();
+        assert_eq!(text.lines().count(), SNIPPET_MAX_LINES);
+        assert!(text.ends_with("line 12…"));
+
+        let long = "é".repeat(SNIPPET_MAX_CHARS * 2);
+        let preview = Preview::snippet(&long).unwrap();
+        assert_eq!(
+            preview.text().unwrap().chars().count(),
+            SNIPPET_MAX_CHARS + 1
+        );
+
+        assert!(Preview::snippet(" \n\u{0}\n ").is_none());
+    }
+
+    #[test]
+    fn test_format_extensions_round_trip() {
+        for format in [
+            PreviewFormat::Png,
+            PreviewFormat::Jpeg,
+            PreviewFormat::Gif,
+            PreviewFormat::Webp,
+            PreviewFormat::Bmp,
+            PreviewFormat::Text,
+        ] {
+            assert_eq!(
+                PreviewFormat::from_extension(format.extension()),
+                Some(format)
+            );
+        }
+        assert!(!PreviewFormat::Text.is_image());
+        assert_eq!(PreviewFormat::Jpeg.media_type(), "image/jpeg");
+    }
+}
@@ -1,3 +1,3 @@
 pub mod project;
 
-pub use project::{Project, ProjectMetadata};
+pub use project::{Project, ProjectMetadata, REPORTS_FOLDER_NAME};
@@ -4,6 +4,14 @@ use super::super::value_objects::{
     project_name::ProjectName, project_note::ProjectNote,
 };
 use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// Folder inside the source folder that holds reports and other
+/// deliverables when no separate reports folder is configured
+pub const REPORTS_FOLDER_NAME: &str = "_corpus_analysis";
+
+/// Folder inside the reports folder for confidential working files
+const PRIVATE_FOLDER_NAME: &str = ".private";
 
 /// Project aggregate root representing a document analysis project
 ///
@@ -15,6 +23,7 @@ use serde::{Deserialize, Serialize};
 /// - Creation timestamp is immutable
 /// - Projects can be updated (name, note) but not source folder
 /// - Source folder changes require creating a new project
+/// - An immutable source requires a reports folder outside the source
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Project {
     id: ProjectId,
@@ -22,6 +31,8 @@ pub struct Project {
     source_folder: FolderPath,
     note: Option<ProjectNote>,
     created_at: CreatedAt,
+    reports_folder: Option<PathBuf>,
+    immutable_source: bool,
 }
 
 impl Project {
@@ -43,10 +54,15 @@ impl Project {
             source_folder: folder_path,
             note: project_note,
             created_at: CreatedAt::now(),
+            reports_folder: None,
+            immutable_source: false,
         })
     }
 
     /// Create a Project from existing data (for repository reconstruction)
+    ///
+    /// The source folder is not required to exist, so projects whose folder
+    /// has gone missing can still be loaded and reported as inaccessible.
     pub fn from_data(
         id: String,
         name: String,
@@ -58,7 +74,7 @@ impl Project {
 
         let project_name = ProjectName::new(name).map_err(ProjectError::InvalidName)?;
 
-        let folder_path = FolderPath::new(source_folder).map_err(ProjectError::InvalidPath)?;
+        let folder_path = FolderPath::from_stored(source_folder);
 
         let project_note = ProjectNote::from_optional(note).map_err(ProjectError::InvalidNote)?;
 
@@ -71,9 +87,76 @@ impl Project {
             source_folder: folder_path,
             note: project_note,
             created_at: timestamp,
+            reports_folder: None,
+            immutable_source: false,
         })
     }
 
+    /// Configure where reports are stored
+    ///
+    /// Without a reports folder, reports are kept in `_corpus_analysis`
+    /// inside the source folder. An immutable source is never written to,
+    /// so it needs a reports folder outside the source folder.
+    pub fn with_reports_settings(
+        mut self,
+        reports_folder: Option<String>,
+        immutable_source: bool,
+    ) -> Result<Self, ProjectError> {
+        let reports_folder = reports_folder
+            .map(|folder| folder.trim().to_string())
+            .filter(|folder| !folder.is_empty())
+            .map(PathBuf::from);
+
+        if let Some(folder) = &reports_folder {
+            if !folder.is_absolute() {
+                return Err(ProjectError::invalid_reports_folder(
+                    "the path must be absolute",
+                ));
+            }
+            if folder.exists() && !folder.is_dir() {
+                return Err(ProjectError::invalid_reports_folder(
+                    "the path is not a directory",
+                ));
+            }
+
+            let folder = folder.canonicalize().unwrap_or_else(|_| folder.clone());
+            if folder == self.source_folder.value() {
+                return Err(ProjectError::invalid_reports_folder(
+                    "it cannot be the source folder itself",
+                ));
+            }
+            if immutable_source && folder.starts_with(self.source_folder.value()) {
+                return Err(ProjectError::invalid_reports_folder(
+                    "it must be outside the source folder when the source is immutable",
+                ));
+            }
+        } else if immutable_source {
+            return Err(ProjectError::invalid_reports_folder(
+                "an immutable source requires a separate reports folder",
+            ));
+        }
+
+        self.reports_folder = reports_folder;
+        self.immutable_source = immutable_source;
+        Ok(self)
+    }
+
+    /// Restore stored reports settings without filesystem validation
+    ///
+    /// The settings were checked when they were chosen; a reports folder
+    /// that has since gone missing must not keep the project from loading.
+    pub fn with_stored_reports_settings(
+        mut self,
+        reports_folder: Option<String>,
+        immutable_source: bool,
+    ) -> Self {
+        self.reports_folder = reports_folder
+            .filter(|folder| !folder.trim().is_empty())
+            .map(PathBuf::from);
+        self.immutable_source = immutable_source;
+        self
+    }
+
     /// Get the project ID
     pub fn id(&self) -> &ProjectId {
         &self.id
@@ -124,6 +207,29 @@ impl Project {
         self.source_folder.is_accessible()
     }
 
+    /// Folder where reports and exported deliverables are written
+    pub fn reports_folder(&self) -> PathBuf {
+        self.reports_folder
+            .clone()
+            .unwrap_or_else(|| self.source_folder.value().join(REPORTS_FOLDER_NAME))
+    }
+
+    /// Folder for confidential working files, such as the pseudonym map
+    /// of anonymization
+    pub fn private_folder(&self) -> PathBuf {
+        self.reports_folder().join(PRIVATE_FOLDER_NAME)
+    }
+
+    /// Reports folder chosen for the project, if any
+    pub fn configured_reports_folder(&self) -> Option<&Path> {
+        self.reports_folder.as_deref()
+    }
+
+    /// Whether files in the source folder must never be written
+    pub fn is_source_immutable(&self) -> bool {
+        self.immutable_source
+    }
+
     /// Get a display-friendly project summary
     pub fn summary(&self) -> String {
         let note_preview = self
@@ -153,6 +259,11 @@ impl Project {
             note_line_count: self.note.as_ref().map(|n| n.line_count()),
             created_at: self.created_at.clone(),
             is_accessible: self.is_source_accessible(),
+            reports_folder_path: self
+                .reports_folder
+                .as_ref()
+                .map(|folder| folder.to_string_lossy().to_string()),
+            immutable_source: self.immutable_source,
         }
     }
 
@@ -177,6 +288,8 @@ pub struct ProjectMetadata {
     pub note_line_count: Option<usize>,
     pub created_at: CreatedAt,
     pub is_accessible: bool,
+    pub reports_folder_path: Option<String>,
+    pub immutable_source: bool,
 }
 
 #[cfg(test)]
@@ -275,6 +388,21 @@ mod tests {
         cleanup_test_folder(&test_folder);
     }
 
+    #[test]
+    fn test_from_data_with_missing_folder() {
+        let project = Project::from_data(
+            "proj_550e8400-e29b-41d4-a716-446655440000".to_string(),
+            "Unplugged Project".to_string(),
+            "/nonexistent/folder".to_string(),
+            None,
+            "2023-12-01T10:30:00Z".to_string(),
+        )
+        .unwrap();
+
+        assert!(!project.is_source_accessible());
+        assert!(!project.metadata().is_accessible);
+    }
+
     #[test]
     fn test_update_name() {
         let test_folder = setup_test_folder("update_name");
@@ -429,4 +557,68 @@ mod tests {
 
         cleanup_test_folder(&test_folder);
     }
+
+    #[test]
+    fn test_reports_settings() {
+        let test_folder = setup_test_folder("reports_settings");
+        let project = Project::new("Reports".to_string(), test_folder.clone(), None).unwrap();
+        let source = project.source_folder().value().to_path_buf();
+
+        assert_eq!(project.reports_folder(), source.join(REPORTS_FOLDER_NAME));
+        assert!(!project.is_source_immutable());
+
+        let immutable = project
+            .clone()
+            .with_reports_settings(Some("/tmp/project_test_reports_out".to_string()), true)
+            .unwrap();
+        assert_eq!(
+            immutable.reports_folder(),
+            PathBuf::from("/tmp/project_test_reports_out")
+        );
+        assert!(immutable.is_source_immutable());
+
+        let inside = source.join("reports").to_string_lossy().to_string();
+        assert!(project
+            .clone()
+            .with_reports_settings(Some(inside.clone()), false)
+            .is_ok());
+        assert!(matches!(
+            project.clone().with_reports_settings(Some(inside), true),
+            Err(ProjectError::InvalidReportsFolder { .. })
+        ));
+        assert!(project.clone().with_reports_settings(None, true).is_err());
+        assert!(project
+            .clone()
+            .with_reports_settings(Some("relative/reports".to_string()), false)
+            .is_err());
+
+        cleanup_test_folder(&test_folder);
+    }
+
+    #[test]
+    fn test_stored_reports_settings_skip_validation() {
+        let test_folder = setup_test_folder("stored_reports_settings");
+        let reports_file = format!("{}/reports.txt", test_folder);
+        fs::write(&reports_file, "not a folder").unwrap();
+
+        let project = Project::from_data(
+            "proj_550e8400-e29b-41d4-a716-446655440000".to_string(),
+            "Stored".to_string(),
+            test_folder.clone(),
+            None,
+            "2023-12-01T10:30:00Z".to_string(),
+        )
+        .unwrap();
+
+        let stored = project
+            .clone()
+            .with_stored_reports_settings(Some(reports_file.clone()), true);
+        assert_eq!(stored.reports_folder(), PathBuf::from(&reports_file));
+        assert!(stored.is_source_immutable());
+        assert!(project
+            .with_reports_settings(Some(reports_file), true)
+            .is_err());
+
+        cleanup_test_folder(&test_folder);
+    }
 }
@@ -30,6 +30,9 @@ pub enum ProjectError {
     #[error("Source folder is not accessible")]
     SourceNotAccessible,
 
+    #[error("Invalid reports folder: {reason}")]
+    InvalidReportsFolder { reason: String },
+
     #[error("Project not found with ID: {id}")]
     NotFound { id: String },
 
@@ -85,6 +88,13 @@ impl ProjectError {
         }
     }
 
+    /// Create an InvalidReportsFolder error with a specific reason
+    pub fn invalid_reports_folder(reason: impl Into<String>) -> Self {
+        ProjectError::InvalidReportsFolder {
+            reason: reason.into(),
+        }
+    }
+
     /// Create a RepositoryError for a specific operation
     pub fn repository_error(operation: impl Into<String>) -> Self {
         ProjectError::RepositoryError {
@@ -105,6 +115,7 @@ impl ProjectError {
             | ProjectError::InvalidPath(_)
             | ProjectError::InvalidNote(_)
             | ProjectError::InvalidTimestamp(_)
+            | ProjectError::InvalidReportsFolder { .. }
             | ProjectError::InvalidId => true,
 
             // These might be temporary issues
@@ -131,6 +142,7 @@ impl ProjectError {
             ProjectError::InvalidName(_)
             | ProjectError::InvalidPath(_)
             | ProjectError::InvalidNote(_)
+            | ProjectError::InvalidReportsFolder { .. }
             | ProjectError::DuplicateName { .. } => true,
 
             // System errors might be handled automatically
@@ -154,6 +166,9 @@ impl ProjectError {
                 "The project's source folder cannot be accessed. It may have been moved or deleted."
                     .to_string()
             }
+            ProjectError::InvalidReportsFolder { reason } => {
+                format!("Reports folder is invalid: {}", reason)
+            }
             ProjectError::NotFound { id } => format!("Project not found (ID: {})", id),
             ProjectError::DuplicateName { name } => {
                 format!("A project named '{}' already exists", name)
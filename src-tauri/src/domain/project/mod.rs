@@ -4,7 +4,7 @@ pub mod repositories;
 pub mod value_objects;
 
 // Re-export commonly used types
-pub use aggregates::{Project, ProjectMetadata};
+pub use aggregates::{Project, ProjectMetadata, REPORTS_FOLDER_NAME};
 pub use errors::{ProjectError, ProjectResult};
 pub use repositories::{ProjectRepository, RepositoryStats};
 pub use value_objects::{CreatedAt, FolderPath, ProjectId, ProjectName, ProjectNote};
@@ -30,6 +30,14 @@ impl FolderPath {
         Ok(FolderPath(canonical_path))
     }
 
+    /// Restore a stored FolderPath without filesystem validation
+    ///
+    /// A stored source folder may since have been moved or unmounted; use
+    /// `is_accessible` to check it.
+    pub fn from_stored(path: String) -> Self {
+        FolderPath(PathBuf::from(path))
+    }
+
     /// Create a FolderPath without filesystem validation (for testing)
     /// This should only be used in test scenarios
     #[cfg(test)]
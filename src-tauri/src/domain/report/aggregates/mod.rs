@@ -0,0 +1,3 @@
+pub mod report;
+
+pub use report::Report;
@@ -0,0 +1,276 @@
+use chrono::{DateTime, Utc};
+
+use crate::domain::document::DocumentId;
+use crate::domain::project::ProjectId;
+use crate::domain::report::errors::ReportError;
+use crate::domain::report::value_objects::{ReportCategory, ReportId, ReportStatus};
+
+/// Longest title accepted for a report
+const MAX_TITLE_LENGTH: usize = 255;
+
+/// Report aggregate root representing an analysis output of a project
+///
+/// Business Rules:
+/// - The content lives in a `.det` file below the project's reports folder,
+///   in the subfolder of the report's category
+/// - The path is stored relative to the reports folder
+/// - A final report's title and content cannot change until it is reopened
+/// - Linked source documents and derivatives are kept without duplicates
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Report {
+    id: ReportId,
+    project_id: ProjectId,
+    title: String,
+    category: ReportCategory,
+    status: ReportStatus,
+    path: String,
+    source_documents: Vec<DocumentId>,
+    derivatives: Vec<String>,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+}
+
+impl Report {
+    /// Create a draft report stored at `path` below the reports folder
+    pub fn new(
+        project_id: ProjectId,
+        title: String,
+        category: ReportCategory,
+        path: String,
+    ) -> Result<Self, ReportError> {
+        let now = Utc::now();
+        Ok(Report {
+            id: ReportId::new(),
+            project_id,
+            title: validate_title(title)?,
+            category,
+            status: ReportStatus::Draft,
+            path,
+            source_documents: Vec::new(),
+            derivatives: Vec::new(),
+            created_at: now,
+            updated_at: now,
+        })
+    }
+
+    /// Create a Report from existing data (for repository reconstruction)
+    #[allow(clippy::too_many_arguments)]
+    pub fn from_data(
+        id: ReportId,
+        project_id: ProjectId,
+        title: String,
+        category: ReportCategory,
+        status: ReportStatus,
+        path: String,
+        source_documents: Vec<DocumentId>,
+        derivatives: Vec<String>,
+        created_at: DateTime<Utc>,
+        updated_at: DateTime<Utc>,
+    ) -> Self {
+        Report {
+            id,
+            project_id,
+            title,
+            category,
+            status,
+            path,
+            source_documents,
+            derivatives,
+            created_at,
+            updated_at,
+        }
+    }
+
+    /// Relative path for a report file: `<category folder>/<slug>.det`
+    ///
+    /// `suffix` disambiguates reports whose titles give the same name.
+    pub fn path_for(category: ReportCategory, title: &str, suffix: Option<u32>) -> String {
+        let mut slug = String::new();
+        for c in title.chars() {
+            if c.is_alphanumeric() {
+                slug.extend(c.to_lowercase());
+            } else if !slug.is_empty() && !slug.ends_with('-') {
+                slug.push('-');
+            }
+        }
+        let slug = match slug.trim_end_matches('-') {
+            "" => "report",
+            slug => slug,
+        };
+
+        match suffix {
+            Some(n) => format!("{}/{}-{}.det", category.folder_name(), slug, n),
+            None => format!("{}/{}.det", category.folder_name(), slug),
+        }
+    }
+
+    pub fn id(&self) -> &ReportId {
+        &self.id
+    }
+
+    pub fn project_id(&self) -> &ProjectId {
+        &self.project_id
+    }
+
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+
+    pub fn category(&self) -> ReportCategory {
+        self.category
+    }
+
+    pub fn status(&self) -> ReportStatus {
+        self.status
+    }
+
+    /// Path of the `.det` file relative to the reports folder
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    pub fn source_documents(&self) -> &[DocumentId] {
+        &self.source_documents
+    }
+
+    /// Paths of linked derivative `.det` files
+    pub fn derivatives(&self) -> &[String] {
+        &self.derivatives
+    }
+
+    pub fn created_at(&self) -> DateTime<Utc> {
+        self.created_at
+    }
+
+    pub fn updated_at(&self) -> DateTime<Utc> {
+        self.updated_at
+    }
+
+    pub fn is_final(&self) -> bool {
+        self.status == ReportStatus::Final
+    }
+
+    /// Fail unless the title and content may be edited
+    pub fn ensure_editable(&self) -> Result<(), ReportError> {
+        if self.is_final() {
+            return Err(ReportError::Finalized(self.title.clone()));
+        }
+        Ok(())
+    }
+
+    pub fn rename(&mut self, title: String) -> Result<(), ReportError> {
+        self.ensure_editable()?;
+        self.title = validate_title(title)?;
+        self.touch();
+        Ok(())
+    }
+
+    /// Record that the report was filed under another category at `path`
+    pub fn move_to(&mut self, category: ReportCategory, path: String) {
+        self.category = category;
+        self.path = path;
+        self.touch();
+    }
+
+    /// Record an edit of the `.det` content
+    pub fn record_content_change(&mut self) -> Result<(), ReportError> {
+        self.ensure_editable()?;
+        self.touch();
+        Ok(())
+    }
+
+    pub fn set_status(&mut self, status: ReportStatus) {
+        if self.status != status {
+            self.status = status;
+            self.touch();
+        }
+    }
+
+    /// Replace the linked source documents
+    pub fn set_source_documents(&mut self, documents: Vec<DocumentId>) {
+        self.source_documents = dedup(documents);
+        self.touch();
+    }
+
+    /// Replace the linked derivatives
+    pub fn set_derivatives(&mut self, derivatives: Vec<String>) {
+        self.derivatives = dedup(derivatives);
+        self.touch();
+    }
+
+    fn touch(&mut self) {
+        self.updated_at = Utc::now();
+    }
+}
+
+fn validate_title(title: String) -> Result<String, ReportError> {
+    let title = title.trim().to_string();
+    if title.is_empty() {
+        return Err(ReportError::InvalidTitle("the title is empty".to_string()));
+    }
+    if title.chars().count() > MAX_TITLE_LENGTH {
+        return Err(ReportError::InvalidTitle(format!(
+            "the title is longer than {} characters",
+            MAX_TITLE_LENGTH
+        )));
+    }
+    Ok(title)
+}
+
+/// Remove repeated entries, keeping the first occurrence
+fn dedup<T: PartialEq>(items: Vec<T>) -> Vec<T> {
+    let mut unique = Vec::with_capacity(items.len());
+    for item in items {
+        if !unique.contains(&item) {
+            unique.push(item);
+        }
+    }
+    unique
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_path_for_uses_category_folder() {
+        assert_eq!(
+            Report::path_for(ReportCategory::ClientReport, "Q3: Costs / Fees", None),
+            "client-reports/q3-costs-fees.det"
+        );
+        assert_eq!(
+            Report::path_for(ReportCategory::Findings, "???", Some(2)),
+            "findings/report-2.det"
+        );
+    }
+
+    #[test]
+    fn test_final_report_is_read_only_until_reopened() {
+        let mut report = Report::new(
+            ProjectId::new(),
+            "  Findings  ".to_string(),
+            ReportCategory::Findings,
+            "findings/findings.det".to_string(),
+        )
+        .unwrap();
+        assert_eq!(report.title(), "Findings");
+        assert_eq!(report.status(), ReportStatus::Draft);
+
+        report.set_status(ReportStatus::Final);
+        assert!(matches!(
+            report.rename("Other".to_string()),
+            Err(ReportError::Finalized(_))
+        ));
+        assert!(report.record_content_change().is_err());
+
+        report.set_status(ReportStatus::Draft);
+        report.rename("Other".to_string()).unwrap();
+        assert!(Report::new(
+            ProjectId::new(),
+            " ".to_string(),
+            ReportCategory::Findings,
+            String::new()
+        )
+        .is_err());
+    }
+}
@@ -0,0 +1,3 @@
+pub mod report_error;
+
+pub use report_error::ReportError;
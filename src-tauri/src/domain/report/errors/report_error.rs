@@ -0,0 +1,23 @@
+use thiserror::Error;
+
+/// Business rule violations of the Report aggregate
+#[derive(Debug, Error)]
+pub enum ReportError {
+    #[error("Invalid report ID: {0}")]
+    InvalidId(String),
+
+    #[error("Invalid report title: {0}")]
+    InvalidTitle(String),
+
+    #[error("Unknown report category '{0}'")]
+    UnknownCategory(String),
+
+    #[error("Unknown report status '{0}'")]
+    UnknownStatus(String),
+
+    #[error("Invalid citation: {0}")]
+    InvalidCitation(String),
+
+    #[error("Report '{0}' is final; reopen it as a draft to edit it")]
+    Finalized(String),
+}
@@ -0,0 +1,12 @@
+pub mod aggregates;
+pub mod errors;
+pub mod repositories;
+pub mod value_objects;
+
+// Re-export commonly used types
+pub use aggregates::Report;
+pub use errors::ReportError;
+pub use repositories::{CitationRepository, ReportRepository};
+pub use value_objects::{
+    Citation, CitationLocator, CitationStatus, ReportCategory, ReportId, ReportStatus,
+};
@@ -0,0 +1,28 @@
+use async_trait::async_trait;
+
+use super::super::value_objects::{Citation, ReportId};
+use crate::domain::document::DocumentId;
+use crate::domain::workspace::repositories::RepositoryError;
+
+/// Repository trait for the citations of reports
+///
+/// Citations are indexed from a report's content whenever it is saved, so
+/// they can be looked up from either end without opening the report.
+#[async_trait]
+pub trait CitationRepository: Send + Sync {
+    /// Replace the citations of a report, keeping their order
+    async fn replace_for_report(
+        &self,
+        report_id: &ReportId,
+        citations: &[Citation],
+    ) -> Result<(), RepositoryError>;
+
+    /// List the citations of a report in reading order
+    async fn list_by_report(&self, report_id: &ReportId) -> Result<Vec<Citation>, RepositoryError>;
+
+    /// List the reports citing a document
+    async fn find_reports_citing(
+        &self,
+        document_id: &DocumentId,
+    ) -> Result<Vec<ReportId>, RepositoryError>;
+}
@@ -0,0 +1,5 @@
+pub mod citation_repository;
+pub mod report_repository;
+
+pub use citation_repository::CitationRepository;
+pub use report_repository::ReportRepository;
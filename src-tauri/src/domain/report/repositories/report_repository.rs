@@ -0,0 +1,33 @@
+use async_trait::async_trait;
+
+use super::super::aggregates::report::Report;
+use super::super::value_objects::ReportId;
+use crate::domain::project::ProjectId;
+use crate::domain::workspace::repositories::RepositoryError;
+
+/// Repository trait for the index of Report aggregates
+///
+/// Report content is kept in `.det` files; the index holds what is needed
+/// to list, filter and link reports without opening them.
+#[async_trait]
+pub trait ReportRepository: Send + Sync {
+    /// Insert or update a report with its links
+    async fn save(&self, report: &Report) -> Result<(), RepositoryError>;
+
+    /// Find a report by its identifier
+    async fn find_by_id(&self, id: &ReportId) -> Result<Option<Report>, RepositoryError>;
+
+    /// List all reports of a project, most recently updated first
+    async fn list_by_project(&self, project_id: &ProjectId)
+        -> Result<Vec<Report>, RepositoryError>;
+
+    /// Whether a report of the project is stored at a relative path
+    async fn path_exists(
+        &self,
+        project_id: &ProjectId,
+        path: &str,
+    ) -> Result<bool, RepositoryError>;
+
+    /// Remove a report from the index; returns false if it was not found
+    async fn delete(&self, id: &ReportId) -> Result<bool, RepositoryError>;
+}
@@ -0,0 +1,228 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::domain::det::{PmMark, PmNode};
+use crate::domain::document::DocumentId;
+use crate::domain::export::CITATION_MARK;
+use crate::domain::report::errors::ReportError;
+
+/// Where in a source document a citation points
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum CitationLocator {
+    /// The document as a whole
+    File,
+    /// A page, counted from 1
+    Page { page: u32 },
+    /// Characters `start..end` of the document's extracted text
+    CharRange { start: u32, end: u32 },
+    /// ProseMirror positions `from..to` in the document's extracted `.det`
+    Position { from: u32, to: u32 },
+}
+
+impl CitationLocator {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CitationLocator::File => "file",
+            CitationLocator::Page { .. } => "page",
+            CitationLocator::CharRange { .. } => "char_range",
+            CitationLocator::Position { .. } => "position",
+        }
+    }
+
+    fn validate(&self) -> Result<(), ReportError> {
+        let (start, end) = match *self {
+            CitationLocator::File => return Ok(()),
+            CitationLocator::Page { page } if page > 0 => return Ok(()),
+            CitationLocator::Page { .. } => {
+                return Err(ReportError::InvalidCitation(
+                    "pages are counted from 1".to_string(),
+                ))
+            }
+            CitationLocator::CharRange { start, end } => (start, end),
+            CitationLocator::Position { from, to } => (from, to),
+        };
+        if start >= end {
+            return Err(ReportError::InvalidCitation(format!(
+                "the range {}..{} is empty",
+                start, end
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// A reference from a report to a passage of a source document
+///
+/// The document identifier keeps the citation valid when the file is
+/// renamed or moved; the path records where the file was when it was
+/// cited, and is all there is for files that are not tracked.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", try_from = "CitationData")]
+pub struct Citation {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    document_id: Option<DocumentId>,
+    path: String,
+    locator: CitationLocator,
+}
+
+/// A citation as deserialized, before validation
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CitationData {
+    #[serde(default)]
+    document_id: Option<DocumentId>,
+    path: String,
+    locator: CitationLocator,
+}
+
+impl TryFrom<CitationData> for Citation {
+    type Error = ReportError;
+
+    fn try_from(data: CitationData) -> Result<Self, Self::Error> {
+        Citation::new(data.document_id, data.path, data.locator)
+    }
+}
+
+impl Citation {
+    pub fn new(
+        document_id: Option<DocumentId>,
+        path: String,
+        locator: CitationLocator,
+    ) -> Result<Self, ReportError> {
+        if path.trim().is_empty() {
+            return Err(ReportError::InvalidCitation(
+                "the cited path is empty".to_string(),
+            ));
+        }
+        locator.validate()?;
+
+        Ok(Citation {
+            document_id,
+            path,
+            locator,
+        })
+    }
+
+    /// Read a `citation` mark; other marks and malformed citations give `None`
+    ///
+    /// A character range takes precedence over a ProseMirror range, and
+    /// either over a page.
+    pub fn from_mark(mark: &PmMark) -> Option<Self> {
+        if mark.mark_type != CITATION_MARK {
+            return None;
+        }
+        let attrs = mark.attrs.as_ref()?;
+        let number = |key: &str| {
+            attrs
+                .get(key)
+                .and_then(Value::as_u64)
+                .and_then(|n| u32::try_from(n).ok())
+        };
+
+        let locator = match (
+            number("charStart").zip(number("charEnd")),
+            number("pmFrom").zip(number("pmTo")),
+            number("page"),
+        ) {
+            (Some((start, end)), _, _) => CitationLocator::CharRange { start, end },
+            (None, Some((from, to)), _) => CitationLocator::Position { from, to },
+            (None, None, Some(page)) => CitationLocator::Page { page },
+            (None, None, None) => CitationLocator::File,
+        };
+        let document_id = attrs
+            .get("documentId")
+            .and_then(Value::as_str)
+            .and_then(|id| DocumentId::from_string(id.to_string()).ok());
+        let path = attrs.get("path").and_then(Value::as_str)?;
+
+        Citation::new(document_id, path.to_string(), locator).ok()
+    }
+
+    /// Citations of a document's content, in reading order and without repeats
+    pub fn collect(content: &PmNode) -> Vec<Citation> {
+        fn walk(node: &PmNode, citations: &mut Vec<Citation>) {
+            for citation in node.marks.iter().filter_map(Citation::from_mark) {
+                if !citations.contains(&citation) {
+                    citations.push(citation);
+                }
+            }
+            for child in &node.content {
+                walk(child, citations);
+            }
+        }
+
+        let mut citations = Vec::new();
+        walk(content, &mut citations);
+        citations
+    }
+
+    pub fn document_id(&self) -> Option<&DocumentId> {
+        self.document_id.as_ref()
+    }
+
+    /// Path of the file when it was cited
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    pub fn locator(&self) -> CitationLocator {
+        self.locator
+    }
+
+    /// Tie an untracked citation to the document now found at its path
+    pub fn with_document_id(mut self, document_id: DocumentId) -> Self {
+        self.document_id = Some(document_id);
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::export::CitedSource;
+
+    #[test]
+    fn test_collect_reads_locators_from_marks() {
+        let document_id = DocumentId::new();
+        let page = CitedSource::new("/corpus/a.pdf")
+            .with_document_id(document_id.value())
+            .with_page(3)
+            .to_mark();
+        let mut range = CitedSource::new("/corpus/b.txt").to_mark();
+        let attrs = range.attrs.as_mut().unwrap();
+        attrs.insert("charStart".to_string(), Value::from(10));
+        attrs.insert("charEnd".to_string(), Value::from(42));
+
+        let content = PmNode::doc(vec![
+            PmNode::paragraph(vec![
+                PmNode::text("one", vec![page.clone()]),
+                PmNode::text("two", vec![PmMark::bold(), page]),
+            ]),
+            PmNode::paragraph(PmNode::text_nodes("three", vec![range])),
+        ]);
+
+        let citations = Citation::collect(&content);
+        assert_eq!(citations.len(), 2);
+        assert_eq!(citations[0].document_id(), Some(&document_id));
+        assert_eq!(citations[0].locator(), CitationLocator::Page { page: 3 });
+        assert_eq!(citations[1].document_id(), None);
+        assert_eq!(
+            citations[1].locator(),
+            CitationLocator::CharRange { start: 10, end: 42 }
+        );
+    }
+
+    #[test]
+    fn test_rejects_empty_ranges() {
+        let range = CitationLocator::Position { from: 5, to: 5 };
+        assert!(Citation::new(None, "/a.pdf".to_string(), range).is_err());
+        assert!(Citation::new(None, " ".to_string(), CitationLocator::File).is_err());
+        assert!(Citation::new(
+            None,
+            "/a.pdf".to_string(),
+            CitationLocator::Page { page: 0 }
+        )
+        .is_err());
+    }
+}
@@ -0,0 +1,38 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Outcome of resolving a citation against the current workspace
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CitationStatus {
+    /// The cited file is where it was cited
+    Valid,
+    /// The cited document was renamed or moved; the citation follows it
+    Moved,
+    /// The cited file can no longer be found
+    Missing,
+    /// The page or range lies beyond the cited document
+    OutOfRange,
+}
+
+impl CitationStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CitationStatus::Valid => "valid",
+            CitationStatus::Moved => "moved",
+            CitationStatus::Missing => "missing",
+            CitationStatus::OutOfRange => "out_of_range",
+        }
+    }
+
+    /// Whether the citation no longer leads to the cited passage
+    pub fn is_broken(&self) -> bool {
+        matches!(self, CitationStatus::Missing | CitationStatus::OutOfRange)
+    }
+}
+
+impl fmt::Display for CitationStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
@@ -0,0 +1,11 @@
+pub mod citation;
+pub mod citation_status;
+pub mod report_category;
+pub mod report_id;
+pub mod report_status;
+
+pub use citation::{Citation, CitationLocator};
+pub use citation_status::CitationStatus;
+pub use report_category::ReportCategory;
+pub use report_id::ReportId;
+pub use report_status::ReportStatus;
@@ -0,0 +1,61 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use crate::domain::report::errors::ReportError;
+
+/// Kind of report, which decides its subfolder of the reports folder
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReportCategory {
+    /// Research findings and conclusions
+    Findings,
+    /// Intermediate analysis, notes and executive summaries
+    AnalysisSummary,
+    /// Final reports for delivery
+    Deliverable,
+    /// Reports prepared for a client
+    ClientReport,
+}
+
+impl ReportCategory {
+    pub const ALL: [ReportCategory; 4] = [
+        ReportCategory::Findings,
+        ReportCategory::AnalysisSummary,
+        ReportCategory::Deliverable,
+        ReportCategory::ClientReport,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ReportCategory::Findings => "findings",
+            ReportCategory::AnalysisSummary => "analysis_summary",
+            ReportCategory::Deliverable => "deliverable",
+            ReportCategory::ClientReport => "client_report",
+        }
+    }
+
+    /// Subfolder of the reports folder holding reports of this category
+    pub fn folder_name(&self) -> &'static str {
+        match self {
+            ReportCategory::Findings => "findings",
+            ReportCategory::AnalysisSummary => "analysis-summaries",
+            ReportCategory::Deliverable => "deliverables",
+            ReportCategory::ClientReport => "client-reports",
+        }
+    }
+
+    /// Parse a category name or folder name
+    pub fn parse(value: &str) -> Result<Self, ReportError> {
+        let value = value.trim().to_lowercase();
+        ReportCategory::ALL
+            .into_iter()
+            .find(|category| category.as_str() == value || category.folder_name() == value)
+            .ok_or(ReportError::UnknownCategory(value))
+    }
+}
+
+impl fmt::Display for ReportCategory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
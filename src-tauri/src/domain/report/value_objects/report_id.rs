@@ -0,0 +1,44 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use uuid::Uuid;
+
+use crate::domain::report::errors::ReportError;
+
+/// ReportId value object identifying a report independent of its file
+///
+/// All report identifiers use the format: report_xxxxxxxx-xxxx-xxxx-xxxx-xxxxxxxxxxxx
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct ReportId(String);
+
+impl ReportId {
+    const PREFIX: &'static str = "report_";
+
+    /// Create a new ReportId with a generated UUID
+    pub fn new() -> Self {
+        ReportId(format!("{}{}", Self::PREFIX, Uuid::new_v4()))
+    }
+
+    /// Create a ReportId from an existing string, validating the prefix format
+    pub fn from_string(value: String) -> Result<Self, ReportError> {
+        match value.strip_prefix(Self::PREFIX) {
+            Some(uuid_part) if uuid_part.parse::<Uuid>().is_ok() => Ok(ReportId(value)),
+            _ => Err(ReportError::InvalidId(value)),
+        }
+    }
+
+    pub fn value(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Default for ReportId {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Display for ReportId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
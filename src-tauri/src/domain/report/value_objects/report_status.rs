@@ -0,0 +1,37 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use crate::domain::report::errors::ReportError;
+
+/// Editing state of a report
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReportStatus {
+    #[default]
+    Draft,
+    /// Signed off; the content is frozen until reopened as a draft
+    Final,
+}
+
+impl ReportStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ReportStatus::Draft => "draft",
+            ReportStatus::Final => "final",
+        }
+    }
+
+    pub fn parse(value: &str) -> Result<Self, ReportError> {
+        match value.trim().to_lowercase().as_str() {
+            "draft" => Ok(ReportStatus::Draft),
+            "final" => Ok(ReportStatus::Final),
+            other => Err(ReportError::UnknownStatus(other.to_string())),
+        }
+    }
+}
+
+impl fmt::Display for ReportStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
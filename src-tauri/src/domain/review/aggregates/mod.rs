@@ -0,0 +1,5 @@
+pub mod review_batch;
+pub mod reviewer;
+
+pub use review_batch::ReviewBatch;
+pub use reviewer::Reviewer;
@@ -0,0 +1,198 @@
+use chrono::{DateTime, Utc};
+
+use crate::domain::document::DocumentId;
+use crate::domain::project::ProjectId;
+use crate::domain::review::aggregates::reviewer::validate_name;
+use crate::domain::review::errors::ReviewError;
+use crate::domain::review::value_objects::{BatchSource, BatchStatus, ReviewBatchId, ReviewerId};
+
+/// ReviewBatch aggregate root: a named set of documents of a project for
+/// one reviewer to code
+///
+/// Business Rules:
+/// - A batch is built from a folder, a search or a category, and keeps the
+///   documents it was given in review order
+/// - A batch is unassigned until handed to a reviewer
+/// - A closed batch no longer changes
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReviewBatch {
+    id: ReviewBatchId,
+    project_id: ProjectId,
+    name: String,
+    source: BatchSource,
+    reviewer_id: Option<ReviewerId>,
+    status: BatchStatus,
+    document_ids: Vec<DocumentId>,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+    closed_at: Option<DateTime<Utc>>,
+}
+
+impl ReviewBatch {
+    /// Create an unassigned batch of `document_ids`
+    pub fn new(
+        project_id: ProjectId,
+        name: String,
+        source: BatchSource,
+        document_ids: Vec<DocumentId>,
+    ) -> Result<Self, ReviewError> {
+        let now = Utc::now();
+        Ok(ReviewBatch {
+            id: ReviewBatchId::new(),
+            project_id,
+            name: validate_name(name)?,
+            source,
+            reviewer_id: None,
+            status: BatchStatus::Unassigned,
+            document_ids,
+            created_at: now,
+            updated_at: now,
+            closed_at: None,
+        })
+    }
+
+    /// Create a ReviewBatch from existing data (for repository reconstruction)
+    #[allow(clippy::too_many_arguments)]
+    pub fn from_data(
+        id: ReviewBatchId,
+        project_id: ProjectId,
+        name: String,
+        source: BatchSource,
+        reviewer_id: Option<ReviewerId>,
+        status: BatchStatus,
+        document_ids: Vec<DocumentId>,
+        created_at: DateTime<Utc>,
+        updated_at: DateTime<Utc>,
+        closed_at: Option<DateTime<Utc>>,
+    ) -> Self {
+        ReviewBatch {
+            id,
+            project_id,
+            name,
+            source,
+            reviewer_id,
+            status,
+            document_ids,
+            created_at,
+            updated_at,
+            closed_at,
+        }
+    }
+
+    pub fn id(&self) -> &ReviewBatchId {
+        &self.id
+    }
+
+    pub fn project_id(&self) -> &ProjectId {
+        &self.project_id
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn source(&self) -> &BatchSource {
+        &self.source
+    }
+
+    pub fn reviewer_id(&self) -> Option<&ReviewerId> {
+        self.reviewer_id.as_ref()
+    }
+
+    pub fn status(&self) -> BatchStatus {
+        self.status
+    }
+
+    pub fn is_closed(&self) -> bool {
+        self.status == BatchStatus::Closed
+    }
+
+    /// The documents of the batch, in review order
+    pub fn document_ids(&self) -> &[DocumentId] {
+        &self.document_ids
+    }
+
+    pub fn created_at(&self) -> DateTime<Utc> {
+        self.created_at
+    }
+
+    pub fn updated_at(&self) -> DateTime<Utc> {
+        self.updated_at
+    }
+
+    pub fn closed_at(&self) -> Option<DateTime<Utc>> {
+        self.closed_at
+    }
+
+    /// Hand the batch to a reviewer, or take it back with `None`
+    pub fn assign(&mut self, reviewer_id: Option<ReviewerId>) -> Result<(), ReviewError> {
+        self.ensure_open()?;
+        self.status = if reviewer_id.is_some() {
+            BatchStatus::Assigned
+        } else {
+            BatchStatus::Unassigned
+        };
+        self.reviewer_id = reviewer_id;
+        self.touch();
+        Ok(())
+    }
+
+    /// Replace the documents of the batch, e.g. when rebalancing
+    pub fn set_documents(&mut self, document_ids: Vec<DocumentId>) -> Result<(), ReviewError> {
+        self.ensure_open()?;
+        self.document_ids = document_ids;
+        self.touch();
+        Ok(())
+    }
+
+    /// Close the batch
+    pub fn close(&mut self) -> Result<(), ReviewError> {
+        self.ensure_open()?;
+        self.status = BatchStatus::Closed;
+        self.closed_at = Some(Utc::now());
+        self.touch();
+        Ok(())
+    }
+
+    fn ensure_open(&self) -> Result<(), ReviewError> {
+        if self.is_closed() {
+            return Err(ReviewError::BatchClosed(self.name.clone()));
+        }
+        Ok(())
+    }
+
+    fn touch(&mut self) {
+        self.updated_at = Utc::now();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_assign_and_close() {
+        let mut batch = ReviewBatch::new(
+            ProjectId::new(),
+            " Contracts ".to_string(),
+            BatchSource::Folder {
+                path: "/case/contracts".to_string(),
+            },
+            vec![DocumentId::new(), DocumentId::new()],
+        )
+        .unwrap();
+        assert_eq!(batch.name(), "Contracts");
+        assert_eq!(batch.status(), BatchStatus::Unassigned);
+
+        let reviewer_id = ReviewerId::new();
+        batch.assign(Some(reviewer_id.clone())).unwrap();
+        assert_eq!(batch.status(), BatchStatus::Assigned);
+        assert_eq!(batch.reviewer_id(), Some(&reviewer_id));
+
+        batch.close().unwrap();
+        assert!(batch.closed_at().is_some());
+        assert!(batch.assign(None).is_err());
+        assert!(batch.set_documents(Vec::new()).is_err());
+        assert!(batch.close().is_err());
+    }
+}
@@ -0,0 +1,71 @@
+use chrono::{DateTime, Utc};
+
+use crate::domain::review::errors::ReviewError;
+use crate::domain::review::value_objects::ReviewerId;
+
+/// Longest reviewer name accepted
+const MAX_NAME_LENGTH: usize = 100;
+
+/// Reviewer aggregate root: a local profile for someone reviewing on this
+/// machine
+///
+/// Business Rules:
+/// - Names are unique, ignoring case, since coding decisions and
+///   annotations record the reviewer by name
+/// - One reviewer at a time may be active; their name is recorded when no
+///   other is given
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Reviewer {
+    id: ReviewerId,
+    name: String,
+    created_at: DateTime<Utc>,
+}
+
+impl Reviewer {
+    /// Create a new reviewer profile
+    pub fn new(name: String) -> Result<Self, ReviewError> {
+        Ok(Reviewer {
+            id: ReviewerId::new(),
+            name: validate_name(name)?,
+            created_at: Utc::now(),
+        })
+    }
+
+    /// Create a Reviewer from existing data (for repository reconstruction)
+    pub fn from_data(id: ReviewerId, name: String, created_at: DateTime<Utc>) -> Self {
+        Reviewer {
+            id,
+            name,
+            created_at,
+        }
+    }
+
+    pub fn id(&self) -> &ReviewerId {
+        &self.id
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn created_at(&self) -> DateTime<Utc> {
+        self.created_at
+    }
+}
+
+/// Trim a reviewer or batch name and check it is usable
+pub(crate) fn validate_name(name: String) -> Result<String, ReviewError> {
+    let name = name.trim().to_string();
+    if name.is_empty() {
+        return Err(ReviewError::InvalidName(
+            "the name cannot be empty".to_string(),
+        ));
+    }
+    if name.chars().count() > MAX_NAME_LENGTH {
+        return Err(ReviewError::InvalidName(format!(
+            "the name cannot be longer than {} characters",
+            MAX_NAME_LENGTH
+        )));
+    }
+    Ok(name)
+}
@@ -0,0 +1,3 @@
+pub mod review_error;
+
+pub use review_error::ReviewError;
@@ -0,0 +1,14 @@
+use thiserror::Error;
+
+/// Errors raised by reviewer profiles and review batches
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum ReviewError {
+    #[error("Invalid review ID: {0}")]
+    InvalidId(String),
+
+    #[error("Invalid name: {0}")]
+    InvalidName(String),
+
+    #[error("The review batch '{0}' is closed")]
+    BatchClosed(String),
+}
@@ -0,0 +1,10 @@
+pub mod aggregates;
+pub mod errors;
+pub mod repositories;
+pub mod value_objects;
+
+// Re-export commonly used types
+pub use aggregates::{ReviewBatch, Reviewer};
+pub use errors::ReviewError;
+pub use repositories::ReviewRepository;
+pub use value_objects::{BatchSource, BatchStatus, ReviewBatchId, ReviewerId};
@@ -0,0 +1,3 @@
+pub mod review_repository;
+
+pub use review_repository::ReviewRepository;
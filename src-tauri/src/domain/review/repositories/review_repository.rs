@@ -0,0 +1,36 @@
+use async_trait::async_trait;
+
+use crate::domain::project::ProjectId;
+use crate::domain::review::aggregates::{ReviewBatch, Reviewer};
+use crate::domain::review::value_objects::{ReviewBatchId, ReviewerId};
+use crate::domain::workspace::repositories::RepositoryError;
+
+/// Repository trait for reviewer profiles and review batches
+#[async_trait]
+pub trait ReviewRepository: Send + Sync {
+    /// Insert or update a reviewer; fails with a constraint violation if
+    /// another reviewer has the same name
+    async fn save_reviewer(&self, reviewer: &Reviewer) -> Result<(), RepositoryError>;
+
+    async fn find_reviewer(&self, id: &ReviewerId) -> Result<Option<Reviewer>, RepositoryError>;
+
+    /// All reviewers, by name
+    async fn list_reviewers(&self) -> Result<Vec<Reviewer>, RepositoryError>;
+
+    /// The reviewer currently working on this machine, if any
+    async fn active_reviewer(&self) -> Result<Option<Reviewer>, RepositoryError>;
+
+    /// Make a reviewer the active one, or clear it with `None`
+    async fn set_active_reviewer(&self, id: Option<&ReviewerId>) -> Result<(), RepositoryError>;
+
+    /// Insert or update a batch
+    async fn save_batch(&self, batch: &ReviewBatch) -> Result<(), RepositoryError>;
+
+    async fn find_batch(&self, id: &ReviewBatchId) -> Result<Option<ReviewBatch>, RepositoryError>;
+
+    /// All batches of a project, oldest first
+    async fn list_batches(
+        &self,
+        project_id: &ProjectId,
+    ) -> Result<Vec<ReviewBatch>, RepositoryError>;
+}
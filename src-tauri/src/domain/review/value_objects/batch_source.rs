@@ -0,0 +1,64 @@
+use serde::{Deserialize, Serialize};
+
+/// What a review batch was built from
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum BatchSource {
+    /// The documents below a folder of the project
+    Folder { path: String },
+
+    /// The documents found by a search query
+    Search { query: String },
+
+    /// The documents assigned to a category or tag
+    #[serde(rename_all = "camelCase")]
+    Category {
+        category_id: String,
+        #[serde(default)]
+        include_descendants: bool,
+    },
+}
+
+impl BatchSource {
+    /// Short name of the source type: "folder", "search" or "category"
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            BatchSource::Folder { .. } => "folder",
+            BatchSource::Search { .. } => "search",
+            BatchSource::Category { .. } => "category",
+        }
+    }
+}
+
+/// Where a review batch stands
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BatchStatus {
+    /// Waiting for a reviewer
+    Unassigned,
+
+    /// Handed to a reviewer
+    Assigned,
+
+    /// Finished with; its uncoded documents may go into new batches
+    Closed,
+}
+
+impl BatchStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BatchStatus::Unassigned => "unassigned",
+            BatchStatus::Assigned => "assigned",
+            BatchStatus::Closed => "closed",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "unassigned" => Some(BatchStatus::Unassigned),
+            "assigned" => Some(BatchStatus::Assigned),
+            "closed" => Some(BatchStatus::Closed),
+            _ => None,
+        }
+    }
+}
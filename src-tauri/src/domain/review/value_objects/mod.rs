@@ -0,0 +1,7 @@
+pub mod batch_source;
+pub mod review_batch_id;
+pub mod reviewer_id;
+
+pub use batch_source::{BatchSource, BatchStatus};
+pub use review_batch_id::ReviewBatchId;
+pub use reviewer_id::ReviewerId;
@@ -0,0 +1,44 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use uuid::Uuid;
+
+use crate::domain::review::errors::ReviewError;
+
+/// ReviewBatchId value object identifying a review batch
+///
+/// All review batch identifiers use the format: batch_xxxxxxxx-xxxx-xxxx-xxxx-xxxxxxxxxxxx
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct ReviewBatchId(String);
+
+impl ReviewBatchId {
+    const PREFIX: &'static str = "batch_";
+
+    /// Create a new ReviewBatchId with a generated UUID
+    pub fn new() -> Self {
+        ReviewBatchId(format!("{}{}", Self::PREFIX, Uuid::new_v4()))
+    }
+
+    /// Create a ReviewBatchId from an existing string, validating the prefix format
+    pub fn from_string(value: String) -> Result<Self, ReviewError> {
+        match value.strip_prefix(Self::PREFIX) {
+            Some(uuid_part) if uuid_part.parse::<Uuid>().is_ok() => Ok(ReviewBatchId(value)),
+            _ => Err(ReviewError::InvalidId(value)),
+        }
+    }
+
+    pub fn value(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Default for ReviewBatchId {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Display for ReviewBatchId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
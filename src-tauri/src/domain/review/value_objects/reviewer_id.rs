@@ -0,0 +1,44 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use uuid::Uuid;
+
+use crate::domain::review::errors::ReviewError;
+
+/// ReviewerId value object identifying a local reviewer profile
+///
+/// All reviewer identifiers use the format: reviewer_xxxxxxxx-xxxx-xxxx-xxxx-xxxxxxxxxxxx
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct ReviewerId(String);
+
+impl ReviewerId {
+    const PREFIX: &'static str = "reviewer_";
+
+    /// Create a new ReviewerId with a generated UUID
+    pub fn new() -> Self {
+        ReviewerId(format!("{}{}", Self::PREFIX, Uuid::new_v4()))
+    }
+
+    /// Create a ReviewerId from an existing string, validating the prefix format
+    pub fn from_string(value: String) -> Result<Self, ReviewError> {
+        match value.strip_prefix(Self::PREFIX) {
+            Some(uuid_part) if uuid_part.parse::<Uuid>().is_ok() => Ok(ReviewerId(value)),
+            _ => Err(ReviewError::InvalidId(value)),
+        }
+    }
+
+    pub fn value(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Default for ReviewerId {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Display for ReviewerId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
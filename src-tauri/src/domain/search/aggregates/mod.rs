@@ -0,0 +1,3 @@
+pub mod saved_search;
+
+pub use saved_search::SavedSearch;
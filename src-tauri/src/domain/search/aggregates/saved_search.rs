@@ -0,0 +1,276 @@
+use chrono::{DateTime, Utc};
+use std::collections::BTreeSet;
+
+use crate::domain::project::ProjectId;
+use crate::domain::search::errors::SearchError;
+use crate::domain::search::value_objects::{SavedSearchId, SearchQuery};
+
+/// Longest name accepted for a saved search
+const MAX_NAME_LENGTH: usize = 255;
+
+/// SavedSearch aggregate root: a named query of a project, shown as a
+/// virtual folder of its results
+///
+/// Business Rules:
+/// - The query must parse; the name must be usable as a folder name
+/// - When viewed, the keys of all results are remembered as seen
+/// - Results found afterwards that were not seen count as new until the
+///   search is viewed again
+/// - Changing the query forgets what was seen
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SavedSearch {
+    id: SavedSearchId,
+    project_id: ProjectId,
+    name: String,
+    query: String,
+    track_new: bool,
+    seen: BTreeSet<String>,
+    new_results: BTreeSet<String>,
+    last_viewed_at: Option<DateTime<Utc>>,
+    checked_at: Option<DateTime<Utc>>,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+}
+
+impl SavedSearch {
+    /// Create a saved search that has not been viewed yet
+    pub fn new(
+        project_id: ProjectId,
+        name: String,
+        query: String,
+        track_new: bool,
+    ) -> Result<Self, SearchError> {
+        SearchQuery::parse(&query)?;
+        let now = Utc::now();
+        Ok(SavedSearch {
+            id: SavedSearchId::new(),
+            project_id,
+            name: validate_name(name)?,
+            query: query.trim().to_string(),
+            track_new,
+            seen: BTreeSet::new(),
+            new_results: BTreeSet::new(),
+            last_viewed_at: None,
+            checked_at: None,
+            created_at: now,
+            updated_at: now,
+        })
+    }
+
+    /// Create a SavedSearch from existing data (for repository reconstruction)
+    #[allow(clippy::too_many_arguments)]
+    pub fn from_data(
+        id: SavedSearchId,
+        project_id: ProjectId,
+        name: String,
+        query: String,
+        track_new: bool,
+        seen: BTreeSet<String>,
+        new_results: BTreeSet<String>,
+        last_viewed_at: Option<DateTime<Utc>>,
+        checked_at: Option<DateTime<Utc>>,
+        created_at: DateTime<Utc>,
+        updated_at: DateTime<Utc>,
+    ) -> Self {
+        SavedSearch {
+            id,
+            project_id,
+            name,
+            query,
+            track_new,
+            seen,
+            new_results,
+            last_viewed_at,
+            checked_at,
+            created_at,
+            updated_at,
+        }
+    }
+
+    pub fn id(&self) -> &SavedSearchId {
+        &self.id
+    }
+
+    pub fn project_id(&self) -> &ProjectId {
+        &self.project_id
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn query(&self) -> &str {
+        &self.query
+    }
+
+    /// The query, parsed
+    pub fn parsed_query(&self) -> Result<SearchQuery, SearchError> {
+        SearchQuery::parse(&self.query)
+    }
+
+    /// Whether results found since the last view are counted
+    pub fn tracks_new(&self) -> bool {
+        self.track_new
+    }
+
+    /// Keys of the results when the search was last viewed
+    pub fn seen(&self) -> &BTreeSet<String> {
+        &self.seen
+    }
+
+    /// Keys of the results found since the last view
+    pub fn new_results(&self) -> &BTreeSet<String> {
+        &self.new_results
+    }
+
+    pub fn last_viewed_at(&self) -> Option<DateTime<Utc>> {
+        self.last_viewed_at
+    }
+
+    /// When new results were last looked for
+    pub fn checked_at(&self) -> Option<DateTime<Utc>> {
+        self.checked_at
+    }
+
+    pub fn created_at(&self) -> DateTime<Utc> {
+        self.created_at
+    }
+
+    pub fn updated_at(&self) -> DateTime<Utc> {
+        self.updated_at
+    }
+
+    /// Number of new results, when they are tracked and the search has been
+    /// viewed before
+    pub fn new_result_count(&self) -> Option<usize> {
+        (self.track_new && self.last_viewed_at.is_some()).then_some(self.new_results.len())
+    }
+
+    pub fn rename(&mut self, name: String) -> Result<(), SearchError> {
+        self.name = validate_name(name)?;
+        self.updated_at = Utc::now();
+        Ok(())
+    }
+
+    /// Replace the query; what was seen no longer applies
+    pub fn set_query(&mut self, query: String) -> Result<(), SearchError> {
+        SearchQuery::parse(&query)?;
+        self.query = query.trim().to_string();
+        self.seen.clear();
+        self.new_results.clear();
+        self.last_viewed_at = None;
+        self.checked_at = None;
+        self.updated_at = Utc::now();
+        Ok(())
+    }
+
+    pub fn set_track_new(&mut self, track_new: bool) {
+        self.track_new = track_new;
+        self.updated_at = Utc::now();
+    }
+
+    /// Remember all current results as seen
+    pub fn mark_viewed(&mut self, keys: impl IntoIterator<Item = String>, at: DateTime<Utc>) {
+        self.seen = keys.into_iter().collect();
+        self.new_results.clear();
+        self.last_viewed_at = Some(at);
+        self.checked_at = Some(at);
+    }
+
+    /// Add the results among items changed since the last check that were
+    /// not seen
+    pub fn record_changed_matches(
+        &mut self,
+        keys: impl IntoIterator<Item = String>,
+        at: DateTime<Utc>,
+    ) {
+        let unseen: Vec<String> = keys
+            .into_iter()
+            .filter(|key| !self.seen.contains(key))
+            .collect();
+        self.new_results.extend(unseen);
+        self.checked_at = Some(at);
+    }
+}
+
+fn validate_name(name: String) -> Result<String, SearchError> {
+    let name = name.trim().to_string();
+    if name.is_empty() {
+        return Err(SearchError::InvalidName(
+            "the name cannot be empty".to_string(),
+        ));
+    }
+    if name.chars().count() > MAX_NAME_LENGTH {
+        return Err(SearchError::InvalidName(format!(
+            "the name cannot be longer than {} characters",
+            MAX_NAME_LENGTH
+        )));
+    }
+    if name.contains(['/', '\\']) {
+        return Err(SearchError::InvalidName(
+            "the name cannot contain slashes".to_string(),
+        ));
+    }
+    Ok(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keys(values: &[&str]) -> Vec<String> {
+        values.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn test_new_results_since_last_view() {
+        let mut search = SavedSearch::new(
+            ProjectId::new(),
+            "Weekly PDFs".to_string(),
+            "ext:pdf modified:>2024-01-01".to_string(),
+            true,
+        )
+        .unwrap();
+        assert_eq!(search.new_result_count(), None);
+
+        search.mark_viewed(keys(&["original:a.pdf"]), Utc::now());
+        assert_eq!(search.new_result_count(), Some(0));
+
+        // A changed result that was seen before is not new
+        search.record_changed_matches(keys(&["original:a.pdf", "original:b.pdf"]), Utc::now());
+        search.record_changed_matches(keys(&["original:b.pdf", "original:c.pdf"]), Utc::now());
+        assert_eq!(search.new_result_count(), Some(2));
+
+        search.mark_viewed(
+            keys(&["original:a.pdf", "original:b.pdf", "original:c.pdf"]),
+            Utc::now(),
+        );
+        assert_eq!(search.new_result_count(), Some(0));
+
+        search.set_query("ext:docx".to_string()).unwrap();
+        assert!(search.seen().is_empty());
+        assert_eq!(search.new_result_count(), None);
+    }
+
+    #[test]
+    fn test_validation() {
+        let create = |name: &str, query: &str| {
+            SavedSearch::new(ProjectId::new(), name.to_string(), query.to_string(), false)
+        };
+
+        assert!(matches!(
+            create(" ", "budget"),
+            Err(SearchError::InvalidName(_))
+        ));
+        assert!(matches!(
+            create("a/b", "budget"),
+            Err(SearchError::InvalidName(_))
+        ));
+        assert_eq!(create("Budget", "").unwrap_err(), SearchError::EmptyQuery);
+
+        let search = create("  Budget ", " budget ").unwrap();
+        assert_eq!(search.name(), "Budget");
+        assert_eq!(search.query(), "budget");
+        assert_eq!(search.new_result_count(), None);
+    }
+}
@@ -0,0 +1,3 @@
+pub mod search_error;
+
+pub use search_error::SearchError;
@@ -0,0 +1,42 @@
+use thiserror::Error;
+
+/// A search query that cannot be understood, or an invalid saved search
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum SearchError {
+    #[error("Enter a search term or filter")]
+    EmptyQuery,
+
+    #[error("Missing closing quote in the search query")]
+    UnterminatedPhrase,
+
+    #[error("Unknown filter '{0}:'; use type:, ext:, tag:, modified: or derivative:")]
+    UnknownFilter(String),
+
+    #[error("Invalid value '{value}' for {filter}: {reason}")]
+    InvalidValue {
+        filter: String,
+        value: String,
+        reason: String,
+    },
+
+    #[error("Invalid saved search ID: {0}")]
+    InvalidId(String),
+
+    #[error("Invalid saved search name: {0}")]
+    InvalidName(String),
+}
+
+impl SearchError {
+    /// Create an InvalidValue error for a filter
+    pub fn invalid_value(
+        filter: impl Into<String>,
+        value: impl Into<String>,
+        reason: impl ToString,
+    ) -> Self {
+        SearchError::InvalidValue {
+            filter: filter.into(),
+            value: value.into(),
+            reason: reason.to_string(),
+        }
+    }
+}
@@ -0,0 +1,13 @@
+pub mod aggregates;
+pub mod errors;
+pub mod repositories;
+pub mod value_objects;
+
+// Re-export commonly used types
+pub use aggregates::SavedSearch;
+pub use errors::SearchError;
+pub use repositories::{SavedSearchRepository, SearchIndexRepository};
+pub use value_objects::{
+    DateBound, IndexedText, SavedSearchId, SearchItem, SearchMatch, SearchQuery, SourceKind,
+    TypeFilter,
+};
@@ -0,0 +1,5 @@
+pub mod saved_search_repository;
+pub mod search_index_repository;
+
+pub use saved_search_repository::SavedSearchRepository;
+pub use search_index_repository::SearchIndexRepository;
@@ -0,0 +1,23 @@
+use async_trait::async_trait;
+
+use crate::domain::project::ProjectId;
+use crate::domain::search::aggregates::SavedSearch;
+use crate::domain::search::value_objects::SavedSearchId;
+use crate::domain::workspace::repositories::RepositoryError;
+
+/// Repository trait for the saved searches of projects
+#[async_trait]
+pub trait SavedSearchRepository: Send + Sync {
+    /// Insert or update a saved search
+    async fn save(&self, search: &SavedSearch) -> Result<(), RepositoryError>;
+
+    async fn find_by_id(&self, id: &SavedSearchId) -> Result<Option<SavedSearch>, RepositoryError>;
+
+    /// List the saved searches of a project by name
+    async fn list_by_project(
+        &self,
+        project_id: &ProjectId,
+    ) -> Result<Vec<SavedSearch>, RepositoryError>;
+
+    async fn delete(&self, id: &SavedSearchId) -> Result<(), RepositoryError>;
+}
@@ -0,0 +1,35 @@
+use async_trait::async_trait;
+use std::collections::{HashMap, HashSet};
+
+use crate::domain::project::ProjectId;
+use crate::domain::search::value_objects::IndexedText;
+use crate::domain::workspace::repositories::RepositoryError;
+
+/// Repository trait for the full-text index of derivatives and reports
+#[async_trait]
+pub trait SearchIndexRepository: Send + Sync {
+    /// Insert or replace the text of a file
+    async fn save(&self, entry: &IndexedText, text: &str) -> Result<(), RepositoryError>;
+
+    /// The indexed files of a project, without their text
+    async fn list_by_project(
+        &self,
+        project_id: &ProjectId,
+    ) -> Result<Vec<IndexedText>, RepositoryError>;
+
+    /// Paths of a project's files whose text contains `words`
+    ///
+    /// A single word matches as a word prefix, several words match as a
+    /// phrase of whole words, as in `SearchQuery`.
+    async fn paths_containing(
+        &self,
+        project_id: &ProjectId,
+        words: &[String],
+    ) -> Result<HashSet<String>, RepositoryError>;
+
+    /// The texts of indexed files by path
+    async fn texts(&self, paths: &[String]) -> Result<HashMap<String, String>, RepositoryError>;
+
+    /// Remove files from the index
+    async fn remove(&self, paths: &[String]) -> Result<(), RepositoryError>;
+}
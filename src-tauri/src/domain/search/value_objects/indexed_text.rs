@@ -0,0 +1,29 @@
+use chrono::{DateTime, Utc};
+
+use crate::domain::document::DocumentId;
+use crate::domain::project::ProjectId;
+
+use super::SourceKind;
+
+/// A derivative or report whose text is in the search index
+///
+/// The text itself is only read back for items that may match a query.
+#[derive(Debug, Clone, PartialEq)]
+pub struct IndexedText {
+    pub project_id: ProjectId,
+
+    /// Path of the `.det` file
+    pub path: String,
+
+    pub kind: SourceKind,
+
+    /// The document a derivative belongs to; reports have none
+    pub document_id: Option<DocumentId>,
+
+    /// When the content was last processed, as shown in search
+    pub modified_at: DateTime<Utc>,
+
+    /// Modification time of the file when it was indexed, so changed files
+    /// can be told apart without reading them
+    pub written_at: DateTime<Utc>,
+}
@@ -0,0 +1,9 @@
+pub mod indexed_text;
+pub mod saved_search_id;
+pub mod search_item;
+pub mod search_query;
+
+pub use indexed_text::IndexedText;
+pub use saved_search_id::SavedSearchId;
+pub use search_item::{SearchItem, SearchMatch, SourceKind};
+pub use search_query::{DateBound, SearchQuery, TypeFilter};
@@ -0,0 +1,44 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use uuid::Uuid;
+
+use crate::domain::search::errors::SearchError;
+
+/// SavedSearchId value object identifying a saved search
+///
+/// All saved search identifiers use the format: search_xxxxxxxx-xxxx-xxxx-xxxx-xxxxxxxxxxxx
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct SavedSearchId(String);
+
+impl SavedSearchId {
+    const PREFIX: &'static str = "search_";
+
+    /// Create a new SavedSearchId with a generated UUID
+    pub fn new() -> Self {
+        SavedSearchId(format!("{}{}", Self::PREFIX, Uuid::new_v4()))
+    }
+
+    /// Create a SavedSearchId from an existing string, validating the prefix format
+    pub fn from_string(value: String) -> Result<Self, SearchError> {
+        match value.strip_prefix(Self::PREFIX) {
+            Some(uuid_part) if uuid_part.parse::<Uuid>().is_ok() => Ok(SavedSearchId(value)),
+            _ => Err(SearchError::InvalidId(value)),
+        }
+    }
+
+    pub fn value(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Default for SavedSearchId {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Display for SavedSearchId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
@@ -0,0 +1,103 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::domain::workspace::value_objects::FileCategory;
+
+/// Where a search hit comes from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SourceKind {
+    /// A tracked source document, matched by name and path
+    Original,
+
+    /// A `.det` file derived from a source document, matched by its text
+    Derivative,
+
+    /// A project report, matched by title and text
+    Report,
+
+    /// Metadata read from a source document, such as embedded media tags
+    Metadata,
+}
+
+impl SourceKind {
+    pub const ALL: [SourceKind; 4] = [
+        SourceKind::Original,
+        SourceKind::Derivative,
+        SourceKind::Report,
+        SourceKind::Metadata,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SourceKind::Original => "original",
+            SourceKind::Derivative => "derivative",
+            SourceKind::Report => "report",
+            SourceKind::Metadata => "metadata",
+        }
+    }
+
+    /// Parse a kind, accepting plurals as used in `type:reports`
+    pub fn parse(value: &str) -> Option<Self> {
+        let value = value.trim().to_lowercase();
+        let value = value.strip_suffix('s').unwrap_or(&value);
+        Self::ALL.into_iter().find(|kind| kind.as_str() == value)
+    }
+}
+
+/// One searchable item, flattened from whichever source it comes from
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchItem {
+    pub kind: SourceKind,
+
+    /// File name or report title
+    pub title: String,
+
+    pub path: String,
+
+    /// Lower-case extension of the source document, if the item has one
+    pub extension: Option<String>,
+
+    /// Category of the source document, if the item has one
+    pub category: Option<FileCategory>,
+
+    /// Derivative file name without `.det`, e.g. "summary"
+    pub derivative: Option<String>,
+
+    pub tags: Vec<String>,
+
+    pub modified_at: Option<DateTime<Utc>>,
+
+    /// Body text to search and quote from
+    pub text: String,
+}
+
+impl SearchItem {
+    /// An item with only a kind, title and path
+    pub fn new(kind: SourceKind, title: impl Into<String>, path: impl Into<String>) -> Self {
+        SearchItem {
+            kind,
+            title: title.into(),
+            path: path.into(),
+            extension: None,
+            category: None,
+            derivative: None,
+            tags: Vec::new(),
+            modified_at: None,
+            text: String::new(),
+        }
+    }
+}
+
+/// How well an item matched a query
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchMatch {
+    /// Relevance; higher is better
+    pub score: f64,
+
+    /// Passage of the text around the first match, if the text matched
+    pub snippet: Option<String>,
+
+    /// Which parts of the item matched: "title", "tags" or "text"
+    pub matched_fields: Vec<&'static str>,
+}
@@ -0,0 +1,662 @@
+use chrono::{DateTime, NaiveDate, Utc};
+
+use super::search_item::{SearchItem, SearchMatch, SourceKind};
+use crate::domain::search::errors::SearchError;
+use crate::domain::workspace::value_objects::FileCategory;
+
+/// Relevance weight of a match in the title, tags, text and path
+const TITLE_WEIGHT: f64 = 3.0;
+const TAG_WEIGHT: f64 = 2.0;
+const TEXT_WEIGHT: f64 = 1.0;
+const PATH_WEIGHT: f64 = 0.5;
+
+/// Phrases are more specific than single terms
+const PHRASE_BOOST: f64 = 1.5;
+
+/// Characters of context quoted before and after the first match
+const SNIPPET_BEFORE: usize = 60;
+const SNIPPET_AFTER: usize = 140;
+
+/// A field's name, weight and words with their byte offsets
+type Field = (&'static str, f64, Vec<(usize, String)>);
+
+/// A `type:` value: a kind of source or a category of file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TypeFilter {
+    Source(SourceKind),
+    Category(FileCategory),
+}
+
+impl TypeFilter {
+    fn parse(value: &str) -> Result<Self, SearchError> {
+        SourceKind::parse(value)
+            .map(TypeFilter::Source)
+            .or_else(|| FileCategory::from_str(value).ok().map(TypeFilter::Category))
+            .ok_or_else(|| {
+                SearchError::invalid_value(
+                    "type",
+                    value,
+                    "use original, derivative, report, metadata, documents, audio, video, images or other",
+                )
+            })
+    }
+
+    fn matches(&self, item: &SearchItem) -> bool {
+        match self {
+            TypeFilter::Source(kind) => item.kind == *kind,
+            TypeFilter::Category(category) => item.category == Some(*category),
+        }
+    }
+
+    /// Whether items of a kind can pass this filter at all
+    fn admits(&self, kind: SourceKind) -> bool {
+        match self {
+            TypeFilter::Source(source) => *source == kind,
+            TypeFilter::Category(_) => kind != SourceKind::Report,
+        }
+    }
+}
+
+/// A `modified:` condition on the day an item was last changed, in UTC
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DateBound {
+    After(NaiveDate),
+    OnOrAfter(NaiveDate),
+    Before(NaiveDate),
+    OnOrBefore(NaiveDate),
+    On(NaiveDate),
+}
+
+impl DateBound {
+    /// Parse `>2024-01-01`, `>=`, `<`, `<=`, `=` or a bare date
+    pub fn parse(value: &str) -> Result<Self, SearchError> {
+        let (bound, date): (fn(NaiveDate) -> DateBound, &str) =
+            if let Some(date) = value.strip_prefix(">=") {
+                (DateBound::OnOrAfter, date)
+            } else if let Some(date) = value.strip_prefix("<=") {
+                (DateBound::OnOrBefore, date)
+            } else if let Some(date) = value.strip_prefix('>') {
+                (DateBound::After, date)
+            } else if let Some(date) = value.strip_prefix('<') {
+                (DateBound::Before, date)
+            } else {
+                (DateBound::On, value.strip_prefix('=').unwrap_or(value))
+            };
+
+        NaiveDate::parse_from_str(date, "%Y-%m-%d")
+            .map(bound)
+            .map_err(|_| SearchError::invalid_value("modified", value, "use e.g. >2024-01-01"))
+    }
+
+    pub fn matches(&self, modified_at: DateTime<Utc>) -> bool {
+        let day = modified_at.date_naive();
+        match *self {
+            DateBound::After(date) => day > date,
+            DateBound::OnOrAfter(date) => day >= date,
+            DateBound::Before(date) => day < date,
+            DateBound::OnOrBefore(date) => day <= date,
+            DateBound::On(date) => day == date,
+        }
+    }
+}
+
+/// A parsed search query
+///
+/// Free words are terms that must all occur (as word prefixes) in an item's
+/// title, path, tags or text; quoted text is a phrase that must occur as a
+/// whole. Filters narrow the items: values of one `type:`, `ext:` or
+/// `derivative:` filter are alternatives (`ext:pdf,docx`), while every
+/// `tag:` and `modified:` filter must hold. Any other word before a colon,
+/// as in `Re: settlement` or `http://example.com`, is ordinary text.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SearchQuery {
+    /// Lower-case words
+    pub terms: Vec<String>,
+
+    /// Lower-case words that must appear in sequence
+    pub phrases: Vec<Vec<String>>,
+
+    pub types: Vec<TypeFilter>,
+
+    /// Lower-case extensions without the dot
+    pub extensions: Vec<String>,
+
+    pub tags: Vec<String>,
+
+    pub modified: Vec<DateBound>,
+
+    /// Lower-case derivative names without `.det`
+    pub derivatives: Vec<String>,
+}
+
+impl SearchQuery {
+    /// Parse a query such as `"board minutes" budget ext:pdf modified:>2024-01-01`
+    pub fn parse(input: &str) -> Result<Self, SearchError> {
+        let mut query = SearchQuery::default();
+
+        for token in tokenize(input)? {
+            if token.quoted_from_start {
+                let phrase = words(&token.text)
+                    .into_iter()
+                    .map(|(_, word)| word)
+                    .collect::<Vec<_>>();
+                if !phrase.is_empty() {
+                    query.phrases.push(phrase);
+                }
+                continue;
+            }
+
+            match filter_name(&token.text) {
+                Some((name, value)) => query.add_filter(&name, value)?,
+                None => {
+                    let mut found: Vec<String> = words(&token.text)
+                        .into_iter()
+                        .map(|(_, word)| word)
+                        .collect();
+                    // "e-mail" must match as a whole, not as two terms
+                    if found.len() > 1 {
+                        query.phrases.push(found);
+                    } else {
+                        query.terms.append(&mut found);
+                    }
+                }
+            }
+        }
+
+        if query.is_empty() {
+            return Err(SearchError::EmptyQuery);
+        }
+        Ok(query)
+    }
+
+    fn add_filter(&mut self, name: &str, value: &str) -> Result<(), SearchError> {
+        let value = value.trim();
+        if value.is_empty() {
+            return Err(SearchError::invalid_value(
+                name,
+                value,
+                "the value is missing",
+            ));
+        }
+        let alternatives = || {
+            value
+                .split(',')
+                .map(|part| part.trim().to_lowercase())
+                .filter(|part| !part.is_empty())
+        };
+
+        match name {
+            "type" => {
+                for part in alternatives() {
+                    self.types.push(TypeFilter::parse(&part)?);
+                }
+            }
+            "ext" => self
+                .extensions
+                .extend(alternatives().map(|part| part.trim_start_matches('.').to_string())),
+            "tag" => self.tags.push(value.to_lowercase()),
+            "modified" => self.modified.push(DateBound::parse(value)?),
+            "derivative" => self
+                .derivatives
+                .extend(alternatives().map(|part| part.trim_end_matches(".det").to_string())),
+            other => return Err(SearchError::UnknownFilter(other.to_string())),
+        }
+        Ok(())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.terms.is_empty()
+            && self.phrases.is_empty()
+            && self.types.is_empty()
+            && self.extensions.is_empty()
+            && self.tags.is_empty()
+            && self.modified.is_empty()
+            && self.derivatives.is_empty()
+    }
+
+    /// Whether items of a kind can match at all, so sources can be skipped
+    pub fn admits(&self, kind: SourceKind) -> bool {
+        (self.types.is_empty() || self.types.iter().any(|filter| filter.admits(kind)))
+            && (self.extensions.is_empty() || kind != SourceKind::Report)
+            && (self.derivatives.is_empty() || kind == SourceKind::Derivative)
+    }
+
+    /// Whether an item passes all filters, ignoring terms and phrases
+    pub fn matches_filters(&self, item: &SearchItem) -> bool {
+        let extension_matches = || {
+            item.extension
+                .as_deref()
+                .is_some_and(|extension| self.extensions.iter().any(|e| e == extension))
+        };
+        let derivative_matches = || {
+            item.derivative.as_deref().is_some_and(|derivative| {
+                self.derivatives
+                    .iter()
+                    .any(|d| d.eq_ignore_ascii_case(derivative))
+            })
+        };
+
+        (self.types.is_empty() || self.types.iter().any(|filter| filter.matches(item)))
+            && (self.extensions.is_empty() || extension_matches())
+            && self.tags.iter().all(|tag| {
+                item.tags
+                    .iter()
+                    .any(|item_tag| item_tag.to_lowercase() == *tag)
+            })
+            && self.modified.iter().all(|bound| {
+                item.modified_at
+                    .is_some_and(|modified_at| bound.matches(modified_at))
+            })
+            && (self.derivatives.is_empty() || derivative_matches())
+    }
+
+    /// Terms and phrases as word sequences, terms first
+    pub fn needles(&self) -> Vec<&[String]> {
+        self.terms
+            .iter()
+            .map(std::slice::from_ref)
+            .chain(self.phrases.iter().map(Vec::as_slice))
+            .collect()
+    }
+
+    /// Whether an item whose text is not loaded yet may match
+    ///
+    /// The item must pass all filters, and each needle (see `needles`) must
+    /// occur in its title, tags or path unless `in_text` says, by the
+    /// needle's position, that it occurs in the text.
+    pub fn may_match(&self, item: &SearchItem, in_text: impl Fn(usize) -> bool) -> bool {
+        if !self.matches_filters(item) {
+            return false;
+        }
+
+        let tags = item.tags.join(" ");
+        let fields = [words(&item.title), words(&tags), words(&item.path)];
+        self.needles()
+            .into_iter()
+            .enumerate()
+            .all(|(index, needle)| {
+                in_text(index)
+                    || fields
+                        .iter()
+                        .any(|field| !occurrences(field, needle).is_empty())
+            })
+    }
+
+    /// Score an item against the query; `None` when it does not match
+    ///
+    /// Each term and phrase must occur somewhere. Its contribution grows
+    /// with the log of its count per field, weighted so that matches in the
+    /// title count most, then tags, text and path.
+    pub fn evaluate(&self, item: &SearchItem) -> Option<SearchMatch> {
+        if !self.matches_filters(item) {
+            return None;
+        }
+
+        let tags = item.tags.join(" ");
+        let fields: [Field; 4] = [
+            ("title", TITLE_WEIGHT, words(&item.title)),
+            ("tags", TAG_WEIGHT, words(&tags)),
+            ("text", TEXT_WEIGHT, words(&item.text)),
+            ("path", PATH_WEIGHT, words(&item.path)),
+        ];
+
+        let terms = self.terms.len();
+        let needles = self
+            .needles()
+            .into_iter()
+            .enumerate()
+            .map(|(index, needle)| (needle, if index < terms { 1.0 } else { PHRASE_BOOST }));
+
+        let mut score = 0.0;
+        let mut matched_fields = Vec::new();
+        let mut first_text_match: Option<usize> = None;
+        let mut any_needle = false;
+
+        for (needle, boost) in needles {
+            any_needle = true;
+            let mut needle_score = 0.0;
+            for (name, weight, field_words) in &fields {
+                let positions = occurrences(field_words, needle);
+                if positions.is_empty() {
+                    continue;
+                }
+                needle_score += weight * (1.0 + (positions.len() as f64).ln());
+                if !matched_fields.contains(name) {
+                    matched_fields.push(*name);
+                }
+                if *name == "text" {
+                    let offset = field_words[positions[0]].0;
+                    first_text_match =
+                        Some(first_text_match.map_or(offset, |first| first.min(offset)));
+                }
+            }
+            if needle_score == 0.0 {
+                return None;
+            }
+            score += needle_score * boost;
+        }
+
+        if !any_needle {
+            // Filters only: every remaining item is equally relevant
+            return Some(SearchMatch {
+                score: 1.0,
+                snippet: snippet(&item.text, 0),
+                matched_fields,
+            });
+        }
+
+        Some(SearchMatch {
+            score,
+            snippet: first_text_match.and_then(|offset| snippet(&item.text, offset)),
+            matched_fields,
+        })
+    }
+}
+
+struct Token {
+    text: String,
+    quoted_from_start: bool,
+}
+
+/// Split a query at whitespace outside quotes, dropping the quotes
+fn tokenize(input: &str) -> Result<Vec<Token>, SearchError> {
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    let mut in_quotes = false;
+    let mut quoted_from_start = false;
+    let mut started = false;
+
+    for c in input.chars() {
+        if c == '"' {
+            if !started {
+                quoted_from_start = true;
+            }
+            started = true;
+            in_quotes = !in_quotes;
+        } else if c.is_whitespace() && !in_quotes {
+            if started {
+                tokens.push(Token {
+                    text: std::mem::take(&mut current),
+                    quoted_from_start,
+                });
+            }
+            started = false;
+            quoted_from_start = false;
+        } else {
+            started = true;
+            current.push(c);
+        }
+    }
+
+    if in_quotes {
+        return Err(SearchError::UnterminatedPhrase);
+    }
+    if started {
+        tokens.push(Token {
+            text: current,
+            quoted_from_start,
+        });
+    }
+    Ok(tokens)
+}
+
+/// Names of the filters a query understands
+const FILTER_NAMES: [&str; 5] = ["type", "ext", "tag", "modified", "derivative"];
+
+/// Split `name:value` when the name is one of the filters
+fn filter_name(token: &str) -> Option<(String, &str)> {
+    let (name, value) = token.split_once(':')?;
+    let name = name.to_lowercase();
+    FILTER_NAMES
+        .contains(&name.as_str())
+        .then_some((name, value))
+}
+
+/// Lower-case words of a text with their byte offsets
+fn words(text: &str) -> Vec<(usize, String)> {
+    let mut found = Vec::new();
+    let mut start: Option<usize> = None;
+
+    for (index, c) in text.char_indices() {
+        match (c.is_alphanumeric(), start) {
+            (true, None) => start = Some(index),
+            (false, Some(begin)) => {
+                found.push((begin, text[begin..index].to_lowercase()));
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    if let Some(begin) = start {
+        found.push((begin, text[begin..].to_lowercase()));
+    }
+    found
+}
+
+/// Positions where a needle occurs; single terms match word prefixes,
+/// phrases match whole words in sequence
+fn occurrences(field: &[(usize, String)], needle: &[String]) -> Vec<usize> {
+    if needle.is_empty() || field.len() < needle.len() {
+        return Vec::new();
+    }
+    if let [term] = needle {
+        return field
+            .iter()
+            .enumerate()
+            .filter(|(_, (_, word))| word.starts_with(term.as_str()))
+            .map(|(index, _)| index)
+            .collect();
+    }
+    field
+        .windows(needle.len())
+        .enumerate()
+        .filter(|(_, window)| {
+            window
+                .iter()
+                .zip(needle)
+                .all(|((_, word), expected)| word == expected)
+        })
+        .map(|(index, _)| index)
+        .collect()
+}
+
+/// A passage of the text around a byte offset, on one line
+fn snippet(text: &str, offset: usize) -> Option<String> {
+    if text.trim().is_empty() {
+        return None;
+    }
+
+    let before: Vec<(usize, char)> = text[..offset].char_indices().collect();
+    let mut start = before
+        .len()
+        .checked_sub(SNIPPET_BEFORE)
+        .map_or(0, |index| before[index].0);
+    // Start at a word boundary
+    if start > 0 {
+        start = text[start..offset]
+            .find(char::is_whitespace)
+            .map_or(start, |space| start + space + 1);
+    }
+    let end = text[offset..]
+        .char_indices()
+        .nth(SNIPPET_AFTER)
+        .map_or(text.len(), |(index, _)| offset + index);
+
+    let passage = text[start..end]
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+    let mut snippet = String::new();
+    if start > 0 {
+        snippet.push('…');
+    }
+    snippet.push_str(&passage);
+    if end < text.len() {
+        snippet.push('…');
+    }
+    Some(snippet)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn item(kind: SourceKind, title: &str, text: &str) -> SearchItem {
+        SearchItem {
+            text: text.to_string(),
+            extension: Some("pdf".to_string()),
+            category: Some(FileCategory::Documents),
+            modified_at: Utc.with_ymd_and_hms(2024, 3, 5, 12, 0, 0).single(),
+            ..SearchItem::new(kind, title, format!("/corpus/{}", title))
+        }
+    }
+
+    #[test]
+    fn test_parse_terms_phrases_and_filters() {
+        let query = SearchQuery::parse(
+            r#"Budget "board  minutes" e-mail type:report,audio ext:.PDF tag:"Cost centre" modified:>=2024-01-01 derivative:summary.det"#,
+        )
+        .unwrap();
+
+        assert_eq!(query.terms, ["budget"]);
+        assert_eq!(query.phrases, [vec!["board", "minutes"], vec!["e", "mail"]]);
+        assert_eq!(
+            query.types,
+            [
+                TypeFilter::Source(SourceKind::Report),
+                TypeFilter::Category(FileCategory::Audio)
+            ]
+        );
+        assert_eq!(query.extensions, ["pdf"]);
+        assert_eq!(query.tags, ["cost centre"]);
+        assert_eq!(
+            query.modified,
+            [DateBound::OnOrAfter(
+                NaiveDate::from_ymd_opt(2024, 1, 1).unwrap()
+            )]
+        );
+        assert_eq!(query.derivatives, ["summary"]);
+
+        assert_eq!(SearchQuery::parse("  "), Err(SearchError::EmptyQuery));
+        assert_eq!(
+            SearchQuery::parse("\"open"),
+            Err(SearchError::UnterminatedPhrase)
+        );
+        assert_eq!(
+            SearchQuery::parse("type:"),
+            Err(SearchError::invalid_value(
+                "type",
+                "",
+                "the value is missing"
+            ))
+        );
+        assert!(SearchQuery::parse("modified:>yesterday").is_err());
+        assert!(SearchQuery::parse("type:spreadsheet").is_err());
+        assert_eq!(SearchQuery::parse("EXT:pdf").unwrap().extensions, ["pdf"]);
+    }
+
+    #[test]
+    fn test_colons_outside_filters_are_text() {
+        let query = SearchQuery::parse("Re: settlement").unwrap();
+        assert_eq!(query.terms, ["re", "settlement"]);
+        assert!(query.phrases.is_empty());
+
+        let query = SearchQuery::parse("Note: privileged ext:pdf").unwrap();
+        assert_eq!(query.terms, ["note", "privileged"]);
+        assert_eq!(query.extensions, ["pdf"]);
+
+        let query = SearchQuery::parse("http://example.com").unwrap();
+        assert_eq!(query.phrases, [vec!["http", "example", "com"]]);
+
+        let query = SearchQuery::parse("author:smith").unwrap();
+        assert_eq!(query.phrases, [vec!["author", "smith"]]);
+    }
+
+    #[test]
+    fn test_evaluate_ranks_title_above_text_and_quotes_context() {
+        let query = SearchQuery::parse("settlement").unwrap();
+        let in_title = item(SourceKind::Original, "settlement.pdf", "");
+        let in_text = item(
+            SourceKind::Derivative,
+            "extracted.det",
+            "The parties reached a settlement on the disputed invoices.",
+        );
+
+        let title_match = query.evaluate(&in_title).unwrap();
+        let text_match = query.evaluate(&in_text).unwrap();
+        assert!(title_match.score > text_match.score);
+        assert_eq!(title_match.matched_fields, ["title", "path"]);
+        assert_eq!(
+            text_match.snippet.as_deref(),
+            Some("The parties reached a settlement on the disputed invoices.")
+        );
+
+        let phrase = SearchQuery::parse("\"disputed invoices\" part").unwrap();
+        assert!(phrase.evaluate(&in_text).is_some());
+        assert!(SearchQuery::parse("\"invoices disputed\"")
+            .unwrap()
+            .evaluate(&in_text)
+            .is_none());
+    }
+
+    #[test]
+    fn test_filters() {
+        let mut summary = item(SourceKind::Derivative, "summary.det", "Budget overview");
+        summary.derivative = Some("summary".to_string());
+        summary.tags = vec!["Finance".to_string()];
+
+        let matches = |input: &str| {
+            SearchQuery::parse(input)
+                .unwrap()
+                .evaluate(&summary)
+                .is_some()
+        };
+        assert!(matches("budget derivative:summary"));
+        assert!(!matches("budget derivative:extracted"));
+        assert!(matches("type:derivative ext:docx,pdf tag:finance"));
+        assert!(!matches("type:original"));
+        assert!(matches("modified:>2024-03-04 modified:<2024-03-06"));
+        assert!(matches("modified:2024-03-05"));
+        assert!(!matches("modified:>2024-03-05"));
+
+        let query = SearchQuery::parse("derivative:summary").unwrap();
+        assert!(query.admits(SourceKind::Derivative));
+        assert!(!query.admits(SourceKind::Report));
+        assert!(!SearchQuery::parse("ext:pdf")
+            .unwrap()
+            .admits(SourceKind::Report));
+    }
+
+    #[test]
+    fn test_may_match_defers_needles_found_in_text() {
+        let query = SearchQuery::parse(r#"settle "board minutes" ext:pdf"#).unwrap();
+        let needles: Vec<Vec<String>> = query.needles().iter().map(|n| n.to_vec()).collect();
+        assert_eq!(
+            needles,
+            [
+                vec!["settle".to_string()],
+                vec!["board".to_string(), "minutes".to_string()]
+            ]
+        );
+
+        let report = item(SourceKind::Derivative, "settlement.det", "");
+        assert!(!query.may_match(&report, |_| false));
+        assert!(query.may_match(&report, |index| index == 1));
+
+        let mut docx = report.clone();
+        docx.extension = Some("docx".to_string());
+        assert!(!query.may_match(&docx, |_| true));
+    }
+
+    #[test]
+    fn test_snippet_is_cut_at_word_boundaries() {
+        let text = format!("{} needle {}", "word ".repeat(40), "tail ".repeat(60));
+        let offset = text.find("needle").unwrap();
+        let snippet = snippet(&text, offset).unwrap();
+
+        assert!(snippet.starts_with("…word"));
+        assert!(snippet.contains("needle"));
+        assert!(snippet.ends_with('…'));
+    }
+}
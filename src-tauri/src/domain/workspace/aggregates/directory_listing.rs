@@ -1,6 +1,6 @@
 use crate::domain::workspace::entities::{FileEntry, FileEntryType};
 use crate::domain::workspace::errors::WorkspaceError;
-use crate::domain::workspace::value_objects::WorkspaceContext;
+use crate::domain::workspace::value_objects::{ArchiveFormat, WorkspaceContext};
 use serde::{Deserialize, Serialize};
 use std::path::{Path, PathBuf};
 
@@ -105,6 +105,8 @@ impl DirectoryListing {
 
     /// Navigate to a folder within this listing
     ///
+    /// Archive files are folders too: navigating to one descends into it.
+    ///
     /// # Arguments
     /// * `folder_name` - Name of the folder to navigate to
     ///
@@ -115,7 +117,7 @@ impl DirectoryListing {
     /// Returns `WorkspaceError` if:
     /// - Folder not found
     /// - Folder name is invalid
-    /// - Target is not a directory
+    /// - Target is neither a directory nor an archive
     pub fn navigate_to_folder(
         &self,
         folder_name: &str,
@@ -132,6 +134,10 @@ impl DirectoryListing {
                 )
             })?;
 
+        if folder_entry.is_file() && ArchiveFormat::is_archive_name(folder_name) {
+            return self.workspace_context.navigate_into_archive(folder_name);
+        }
+
         // Verify it's a directory
         if !folder_entry.is_directory() {
             return Err(WorkspaceError::invalid_path(
@@ -338,6 +344,7 @@ impl DirectoryListing {
 mod tests {
     use super::*;
     use crate::domain::project::value_objects::ProjectId;
+    use crate::domain::workspace::value_objects::ArchiveMember;
     use std::time::SystemTime;
     use tempfile::TempDir;
 
@@ -578,4 +585,40 @@ mod tests {
         assert_eq!(filtered_listing.entry_count(), 1);
         assert_eq!(filtered_listing.entries()[0].name(), "file2.txt");
     }
+
+    #[test]
+    fn test_archive_listing() {
+        let temp_dir = TempDir::new().unwrap();
+        let archive = temp_dir.path().join("mail.zip");
+        std::fs::write(&archive, "PK").unwrap();
+        let context = create_test_workspace_context(&temp_dir);
+        let listing = DirectoryListing::new(
+            context,
+            vec![FileEntry::file("mail.zip", &archive, Some(2), SystemTime::now()).unwrap()],
+        )
+        .unwrap();
+
+        let inside = listing.navigate_to_folder("mail.zip").unwrap();
+        let root = inside.archive_path().unwrap();
+        let entries = vec![
+            FileEntry::archive_member(
+                &root.join("2019"),
+                &ArchiveMember::new("2019", true, 0, None).unwrap(),
+            )
+            .unwrap(),
+            FileEntry::archive_member(
+                &root.join("offer.pdf"),
+                &ArchiveMember::new("offer.pdf", false, 120, None).unwrap(),
+            )
+            .unwrap(),
+        ];
+
+        let archive_listing = DirectoryListing::new(inside, entries).unwrap();
+        archive_listing.validate().unwrap();
+        assert!(archive_listing.can_navigate_up());
+        assert_eq!(archive_listing.parent_path(), Some(temp_dir.path()));
+        assert_eq!(archive_listing.breadcrumb_trail(), ["mail.zip!"]);
+        assert!(archive_listing.entries().iter().all(FileEntry::is_virtual));
+        assert!(archive_listing.navigate_to_folder("2019").is_ok());
+    }
 }
@@ -1,4 +1,6 @@
-use crate::domain::workspace::value_objects::{DocumentCaddyId, FilePath};
+use crate::domain::workspace::value_objects::{
+    file_category, DocumentCaddyId, FileCategory, FilePath,
+};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -104,18 +106,21 @@ impl DocumentCaddy {
             .map(|s| s.to_lowercase())
     }
 
+    /// Content category by extension alone; the file is not read
+    pub fn category(&self) -> FileCategory {
+        self.get_file_extension()
+            .and_then(|ext| FileCategory::from_extension(&ext))
+            .unwrap_or(FileCategory::Other)
+    }
+
     pub fn is_text_file(&self) -> bool {
-        matches!(
-            self.get_file_extension().as_deref(),
-            Some("txt") | Some("md") | Some("json") | Some("xml") | Some("csv") | Some("log")
-        )
+        self.get_file_extension()
+            .is_some_and(|ext| file_category::is_text_extension(&ext))
     }
 
+    /// Rich (non plain-text) documents such as PDF or Office files
     pub fn is_document_file(&self) -> bool {
-        matches!(
-            self.get_file_extension().as_deref(),
-            Some("pdf") | Some("doc") | Some("docx") | Some("ppt") | Some("pptx")
-        )
+        self.category() == FileCategory::Documents && !self.is_text_file()
     }
 
     fn extract_title_from_path(file_path: &FilePath) -> Result<String, String> {
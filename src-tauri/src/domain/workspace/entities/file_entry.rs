@@ -1,7 +1,8 @@
 use crate::domain::workspace::errors::WorkspaceError;
+use crate::domain::workspace::value_objects::{ArchiveMember, ArchivePath};
 use serde::{Deserialize, Serialize};
 use std::path::{Path, PathBuf};
-use std::time::SystemTime;
+use std::time::{SystemTime, UNIX_EPOCH};
 
 /// FileEntry represents a file or folder within the workspace with metadata
 ///
@@ -19,6 +20,9 @@ pub struct FileEntry {
     size: Option<u64>,
     /// The last modification time
     modified: SystemTime,
+    /// Whether the entry is a read-only member of an archive file
+    #[serde(default)]
+    is_virtual: bool,
 }
 
 /// Enum representing the type of file system entry
@@ -86,9 +90,38 @@ impl FileEntry {
             entry_type,
             size,
             modified,
+            is_virtual: false,
         })
     }
 
+    /// Create a read-only entry for a member of an archive
+    ///
+    /// # Arguments
+    /// * `location` - The member's path, see `ArchivePath`
+    /// * `member` - The member as listed by the archive
+    pub fn archive_member(
+        location: &ArchivePath,
+        member: &ArchiveMember,
+    ) -> Result<Self, WorkspaceError> {
+        let entry_type = if member.is_dir {
+            FileEntryType::Directory
+        } else {
+            FileEntryType::File
+        };
+        let size = (!member.is_dir).then_some(member.size);
+        let modified = member.modified.map_or(UNIX_EPOCH, SystemTime::from);
+
+        let mut entry = Self::new(
+            member.name(),
+            location.to_path_buf(),
+            entry_type,
+            size,
+            modified,
+        )?;
+        entry.is_virtual = true;
+        Ok(entry)
+    }
+
     /// Create a file entry
     pub fn file(
         name: impl Into<String>,
@@ -133,6 +166,11 @@ impl FileEntry {
         self.modified
     }
 
+    /// Check if this is a read-only member of an archive
+    pub fn is_virtual(&self) -> bool {
+        self.is_virtual
+    }
+
     /// Check if this is a file
     pub fn is_file(&self) -> bool {
         matches!(self.entry_type, FileEntryType::File)
@@ -158,8 +196,14 @@ impl FileEntry {
     }
 
     /// Check if the entry is within the given workspace boundary
+    ///
+    /// Archive members are within the workspace when their archive file is.
     pub fn is_within_workspace(&self, workspace_root: &Path) -> Result<bool, WorkspaceError> {
-        let canonical_path = self.path.canonicalize().map_err(|e| {
+        let archive = ArchivePath::parse(&self.path).filter(|_| self.is_virtual);
+        let path = archive
+            .as_ref()
+            .map_or(self.path.as_path(), |a| a.archive());
+        let canonical_path = path.canonicalize().map_err(|e| {
             WorkspaceError::metadata_retrieval_failed(
                 self.path.display().to_string(),
                 format!("Failed to canonicalize path: {}", e),
@@ -469,4 +513,32 @@ mod tests {
         assert!(inside_entry.is_within_workspace(workspace.path()).unwrap());
         assert!(!outside_entry.is_within_workspace(workspace.path()).unwrap());
     }
+
+    #[test]
+    fn test_archive_member_entry() {
+        let workspace = TempDir::new().unwrap();
+        let archive = workspace.path().join("mail.zip");
+        std::fs::write(&archive, "PK").unwrap();
+
+        let location = ArchivePath::root(&archive).join("2019/offer.pdf");
+        let member = ArchiveMember::new("2019/offer.pdf", false, 120, None).unwrap();
+        let entry = FileEntry::archive_member(&location, &member).unwrap();
+
+        assert!(entry.is_virtual());
+        assert!(entry.is_file());
+        assert_eq!(entry.name(), "offer.pdf");
+        assert_eq!(entry.size(), Some(120));
+        assert_eq!(entry.modified(), UNIX_EPOCH);
+        assert_eq!(
+            entry.parent(),
+            Some(PathBuf::from(format!("{}!/2019", archive.display())))
+        );
+        assert!(entry.is_within_workspace(workspace.path()).unwrap());
+
+        let folder = ArchiveMember::new("2019", true, 0, None).unwrap();
+        let folder =
+            FileEntry::archive_member(&ArchivePath::root(&archive).join("2019"), &folder).unwrap();
+        assert!(folder.is_directory());
+        assert_eq!(folder.size(), None);
+    }
 }
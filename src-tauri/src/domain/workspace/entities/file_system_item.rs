@@ -1,4 +1,4 @@
-use crate::domain::workspace::value_objects::FilePath;
+use crate::domain::workspace::value_objects::{FileCategory, FilePath};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::path::{Path, PathBuf};
@@ -100,6 +100,19 @@ impl FileSystemItem {
         path_str.starts_with(source_path) || path_str.starts_with(reports_path)
     }
 
+    /// Content category of the item by extension alone, `None` for directories
+    pub fn category(&self) -> Option<FileCategory> {
+        if self.is_file() {
+            Some(
+                self.get_extension()
+                    .and_then(|ext| FileCategory::from_extension(&ext))
+                    .unwrap_or(FileCategory::Other),
+            )
+        } else {
+            None
+        }
+    }
+
     pub fn can_be_opened(&self) -> bool {
         self.is_accessible && self.is_file()
     }
@@ -243,5 +256,19 @@ mod tests {
 
         assert!(accessible_file.can_be_opened());
         assert!(!inaccessible_file.can_be_opened());
+
+        let unknown_path =
+            FilePath::new("/Users/test/Documents/Source/data.unknownext".to_string()).unwrap();
+        let unknown_file = FileSystemItem::new(
+            unknown_path,
+            FileSystemItemType::File,
+            Utc::now(),
+            Some(1024),
+            true,
+        )
+        .unwrap();
+
+        assert_eq!(unknown_file.category(), Some(FileCategory::Other));
+        assert!(unknown_file.can_be_opened());
     }
 }
@@ -0,0 +1,450 @@
+use crate::domain::project;
+use crate::domain::workspace::entities::file_entry::FileEntry;
+use crate::domain::workspace::errors::WorkspaceError;
+use crate::domain::workspace::value_objects::{ContentHash, HashAlgorithm, SnapshotId};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+use std::time::UNIX_EPOCH;
+
+/// One file recorded in a manifest snapshot
+///
+/// Paths are stored relative to the project's source folder with `/`
+/// separators, so snapshots stay comparable if the folder itself moves.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ManifestEntry {
+    pub path: String,
+    pub size: u64,
+    pub modified_ns: i64,
+    pub hash: ContentHash,
+}
+
+impl ManifestEntry {
+    /// Record a file entry found below `root`
+    ///
+    /// # Errors
+    /// Returns `WorkspaceError` if the entry is a directory, has no known
+    /// size, or lies outside `root`.
+    pub fn from_file_entry(
+        entry: &FileEntry,
+        root: &Path,
+        hash: ContentHash,
+    ) -> Result<Self, WorkspaceError> {
+        if !entry.is_file() {
+            return Err(WorkspaceError::invalid_workspace_context(
+                "Only files can be recorded in a manifest",
+            ));
+        }
+
+        let size = entry.size().ok_or_else(|| {
+            WorkspaceError::metadata_retrieval_failed(
+                entry.path().display().to_string(),
+                "File size is unknown",
+            )
+        })?;
+
+        let relative = entry.path().strip_prefix(root).map_err(|_| {
+            WorkspaceError::navigation_boundary_violation(
+                entry.path().display().to_string(),
+                root.display().to_string(),
+            )
+        })?;
+
+        let modified_ns = entry
+            .modified()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_nanos() as i64)
+            .unwrap_or(0);
+
+        Ok(ManifestEntry {
+            path: relative
+                .components()
+                .map(|c| c.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/"),
+            size,
+            modified_ns,
+            hash,
+        })
+    }
+
+    /// File name component of the relative path
+    pub fn file_name(&self) -> &str {
+        self.path.rsplit('/').next().unwrap_or(&self.path)
+    }
+}
+
+/// Manifest of a project's source folder at a point in time
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ManifestSnapshot {
+    id: SnapshotId,
+    project_id: project::ProjectId,
+    label: Option<String>,
+    algorithm: HashAlgorithm,
+    entries: Vec<ManifestEntry>,
+    created_at: DateTime<Utc>,
+}
+
+impl ManifestSnapshot {
+    /// Create a new snapshot from the recorded entries
+    pub fn new(
+        project_id: project::ProjectId,
+        label: Option<String>,
+        algorithm: HashAlgorithm,
+        entries: Vec<ManifestEntry>,
+    ) -> Self {
+        Self::from_data(
+            SnapshotId::new(),
+            project_id,
+            label,
+            algorithm,
+            entries,
+            Utc::now(),
+        )
+    }
+
+    /// Create a snapshot from existing data (for repository reconstruction)
+    pub fn from_data(
+        id: SnapshotId,
+        project_id: project::ProjectId,
+        label: Option<String>,
+        algorithm: HashAlgorithm,
+        mut entries: Vec<ManifestEntry>,
+        created_at: DateTime<Utc>,
+    ) -> Self {
+        entries.sort_by(|a, b| a.path.cmp(&b.path));
+        let label = label
+            .map(|l| l.trim().to_string())
+            .filter(|l| !l.is_empty());
+
+        ManifestSnapshot {
+            id,
+            project_id,
+            label,
+            algorithm,
+            entries,
+            created_at,
+        }
+    }
+
+    pub fn id(&self) -> &SnapshotId {
+        &self.id
+    }
+
+    pub fn project_id(&self) -> &project::ProjectId {
+        &self.project_id
+    }
+
+    pub fn label(&self) -> Option<&str> {
+        self.label.as_deref()
+    }
+
+    pub fn algorithm(&self) -> HashAlgorithm {
+        self.algorithm
+    }
+
+    /// Recorded files, sorted by path
+    pub fn entries(&self) -> &[ManifestEntry] {
+        &self.entries
+    }
+
+    pub fn created_at(&self) -> DateTime<Utc> {
+        self.created_at
+    }
+
+    pub fn file_count(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn total_size(&self) -> u64 {
+        self.entries.iter().map(|e| e.size).sum()
+    }
+
+    /// Summary of this snapshot without its entries
+    pub fn summary(&self) -> ManifestSnapshotSummary {
+        ManifestSnapshotSummary {
+            id: self.id.clone(),
+            project_id: self.project_id.clone(),
+            label: self.label.clone(),
+            algorithm: self.algorithm,
+            file_count: self.file_count(),
+            total_size: self.total_size(),
+            created_at: self.created_at,
+        }
+    }
+
+    /// Changes needed to get from this snapshot to `target`
+    ///
+    /// # Errors
+    /// Returns `WorkspaceError` if the snapshots were hashed with different
+    /// algorithms, since their digests cannot be compared.
+    pub fn diff(&self, target: &ManifestSnapshot) -> Result<ManifestDiff, WorkspaceError> {
+        if self.algorithm != target.algorithm {
+            return Err(WorkspaceError::invalid_workspace_context(format!(
+                "Cannot compare a {} snapshot with a {} snapshot",
+                self.algorithm, target.algorithm
+            )));
+        }
+
+        Ok(ManifestDiff::between(&self.entries, &target.entries))
+    }
+}
+
+/// Snapshot metadata used for listings
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ManifestSnapshotSummary {
+    pub id: SnapshotId,
+    pub project_id: project::ProjectId,
+    pub label: Option<String>,
+    pub algorithm: HashAlgorithm,
+    pub file_count: usize,
+    pub total_size: u64,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Kind of change between two manifests
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ManifestChangeKind {
+    Added,
+    Removed,
+    Modified,
+    Renamed,
+}
+
+impl ManifestChangeKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ManifestChangeKind::Added => "added",
+            ManifestChangeKind::Removed => "removed",
+            ManifestChangeKind::Modified => "modified",
+            ManifestChangeKind::Renamed => "renamed",
+        }
+    }
+}
+
+/// A single file-level change between two manifests
+///
+/// `previous` describes the file in the base manifest and `current` in the
+/// target; added files have no `previous` and removed files no `current`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ManifestChange {
+    pub kind: ManifestChangeKind,
+    pub previous: Option<ManifestEntry>,
+    pub current: Option<ManifestEntry>,
+}
+
+impl ManifestChange {
+    /// Path the change is reported under (the new path where there is one)
+    pub fn path(&self) -> &str {
+        self.current
+            .as_ref()
+            .or(self.previous.as_ref())
+            .map(|e| e.path.as_str())
+            .unwrap_or_default()
+    }
+}
+
+/// Differences between two manifests
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ManifestDiff {
+    pub changes: Vec<ManifestChange>,
+    pub unchanged: usize,
+}
+
+impl ManifestDiff {
+    /// Compare two sets of manifest entries
+    ///
+    /// Files at the same path are modified if their hash differs. A removed
+    /// file and an added file with the same hash are reported as a rename,
+    /// preferring pairs that keep the file name (moves) when several
+    /// identical files changed location.
+    pub fn between(base: &[ManifestEntry], target: &[ManifestEntry]) -> Self {
+        let target_by_path: HashMap<&str, &ManifestEntry> =
+            target.iter().map(|e| (e.path.as_str(), e)).collect();
+        let base_paths: HashMap<&str, &ManifestEntry> =
+            base.iter().map(|e| (e.path.as_str(), e)).collect();
+
+        let mut diff = ManifestDiff::default();
+        let mut removed: HashMap<&ContentHash, Vec<&ManifestEntry>> = HashMap::new();
+
+        for entry in base {
+            match target_by_path.get(entry.path.as_str()) {
+                Some(current) if current.hash == entry.hash => diff.unchanged += 1,
+                Some(current) => diff.changes.push(ManifestChange {
+                    kind: ManifestChangeKind::Modified,
+                    previous: Some(entry.clone()),
+                    current: Some((*current).clone()),
+                }),
+                None => removed.entry(&entry.hash).or_default().push(entry),
+            }
+        }
+
+        for entry in target {
+            if base_paths.contains_key(entry.path.as_str()) {
+                continue;
+            }
+
+            let previous = removed.get_mut(&entry.hash).and_then(|candidates| {
+                if candidates.is_empty() {
+                    return None;
+                }
+                let index = candidates
+                    .iter()
+                    .position(|c| c.file_name() == entry.file_name())
+                    .unwrap_or(0);
+                Some(candidates.remove(index))
+            });
+
+            diff.changes.push(match previous {
+                Some(previous) => ManifestChange {
+                    kind: ManifestChangeKind::Renamed,
+                    previous: Some(previous.clone()),
+                    current: Some(entry.clone()),
+                },
+                None => ManifestChange {
+                    kind: ManifestChangeKind::Added,
+                    previous: None,
+                    current: Some(entry.clone()),
+                },
+            });
+        }
+
+        for entry in removed.into_values().flatten() {
+            diff.changes.push(ManifestChange {
+                kind: ManifestChangeKind::Removed,
+                previous: Some(entry.clone()),
+                current: None,
+            });
+        }
+
+        diff.changes.sort_by(|a, b| {
+            a.path()
+                .cmp(b.path())
+                .then(a.kind.as_str().cmp(b.kind.as_str()))
+        });
+        diff
+    }
+
+    /// Number of changes of the given kind
+    pub fn count(&self, kind: ManifestChangeKind) -> usize {
+        self.changes.iter().filter(|c| c.kind == kind).count()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::SystemTime;
+
+    fn entry(path: &str, contents: &str) -> ManifestEntry {
+        ManifestEntry {
+            path: path.to_string(),
+            size: contents.len() as u64,
+            modified_ns: 0,
+            hash: ContentHash::of_bytes(HashAlgorithm::Blake3, contents.as_bytes()),
+        }
+    }
+
+    #[test]
+    fn test_from_file_entry_uses_relative_path() {
+        let file = FileEntry::file(
+            "offer.pdf",
+            "/corpus/production-1/offer.pdf",
+            Some(12),
+            SystemTime::now(),
+        )
+        .unwrap();
+        let hash = ContentHash::of_bytes(HashAlgorithm::Blake3, b"offer");
+
+        let manifest_entry =
+            ManifestEntry::from_file_entry(&file, Path::new("/corpus"), hash).unwrap();
+        assert_eq!(manifest_entry.path, "production-1/offer.pdf");
+        assert_eq!(manifest_entry.file_name(), "offer.pdf");
+        assert_eq!(manifest_entry.size, 12);
+
+        let outside = ManifestEntry::from_file_entry(
+            &file,
+            Path::new("/elsewhere"),
+            ContentHash::of_bytes(HashAlgorithm::Blake3, b"offer"),
+        );
+        assert!(outside.is_err());
+    }
+
+    #[test]
+    fn test_diff_classifies_changes() {
+        let base = vec![
+            entry("a.pdf", "unchanged"),
+            entry("b.pdf", "original"),
+            entry("c.pdf", "deleted"),
+            entry("old/d.pdf", "moved"),
+        ];
+        let target = vec![
+            entry("a.pdf", "unchanged"),
+            entry("b.pdf", "revised"),
+            entry("new/d.pdf", "moved"),
+            entry("e.pdf", "new"),
+        ];
+
+        let diff = ManifestDiff::between(&base, &target);
+        assert_eq!(diff.unchanged, 1);
+        assert_eq!(diff.count(ManifestChangeKind::Modified), 1);
+        assert_eq!(diff.count(ManifestChangeKind::Removed), 1);
+        assert_eq!(diff.count(ManifestChangeKind::Added), 1);
+        assert_eq!(diff.count(ManifestChangeKind::Renamed), 1);
+
+        let renamed = diff
+            .changes
+            .iter()
+            .find(|c| c.kind == ManifestChangeKind::Renamed)
+            .unwrap();
+        assert_eq!(renamed.previous.as_ref().unwrap().path, "old/d.pdf");
+        assert_eq!(renamed.path(), "new/d.pdf");
+    }
+
+    #[test]
+    fn test_diff_prefers_moves_among_identical_files() {
+        let base = vec![entry("x/copy.pdf", "same"), entry("x/scan.pdf", "same")];
+        let target = vec![entry("y/scan.pdf", "same"), entry("y/renamed.pdf", "same")];
+
+        let diff = ManifestDiff::between(&base, &target);
+        assert_eq!(diff.count(ManifestChangeKind::Renamed), 2);
+
+        let moved = diff
+            .changes
+            .iter()
+            .find(|c| c.path() == "y/scan.pdf")
+            .unwrap();
+        assert_eq!(moved.previous.as_ref().unwrap().path, "x/scan.pdf");
+    }
+
+    #[test]
+    fn test_snapshot_diff_requires_same_algorithm() {
+        let project_id = project::ProjectId::new();
+        let blake3 = ManifestSnapshot::new(project_id.clone(), None, HashAlgorithm::Blake3, vec![]);
+        let sha256 = ManifestSnapshot::new(project_id, None, HashAlgorithm::Sha256, vec![]);
+
+        assert!(blake3.diff(&blake3).unwrap().is_empty());
+        assert!(blake3.diff(&sha256).is_err());
+    }
+
+    #[test]
+    fn test_snapshot_summary() {
+        let snapshot = ManifestSnapshot::new(
+            project::ProjectId::new(),
+            Some("  Production 2  ".to_string()),
+            HashAlgorithm::Blake3,
+            vec![entry("b.pdf", "bb"), entry("a.pdf", "a")],
+        );
+
+        assert_eq!(snapshot.label(), Some("Production 2"));
+        assert_eq!(snapshot.entries()[0].path, "a.pdf");
+        assert_eq!(snapshot.summary().file_count, 2);
+        assert_eq!(snapshot.summary().total_size, 3);
+    }
+}
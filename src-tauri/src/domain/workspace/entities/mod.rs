@@ -1,9 +1,11 @@
 pub mod document_caddy;
 pub mod file_entry;
 pub mod file_system_item;
+pub mod manifest_snapshot;
 pub mod workspace_layout;
 
 pub use document_caddy::*;
 pub use file_entry::*;
 pub use file_system_item::*;
+pub use manifest_snapshot::*;
 pub use workspace_layout::*;
@@ -0,0 +1,46 @@
+use thiserror::Error;
+
+/// Errors raised while browsing or reading an archive file
+#[derive(Debug, Error)]
+pub enum ArchiveError {
+    #[error("'{name}' is not a supported archive")]
+    UnsupportedFormat { name: String },
+
+    #[error("The {format} archive {path} is damaged or cannot be read: {reason}")]
+    Unreadable {
+        format: String,
+        path: String,
+        reason: String,
+    },
+
+    #[error("'{path}' was not found in the archive")]
+    MemberNotFound { path: String },
+
+    #[error("'{path}' is too large to open inside an archive ({size} bytes, limit {limit})")]
+    TooLarge { path: String, size: u64, limit: u64 },
+}
+
+impl ArchiveError {
+    /// Create an UnsupportedFormat error for a file name
+    pub fn unsupported(name: impl Into<String>) -> Self {
+        ArchiveError::UnsupportedFormat { name: name.into() }
+    }
+
+    /// Create an Unreadable error for an archive
+    pub fn unreadable(
+        format: impl Into<String>,
+        path: impl Into<String>,
+        reason: impl ToString,
+    ) -> Self {
+        ArchiveError::Unreadable {
+            format: format.into(),
+            path: path.into(),
+            reason: reason.to_string(),
+        }
+    }
+
+    /// Create a MemberNotFound error for a member path
+    pub fn member_not_found(path: impl Into<String>) -> Self {
+        ArchiveError::MemberNotFound { path: path.into() }
+    }
+}
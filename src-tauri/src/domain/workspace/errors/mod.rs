@@ -1,3 +1,5 @@
+pub mod archive_error;
 pub mod workspace_error;
 
+pub use archive_error::ArchiveError;
 pub use workspace_error::WorkspaceError;
@@ -3,11 +3,14 @@ use async_trait::async_trait;
 
 pub mod workspace_repository;
 use crate::domain::workspace::entities::{
-    document_caddy::DocumentCaddy, file_system_item::FileSystemItem,
+    document_caddy::DocumentCaddy,
+    file_system_item::FileSystemItem,
+    manifest_snapshot::{ManifestSnapshot, ManifestSnapshotSummary},
     workspace_layout::WorkspaceLayout,
 };
 use crate::domain::workspace::value_objects::{
-    DocumentCaddyId, FilePath, ProjectId, WorkspaceLayoutId,
+    DocumentCaddyId, FileCategoryConfig, FileHashRecord, FilePath, HashAlgorithm, ProjectId,
+    SnapshotId, WorkspaceLayoutId,
 };
 pub use workspace_repository::*;
 
@@ -117,6 +120,66 @@ pub trait DocumentCaddyRepository: Send + Sync {
     ) -> Result<Option<DocumentCaddy>, RepositoryError>;
 }
 
+/// Repository for per-project file classification settings
+#[async_trait]
+pub trait FileCategoryConfigRepository: Send + Sync {
+    /// Load the configuration for a project, falling back to defaults
+    async fn load(
+        &self,
+        project_id: &crate::domain::project::ProjectId,
+    ) -> Result<FileCategoryConfig, RepositoryError>;
+
+    /// Persist the configuration for a project
+    async fn save(
+        &self,
+        project_id: &crate::domain::project::ProjectId,
+        config: &FileCategoryConfig,
+    ) -> Result<(), RepositoryError>;
+}
+
+/// Repository for cached file content hashes
+#[async_trait]
+pub trait FileHashRepository: Send + Sync {
+    /// Find the cached hash for a path, whether or not it is still current
+    async fn find(
+        &self,
+        path: &str,
+        algorithm: HashAlgorithm,
+    ) -> Result<Option<FileHashRecord>, RepositoryError>;
+
+    /// Find cached hashes for several paths at once
+    async fn find_many(
+        &self,
+        paths: &[String],
+        algorithm: HashAlgorithm,
+    ) -> Result<Vec<FileHashRecord>, RepositoryError>;
+
+    /// Insert or replace the cached hash for a path
+    async fn save(&self, record: &FileHashRecord) -> Result<(), RepositoryError>;
+}
+
+/// Repository for source folder manifest snapshots
+#[async_trait]
+pub trait ManifestSnapshotRepository: Send + Sync {
+    /// Save a snapshot together with all its entries
+    async fn save(&self, snapshot: &ManifestSnapshot) -> Result<(), RepositoryError>;
+
+    /// Load a snapshot with its entries
+    async fn find_by_id(
+        &self,
+        id: &SnapshotId,
+    ) -> Result<Option<ManifestSnapshot>, RepositoryError>;
+
+    /// List snapshots of a project, newest first, without their entries
+    async fn list_by_project(
+        &self,
+        project_id: &crate::domain::project::ProjectId,
+    ) -> Result<Vec<ManifestSnapshotSummary>, RepositoryError>;
+
+    /// Delete a snapshot, returning whether it existed
+    async fn delete(&self, id: &SnapshotId) -> Result<bool, RepositoryError>;
+}
+
 /// Repository for managing projects
 #[async_trait]
 pub trait ProjectRepository: Send + Sync {
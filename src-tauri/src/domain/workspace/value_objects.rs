@@ -3,7 +3,13 @@ use std::fmt::{self, Display};
 use std::path::PathBuf;
 use uuid::Uuid;
 
+pub mod archive_path;
+pub mod content_hash;
+pub mod file_category;
 pub mod workspace_context;
+pub use archive_path::{ArchiveFormat, ArchiveMember, ArchivePath, ARCHIVE_SEPARATOR};
+pub use content_hash::{ContentHash, ContentHasher, FileHashRecord, HashAlgorithm};
+pub use file_category::{FileCategory, FileCategoryConfig};
 pub use workspace_context::WorkspaceContext;
 
 /// FilePath value object - represents an absolute filesystem path
@@ -190,6 +196,47 @@ impl Display for DocumentCaddyId {
     }
 }
 
+/// SnapshotId value object - prefixed UUID for source folder manifest snapshots
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct SnapshotId {
+    id: String,
+}
+
+impl SnapshotId {
+    pub fn new() -> Self {
+        let uuid = Uuid::new_v4();
+        Self {
+            id: format!("snap_{}", uuid.hyphenated()),
+        }
+    }
+
+    pub fn from_string(id: String) -> Result<Self, String> {
+        if !id.starts_with("snap_") {
+            return Err("SnapshotId must start with 'snap_'".to_string());
+        }
+
+        let uuid_part = &id[5..]; // Remove "snap_" prefix
+        if uuid_part.len() != 36 {
+            return Err("Invalid UUID format".to_string());
+        }
+
+        // Validate UUID format
+        Uuid::parse_str(uuid_part).map_err(|_| "Invalid UUID format".to_string())?;
+
+        Ok(Self { id })
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.id
+    }
+}
+
+impl Display for SnapshotId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.id)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -259,6 +306,20 @@ mod tests {
         assert!(invalid_id.is_err());
     }
 
+    #[test]
+    fn test_snapshot_id() {
+        let id = SnapshotId::new();
+        assert!(id.as_str().starts_with("snap_"));
+        assert_eq!(id.as_str().len(), 41); // "snap_" (5) + UUID (36)
+
+        // Test from_string validation
+        let valid_id = SnapshotId::from_string(id.as_str().to_string());
+        assert!(valid_id.is_ok());
+
+        let invalid_id = SnapshotId::from_string("invalid_id".to_string());
+        assert!(invalid_id.is_err());
+    }
+
     #[test]
     fn test_id_uniqueness() {
         let id1 = ProjectId::new();
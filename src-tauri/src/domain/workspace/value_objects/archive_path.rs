@@ -0,0 +1,394 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt::{self, Display};
+use std::path::{Path, PathBuf};
+
+/// Marks the boundary between an archive file and the paths inside it
+pub const ARCHIVE_SEPARATOR: char = '!';
+
+/// Archive formats that can be browsed as read-only folders
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ArchiveFormat {
+    Zip,
+    SevenZ,
+    Tar,
+    TarGz,
+}
+
+impl ArchiveFormat {
+    /// Recognise the format from a file name
+    pub fn from_name(name: &str) -> Option<Self> {
+        let name = name.to_lowercase();
+        if name.ends_with(".zip") {
+            Some(ArchiveFormat::Zip)
+        } else if name.ends_with(".7z") {
+            Some(ArchiveFormat::SevenZ)
+        } else if name.ends_with(".tar.gz") || name.ends_with(".tgz") {
+            Some(ArchiveFormat::TarGz)
+        } else if name.ends_with(".tar") {
+            Some(ArchiveFormat::Tar)
+        } else {
+            None
+        }
+    }
+
+    /// Whether a file name is that of a supported archive
+    pub fn is_archive_name(name: &str) -> bool {
+        Self::from_name(name).is_some()
+    }
+
+    /// Get string representation
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ArchiveFormat::Zip => "zip",
+            ArchiveFormat::SevenZ => "7z",
+            ArchiveFormat::Tar => "tar",
+            ArchiveFormat::TarGz => "tar.gz",
+        }
+    }
+}
+
+/// Location of an item inside an archive file, possibly inside nested archives
+///
+/// Written as `<archive file>!/<member path>`, e.g. `/case/mail.zip!/2019/offer.pdf`,
+/// with `<archive file>!` for the root of the archive. Archives stored in
+/// archives add a level of their own: `/case/mail.zip!/backup.tar!/notes.txt`.
+/// Member paths always use `/`, whatever the platform.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ArchivePath {
+    /// The archive file on disk
+    archive: PathBuf,
+    /// Member paths of the archives nested inside it, outermost first
+    nested: Vec<String>,
+    /// Path inside the innermost archive; empty for its root
+    member: String,
+}
+
+impl ArchivePath {
+    /// The root of an archive file on disk
+    pub fn root(archive: impl AsRef<Path>) -> Self {
+        ArchivePath {
+            archive: archive.as_ref().to_path_buf(),
+            nested: Vec::new(),
+            member: String::new(),
+        }
+    }
+
+    /// Parse a path that points into an archive
+    ///
+    /// Returns `None` for ordinary paths, and for member paths that try to
+    /// leave their archive with `..`.
+    pub fn parse(path: impl AsRef<Path>) -> Option<Self> {
+        let text = path.as_ref().to_string_lossy();
+
+        let mut pieces = Vec::new();
+        let mut start = 0;
+        for (index, _) in text.match_indices(ARCHIVE_SEPARATOR) {
+            let rest = &text[index + 1..];
+            let name_start = text[..index].rfind(['/', '\\']).map_or(0, |i| i + 1);
+            if index > start
+                && (rest.is_empty() || rest.starts_with('/'))
+                && ArchiveFormat::is_archive_name(&text[name_start..index])
+            {
+                pieces.push(&text[start..index]);
+                start = index + 1;
+            }
+        }
+        let (archive, nested) = pieces.split_first()?;
+
+        let nested = nested
+            .iter()
+            .map(|piece| normalize_member_path(piece))
+            .collect::<Option<Vec<_>>>()?;
+        Some(ArchivePath {
+            archive: PathBuf::from(archive),
+            nested,
+            member: normalize_member_path(&text[start..])?,
+        })
+    }
+
+    /// The archive file on disk
+    pub fn archive(&self) -> &Path {
+        &self.archive
+    }
+
+    /// Member paths of the nested archives, outermost first
+    pub fn nested(&self) -> &[String] {
+        &self.nested
+    }
+
+    /// Path inside the innermost archive; empty for its root
+    pub fn member(&self) -> &str {
+        &self.member
+    }
+
+    /// Check if this is the root of the innermost archive
+    pub fn is_root(&self) -> bool {
+        self.member.is_empty()
+    }
+
+    /// Format of the innermost archive
+    pub fn format(&self) -> Option<ArchiveFormat> {
+        match self.nested.last() {
+            Some(nested) => ArchiveFormat::from_name(nested),
+            None => ArchiveFormat::from_name(&self.archive.to_string_lossy()),
+        }
+    }
+
+    /// Name of the member, or of the archive at its root
+    pub fn name(&self) -> String {
+        match self
+            .member
+            .rsplit('/')
+            .next()
+            .filter(|name| !name.is_empty())
+        {
+            Some(name) => name.to_string(),
+            None => match self.nested.last() {
+                Some(nested) => nested.rsplit('/').next().unwrap_or(nested).to_string(),
+                None => self
+                    .archive
+                    .file_name()
+                    .map(|name| name.to_string_lossy().to_string())
+                    .unwrap_or_default(),
+            },
+        }
+    }
+
+    /// The root of the innermost archive
+    pub fn container(&self) -> Self {
+        ArchivePath {
+            member: String::new(),
+            ..self.clone()
+        }
+    }
+
+    /// The location of a member of this folder
+    pub fn join(&self, name: &str) -> Self {
+        let mut joined = self.clone();
+        let name = name.trim_matches('/');
+        joined.member = if self.member.is_empty() {
+            name.to_string()
+        } else {
+            format!("{}/{}", self.member, name)
+        };
+        joined
+    }
+
+    /// The root of the archive stored at this member
+    ///
+    /// Returns `None` when the member is not an archive.
+    pub fn enter(&self) -> Option<Self> {
+        if self.member.is_empty() || !ArchiveFormat::is_archive_name(&self.member) {
+            return None;
+        }
+        let mut entered = self.clone();
+        entered.nested.push(std::mem::take(&mut entered.member));
+        Some(entered)
+    }
+
+    /// The path as written in listings and commands
+    pub fn to_path_buf(&self) -> PathBuf {
+        PathBuf::from(self.to_string())
+    }
+}
+
+impl Display for ArchivePath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", self.archive.display(), ARCHIVE_SEPARATOR)?;
+        for nested in &self.nested {
+            write!(f, "/{}{}", nested, ARCHIVE_SEPARATOR)?;
+        }
+        if !self.member.is_empty() {
+            write!(f, "/{}", self.member)?;
+        }
+        Ok(())
+    }
+}
+
+/// A file or folder stored in an archive
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ArchiveMember {
+    /// Path inside the archive, `/`-separated, without leading or trailing `/`
+    pub path: String,
+
+    pub is_dir: bool,
+
+    /// Uncompressed size in bytes (0 for folders)
+    pub size: u64,
+
+    pub modified: Option<DateTime<Utc>>,
+}
+
+impl ArchiveMember {
+    /// Create a member from the path stored in an archive
+    ///
+    /// Returns `None` for paths that cannot be shown safely, such as those
+    /// leaving the archive with `..`.
+    pub fn new(
+        raw_path: &str,
+        is_dir: bool,
+        size: u64,
+        modified: Option<DateTime<Utc>>,
+    ) -> Option<Self> {
+        let path = normalize_member_path(&raw_path.replace('\\', "/"))?;
+        if path.is_empty() {
+            return None;
+        }
+        Some(ArchiveMember {
+            path,
+            is_dir,
+            size: if is_dir { 0 } else { size },
+            modified,
+        })
+    }
+
+    /// Name of the member without its folder
+    pub fn name(&self) -> &str {
+        self.path.rsplit('/').next().unwrap_or(&self.path)
+    }
+
+    /// The members directly inside `folder` (empty for the archive root)
+    ///
+    /// Many archives only store files; folders implied by their paths are
+    /// listed as well. Members are ordered by path.
+    pub fn children(members: &[ArchiveMember], folder: &str) -> Vec<ArchiveMember> {
+        let prefix = if folder.is_empty() {
+            String::new()
+        } else {
+            format!("{}/", folder)
+        };
+
+        let mut children: BTreeMap<String, ArchiveMember> = BTreeMap::new();
+        for member in members {
+            let Some(rest) = member.path.strip_prefix(&prefix) else {
+                continue;
+            };
+            match rest.split_once('/') {
+                Some((name, _)) => {
+                    let path = format!("{}{}", prefix, name);
+                    children.entry(path.clone()).or_insert(ArchiveMember {
+                        path,
+                        is_dir: true,
+                        size: 0,
+                        modified: None,
+                    });
+                }
+                None => {
+                    children.insert(member.path.clone(), member.clone());
+                }
+            }
+        }
+        children.into_values().collect()
+    }
+
+    /// Whether `folder` is a folder of the archive, stored or implied
+    pub fn is_folder(members: &[ArchiveMember], folder: &str) -> bool {
+        folder.is_empty()
+            || members.iter().any(|member| {
+                (member.is_dir && member.path == folder)
+                    || member
+                        .path
+                        .strip_prefix(folder)
+                        .is_some_and(|rest| rest.starts_with('/'))
+            })
+    }
+}
+
+/// Member path without empty or `.` segments; `None` if it contains `..`
+fn normalize_member_path(path: &str) -> Option<String> {
+    let mut segments = Vec::new();
+    for segment in path.split('/') {
+        match segment {
+            "" | "." => {}
+            ".." => return None,
+            segment => segments.push(segment),
+        }
+    }
+    Some(segments.join("/"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_and_display_archive_paths() {
+        assert!(ArchivePath::parse("/case/mail.zip").is_none());
+        assert!(ArchivePath::parse("/case/wow!/notes.txt").is_none());
+
+        let root = ArchivePath::parse("/case/mail.zip!").unwrap();
+        assert_eq!(root, ArchivePath::root("/case/mail.zip"));
+        assert!(root.is_root());
+        assert_eq!(root.format(), Some(ArchiveFormat::Zip));
+        assert_eq!(root.name(), "mail.zip");
+        assert_eq!(root.to_string(), "/case/mail.zip!");
+
+        let member = ArchivePath::parse("/case/mail.zip!/2019//offer.pdf").unwrap();
+        assert_eq!(member.archive(), Path::new("/case/mail.zip"));
+        assert_eq!(member.member(), "2019/offer.pdf");
+        assert_eq!(member.name(), "offer.pdf");
+        assert_eq!(member, root.join("2019").join("offer.pdf"));
+        assert_eq!(member.container(), root);
+        assert_eq!(
+            member.to_path_buf().parent(),
+            Some(Path::new("/case/mail.zip!/2019"))
+        );
+
+        let nested = ArchivePath::parse("/case/mail.zip!/old/backup.tar.gz!/a.txt").unwrap();
+        assert_eq!(nested.nested(), ["old/backup.tar.gz"]);
+        assert_eq!(nested.member(), "a.txt");
+        assert_eq!(nested.format(), Some(ArchiveFormat::TarGz));
+        assert_eq!(
+            root.join("old/backup.tar.gz")
+                .enter()
+                .unwrap()
+                .join("a.txt"),
+            nested
+        );
+        assert_eq!(
+            nested.to_string(),
+            "/case/mail.zip!/old/backup.tar.gz!/a.txt"
+        );
+        assert!(member.enter().is_none());
+
+        assert!(ArchivePath::parse("/case/mail.zip!/../secret.txt").is_none());
+    }
+
+    #[test]
+    fn test_children_include_implied_folders() {
+        let members = vec![
+            ArchiveMember::new("./docs/a.txt", false, 3, None).unwrap(),
+            ArchiveMember::new("docs/sub/b.txt", false, 5, None).unwrap(),
+            ArchiveMember::new("readme.md", false, 1, None).unwrap(),
+            ArchiveMember::new("empty/", true, 0, None).unwrap(),
+        ];
+        assert!(ArchiveMember::new("../evil.sh", false, 1, None).is_none());
+
+        let root: Vec<(String, bool)> = ArchiveMember::children(&members, "")
+            .into_iter()
+            .map(|member| (member.path, member.is_dir))
+            .collect();
+        assert_eq!(
+            root,
+            [
+                ("docs".to_string(), true),
+                ("empty".to_string(), true),
+                ("readme.md".to_string(), false)
+            ]
+        );
+
+        let docs = ArchiveMember::children(&members, "docs");
+        assert_eq!(docs.len(), 2);
+        assert_eq!(docs[0].name(), "a.txt");
+        assert_eq!(docs[0].size, 3);
+        assert!(docs[1].is_dir);
+
+        assert!(ArchiveMember::is_folder(&members, "docs/sub"));
+        assert!(ArchiveMember::is_folder(&members, "empty"));
+        assert!(!ArchiveMember::is_folder(&members, "readme.md"));
+        assert!(!ArchiveMember::is_folder(&members, "doc"));
+    }
+}
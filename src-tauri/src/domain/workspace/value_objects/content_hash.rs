@@ -0,0 +1,231 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::Digest;
+use std::fmt::{self, Display};
+
+/// Digest algorithm used to fingerprint file contents
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum HashAlgorithm {
+    #[default]
+    Blake3,
+    Sha256,
+}
+
+impl HashAlgorithm {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            HashAlgorithm::Blake3 => "blake3",
+            HashAlgorithm::Sha256 => "sha256",
+        }
+    }
+
+    pub fn from_str(value: &str) -> Result<Self, String> {
+        match value.trim().to_lowercase().as_str() {
+            "blake3" => Ok(HashAlgorithm::Blake3),
+            "sha256" | "sha-256" => Ok(HashAlgorithm::Sha256),
+            other => Err(format!("Unsupported hash algorithm: {}", other)),
+        }
+    }
+
+    /// Length of the hex-encoded digest
+    pub fn hex_len(&self) -> usize {
+        64
+    }
+
+    /// Start an incremental hash computation
+    pub fn hasher(&self) -> ContentHasher {
+        match self {
+            HashAlgorithm::Blake3 => ContentHasher::Blake3(Box::default()),
+            HashAlgorithm::Sha256 => ContentHasher::Sha256(sha2::Sha256::new()),
+        }
+    }
+}
+
+impl Display for HashAlgorithm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// Incremental hasher for streaming file contents
+pub enum ContentHasher {
+    Blake3(Box<blake3::Hasher>),
+    Sha256(sha2::Sha256),
+}
+
+impl ContentHasher {
+    pub fn update(&mut self, data: &[u8]) {
+        match self {
+            ContentHasher::Blake3(hasher) => {
+                hasher.update(data);
+            }
+            ContentHasher::Sha256(hasher) => hasher.update(data),
+        }
+    }
+
+    pub fn finalize(self) -> ContentHash {
+        match self {
+            ContentHasher::Blake3(hasher) => ContentHash {
+                algorithm: HashAlgorithm::Blake3,
+                digest: hasher.finalize().to_hex().to_string(),
+            },
+            ContentHasher::Sha256(hasher) => ContentHash {
+                algorithm: HashAlgorithm::Sha256,
+                digest: hex::encode(hasher.finalize()),
+            },
+        }
+    }
+}
+
+/// ContentHash value object - digest of a file's bytes
+///
+/// Rendered as `<algorithm>:<hex digest>` (e.g. `blake3:af13…`) so stored
+/// references stay unambiguous if the default algorithm ever changes.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct ContentHash {
+    algorithm: HashAlgorithm,
+    digest: String,
+}
+
+impl ContentHash {
+    pub fn new(algorithm: HashAlgorithm, digest: String) -> Result<Self, String> {
+        let digest = digest.to_lowercase();
+        if digest.len() != algorithm.hex_len() || !digest.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(format!("Invalid {} digest", algorithm));
+        }
+        Ok(Self { algorithm, digest })
+    }
+
+    /// Hash an in-memory buffer
+    pub fn of_bytes(algorithm: HashAlgorithm, data: &[u8]) -> Self {
+        let mut hasher = algorithm.hasher();
+        hasher.update(data);
+        hasher.finalize()
+    }
+
+    /// Parse the `<algorithm>:<hex digest>` form
+    pub fn parse(value: &str) -> Result<Self, String> {
+        let (algorithm, digest) = value
+            .split_once(':')
+            .ok_or_else(|| "Content hash must be '<algorithm>:<digest>'".to_string())?;
+        Self::new(HashAlgorithm::from_str(algorithm)?, digest.to_string())
+    }
+
+    pub fn algorithm(&self) -> HashAlgorithm {
+        self.algorithm
+    }
+
+    pub fn digest(&self) -> &str {
+        &self.digest
+    }
+}
+
+impl Display for ContentHash {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.algorithm, self.digest)
+    }
+}
+
+impl TryFrom<String> for ContentHash {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Self::parse(&value)
+    }
+}
+
+impl From<ContentHash> for String {
+    fn from(hash: ContentHash) -> Self {
+        hash.to_string()
+    }
+}
+
+/// Cached digest of a file, valid while its size and mtime are unchanged
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileHashRecord {
+    pub path: String,
+    pub size: u64,
+    pub modified_ns: i64,
+    pub hash: ContentHash,
+    pub computed_at: DateTime<Utc>,
+}
+
+impl FileHashRecord {
+    pub fn new(path: String, size: u64, modified_ns: i64, hash: ContentHash) -> Self {
+        Self {
+            path,
+            size,
+            modified_ns,
+            hash,
+            computed_at: Utc::now(),
+        }
+    }
+
+    /// Whether the cached digest still describes a file with this size and mtime
+    pub fn is_current(&self, size: u64, modified_ns: i64) -> bool {
+        self.size == size && self.modified_ns == modified_ns
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_known_digests() {
+        let blake3 = ContentHash::of_bytes(HashAlgorithm::Blake3, b"abc");
+        assert_eq!(
+            blake3.digest(),
+            "6437b3ac38465133ffb63b75273a8db548c558465d79db03fd359c6cd5bd9d85"
+        );
+
+        let sha256 = ContentHash::of_bytes(HashAlgorithm::Sha256, b"abc");
+        assert_eq!(
+            sha256.digest(),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+    }
+
+    #[test]
+    fn test_streaming_matches_single_update() {
+        let mut hasher = HashAlgorithm::Sha256.hasher();
+        hasher.update(b"hello ");
+        hasher.update(b"world");
+
+        assert_eq!(
+            hasher.finalize(),
+            ContentHash::of_bytes(HashAlgorithm::Sha256, b"hello world")
+        );
+    }
+
+    #[test]
+    fn test_parse_and_display_round_trip() {
+        let hash = ContentHash::of_bytes(HashAlgorithm::Blake3, b"corpus");
+        let rendered = hash.to_string();
+        assert!(rendered.starts_with("blake3:"));
+        assert_eq!(ContentHash::parse(&rendered).unwrap(), hash);
+
+        assert!(ContentHash::parse("md5:abc").is_err());
+        assert!(ContentHash::parse("blake3:xyz").is_err());
+        assert!(ContentHash::parse("no-separator").is_err());
+    }
+
+    #[test]
+    fn test_serializes_as_string() {
+        let hash = ContentHash::of_bytes(HashAlgorithm::Sha256, b"x");
+        let json = serde_json::to_string(&hash).unwrap();
+        assert_eq!(json, format!("\"{}\"", hash));
+        assert_eq!(serde_json::from_str::<ContentHash>(&json).unwrap(), hash);
+    }
+
+    #[test]
+    fn test_record_is_current() {
+        let hash = ContentHash::of_bytes(HashAlgorithm::Blake3, b"x");
+        let record = FileHashRecord::new("/a.txt".to_string(), 1, 100, hash);
+        assert!(record.is_current(1, 100));
+        assert!(!record.is_current(2, 100));
+        assert!(!record.is_current(1, 101));
+    }
+}
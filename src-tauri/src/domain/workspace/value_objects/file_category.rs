@@ -0,0 +1,463 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt::{self, Display};
+use std::io::Read;
+use std::path::Path;
+
+/// Number of leading bytes read from a file for magic-byte sniffing
+pub const SNIFF_HEADER_LEN: usize = 64;
+
+/// Extensions of plain-text formats that can be shown without conversion
+const TEXT_EXTENSIONS: &[&str] = &[
+    "txt", "md", "markdown", "json", "xml", "csv", "tsv", "log", "yaml", "yml", "html", "htm",
+];
+
+/// Extensions of rich document formats that need an extractor or viewer
+const RICH_DOCUMENT_EXTENSIONS: &[&str] = &[
+    "pdf", "doc", "docx", "ppt", "pptx", "xls", "xlsx", "odt", "ods", "odp", "rtf", "pages", "key",
+    "numbers", "eml", "msg", "mbox", "det",
+];
+
+const AUDIO_EXTENSIONS: &[&str] = &[
+    "mp3", "wav", "flac", "ogg", "oga", "m4a", "aac", "wma", "aiff", "aif", "opus",
+];
+
+const VIDEO_EXTENSIONS: &[&str] = &[
+    "mp4", "m4v", "mov", "mkv", "webm", "avi", "wmv", "mpg", "mpeg", "3gp",
+];
+
+const IMAGE_EXTENSIONS: &[&str] = &[
+    "png", "jpg", "jpeg", "gif", "bmp", "tif", "tiff", "webp", "heic", "heif", "svg",
+];
+
+/// FileCategory is the single source of truth for what kind of content a file holds
+///
+/// Categories drive the per-project file type counts, whether a file can be
+/// opened in a document caddy, and which processing pipelines apply to it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FileCategory {
+    Documents,
+    Audio,
+    Video,
+    Images,
+    Other,
+}
+
+impl FileCategory {
+    /// All categories in display order
+    pub const ALL: [FileCategory; 5] = [
+        FileCategory::Documents,
+        FileCategory::Audio,
+        FileCategory::Video,
+        FileCategory::Images,
+        FileCategory::Other,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FileCategory::Documents => "documents",
+            FileCategory::Audio => "audio",
+            FileCategory::Video => "video",
+            FileCategory::Images => "images",
+            FileCategory::Other => "other",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Result<Self, String> {
+        match s.trim().to_lowercase().as_str() {
+            "documents" | "document" => Ok(FileCategory::Documents),
+            "audio" => Ok(FileCategory::Audio),
+            "video" => Ok(FileCategory::Video),
+            "images" | "image" => Ok(FileCategory::Images),
+            "other" => Ok(FileCategory::Other),
+            _ => Err(format!("Invalid file category: {}", s)),
+        }
+    }
+
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            FileCategory::Documents => "Documents",
+            FileCategory::Audio => "Audio",
+            FileCategory::Video => "Video",
+            FileCategory::Images => "Images",
+            FileCategory::Other => "Other",
+        }
+    }
+
+    /// Classify using the built-in extension table only
+    ///
+    /// Returns `None` when the extension is not known, so callers can fall
+    /// back to content sniffing.
+    pub fn from_extension(extension: &str) -> Option<Self> {
+        let ext = normalize_extension(extension);
+        let ext = ext.as_str();
+
+        if TEXT_EXTENSIONS.contains(&ext) || RICH_DOCUMENT_EXTENSIONS.contains(&ext) {
+            Some(FileCategory::Documents)
+        } else if AUDIO_EXTENSIONS.contains(&ext) {
+            Some(FileCategory::Audio)
+        } else if VIDEO_EXTENSIONS.contains(&ext) {
+            Some(FileCategory::Video)
+        } else if IMAGE_EXTENSIONS.contains(&ext) {
+            Some(FileCategory::Images)
+        } else {
+            None
+        }
+    }
+
+    /// Classify from the leading bytes of a file
+    ///
+    /// Returns `None` when the header does not match any known signature.
+    pub fn from_magic_bytes(header: &[u8]) -> Option<Self> {
+        if header.is_empty() {
+            return None;
+        }
+
+        let starts = |sig: &[u8]| header.starts_with(sig);
+        let at = |offset: usize, sig: &[u8]| {
+            header.len() >= offset + sig.len() && &header[offset..offset + sig.len()] == sig
+        };
+
+        // Documents
+        if starts(b"%PDF") || starts(b"{\\rtf") || starts(&[0xD0, 0xCF, 0x11, 0xE0]) {
+            return Some(FileCategory::Documents);
+        }
+        if starts(b"PK\x03\x04") {
+            // Office Open XML and OpenDocument packages name their manifest first
+            let window = String::from_utf8_lossy(header);
+            if window.contains("[Content_Types].xml") || window.contains("mimetype") {
+                return Some(FileCategory::Documents);
+            }
+            return None;
+        }
+
+        // Images
+        if starts(&[0x89, b'P', b'N', b'G'])
+            || starts(&[0xFF, 0xD8, 0xFF])
+            || starts(b"GIF87a")
+            || starts(b"GIF89a")
+            || is_bmp(header)
+            || starts(b"II*\0")
+            || starts(b"MM\0*")
+            || (starts(b"RIFF") && at(8, b"WEBP"))
+        {
+            return Some(FileCategory::Images);
+        }
+
+        // Audio
+        if starts(b"ID3")
+            || starts(b"fLaC")
+            || starts(b"OggS")
+            || (starts(b"RIFF") && at(8, b"WAVE"))
+            || (starts(b"FORM") && (at(8, b"AIFF") || at(8, b"AIFC")))
+            || (header.len() >= 2 && header[0] == 0xFF && header[1] & 0xE0 == 0xE0)
+        {
+            return Some(FileCategory::Audio);
+        }
+
+        // Video (ISO base media files can also carry audio only)
+        if at(4, b"ftyp") {
+            if at(8, b"M4A ") || at(8, b"M4B ") {
+                return Some(FileCategory::Audio);
+            }
+            return Some(FileCategory::Video);
+        }
+        if starts(&[0x1A, 0x45, 0xDF, 0xA3])
+            || (starts(b"RIFF") && at(8, b"AVI "))
+            || starts(&[0x00, 0x00, 0x01, 0xBA])
+            || starts(&[0x30, 0x26, 0xB2, 0x75])
+        {
+            return Some(FileCategory::Video);
+        }
+
+        // Plain text without an extension still counts as a document
+        if !header.contains(&0) && std::str::from_utf8(trim_partial_utf8(header)).is_ok() {
+            return Some(FileCategory::Documents);
+        }
+
+        None
+    }
+}
+
+impl Display for FileCategory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.display_name())
+    }
+}
+
+/// Whether the extension names a plain-text format
+pub fn is_text_extension(extension: &str) -> bool {
+    TEXT_EXTENSIONS.contains(&normalize_extension(extension).as_str())
+}
+
+/// A Windows bitmap: "BM", reserved zero fields and a known DIB header size
+fn is_bmp(header: &[u8]) -> bool {
+    const DIB_HEADER_SIZES: [u32; 7] = [12, 40, 52, 56, 64, 108, 124];
+
+    header.len() >= 18
+        && header.starts_with(b"BM")
+        && header[6..10] == [0, 0, 0, 0]
+        && DIB_HEADER_SIZES.contains(&u32::from_le_bytes([
+            header[14], header[15], header[16], header[17],
+        ]))
+}
+
+fn normalize_extension(extension: &str) -> String {
+    extension.trim().trim_start_matches('.').to_lowercase()
+}
+
+/// Drop a UTF-8 sequence cut off by the end of the sniffing window
+fn trim_partial_utf8(bytes: &[u8]) -> &[u8] {
+    match std::str::from_utf8(bytes) {
+        Ok(_) => bytes,
+        Err(error) if error.error_len().is_none() => &bytes[..error.valid_up_to()],
+        Err(_) => bytes,
+    }
+}
+
+/// Per-project classification settings
+///
+/// Overrides take precedence over the built-in extension table. Content
+/// sniffing is only used when the extension is unknown or missing.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FileCategoryConfig {
+    /// Extension (lowercase, without dot) to category overrides
+    overrides: HashMap<String, FileCategory>,
+    /// Whether to read file headers when the extension is not conclusive
+    sniff_content: bool,
+}
+
+impl Default for FileCategoryConfig {
+    fn default() -> Self {
+        Self {
+            overrides: HashMap::new(),
+            sniff_content: true,
+        }
+    }
+}
+
+impl FileCategoryConfig {
+    pub fn new(overrides: HashMap<String, FileCategory>, sniff_content: bool) -> Self {
+        let overrides = overrides
+            .into_iter()
+            .map(|(ext, category)| (normalize_extension(&ext), category))
+            .filter(|(ext, _)| !ext.is_empty())
+            .collect();
+
+        Self {
+            overrides,
+            sniff_content,
+        }
+    }
+
+    pub fn overrides(&self) -> &HashMap<String, FileCategory> {
+        &self.overrides
+    }
+
+    pub fn sniff_content(&self) -> bool {
+        self.sniff_content
+    }
+
+    /// Map an extension to a category for this project
+    pub fn with_override(mut self, extension: &str, category: FileCategory) -> Self {
+        let ext = normalize_extension(extension);
+        if !ext.is_empty() {
+            self.overrides.insert(ext, category);
+        }
+        self
+    }
+
+    /// Classify by extension, honouring project overrides
+    pub fn classify_extension(&self, extension: &str) -> Option<FileCategory> {
+        let ext = normalize_extension(extension);
+        self.overrides
+            .get(&ext)
+            .copied()
+            .or_else(|| FileCategory::from_extension(&ext))
+    }
+
+    /// Classify a file by name and, when needed, by its leading bytes
+    ///
+    /// `header` is only consulted when the extension is unknown and sniffing
+    /// is enabled for the project.
+    pub fn classify(&self, file_name: &str, header: Option<&[u8]>) -> FileCategory {
+        let by_extension = Path::new(file_name)
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .and_then(|ext| self.classify_extension(ext));
+
+        if let Some(category) = by_extension {
+            return category;
+        }
+
+        if self.sniff_content {
+            if let Some(category) = header.and_then(FileCategory::from_magic_bytes) {
+                return category;
+            }
+        }
+
+        FileCategory::Other
+    }
+
+    /// Classify a file on disk, reading its header only when necessary
+    pub fn classify_path(&self, path: &Path) -> FileCategory {
+        let file_name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+
+        let by_extension = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .and_then(|ext| self.classify_extension(ext));
+
+        if let Some(category) = by_extension {
+            return category;
+        }
+
+        if !self.sniff_content {
+            return FileCategory::Other;
+        }
+
+        let header = read_header(path);
+        self.classify(&file_name, header.as_deref())
+    }
+}
+
+fn read_header(path: &Path) -> Option<Vec<u8>> {
+    let file = std::fs::File::open(path).ok()?;
+    let mut buffer = Vec::with_capacity(SNIFF_HEADER_LEN);
+    file.take(SNIFF_HEADER_LEN as u64)
+        .read_to_end(&mut buffer)
+        .ok()?;
+    Some(buffer)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_from_extension() {
+        assert_eq!(
+            FileCategory::from_extension("PDF"),
+            Some(FileCategory::Documents)
+        );
+        assert_eq!(
+            FileCategory::from_extension(".md"),
+            Some(FileCategory::Documents)
+        );
+        assert_eq!(
+            FileCategory::from_extension("mp3"),
+            Some(FileCategory::Audio)
+        );
+        assert_eq!(
+            FileCategory::from_extension("mkv"),
+            Some(FileCategory::Video)
+        );
+        assert_eq!(
+            FileCategory::from_extension("jpeg"),
+            Some(FileCategory::Images)
+        );
+        assert_eq!(FileCategory::from_extension("xyz"), None);
+    }
+
+    #[test]
+    fn test_text_extensions() {
+        assert!(is_text_extension("txt"));
+        assert!(is_text_extension("CSV"));
+        assert!(!is_text_extension("pdf"));
+    }
+
+    #[test]
+    fn test_from_magic_bytes() {
+        assert_eq!(
+            FileCategory::from_magic_bytes(b"%PDF-1.7\n"),
+            Some(FileCategory::Documents)
+        );
+        assert_eq!(
+            FileCategory::from_magic_bytes(&[0x89, b'P', b'N', b'G', 0x0D, 0x0A]),
+            Some(FileCategory::Images)
+        );
+        assert_eq!(
+            FileCategory::from_magic_bytes(b"ID3\x04\x00"),
+            Some(FileCategory::Audio)
+        );
+        assert_eq!(
+            FileCategory::from_magic_bytes(b"RIFF\x24\x00\x00\x00WAVEfmt "),
+            Some(FileCategory::Audio)
+        );
+        assert_eq!(
+            FileCategory::from_magic_bytes(b"\x00\x00\x00\x18ftypmp42"),
+            Some(FileCategory::Video)
+        );
+        assert_eq!(
+            FileCategory::from_magic_bytes(b"\x00\x00\x00\x18ftypM4A "),
+            Some(FileCategory::Audio)
+        );
+        assert_eq!(
+            FileCategory::from_magic_bytes(b"plain notes"),
+            Some(FileCategory::Documents)
+        );
+        assert_eq!(FileCategory::from_magic_bytes(&[0x00, 0x01, 0x02]), None);
+    }
+
+    #[test]
+    fn test_bitmap_needs_header_fields() {
+        let mut bitmap = b"BM\x46\x00\x00\x00\x00\x00\x00\x00\x36\x00\x00\x00".to_vec();
+        bitmap.extend(40u32.to_le_bytes());
+        assert_eq!(
+            FileCategory::from_magic_bytes(&bitmap),
+            Some(FileCategory::Images)
+        );
+        assert_eq!(
+            FileCategory::from_magic_bytes(b"BMW fleet report, Q3"),
+            Some(FileCategory::Documents)
+        );
+        assert_eq!(FileCategory::from_magic_bytes(b"BM\x00\x01"), None);
+    }
+
+    #[test]
+    fn test_category_round_trip() {
+        for category in FileCategory::ALL {
+            assert_eq!(FileCategory::from_str(category.as_str()).unwrap(), category);
+        }
+        assert!(FileCategory::from_str("spreadsheets").is_err());
+    }
+
+    #[test]
+    fn test_config_overrides_take_precedence() {
+        let config = FileCategoryConfig::default().with_override(".LOG", FileCategory::Other);
+
+        assert_eq!(config.classify("server.log", None), FileCategory::Other);
+        assert_eq!(config.classify("notes.txt", None), FileCategory::Documents);
+    }
+
+    #[test]
+    fn test_sniffing_can_be_disabled() {
+        let sniffing = FileCategoryConfig::default();
+        let no_sniffing = FileCategoryConfig::new(HashMap::new(), false);
+
+        assert_eq!(
+            sniffing.classify("scan", Some(b"%PDF-1.4")),
+            FileCategory::Documents
+        );
+        assert_eq!(
+            no_sniffing.classify("scan", Some(b"%PDF-1.4")),
+            FileCategory::Other
+        );
+    }
+
+    #[test]
+    fn test_classify_path_reads_header() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("recording");
+        std::fs::write(&path, b"fLaC\x00\x00\x00\x22").unwrap();
+
+        let config = FileCategoryConfig::default();
+        assert_eq!(config.classify_path(&path), FileCategory::Audio);
+    }
+}
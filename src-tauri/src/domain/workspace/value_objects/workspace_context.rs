@@ -1,5 +1,6 @@
 use crate::domain::project::value_objects::ProjectId;
 use crate::domain::workspace::errors::WorkspaceError;
+use crate::domain::workspace::value_objects::{ArchiveFormat, ArchivePath};
 use serde::{Deserialize, Serialize};
 use std::path::{Path, PathBuf};
 
@@ -95,6 +96,16 @@ impl WorkspaceContext {
         &self.current_path
     }
 
+    /// Get the location inside an archive, if the current path is in one
+    pub fn archive_path(&self) -> Option<ArchivePath> {
+        ArchivePath::parse(&self.current_path)
+    }
+
+    /// Check if the current path is inside an archive file
+    pub fn is_in_archive(&self) -> bool {
+        self.archive_path().is_some()
+    }
+
     /// Check if the current path is at the workspace root
     pub fn is_at_root(&self) -> bool {
         self.current_path == self.source_folder
@@ -165,6 +176,44 @@ impl WorkspaceContext {
         self.with_current_path(new_path)
     }
 
+    /// Descend into an archive file in the current folder
+    ///
+    /// The archive is browsed as a read-only folder; archives stored in
+    /// archives can be entered in turn.
+    ///
+    /// # Arguments
+    /// * `file_name` - Name of the archive file (relative to current path)
+    ///
+    /// # Errors
+    /// Returns `WorkspaceError` if:
+    /// - File name contains invalid characters
+    /// - The file is not a supported archive
+    /// - Navigation would exceed workspace boundaries
+    pub fn navigate_into_archive(&self, file_name: &str) -> Result<Self, WorkspaceError> {
+        if file_name.contains("..") || file_name.contains('/') || file_name.contains('\\') {
+            return Err(WorkspaceError::invalid_path(
+                file_name.to_string(),
+                "File name contains invalid characters or path traversal",
+            ));
+        }
+
+        if !ArchiveFormat::is_archive_name(file_name) {
+            return Err(WorkspaceError::invalid_path(
+                file_name.to_string(),
+                "File is not a supported archive",
+            ));
+        }
+
+        let archive_root = match self.archive_path() {
+            Some(folder) => folder.join(file_name).enter(),
+            None => Some(ArchivePath::root(self.current_path.join(file_name))),
+        }
+        .ok_or_else(|| {
+            WorkspaceError::invalid_path(file_name.to_string(), "File is not a supported archive")
+        })?;
+        self.with_current_path(archive_root.to_path_buf())
+    }
+
     /// Navigate to parent directory
     ///
     /// # Errors
@@ -181,8 +230,12 @@ impl WorkspaceContext {
 
     /// Check if a path is within the workspace boundary
     ///
-    /// This is a critical security function that prevents path traversal attacks
+    /// This is a critical security function that prevents path traversal attacks.
+    /// Paths inside an archive are checked by the archive file's own path.
     fn is_path_within_boundary(path: &Path, boundary: &Path) -> Result<bool, WorkspaceError> {
+        let archive = ArchivePath::parse(path);
+        let path = archive.as_ref().map_or(path, |archive| archive.archive());
+
         // Canonicalize both paths to resolve any symlinks or relative components
         let canonical_path = path
             .canonicalize()
@@ -386,4 +439,40 @@ mod tests {
         let relative = context.relative_path().unwrap();
         assert_eq!(relative, PathBuf::from("documents").join("archived"));
     }
+
+    #[test]
+    fn test_navigation_into_archive() {
+        let temp_dir = TempDir::new().unwrap();
+        let project_id = create_test_project_id();
+        let archive = temp_dir.path().join("mail.zip");
+        std::fs::write(&archive, "PK").unwrap();
+
+        let context =
+            WorkspaceContext::new(project_id, "Test Project", temp_dir.path(), None::<&str>)
+                .unwrap();
+        assert!(!context.is_in_archive());
+        assert!(context.navigate_into_archive("notes.txt").is_err());
+
+        let inside = context.navigate_into_archive("mail.zip").unwrap();
+        assert!(inside.is_in_archive());
+        assert_eq!(inside.archive_path().unwrap(), ArchivePath::root(&archive));
+
+        let folder = inside.navigate_to_folder("2019").unwrap();
+        let nested = folder.navigate_into_archive("old.tar").unwrap();
+        assert_eq!(
+            nested.current_path(),
+            PathBuf::from(format!("{}!/2019/old.tar!", archive.display()))
+        );
+
+        let back = nested
+            .navigate_to_parent()
+            .unwrap()
+            .navigate_to_parent()
+            .unwrap();
+        assert_eq!(back.current_path(), inside.current_path());
+        assert_eq!(
+            back.navigate_to_parent().unwrap().current_path(),
+            temp_dir.path()
+        );
+    }
 }
@@ -0,0 +1,512 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use flate2::read::GzDecoder;
+use std::fs::File;
+use std::io::{Cursor, Read, Seek, SeekFrom, Write};
+use std::path::Path;
+use std::time::SystemTime;
+
+use crate::domain::workspace::{
+    ArchiveError, ArchiveFormat, ArchiveMember, ArchivePath, ARCHIVE_SEPARATOR,
+};
+
+/// Largest member read from an archive, including archives nested in it
+pub const MAX_MEMBER_SIZE: u64 = 512 * 1024 * 1024;
+
+/// Depth up to which archives stored in archives are walked
+pub const MAX_NESTING: usize = 3;
+
+/// Reads the members of ZIP, 7z, TAR and gzipped TAR archives
+///
+/// Archives are only ever read, never changed or unpacked as a whole.
+/// Archives stored in archives are opened in memory, one level after the
+/// other, so they are subject to `MAX_MEMBER_SIZE` like any member.
+pub struct ArchiveReader;
+
+/// An archive file on disk, or a nested archive read into memory
+enum Source {
+    File(File),
+    Memory(Cursor<Vec<u8>>),
+}
+
+impl Read for Source {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        match self {
+            Source::File(file) => file.read(buf),
+            Source::Memory(cursor) => cursor.read(buf),
+        }
+    }
+}
+
+impl Seek for Source {
+    fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+        match self {
+            Source::File(file) => file.seek(pos),
+            Source::Memory(cursor) => cursor.seek(pos),
+        }
+    }
+}
+
+/// An opened archive and the name used for it in errors
+struct Opened {
+    format: ArchiveFormat,
+    source: Source,
+    name: String,
+}
+
+impl ArchiveReader {
+    /// List every member of the innermost archive of `location`, in
+    /// archive order
+    ///
+    /// Members whose paths would leave the archive are left out.
+    pub fn list(location: &ArchivePath) -> Result<Vec<ArchiveMember>, ArchiveError> {
+        let opened = Self::open(location)?;
+        let fail = |e: &dyn ToString| {
+            ArchiveError::unreadable(opened.format.as_str(), opened.name.clone(), e.to_string())
+        };
+
+        match opened.format {
+            ArchiveFormat::Zip => {
+                let mut archive = zip::ZipArchive::new(opened.source).map_err(|e| fail(&e))?;
+                let mut members = Vec::new();
+                for index in 0..archive.len() {
+                    let file = archive.by_index(index).map_err(|e| fail(&e))?;
+                    let modified = file.last_modified().and_then(zip_time);
+                    members.extend(ArchiveMember::new(
+                        file.name(),
+                        file.is_dir(),
+                        file.size(),
+                        modified,
+                    ));
+                }
+                Ok(members)
+            }
+            ArchiveFormat::SevenZ => {
+                let mut source = opened.source;
+                let len = source.seek(SeekFrom::End(0)).map_err(|e| fail(&e))?;
+                source.rewind().map_err(|e| fail(&e))?;
+                let reader =
+                    sevenz_rust::SevenZReader::new(source, len, sevenz_rust::Password::empty())
+                        .map_err(|e| fail(&e))?;
+                Ok(reader
+                    .archive()
+                    .files
+                    .iter()
+                    .filter(|entry| !entry.is_anti_item())
+                    .filter_map(|entry| {
+                        let modified = entry
+                            .has_last_modified_date
+                            .then(|| SystemTime::from(entry.last_modified_date()).into());
+                        ArchiveMember::new(
+                            entry.name(),
+                            entry.is_directory(),
+                            entry.size(),
+                            modified,
+                        )
+                    })
+                    .collect())
+            }
+            ArchiveFormat::Tar | ArchiveFormat::TarGz => {
+                let mut archive = tar_archive(opened.format, opened.source);
+                let mut members = Vec::new();
+                for entry in archive.entries().map_err(|e| fail(&e))? {
+                    let entry = entry.map_err(|e| fail(&e))?;
+                    let header = entry.header();
+                    let path = entry.path().map_err(|e| fail(&e))?;
+                    let modified = header
+                        .mtime()
+                        .ok()
+                        .and_then(|mtime| DateTime::from_timestamp(mtime as i64, 0));
+                    members.extend(ArchiveMember::new(
+                        &path.to_string_lossy(),
+                        header.entry_type().is_dir(),
+                        entry.size(),
+                        modified,
+                    ));
+                }
+                Ok(members)
+            }
+        }
+    }
+
+    /// Every file of an archive, including those of the archives stored in
+    /// it, up to `MAX_NESTING` levels deep
+    ///
+    /// Nested archives count as files themselves. Those that cannot be read
+    /// are skipped; only the outer archive failing is an error.
+    pub fn walk(archive: &Path) -> Result<Vec<(ArchivePath, ArchiveMember)>, ArchiveError> {
+        let mut files = Vec::new();
+        let mut pending = vec![(ArchivePath::root(archive), 0)];
+
+        while let Some((folder, depth)) = pending.pop() {
+            let members = match Self::list(&folder) {
+                Ok(members) => members,
+                Err(error) if !folder.nested().is_empty() => {
+                    tracing::warn!("Skipping nested archive {}: {}", folder, error);
+                    continue;
+                }
+                Err(error) => return Err(error),
+            };
+            for member in members.into_iter().filter(|member| !member.is_dir) {
+                let location = folder.join(&member.path);
+                if depth < MAX_NESTING {
+                    if let Some(inner) = location.enter() {
+                        pending.push((inner, depth + 1));
+                    }
+                }
+                files.push((location, member));
+            }
+        }
+        Ok(files)
+    }
+
+    /// Read one member of an archive into memory
+    ///
+    /// # Errors
+    /// Fails when the member does not exist, is a folder, or is larger
+    /// than `limit` bytes.
+    pub fn read_member(location: &ArchivePath, limit: u64) -> Result<Vec<u8>, ArchiveError> {
+        let opened = Self::open(location)?;
+        Self::read_from(opened, location.member(), limit)
+    }
+
+    /// Copy one member of an archive to `out` without holding it in memory,
+    /// returning the number of bytes copied
+    ///
+    /// # Errors
+    /// Fails like `read_member`, and when `out` cannot be written. `out`
+    /// may then hold part of the member.
+    pub fn copy_member(
+        location: &ArchivePath,
+        limit: u64,
+        out: &mut dyn Write,
+    ) -> Result<u64, ArchiveError> {
+        let opened = Self::open(location)?;
+        Self::copy_from(opened, location.member(), limit, out)
+    }
+
+    /// Open the innermost archive of a location
+    fn open(location: &ArchivePath) -> Result<Opened, ArchiveError> {
+        let name = location.archive().display().to_string();
+        let format = ArchiveFormat::from_name(&name)
+            .ok_or_else(|| ArchiveError::unsupported(name.clone()))?;
+        let file = File::open(location.archive())
+            .map_err(|e| ArchiveError::unreadable(format.as_str(), name.clone(), e))?;
+        let mut opened = Opened {
+            format,
+            source: Source::File(file),
+            name,
+        };
+
+        for nested in location.nested() {
+            let format = ArchiveFormat::from_name(nested)
+                .ok_or_else(|| ArchiveError::unsupported(nested.clone()))?;
+            let name = format!("{}{}/{}", opened.name, ARCHIVE_SEPARATOR, nested);
+            let bytes = Self::read_from(opened, nested, MAX_MEMBER_SIZE)?;
+            opened = Opened {
+                format,
+                source: Source::Memory(Cursor::new(bytes)),
+                name,
+            };
+        }
+        Ok(opened)
+    }
+
+    fn read_from(opened: Opened, member: &str, limit: u64) -> Result<Vec<u8>, ArchiveError> {
+        let mut bytes = Vec::new();
+        Self::copy_from(opened, member, limit, &mut bytes)?;
+        Ok(bytes)
+    }
+
+    /// Copy a member to `out`
+    ///
+    /// The declared size is checked first, but an archive may understate
+    /// it, so at most one byte more than `limit` is ever read.
+    fn copy_from(
+        opened: Opened,
+        member: &str,
+        limit: u64,
+        out: &mut dyn Write,
+    ) -> Result<u64, ArchiveError> {
+        let fail = |e: &dyn ToString| {
+            ArchiveError::unreadable(opened.format.as_str(), opened.name.clone(), e.to_string())
+        };
+        let too_large = |size: u64| ArchiveError::TooLarge {
+            path: member.to_string(),
+            size,
+            limit,
+        };
+        let matches = |path: &str, is_dir: bool| {
+            !is_dir
+                && ArchiveMember::new(path, false, 0, None)
+                    .is_some_and(|candidate| candidate.path == member)
+        };
+
+        let mut copy = |data: &mut dyn Read| std::io::copy(&mut data.take(limit + 1), out);
+
+        let copied = match opened.format {
+            ArchiveFormat::Zip => {
+                let mut archive = zip::ZipArchive::new(opened.source).map_err(|e| fail(&e))?;
+                let mut copied = None;
+                for index in 0..archive.len() {
+                    let mut file = archive.by_index(index).map_err(|e| fail(&e))?;
+                    if !matches(file.name(), file.is_dir()) {
+                        continue;
+                    }
+                    if file.size() > limit {
+                        return Err(too_large(file.size()));
+                    }
+                    copied = Some(copy(&mut file).map_err(|e| fail(&e))?);
+                    break;
+                }
+                copied
+            }
+            ArchiveFormat::SevenZ => {
+                let mut source = opened.source;
+                let len = source.seek(SeekFrom::End(0)).map_err(|e| fail(&e))?;
+                source.rewind().map_err(|e| fail(&e))?;
+                let mut reader =
+                    sevenz_rust::SevenZReader::new(source, len, sevenz_rust::Password::empty())
+                        .map_err(|e| fail(&e))?;
+                if let Some(entry) = reader
+                    .archive()
+                    .files
+                    .iter()
+                    .find(|entry| matches(entry.name(), entry.is_directory()))
+                {
+                    if entry.size() > limit {
+                        return Err(too_large(entry.size()));
+                    }
+                }
+
+                let mut copied = None;
+                reader
+                    .for_each_entries(|entry, data| {
+                        if copied.is_some() {
+                            return Ok(false);
+                        }
+                        if matches(entry.name(), entry.is_directory()) {
+                            copied = Some(copy(data)?);
+                            return Ok(false);
+                        }
+                        // Solid archives decode members in order, so skipped
+                        // ones still have to be read
+                        std::io::copy(data, &mut std::io::sink())?;
+                        Ok(true)
+                    })
+                    .map_err(|e| fail(&e))?;
+                copied
+            }
+            ArchiveFormat::Tar | ArchiveFormat::TarGz => {
+                let mut archive = tar_archive(opened.format, opened.source);
+                let mut copied = None;
+                for entry in archive.entries().map_err(|e| fail(&e))? {
+                    let mut entry = entry.map_err(|e| fail(&e))?;
+                    let path = entry.path().map_err(|e| fail(&e))?;
+                    let is_dir = entry.header().entry_type().is_dir();
+                    if !matches(&path.to_string_lossy(), is_dir) {
+                        continue;
+                    }
+                    if entry.size() > limit {
+                        return Err(too_large(entry.size()));
+                    }
+                    copied = Some(copy(&mut entry).map_err(|e| fail(&e))?);
+                    break;
+                }
+                copied
+            }
+        };
+
+        match copied {
+            Some(copied) if copied > limit => Err(too_large(copied)),
+            Some(copied) => Ok(copied),
+            None => Err(ArchiveError::member_not_found(member)),
+        }
+    }
+}
+
+fn tar_archive(format: ArchiveFormat, source: Source) -> tar::Archive<Box<dyn Read>> {
+    let reader: Box<dyn Read> = match format {
+        ArchiveFormat::TarGz => Box::new(GzDecoder::new(source)),
+        _ => Box::new(source),
+    };
+    tar::Archive::new(reader)
+}
+
+/// ZIP times are local times without a zone; they are read as UTC
+fn zip_time(time: zip::DateTime) -> Option<DateTime<Utc>> {
+    NaiveDate::from_ymd_opt(
+        i32::from(time.year()),
+        u32::from(time.month()),
+        u32::from(time.day()),
+    )?
+    .and_hms_opt(
+        u32::from(time.hour()),
+        u32::from(time.minute()),
+        u32::from(time.second()),
+    )
+    .map(|time| time.and_utc())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use tempfile::TempDir;
+
+    fn zip_bytes(files: &[(&str, &[u8])]) -> Vec<u8> {
+        let mut writer = zip::ZipWriter::new(Cursor::new(Vec::new()));
+        for (name, data) in files {
+            writer
+                .start_file(*name, zip::write::SimpleFileOptions::default())
+                .unwrap();
+            writer.write_all(data).unwrap();
+        }
+        writer.finish().unwrap().into_inner()
+    }
+
+    fn tar_bytes(files: &[(&str, &[u8])]) -> Vec<u8> {
+        let mut builder = tar::Builder::new(Vec::new());
+        for (name, data) in files {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(data.len() as u64);
+            header.set_mtime(1_700_000_000);
+            header.set_mode(0o644);
+            header.set_cksum();
+            builder.append_data(&mut header, name, *data).unwrap();
+        }
+        builder.into_inner().unwrap()
+    }
+
+    #[test]
+    fn test_list_and_read_nested_archives() {
+        let dir = TempDir::new().unwrap();
+        let tar = tar_bytes(&[("notes/a.txt", b"inner text")]);
+        let mut gz = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        gz.write_all(&tar).unwrap();
+        let zip = zip_bytes(&[
+            ("docs/offer.pdf", b"%PDF-1.4"),
+            ("backup.tar.gz", &gz.finish().unwrap()),
+        ]);
+        let archive = dir.path().join("mail.zip");
+        std::fs::write(&archive, zip).unwrap();
+
+        let root = ArchivePath::root(&archive);
+        let members = ArchiveReader::list(&root).unwrap();
+        let paths: Vec<&str> = members.iter().map(|m| m.path.as_str()).collect();
+        assert_eq!(paths, ["docs/offer.pdf", "backup.tar.gz"]);
+        assert_eq!(members[0].size, 8);
+        assert!(members[0].modified.is_some());
+
+        let offer = root.join("docs/offer.pdf");
+        assert_eq!(
+            ArchiveReader::read_member(&offer, 1024).unwrap(),
+            b"%PDF-1.4"
+        );
+        assert!(matches!(
+            ArchiveReader::read_member(&offer, 4),
+            Err(ArchiveError::TooLarge { size: 8, .. })
+        ));
+        assert!(matches!(
+            ArchiveReader::read_member(&root.join("docs"), 1024),
+            Err(ArchiveError::MemberNotFound { .. })
+        ));
+
+        let nested = root.join("backup.tar.gz").enter().unwrap();
+        let members = ArchiveReader::list(&nested).unwrap();
+        assert_eq!(members[0].path, "notes/a.txt");
+        assert_eq!(members[0].modified.unwrap().timestamp(), 1_700_000_000);
+        assert_eq!(
+            ArchiveReader::read_member(&nested.join("notes/a.txt"), 1024).unwrap(),
+            b"inner text"
+        );
+
+        let walked: Vec<String> = ArchiveReader::walk(&archive)
+            .unwrap()
+            .iter()
+            .map(|(location, _)| location.to_string())
+            .collect();
+        assert_eq!(
+            walked,
+            [
+                format!("{}!/docs/offer.pdf", archive.display()),
+                format!("{}!/backup.tar.gz", archive.display()),
+                format!("{}!/backup.tar.gz!/notes/a.txt", archive.display()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_understated_member_size_is_bounded() {
+        let dir = TempDir::new().unwrap();
+        let mut zip = zip_bytes(&[("bomb.txt", &[b'a'; 4096])]);
+        // Claim 4 bytes uncompressed in the local and the central header
+        for (signature, offset) in [(b"PK\x03\x04", 22), (b"PK\x01\x02", 24)] {
+            let start = zip
+                .windows(4)
+                .position(|window| window == signature)
+                .unwrap();
+            zip[start + offset..start + offset + 4].copy_from_slice(&4u32.to_le_bytes());
+        }
+        let archive = dir.path().join("bomb.zip");
+        std::fs::write(&archive, zip).unwrap();
+
+        let member = ArchivePath::root(&archive).join("bomb.txt");
+        assert_eq!(
+            ArchiveReader::list(&ArchivePath::root(&archive)).unwrap()[0].size,
+            4
+        );
+        assert!(matches!(
+            ArchiveReader::read_member(&member, 64),
+            Err(ArchiveError::TooLarge {
+                size: 65,
+                limit: 64,
+                ..
+            })
+        ));
+
+        let mut copied = Vec::new();
+        assert!(ArchiveReader::copy_member(&member, 64, &mut copied).is_err());
+        assert!(copied.len() <= 65);
+    }
+
+    #[test]
+    fn test_list_and_read_7z() {
+        let dir = TempDir::new().unwrap();
+        let content = dir.path().join("content");
+        std::fs::create_dir_all(content.join("sub")).unwrap();
+        std::fs::write(content.join("one.txt"), "first").unwrap();
+        std::fs::write(content.join("sub").join("two.txt"), "second").unwrap();
+        let archive = dir.path().join("box.7z");
+        sevenz_rust::compress_to_path(&content, &archive).unwrap();
+
+        let root = ArchivePath::root(&archive);
+        let members = ArchiveReader::list(&root).unwrap();
+        assert!(members
+            .iter()
+            .any(|m| m.path == "sub/two.txt" && m.size == 6));
+        assert_eq!(
+            ArchiveReader::read_member(&root.join("sub/two.txt"), 1024).unwrap(),
+            b"second"
+        );
+        assert_eq!(
+            ArchiveReader::read_member(&root.join("one.txt"), 1024).unwrap(),
+            b"first"
+        );
+    }
+
+    #[test]
+    fn test_damaged_and_unsupported_archives() {
+        let dir = TempDir::new().unwrap();
+        let damaged = dir.path().join("broken.zip");
+        std::fs::write(&damaged, b"not a zip").unwrap();
+
+        assert!(matches!(
+            ArchiveReader::list(&ArchivePath::root(&damaged)),
+            Err(ArchiveError::Unreadable { .. })
+        ));
+        assert!(matches!(
+            ArchiveReader::list(&ArchivePath::root(Path::new("/tmp/notes.txt"))),
+            Err(ArchiveError::UnsupportedFormat { .. })
+        ));
+    }
+}
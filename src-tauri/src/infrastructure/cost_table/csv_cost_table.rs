@@ -0,0 +1,113 @@
+use crate::domain::cost_table::{
+    row_to_record, ColumnLayout, CostRow, CostTable, CostTableCodec, CostTableError,
+    CostTableFormat, COST_TABLE_COLUMNS,
+};
+
+/// Cost tables as comma-separated values with a header row
+///
+/// Only the rows are written so an exported file can be imported again
+/// unchanged; totals are left to the spreadsheet. A byte order mark, as
+/// Excel writes, is skipped on import.
+pub struct CsvCostTableCodec;
+
+const FORMAT: CostTableFormat = CostTableFormat::Csv;
+
+impl CostTableCodec for CsvCostTableCodec {
+    fn format(&self) -> CostTableFormat {
+        FORMAT
+    }
+
+    fn encode(&self, table: &CostTable) -> Result<Vec<u8>, CostTableError> {
+        let mut writer = csv::Writer::from_writer(Vec::new());
+        let failed = |e: csv::Error| CostTableError::invalid_file(FORMAT.as_str(), e);
+        writer.write_record(COST_TABLE_COLUMNS).map_err(failed)?;
+        for row in table.rows() {
+            writer.write_record(row_to_record(row)).map_err(failed)?;
+        }
+        writer
+            .into_inner()
+            .map_err(|e| CostTableError::invalid_file(FORMAT.as_str(), e))
+    }
+
+    fn decode(&self, bytes: &[u8]) -> Result<Vec<CostRow>, CostTableError> {
+        let bytes = bytes.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(bytes);
+        let mut reader = csv::ReaderBuilder::new().flexible(true).from_reader(bytes);
+        let invalid = |e: csv::Error| CostTableError::invalid_file(FORMAT.as_str(), e);
+
+        let header: Vec<String> = reader
+            .headers()
+            .map_err(invalid)?
+            .iter()
+            .map(str::to_string)
+            .collect();
+        let layout = ColumnLayout::from_header(&header, FORMAT)?;
+
+        let mut rows = Vec::new();
+        for (index, record) in reader.records().enumerate() {
+            let cells: Vec<String> = record
+                .map_err(invalid)?
+                .iter()
+                .map(str::to_string)
+                .collect();
+            // The header is row 1
+            if let Some(row) = layout.read_row(&cells, index + 2)? {
+                rows.push(row);
+            }
+        }
+        Ok(rows)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::cost_table::{Amount, CostCategory, Currency};
+    use crate::domain::report::{Citation, CitationLocator};
+    use chrono::NaiveDate;
+
+    #[test]
+    fn test_round_trip_and_accountant_edits() {
+        let row = CostRow::new(
+            NaiveDate::from_ymd_opt(2024, 1, 31).unwrap(),
+            "Repairs, roof",
+            CostCategory::Direct,
+            Amount::from_cents(120_050),
+            Currency::parse("EUR").unwrap(),
+        )
+        .unwrap()
+        .with_source(
+            Citation::new(
+                None,
+                "/c/invoice.pdf".to_string(),
+                CitationLocator::Page { page: 3 },
+            )
+            .unwrap(),
+        );
+        let table = CostTable::new("Damages", None)
+            .unwrap()
+            .with_rows(vec![row]);
+
+        let bytes = CsvCostTableCodec.encode(&table).unwrap();
+        assert!(String::from_utf8_lossy(&bytes).contains("\"Repairs, roof\""));
+        assert_eq!(CsvCostTableCodec.decode(&bytes).unwrap(), table.rows());
+
+        let edited = "\u{feff}Notes,Amount,Currency,Date,Category,Description\n\
+                      checked,\"1.234,50\",usd,2024-02-01,Indirect costs,Overhead\n\
+                      ,,,,,\n";
+        let rows = CsvCostTableCodec.decode(edited.as_bytes()).unwrap();
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].amount(), Amount::from_cents(123_450));
+        assert_eq!(rows[0].category(), CostCategory::Indirect);
+        assert!(rows[0].source().is_none());
+
+        let bad = "date,description,category,amount,currency\n2024-02-30,Fee,direct,1,EUR\n";
+        assert!(matches!(
+            CsvCostTableCodec.decode(bad.as_bytes()),
+            Err(CostTableError::InvalidRow { row: 2, .. })
+        ));
+        assert!(matches!(
+            CsvCostTableCodec.decode(b"date,amount\n"),
+            Err(CostTableError::InvalidFile { .. })
+        ));
+    }
+}
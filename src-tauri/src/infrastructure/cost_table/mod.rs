@@ -0,0 +1,37 @@
+pub mod csv_cost_table;
+pub mod xlsx_cost_table;
+
+pub use csv_cost_table::CsvCostTableCodec;
+pub use xlsx_cost_table::XlsxCostTableCodec;
+
+use std::sync::Arc;
+
+use crate::domain::cost_table::{CostTableCodec, CostTableFormat};
+
+/// Looks up the codec for a spreadsheet format
+#[derive(Clone, Default)]
+pub struct CostTableCodecRegistry {
+    codecs: Vec<Arc<dyn CostTableCodec>>,
+}
+
+impl CostTableCodecRegistry {
+    /// Create a registry with the CSV and XLSX codecs
+    pub fn with_defaults() -> Self {
+        let mut registry = Self::default();
+        registry.register(Arc::new(CsvCostTableCodec));
+        registry.register(Arc::new(XlsxCostTableCodec));
+        registry
+    }
+
+    /// Add a codec; later registrations take precedence
+    pub fn register(&mut self, codec: Arc<dyn CostTableCodec>) {
+        self.codecs.insert(0, codec);
+    }
+
+    pub fn for_format(&self, format: CostTableFormat) -> Option<Arc<dyn CostTableCodec>> {
+        self.codecs
+            .iter()
+            .find(|codec| codec.format() == format)
+            .cloned()
+    }
+}
@@ -0,0 +1,599 @@
+use chrono::{Datelike, NaiveDate};
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+use std::io::{Cursor, Read, Write};
+use zip::write::SimpleFileOptions;
+
+use crate::domain::cost_table::{
+    row_to_record, Amount, ColumnLayout, CostRow, CostTable, CostTableCodec, CostTableError,
+    CostTableFormat, COST_TABLE_COLUMNS,
+};
+
+/// Cost tables as Excel workbooks (.xlsx)
+///
+/// Writes the package parts directly: a "Costs" sheet with real dates and
+/// numbers so formulas work on them, and a "Totals" sheet per category and
+/// currency. Import reads the first sheet, with shared or inline strings,
+/// and converts date serials and numbers back.
+pub struct XlsxCostTableCodec;
+
+const FORMAT: CostTableFormat = CostTableFormat::Xlsx;
+
+const NS_MAIN: &str = "http://schemas.openxmlformats.org/spreadsheetml/2006/main";
+const NS_R: &str = "http://schemas.openxmlformats.org/officeDocument/2006/relationships";
+const REL_TYPE: &str = "http://schemas.openxmlformats.org/officeDocument/2006/relationships";
+
+/// Cell styles defined in `styles.xml`
+const STYLE_DATE: u32 = 1;
+const STYLE_AMOUNT: u32 = 2;
+const STYLE_HEADER: u32 = 3;
+
+/// Days from 0001-01-01 to 1899-12-30, day 0 of Excel's 1900 date system
+/// once its 1900 leap year bug is allowed for
+const EXCEL_EPOCH_DAYS_FROM_CE: i32 = 693_594;
+
+/// A cell value as read from a sheet
+enum Cell {
+    Text(String),
+    Number(f64),
+}
+
+impl CostTableCodec for XlsxCostTableCodec {
+    fn format(&self) -> CostTableFormat {
+        FORMAT
+    }
+
+    fn encode(&self, table: &CostTable) -> Result<Vec<u8>, CostTableError> {
+        let parts = [
+            ("[Content_Types].xml", content_types()),
+            ("_rels/.rels", package_rels()),
+            ("xl/workbook.xml", workbook()),
+            ("xl/_rels/workbook.xml.rels", workbook_rels()),
+            ("xl/styles.xml", styles()),
+            ("xl/worksheets/sheet1.xml", costs_sheet(table)),
+            ("xl/worksheets/sheet2.xml", totals_sheet(table)),
+        ];
+
+        let failed = |e: zip::result::ZipError| CostTableError::invalid_file(FORMAT.as_str(), e);
+        let mut buffer = Cursor::new(Vec::new());
+        let mut zip = zip::ZipWriter::new(&mut buffer);
+        let options =
+            SimpleFileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+        for (name, xml) in parts {
+            zip.start_file(name, options).map_err(failed)?;
+            zip.write_all(xml.as_bytes())
+                .map_err(|e| CostTableError::invalid_file(FORMAT.as_str(), e))?;
+        }
+        zip.finish().map_err(failed)?;
+        Ok(buffer.into_inner())
+    }
+
+    fn decode(&self, bytes: &[u8]) -> Result<Vec<CostRow>, CostTableError> {
+        let mut archive = zip::ZipArchive::new(Cursor::new(bytes))
+            .map_err(|e| CostTableError::invalid_file(FORMAT.as_str(), e))?;
+        let shared_strings = match read_part(&mut archive, "xl/sharedStrings.xml")? {
+            Some(xml) => parse_shared_strings(&xml)?,
+            None => Vec::new(),
+        };
+        let sheet_path = first_sheet_path(&mut archive)?;
+        let sheet = read_part(&mut archive, &sheet_path)?.ok_or_else(|| {
+            CostTableError::invalid_file(FORMAT.as_str(), "the workbook has no sheets")
+        })?;
+        let table = parse_sheet(&sheet, &shared_strings)?;
+
+        let mut rows = table.into_iter();
+        let Some((_, header)) = rows.next() else {
+            return Ok(Vec::new());
+        };
+        let header: Vec<String> = header.into_iter().map(text_of).collect();
+        let layout = ColumnLayout::from_header(&header, FORMAT)?;
+        let date_column = layout.index_of("date");
+        let amount_column = layout.index_of("amount");
+
+        let mut costs = Vec::new();
+        for (number, cells) in rows {
+            let mut texts = Vec::with_capacity(cells.len());
+            for (index, cell) in cells.into_iter().enumerate() {
+                let text = match cell {
+                    Cell::Number(serial) if Some(index) == date_column => date_from_serial(serial)
+                        .ok_or_else(|| {
+                            CostTableError::row(
+                                number,
+                                CostTableError::InvalidDate(serial.to_string()),
+                            )
+                        })?
+                        .to_string(),
+                    Cell::Number(value) if Some(index) == amount_column => Amount::from_f64(value)
+                        .map_err(|e| CostTableError::row(number, e))?
+                        .to_string(),
+                    cell => text_of(cell),
+                };
+                texts.push(text);
+            }
+            if let Some(cost) = layout.read_row(&texts, number)? {
+                costs.push(cost);
+            }
+        }
+        Ok(costs)
+    }
+}
+
+fn text_of(cell: Cell) -> String {
+    match cell {
+        Cell::Text(text) => text,
+        Cell::Number(value) => value.to_string(),
+    }
+}
+
+fn date_from_serial(serial: f64) -> Option<NaiveDate> {
+    let days = i32::try_from(serial.floor() as i64).ok()?;
+    NaiveDate::from_num_days_from_ce_opt(EXCEL_EPOCH_DAYS_FROM_CE.checked_add(days)?)
+}
+
+fn date_serial(date: NaiveDate) -> i32 {
+    date.num_days_from_ce() - EXCEL_EPOCH_DAYS_FROM_CE
+}
+
+fn read_part(
+    archive: &mut zip::ZipArchive<Cursor<&[u8]>>,
+    name: &str,
+) -> Result<Option<String>, CostTableError> {
+    let mut file = match archive.by_name(name) {
+        Ok(file) => file,
+        Err(zip::result::ZipError::FileNotFound) => return Ok(None),
+        Err(e) => return Err(CostTableError::invalid_file(FORMAT.as_str(), e)),
+    };
+    let mut xml = String::new();
+    file.read_to_string(&mut xml)
+        .map_err(|e| CostTableError::invalid_file(FORMAT.as_str(), e))?;
+    Ok(Some(xml))
+}
+
+/// Value of an attribute by qualified name
+fn attr(e: &BytesStart<'_>, name: &[u8]) -> Option<String> {
+    e.try_get_attribute(name)
+        .ok()
+        .flatten()
+        .and_then(|a| a.unescape_value().ok().map(|v| v.into_owned()))
+}
+
+/// Path of the first sheet in workbook order
+///
+/// Falls back to `sheet1.xml` for packages without relationships.
+fn first_sheet_path(
+    archive: &mut zip::ZipArchive<Cursor<&[u8]>>,
+) -> Result<String, CostTableError> {
+    let fallback = "xl/worksheets/sheet1.xml".to_string();
+    let (Some(workbook), Some(rels)) = (
+        read_part(archive, "xl/workbook.xml")?,
+        read_part(archive, "xl/_rels/workbook.xml.rels")?,
+    ) else {
+        return Ok(fallback);
+    };
+
+    let mut first_id = None;
+    for_each_start(&workbook, |e| {
+        if first_id.is_none() && e.name().as_ref() == b"sheet" {
+            first_id = attr(e, b"r:id");
+        }
+    })?;
+    let Some(first_id) = first_id else {
+        return Ok(fallback);
+    };
+
+    let mut target = None;
+    for_each_start(&rels, |e| {
+        if e.name().as_ref() == b"Relationship" && attr(e, b"Id").as_ref() == Some(&first_id) {
+            target = attr(e, b"Target");
+        }
+    })?;
+    Ok(match target {
+        Some(target) => match target.strip_prefix('/') {
+            Some(absolute) => absolute.to_string(),
+            None => format!("xl/{}", target),
+        },
+        None => fallback,
+    })
+}
+
+fn for_each_start(
+    xml: &str,
+    mut handle: impl FnMut(&BytesStart<'_>),
+) -> Result<(), CostTableError> {
+    let mut reader = Reader::from_str(xml);
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(e)) | Ok(Event::Empty(e)) => handle(&e),
+            Ok(Event::Eof) => return Ok(()),
+            Ok(_) => {}
+            Err(e) => return Err(CostTableError::invalid_file(FORMAT.as_str(), e)),
+        }
+    }
+}
+
+/// Texts of the shared string table, joining rich text runs
+fn parse_shared_strings(xml: &str) -> Result<Vec<String>, CostTableError> {
+    let mut reader = Reader::from_str(xml);
+    let mut strings = Vec::new();
+    let mut current = String::new();
+    let mut in_text = false;
+    // Phonetic hints repeat the text in another script
+    let mut in_phonetic = false;
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(e)) => match e.name().as_ref() {
+                b"si" => current.clear(),
+                b"t" => in_text = !in_phonetic,
+                b"rPh" => in_phonetic = true,
+                _ => {}
+            },
+            Ok(Event::Empty(e)) if e.name().as_ref() == b"si" => strings.push(String::new()),
+            Ok(Event::Text(t)) if in_text => current.push_str(
+                &t.unescape()
+                    .map_err(|e| CostTableError::invalid_file(FORMAT.as_str(), e))?,
+            ),
+            Ok(Event::End(e)) => match e.name().as_ref() {
+                b"si" => strings.push(std::mem::take(&mut current)),
+                b"t" => in_text = false,
+                b"rPh" => in_phonetic = false,
+                _ => {}
+            },
+            Ok(Event::Eof) => return Ok(strings),
+            Ok(_) => {}
+            Err(e) => return Err(CostTableError::invalid_file(FORMAT.as_str(), e)),
+        }
+    }
+}
+
+/// Zero-based column of a cell reference such as "AB12"
+fn column_index(reference: &str) -> Option<usize> {
+    let letters: Vec<u8> = reference
+        .bytes()
+        .take_while(u8::is_ascii_alphabetic)
+        .collect();
+    if letters.is_empty() {
+        return None;
+    }
+    let number = letters.iter().fold(0usize, |n, letter| {
+        n * 26 + (letter.to_ascii_uppercase() - b'A') as usize + 1
+    });
+    Some(number - 1)
+}
+
+/// Column letters of a zero-based column index
+fn column_name(mut index: usize) -> String {
+    let mut letters = Vec::new();
+    loop {
+        letters.push(char::from(b'A' + (index % 26) as u8));
+        if index < 26 {
+            break;
+        }
+        index = index / 26 - 1;
+    }
+    letters.iter().rev().collect()
+}
+
+/// Rows of a sheet with their 1-based row numbers, cells placed by column
+fn parse_sheet(
+    xml: &str,
+    shared_strings: &[String],
+) -> Result<Vec<(usize, Vec<Cell>)>, CostTableError> {
+    let invalid = |e: &dyn std::fmt::Display| CostTableError::invalid_file(FORMAT.as_str(), e);
+    let mut reader = Reader::from_str(xml);
+    let mut rows: Vec<(usize, Vec<Cell>)> = Vec::new();
+
+    let mut cell_column = 0;
+    let mut cell_type = String::new();
+    let mut value = String::new();
+    let mut in_value = false;
+    let mut in_cell = false;
+
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(e)) | Ok(Event::Empty(e)) => match e.name().as_ref() {
+                b"row" => {
+                    let number = attr(&e, b"r")
+                        .and_then(|r| r.parse().ok())
+                        .unwrap_or_else(|| rows.last().map_or(1, |(n, _)| n + 1));
+                    rows.push((number, Vec::new()));
+                }
+                b"c" => {
+                    let cells = rows.last().map_or(0, |(_, cells)| cells.len());
+                    cell_column = attr(&e, b"r")
+                        .and_then(|r| column_index(&r))
+                        .unwrap_or(cells);
+                    cell_type = attr(&e, b"t").unwrap_or_default();
+                    value.clear();
+                    in_cell = true;
+                }
+                b"v" | b"t" if in_cell => in_value = true,
+                _ => {}
+            },
+            Ok(Event::Text(t)) if in_value => {
+                value.push_str(&t.unescape().map_err(|e| invalid(&e))?);
+            }
+            Ok(Event::End(e)) => match e.name().as_ref() {
+                b"v" | b"t" => in_value = false,
+                b"c" => {
+                    in_cell = false;
+                    let cell = match cell_type.as_str() {
+                        "s" => Cell::Text(
+                            value
+                                .trim()
+                                .parse::<usize>()
+                                .ok()
+                                .and_then(|i| shared_strings.get(i))
+                                .cloned()
+                                .ok_or_else(|| invalid(&"a shared string is missing"))?,
+                        ),
+                        "inlineStr" | "str" | "e" => Cell::Text(value.clone()),
+                        "b" => Cell::Text(
+                            if value.trim() == "1" { "TRUE" } else { "FALSE" }.to_string(),
+                        ),
+                        _ if value.trim().is_empty() => Cell::Text(String::new()),
+                        _ => Cell::Number(value.trim().parse().map_err(|_| {
+                            invalid(&format!("'{}' is not a number", value.trim()))
+                        })?),
+                    };
+                    if let Some((_, cells)) = rows.last_mut() {
+                        while cells.len() < cell_column {
+                            cells.push(Cell::Text(String::new()));
+                        }
+                        if cells.len() == cell_column {
+                            cells.push(cell);
+                        }
+                    }
+                }
+                _ => {}
+            },
+            Ok(Event::Eof) => return Ok(rows),
+            Ok(_) => {}
+            Err(e) => return Err(invalid(&e)),
+        }
+    }
+}
+
+fn content_types() -> String {
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Types xmlns="http://schemas.openxmlformats.org/package/2006/content-types"><Default Extension="rels" ContentType="application/vnd.openxmlformats-package.relationships+xml"/><Default Extension="xml" ContentType="application/xml"/><Override PartName="/xl/workbook.xml" ContentType="application/vnd.openxmlformats-officedocument.spreadsheetml.sheet.main+xml"/><Override PartName="/xl/styles.xml" ContentType="application/vnd.openxmlformats-officedocument.spreadsheetml.styles+xml"/>{}{}</Types>"#,
+        sheet_override(1),
+        sheet_override(2)
+    )
+}
+
+fn sheet_override(number: usize) -> String {
+    format!(
+        r#"<Override PartName="/xl/worksheets/sheet{}.xml" ContentType="application/vnd.openxmlformats-officedocument.spreadsheetml.worksheet+xml"/>"#,
+        number
+    )
+}
+
+fn package_rels() -> String {
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships"><Relationship Id="rId1" Type="{}/officeDocument" Target="xl/workbook.xml"/></Relationships>"#,
+        REL_TYPE
+    )
+}
+
+fn workbook() -> String {
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<workbook xmlns="{}" xmlns:r="{}"><sheets><sheet name="Costs" sheetId="1" r:id="rId1"/><sheet name="Totals" sheetId="2" r:id="rId2"/></sheets></workbook>"#,
+        NS_MAIN, NS_R
+    )
+}
+
+fn workbook_rels() -> String {
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships"><Relationship Id="rId1" Type="{0}/worksheet" Target="worksheets/sheet1.xml"/><Relationship Id="rId2" Type="{0}/worksheet" Target="worksheets/sheet2.xml"/><Relationship Id="rId3" Type="{0}/styles" Target="styles.xml"/></Relationships>"#,
+        REL_TYPE
+    )
+}
+
+/// Default, date, amount and header cell formats, in `STYLE_*` order
+fn styles() -> String {
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<styleSheet xmlns="{}"><numFmts count="1"><numFmt numFmtId="164" formatCode="yyyy-mm-dd"/></numFmts><fonts count="2"><font><sz val="11"/><name val="Calibri"/></font><font><b/><sz val="11"/><name val="Calibri"/></font></fonts><fills count="2"><fill><patternFill patternType="none"/></fill><fill><patternFill patternType="gray125"/></fill></fills><borders count="1"><border><left/><right/><top/><bottom/><diagonal/></border></borders><cellStyleXfs count="1"><xf numFmtId="0" fontId="0" fillId="0" borderId="0"/></cellStyleXfs><cellXfs count="4"><xf numFmtId="0" fontId="0" fillId="0" borderId="0" xfId="0"/><xf numFmtId="164" fontId="0" fillId="0" borderId="0" xfId="0" applyNumberFormat="1"/><xf numFmtId="4" fontId="0" fillId="0" borderId="0" xfId="0" applyNumberFormat="1"/><xf numFmtId="0" fontId="1" fillId="0" borderId="0" xfId="0" applyFont="1"/></cellXfs></styleSheet>"#,
+        NS_MAIN
+    )
+}
+
+/// Rows of a sheet being written
+#[derive(Default)]
+struct SheetBuilder {
+    rows: String,
+    count: usize,
+}
+
+impl SheetBuilder {
+    fn row(&mut self, cells: impl IntoIterator<Item = String>) {
+        self.count += 1;
+        self.rows.push_str(&format!("<row r=\"{}\">", self.count));
+        self.rows.extend(cells);
+        self.rows.push_str("</row>");
+    }
+
+    fn text(&self, column: usize, text: &str, style: u32) -> String {
+        format!(
+            r#"<c r="{}{}" t="inlineStr"{}><is><t xml:space="preserve">{}</t></is></c>"#,
+            column_name(column),
+            self.count + 1,
+            style_attr(style),
+            escape(text)
+        )
+    }
+
+    fn number(&self, column: usize, value: impl std::fmt::Display, style: u32) -> String {
+        format!(
+            r#"<c r="{}{}"{}><v>{}</v></c>"#,
+            column_name(column),
+            self.count + 1,
+            style_attr(style),
+            value
+        )
+    }
+
+    fn header(&mut self, labels: &[&str]) {
+        let cells: Vec<String> = labels
+            .iter()
+            .enumerate()
+            .map(|(column, label)| self.text(column, label, STYLE_HEADER))
+            .collect();
+        self.row(cells);
+    }
+
+    fn finish(self, column_widths: &[u32]) -> String {
+        let columns: String = column_widths
+            .iter()
+            .enumerate()
+            .map(|(i, width)| {
+                format!(
+                    r#"<col min="{0}" max="{0}" width="{1}" customWidth="1"/>"#,
+                    i + 1,
+                    width
+                )
+            })
+            .collect();
+        format!(
+            r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<worksheet xmlns="{}"><sheetViews><sheetView workbookViewId="0"><pane ySplit="1" topLeftCell="A2" activePane="bottomLeft" state="frozen"/></sheetView></sheetViews><cols>{}</cols><sheetData>{}</sheetData></worksheet>"#,
+            NS_MAIN, columns, self.rows
+        )
+    }
+}
+
+fn style_attr(style: u32) -> String {
+    if style == 0 {
+        String::new()
+    } else {
+        format!(" s=\"{}\"", style)
+    }
+}
+
+fn costs_sheet(table: &CostTable) -> String {
+    let mut sheet = SheetBuilder::default();
+    sheet.header(&COST_TABLE_COLUMNS);
+    for row in table.rows() {
+        let record = row_to_record(row);
+        let cells: Vec<String> = record
+            .iter()
+            .enumerate()
+            .filter(|(_, value)| !value.is_empty())
+            .map(|(column, value)| match COST_TABLE_COLUMNS[column] {
+                "date" => sheet.number(column, date_serial(row.date()), STYLE_DATE),
+                "amount" => sheet.number(column, row.amount(), STYLE_AMOUNT),
+                "source_page" => sheet.number(column, value, 0),
+                _ => sheet.text(column, value, 0),
+            })
+            .collect();
+        sheet.row(cells);
+    }
+    sheet.finish(&[12, 40, 12, 14, 10, 40, 40, 12])
+}
+
+fn totals_sheet(table: &CostTable) -> String {
+    let mut sheet = SheetBuilder::default();
+    sheet.header(&["category", "currency", "total", "rows"]);
+    for total in table.totals().iter().chain(table.grand_totals().iter()) {
+        let category = total.category.map_or("all", |c| c.as_str());
+        let cells = vec![
+            sheet.text(0, category, 0),
+            sheet.text(1, total.currency.code(), 0),
+            sheet.number(2, total.amount, STYLE_AMOUNT),
+            sheet.number(3, total.row_count, 0),
+        ];
+        sheet.row(cells);
+    }
+    sheet.finish(&[14, 10, 14, 8])
+}
+
+fn escape(text: &str) -> String {
+    let mut output = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => output.push_str("&amp;"),
+            '<' => output.push_str("&lt;"),
+            '>' => output.push_str("&gt;"),
+            '"' => output.push_str("&quot;"),
+            // Control characters are not allowed in XML 1.0
+            c if c.is_control() && c != '\t' && c != '\n' => {}
+            _ => output.push(c),
+        }
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::cost_table::{CostCategory, Currency};
+
+    fn create_test_table() -> CostTable {
+        let row = |day, description: &str, cents, currency: &str| {
+            CostRow::new(
+                NaiveDate::from_ymd_opt(2024, 5, day).unwrap(),
+                description,
+                CostCategory::Direct,
+                Amount::from_cents(cents),
+                Currency::parse(currency).unwrap(),
+            )
+            .unwrap()
+        };
+        CostTable::new("Damages", None).unwrap().with_rows(vec![
+            row(1, "Plumber <emergency> & parts", 45_075, "EUR"),
+            row(2, "Hotel", 10_000, "EUR"),
+        ])
+    }
+
+    #[test]
+    fn test_round_trip_with_totals_sheet() {
+        let table = create_test_table();
+        let bytes = XlsxCostTableCodec.encode(&table).unwrap();
+        assert_eq!(XlsxCostTableCodec.decode(&bytes).unwrap(), table.rows());
+
+        let mut archive = zip::ZipArchive::new(Cursor::new(bytes.as_slice())).unwrap();
+        let costs = read_part(&mut archive, "xl/worksheets/sheet1.xml")
+            .unwrap()
+            .unwrap();
+        assert!(costs.contains(r#"<c r="A2" s="1"><v>45413</v></c>"#));
+        let totals = read_part(&mut archive, "xl/worksheets/sheet2.xml")
+            .unwrap()
+            .unwrap();
+        assert!(totals.contains("<v>550.75</v>"));
+    }
+
+    #[test]
+    fn test_reads_shared_strings_and_sparse_rows() {
+        let sheet = r#"<worksheet><sheetData>
+            <row r="1"><c r="A1" t="s"><v>0</v></c><c r="B1" t="s"><v>1</v></c><c r="C1" t="inlineStr"><is><t>Category</t></is></c><c r="D1" t="s"><v>2</v></c><c r="E1" t="s"><v>3</v></c></row>
+            <row r="3"><c r="A3"><v>45413.5</v></c><c r="B3" t="s"><v>4</v></c><c r="C3" t="str"><v>other</v></c><c r="D3"><v>0.30000000000000004</v></c><c r="E3" t="inlineStr"><is><t>CHF</t></is></c></row>
+        </sheetData></worksheet>"#;
+        let strings = r#"<sst><si><t>Date</t></si><si><r><t>Descrip</t></r><r><t>tion</t></r></si><si><t>Amount</t></si><si><t>Currency</t></si><si><t>Taxi</t><rPh><t>x</t></rPh></si></sst>"#;
+
+        let mut buffer = Cursor::new(Vec::new());
+        let mut zip = zip::ZipWriter::new(&mut buffer);
+        for (name, xml) in [
+            ("xl/worksheets/sheet1.xml", sheet),
+            ("xl/sharedStrings.xml", strings),
+        ] {
+            zip.start_file(name, SimpleFileOptions::default()).unwrap();
+            zip.write_all(xml.as_bytes()).unwrap();
+        }
+        zip.finish().unwrap();
+
+        let rows = XlsxCostTableCodec.decode(&buffer.into_inner()).unwrap();
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].description(), "Taxi");
+        assert_eq!(rows[0].date(), NaiveDate::from_ymd_opt(2024, 5, 1).unwrap());
+        assert_eq!(rows[0].amount(), Amount::from_cents(30));
+        assert_eq!(rows[0].category(), CostCategory::Other);
+
+        assert!(XlsxCostTableCodec.decode(b"not a zip").is_err());
+        assert_eq!(column_name(27), "AB");
+        assert_eq!(column_index("AB12"), Some(27));
+    }
+}
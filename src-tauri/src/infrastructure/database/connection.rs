@@ -134,6 +134,394 @@ impl DatabaseConnection {
                 CREATE INDEX IF NOT EXISTS idx_projects_created_at ON projects(created_at);
             "#,
             ),
+            // Per-project file classification settings
+            (
+                3,
+                "create_file_category_configs_table",
+                r#"
+                CREATE TABLE IF NOT EXISTS file_category_configs (
+                    project_uuid TEXT PRIMARY KEY,
+                    config_json TEXT NOT NULL,
+                    updated_at DATETIME DEFAULT CURRENT_TIMESTAMP
+                );
+            "#,
+            ),
+            // Background job records
+            (
+                4,
+                "create_jobs_table",
+                r#"
+                CREATE TABLE IF NOT EXISTS jobs (
+                    id TEXT PRIMARY KEY,
+                    kind TEXT NOT NULL,
+                    project_uuid TEXT,
+                    params_json TEXT NOT NULL,
+                    status TEXT NOT NULL CHECK(status IN ('queued', 'running', 'succeeded', 'failed', 'cancelled')),
+                    idempotent BOOLEAN NOT NULL DEFAULT 0,
+                    progress_current INTEGER NOT NULL DEFAULT 0,
+                    progress_total INTEGER,
+                    progress_message TEXT,
+                    result_json TEXT,
+                    error TEXT,
+                    attempts INTEGER NOT NULL DEFAULT 0,
+                    created_at DATETIME NOT NULL,
+                    started_at DATETIME,
+                    finished_at DATETIME
+                );
+                CREATE INDEX IF NOT EXISTS idx_jobs_status ON jobs(status);
+                CREATE INDEX IF NOT EXISTS idx_jobs_project ON jobs(project_uuid);
+                CREATE INDEX IF NOT EXISTS idx_jobs_created_at ON jobs(created_at);
+            "#,
+            ),
+            // Cached file content hashes
+            (
+                5,
+                "create_file_hashes_table",
+                r#"
+                CREATE TABLE IF NOT EXISTS file_hashes (
+                    path TEXT NOT NULL,
+                    algorithm TEXT NOT NULL CHECK(algorithm IN ('blake3', 'sha256')),
+                    size INTEGER NOT NULL,
+                    modified_ns INTEGER NOT NULL,
+                    digest TEXT NOT NULL,
+                    computed_at DATETIME NOT NULL,
+                    PRIMARY KEY (path, algorithm)
+                );
+                CREATE INDEX IF NOT EXISTS idx_file_hashes_digest ON file_hashes(algorithm, digest);
+            "#,
+            ),
+            // Stable document identities for source files
+            (
+                6,
+                "create_documents_table",
+                r#"
+                CREATE TABLE IF NOT EXISTS documents (
+                    id TEXT PRIMARY KEY,
+                    project_uuid TEXT NOT NULL,
+                    path TEXT NOT NULL,
+                    content_hash TEXT NOT NULL,
+                    size INTEGER NOT NULL,
+                    modified_ns INTEGER NOT NULL,
+                    missing BOOLEAN NOT NULL DEFAULT 0,
+                    created_at DATETIME NOT NULL,
+                    updated_at DATETIME NOT NULL
+                );
+                CREATE INDEX IF NOT EXISTS idx_documents_project_path ON documents(project_uuid, path);
+                CREATE INDEX IF NOT EXISTS idx_documents_project_hash ON documents(project_uuid, content_hash);
+            "#,
+            ),
+            // Source folder manifest snapshots
+            (
+                7,
+                "create_manifest_snapshots_tables",
+                r#"
+                CREATE TABLE IF NOT EXISTS manifest_snapshots (
+                    id TEXT PRIMARY KEY,
+                    project_uuid TEXT NOT NULL,
+                    label TEXT CHECK(length(label) <= 255),
+                    algorithm TEXT NOT NULL CHECK(algorithm IN ('blake3', 'sha256')),
+                    file_count INTEGER NOT NULL,
+                    total_size INTEGER NOT NULL,
+                    created_at DATETIME NOT NULL
+                );
+                CREATE TABLE IF NOT EXISTS manifest_entries (
+                    snapshot_id TEXT NOT NULL REFERENCES manifest_snapshots(id) ON DELETE CASCADE,
+                    path TEXT NOT NULL,
+                    size INTEGER NOT NULL,
+                    modified_ns INTEGER NOT NULL,
+                    digest TEXT NOT NULL,
+                    PRIMARY KEY (snapshot_id, path)
+                );
+                CREATE INDEX IF NOT EXISTS idx_manifest_snapshots_project ON manifest_snapshots(project_uuid, created_at);
+            "#,
+            ),
+            // Reports folder settings and the report index
+            (
+                8,
+                "create_reports_tables",
+                r#"
+                ALTER TABLE projects ADD COLUMN reports_folder TEXT;
+                ALTER TABLE projects ADD COLUMN immutable_source BOOLEAN NOT NULL DEFAULT 0;
+                CREATE TABLE IF NOT EXISTS reports (
+                    id TEXT PRIMARY KEY,
+                    project_uuid TEXT NOT NULL,
+                    title TEXT NOT NULL CHECK(length(title) > 0 AND length(title) <= 255),
+                    category TEXT NOT NULL CHECK(category IN ('findings', 'analysis_summary', 'deliverable', 'client_report')),
+                    status TEXT NOT NULL CHECK(status IN ('draft', 'final')),
+                    path TEXT NOT NULL,
+                    created_at DATETIME NOT NULL,
+                    updated_at DATETIME NOT NULL
+                );
+                CREATE UNIQUE INDEX IF NOT EXISTS idx_reports_project_path ON reports(project_uuid, path);
+                CREATE TABLE IF NOT EXISTS report_links (
+                    report_id TEXT NOT NULL REFERENCES reports(id) ON DELETE CASCADE,
+                    kind TEXT NOT NULL CHECK(kind IN ('document', 'derivative')),
+                    target TEXT NOT NULL,
+                    position INTEGER NOT NULL,
+                    PRIMARY KEY (report_id, kind, target)
+                );
+                CREATE INDEX IF NOT EXISTS idx_report_links_target ON report_links(kind, target);
+            "#,
+            ),
+            // Citations from reports to passages of source documents
+            (
+                9,
+                "create_citations_table",
+                r#"
+                CREATE TABLE IF NOT EXISTS citations (
+                    report_id TEXT NOT NULL REFERENCES reports(id) ON DELETE CASCADE,
+                    position INTEGER NOT NULL,
+                    document_id TEXT,
+                    path TEXT NOT NULL,
+                    locator TEXT NOT NULL CHECK(locator IN ('file', 'page', 'char_range', 'position')),
+                    page INTEGER,
+                    range_start INTEGER,
+                    range_end INTEGER,
+                    PRIMARY KEY (report_id, position)
+                );
+                CREATE INDEX IF NOT EXISTS idx_citations_document ON citations(document_id);
+            "#,
+            ),
+            // Steps deriving document family members from each other
+            (
+                10,
+                "create_derivation_steps_table",
+                r#"
+                CREATE TABLE IF NOT EXISTS derivation_steps (
+                    document_id TEXT NOT NULL,
+                    project_uuid TEXT NOT NULL,
+                    position INTEGER NOT NULL,
+                    parent TEXT NOT NULL,
+                    child TEXT NOT NULL CHECK(child != 'original' AND child != parent),
+                    operation TEXT NOT NULL,
+                    parameters TEXT NOT NULL DEFAULT '{}',
+                    tool_version TEXT NOT NULL,
+                    produced_at TEXT NOT NULL,
+                    PRIMARY KEY (document_id, parent, child)
+                );
+                CREATE INDEX IF NOT EXISTS idx_derivation_steps_project ON derivation_steps(project_uuid);
+            "#,
+            ),
+            // Probed audio and video properties of media documents
+            (
+                11,
+                "create_media_metadata_table",
+                r#"
+                CREATE TABLE IF NOT EXISTS media_metadata (
+                    document_id TEXT PRIMARY KEY,
+                    project_uuid TEXT NOT NULL,
+                    kind TEXT NOT NULL CHECK(kind IN ('audio', 'video')),
+                    container TEXT NOT NULL,
+                    duration_ms INTEGER,
+                    audio_codec TEXT,
+                    video_codec TEXT,
+                    bitrate_kbps INTEGER,
+                    sample_rate INTEGER,
+                    channels INTEGER,
+                    width INTEGER,
+                    height INTEGER,
+                    frame_rate REAL,
+                    title TEXT,
+                    artist TEXT,
+                    album TEXT,
+                    genre TEXT,
+                    year TEXT,
+                    tags TEXT NOT NULL DEFAULT '{}',
+                    method TEXT NOT NULL,
+                    content_hash TEXT NOT NULL,
+                    probed_at TEXT NOT NULL
+                );
+                CREATE INDEX IF NOT EXISTS idx_media_metadata_kind ON media_metadata(project_uuid, kind);
+                CREATE INDEX IF NOT EXISTS idx_media_metadata_duration ON media_metadata(project_uuid, duration_ms);
+            "#,
+            ),
+            // Saved searches shown as virtual folders, with the results
+            // seen when last viewed and those found since
+            (
+                12,
+                "create_saved_searches_table",
+                r#"
+                CREATE TABLE IF NOT EXISTS saved_searches (
+                    id TEXT PRIMARY KEY,
+                    project_uuid TEXT NOT NULL,
+                    name TEXT NOT NULL,
+                    query TEXT NOT NULL,
+                    track_new INTEGER NOT NULL DEFAULT 0,
+                    seen TEXT NOT NULL DEFAULT '[]',
+                    new_results TEXT NOT NULL DEFAULT '[]',
+                    last_viewed_at TEXT,
+                    checked_at TEXT,
+                    created_at TEXT NOT NULL,
+                    updated_at TEXT NOT NULL,
+                    UNIQUE(project_uuid, name)
+                );
+            "#,
+            ),
+            // Hierarchical categories and tags, and their assignment to
+            // documents by stable id
+            (
+                13,
+                "create_categories_tables",
+                r#"
+                CREATE TABLE IF NOT EXISTS categories (
+                    id TEXT PRIMARY KEY,
+                    project_uuid TEXT NOT NULL,
+                    kind TEXT NOT NULL CHECK(kind IN ('category', 'tag')),
+                    name TEXT NOT NULL,
+                    parent_id TEXT,
+                    color TEXT,
+                    shortcut TEXT,
+                    created_at TEXT NOT NULL,
+                    updated_at TEXT NOT NULL
+                );
+                CREATE INDEX IF NOT EXISTS idx_categories_project ON categories(project_uuid);
+                CREATE UNIQUE INDEX IF NOT EXISTS idx_categories_sibling_name
+                    ON categories(project_uuid, kind, IFNULL(parent_id, ''), name COLLATE NOCASE);
+                CREATE UNIQUE INDEX IF NOT EXISTS idx_categories_shortcut
+                    ON categories(project_uuid, shortcut) WHERE shortcut IS NOT NULL;
+
+                CREATE TABLE IF NOT EXISTS document_categories (
+                    document_id TEXT NOT NULL,
+                    category_id TEXT NOT NULL,
+                    assigned_at TEXT NOT NULL,
+                    PRIMARY KEY (document_id, category_id)
+                );
+                CREATE INDEX IF NOT EXISTS idx_document_categories_category
+                    ON document_categories(category_id);
+            "#,
+            ),
+            // Highlights and comments on documents, anchored to a page of the
+            // original or a range of the extraction
+            (
+                14,
+                "create_annotations_table",
+                r#"
+                CREATE TABLE IF NOT EXISTS annotations (
+                    id TEXT PRIMARY KEY,
+                    project_uuid TEXT NOT NULL,
+                    document_id TEXT NOT NULL,
+                    anchor TEXT NOT NULL,
+                    quote TEXT NOT NULL,
+                    color TEXT,
+                    body TEXT NOT NULL,
+                    author TEXT NOT NULL,
+                    status TEXT NOT NULL CHECK(status IN ('attached', 'reattached', 'orphaned')),
+                    created_at TEXT NOT NULL,
+                    updated_at TEXT NOT NULL
+                );
+                CREATE INDEX IF NOT EXISTS idx_annotations_document ON annotations(document_id);
+                CREATE INDEX IF NOT EXISTS idx_annotations_project ON annotations(project_uuid);
+            "#,
+            ),
+            // Coding schemas per project, and every coding decision made on a
+            // document; the latest decision is its current coding
+            (
+                15,
+                "create_coding_tables",
+                r#"
+                CREATE TABLE IF NOT EXISTS coding_schemas (
+                    project_uuid TEXT PRIMARY KEY,
+                    fields TEXT NOT NULL,
+                    updated_at TEXT NOT NULL
+                );
+                CREATE TABLE IF NOT EXISTS coding_decisions (
+                    id TEXT PRIMARY KEY,
+                    project_uuid TEXT NOT NULL,
+                    document_id TEXT NOT NULL,
+                    "values" TEXT NOT NULL,
+                    reviewer TEXT NOT NULL,
+                    decided_at TEXT NOT NULL
+                );
+                CREATE INDEX IF NOT EXISTS idx_coding_decisions_document
+                    ON coding_decisions(document_id, decided_at);
+                CREATE INDEX IF NOT EXISTS idx_coding_decisions_project
+                    ON coding_decisions(project_uuid);
+            "#,
+            ),
+            // Local reviewer profiles, one of which may be active, and the
+            // review batches handed to them
+            (
+                16,
+                "create_review_tables",
+                r#"
+                CREATE TABLE IF NOT EXISTS reviewers (
+                    id TEXT PRIMARY KEY,
+                    name TEXT NOT NULL UNIQUE COLLATE NOCASE,
+                    active INTEGER NOT NULL DEFAULT 0,
+                    created_at TEXT NOT NULL
+                );
+                CREATE TABLE IF NOT EXISTS review_batches (
+                    id TEXT PRIMARY KEY,
+                    project_uuid TEXT NOT NULL,
+                    name TEXT NOT NULL,
+                    source TEXT NOT NULL,
+                    reviewer_id TEXT,
+                    status TEXT NOT NULL CHECK(status IN ('unassigned', 'assigned', 'closed')),
+                    document_ids TEXT NOT NULL,
+                    created_at TEXT NOT NULL,
+                    updated_at TEXT NOT NULL,
+                    closed_at TEXT
+                );
+                CREATE INDEX IF NOT EXISTS idx_review_batches_project
+                    ON review_batches(project_uuid);
+            "#,
+            ),
+            // Child documents taken out of other documents, such as email
+            // attachments
+            (
+                17,
+                "add_document_parents",
+                r#"
+                ALTER TABLE documents ADD COLUMN parent_id TEXT;
+                CREATE INDEX IF NOT EXISTS idx_documents_parent ON documents(parent_id);
+            "#,
+            ),
+            // Headers of the messages in email documents
+            (
+                18,
+                "create_email_messages_table",
+                r#"
+                CREATE TABLE IF NOT EXISTS email_messages (
+                    document_id TEXT NOT NULL,
+                    message_index INTEGER NOT NULL,
+                    project_uuid TEXT NOT NULL,
+                    content_hash TEXT NOT NULL,
+                    method TEXT NOT NULL,
+                    headers TEXT NOT NULL,
+                    message_id TEXT,
+                    sent_at TEXT,
+                    attachment_ids TEXT NOT NULL,
+                    parsed_at TEXT NOT NULL,
+                    PRIMARY KEY (document_id, message_index)
+                );
+                CREATE INDEX IF NOT EXISTS idx_email_messages_project
+                    ON email_messages(project_uuid, sent_at);
+                CREATE INDEX IF NOT EXISTS idx_email_messages_message_id
+                    ON email_messages(message_id);
+            "#,
+            ),
+            // Full-text index of derivatives and reports for search; the
+            // text rows share their rowid with the file rows
+            (
+                19,
+                "create_search_index_tables",
+                r#"
+                CREATE TABLE IF NOT EXISTS search_index (
+                    path TEXT PRIMARY KEY,
+                    project_uuid TEXT NOT NULL,
+                    kind TEXT NOT NULL,
+                    document_id TEXT,
+                    modified_at TEXT NOT NULL,
+                    written_at TEXT NOT NULL
+                );
+                CREATE INDEX IF NOT EXISTS idx_search_index_project
+                    ON search_index(project_uuid);
+                CREATE VIRTUAL TABLE IF NOT EXISTS search_text USING fts5(
+                    text,
+                    tokenize = 'unicode61 remove_diacritics 0'
+                );
+            "#,
+            ),
         ];
 
         for (version, name, sql) in migrations {
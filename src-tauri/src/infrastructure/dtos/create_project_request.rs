@@ -15,6 +15,14 @@ pub struct CreateProjectRequest {
 
     /// Optional project note/description (max 1000 characters)
     pub note: Option<String>,
+
+    /// Optional folder for reports; defaults to `_corpus_analysis` in the source folder
+    #[serde(default)]
+    pub reports_folder: Option<String>,
+
+    /// Whether the source folder must never be written to
+    #[serde(default)]
+    pub immutable_source: bool,
 }
 
 impl CreateProjectRequest {
@@ -24,6 +32,8 @@ impl CreateProjectRequest {
             name,
             source_folder,
             note,
+            reports_folder: None,
+            immutable_source: false,
         }
     }
 
@@ -75,6 +85,15 @@ impl CreateProjectRequest {
             .map(|n| n.to_string())
     }
 
+    /// Get the trimmed reports folder path (None if empty or whitespace-only)
+    pub fn get_reports_folder(&self) -> Option<String> {
+        self.reports_folder
+            .as_ref()
+            .map(|f| f.trim())
+            .filter(|f| !f.is_empty())
+            .map(|f| f.to_string())
+    }
+
     /// Convert to domain creation parameters after validation
     pub fn to_domain_params(
         &self,
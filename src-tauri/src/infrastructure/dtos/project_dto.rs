@@ -19,6 +19,10 @@ pub struct ProjectDto {
     pub note_line_count: Option<usize>,
     pub created_at: String,
     pub is_accessible: bool,
+    #[serde(default)]
+    pub reports_folder: Option<String>,
+    #[serde(default)]
+    pub immutable_source: bool,
 }
 
 impl ProjectDto {
@@ -36,6 +40,10 @@ impl ProjectDto {
             note_line_count: project.note().map(|n| n.line_count()),
             created_at: project.created_at().to_string(),
             is_accessible: project.is_source_accessible(),
+            reports_folder: project
+                .configured_reports_folder()
+                .map(|folder| folder.to_string_lossy().to_string()),
+            immutable_source: project.is_source_immutable(),
         }
     }
 
@@ -51,6 +59,8 @@ impl ProjectDto {
             note_line_count: metadata.note_line_count,
             created_at: metadata.created_at.to_string(),
             is_accessible: metadata.is_accessible,
+            reports_folder: metadata.reports_folder_path.clone(),
+            immutable_source: metadata.immutable_source,
         }
     }
 
@@ -63,6 +73,9 @@ impl ProjectDto {
             self.note.clone(),
             self.created_at.clone(),
         )
+        .map(|project| {
+            project.with_stored_reports_settings(self.reports_folder.clone(), self.immutable_source)
+        })
     }
 
     /// Get a display-friendly summary
@@ -332,6 +345,8 @@ mod tests {
             note_line_count: Some(1),
             created_at: "2023-12-01T10:30:00Z".to_string(),
             is_accessible: true,
+            reports_folder: None,
+            immutable_source: false,
         };
 
         let result = dto.to_project();
@@ -359,6 +374,8 @@ mod tests {
             note_line_count: Some(1),
             created_at: "2023-12-01T10:30:00Z".to_string(),
             is_accessible: true,
+            reports_folder: None,
+            immutable_source: false,
         };
 
         assert!(valid_dto.validate().is_ok());
@@ -448,6 +465,8 @@ mod tests {
             note_line_count: Some(2),
             created_at: "2023-12-01T10:30:00Z".to_string(),
             is_accessible: true,
+            reports_folder: None,
+            immutable_source: false,
         };
 
         assert!(dto.has_note());
@@ -474,6 +493,8 @@ mod tests {
             note_line_count: None,
             created_at: "2023-12-01T10:30:00Z".to_string(),
             is_accessible: true,
+            reports_folder: None,
+            immutable_source: false,
         };
 
         assert!(!dto.has_note());
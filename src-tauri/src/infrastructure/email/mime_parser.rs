@@ -0,0 +1,210 @@
+use chrono::DateTime;
+use mail_parser::mailbox::mbox::MessageIterator;
+use mail_parser::{Address, HeaderValue, Message, MessageParser, MimeHeaders, PartType};
+
+use crate::domain::email::{EmailAttachment, EmailError, EmailHeaders, ParsedEmail};
+
+/// Parse an Internet message (.eml, RFC 5322 with MIME)
+pub fn parse_eml(bytes: &[u8]) -> Result<ParsedEmail, EmailError> {
+    let message = MessageParser::default()
+        .parse(bytes)
+        .map(|message| to_parsed_email(&message))
+        // Any text parses as a message; one without envelope headers is not
+        .filter(|message| message.headers != EmailHeaders::default())
+        .ok_or_else(|| EmailError::invalid("EML", "no message headers found"))?;
+    Ok(message)
+}
+
+/// Parse every message of a Unix mailbox (.mbox)
+///
+/// Messages that cannot be read are skipped; the mailbox only fails when
+/// none of them can.
+pub fn parse_mbox(bytes: &[u8]) -> Result<Vec<ParsedEmail>, EmailError> {
+    let mut messages = Vec::new();
+    let mut skipped = 0;
+    for entry in MessageIterator::new(bytes) {
+        match entry
+            .ok()
+            .and_then(|entry| parse_eml(entry.contents()).ok())
+        {
+            Some(message) => messages.push(message),
+            None => skipped += 1,
+        }
+    }
+
+    if messages.is_empty() {
+        return Err(EmailError::invalid("MBOX", "no readable messages found"));
+    }
+    if skipped > 0 {
+        tracing::warn!("Skipped {} unreadable messages in a mailbox", skipped);
+    }
+    Ok(messages)
+}
+
+/// Read envelope headers from a block of raw header lines, such as the
+/// transport headers kept in Outlook messages
+pub(super) fn parse_header_block(text: &str) -> Option<EmailHeaders> {
+    let block = format!("{}\r\n\r\n", text.trim_end());
+    let message = MessageParser::default().parse_headers(block.as_bytes())?;
+    Some(headers_of(&message))
+}
+
+fn to_parsed_email(message: &Message) -> ParsedEmail {
+    let body = (0..message.text_body_count())
+        .filter_map(|index| message.body_text(index))
+        .map(|text| text.trim().to_string())
+        .filter(|text| !text.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n\n");
+
+    let attachments = message
+        .attachments()
+        .enumerate()
+        .map(|(index, part)| {
+            let content_type =
+                part.content_type()
+                    .map(|content_type| match content_type.subtype() {
+                        Some(subtype) => format!("{}/{}", content_type.ctype(), subtype),
+                        None => content_type.ctype().to_string(),
+                    });
+            let file_name = match (&part.body, part.attachment_name()) {
+                (_, Some(name)) => name.to_string(),
+                // Forwarded messages are kept as messages
+                (PartType::Message(inner), None) => format!(
+                    "{}.eml",
+                    inner.subject().unwrap_or("Forwarded message").trim()
+                ),
+                (_, None) => format!("attachment-{}", index + 1),
+            };
+            EmailAttachment {
+                file_name,
+                content_type,
+                data: part.contents().to_vec(),
+            }
+        })
+        .collect();
+
+    ParsedEmail {
+        headers: headers_of(message),
+        body,
+        attachments,
+    }
+}
+
+fn headers_of(message: &Message) -> EmailHeaders {
+    EmailHeaders {
+        from: message
+            .from()
+            .and_then(|from| addresses(from).into_iter().next()),
+        to: message.to().map(addresses).unwrap_or_default(),
+        cc: message.cc().map(addresses).unwrap_or_default(),
+        date: message
+            .date()
+            .and_then(|date| DateTime::from_timestamp(date.to_timestamp(), 0)),
+        subject: message
+            .subject()
+            .map(|subject| subject.trim().to_string())
+            .filter(|subject| !subject.is_empty()),
+        message_id: message.message_id().and_then(EmailHeaders::normalize_id),
+        in_reply_to: ids(message.in_reply_to()),
+        references: ids(message.references()),
+    }
+}
+
+/// Addresses as `Name <address>`, or whichever part is present
+fn addresses(address: &Address) -> Vec<String> {
+    address
+        .iter()
+        .filter_map(|addr| match (addr.name(), addr.address()) {
+            (Some(name), Some(address)) if !name.trim().is_empty() => {
+                Some(format!("{} <{}>", name.trim(), address))
+            }
+            (_, Some(address)) => Some(address.to_string()),
+            (Some(name), None) => Some(name.trim().to_string()),
+            (None, None) => None,
+        })
+        .collect()
+}
+
+fn ids(value: &HeaderValue) -> Vec<String> {
+    value
+        .as_text_list()
+        .unwrap_or_default()
+        .into_iter()
+        .flat_map(EmailHeaders::parse_ids)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const REPLY: &str = "From: \"Ana Silva\" <ana@example.com>\r\n\
+        To: ben@example.com, Carla <carla@example.com>\r\n\
+        Cc: legal@example.com\r\n\
+        Date: Tue, 5 Mar 2024 09:30:00 +0100\r\n\
+        Subject: Re: Offer for lot 4\r\n\
+        Message-ID: <reply-1@example.com>\r\n\
+        In-Reply-To: <offer-1@example.com>\r\n\
+        References: <start@example.com> <offer-1@example.com>\r\n\
+        MIME-Version: 1.0\r\n\
+        Content-Type: multipart/mixed; boundary=\"b1\"\r\n\
+        \r\n\
+        --b1\r\n\
+        Content-Type: text/plain; charset=utf-8\r\n\
+        \r\n\
+        We accept the offer.\r\n\
+        --b1\r\n\
+        Content-Type: application/pdf; name=\"signed offer.pdf\"\r\n\
+        Content-Disposition: attachment; filename=\"signed offer.pdf\"\r\n\
+        Content-Transfer-Encoding: base64\r\n\
+        \r\n\
+        JVBERi0xLjQK\r\n\
+        --b1--\r\n";
+
+    #[test]
+    fn test_parse_eml_headers_body_and_attachments() {
+        let message = parse_eml(REPLY.as_bytes()).unwrap();
+        let headers = &message.headers;
+
+        assert_eq!(headers.from.as_deref(), Some("Ana Silva <ana@example.com>"));
+        assert_eq!(headers.to, ["ben@example.com", "Carla <carla@example.com>"]);
+        assert_eq!(headers.cc, ["legal@example.com"]);
+        assert_eq!(
+            headers.date.unwrap().to_rfc3339(),
+            "2024-03-05T08:30:00+00:00"
+        );
+        assert_eq!(headers.subject.as_deref(), Some("Re: Offer for lot 4"));
+        assert_eq!(headers.message_id.as_deref(), Some("reply-1@example.com"));
+        assert_eq!(headers.in_reply_to, ["offer-1@example.com"]);
+        assert_eq!(
+            headers.references,
+            ["start@example.com", "offer-1@example.com"]
+        );
+        assert_eq!(message.body, "We accept the offer.");
+
+        assert_eq!(message.attachments.len(), 1);
+        let attachment = &message.attachments[0];
+        assert_eq!(attachment.file_name, "signed offer.pdf");
+        assert_eq!(attachment.content_type.as_deref(), Some("application/pdf"));
+        assert_eq!(attachment.data, b"%PDF-1.4\n");
+
+        assert!(parse_eml(b"\x00\x01 not a message").is_err());
+    }
+
+    #[test]
+    fn test_parse_mbox_messages() {
+        let mbox = format!(
+            "From ana@example.com Tue Mar  5 09:30:00 2024\r\n{}\r\n\
+             From ben@example.com Wed Mar  6 10:00:00 2024\r\n\
+             From: ben@example.com\r\nSubject: Lunch\r\n\r\nNoon?\r\n",
+            REPLY
+        );
+
+        let messages = parse_mbox(mbox.as_bytes()).unwrap();
+        assert_eq!(messages.len(), 2);
+        assert_eq!(messages[0].attachments.len(), 1);
+        assert_eq!(messages[1].headers.subject.as_deref(), Some("Lunch"));
+        assert_eq!(messages[1].body, "Noon?");
+    }
+}
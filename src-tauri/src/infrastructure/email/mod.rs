@@ -0,0 +1,40 @@
+pub mod mime_parser;
+pub mod msg_parser;
+
+use crate::domain::email::{EmailError, ParsedEmail};
+
+/// Magic bytes of OLE compound files, which Outlook .msg files are
+const OLE_MAGIC: [u8; 8] = [0xD0, 0xCF, 0x11, 0xE0, 0xA1, 0xB1, 0x1A, 0xE1];
+
+/// Reads email files: single messages (.eml, .msg) and mailboxes (.mbox)
+///
+/// The format is recognised from the contents, since exports often name
+/// mailboxes `.txt` or messages `.mbox`. Everything is pure Rust; nothing
+/// is ever fetched, so remote images and links in messages stay inert.
+pub struct EmailParser;
+
+impl EmailParser {
+    /// Versioned method name stored with parsed messages
+    pub const METHOD: &'static str = "email-parser-v1";
+
+    /// Lower-case file extensions treated as email
+    pub const EXTENSIONS: [&'static str; 3] = ["eml", "mbox", "msg"];
+
+    /// Whether files with the given extension are email files
+    pub fn supports(extension: &str) -> bool {
+        Self::EXTENSIONS
+            .iter()
+            .any(|e| e.eq_ignore_ascii_case(extension))
+    }
+
+    /// Parse all messages of a file, in file order
+    pub fn parse(bytes: &[u8]) -> Result<Vec<ParsedEmail>, EmailError> {
+        if bytes.starts_with(&OLE_MAGIC) {
+            msg_parser::parse_msg(bytes).map(|message| vec![message])
+        } else if bytes.starts_with(b"From ") {
+            mime_parser::parse_mbox(bytes)
+        } else {
+            mime_parser::parse_eml(bytes).map(|message| vec![message])
+        }
+    }
+}
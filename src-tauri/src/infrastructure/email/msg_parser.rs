@@ -0,0 +1,369 @@
+use cfb::CompoundFile;
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+use std::io::{Cursor, Read, Seek};
+
+use super::mime_parser::parse_header_block;
+use crate::domain::email::{EmailAttachment, EmailError, EmailHeaders, ParsedEmail};
+
+const FORMAT: &str = "MSG";
+
+/// MAPI property identifiers read from Outlook messages
+mod tag {
+    pub const SUBJECT: u16 = 0x0037;
+    pub const CLIENT_SUBMIT_TIME: u16 = 0x0039;
+    pub const TRANSPORT_HEADERS: u16 = 0x007D;
+    pub const SENDER_NAME: u16 = 0x0C1A;
+    pub const SENDER_EMAIL: u16 = 0x0C1F;
+    pub const RECIPIENT_TYPE: u16 = 0x0C15;
+    pub const DELIVERY_TIME: u16 = 0x0E06;
+    pub const BODY: u16 = 0x1000;
+    pub const HTML_BODY: u16 = 0x1013;
+    pub const MESSAGE_ID: u16 = 0x1035;
+    pub const REFERENCES: u16 = 0x1039;
+    pub const IN_REPLY_TO: u16 = 0x1042;
+    pub const DISPLAY_NAME: u16 = 0x3001;
+    pub const EMAIL_ADDRESS: u16 = 0x3003;
+    pub const ATTACH_DATA: u16 = 0x3701;
+    pub const ATTACH_FILENAME: u16 = 0x3704;
+    pub const ATTACH_LONG_FILENAME: u16 = 0x3707;
+    pub const ATTACH_MIME_TAG: u16 = 0x370E;
+    pub const SMTP_ADDRESS: u16 = 0x39FE;
+    pub const SENDER_SMTP_ADDRESS: u16 = 0x5D01;
+}
+
+/// Property value types as they appear in stream names
+const UNICODE: u16 = 0x001F;
+const STRING8: u16 = 0x001E;
+const BINARY: u16 = 0x0102;
+
+/// Size of the header before the fixed-size property entries: 32 bytes
+/// for the message itself, 8 for recipients and attachments
+const MESSAGE_PROPERTIES_HEADER: usize = 32;
+const CHILD_PROPERTIES_HEADER: usize = 8;
+
+/// Seconds between 1601-01-01 (FILETIME epoch) and 1970-01-01
+const FILETIME_UNIX_OFFSET: i64 = 11_644_473_600;
+
+/// Parse an Outlook message (.msg, an OLE compound file of MAPI properties)
+///
+/// Headers come from the transport headers Outlook keeps for received
+/// mail, completed from the MAPI properties for drafts and sent items.
+/// Attached Outlook items (embedded messages) have no file contents and
+/// are skipped.
+pub fn parse_msg(bytes: &[u8]) -> Result<ParsedEmail, EmailError> {
+    let mut file =
+        CompoundFile::open(Cursor::new(bytes)).map_err(|e| EmailError::invalid(FORMAT, e))?;
+    if !file.is_stream("/__properties_version1.0") {
+        return Err(EmailError::invalid(FORMAT, "not an Outlook message"));
+    }
+
+    let mut headers = string(&mut file, "", tag::TRANSPORT_HEADERS)
+        .and_then(|text| parse_header_block(&text))
+        .unwrap_or_default();
+    let properties = fixed_properties(&mut file, "", MESSAGE_PROPERTIES_HEADER);
+
+    if headers.subject.is_none() {
+        headers.subject = string(&mut file, "", tag::SUBJECT);
+    }
+    if headers.from.is_none() {
+        let address = string(&mut file, "", tag::SENDER_SMTP_ADDRESS)
+            .or_else(|| string(&mut file, "", tag::SENDER_EMAIL));
+        headers.from = mailbox(string(&mut file, "", tag::SENDER_NAME), address);
+    }
+    if headers.to.is_empty() && headers.cc.is_empty() {
+        (headers.to, headers.cc) = recipients(&mut file);
+    }
+    if headers.date.is_none() {
+        headers.date = [tag::CLIENT_SUBMIT_TIME, tag::DELIVERY_TIME]
+            .iter()
+            .find_map(|id| properties.get(id).and_then(|value| filetime(*value)));
+    }
+    if headers.message_id.is_none() {
+        headers.message_id =
+            string(&mut file, "", tag::MESSAGE_ID).and_then(|id| EmailHeaders::normalize_id(&id));
+    }
+    if headers.in_reply_to.is_empty() {
+        headers.in_reply_to = string(&mut file, "", tag::IN_REPLY_TO)
+            .map(|ids| EmailHeaders::parse_ids(&ids))
+            .unwrap_or_default();
+    }
+    if headers.references.is_empty() {
+        headers.references = string(&mut file, "", tag::REFERENCES)
+            .map(|ids| EmailHeaders::parse_ids(&ids))
+            .unwrap_or_default();
+    }
+
+    let body = string(&mut file, "", tag::BODY)
+        .or_else(|| {
+            string(&mut file, "", tag::HTML_BODY)
+                .or_else(|| {
+                    binary(&mut file, "", tag::HTML_BODY)
+                        .map(|html| String::from_utf8_lossy(&html).to_string())
+                })
+                .map(|html| mail_parser::decoders::html::html_to_text(&html))
+        })
+        .unwrap_or_default()
+        .trim()
+        .to_string();
+
+    Ok(ParsedEmail {
+        headers,
+        body,
+        attachments: attachments(&mut file),
+    })
+}
+
+/// To and Cc recipients from the recipient storages
+fn recipients<F: Read + Seek>(file: &mut CompoundFile<F>) -> (Vec<String>, Vec<String>) {
+    let mut to = Vec::new();
+    let mut cc = Vec::new();
+    for storage in storages(file, "__recip_version1.0_") {
+        let kind = fixed_properties(file, &storage, CHILD_PROPERTIES_HEADER)
+            .get(&tag::RECIPIENT_TYPE)
+            .map(|value| u32::from_le_bytes([value[0], value[1], value[2], value[3]]));
+        let address = string(file, &storage, tag::SMTP_ADDRESS)
+            .or_else(|| string(file, &storage, tag::EMAIL_ADDRESS));
+        let Some(recipient) = mailbox(string(file, &storage, tag::DISPLAY_NAME), address) else {
+            continue;
+        };
+        match kind {
+            Some(2) => cc.push(recipient),
+            // Blind copies are not part of the visible envelope
+            Some(3) => {}
+            _ => to.push(recipient),
+        }
+    }
+    (to, cc)
+}
+
+fn attachments<F: Read + Seek>(file: &mut CompoundFile<F>) -> Vec<EmailAttachment> {
+    let mut attachments = Vec::new();
+    for (index, storage) in storages(file, "__attach_version1.0_")
+        .into_iter()
+        .enumerate()
+    {
+        let Some(data) = binary(file, &storage, tag::ATTACH_DATA) else {
+            continue;
+        };
+        let file_name = [
+            tag::ATTACH_LONG_FILENAME,
+            tag::ATTACH_FILENAME,
+            tag::DISPLAY_NAME,
+        ]
+        .iter()
+        .find_map(|id| string(file, &storage, *id))
+        .unwrap_or_else(|| format!("attachment-{}", index + 1));
+        attachments.push(EmailAttachment {
+            file_name,
+            content_type: string(file, &storage, tag::ATTACH_MIME_TAG),
+            data,
+        });
+    }
+    attachments
+}
+
+/// Paths of the top-level storages whose names start with `prefix`, in
+/// name order
+fn storages<F: Read + Seek>(file: &CompoundFile<F>, prefix: &str) -> Vec<String> {
+    let mut paths: Vec<String> = file
+        .read_root_storage()
+        .filter(|entry| entry.is_storage() && entry.name().starts_with(prefix))
+        .map(|entry| entry.path().to_string_lossy().to_string())
+        .collect();
+    paths.sort();
+    paths
+}
+
+fn stream<F: Read + Seek>(file: &mut CompoundFile<F>, path: &str) -> Option<Vec<u8>> {
+    let mut stream = file.open_stream(path).ok()?;
+    let mut bytes = Vec::new();
+    stream.read_to_end(&mut bytes).ok()?;
+    Some(bytes)
+}
+
+fn property_path(storage: &str, id: u16, kind: u16) -> String {
+    format!("{}/__substg1.0_{:04X}{:04X}", storage, id, kind)
+}
+
+/// A string property, stored as UTF-16 or as 8-bit text
+fn string<F: Read + Seek>(file: &mut CompoundFile<F>, storage: &str, id: u16) -> Option<String> {
+    let text = if let Some(bytes) = stream(file, &property_path(storage, id, UNICODE)) {
+        let units: Vec<u16> = bytes
+            .chunks_exact(2)
+            .map(|pair| u16::from_le_bytes([pair[0], pair[1]]))
+            .collect();
+        String::from_utf16_lossy(&units)
+    } else {
+        // 8-bit strings use the sender's code page; Latin-1 is close enough
+        // to Windows-1252 for names and subjects
+        let bytes = stream(file, &property_path(storage, id, STRING8))?;
+        bytes.iter().map(|&b| char::from(b)).collect()
+    };
+    let text = text.trim_end_matches('\0').trim();
+    (!text.is_empty()).then(|| text.to_string())
+}
+
+fn binary<F: Read + Seek>(file: &mut CompoundFile<F>, storage: &str, id: u16) -> Option<Vec<u8>> {
+    stream(file, &property_path(storage, id, BINARY))
+}
+
+/// Fixed-size property values of a storage, by property id
+fn fixed_properties<F: Read + Seek>(
+    file: &mut CompoundFile<F>,
+    storage: &str,
+    header: usize,
+) -> HashMap<u16, [u8; 8]> {
+    let path = format!("{}/__properties_version1.0", storage);
+    let Some(bytes) = stream(file, &path) else {
+        return HashMap::new();
+    };
+    bytes
+        .get(header..)
+        .unwrap_or_default()
+        .chunks_exact(16)
+        .map(|entry| {
+            let id = u16::from_le_bytes([entry[2], entry[3]]);
+            let mut value = [0u8; 8];
+            value.copy_from_slice(&entry[8..16]);
+            (id, value)
+        })
+        .collect()
+}
+
+/// A FILETIME (100 ns intervals since 1601) as a UTC time
+fn filetime(value: [u8; 8]) -> Option<DateTime<Utc>> {
+    let ticks = i64::try_from(u64::from_le_bytes(value)).ok()?;
+    if ticks == 0 {
+        return None;
+    }
+    DateTime::from_timestamp(ticks / 10_000_000 - FILETIME_UNIX_OFFSET, 0)
+}
+
+fn mailbox(name: Option<String>, address: Option<String>) -> Option<String> {
+    // Exchange-internal addresses ("/O=ORG/OU=...") are no use outside
+    let address = address.filter(|address| address.contains('@'));
+    match (name, address) {
+        (Some(name), Some(address)) if name != address => Some(format!("{} <{}>", name, address)),
+        (_, Some(address)) => Some(address),
+        (name, None) => name,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    fn utf16(text: &str) -> Vec<u8> {
+        text.encode_utf16().flat_map(|u| u.to_le_bytes()).collect()
+    }
+
+    fn write(file: &mut CompoundFile<Cursor<Vec<u8>>>, path: &str, bytes: &[u8]) {
+        file.create_stream(path).unwrap().write_all(bytes).unwrap();
+    }
+
+    /// An Outlook message with properties only, as for a sent item
+    fn sample_msg() -> Vec<u8> {
+        let mut file = CompoundFile::create(Cursor::new(Vec::new())).unwrap();
+
+        let mut properties = vec![0u8; MESSAGE_PROPERTIES_HEADER];
+        properties.extend_from_slice(&0x0039_0040u32.to_le_bytes());
+        properties.extend_from_slice(&[0; 4]);
+        // 2024-03-05T08:30:00Z
+        let ticks = (1_709_627_400 + FILETIME_UNIX_OFFSET) as u64 * 10_000_000;
+        properties.extend_from_slice(&ticks.to_le_bytes());
+        write(&mut file, "/__properties_version1.0", &properties);
+
+        write(
+            &mut file,
+            "/__substg1.0_0037001F",
+            &utf16("Offer for lot 4"),
+        );
+        write(&mut file, "/__substg1.0_0C1A001F", &utf16("Ana Silva"));
+        write(
+            &mut file,
+            "/__substg1.0_5D01001F",
+            &utf16("ana@example.com"),
+        );
+        write(
+            &mut file,
+            "/__substg1.0_1000001E",
+            b"Please find the offer attached.\0",
+        );
+        write(
+            &mut file,
+            "/__substg1.0_1035001F",
+            &utf16("<offer-1@example.com>"),
+        );
+
+        for (index, (name, address, kind)) in [
+            ("Ben", "ben@example.com", 1u32),
+            ("Legal", "legal@example.com", 2),
+        ]
+        .iter()
+        .enumerate()
+        {
+            let storage = format!("/__recip_version1.0_#{:08X}", index);
+            file.create_storage(&storage).unwrap();
+            let mut properties = vec![0u8; CHILD_PROPERTIES_HEADER];
+            properties.extend_from_slice(&0x0C15_0003u32.to_le_bytes());
+            properties.extend_from_slice(&[0; 4]);
+            properties.extend_from_slice(&u64::from(*kind).to_le_bytes());
+            write(
+                &mut file,
+                &format!("{}/__properties_version1.0", storage),
+                &properties,
+            );
+            write(
+                &mut file,
+                &format!("{}/__substg1.0_3001001F", storage),
+                &utf16(name),
+            );
+            write(
+                &mut file,
+                &format!("{}/__substg1.0_39FE001F", storage),
+                &utf16(address),
+            );
+        }
+
+        file.create_storage("/__attach_version1.0_#00000000")
+            .unwrap();
+        write(
+            &mut file,
+            "/__attach_version1.0_#00000000/__substg1.0_3707001F",
+            &utf16("offer.pdf"),
+        );
+        write(
+            &mut file,
+            "/__attach_version1.0_#00000000/__substg1.0_37010102",
+            b"%PDF-1.4\n",
+        );
+
+        file.flush().unwrap();
+        file.into_inner().into_inner()
+    }
+
+    #[test]
+    fn test_parse_msg_from_properties() {
+        let message = parse_msg(&sample_msg()).unwrap();
+        let headers = &message.headers;
+
+        assert_eq!(headers.subject.as_deref(), Some("Offer for lot 4"));
+        assert_eq!(headers.from.as_deref(), Some("Ana Silva <ana@example.com>"));
+        assert_eq!(headers.to, ["Ben <ben@example.com>"]);
+        assert_eq!(headers.cc, ["Legal <legal@example.com>"]);
+        assert_eq!(
+            headers.date.unwrap().to_rfc3339(),
+            "2024-03-05T08:30:00+00:00"
+        );
+        assert_eq!(headers.message_id.as_deref(), Some("offer-1@example.com"));
+        assert_eq!(message.body, "Please find the offer attached.");
+
+        assert_eq!(message.attachments.len(), 1);
+        assert_eq!(message.attachments[0].file_name, "offer.pdf");
+        assert_eq!(message.attachments[0].data, b"%PDF-1.4\n");
+
+        assert!(parse_msg(b"not an OLE file").is_err());
+    }
+}
@@ -1,6 +1,23 @@
 use serde::{Deserialize, Serialize};
 
+use crate::domain::annotation::AnnotationError;
+use crate::domain::anonymization::AnonymizationError;
+use crate::domain::category::CategoryError;
+use crate::domain::coding::CodingError;
+use crate::domain::cost_table::CostTableError;
+use crate::domain::det::DetError;
+use crate::domain::document::DerivationError;
+use crate::domain::email::EmailError;
+use crate::domain::export::ExportError;
+use crate::domain::extraction::ExtractionError;
+use crate::domain::media::MediaProbeError;
+use crate::domain::preview::PreviewError;
 use crate::domain::project::ProjectError;
+use crate::domain::report::ReportError;
+use crate::domain::review::ReviewError;
+use crate::domain::search::SearchError;
+use crate::domain::workspace::repositories::RepositoryError;
+use crate::domain::workspace::ArchiveError;
 use crate::infrastructure::dtos::{
     CreateProjectRequestError, DeleteProjectRequestError, ProjectDtoError,
     UpdateProjectRequestError,
@@ -145,6 +162,9 @@ impl From<ProjectError> for AppError {
             ProjectError::InvalidId => {
                 AppError::validation_error("Invalid project ID format", None)
             }
+            ProjectError::InvalidReportsFolder { reason } => {
+                AppError::validation_error("Invalid reports folder", Some(reason))
+            }
 
             // Business rule violations
             ProjectError::SourceNotAccessible => AppError::filesystem_error(
@@ -201,6 +221,22 @@ impl From<ProjectError> for AppError {
     }
 }
 
+/// Convert workspace RepositoryError to AppError
+impl From<RepositoryError> for AppError {
+    fn from(error: RepositoryError) -> Self {
+        match error {
+            RepositoryError::NotFound(resource) => AppError::not_found(resource),
+            RepositoryError::DatabaseError(operation) => AppError::database_error(operation),
+            RepositoryError::ValidationError(message) => AppError::validation_error(message, None),
+            RepositoryError::AccessError(message) => AppError::permission_error(message),
+            RepositoryError::FileSystemError(message) => AppError::filesystem_error(message),
+            RepositoryError::ConstraintViolation(message) => AppError::conflict(message),
+            RepositoryError::SerializationError(message)
+            | RepositoryError::InternalError(message) => AppError::internal_error(message),
+        }
+    }
+}
+
 /// Convert DTO validation errors to AppError
 impl From<CreateProjectRequestError> for AppError {
     fn from(error: CreateProjectRequestError) -> Self {
@@ -278,6 +314,205 @@ impl From<ProjectDtoError> for AppError {
     }
 }
 
+/// Convert document extraction errors to AppError
+impl From<ExtractionError> for AppError {
+    fn from(error: ExtractionError) -> Self {
+        let message = error.to_string();
+        match error {
+            ExtractionError::UnsupportedFormat { .. } => {
+                AppError::new("UNSUPPORTED_FORMAT", message, None, false, true)
+            }
+            ExtractionError::InvalidDocument { .. } | ExtractionError::Encrypted { .. } => {
+                AppError::new("EXTRACTION_FAILED", message, None, false, true)
+            }
+            ExtractionError::Failed(_) => AppError::internal_error(message),
+        }
+    }
+}
+
+/// Convert `.det` document errors to AppError
+impl From<DetError> for AppError {
+    fn from(error: DetError) -> Self {
+        let message = error.to_string();
+        match error {
+            DetError::Io(_) => AppError::filesystem_error(message),
+            DetError::SchemaViolations(violations) => AppError::validation_error(
+                "The document does not match the editor schema",
+                Some(
+                    violations
+                        .iter()
+                        .map(ToString::to_string)
+                        .collect::<Vec<_>>()
+                        .join("\n"),
+                ),
+            ),
+            DetError::Parse(_)
+            | DetError::InvalidEnvelope(_)
+            | DetError::UnsupportedVersion { .. }
+            | DetError::Migration { .. } => {
+                AppError::new("INVALID_DOCUMENT", message, None, false, true)
+            }
+        }
+    }
+}
+
+/// Convert report rule violations to AppError
+impl From<ReportError> for AppError {
+    fn from(error: ReportError) -> Self {
+        let message = error.to_string();
+        match error {
+            ReportError::Finalized(_) => AppError::conflict(message),
+            ReportError::InvalidId(_)
+            | ReportError::InvalidTitle(_)
+            | ReportError::UnknownCategory(_)
+            | ReportError::UnknownStatus(_)
+            | ReportError::InvalidCitation(_) => AppError::validation_error(message, None),
+        }
+    }
+}
+
+/// Convert derivation graph rule violations to AppError
+impl From<DerivationError> for AppError {
+    fn from(error: DerivationError) -> Self {
+        let message = error.to_string();
+        match error {
+            DerivationError::Cycle { .. } => AppError::conflict(message),
+            DerivationError::InvalidMember(_) | DerivationError::InvalidStep(_) => {
+                AppError::validation_error(message, None)
+            }
+        }
+    }
+}
+
+/// Convert anonymization rule errors to AppError
+impl From<AnonymizationError> for AppError {
+    fn from(error: AnonymizationError) -> Self {
+        AppError::validation_error(error.to_string(), None)
+    }
+}
+
+/// Convert cost table validation and file format errors to AppError
+impl From<CostTableError> for AppError {
+    fn from(error: CostTableError) -> Self {
+        AppError::validation_error(error.to_string(), None)
+    }
+}
+
+/// Convert annotation errors to AppError
+impl From<AnnotationError> for AppError {
+    fn from(error: AnnotationError) -> Self {
+        AppError::validation_error(error.to_string(), None)
+    }
+}
+
+/// Convert category errors to AppError
+impl From<CategoryError> for AppError {
+    fn from(error: CategoryError) -> Self {
+        AppError::validation_error(error.to_string(), None)
+    }
+}
+
+/// Convert coding errors to AppError
+impl From<CodingError> for AppError {
+    fn from(error: CodingError) -> Self {
+        AppError::validation_error(error.to_string(), None)
+    }
+}
+
+/// Convert review errors to AppError
+impl From<ReviewError> for AppError {
+    fn from(error: ReviewError) -> Self {
+        AppError::validation_error(error.to_string(), None)
+    }
+}
+
+/// Convert search query errors to AppError
+impl From<SearchError> for AppError {
+    fn from(error: SearchError) -> Self {
+        AppError::validation_error(error.to_string(), None)
+    }
+}
+
+/// Convert export errors to AppError
+impl From<ExportError> for AppError {
+    fn from(error: ExportError) -> Self {
+        let message = error.to_string();
+        match error {
+            ExportError::UnsupportedFormat(_) | ExportError::InvalidOptions(_) => {
+                AppError::validation_error(message, None)
+            }
+            ExportError::Failed(_) => AppError::new("EXPORT_FAILED", message, None, true, true),
+        }
+    }
+}
+
+/// Convert media probe errors to AppError
+impl From<MediaProbeError> for AppError {
+    fn from(error: MediaProbeError) -> Self {
+        let message = error.to_string();
+        match error {
+            MediaProbeError::UnsupportedFormat { .. } => {
+                AppError::new("UNSUPPORTED_FORMAT", message, None, false, true)
+            }
+            MediaProbeError::InvalidFile { .. } => {
+                AppError::new("MEDIA_PROBE_FAILED", message, None, false, true)
+            }
+            MediaProbeError::Io(_) => AppError::filesystem_error(message),
+        }
+    }
+}
+
+/// Convert email parsing errors to AppError
+impl From<EmailError> for AppError {
+    fn from(error: EmailError) -> Self {
+        let message = error.to_string();
+        match error {
+            EmailError::UnsupportedFormat { .. } => {
+                AppError::new("UNSUPPORTED_FORMAT", message, None, false, true)
+            }
+            EmailError::InvalidFile { .. } => {
+                AppError::new("EMAIL_PARSE_FAILED", message, None, false, true)
+            }
+        }
+    }
+}
+
+impl From<ArchiveError> for AppError {
+    fn from(error: ArchiveError) -> Self {
+        let message = error.to_string();
+        match error {
+            ArchiveError::UnsupportedFormat { .. } => {
+                AppError::new("UNSUPPORTED_FORMAT", message, None, false, true)
+            }
+            ArchiveError::Unreadable { .. } => {
+                AppError::new("ARCHIVE_READ_FAILED", message, None, false, true)
+            }
+            ArchiveError::MemberNotFound { .. } => {
+                AppError::new("NOT_FOUND", message, None, false, true)
+            }
+            ArchiveError::TooLarge { .. } => {
+                AppError::new("ARCHIVE_MEMBER_TOO_LARGE", message, None, false, true)
+            }
+        }
+    }
+}
+
+/// Convert preview generation errors to AppError
+impl From<PreviewError> for AppError {
+    fn from(error: PreviewError) -> Self {
+        let message = error.to_string();
+        match error {
+            PreviewError::UnsupportedFormat { .. } => {
+                AppError::new("UNSUPPORTED_FORMAT", message, None, false, true)
+            }
+            PreviewError::InvalidFile { .. } | PreviewError::NotAvailable(_) => {
+                AppError::new("PREVIEW_FAILED", message, None, false, true)
+            }
+            PreviewError::Io(_) => AppError::filesystem_error(message),
+        }
+    }
+}
+
 // Note: InvokeError conversion is handled automatically by Tauri
 // when commands return Result<T, String>
 
@@ -0,0 +1,848 @@
+use std::io::{Cursor, Write};
+use zip::write::SimpleFileOptions;
+
+use crate::domain::det::{PmMark, PmNode};
+use crate::domain::export::{DocumentExporter, ExportDocument, ExportError, ExportFormat};
+
+/// Renders `.det` content as a Word document (.docx)
+///
+/// Writes the package parts directly: built-in heading styles so Word's
+/// navigation pane and table of contents work, list numbering, tables with
+/// merged cells, and header and footer parts with page number fields. The
+/// table of contents is a TOC field with the headings pre-filled; Word
+/// adds page numbers when it updates fields on opening.
+pub struct DocxExporter;
+
+const NS_W: &str = "http://schemas.openxmlformats.org/wordprocessingml/2006/main";
+const NS_R: &str = "http://schemas.openxmlformats.org/officeDocument/2006/relationships";
+const REL_TYPE: &str = "http://schemas.openxmlformats.org/officeDocument/2006/relationships";
+
+/// A4 text width in twips with 2.54 cm margins
+const TEXT_WIDTH: u32 = 9026;
+
+/// Bookmark ids of cited sources start here, after the headings
+const SOURCE_BOOKMARK_BASE: usize = 100_000;
+
+impl DocumentExporter for DocxExporter {
+    fn format(&self) -> ExportFormat {
+        ExportFormat::Docx
+    }
+
+    fn export(&self, document: &ExportDocument) -> Result<Vec<u8>, ExportError> {
+        let mut builder = Builder {
+            document,
+            heading_index: 0,
+            body: String::new(),
+            hyperlinks: Vec::new(),
+            ordered_lists: Vec::new(),
+        };
+        builder.toc();
+        for node in &document.content.content {
+            builder.block(node, &Context::default());
+        }
+        builder.sources();
+
+        let header = document.options.template.header.as_deref();
+        let footer = document.options.template.footer.as_deref();
+        let document_xml = builder.document_xml(header.is_some(), footer.is_some());
+
+        let mut parts: Vec<(&str, String)> = vec![
+            (
+                "[Content_Types].xml",
+                content_types(header.is_some(), footer.is_some()),
+            ),
+            ("_rels/.rels", package_rels()),
+            ("docProps/core.xml", core_properties(&document.title)),
+            ("word/document.xml", document_xml),
+            (
+                "word/_rels/document.xml.rels",
+                builder.document_rels(header.is_some(), footer.is_some()),
+            ),
+            ("word/styles.xml", styles()),
+            ("word/numbering.xml", builder.numbering()),
+            ("word/settings.xml", settings()),
+        ];
+        if let Some(header) = header {
+            parts.push((
+                "word/header1.xml",
+                page_part("hdr", "Header", header, document),
+            ));
+        }
+        if let Some(footer) = footer {
+            parts.push((
+                "word/footer1.xml",
+                page_part("ftr", "Footer", footer, document),
+            ));
+        }
+
+        let mut buffer = Cursor::new(Vec::new());
+        let mut zip = zip::ZipWriter::new(&mut buffer);
+        let options =
+            SimpleFileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+        for (name, xml) in parts {
+            zip.start_file(name, options)
+                .map_err(|e| ExportError::Failed(e.to_string()))?;
+            zip.write_all(xml.as_bytes())
+                .map_err(|e| ExportError::Failed(e.to_string()))?;
+        }
+        zip.finish()
+            .map_err(|e| ExportError::Failed(e.to_string()))?;
+        Ok(buffer.into_inner())
+    }
+}
+
+/// Where a block sits, which decides its paragraph properties
+#[derive(Clone, Default)]
+struct Context {
+    quote: bool,
+    /// Bold runs, for header cells
+    strong: bool,
+    /// Numbering id and level of the enclosing list
+    list: Option<(usize, usize)>,
+    /// Whether the next paragraph starts a list item and gets its number
+    numbered: bool,
+}
+
+struct Builder<'a> {
+    document: &'a ExportDocument,
+    heading_index: usize,
+    body: String,
+    /// External link targets; the n-th has relationship id `rIdLink{n}`
+    hyperlinks: Vec<String>,
+    /// Start number of each ordered list; the n-th has numbering id n + 2
+    ordered_lists: Vec<u64>,
+}
+
+impl Builder<'_> {
+    fn block(&mut self, node: &PmNode, context: &Context) {
+        match node.node_type.as_str() {
+            "heading" => {
+                let level = node
+                    .attr("level")
+                    .and_then(|l| l.as_u64())
+                    .unwrap_or(1)
+                    .clamp(1, 6);
+                self.heading_index += 1;
+                let id = self.heading_index;
+                let runs = self.runs(&node.content, context);
+                self.body.push_str(&format!(
+                    r#"<w:p><w:pPr><w:pStyle w:val="Heading{}"/></w:pPr><w:bookmarkStart w:id="{}" w:name="_Toc{}"/>{}<w:bookmarkEnd w:id="{}"/></w:p>"#,
+                    level, id, id, runs, id
+                ));
+            }
+            "blockquote" => {
+                let inner = Context {
+                    quote: true,
+                    ..context.clone()
+                };
+                for child in &node.content {
+                    self.block(child, &inner);
+                }
+            }
+            "codeBlock" => {
+                let code = node
+                    .plain_text()
+                    .split('\n')
+                    .map(|line| format!(r#"<w:r><w:t xml:space="preserve">{}</w:t></w:r>"#, escape(line)))
+                    .collect::<Vec<_>>()
+                    .join("<w:r><w:br/></w:r>");
+                self.body.push_str(&format!(
+                    r#"<w:p><w:pPr><w:pStyle w:val="Code"/></w:pPr>{}</w:p>"#,
+                    code
+                ));
+            }
+            "horizontalRule" => self.body.push_str(
+                r#"<w:p><w:pPr><w:pBdr><w:bottom w:val="single" w:sz="6" w:space="1" w:color="BBBBBB"/></w:pBdr></w:pPr></w:p>"#,
+            ),
+            "bulletList" | "orderedList" => {
+                let level = context.list.map_or(0, |(_, level)| level + 1).min(8);
+                let num_id = if node.node_type == "bulletList" {
+                    1
+                } else {
+                    let start = node.attr("start").and_then(|s| s.as_u64()).unwrap_or(1);
+                    self.ordered_lists.push(start);
+                    self.ordered_lists.len() + 1
+                };
+                for item in &node.content {
+                    let mut inner = Context {
+                        list: Some((num_id, level)),
+                        numbered: true,
+                        ..context.clone()
+                    };
+                    for child in &item.content {
+                        self.block(child, &inner);
+                        inner.numbered = false;
+                    }
+                }
+            }
+            "table" => self.table(node, context),
+            "paragraph" => self.paragraph(node, context),
+            _ if node
+                .content
+                .iter()
+                .any(|child| child.is_text() || child.node_type == "hardBreak") =>
+            {
+                self.paragraph(node, context)
+            }
+            _ => {
+                for child in &node.content {
+                    self.block(child, context);
+                }
+            }
+        }
+    }
+
+    fn paragraph(&mut self, node: &PmNode, context: &Context) {
+        let properties = paragraph_properties(context);
+        let runs = self.runs(&node.content, context);
+        self.body
+            .push_str(&format!("<w:p>{}{}</w:p>", properties, runs));
+    }
+
+    fn table(&mut self, node: &PmNode, context: &Context) {
+        let span = |cell: &PmNode, key: &str| {
+            cell.attr(key).and_then(|v| v.as_u64()).unwrap_or(1).max(1) as usize
+        };
+        let columns = node
+            .content
+            .iter()
+            .map(|row| {
+                row.content
+                    .iter()
+                    .map(|cell| span(cell, "colspan"))
+                    .sum::<usize>()
+            })
+            .max()
+            .unwrap_or(1)
+            .max(1);
+        let width = TEXT_WIDTH / columns as u32;
+
+        self.body.push_str(
+            r#"<w:tbl><w:tblPr><w:tblStyle w:val="TableGrid"/><w:tblW w:w="0" w:type="auto"/></w:tblPr><w:tblGrid>"#,
+        );
+        for _ in 0..columns {
+            self.body
+                .push_str(&format!(r#"<w:gridCol w:w="{}"/>"#, width));
+        }
+        self.body.push_str("</w:tblGrid>");
+
+        // Rows still covered by a cell above, and that cell's width, per
+        // column
+        let mut covered = vec![(0usize, 1usize); columns];
+        let cell_context = Context {
+            list: None,
+            numbered: false,
+            ..context.clone()
+        };
+
+        for row in &node.content {
+            self.body.push_str("<w:tr>");
+            let mut column = 0;
+            let mut cells = row.content.iter().peekable();
+            while column < columns {
+                if covered[column].0 > 0 {
+                    let colspan = covered[column].1;
+                    covered[column].0 -= 1;
+                    self.body.push_str(&format!(
+                        r#"<w:tc><w:tcPr><w:gridSpan w:val="{}"/><w:vMerge/></w:tcPr><w:p/></w:tc>"#,
+                        colspan
+                    ));
+                    column += colspan;
+                    continue;
+                }
+                let Some(cell) = cells.next() else {
+                    break;
+                };
+                let colspan = span(cell, "colspan").min(columns - column);
+                let rowspan = span(cell, "rowspan");
+                let mut properties =
+                    format!(r#"<w:tcW w:w="{}" w:type="dxa"/>"#, width * colspan as u32);
+                if colspan > 1 {
+                    properties.push_str(&format!(r#"<w:gridSpan w:val="{}"/>"#, colspan));
+                }
+                if rowspan > 1 {
+                    properties.push_str(r#"<w:vMerge w:val="restart"/>"#);
+                    covered[column] = (rowspan - 1, colspan);
+                }
+                self.body
+                    .push_str(&format!("<w:tc><w:tcPr>{}</w:tcPr>", properties));
+
+                let inner = Context {
+                    strong: cell.node_type == "tableHeader",
+                    ..cell_context.clone()
+                };
+                let before = self.body.len();
+                for block in &cell.content {
+                    self.block(block, &inner);
+                }
+                if !self.body[before..].contains("<w:p") {
+                    self.body.push_str("<w:p/>");
+                }
+                self.body.push_str("</w:tc>");
+                column += colspan;
+            }
+            self.body.push_str("</w:tr>");
+        }
+        self.body.push_str("</w:tbl>");
+        // Word needs a paragraph between adjacent tables
+        self.body.push_str("<w:p/>");
+    }
+
+    fn runs(&mut self, nodes: &[PmNode], context: &Context) -> String {
+        let mut output = String::new();
+        for (index, node) in nodes.iter().enumerate() {
+            if node.node_type == "hardBreak" {
+                output.push_str("<w:r><w:br/></w:r>");
+            } else if let Some(text) = &node.text {
+                let link = node
+                    .marks
+                    .iter()
+                    .find(|mark| mark.mark_type == "link")
+                    .and_then(|mark| mark.attrs.as_ref()?.get("href")?.as_str());
+                let run = run(text, &node.marks, context.strong, link.is_some());
+                match link {
+                    Some(href) => {
+                        let id = self.hyperlink(href);
+                        output.push_str(&format!(
+                            r#"<w:hyperlink r:id="{}" w:history="1">{}</w:hyperlink>"#,
+                            id, run
+                        ));
+                    }
+                    None => output.push_str(&run),
+                }
+            }
+
+            for number in self.document.citations_after(nodes, index) {
+                output.push_str(&format!(
+                    r#"<w:hyperlink w:anchor="_Source{0}"><w:r><w:rPr><w:vertAlign w:val="superscript"/></w:rPr><w:t>[{0}]</w:t></w:r></w:hyperlink>"#,
+                    number
+                ));
+            }
+        }
+        output
+    }
+
+    fn hyperlink(&mut self, target: &str) -> String {
+        let index = match self.hyperlinks.iter().position(|t| t == target) {
+            Some(index) => index,
+            None => {
+                self.hyperlinks.push(target.to_string());
+                self.hyperlinks.len() - 1
+            }
+        };
+        format!("rIdLink{}", index + 1)
+    }
+
+    fn toc(&mut self) {
+        let toc = self.document.toc();
+        if toc.is_empty() {
+            return;
+        }
+
+        self.body.push_str(
+            r#"<w:p><w:pPr><w:pStyle w:val="TOCHeading"/></w:pPr><w:r><w:t>Contents</w:t></w:r></w:p>"#,
+        );
+        let field_begin = format!(
+            r#"<w:r><w:fldChar w:fldCharType="begin"/></w:r><w:r><w:instrText xml:space="preserve"> TOC \o "1-{}" \h \z \u </w:instrText></w:r><w:r><w:fldChar w:fldCharType="separate"/></w:r>"#,
+            self.document.options.toc_depth
+        );
+        for (index, entry) in toc.iter().enumerate() {
+            let heading = self
+                .document
+                .headings
+                .iter()
+                .position(|h| h.anchor == entry.anchor)
+                .map_or(0, |i| i + 1);
+            self.body.push_str(&format!(
+                r#"<w:p><w:pPr><w:pStyle w:val="TOC{}"/></w:pPr>{}<w:hyperlink w:anchor="_Toc{}" w:history="1"><w:r><w:t xml:space="preserve">{}</w:t></w:r></w:hyperlink></w:p>"#,
+                entry.level,
+                if index == 0 { field_begin.as_str() } else { "" },
+                heading,
+                escape(&entry.text)
+            ));
+        }
+        self.body
+            .push_str(r#"<w:p><w:r><w:fldChar w:fldCharType="end"/></w:r></w:p>"#);
+    }
+
+    fn sources(&mut self) {
+        let sources = self.document.listed_sources();
+        if sources.is_empty() {
+            return;
+        }
+
+        self.body.push_str(
+            r#"<w:p><w:pPr><w:pStyle w:val="SectionHeading"/></w:pPr><w:r><w:t>Sources</w:t></w:r></w:p>"#,
+        );
+        for (index, source) in sources.iter().enumerate() {
+            let number = index + 1;
+            let id = self.hyperlink(&source.file_url());
+            self.body.push_str(&format!(
+                r#"<w:p><w:pPr><w:pStyle w:val="Source"/></w:pPr><w:bookmarkStart w:id="{0}" w:name="_Source{1}"/><w:r><w:t xml:space="preserve">[{1}] </w:t></w:r><w:bookmarkEnd w:id="{0}"/><w:hyperlink r:id="{2}" w:history="1"><w:r><w:rPr><w:rStyle w:val="Hyperlink"/></w:rPr><w:t xml:space="preserve">{3}</w:t></w:r></w:hyperlink></w:p>"#,
+                SOURCE_BOOKMARK_BASE + number,
+                number,
+                id,
+                escape(&source.display_label())
+            ));
+        }
+    }
+
+    fn document_xml(&self, header: bool, footer: bool) -> String {
+        let mut references = String::new();
+        if header {
+            references.push_str(r#"<w:headerReference w:type="default" r:id="rIdHeader"/>"#);
+        }
+        if footer {
+            references.push_str(r#"<w:footerReference w:type="default" r:id="rIdFooter"/>"#);
+        }
+        format!(
+            r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<w:document xmlns:w="{}" xmlns:r="{}"><w:body>{}<w:sectPr>{}<w:pgSz w:w="11906" w:h="16838"/><w:pgMar w:top="1440" w:right="1440" w:bottom="1440" w:left="1440" w:header="708" w:footer="708" w:gutter="0"/></w:sectPr></w:body></w:document>"#,
+            NS_W, NS_R, self.body, references
+        )
+    }
+
+    fn document_rels(&self, header: bool, footer: bool) -> String {
+        let mut relationships = vec![
+            relationship("rIdStyles", "styles", "styles.xml"),
+            relationship("rIdNumbering", "numbering", "numbering.xml"),
+            relationship("rIdSettings", "settings", "settings.xml"),
+        ];
+        if header {
+            relationships.push(relationship("rIdHeader", "header", "header1.xml"));
+        }
+        if footer {
+            relationships.push(relationship("rIdFooter", "footer", "footer1.xml"));
+        }
+        for (index, target) in self.hyperlinks.iter().enumerate() {
+            relationships.push(format!(
+                r#"<Relationship Id="rIdLink{}" Type="{}/hyperlink" Target="{}" TargetMode="External"/>"#,
+                index + 1,
+                REL_TYPE,
+                escape(target)
+            ));
+        }
+        relationships_xml(&relationships)
+    }
+
+    /// Bullet lists share numbering 1; each ordered list gets its own
+    /// numbering so it restarts at its start number
+    fn numbering(&self) -> String {
+        let levels = |format: &str| {
+            (0..9)
+                .map(|level| {
+                    let (text, format) = if format == "bullet" {
+                        (["\u{2022}", "\u{25E6}", "\u{25AA}"][level % 3].to_string(), "bullet")
+                    } else {
+                        (format!("%{}.", level + 1), "decimal")
+                    };
+                    format!(
+                        r#"<w:lvl w:ilvl="{}"><w:start w:val="1"/><w:numFmt w:val="{}"/><w:lvlText w:val="{}"/><w:lvlJc w:val="left"/><w:pPr><w:ind w:left="{}" w:hanging="360"/></w:pPr></w:lvl>"#,
+                        level,
+                        format,
+                        text,
+                        720 * (level + 1)
+                    )
+                })
+                .collect::<String>()
+        };
+
+        let mut xml = format!(
+            r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<w:numbering xmlns:w="{}"><w:abstractNum w:abstractNumId="0">{}</w:abstractNum><w:abstractNum w:abstractNumId="1">{}</w:abstractNum><w:num w:numId="1"><w:abstractNumId w:val="0"/></w:num>"#,
+            NS_W,
+            levels("bullet"),
+            levels("decimal")
+        );
+        for (index, start) in self.ordered_lists.iter().enumerate() {
+            let overrides = (0..9)
+                .map(|level| {
+                    format!(
+                        r#"<w:lvlOverride w:ilvl="{}"><w:startOverride w:val="{}"/></w:lvlOverride>"#,
+                        level, start
+                    )
+                })
+                .collect::<String>();
+            xml.push_str(&format!(
+                r#"<w:num w:numId="{}"><w:abstractNumId w:val="1"/>{}</w:num>"#,
+                index + 2,
+                overrides
+            ));
+        }
+        xml.push_str("</w:numbering>");
+        xml
+    }
+}
+
+fn paragraph_properties(context: &Context) -> String {
+    let mut properties = String::new();
+    if context.quote {
+        properties.push_str(r#"<w:pStyle w:val="Quote"/>"#);
+    }
+    if let Some((num_id, level)) = context.list {
+        if context.numbered {
+            properties.push_str(&format!(
+                r#"<w:numPr><w:ilvl w:val="{}"/><w:numId w:val="{}"/></w:numPr>"#,
+                level, num_id
+            ));
+        } else {
+            properties.push_str(&format!(r#"<w:ind w:left="{}"/>"#, 720 * (level + 1)));
+        }
+    }
+    if properties.is_empty() {
+        properties
+    } else {
+        format!("<w:pPr>{}</w:pPr>", properties)
+    }
+}
+
+/// A run of text with its character formatting
+fn run(text: &str, marks: &[PmMark], strong: bool, link: bool) -> String {
+    let has = |name: &str| marks.iter().any(|mark| mark.mark_type == name);
+
+    // Elements in the order the schema requires
+    let mut properties = String::new();
+    if link {
+        properties.push_str(r#"<w:rStyle w:val="Hyperlink"/>"#);
+    }
+    if has("code") {
+        properties.push_str(r#"<w:rFonts w:ascii="Consolas" w:hAnsi="Consolas" w:cs="Consolas"/>"#);
+    }
+    if strong || has("bold") {
+        properties.push_str("<w:b/>");
+    }
+    if has("italic") {
+        properties.push_str("<w:i/>");
+    }
+    if has("strike") {
+        properties.push_str("<w:strike/>");
+    }
+    if let Some(mark) = marks.iter().find(|mark| mark.mark_type == "highlight") {
+        let color = mark
+            .attrs
+            .as_ref()
+            .and_then(|attrs| attrs.get("color")?.as_str())
+            .and_then(|color| color.strip_prefix('#'))
+            .filter(|hex| hex.len() == 6 && hex.chars().all(|c| c.is_ascii_hexdigit()));
+        if color.is_none() {
+            properties.push_str(r#"<w:highlight w:val="yellow"/>"#);
+        }
+        if has("underline") {
+            properties.push_str(r#"<w:u w:val="single"/>"#);
+        }
+        if let Some(hex) = color {
+            properties.push_str(&format!(
+                r#"<w:shd w:val="clear" w:color="auto" w:fill="{}"/>"#,
+                hex
+            ));
+        }
+    } else if has("underline") {
+        properties.push_str(r#"<w:u w:val="single"/>"#);
+    }
+
+    let text = text
+        .split('\n')
+        .map(|line| format!(r#"<w:t xml:space="preserve">{}</w:t>"#, escape(line)))
+        .collect::<Vec<_>>()
+        .join("<w:br/>");
+    if properties.is_empty() {
+        format!("<w:r>{}</w:r>", text)
+    } else {
+        format!("<w:r><w:rPr>{}</w:rPr>{}</w:r>", properties, text)
+    }
+}
+
+/// Header or footer part, with page placeholders as fields
+fn page_part(root: &str, style: &str, template: &str, document: &ExportDocument) -> String {
+    let paragraphs: String = template
+        .lines()
+        .map(|line| {
+            let line = line
+                .replace("{title}", &document.title)
+                .replace("{date}", &document.date);
+            let mut runs = String::new();
+            let mut rest = line.as_str();
+            while let Some(start) = rest.find("{page") {
+                let (field, length) = if rest[start..].starts_with("{pages}") {
+                    ("NUMPAGES", 7)
+                } else if rest[start..].starts_with("{page}") {
+                    ("PAGE", 6)
+                } else {
+                    break;
+                };
+                runs.push_str(&text_run(&rest[..start]));
+                runs.push_str(&format!(
+                    r#"<w:fldSimple w:instr=" {} "><w:r><w:t>1</w:t></w:r></w:fldSimple>"#,
+                    field
+                ));
+                rest = &rest[start + length..];
+            }
+            runs.push_str(&text_run(rest));
+            format!(
+                r#"<w:p><w:pPr><w:pStyle w:val="{}"/></w:pPr>{}</w:p>"#,
+                style, runs
+            )
+        })
+        .collect();
+
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<w:{0} xmlns:w="{1}" xmlns:r="{2}">{3}</w:{0}>"#,
+        root, NS_W, NS_R, paragraphs
+    )
+}
+
+fn text_run(text: &str) -> String {
+    if text.is_empty() {
+        String::new()
+    } else {
+        format!(
+            r#"<w:r><w:t xml:space="preserve">{}</w:t></w:r>"#,
+            escape(text)
+        )
+    }
+}
+
+fn relationship(id: &str, kind: &str, target: &str) -> String {
+    format!(
+        r#"<Relationship Id="{}" Type="{}/{}" Target="{}"/>"#,
+        id, REL_TYPE, kind, target
+    )
+}
+
+fn relationships_xml(relationships: &[String]) -> String {
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">{}</Relationships>"#,
+        relationships.concat()
+    )
+}
+
+fn content_types(header: bool, footer: bool) -> String {
+    let part = |name: &str, kind: &str| {
+        format!(
+            r#"<Override PartName="{}" ContentType="application/vnd.openxmlformats-officedocument.wordprocessingml.{}+xml"/>"#,
+            name, kind
+        )
+    };
+    let mut overrides = vec![
+        part("/word/document.xml", "document.main"),
+        part("/word/styles.xml", "styles"),
+        part("/word/numbering.xml", "numbering"),
+        part("/word/settings.xml", "settings"),
+        r#"<Override PartName="/docProps/core.xml" ContentType="application/vnd.openxmlformats-package.core-properties+xml"/>"#.to_string(),
+    ];
+    if header {
+        overrides.push(part("/word/header1.xml", "header"));
+    }
+    if footer {
+        overrides.push(part("/word/footer1.xml", "footer"));
+    }
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Types xmlns="http://schemas.openxmlformats.org/package/2006/content-types"><Default Extension="rels" ContentType="application/vnd.openxmlformats-package.relationships+xml"/><Default Extension="xml" ContentType="application/xml"/>{}</Types>"#,
+        overrides.concat()
+    )
+}
+
+fn package_rels() -> String {
+    relationships_xml(&[
+        relationship("rId1", "officeDocument", "word/document.xml"),
+        r#"<Relationship Id="rId2" Type="http://schemas.openxmlformats.org/package/2006/relationships/metadata/core-properties" Target="docProps/core.xml"/>"#.to_string(),
+    ])
+}
+
+fn core_properties(title: &str) -> String {
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<cp:coreProperties xmlns:cp="http://schemas.openxmlformats.org/package/2006/metadata/core-properties" xmlns:dc="http://purl.org/dc/elements/1.1/"><dc:title>{}</dc:title><dc:creator>Corpus Review</dc:creator></cp:coreProperties>"#,
+        escape(title)
+    )
+}
+
+fn settings() -> String {
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<w:settings xmlns:w="{}"><w:updateFields w:val="true"/><w:defaultTabStop w:val="708"/></w:settings>"#,
+        NS_W
+    )
+}
+
+fn styles() -> String {
+    let heading = |level: usize, size: u32| {
+        format!(
+            r#"<w:style w:type="paragraph" w:styleId="Heading{0}"><w:name w:val="heading {0}"/><w:basedOn w:val="Normal"/><w:next w:val="Normal"/><w:qFormat/><w:pPr><w:keepNext/><w:spacing w:before="240" w:after="80"/><w:outlineLvl w:val="{1}"/></w:pPr><w:rPr><w:b/><w:sz w:val="{2}"/></w:rPr></w:style>"#,
+            level,
+            level - 1,
+            size
+        )
+    };
+    let toc = |level: usize| {
+        format!(
+            r#"<w:style w:type="paragraph" w:styleId="TOC{0}"><w:name w:val="toc {0}"/><w:basedOn w:val="Normal"/><w:next w:val="Normal"/><w:pPr><w:tabs><w:tab w:val="right" w:leader="dot" w:pos="{1}"/></w:tabs><w:spacing w:after="60"/><w:ind w:left="{2}"/></w:pPr></w:style>"#,
+            level,
+            TEXT_WIDTH,
+            220 * (level - 1)
+        )
+    };
+
+    let mut styles = String::from(
+        r#"<w:docDefaults><w:rPrDefault><w:rPr><w:rFonts w:ascii="Calibri" w:hAnsi="Calibri" w:cs="Calibri" w:eastAsia="Calibri"/><w:sz w:val="22"/><w:szCs w:val="22"/><w:lang w:val="en-GB"/></w:rPr></w:rPrDefault><w:pPrDefault><w:pPr><w:spacing w:after="120" w:line="276" w:lineRule="auto"/></w:pPr></w:pPrDefault></w:docDefaults>"#,
+    );
+    styles.push_str(r#"<w:style w:type="paragraph" w:default="1" w:styleId="Normal"><w:name w:val="Normal"/><w:qFormat/></w:style>"#);
+    for (level, size) in [(1, 32), (2, 28), (3, 26), (4, 24), (5, 22), (6, 22)] {
+        styles.push_str(&heading(level, size));
+    }
+    styles.push_str(r#"<w:style w:type="paragraph" w:styleId="Quote"><w:name w:val="Quote"/><w:basedOn w:val="Normal"/><w:qFormat/><w:pPr><w:pBdr><w:left w:val="single" w:sz="12" w:space="8" w:color="CCCCCC"/></w:pBdr><w:ind w:left="567"/></w:pPr><w:rPr><w:i/><w:color w:val="555555"/></w:rPr></w:style>"#);
+    styles.push_str(r#"<w:style w:type="paragraph" w:styleId="Code"><w:name w:val="Code"/><w:basedOn w:val="Normal"/><w:pPr><w:shd w:val="clear" w:color="auto" w:fill="F5F5F5"/><w:spacing w:after="120" w:line="240" w:lineRule="auto"/></w:pPr><w:rPr><w:rFonts w:ascii="Consolas" w:hAnsi="Consolas" w:cs="Consolas"/><w:sz w:val="19"/></w:rPr></w:style>"#);
+    styles.push_str(r#"<w:style w:type="paragraph" w:styleId="TOCHeading"><w:name w:val="TOC Heading"/><w:basedOn w:val="Normal"/><w:next w:val="Normal"/><w:pPr><w:keepNext/><w:spacing w:before="240" w:after="120"/></w:pPr><w:rPr><w:b/><w:sz w:val="28"/></w:rPr></w:style>"#);
+    for level in 1..=6 {
+        styles.push_str(&toc(level));
+    }
+    styles.push_str(r#"<w:style w:type="paragraph" w:styleId="SectionHeading"><w:name w:val="Section Heading"/><w:basedOn w:val="TOCHeading"/><w:next w:val="Normal"/></w:style>"#);
+    styles.push_str(r#"<w:style w:type="paragraph" w:styleId="Source"><w:name w:val="Source"/><w:basedOn w:val="Normal"/><w:pPr><w:spacing w:after="60"/><w:ind w:left="567" w:hanging="567"/></w:pPr><w:rPr><w:sz w:val="20"/></w:rPr></w:style>"#);
+    styles.push_str(r#"<w:style w:type="paragraph" w:styleId="Header"><w:name w:val="header"/><w:basedOn w:val="Normal"/><w:pPr><w:spacing w:after="0"/></w:pPr><w:rPr><w:color w:val="666666"/><w:sz w:val="18"/></w:rPr></w:style>"#);
+    styles.push_str(r#"<w:style w:type="paragraph" w:styleId="Footer"><w:name w:val="footer"/><w:basedOn w:val="Header"/></w:style>"#);
+    styles.push_str(r#"<w:style w:type="character" w:styleId="Hyperlink"><w:name w:val="Hyperlink"/><w:rPr><w:color w:val="0563C1"/><w:u w:val="single"/></w:rPr></w:style>"#);
+    styles.push_str(r#"<w:style w:type="table" w:styleId="TableGrid"><w:name w:val="Table Grid"/><w:tblPr><w:tblBorders><w:top w:val="single" w:sz="4" w:space="0" w:color="BBBBBB"/><w:left w:val="single" w:sz="4" w:space="0" w:color="BBBBBB"/><w:bottom w:val="single" w:sz="4" w:space="0" w:color="BBBBBB"/><w:right w:val="single" w:sz="4" w:space="0" w:color="BBBBBB"/><w:insideH w:val="single" w:sz="4" w:space="0" w:color="BBBBBB"/><w:insideV w:val="single" w:sz="4" w:space="0" w:color="BBBBBB"/></w:tblBorders><w:tblCellMar><w:left w:w="108" w:type="dxa"/><w:right w:w="108" w:type="dxa"/></w:tblCellMar></w:tblPr></w:style>"#);
+
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<w:styles xmlns:w="{}">{}</w:styles>"#,
+        NS_W, styles
+    )
+}
+
+fn escape(text: &str) -> String {
+    let mut output = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => output.push_str("&amp;"),
+            '<' => output.push_str("&lt;"),
+            '>' => output.push_str("&gt;"),
+            '"' => output.push_str("&quot;"),
+            // Control characters are not allowed in XML 1.0
+            c if c.is_control() && c != '\t' => {}
+            _ => output.push(c),
+        }
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::det::{DetDocument, DetKind, ProcessingMetadata};
+    use crate::domain::export::{CitedSource, ExportOptions, PageTemplate};
+    use crate::domain::extraction::DocumentExtractor;
+    use crate::infrastructure::extraction::DocxExtractor;
+    use std::io::Read;
+
+    fn paragraph(text: &str) -> PmNode {
+        PmNode::paragraph(PmNode::text_nodes(text, Vec::new()))
+    }
+
+    fn export() -> Vec<u8> {
+        let source = CitedSource::new("/corpus/invoice.pdf").with_page(4);
+        let det = DetDocument::new(
+            DetKind::Report,
+            PmNode::doc(vec![
+                PmNode::heading(1, PmNode::text_nodes("Findings", Vec::new())),
+                PmNode::paragraph(vec![
+                    PmNode::text("Costs rose", vec![PmMark::bold(), source.to_mark()]),
+                    PmNode::text(" by 5 % & more", Vec::new()),
+                ]),
+                PmNode::bullet_list(vec![
+                    PmNode::list_item(vec![paragraph("Labour")]),
+                    PmNode::list_item(vec![paragraph("Materials")]),
+                ]),
+                PmNode::ordered_list(vec![PmNode::list_item(vec![paragraph("First")])], 4),
+                PmNode::table(vec![
+                    PmNode::table_row(vec![
+                        PmNode::table_header(vec![paragraph("Item")]).with_attr("rowspan", 2),
+                        PmNode::table_header(vec![paragraph("Cost")]),
+                    ]),
+                    PmNode::table_row(vec![PmNode::table_cell(vec![paragraph("10")])]),
+                ]),
+            ]),
+            ProcessingMetadata::completed("manual"),
+        )
+        .with_title("Cost report");
+        let options = ExportOptions {
+            include_toc: true,
+            template: PageTemplate {
+                header: Some("{title}".to_string()),
+                footer: Some("Page {page} of {pages}".to_string()),
+            },
+            ..ExportOptions::default()
+        };
+        let document = ExportDocument::new(&det, "report", options, "2024-09-24");
+        DocxExporter.export(&document).unwrap()
+    }
+
+    fn part(bytes: &[u8], name: &str) -> String {
+        let mut archive = zip::ZipArchive::new(Cursor::new(bytes)).unwrap();
+        let mut xml = String::new();
+        archive
+            .by_name(name)
+            .unwrap()
+            .read_to_string(&mut xml)
+            .unwrap();
+        xml
+    }
+
+    #[test]
+    fn test_writes_package_parts() {
+        let bytes = export();
+
+        let document = part(&bytes, "word/document.xml");
+        assert!(document.contains(
+            r#"<w:instrText xml:space="preserve"> TOC \o "1-3" \h \z \u </w:instrText>"#
+        ));
+        assert!(document.contains(r#"<w:hyperlink w:anchor="_Toc1" w:history="1"><w:r><w:t xml:space="preserve">Findings</w:t></w:r></w:hyperlink>"#));
+        assert!(document.contains(r#"<w:hyperlink w:anchor="_Source1">"#));
+        assert!(
+            document.contains(r#"w:name="_Source1"/><w:r><w:t xml:space="preserve">[1] </w:t>"#)
+        );
+        assert!(document.contains("5 % &amp; more"));
+        assert!(document.contains(r#"<w:vMerge w:val="restart"/>"#));
+        assert!(document
+            .contains(r#"<w:tc><w:tcPr><w:gridSpan w:val="1"/><w:vMerge/></w:tcPr><w:p/></w:tc>"#));
+
+        let numbering = part(&bytes, "word/numbering.xml");
+        assert!(numbering.contains(r#"<w:num w:numId="2"><w:abstractNumId w:val="1"/><w:lvlOverride w:ilvl="0"><w:startOverride w:val="4"/>"#));
+
+        let footer = part(&bytes, "word/footer1.xml");
+        assert!(footer.contains(r#"<w:fldSimple w:instr=" PAGE ">"#));
+        assert!(footer.contains(r#"<w:fldSimple w:instr=" NUMPAGES ">"#));
+
+        let rels = part(&bytes, "word/_rels/document.xml.rels");
+        assert!(
+            rels.contains(r#"Target="file:///corpus/invoice.pdf#page=4" TargetMode="External""#)
+        );
+        assert!(part(&bytes, "docProps/core.xml").contains("<dc:title>Cost report</dc:title>"));
+    }
+
+    #[test]
+    fn test_output_reads_back_with_docx_extractor() {
+        let extracted = DocxExtractor.extract(&export()).unwrap();
+        let blocks = &extracted.content.content;
+
+        let heading = blocks
+            .iter()
+            .find(|block| block.node_type == "heading" && block.plain_text() == "Findings")
+            .unwrap();
+        assert_eq!(heading.attr("level").unwrap(), 1);
+        assert!(blocks.iter().any(|block| block.node_type == "bulletList"));
+        let ordered = blocks
+            .iter()
+            .find(|block| block.node_type == "orderedList")
+            .unwrap();
+        assert_eq!(ordered.plain_text(), "First");
+        assert!(blocks.iter().any(|block| block.node_type == "table"));
+        assert!(extracted
+            .content
+            .plain_text()
+            .contains("Costs rose[1] by 5 % & more"));
+    }
+}
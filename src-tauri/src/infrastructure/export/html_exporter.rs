@@ -0,0 +1,331 @@
+use crate::domain::det::PmNode;
+use crate::domain::export::{DocumentExporter, ExportDocument, ExportError, ExportFormat};
+
+/// Print-friendly styles embedded in every exported page
+const STYLESHEET: &str = "\
+body { font-family: Helvetica, Arial, sans-serif; font-size: 11pt; line-height: 1.5; color: #222; max-width: 48em; margin: 2em auto; padding: 0 1em; }
+h1, h2, h3, h4, h5, h6 { line-height: 1.25; margin: 1.4em 0 0.5em; }
+blockquote { margin: 1em 0; padding-left: 1em; border-left: 3px solid #ccc; color: #555; }
+pre { background: #f5f5f5; padding: 0.75em; overflow-x: auto; }
+code { font-family: Consolas, Menlo, monospace; font-size: 0.95em; }
+table { border-collapse: collapse; margin: 1em 0; }
+th, td { border: 1px solid #bbb; padding: 0.3em 0.6em; text-align: left; vertical-align: top; }
+th { background: #f0f0f0; }
+.page-header, .page-footer { color: #666; font-size: 9pt; white-space: pre-line; }
+.page-header { border-bottom: 1px solid #ddd; padding-bottom: 0.5em; margin-bottom: 1.5em; }
+.page-footer { border-top: 1px solid #ddd; padding-top: 0.5em; margin-top: 2em; }
+.toc ul { list-style: none; padding-left: 1.2em; }
+.toc > ul { padding-left: 0; }
+.citation { font-size: 0.75em; vertical-align: super; line-height: 0; }
+.citation a { text-decoration: none; }
+@media print { body { margin: 0; max-width: none; } a { color: inherit; } }
+";
+
+/// Renders `.det` content as a standalone HTML page
+///
+/// Styles are inlined so the file can be mailed or opened without the
+/// application. Citations link to a numbered source list whose entries
+/// link to the source files.
+pub struct HtmlExporter;
+
+impl DocumentExporter for HtmlExporter {
+    fn format(&self) -> ExportFormat {
+        ExportFormat::Html
+    }
+
+    fn export(&self, document: &ExportDocument) -> Result<Vec<u8>, ExportError> {
+        let mut renderer = Renderer {
+            document,
+            heading_index: 0,
+        };
+        let mut html = String::new();
+
+        html.push_str("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n");
+        html.push_str(&format!("<title>{}</title>\n", escape(&document.title)));
+        html.push_str(&format!("<style>\n{}</style>\n", STYLESHEET));
+        html.push_str("</head>\n<body>\n");
+
+        let fields = document.page_fields(None);
+        if let Some(header) = document.options.template.header(&fields) {
+            html.push_str(&format!(
+                "<header class=\"page-header\">{}</header>\n",
+                escape(&header)
+            ));
+        }
+
+        let toc = document.toc();
+        if !toc.is_empty() {
+            html.push_str("<nav class=\"toc\">\n<h2>Contents</h2>\n");
+            let min_level = toc.iter().map(|entry| entry.level).min().unwrap_or(1);
+            let mut depth = min_level - 1;
+            for entry in toc {
+                while depth < entry.level {
+                    html.push_str("<ul>\n");
+                    depth += 1;
+                }
+                while depth > entry.level {
+                    html.push_str("</ul>\n");
+                    depth -= 1;
+                }
+                html.push_str(&format!(
+                    "<li><a href=\"#{}\">{}</a></li>\n",
+                    entry.anchor,
+                    escape(&entry.text)
+                ));
+            }
+            while depth >= min_level {
+                html.push_str("</ul>\n");
+                depth -= 1;
+            }
+            html.push_str("</nav>\n");
+        }
+
+        html.push_str("<main>\n");
+        for node in &document.content.content {
+            renderer.block(node, &mut html);
+        }
+        html.push_str("</main>\n");
+
+        let sources = document.listed_sources();
+        if !sources.is_empty() {
+            html.push_str("<section class=\"sources\">\n<h2>Sources</h2>\n<ol>\n");
+            for (index, source) in sources.iter().enumerate() {
+                html.push_str(&format!(
+                    "<li id=\"source-{}\"><a href=\"{}\">{}</a></li>\n",
+                    index + 1,
+                    escape(&source.file_url()),
+                    escape(&source.display_label())
+                ));
+            }
+            html.push_str("</ol>\n</section>\n");
+        }
+
+        if let Some(footer) = document.options.template.footer(&fields) {
+            html.push_str(&format!(
+                "<footer class=\"page-footer\">{}</footer>\n",
+                escape(&footer)
+            ));
+        }
+
+        html.push_str("</body>\n</html>\n");
+        Ok(html.into_bytes())
+    }
+}
+
+struct Renderer<'a> {
+    document: &'a ExportDocument,
+    heading_index: usize,
+}
+
+impl Renderer<'_> {
+    fn block(&mut self, node: &PmNode, html: &mut String) {
+        match node.node_type.as_str() {
+            "paragraph" => {
+                html.push_str("<p>");
+                self.inlines(&node.content, html);
+                html.push_str("</p>\n");
+            }
+            "heading" => {
+                let level = node
+                    .attr("level")
+                    .and_then(|l| l.as_u64())
+                    .unwrap_or(1)
+                    .clamp(1, 6);
+                match self.document.heading_anchor(self.heading_index) {
+                    Some(anchor) => html.push_str(&format!("<h{} id=\"{}\">", level, anchor)),
+                    None => html.push_str(&format!("<h{}>", level)),
+                }
+                self.heading_index += 1;
+                self.inlines(&node.content, html);
+                html.push_str(&format!("</h{}>\n", level));
+            }
+            "blockquote" => self.wrap("blockquote", node, html),
+            "codeBlock" => {
+                match node.attr("language").and_then(|l| l.as_str()) {
+                    Some(language) => html.push_str(&format!(
+                        "<pre><code class=\"language-{}\">",
+                        escape(language)
+                    )),
+                    None => html.push_str("<pre><code>"),
+                }
+                html.push_str(&escape(&node.plain_text()));
+                html.push_str("</code></pre>\n");
+            }
+            "horizontalRule" => html.push_str("<hr>\n"),
+            "bulletList" => self.wrap("ul", node, html),
+            "orderedList" => match node.attr("start").and_then(|s| s.as_u64()) {
+                Some(start) if start != 1 => {
+                    html.push_str(&format!("<ol start=\"{}\">\n", start));
+                    self.children(node, html);
+                    html.push_str("</ol>\n");
+                }
+                _ => self.wrap("ol", node, html),
+            },
+            "listItem" => self.wrap("li", node, html),
+            "table" => self.wrap("table", node, html),
+            "tableRow" => self.wrap("tr", node, html),
+            "tableCell" | "tableHeader" => {
+                let tag = if node.node_type == "tableHeader" {
+                    "th"
+                } else {
+                    "td"
+                };
+                html.push('<');
+                html.push_str(tag);
+                for span in ["colspan", "rowspan"] {
+                    if let Some(value) = node.attr(span).and_then(|v| v.as_u64()).filter(|v| *v > 1)
+                    {
+                        html.push_str(&format!(" {}=\"{}\"", span, value));
+                    }
+                }
+                html.push('>');
+                // A single paragraph renders without its <p> to keep
+                // cells compact
+                match node.content.as_slice() {
+                    [only] if only.node_type == "paragraph" => self.inlines(&only.content, html),
+                    _ => self.children(node, html),
+                }
+                html.push_str(&format!("</{}>\n", tag));
+            }
+            _ if node.content.iter().any(PmNode::is_text) => {
+                html.push_str("<p>");
+                self.inlines(&node.content, html);
+                html.push_str("</p>\n");
+            }
+            _ => self.children(node, html),
+        }
+    }
+
+    fn wrap(&mut self, tag: &str, node: &PmNode, html: &mut String) {
+        html.push_str(&format!("<{}>\n", tag));
+        self.children(node, html);
+        html.push_str(&format!("</{}>\n", tag));
+    }
+
+    fn children(&mut self, node: &PmNode, html: &mut String) {
+        for child in &node.content {
+            self.block(child, html);
+        }
+    }
+
+    fn inlines(&self, nodes: &[PmNode], html: &mut String) {
+        for (index, node) in nodes.iter().enumerate() {
+            if node.node_type == "hardBreak" {
+                html.push_str("<br>");
+            } else if let Some(text) = &node.text {
+                let mut open = String::new();
+                let mut close = Vec::new();
+                for mark in &node.marks {
+                    let attr = |key: &str| {
+                        mark.attrs
+                            .as_ref()
+                            .and_then(|attrs| attrs.get(key))
+                            .and_then(|v| v.as_str())
+                    };
+                    let (tag, attributes) = match mark.mark_type.as_str() {
+                        "bold" => ("strong", String::new()),
+                        "italic" => ("em", String::new()),
+                        "underline" => ("u", String::new()),
+                        "strike" => ("s", String::new()),
+                        "code" => ("code", String::new()),
+                        "highlight" => (
+                            "mark",
+                            attr("color")
+                                .map(|color| {
+                                    format!(" style=\"background-color: {}\"", escape(color))
+                                })
+                                .unwrap_or_default(),
+                        ),
+                        "link" => (
+                            "a",
+                            format!(" href=\"{}\"", escape(attr("href").unwrap_or_default())),
+                        ),
+                        _ => continue,
+                    };
+                    open.push_str(&format!("<{}{}>", tag, attributes));
+                    close.push(tag);
+                }
+                html.push_str(&open);
+                html.push_str(&escape(text));
+                for tag in close.iter().rev() {
+                    html.push_str(&format!("</{}>", tag));
+                }
+            }
+
+            for number in self.document.citations_after(nodes, index) {
+                html.push_str(&format!(
+                    "<sup class=\"citation\"><a href=\"#source-{0}\">[{0}]</a></sup>",
+                    number
+                ));
+            }
+        }
+    }
+}
+
+fn escape(text: &str) -> String {
+    let mut output = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => output.push_str("&amp;"),
+            '<' => output.push_str("&lt;"),
+            '>' => output.push_str("&gt;"),
+            '"' => output.push_str("&quot;"),
+            '\'' => output.push_str("&#39;"),
+            _ => output.push(c),
+        }
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::det::{DetDocument, DetKind, PmMark, ProcessingMetadata};
+    use crate::domain::export::{CitedSource, ExportOptions, PageTemplate};
+
+    #[test]
+    fn test_renders_standalone_page() {
+        let source = CitedSource::new("/corpus/invoice.pdf");
+        let det = DetDocument::new(
+            DetKind::Report,
+            PmNode::doc(vec![
+                PmNode::heading(1, PmNode::text_nodes("Costs <2024>", Vec::new())),
+                PmNode::paragraph(vec![
+                    PmNode::text("Paid", vec![PmMark::bold(), source.to_mark()]),
+                    PmNode::hard_break(),
+                    PmNode::text("site", vec![PmMark::link("https://example.com/?a=1&b=2")]),
+                ]),
+                PmNode::table(vec![PmNode::table_row(vec![PmNode::table_cell(vec![
+                    PmNode::paragraph(PmNode::text_nodes("10", Vec::new())),
+                ])
+                .with_attr("colspan", 2)])]),
+            ]),
+            ProcessingMetadata::completed("manual"),
+        )
+        .with_title("Findings & notes");
+        let options = ExportOptions {
+            include_toc: true,
+            template: PageTemplate {
+                header: Some("{title} - {date}".to_string()),
+                footer: Some("Page {page}".to_string()),
+            },
+            ..ExportOptions::default()
+        };
+        let document = ExportDocument::new(&det, "findings", options, "2024-09-24");
+        let html = String::from_utf8(HtmlExporter.export(&document).unwrap()).unwrap();
+
+        assert!(html.contains("<title>Findings &amp; notes</title>"));
+        assert!(html
+            .contains("<header class=\"page-header\">Findings &amp; notes - 2024-09-24</header>"));
+        assert!(!html.contains("page-footer\">"));
+        assert!(html.contains("<li><a href=\"#costs-2024\">Costs &lt;2024&gt;</a></li>"));
+        assert!(html.contains("<h1 id=\"costs-2024\">Costs &lt;2024&gt;</h1>"));
+        assert!(html.contains(
+            "<p><strong>Paid</strong><sup class=\"citation\"><a href=\"#source-1\">[1]</a></sup><br><a href=\"https://example.com/?a=1&amp;b=2\">site</a></p>"
+        ));
+        assert!(html.contains("<td colspan=\"2\">10</td>"));
+        assert!(html.contains(
+            "<li id=\"source-1\"><a href=\"file:///corpus/invoice.pdf\">invoice.pdf</a></li>"
+        ));
+    }
+}
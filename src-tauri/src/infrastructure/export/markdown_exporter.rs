@@ -0,0 +1,394 @@
+use crate::domain::det::{PmMark, PmNode};
+use crate::domain::export::{DocumentExporter, ExportDocument, ExportError, ExportFormat};
+
+/// Renders `.det` content as CommonMark with GitHub tables and footnotes
+///
+/// Citations become footnotes linking to the source file. Headings get an
+/// HTML anchor when a table of contents links to them, since renderers
+/// disagree on how they derive heading ids.
+pub struct MarkdownExporter;
+
+impl DocumentExporter for MarkdownExporter {
+    fn format(&self) -> ExportFormat {
+        ExportFormat::Markdown
+    }
+
+    fn export(&self, document: &ExportDocument) -> Result<Vec<u8>, ExportError> {
+        let mut renderer = Renderer {
+            document,
+            heading_index: 0,
+        };
+        let mut sections = Vec::new();
+
+        let fields = document.page_fields(None);
+        if let Some(header) = document.options.template.header(&fields) {
+            sections.push(escape_lines(&header));
+        }
+
+        let toc = document.toc();
+        if !toc.is_empty() {
+            let min_level = toc.iter().map(|entry| entry.level).min().unwrap_or(1);
+            let mut lines = vec!["**Contents**".to_string(), String::new()];
+            for entry in toc {
+                lines.push(format!(
+                    "{}- [{}](#{})",
+                    "  ".repeat(usize::from(entry.level - min_level)),
+                    escape(&entry.text),
+                    entry.anchor
+                ));
+            }
+            sections.push(lines.join("\n"));
+        }
+
+        sections.extend(renderer.blocks(&document.content.content));
+
+        let sources = document.listed_sources();
+        if !sources.is_empty() {
+            let notes: Vec<String> = sources
+                .iter()
+                .enumerate()
+                .map(|(index, source)| {
+                    format!(
+                        "[^{}]: [{}](<{}>)",
+                        index + 1,
+                        escape(&source.display_label()),
+                        source.file_url()
+                    )
+                })
+                .collect();
+            sections.push(notes.join("\n"));
+        }
+
+        if let Some(footer) = document.options.template.footer(&fields) {
+            sections.push(format!("---\n\n{}", escape_lines(&footer)));
+        }
+
+        let mut output = sections
+            .into_iter()
+            .filter(|section| !section.is_empty())
+            .collect::<Vec<_>>()
+            .join("\n\n");
+        output.push('\n');
+        Ok(output.into_bytes())
+    }
+}
+
+struct Renderer<'a> {
+    document: &'a ExportDocument,
+    heading_index: usize,
+}
+
+impl Renderer<'_> {
+    fn blocks(&mut self, nodes: &[PmNode]) -> Vec<String> {
+        nodes.iter().map(|node| self.block(node)).collect()
+    }
+
+    fn block(&mut self, node: &PmNode) -> String {
+        match node.node_type.as_str() {
+            "heading" => {
+                let level = node.attr("level").and_then(|l| l.as_u64()).unwrap_or(1);
+                let anchor = self.document.heading_anchor(self.heading_index);
+                self.heading_index += 1;
+
+                let heading = format!(
+                    "{} {}",
+                    "#".repeat(level.clamp(1, 6) as usize),
+                    self.inlines(&node.content, false)
+                );
+                match anchor {
+                    Some(anchor) if self.document.options.include_toc => {
+                        format!("<a id=\"{}\"></a>\n{}", anchor, heading)
+                    }
+                    _ => heading,
+                }
+            }
+            "blockquote" => prefix_lines(&self.blocks(&node.content).join("\n\n"), "> ", "> "),
+            "codeBlock" => {
+                let code = node.plain_text();
+                let fence = if code.contains("```") { "~~~" } else { "```" };
+                let language = node
+                    .attr("language")
+                    .and_then(|l| l.as_str())
+                    .unwrap_or_default();
+                format!("{}{}\n{}\n{}", fence, language, code, fence)
+            }
+            "horizontalRule" => "---".to_string(),
+            "bulletList" => self.list(node, None),
+            "orderedList" => {
+                let start = node.attr("start").and_then(|s| s.as_u64()).unwrap_or(1);
+                self.list(node, Some(start))
+            }
+            "table" => self.table(node),
+            _ if node.content.iter().any(PmNode::is_text) || node.content.is_empty() => {
+                self.inlines(&node.content, false)
+            }
+            _ => self.blocks(&node.content).join("\n\n"),
+        }
+    }
+
+    fn list(&mut self, node: &PmNode, start: Option<u64>) -> String {
+        let mut items = Vec::new();
+        for (index, item) in node.content.iter().enumerate() {
+            let marker = match start {
+                Some(start) => format!("{}. ", start + index as u64),
+                None => "- ".to_string(),
+            };
+
+            // Nested lists follow their paragraph directly; further
+            // paragraphs need a blank line
+            let mut body = String::new();
+            for (position, child) in item.content.iter().enumerate() {
+                if position > 0 {
+                    let is_list = matches!(child.node_type.as_str(), "bulletList" | "orderedList");
+                    body.push_str(if is_list { "\n" } else { "\n\n" });
+                }
+                body.push_str(&self.block(child));
+            }
+            let indent = " ".repeat(marker.len());
+            items.push(prefix_lines(&body, &marker, &indent));
+        }
+        items.join("\n")
+    }
+
+    fn table(&mut self, node: &PmNode) -> String {
+        let rows: Vec<Vec<String>> = node
+            .content
+            .iter()
+            .map(|row| {
+                let mut cells = Vec::new();
+                for cell in &row.content {
+                    let text = cell
+                        .content
+                        .iter()
+                        .map(|block| self.inlines(&block.content, true))
+                        .collect::<Vec<_>>()
+                        .join("<br>");
+                    cells.push(text);
+                    let colspan = cell.attr("colspan").and_then(|c| c.as_u64()).unwrap_or(1);
+                    cells.extend((1..colspan).map(|_| String::new()));
+                }
+                cells
+            })
+            .collect();
+
+        let columns = rows.iter().map(Vec::len).max().unwrap_or(0).max(1);
+        let line = |cells: &[String]| {
+            let mut padded: Vec<&str> = cells.iter().map(String::as_str).collect();
+            padded.resize(columns, "");
+            format!("| {} |", padded.join(" | "))
+        };
+
+        let mut lines = Vec::new();
+        let empty = Vec::new();
+        lines.push(line(rows.first().unwrap_or(&empty)));
+        lines.push(format!("|{}", " --- |".repeat(columns)));
+        for row in rows.iter().skip(1) {
+            lines.push(line(row));
+        }
+        lines.join("\n")
+    }
+
+    fn inlines(&mut self, nodes: &[PmNode], in_table: bool) -> String {
+        let mut output = String::new();
+        for (index, node) in nodes.iter().enumerate() {
+            if node.node_type == "hardBreak" {
+                output.push_str(if in_table { "<br>" } else { "\\\n" });
+                continue;
+            }
+            if let Some(text) = &node.text {
+                output.push_str(&mark_text(text, &node.marks));
+            }
+            for number in self.document.citations_after(nodes, index) {
+                output.push_str(&format!("[^{}]", number));
+            }
+        }
+        output
+    }
+}
+
+/// Apply marks to one text node
+///
+/// Emphasis delimiters must touch non-space characters, so surrounding
+/// whitespace is moved outside them.
+fn mark_text(text: &str, marks: &[PmMark]) -> String {
+    let has = |name: &str| marks.iter().any(|mark| mark.mark_type == name);
+
+    let trimmed = text.trim();
+    if trimmed.is_empty() {
+        return escape(text);
+    }
+    let leading = &text[..text.len() - text.trim_start().len()];
+    let trailing = &text[text.trim_end().len()..];
+
+    let mut inner = if !has("code") {
+        escape(trimmed)
+    } else if trimmed.contains('`') {
+        format!("`` {} ``", trimmed)
+    } else {
+        format!("`{}`", trimmed)
+    };
+    if has("italic") {
+        inner = format!("*{}*", inner);
+    }
+    if has("bold") {
+        inner = format!("**{}**", inner);
+    }
+    if has("strike") {
+        inner = format!("~~{}~~", inner);
+    }
+    if has("underline") {
+        inner = format!("<u>{}</u>", inner);
+    }
+    if has("highlight") {
+        inner = format!("<mark>{}</mark>", inner);
+    }
+    if let Some(href) = marks
+        .iter()
+        .find(|mark| mark.mark_type == "link")
+        .and_then(|mark| mark.attrs.as_ref()?.get("href")?.as_str())
+    {
+        inner = format!("[{}](<{}>)", inner, href.replace('>', "%3E"));
+    }
+
+    format!("{}{}{}", leading, inner, trailing)
+}
+
+/// Escape characters with a meaning in Markdown
+fn escape(text: &str) -> String {
+    let mut output = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(
+            c,
+            '\\' | '`' | '*' | '_' | '[' | ']' | '<' | '>' | '#' | '|' | '~'
+        ) {
+            output.push('\\');
+        }
+        output.push(c);
+    }
+    output
+}
+
+/// Escape template text, keeping its line breaks
+fn escape_lines(text: &str) -> String {
+    text.lines().map(escape).collect::<Vec<_>>().join("  \n")
+}
+
+/// Prefix the first line with `first` and the others with `rest`
+fn prefix_lines(text: &str, first: &str, rest: &str) -> String {
+    text.split('\n')
+        .enumerate()
+        .map(|(index, line)| {
+            let prefix = if index == 0 { first } else { rest };
+            if line.is_empty() {
+                prefix.trim_end().to_string()
+            } else {
+                format!("{}{}", prefix, line)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::det::{DetDocument, DetKind, ProcessingMetadata};
+    use crate::domain::export::{CitedSource, ExportOptions, PageTemplate};
+
+    fn export(content: Vec<PmNode>, options: ExportOptions) -> String {
+        let det = DetDocument::new(
+            DetKind::Report,
+            PmNode::doc(content),
+            ProcessingMetadata::completed("manual"),
+        )
+        .with_title("Findings");
+        let document = ExportDocument::new(&det, "findings", options, "2024-09-24");
+        String::from_utf8(MarkdownExporter.export(&document).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_renders_structure_and_marks() {
+        let markdown = export(
+            vec![
+                PmNode::heading(2, PmNode::text_nodes("Costs", Vec::new())),
+                PmNode::paragraph(vec![
+                    PmNode::text("Paid ", Vec::new()),
+                    PmNode::text("in full ", vec![PmMark::bold()]),
+                    PmNode::text("x*y", vec![PmMark::code()]),
+                ]),
+                PmNode::ordered_list(
+                    vec![
+                        PmNode::list_item(vec![
+                            PmNode::paragraph(PmNode::text_nodes("One", Vec::new())),
+                            PmNode::bullet_list(vec![PmNode::list_item(vec![PmNode::paragraph(
+                                PmNode::text_nodes("Nested", Vec::new()),
+                            )])]),
+                        ]),
+                        PmNode::list_item(vec![PmNode::paragraph(PmNode::text_nodes(
+                            "Two",
+                            Vec::new(),
+                        ))]),
+                    ],
+                    3,
+                ),
+                PmNode::table(vec![
+                    PmNode::table_row(vec![
+                        PmNode::table_header(vec![PmNode::paragraph(PmNode::text_nodes(
+                            "Item",
+                            Vec::new(),
+                        ))]),
+                        PmNode::table_header(vec![PmNode::paragraph(PmNode::text_nodes(
+                            "Cost",
+                            Vec::new(),
+                        ))]),
+                    ]),
+                    PmNode::table_row(vec![
+                        PmNode::table_cell(vec![PmNode::paragraph(PmNode::text_nodes(
+                            "A|B",
+                            Vec::new(),
+                        ))]),
+                        PmNode::table_cell(vec![PmNode::paragraph(PmNode::text_nodes(
+                            "10",
+                            Vec::new(),
+                        ))]),
+                    ]),
+                ]),
+            ],
+            ExportOptions::default(),
+        );
+
+        assert!(markdown.starts_with("## Costs\n\nPaid **in full** `x*y`\n\n"));
+        assert!(markdown.contains("3. One\n   - Nested\n4. Two"));
+        assert!(markdown.contains("| Item | Cost |\n| --- | --- |\n| A\\|B | 10 |"));
+    }
+
+    #[test]
+    fn test_renders_citations_toc_and_template() {
+        let source = CitedSource::new("/corpus/invoice 1.pdf").with_page(2);
+        let options = ExportOptions {
+            include_toc: true,
+            template: PageTemplate {
+                header: Some("{title}\nPage {page}".to_string()),
+                footer: Some("Prepared {date}".to_string()),
+            },
+            ..ExportOptions::default()
+        };
+        let markdown = export(
+            vec![
+                PmNode::heading(1, PmNode::text_nodes("Summary", Vec::new())),
+                PmNode::paragraph(vec![
+                    PmNode::text("Costs rose", vec![source.to_mark()]),
+                    PmNode::text(".", Vec::new()),
+                ]),
+            ],
+            options,
+        );
+
+        assert!(markdown.starts_with("Findings\n\n**Contents**\n\n- [Summary](#summary)\n\n"));
+        assert!(markdown.contains("<a id=\"summary\"></a>\n# Summary"));
+        assert!(markdown.contains("Costs rose[^1]."));
+        assert!(markdown
+            .contains("[^1]: [invoice 1.pdf, p. 2](<file:///corpus/invoice%201.pdf#page=2>)"));
+        assert!(markdown.ends_with("---\n\nPrepared 2024-09-24\n"));
+    }
+}
@@ -0,0 +1,51 @@
+pub mod docx_exporter;
+pub mod html_exporter;
+pub mod markdown_exporter;
+pub mod pdf_exporter;
+
+pub use docx_exporter::DocxExporter;
+pub use html_exporter::HtmlExporter;
+pub use markdown_exporter::MarkdownExporter;
+pub use pdf_exporter::PdfExporter;
+
+use std::sync::Arc;
+
+use crate::domain::export::{DocumentExporter, ExportFormat};
+
+/// Looks up the exporter for an output format
+#[derive(Clone, Default)]
+pub struct ExporterRegistry {
+    exporters: Vec<Arc<dyn DocumentExporter>>,
+}
+
+impl ExporterRegistry {
+    /// Create a registry with all built-in exporters
+    pub fn with_defaults() -> Self {
+        let mut registry = Self::default();
+        registry.register(Arc::new(MarkdownExporter));
+        registry.register(Arc::new(HtmlExporter));
+        registry.register(Arc::new(DocxExporter));
+        registry.register(Arc::new(PdfExporter));
+        registry
+    }
+
+    /// Add an exporter; later registrations take precedence
+    pub fn register(&mut self, exporter: Arc<dyn DocumentExporter>) {
+        self.exporters.insert(0, exporter);
+    }
+
+    pub fn for_format(&self, format: ExportFormat) -> Option<Arc<dyn DocumentExporter>> {
+        self.exporters
+            .iter()
+            .find(|exporter| exporter.format() == format)
+            .cloned()
+    }
+
+    /// Formats that can be exported, in declaration order
+    pub fn formats(&self) -> Vec<ExportFormat> {
+        ExportFormat::ALL
+            .into_iter()
+            .filter(|format| self.for_format(*format).is_some())
+            .collect()
+    }
+}
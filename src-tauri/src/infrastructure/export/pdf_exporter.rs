@@ -0,0 +1,1168 @@
+use lopdf::content::{Content, Operation};
+use lopdf::{dictionary, Dictionary, Document, Object, ObjectId, Stream, StringFormat};
+
+use crate::domain::det::PmNode;
+use crate::domain::export::{DocumentExporter, ExportDocument, ExportError, ExportFormat};
+
+/// Renders `.det` content as an A4 PDF
+///
+/// Lays the content out with the standard PDF fonts (Helvetica and
+/// Courier), so no font files are embedded and nothing is fetched. Text
+/// outside Windows-1252 is printed as "?". The table of contents and
+/// citation markers are links within the document; sources link to their
+/// files.
+pub struct PdfExporter;
+
+const PAGE_WIDTH: f32 = 595.0;
+const PAGE_HEIGHT: f32 = 842.0;
+const MARGIN_X: f32 = 64.0;
+const MARGIN_TOP: f32 = 72.0;
+const MARGIN_BOTTOM: f32 = 72.0;
+const TEXT_WIDTH: f32 = PAGE_WIDTH - 2.0 * MARGIN_X;
+
+const BODY_SIZE: f32 = 10.5;
+const CODE_SIZE: f32 = 9.0;
+const TEMPLATE_SIZE: f32 = 8.5;
+const HEADING_SIZES: [f32; 6] = [18.0, 15.0, 13.0, 11.5, 10.5, 10.5];
+/// Line height as a multiple of the font size
+const LEADING: f32 = 1.4;
+/// Indent of list item content and block quotes
+const INDENT: f32 = 16.0;
+/// Padding inside table cells
+const CELL_PADDING: f32 = 4.0;
+
+const BLACK: [f32; 3] = [0.13, 0.13, 0.13];
+const GRAY: [f32; 3] = [0.4, 0.4, 0.4];
+const LIGHT_GRAY: [f32; 3] = [0.75, 0.75, 0.75];
+const LINK_BLUE: [f32; 3] = [0.02, 0.39, 0.76];
+
+impl DocumentExporter for PdfExporter {
+    fn format(&self) -> ExportFormat {
+        ExportFormat::Pdf
+    }
+
+    fn export(&self, document: &ExportDocument) -> Result<Vec<u8>, ExportError> {
+        let mut renderer = Renderer {
+            document,
+            layout: Layout::new(),
+            pending_marker: None,
+        };
+        for node in &document.content.content {
+            renderer.block(node, 0.0, false);
+        }
+        renderer.sources();
+        let body = renderer.layout;
+
+        let (mut pages, slots) = toc_layout(document);
+        let offset = pages.len();
+
+        // Page numbers of the contents are known once both are laid out
+        for (page, baseline, heading) in slots {
+            if let Some((heading_page, _)) = body.headings.get(heading) {
+                let number = (offset + heading_page + 1).to_string();
+                let width = Font::Regular.text_width(&number, BODY_SIZE);
+                pages[page].text(
+                    &number,
+                    MARGIN_X + TEXT_WIDTH - width,
+                    baseline,
+                    &Style::body(),
+                );
+            }
+        }
+
+        let headings: Vec<(usize, f32)> = body
+            .headings
+            .iter()
+            .map(|(page, y)| (page + offset, *y))
+            .collect();
+        let sources: Vec<(usize, f32)> = body
+            .sources
+            .iter()
+            .map(|(page, y)| (page + offset, *y))
+            .collect();
+        pages.extend(body.into_pages());
+
+        let total = pages.len() as u32;
+        for (index, page) in pages.iter_mut().enumerate() {
+            let fields = document.page_fields(Some((index as u32 + 1, total)));
+            if let Some(header) = document.options.template.header(&fields) {
+                page.template(&header, PAGE_HEIGHT - 40.0, true);
+            }
+            if let Some(footer) = document.options.template.footer(&fields) {
+                page.template(&footer, 36.0, false);
+            }
+        }
+
+        assemble(&document.title, pages, &headings, &sources)
+    }
+}
+
+/// The standard fonts used, with their metrics
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Font {
+    Regular,
+    Bold,
+    Italic,
+    BoldItalic,
+    Mono,
+}
+
+/// Helvetica advance widths for ' ' to '~', in 1/1000 em
+const HELVETICA_WIDTHS: [u16; 95] = [
+    278, 278, 355, 556, 556, 889, 667, 191, 333, 333, 389, 584, 278, 333, 278, 278, 556, 556, 556,
+    556, 556, 556, 556, 556, 556, 556, 278, 278, 584, 584, 584, 556, 1015, 667, 667, 722, 722, 667,
+    611, 778, 722, 278, 500, 667, 556, 833, 722, 778, 667, 778, 722, 667, 611, 722, 667, 944, 667,
+    667, 611, 278, 278, 278, 469, 556, 333, 556, 556, 500, 556, 556, 278, 556, 556, 222, 222, 500,
+    222, 833, 556, 556, 556, 556, 333, 500, 278, 556, 500, 722, 500, 500, 500, 334, 260, 334, 584,
+];
+
+/// Helvetica-Bold advance widths for ' ' to '~', in 1/1000 em
+const HELVETICA_BOLD_WIDTHS: [u16; 95] = [
+    278, 333, 474, 556, 556, 889, 722, 238, 333, 333, 389, 584, 278, 333, 278, 278, 556, 556, 556,
+    556, 556, 556, 556, 556, 556, 556, 333, 333, 584, 584, 584, 611, 975, 722, 722, 722, 722, 667,
+    611, 778, 722, 278, 556, 722, 611, 833, 722, 778, 667, 778, 722, 667, 611, 722, 667, 944, 667,
+    667, 611, 333, 278, 333, 584, 556, 333, 556, 611, 556, 611, 556, 333, 611, 611, 278, 278, 556,
+    278, 889, 611, 611, 611, 611, 389, 556, 333, 611, 556, 778, 556, 556, 500, 389, 280, 389, 584,
+];
+
+impl Font {
+    const ALL: [Font; 5] = [
+        Font::Regular,
+        Font::Bold,
+        Font::Italic,
+        Font::BoldItalic,
+        Font::Mono,
+    ];
+
+    fn styled(bold: bool, italic: bool) -> Self {
+        match (bold, italic) {
+            (false, false) => Font::Regular,
+            (true, false) => Font::Bold,
+            (false, true) => Font::Italic,
+            (true, true) => Font::BoldItalic,
+        }
+    }
+
+    fn resource(self) -> &'static str {
+        match self {
+            Font::Regular => "F1",
+            Font::Bold => "F2",
+            Font::Italic => "F3",
+            Font::BoldItalic => "F4",
+            Font::Mono => "F5",
+        }
+    }
+
+    fn base_font(self) -> &'static str {
+        match self {
+            Font::Regular => "Helvetica",
+            Font::Bold => "Helvetica-Bold",
+            Font::Italic => "Helvetica-Oblique",
+            Font::BoldItalic => "Helvetica-BoldOblique",
+            Font::Mono => "Courier",
+        }
+    }
+
+    fn char_width(self, c: char) -> f32 {
+        let table = match self {
+            Font::Mono => return 600.0,
+            Font::Regular | Font::Italic => &HELVETICA_WIDTHS,
+            Font::Bold | Font::BoldItalic => &HELVETICA_BOLD_WIDTHS,
+        };
+        let width = match c {
+            ' '..='~' => table[c as usize - 32],
+            '\u{2022}' => 350,
+            '\u{2018}' | '\u{2019}' | '\u{201A}' => 222,
+            '\u{201C}' | '\u{201D}' | '\u{201E}' => 333,
+            '\u{2014}' | '\u{2026}' | '\u{2030}' => 1000,
+            _ => 556,
+        };
+        f32::from(width)
+    }
+
+    fn text_width(self, text: &str, size: f32) -> f32 {
+        text.chars().map(|c| self.char_width(c)).sum::<f32>() * size / 1000.0
+    }
+}
+
+/// Where a link on the page points
+#[derive(Debug, Clone, PartialEq)]
+enum Target {
+    Uri(String),
+    /// n-th heading of the content
+    Heading(usize),
+    /// 1-based source number
+    Source(usize),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Style {
+    font: Font,
+    size: f32,
+    color: [f32; 3],
+    underline: bool,
+    strike: bool,
+    highlight: bool,
+    /// Baseline shift, for citation markers
+    rise: f32,
+    link: Option<Target>,
+}
+
+impl Style {
+    fn body() -> Self {
+        Style {
+            font: Font::Regular,
+            size: BODY_SIZE,
+            color: BLACK,
+            underline: false,
+            strike: false,
+            highlight: false,
+            rise: 0.0,
+            link: None,
+        }
+    }
+
+    fn with_font(mut self, font: Font, size: f32) -> Self {
+        self.font = font;
+        self.size = size;
+        self
+    }
+}
+
+/// A word, or the part of a word in one style
+#[derive(Debug, Clone)]
+struct Piece {
+    text: String,
+    style: Style,
+    width: f32,
+    /// Width of the space after the piece; 0 if the next piece continues
+    /// the word
+    space: f32,
+    line_break: bool,
+}
+
+impl Piece {
+    fn new(text: &str, style: &Style) -> Self {
+        Piece {
+            width: style.font.text_width(text, style.size),
+            text: text.to_string(),
+            style: style.clone(),
+            space: 0.0,
+            line_break: false,
+        }
+    }
+
+    fn line_break(style: &Style) -> Self {
+        Piece {
+            line_break: true,
+            ..Piece::new("", style)
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct Line {
+    /// Pieces with their offset from the start of the line
+    pieces: Vec<(f32, Piece)>,
+    /// Largest font size on the line
+    size: f32,
+}
+
+impl Line {
+    fn height(&self) -> f32 {
+        self.size * LEADING
+    }
+}
+
+/// Break pieces into lines of at most `width`
+///
+/// Lines break at spaces; a word wider than a line is split between
+/// characters.
+fn wrap(pieces: Vec<Piece>, width: f32, min_size: f32) -> Vec<Line> {
+    let mut lines = Vec::new();
+    let mut line = Line {
+        pieces: Vec::new(),
+        size: min_size,
+    };
+    let mut x = 0.0;
+    let mut word: Vec<Piece> = Vec::new();
+
+    let flush = |word: &mut Vec<Piece>, line: &mut Line, x: &mut f32, lines: &mut Vec<Line>| {
+        let word_width: f32 = word.iter().map(|p| p.width).sum();
+        if *x > 0.0 && *x + word_width > width {
+            lines.push(std::mem::replace(
+                line,
+                Line {
+                    pieces: Vec::new(),
+                    size: min_size,
+                },
+            ));
+            *x = 0.0;
+        }
+        for piece in word.drain(..) {
+            for part in split_to_fit(piece, width) {
+                if *x > 0.0 && *x + part.width > width {
+                    lines.push(std::mem::replace(
+                        line,
+                        Line {
+                            pieces: Vec::new(),
+                            size: min_size,
+                        },
+                    ));
+                    *x = 0.0;
+                }
+                line.size = line.size.max(part.style.size);
+                let advance = part.width + part.space;
+                let line_break = part.line_break;
+                line.pieces.push((*x, part));
+                *x += advance;
+                if line_break {
+                    lines.push(std::mem::replace(
+                        line,
+                        Line {
+                            pieces: Vec::new(),
+                            size: min_size,
+                        },
+                    ));
+                    *x = 0.0;
+                }
+            }
+        }
+    };
+
+    for piece in pieces {
+        let ends_word = piece.space > 0.0 || piece.line_break;
+        word.push(piece);
+        if ends_word {
+            flush(&mut word, &mut line, &mut x, &mut lines);
+        }
+    }
+    flush(&mut word, &mut line, &mut x, &mut lines);
+    if !line.pieces.is_empty() || lines.is_empty() {
+        lines.push(line);
+    }
+    lines
+}
+
+/// Split a piece wider than `width` between characters
+fn split_to_fit(piece: Piece, width: f32) -> Vec<Piece> {
+    if piece.width <= width {
+        return vec![piece];
+    }
+
+    let mut parts = Vec::new();
+    let mut current = String::new();
+    for c in piece.text.chars() {
+        current.push(c);
+        if piece.style.font.text_width(&current, piece.style.size) > width && current.len() > 1 {
+            current.pop();
+            parts.push(Piece::new(&current, &piece.style));
+            current = c.to_string();
+        }
+    }
+    let mut last = Piece::new(&current, &piece.style);
+    last.space = piece.space;
+    last.line_break = piece.line_break;
+    parts.push(last);
+    parts
+}
+
+/// Drawing operations and links of one page
+#[derive(Default)]
+struct Page {
+    operations: Vec<Operation>,
+    links: Vec<([f32; 4], Target)>,
+}
+
+impl Page {
+    fn text(&mut self, text: &str, x: f32, baseline: f32, style: &Style) {
+        let [r, g, b] = style.color;
+        self.operations.extend([
+            Operation::new("BT", vec![]),
+            Operation::new("Tf", vec![style.font.resource().into(), style.size.into()]),
+            Operation::new("rg", vec![r.into(), g.into(), b.into()]),
+            Operation::new("Ts", vec![style.rise.into()]),
+            Operation::new("Td", vec![x.into(), baseline.into()]),
+            Operation::new(
+                "Tj",
+                vec![Object::String(encode(text), StringFormat::Literal)],
+            ),
+            Operation::new("ET", vec![]),
+        ]);
+    }
+
+    fn rule(&mut self, from: (f32, f32), to: (f32, f32), color: [f32; 3], width: f32) {
+        let [r, g, b] = color;
+        self.operations.extend([
+            Operation::new("w", vec![width.into()]),
+            Operation::new("RG", vec![r.into(), g.into(), b.into()]),
+            Operation::new("m", vec![from.0.into(), from.1.into()]),
+            Operation::new("l", vec![to.0.into(), to.1.into()]),
+            Operation::new("S", vec![]),
+        ]);
+    }
+
+    fn rectangle(&mut self, x: f32, y: f32, width: f32, height: f32, fill: Option<[f32; 3]>) {
+        let (color_op, paint) = match fill {
+            Some(_) => ("rg", "f"),
+            None => ("RG", "S"),
+        };
+        let [r, g, b] = fill.unwrap_or(LIGHT_GRAY);
+        self.operations.extend([
+            Operation::new("w", vec![0.5.into()]),
+            Operation::new(color_op, vec![r.into(), g.into(), b.into()]),
+            Operation::new("re", vec![x.into(), y.into(), width.into(), height.into()]),
+            Operation::new(paint, vec![]),
+        ]);
+    }
+
+    /// Draw a wrapped line with its first baseline at `baseline`
+    fn line(&mut self, line: &Line, x: f32, baseline: f32) {
+        for (offset, piece) in &line.pieces {
+            if piece.text.is_empty() {
+                continue;
+            }
+            let left = x + offset;
+            let style = &piece.style;
+            if style.highlight {
+                self.rectangle(
+                    left,
+                    baseline - style.size * 0.25,
+                    piece.width,
+                    style.size * 1.15,
+                    Some([1.0, 0.95, 0.6]),
+                );
+            }
+            self.text(&piece.text, left, baseline, style);
+            if style.underline {
+                let y = baseline - style.size * 0.12;
+                self.rule((left, y), (left + piece.width, y), style.color, 0.5);
+            }
+            if style.strike {
+                let y = baseline + style.size * 0.3;
+                self.rule((left, y), (left + piece.width, y), style.color, 0.5);
+            }
+            if let Some(target) = &style.link {
+                let bottom = baseline + style.rise - style.size * 0.25;
+                self.links.push((
+                    [left, bottom, left + piece.width, bottom + style.size * 1.15],
+                    target.clone(),
+                ));
+            }
+        }
+    }
+
+    /// Header (below the top edge) or footer (above the bottom edge)
+    fn template(&mut self, text: &str, y: f32, downwards: bool) {
+        let style = Style {
+            color: GRAY,
+            ..Style::body().with_font(Font::Regular, TEMPLATE_SIZE)
+        };
+        let lines: Vec<&str> = text.lines().collect();
+        let step = TEMPLATE_SIZE * LEADING;
+        for (index, line) in lines.iter().enumerate() {
+            let baseline = if downwards {
+                y - index as f32 * step
+            } else {
+                y + (lines.len() - 1 - index) as f32 * step
+            };
+            self.text(line, MARGIN_X, baseline, &style);
+        }
+    }
+}
+
+/// Pages being filled from top to bottom
+struct Layout {
+    /// Pages that are full, before the current one
+    finished: Vec<Page>,
+    current: Page,
+    y: f32,
+    /// Page index and top of each heading, in document order
+    headings: Vec<(usize, f32)>,
+    /// Page index and top of each source entry
+    sources: Vec<(usize, f32)>,
+}
+
+impl Layout {
+    fn new() -> Self {
+        Layout {
+            finished: Vec::new(),
+            current: Page::default(),
+            y: PAGE_HEIGHT - MARGIN_TOP,
+            headings: Vec::new(),
+            sources: Vec::new(),
+        }
+    }
+
+    fn page(&mut self) -> &mut Page {
+        &mut self.current
+    }
+
+    fn page_index(&self) -> usize {
+        self.finished.len()
+    }
+
+    /// The page at `index`, which is the current one unless it is finished
+    fn page_at(&mut self, index: usize) -> &mut Page {
+        self.finished.get_mut(index).unwrap_or(&mut self.current)
+    }
+
+    /// All pages laid out, the current one last
+    fn into_pages(self) -> Vec<Page> {
+        let mut pages = self.finished;
+        pages.push(self.current);
+        pages
+    }
+
+    fn at_top(&self) -> bool {
+        self.y >= PAGE_HEIGHT - MARGIN_TOP
+    }
+
+    /// Start a new page unless `height` still fits on this one
+    fn ensure(&mut self, height: f32) {
+        if self.y - height < MARGIN_BOTTOM && !self.at_top() {
+            self.finished.push(std::mem::take(&mut self.current));
+            self.y = PAGE_HEIGHT - MARGIN_TOP;
+        }
+    }
+
+    /// Vertical space between blocks; dropped at the top of a page
+    fn space(&mut self, height: f32) {
+        if !self.at_top() {
+            self.y -= height;
+        }
+    }
+
+    /// Place lines at `x`, returning the page and baseline of the first
+    fn lines(&mut self, lines: &[Line], x: f32, quote_bar: Option<f32>) -> (usize, f32) {
+        let mut first = None;
+        for line in lines {
+            self.ensure(line.height());
+            let baseline = self.y - line.size;
+            first.get_or_insert((self.page_index(), baseline));
+            let top = self.y;
+            self.page().line(line, x, baseline);
+            if let Some(bar) = quote_bar {
+                let bottom = top - line.height();
+                self.page().rule((bar, top), (bar, bottom), LIGHT_GRAY, 2.0);
+            }
+            self.y -= line.height();
+        }
+        first.unwrap_or((self.page_index(), self.y))
+    }
+}
+
+struct Renderer<'a> {
+    document: &'a ExportDocument,
+    layout: Layout,
+    /// List marker to print beside the next paragraph
+    pending_marker: Option<(String, f32)>,
+}
+
+impl Renderer<'_> {
+    fn block(&mut self, node: &PmNode, indent: f32, quote: bool) {
+        let x = MARGIN_X + indent;
+        let width = TEXT_WIDTH - indent;
+        let quote_bar = quote.then_some(x - INDENT / 2.0);
+        let base = if quote {
+            Style {
+                color: GRAY,
+                ..Style::body().with_font(Font::Italic, BODY_SIZE)
+            }
+        } else {
+            Style::body()
+        };
+
+        match node.node_type.as_str() {
+            "heading" => {
+                let level = node
+                    .attr("level")
+                    .and_then(|l| l.as_u64())
+                    .unwrap_or(1)
+                    .clamp(1, 6) as usize;
+                let size = HEADING_SIZES[level - 1];
+                let style = Style::body().with_font(Font::Bold, size);
+                let lines = wrap(self.pieces(&node.content, &style), width, size);
+
+                self.layout.space(size * 0.6);
+                // Keep the heading with the first line that follows
+                let height: f32 = lines.iter().map(Line::height).sum();
+                self.layout.ensure(height + BODY_SIZE * LEADING * 2.0);
+                let top = self.layout.y;
+                let page = self.layout.page_index();
+                self.layout.headings.push((page, top));
+                self.layout.lines(&lines, x, quote_bar);
+                self.layout.space(size * 0.25);
+            }
+            "codeBlock" => {
+                let style = Style::body().with_font(Font::Mono, CODE_SIZE);
+                let pieces: Vec<Piece> = node
+                    .plain_text()
+                    .split('\n')
+                    .map(|line| Piece {
+                        line_break: true,
+                        ..Piece::new(line, &style)
+                    })
+                    .collect();
+                let lines = wrap(pieces, width - 8.0, CODE_SIZE);
+                self.layout.lines(&lines, x + 8.0, Some(x + 2.0));
+                self.layout.space(6.0);
+            }
+            "horizontalRule" => {
+                self.layout.ensure(14.0);
+                let y = self.layout.y - 7.0;
+                self.layout
+                    .page()
+                    .rule((x, y), (x + width, y), LIGHT_GRAY, 0.75);
+                self.layout.y -= 14.0;
+            }
+            "blockquote" => {
+                for child in &node.content {
+                    self.block(child, indent + INDENT, true);
+                }
+            }
+            "bulletList" | "orderedList" => {
+                let start = node.attr("start").and_then(|s| s.as_u64()).unwrap_or(1);
+                for (index, item) in node.content.iter().enumerate() {
+                    let marker = if node.node_type == "bulletList" {
+                        "\u{2022}".to_string()
+                    } else {
+                        format!("{}.", start + index as u64)
+                    };
+                    self.pending_marker = Some((marker, x));
+                    for child in &item.content {
+                        self.block(child, indent + INDENT, quote);
+                    }
+                }
+                self.layout.space(2.0);
+            }
+            "table" => self.table(node, x, width),
+            "paragraph" => self.paragraph(&node.content, &base, x, width, quote_bar),
+            _ if node.content.iter().any(PmNode::is_text) => {
+                self.paragraph(&node.content, &base, x, width, quote_bar)
+            }
+            _ => {
+                for child in &node.content {
+                    self.block(child, indent, quote);
+                }
+            }
+        }
+    }
+
+    fn paragraph(
+        &mut self,
+        inlines: &[PmNode],
+        style: &Style,
+        x: f32,
+        width: f32,
+        quote_bar: Option<f32>,
+    ) {
+        let lines = wrap(self.pieces(inlines, style), width, style.size);
+        let (page, baseline) = self.layout.lines(&lines, x, quote_bar);
+
+        if let Some((marker, marker_x)) = self.pending_marker.take() {
+            self.layout
+                .page_at(page)
+                .text(&marker, marker_x, baseline, &Style::body());
+        }
+        self.layout.space(BODY_SIZE * 0.5);
+    }
+
+    fn table(&mut self, node: &PmNode, x: f32, width: f32) {
+        let span = |cell: &PmNode, key: &str| {
+            cell.attr(key).and_then(|v| v.as_u64()).unwrap_or(1).max(1) as usize
+        };
+        let columns = node
+            .content
+            .iter()
+            .map(|row| {
+                row.content
+                    .iter()
+                    .map(|cell| span(cell, "colspan"))
+                    .sum::<usize>()
+            })
+            .max()
+            .unwrap_or(1)
+            .max(1);
+        let column_width = width / columns as f32;
+        // Rows each column is still covered by a cell above
+        let mut covered = vec![0usize; columns];
+
+        for row in &node.content {
+            let mut cells = Vec::new();
+            let mut column = 0;
+            let mut row_cells = row.content.iter();
+            while column < columns {
+                if covered[column] > 0 {
+                    covered[column] -= 1;
+                    column += 1;
+                    continue;
+                }
+                let Some(cell) = row_cells.next() else {
+                    break;
+                };
+                let colspan = span(cell, "colspan").min(columns - column);
+                let rowspan = span(cell, "rowspan");
+                for covered_column in covered.iter_mut().skip(column).take(colspan) {
+                    *covered_column = rowspan - 1;
+                }
+
+                let style = if cell.node_type == "tableHeader" {
+                    Style::body().with_font(Font::Bold, BODY_SIZE)
+                } else {
+                    Style::body()
+                };
+                let mut pieces = Vec::new();
+                for (index, block) in cell.content.iter().enumerate() {
+                    if index > 0 {
+                        pieces.push(Piece::line_break(&style));
+                    }
+                    if block.is_textblock() {
+                        pieces.extend(self.pieces(&block.content, &style));
+                    } else {
+                        pieces.extend(words(&block.plain_text(), &style));
+                    }
+                }
+                let cell_width = column_width * colspan as f32;
+                let lines = wrap(pieces, cell_width - 2.0 * CELL_PADDING, BODY_SIZE);
+                cells.push((column, cell_width, lines));
+                column += colspan;
+            }
+
+            let height = cells
+                .iter()
+                .map(|(_, _, lines)| lines.iter().map(Line::height).sum::<f32>())
+                .fold(0.0, f32::max)
+                + 2.0 * CELL_PADDING;
+            self.layout.ensure(height);
+            let top = self.layout.y;
+            let page = self.layout.page();
+            for (column, cell_width, lines) in &cells {
+                let left = x + *column as f32 * column_width;
+                page.rectangle(left, top - height, *cell_width, height, None);
+                let mut y = top - CELL_PADDING;
+                for line in lines {
+                    page.line(line, left + CELL_PADDING, y - line.size);
+                    y -= line.height();
+                }
+            }
+            self.layout.y -= height;
+        }
+        self.layout.space(BODY_SIZE);
+    }
+
+    fn sources(&mut self) {
+        let sources = self.document.listed_sources();
+        if sources.is_empty() {
+            return;
+        }
+
+        let size = HEADING_SIZES[2];
+        self.layout.space(size);
+        self.layout.ensure(size * LEADING * 2.0);
+        let heading = Style::body().with_font(Font::Bold, size);
+        let lines = wrap(words("Sources", &heading), TEXT_WIDTH, size);
+        self.layout.lines(&lines, MARGIN_X, None);
+        self.layout.space(4.0);
+
+        for (index, source) in sources.iter().enumerate() {
+            let style = Style::body();
+            let link = Style {
+                color: LINK_BLUE,
+                link: Some(Target::Uri(source.file_url())),
+                ..style.clone()
+            };
+            let label_x = MARGIN_X + 24.0;
+            let lines = wrap(
+                words(&source.display_label(), &link),
+                TEXT_WIDTH - 24.0,
+                BODY_SIZE,
+            );
+
+            self.layout.ensure(BODY_SIZE * LEADING);
+            let page = self.layout.page_index();
+            self.layout.sources.push((page, self.layout.y));
+            let (page, baseline) = self.layout.lines(&lines, label_x, None);
+            self.layout
+                .page_at(page)
+                .text(&format!("[{}]", index + 1), MARGIN_X, baseline, &style);
+            self.layout.space(2.0);
+        }
+    }
+
+    /// Pieces of inline content, with citation markers
+    fn pieces(&self, inlines: &[PmNode], base: &Style) -> Vec<Piece> {
+        let mut pieces: Vec<Piece> = Vec::new();
+        for (index, node) in inlines.iter().enumerate() {
+            if node.node_type == "hardBreak" {
+                pieces.push(Piece::line_break(base));
+            } else if let Some(text) = &node.text {
+                let style = self.mark_style(node, base);
+                for (line_index, line) in text.split('\n').enumerate() {
+                    if line_index > 0 {
+                        pieces.push(Piece::line_break(&style));
+                    }
+                    append_words(&mut pieces, line, &style);
+                }
+            }
+
+            for number in self.document.citations_after(inlines, index) {
+                let size = base.size * 0.7;
+                pieces.push(Piece::new(
+                    &format!("[{}]", number),
+                    &Style {
+                        color: LINK_BLUE,
+                        rise: base.size * 0.35,
+                        link: Some(Target::Source(number)),
+                        ..Style::body().with_font(Font::Regular, size)
+                    },
+                ));
+            }
+        }
+        pieces
+    }
+
+    fn mark_style(&self, node: &PmNode, base: &Style) -> Style {
+        let has = |name: &str| node.marks.iter().any(|mark| mark.mark_type == name);
+        let bold = has("bold") || matches!(base.font, Font::Bold | Font::BoldItalic);
+        let italic = has("italic") || matches!(base.font, Font::Italic | Font::BoldItalic);
+
+        let mut style = base.clone();
+        style.font = if has("code") {
+            Font::Mono
+        } else {
+            Font::styled(bold, italic)
+        };
+        style.underline = has("underline");
+        style.strike = has("strike");
+        style.highlight = has("highlight");
+        if let Some(href) = node
+            .marks
+            .iter()
+            .find(|mark| mark.mark_type == "link")
+            .and_then(|mark| mark.attrs.as_ref()?.get("href")?.as_str())
+        {
+            style.color = LINK_BLUE;
+            style.underline = true;
+            style.link = Some(Target::Uri(href.to_string()));
+        }
+        style
+    }
+}
+
+/// Split text into word pieces in one style
+fn words(text: &str, style: &Style) -> Vec<Piece> {
+    let mut pieces = Vec::new();
+    append_words(&mut pieces, text, style);
+    pieces
+}
+
+/// Append the words of `text`, attaching spaces to the preceding piece
+fn append_words(pieces: &mut Vec<Piece>, text: &str, style: &Style) {
+    let space = style.font.text_width(" ", style.size);
+    for (index, word) in text.split(' ').enumerate() {
+        if index > 0 {
+            if let Some(previous) = pieces.last_mut().filter(|p| !p.line_break) {
+                previous.space += space;
+            }
+        }
+        if !word.is_empty() {
+            pieces.push(Piece::new(word, style));
+        }
+    }
+}
+
+/// Lay out the table of contents on its own pages
+///
+/// Page numbers depend on how many pages the contents take, so their
+/// positions are returned as (page, baseline, heading index) to be
+/// filled in afterwards.
+fn toc_layout(document: &ExportDocument) -> (Vec<Page>, Vec<(usize, f32, usize)>) {
+    let toc = document.toc();
+    let mut slots = Vec::new();
+    if toc.is_empty() {
+        return (Vec::new(), slots);
+    }
+    let mut layout = Layout::new();
+
+    let size = HEADING_SIZES[1];
+    let heading = Style::body().with_font(Font::Bold, size);
+    layout.lines(
+        &wrap(words("Contents", &heading), TEXT_WIDTH, size),
+        MARGIN_X,
+        None,
+    );
+    layout.space(8.0);
+
+    let min_level = toc.iter().map(|entry| entry.level).min().unwrap_or(1);
+    for entry in toc {
+        let heading = document
+            .headings
+            .iter()
+            .position(|h| h.anchor == entry.anchor)
+            .unwrap_or(0);
+        let indent = f32::from(entry.level - min_level) * 12.0;
+        let style = Style {
+            link: Some(Target::Heading(heading)),
+            ..Style::body()
+        };
+        let lines = wrap(
+            words(&entry.text, &style),
+            TEXT_WIDTH - indent - 36.0,
+            BODY_SIZE,
+        );
+        layout.lines(&lines, MARGIN_X + indent, None);
+        // The number goes beside the last line, which is on the current page
+        let last_baseline = layout.y + BODY_SIZE * LEADING - BODY_SIZE;
+        slots.push((layout.page_index(), last_baseline, heading));
+        layout.space(2.0);
+    }
+    (layout.into_pages(), slots)
+}
+
+/// Encode text in WinAnsiEncoding, the encoding of the standard fonts
+fn encode(text: &str) -> Vec<u8> {
+    text.chars()
+        .map(|c| match c {
+            ' '..='~' | '\u{A0}'..='\u{FF}' => c as u8,
+            '\u{20AC}' => 0x80,
+            '\u{201A}' => 0x82,
+            '\u{0192}' => 0x83,
+            '\u{201E}' => 0x84,
+            '\u{2026}' => 0x85,
+            '\u{2020}' => 0x86,
+            '\u{2021}' => 0x87,
+            '\u{02C6}' => 0x88,
+            '\u{2030}' => 0x89,
+            '\u{0160}' => 0x8A,
+            '\u{2039}' => 0x8B,
+            '\u{0152}' => 0x8C,
+            '\u{017D}' => 0x8E,
+            '\u{2018}' => 0x91,
+            '\u{2019}' => 0x92,
+            '\u{201C}' => 0x93,
+            '\u{201D}' => 0x94,
+            '\u{2022}' => 0x95,
+            '\u{2013}' => 0x96,
+            '\u{2014}' => 0x97,
+            '\u{02DC}' => 0x98,
+            '\u{2122}' => 0x99,
+            '\u{0161}' => 0x9A,
+            '\u{203A}' => 0x9B,
+            '\u{0153}' => 0x9C,
+            '\u{017E}' => 0x9E,
+            '\u{0178}' => 0x9F,
+            '\t' => b' ',
+            _ => b'?',
+        })
+        .collect()
+}
+
+/// PDF text string: literal for ASCII, UTF-16 otherwise
+fn text_string(text: &str) -> Object {
+    if text.is_ascii() {
+        Object::string_literal(text)
+    } else {
+        let mut bytes = vec![0xFE, 0xFF];
+        for unit in text.encode_utf16() {
+            bytes.extend(unit.to_be_bytes());
+        }
+        Object::String(bytes, StringFormat::Hexadecimal)
+    }
+}
+
+/// Build the PDF file from laid-out pages
+fn assemble(
+    title: &str,
+    pages: Vec<Page>,
+    headings: &[(usize, f32)],
+    sources: &[(usize, f32)],
+) -> Result<Vec<u8>, ExportError> {
+    let mut pdf = Document::with_version("1.5");
+    let pages_id = pdf.new_object_id();
+    let page_ids: Vec<ObjectId> = pages.iter().map(|_| pdf.new_object_id()).collect();
+
+    let mut fonts = Dictionary::new();
+    for font in Font::ALL {
+        let id = pdf.add_object(dictionary! {
+            "Type" => "Font",
+            "Subtype" => "Type1",
+            "BaseFont" => font.base_font(),
+            "Encoding" => "WinAnsiEncoding",
+        });
+        fonts.set(font.resource(), id);
+    }
+    let resources_id = pdf.add_object(dictionary! { "Font" => fonts });
+
+    let destination = |(page, y): (usize, f32)| -> Object {
+        vec![
+            page_ids[page].into(),
+            "XYZ".into(),
+            Object::Null,
+            (y + 4.0).into(),
+            Object::Null,
+        ]
+        .into()
+    };
+
+    for (page, id) in pages.into_iter().zip(&page_ids) {
+        let content = Content {
+            operations: page.operations,
+        }
+        .encode()
+        .map_err(|e| ExportError::Failed(e.to_string()))?;
+        let content_id = pdf.add_object(Stream::new(dictionary! {}, content));
+
+        let mut annotations = Vec::new();
+        for (rect, target) in page.links {
+            let mut annotation = dictionary! {
+                "Type" => "Annot",
+                "Subtype" => "Link",
+                "Rect" => rect.iter().map(|v| Object::from(*v)).collect::<Vec<_>>(),
+                "Border" => vec![0.into(), 0.into(), 0.into()],
+            };
+            match target {
+                Target::Uri(uri) => annotation.set(
+                    "A",
+                    dictionary! { "S" => "URI", "URI" => Object::string_literal(uri) },
+                ),
+                Target::Heading(index) => match headings.get(index) {
+                    Some(position) => annotation.set("Dest", destination(*position)),
+                    None => continue,
+                },
+                Target::Source(number) => match sources.get(number - 1) {
+                    Some(position) => annotation.set("Dest", destination(*position)),
+                    None => continue,
+                },
+            }
+            annotations.push(Object::from(pdf.add_object(annotation)));
+        }
+
+        let mut page_dictionary = dictionary! {
+            "Type" => "Page",
+            "Parent" => pages_id,
+            "Contents" => content_id,
+        };
+        if !annotations.is_empty() {
+            page_dictionary.set("Annots", annotations);
+        }
+        pdf.objects.insert(*id, Object::Dictionary(page_dictionary));
+    }
+
+    pdf.objects.insert(
+        pages_id,
+        Object::Dictionary(dictionary! {
+            "Type" => "Pages",
+            "Kids" => page_ids.iter().map(|id| Object::from(*id)).collect::<Vec<_>>(),
+            "Count" => page_ids.len() as i64,
+            "Resources" => resources_id,
+            "MediaBox" => vec![0.into(), 0.into(), PAGE_WIDTH.into(), PAGE_HEIGHT.into()],
+        }),
+    );
+    let catalog_id = pdf.add_object(dictionary! {
+        "Type" => "Catalog",
+        "Pages" => pages_id,
+    });
+    let info_id = pdf.add_object(dictionary! {
+        "Title" => text_string(title),
+        "Producer" => Object::string_literal("Corpus Review"),
+    });
+    pdf.trailer.set("Root", catalog_id);
+    pdf.trailer.set("Info", info_id);
+    pdf.compress();
+
+    let mut bytes = Vec::new();
+    pdf.save_to(&mut bytes)
+        .map_err(|e| ExportError::Failed(e.to_string()))?;
+    Ok(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::det::{DetDocument, DetKind, PmMark, ProcessingMetadata};
+    use crate::domain::export::{CitedSource, ExportOptions, PageTemplate};
+    use crate::domain::extraction::DocumentExtractor;
+    use crate::infrastructure::extraction::PdfExtractor;
+
+    fn export(paragraphs: usize) -> Vec<u8> {
+        let source = CitedSource::new("/corpus/invoice.pdf").with_page(4);
+        let mut blocks = vec![
+            PmNode::heading(1, PmNode::text_nodes("Findings", Vec::new())),
+            PmNode::paragraph(vec![
+                PmNode::text("Costs rose", vec![PmMark::bold(), source.to_mark()]),
+                PmNode::text(" by 5 % & more", Vec::new()),
+            ]),
+            PmNode::bullet_list(vec![PmNode::list_item(vec![PmNode::paragraph(
+                PmNode::text_nodes("Labour", Vec::new()),
+            )])]),
+        ];
+        for _ in 0..paragraphs {
+            blocks.push(PmNode::paragraph(PmNode::text_nodes(
+                &"The contractor invoiced the work in stages. ".repeat(12),
+                Vec::new(),
+            )));
+        }
+        blocks.push(PmNode::heading(2, PmNode::text_nodes("Résumé", Vec::new())));
+        let det = DetDocument::new(
+            DetKind::Report,
+            PmNode::doc(blocks),
+            ProcessingMetadata::completed("manual"),
+        )
+        .with_title("Cost report");
+        let options = ExportOptions {
+            include_toc: true,
+            template: PageTemplate {
+                header: None,
+                footer: Some("Page {page} of {pages}".to_string()),
+            },
+            ..ExportOptions::default()
+        };
+        let document = ExportDocument::new(&det, "report", options, "2024-09-24");
+        PdfExporter.export(&document).unwrap()
+    }
+
+    #[test]
+    fn test_output_reads_back_with_pdf_extractor() {
+        let extracted = PdfExtractor.extract(&export(20)).unwrap();
+        let text = extracted.content.plain_text();
+
+        assert_eq!(extracted.page_count, Some(4));
+        assert!(text.contains("Contents"));
+        assert!(text.contains("Page 1 of 4"));
+        assert!(text.contains("Costs rose"));
+        assert!(text.contains("by 5 % & more"));
+        assert!(text.contains("Résumé"));
+        assert!(text.contains("invoice.pdf, p. 4"));
+    }
+
+    #[test]
+    fn test_links_toc_citations_and_sources() {
+        let pdf = Document::load_mem(&export(0)).unwrap();
+        let pages = pdf.get_pages();
+        assert_eq!(pages.len(), 2);
+
+        let annotations = |page: u32| -> Vec<Dictionary> {
+            pdf.get_page_annotations(pages[&page])
+                .unwrap_or_default()
+                .into_iter()
+                .cloned()
+                .collect()
+        };
+        // Both contents entries point at the body page
+        let toc = annotations(1);
+        assert_eq!(toc.len(), 2);
+        for link in &toc {
+            let destination = link.get(b"Dest").unwrap().as_array().unwrap();
+            assert_eq!(destination[0].as_reference().unwrap(), pages[&2]);
+        }
+
+        let body = annotations(2);
+        assert!(body.iter().any(|link| link.get(b"Dest").is_ok()));
+        let uri = body
+            .iter()
+            .find_map(|link| link.get(b"A").ok()?.as_dict().ok()?.get(b"URI").ok())
+            .unwrap();
+        assert_eq!(uri.as_str().unwrap(), b"file:///corpus/invoice.pdf#page=4");
+    }
+}
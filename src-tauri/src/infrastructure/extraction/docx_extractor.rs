@@ -0,0 +1,696 @@
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+use std::collections::HashMap;
+use std::io::{Cursor, Read};
+
+use crate::domain::extraction::{
+    DocumentExtractor, ExtractedDocument, ExtractionError, ExtractionWarning, PmMark, PmNode,
+    QualityStats,
+};
+
+use super::structure::{assemble_blocks, BlockItem};
+
+/// Extractor for Word documents in Office Open XML format (.docx)
+///
+/// Reads `word/document.xml` with the paragraph styles, list numbering and
+/// hyperlink targets it references. Headings are recognised by outline
+/// level, so localized heading styles work too. Drawings and embedded
+/// objects are skipped with a warning.
+pub struct DocxExtractor;
+
+const FORMAT: &str = "DOCX";
+
+/// Magic bytes of OLE compound files: legacy .doc and encrypted .docx
+const OLE_MAGIC: [u8; 8] = [0xD0, 0xCF, 0x11, 0xE0, 0xA1, 0xB1, 0x1A, 0xE1];
+
+/// Elements whose whole subtree is skipped, and whether that loses content
+const SKIPPED_ELEMENTS: [(&[u8], bool); 5] = [
+    (b"w:drawing", true),
+    (b"w:pict", true),
+    (b"w:object", true),
+    (b"mc:Fallback", false),
+    (b"w:delText", false),
+];
+
+/// Paragraph, list and hyperlink definitions from the package parts
+#[derive(Default)]
+struct Definitions {
+    /// Style id to heading level
+    heading_styles: HashMap<String, u8>,
+
+    /// (numId, ilvl) to (ordered, start)
+    numbering: HashMap<(String, usize), (bool, u64)>,
+
+    /// Relationship id to external target
+    links: HashMap<String, String>,
+}
+
+#[derive(Default)]
+struct Paragraph {
+    style: Option<String>,
+    outline_level: Option<u8>,
+    num_id: Option<String>,
+    list_level: usize,
+    inlines: Vec<PmNode>,
+    skipped: bool,
+}
+
+#[derive(Default)]
+struct Table {
+    rows: Vec<PmNode>,
+    cells: Vec<PmNode>,
+    cell_items: Vec<BlockItem>,
+    header_row: bool,
+}
+
+#[derive(Default)]
+struct RunProps {
+    bold: bool,
+    italic: bool,
+    underline: bool,
+    strike: bool,
+}
+
+struct Parser<'d> {
+    definitions: &'d Definitions,
+    body: Vec<BlockItem>,
+    tables: Vec<Table>,
+    paragraph: Option<Paragraph>,
+    run: RunProps,
+    link: Option<String>,
+    in_ppr: bool,
+    in_rpr: bool,
+    in_text: bool,
+    skip_depth: usize,
+    section: String,
+    warnings: Vec<ExtractionWarning>,
+    stats: QualityStats,
+}
+
+impl<'d> Parser<'d> {
+    fn new(definitions: &'d Definitions) -> Self {
+        Parser {
+            definitions,
+            body: Vec::new(),
+            tables: Vec::new(),
+            paragraph: None,
+            run: RunProps::default(),
+            link: None,
+            in_ppr: false,
+            in_rpr: false,
+            in_text: false,
+            skip_depth: 0,
+            section: "Beginning".to_string(),
+            warnings: Vec::new(),
+            stats: QualityStats::default(),
+        }
+    }
+
+    fn start(&mut self, e: &BytesStart<'_>, empty: bool) {
+        let name = e.name();
+        let name = name.as_ref();
+
+        if self.skip_depth > 0 {
+            if !empty {
+                self.skip_depth += 1;
+            }
+            return;
+        }
+        if let Some((_, lossy)) = SKIPPED_ELEMENTS.iter().find(|(n, _)| *n == name) {
+            if *lossy {
+                self.stats.skipped_elements += 1;
+                if let Some(paragraph) = self.paragraph.as_mut() {
+                    paragraph.skipped = true;
+                }
+                let section = self.section.clone();
+                self.warnings.push(ExtractionWarning::section(
+                    section,
+                    "An image or embedded object was left out",
+                ));
+            }
+            if !empty {
+                self.skip_depth = 1;
+            }
+            return;
+        }
+
+        match name {
+            b"w:p" if !empty => self.paragraph = Some(Paragraph::default()),
+            b"w:pPr" => self.in_ppr = !empty,
+            b"w:rPr" => self.in_rpr = !empty,
+            b"w:r" => self.run = RunProps::default(),
+            b"w:t" => self.in_text = !empty,
+            b"w:pStyle" if self.in_ppr => {
+                if let Some(paragraph) = self.paragraph.as_mut() {
+                    paragraph.style = attr(e, b"w:val");
+                }
+            }
+            b"w:outlineLvl" if self.in_ppr => {
+                if let Some(paragraph) = self.paragraph.as_mut() {
+                    paragraph.outline_level = attr(e, b"w:val")
+                        .and_then(|v| v.parse::<u8>().ok())
+                        .filter(|level| *level < 9);
+                }
+            }
+            b"w:numId" if self.in_ppr => {
+                if let Some(paragraph) = self.paragraph.as_mut() {
+                    paragraph.num_id = attr(e, b"w:val").filter(|id| id != "0");
+                }
+            }
+            b"w:ilvl" if self.in_ppr => {
+                if let Some(paragraph) = self.paragraph.as_mut() {
+                    paragraph.list_level =
+                        attr(e, b"w:val").and_then(|v| v.parse().ok()).unwrap_or(0);
+                }
+            }
+            b"w:b" if self.in_rpr && !self.in_ppr => self.run.bold = is_on(e),
+            b"w:i" if self.in_rpr && !self.in_ppr => self.run.italic = is_on(e),
+            b"w:u" if self.in_rpr && !self.in_ppr => {
+                self.run.underline = attr(e, b"w:val").is_none_or(|v| v != "none")
+            }
+            b"w:strike" | b"w:dstrike" if self.in_rpr && !self.in_ppr => self.run.strike = is_on(e),
+            b"w:tab" if !self.in_ppr => self.push_text("\t"),
+            b"w:noBreakHyphen" => self.push_text("-"),
+            b"w:br" | b"w:cr" => {
+                if attr(e, b"w:type").is_none_or(|t| t == "textWrapping") {
+                    self.push_inline(PmNode::hard_break());
+                }
+            }
+            b"w:hyperlink" if !empty => {
+                self.link = attr(e, b"r:id")
+                    .and_then(|id| self.definitions.links.get(&id).cloned())
+                    .or_else(|| attr(e, b"w:anchor").map(|anchor| format!("#{}", anchor)));
+            }
+            b"w:tbl" if !empty => self.tables.push(Table::default()),
+            b"w:tblHeader" => {
+                if let Some(table) = self.tables.last_mut() {
+                    table.header_row = table.header_row || is_on(e);
+                }
+            }
+            b"w:tr" => {
+                if let Some(table) = self.tables.last_mut() {
+                    table.cells.clear();
+                    table.header_row = false;
+                }
+            }
+            b"w:tc" => {
+                if let Some(table) = self.tables.last_mut() {
+                    table.cell_items.clear();
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn end(&mut self, name: &[u8]) {
+        if self.skip_depth > 0 {
+            self.skip_depth -= 1;
+            return;
+        }
+
+        match name {
+            b"w:p" => self.finish_paragraph(),
+            b"w:pPr" => self.in_ppr = false,
+            b"w:rPr" => self.in_rpr = false,
+            b"w:t" => self.in_text = false,
+            b"w:hyperlink" => self.link = None,
+            b"w:tc" => {
+                if let Some(table) = self.tables.last_mut() {
+                    let blocks = assemble_blocks(std::mem::take(&mut table.cell_items));
+                    table.cells.push(if table.header_row {
+                        PmNode::table_header(blocks)
+                    } else {
+                        PmNode::table_cell(blocks)
+                    });
+                }
+            }
+            b"w:tr" => {
+                if let Some(table) = self.tables.last_mut() {
+                    let cells = std::mem::take(&mut table.cells);
+                    if !cells.is_empty() {
+                        table.rows.push(PmNode::table_row(cells));
+                    }
+                }
+            }
+            b"w:tbl" => {
+                if let Some(table) = self.tables.pop() {
+                    if !table.rows.is_empty() {
+                        self.emit(BlockItem::Block(PmNode::table(table.rows)));
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn text(&mut self, text: &str) {
+        if self.in_text && self.skip_depth == 0 {
+            self.push_text(text);
+        }
+    }
+
+    fn push_text(&mut self, text: &str) {
+        if text.is_empty() {
+            return;
+        }
+        self.stats.record_text(text);
+
+        let mut marks = Vec::new();
+        if self.run.bold {
+            marks.push(PmMark::bold());
+        }
+        if self.run.italic {
+            marks.push(PmMark::italic());
+        }
+        if self.run.underline {
+            marks.push(PmMark::underline());
+        }
+        if self.run.strike {
+            marks.push(PmMark::strike());
+        }
+        if let Some(href) = &self.link {
+            marks.push(PmMark::link(href.clone()));
+        }
+
+        // Word splits text into runs freely; merge runs with equal marks
+        if let Some(paragraph) = self.paragraph.as_mut() {
+            if let Some(last) = paragraph.inlines.last_mut() {
+                if last.is_text() && last.marks == marks {
+                    if let Some(existing) = last.text.as_mut() {
+                        existing.push_str(text);
+                        return;
+                    }
+                }
+            }
+        }
+        self.push_inline(PmNode::text(text, marks));
+    }
+
+    fn push_inline(&mut self, node: PmNode) {
+        if let Some(paragraph) = self.paragraph.as_mut() {
+            paragraph.inlines.push(node);
+        }
+    }
+
+    fn finish_paragraph(&mut self) {
+        let Some(mut paragraph) = self.paragraph.take() else {
+            return;
+        };
+
+        // Drop trailing breaks Word leaves before page or section ends
+        while paragraph
+            .inlines
+            .last()
+            .is_some_and(|n| n.node_type == "hardBreak")
+        {
+            paragraph.inlines.pop();
+        }
+
+        let has_text = paragraph.inlines.iter().any(PmNode::is_text);
+        if has_text || paragraph.skipped {
+            self.stats.record_unit(has_text);
+        }
+        if !has_text {
+            return;
+        }
+
+        let heading_level = paragraph.outline_level.map(|l| l + 1).or_else(|| {
+            paragraph
+                .style
+                .as_ref()
+                .and_then(|s| self.definitions.heading_styles.get(s).copied())
+        });
+
+        if let Some(level) = heading_level {
+            let heading = PmNode::heading(level, paragraph.inlines);
+            self.section = heading.plain_text();
+            self.emit(BlockItem::Block(heading));
+            return;
+        }
+
+        let node = PmNode::paragraph(paragraph.inlines);
+        let list = paragraph.num_id.and_then(|num_id| {
+            self.definitions
+                .numbering
+                .get(&(num_id, paragraph.list_level))
+                .copied()
+        });
+        self.emit(match list {
+            Some((ordered, start)) => BlockItem::ListEntry {
+                ordered,
+                level: paragraph.list_level,
+                start,
+                paragraph: node,
+            },
+            None => BlockItem::Block(node),
+        });
+    }
+
+    fn emit(&mut self, item: BlockItem) {
+        match self.tables.last_mut() {
+            Some(table) => table.cell_items.push(item),
+            None => self.body.push(item),
+        }
+    }
+}
+
+/// Value of an attribute by qualified name
+fn attr(e: &BytesStart<'_>, name: &[u8]) -> Option<String> {
+    e.try_get_attribute(name)
+        .ok()
+        .flatten()
+        .and_then(|a| a.unescape_value().ok().map(|v| v.into_owned()))
+}
+
+/// Whether a toggle property (w:b, w:i, ...) is switched on
+fn is_on(e: &BytesStart<'_>) -> bool {
+    attr(e, b"w:val").is_none_or(|v| !matches!(v.as_str(), "0" | "false" | "off"))
+}
+
+fn read_part(
+    archive: &mut zip::ZipArchive<Cursor<&[u8]>>,
+    name: &str,
+) -> Result<Option<String>, ExtractionError> {
+    let mut file = match archive.by_name(name) {
+        Ok(file) => file,
+        Err(zip::result::ZipError::FileNotFound) => return Ok(None),
+        Err(e) => return Err(ExtractionError::invalid(FORMAT, e)),
+    };
+    let mut xml = String::new();
+    file.read_to_string(&mut xml)
+        .map_err(|e| ExtractionError::invalid(FORMAT, e))?;
+    Ok(Some(xml))
+}
+
+/// An element boundary in a package part
+enum Element<'a, 'x> {
+    Open(&'a BytesStart<'x>),
+    Close(&'a [u8]),
+}
+
+/// Run `handle` for every element opened or closed in an XML part
+///
+/// Empty elements are reported as opened only.
+fn for_each_element(
+    xml: &str,
+    mut handle: impl FnMut(Element<'_, '_>),
+) -> Result<(), ExtractionError> {
+    let mut reader = Reader::from_str(xml);
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(e)) | Ok(Event::Empty(e)) => handle(Element::Open(&e)),
+            Ok(Event::End(e)) => handle(Element::Close(e.name().as_ref())),
+            Ok(Event::Eof) => return Ok(()),
+            Ok(_) => {}
+            Err(e) => return Err(ExtractionError::invalid(FORMAT, e)),
+        }
+    }
+}
+
+fn parse_styles(xml: &str, definitions: &mut Definitions) -> Result<(), ExtractionError> {
+    let mut style_id: Option<String> = None;
+
+    for_each_element(xml, |element| {
+        let Element::Open(e) = element else {
+            return;
+        };
+        match e.name().as_ref() {
+            b"w:style" => style_id = attr(e, b"w:styleId"),
+            b"w:name" => {
+                let name = attr(e, b"w:val").unwrap_or_default().to_lowercase();
+                let level = if name == "title" {
+                    Some(1)
+                } else {
+                    name.strip_prefix("heading ")
+                        .and_then(|n| n.trim().parse::<u8>().ok())
+                };
+                if let (Some(id), Some(level)) = (&style_id, level) {
+                    definitions.heading_styles.insert(id.clone(), level);
+                }
+            }
+            b"w:outlineLvl" => {
+                let level = attr(e, b"w:val").and_then(|v| v.parse::<u8>().ok());
+                if let (Some(id), Some(level)) = (&style_id, level.filter(|l| *l < 9)) {
+                    definitions.heading_styles.insert(id.clone(), level + 1);
+                }
+            }
+            _ => {}
+        }
+    })
+}
+
+fn parse_numbering(xml: &str, definitions: &mut Definitions) -> Result<(), ExtractionError> {
+    // abstractNumId -> ilvl -> (ordered, start)
+    let mut abstract_levels: HashMap<String, HashMap<usize, (bool, u64)>> = HashMap::new();
+    // numId -> abstractNumId
+    let mut instances: HashMap<String, String> = HashMap::new();
+
+    let mut abstract_id: Option<String> = None;
+    let mut num_id: Option<String> = None;
+    let mut level: Option<usize> = None;
+
+    for_each_element(xml, |element| match element {
+        Element::Open(e) => match e.name().as_ref() {
+            b"w:abstractNum" => abstract_id = attr(e, b"w:abstractNumId"),
+            b"w:num" => num_id = attr(e, b"w:numId"),
+            b"w:abstractNumId" => {
+                if let (Some(num), Some(target)) = (&num_id, attr(e, b"w:val")) {
+                    instances.insert(num.clone(), target);
+                }
+            }
+            b"w:lvl" => level = attr(e, b"w:ilvl").and_then(|v| v.parse().ok()),
+            b"w:numFmt" | b"w:start" => {
+                if let (Some(id), Some(lvl)) = (&abstract_id, level) {
+                    let entry = abstract_levels
+                        .entry(id.clone())
+                        .or_default()
+                        .entry(lvl)
+                        .or_insert((true, 1));
+                    let value = attr(e, b"w:val").unwrap_or_default();
+                    if e.name().as_ref() == b"w:numFmt" {
+                        entry.0 = !matches!(value.as_str(), "bullet" | "none");
+                    } else {
+                        entry.1 = value.parse().unwrap_or(1);
+                    }
+                }
+            }
+            _ => {}
+        },
+        Element::Close(b"w:abstractNum") => abstract_id = None,
+        Element::Close(b"w:num") => num_id = None,
+        Element::Close(b"w:lvl") => level = None,
+        Element::Close(_) => {}
+    })?;
+
+    for (num, abstract_num) in instances {
+        if let Some(levels) = abstract_levels.get(&abstract_num) {
+            for (lvl, format) in levels {
+                definitions.numbering.insert((num.clone(), *lvl), *format);
+            }
+        }
+    }
+    Ok(())
+}
+
+fn parse_relationships(xml: &str, definitions: &mut Definitions) -> Result<(), ExtractionError> {
+    for_each_element(xml, |element| {
+        let Element::Open(e) = element else {
+            return;
+        };
+        if e.name().as_ref() == b"Relationship"
+            && attr(e, b"TargetMode").as_deref() == Some("External")
+        {
+            if let (Some(id), Some(target)) = (attr(e, b"Id"), attr(e, b"Target")) {
+                definitions.links.insert(id, target);
+            }
+        }
+    })
+}
+
+impl DocumentExtractor for DocxExtractor {
+    fn method(&self) -> &'static str {
+        "docx-ooxml-v1"
+    }
+
+    fn extensions(&self) -> &'static [&'static str] {
+        &["docx", "docm", "dotx"]
+    }
+
+    fn extract(&self, bytes: &[u8]) -> Result<ExtractedDocument, ExtractionError> {
+        if bytes.starts_with(&OLE_MAGIC) {
+            return Err(ExtractionError::Encrypted {
+                format: FORMAT.to_string(),
+            });
+        }
+
+        let mut archive = zip::ZipArchive::new(Cursor::new(bytes))
+            .map_err(|e| ExtractionError::invalid(FORMAT, e))?;
+        let document = read_part(&mut archive, "word/document.xml")?
+            .ok_or_else(|| ExtractionError::invalid(FORMAT, "word/document.xml is missing"))?;
+
+        let mut definitions = Definitions::default();
+        if let Some(styles) = read_part(&mut archive, "word/styles.xml")? {
+            parse_styles(&styles, &mut definitions)?;
+        }
+        if let Some(numbering) = read_part(&mut archive, "word/numbering.xml")? {
+            parse_numbering(&numbering, &mut definitions)?;
+        }
+        if let Some(rels) = read_part(&mut archive, "word/_rels/document.xml.rels")? {
+            parse_relationships(&rels, &mut definitions)?;
+        }
+
+        let mut parser = Parser::new(&definitions);
+        let mut reader = Reader::from_str(&document);
+        loop {
+            match reader.read_event() {
+                Ok(Event::Start(e)) => parser.start(&e, false),
+                Ok(Event::Empty(e)) => parser.start(&e, true),
+                Ok(Event::End(e)) => parser.end(e.name().as_ref()),
+                Ok(Event::Text(t)) => {
+                    let text = t
+                        .unescape()
+                        .map_err(|e| ExtractionError::invalid(FORMAT, e))?;
+                    parser.text(&text);
+                }
+                Ok(Event::CData(t)) => parser.text(&String::from_utf8_lossy(&t)),
+                Ok(Event::Eof) => break,
+                Ok(_) => {}
+                Err(e) => return Err(ExtractionError::invalid(FORMAT, e)),
+            }
+        }
+
+        Ok(ExtractedDocument::new(
+            assemble_blocks(parser.body),
+            parser.warnings,
+            parser.stats,
+        ))
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use std::io::Write;
+    use zip::write::SimpleFileOptions;
+
+    /// Build a minimal .docx package around a document body
+    pub(crate) fn docx(body: &str, numbering: Option<&str>) -> Vec<u8> {
+        let mut buffer = Cursor::new(Vec::new());
+        let mut zip = zip::ZipWriter::new(&mut buffer);
+        let options = SimpleFileOptions::default();
+
+        zip.start_file("word/document.xml", options).unwrap();
+        write!(
+            zip,
+            r#"<?xml version="1.0" encoding="UTF-8"?><w:document xmlns:w="w" xmlns:r="r"><w:body>{}</w:body></w:document>"#,
+            body
+        )
+        .unwrap();
+
+        zip.start_file("word/styles.xml", options).unwrap();
+        zip.write_all(br#"<w:styles xmlns:w="w"><w:style w:styleId="berschrift1"><w:name w:val="heading 1"/></w:style></w:styles>"#).unwrap();
+
+        zip.start_file("word/_rels/document.xml.rels", options)
+            .unwrap();
+        zip.write_all(br#"<Relationships><Relationship Id="rId9" Target="https://example.com" TargetMode="External"/></Relationships>"#).unwrap();
+
+        if let Some(numbering) = numbering {
+            zip.start_file("word/numbering.xml", options).unwrap();
+            zip.write_all(numbering.as_bytes()).unwrap();
+        }
+
+        zip.finish().unwrap();
+        buffer.into_inner()
+    }
+
+    const NUMBERING: &str = r#"<w:numbering xmlns:w="w">
+        <w:abstractNum w:abstractNumId="1"><w:lvl w:ilvl="0"><w:start w:val="1"/><w:numFmt w:val="decimal"/></w:lvl></w:abstractNum>
+        <w:abstractNum w:abstractNumId="2"><w:lvl w:ilvl="0"><w:numFmt w:val="bullet"/></w:lvl><w:lvl w:ilvl="1"><w:numFmt w:val="bullet"/></w:lvl></w:abstractNum>
+        <w:num w:numId="5"><w:abstractNumId w:val="1"/></w:num>
+        <w:num w:numId="6"><w:abstractNumId w:val="2"/></w:num>
+    </w:numbering>"#;
+
+    fn list_paragraph(num_id: &str, level: usize, text: &str) -> String {
+        format!(
+            r#"<w:p><w:pPr><w:numPr><w:ilvl w:val="{}"/><w:numId w:val="{}"/></w:numPr></w:pPr><w:r><w:t>{}</w:t></w:r></w:p>"#,
+            level, num_id, text
+        )
+    }
+
+    #[test]
+    fn test_extracts_headings_runs_and_links() {
+        let body = r#"
+            <w:p><w:pPr><w:pStyle w:val="berschrift1"/></w:pPr><w:r><w:t>Vertrag</w:t></w:r></w:p>
+            <w:p><w:r><w:t xml:space="preserve">Signed by </w:t></w:r><w:r><w:rPr><w:b/></w:rPr><w:t>both</w:t></w:r><w:r><w:rPr><w:b w:val="0"/></w:rPr><w:t xml:space="preserve"> parties, see </w:t></w:r><w:hyperlink r:id="rId9"><w:r><w:t>annex</w:t></w:r></w:hyperlink></w:p>
+            <w:p></w:p>
+            <w:p><w:r><w:drawing><w:t>alt</w:t></w:drawing></w:r></w:p>
+        "#;
+        let result = DocxExtractor.extract(&docx(body, None)).unwrap();
+        let blocks = &result.content.content;
+
+        assert_eq!(blocks.len(), 2);
+        assert_eq!(blocks[0].node_type, "heading");
+        assert_eq!(blocks[0].plain_text(), "Vertrag");
+
+        let inlines = &blocks[1].content;
+        assert_eq!(inlines[0].text.as_deref(), Some("Signed by "));
+        assert_eq!(inlines[1].marks, vec![PmMark::bold()]);
+        assert_eq!(inlines[2].text.as_deref(), Some(" parties, see "));
+        assert_eq!(inlines[3].marks, vec![PmMark::link("https://example.com")]);
+
+        assert_eq!(result.warnings.len(), 1);
+        assert_eq!(
+            result.warnings[0].location,
+            crate::domain::extraction::WarningLocation::Section("Vertrag".to_string())
+        );
+        assert!(result.quality().needs_review());
+    }
+
+    #[test]
+    fn test_extracts_lists_and_tables() {
+        let body = [
+            list_paragraph("5", 0, "first"),
+            list_paragraph("5", 0, "second"),
+            list_paragraph("6", 0, "bullet"),
+            list_paragraph("6", 1, "nested"),
+            r#"<w:tbl><w:tr><w:trPr><w:tblHeader/></w:trPr><w:tc><w:p><w:r><w:t>Item</w:t></w:r></w:p></w:tc><w:tc><w:p><w:r><w:t>Cost</w:t></w:r></w:p></w:tc></w:tr>
+               <w:tr><w:tc><w:p><w:r><w:t>Fee</w:t></w:r></w:p></w:tc><w:tc><w:p/></w:tc></w:tr></w:tbl>"#
+                .to_string(),
+        ]
+        .concat();
+        let result = DocxExtractor
+            .extract(&docx(&body, Some(NUMBERING)))
+            .unwrap();
+        let blocks = &result.content.content;
+        crate::domain::det::DetSchema::tiptap()
+            .validate(&result.content)
+            .unwrap();
+
+        assert_eq!(blocks[0].node_type, "orderedList");
+        assert_eq!(blocks[0].content.len(), 2);
+        assert_eq!(blocks[1].node_type, "bulletList");
+        assert_eq!(blocks[1].content[0].content[1].node_type, "bulletList");
+
+        let table = &blocks[2];
+        assert_eq!(table.node_type, "table");
+        assert_eq!(table.content[0].content[0].node_type, "tableHeader");
+        assert_eq!(table.content[1].content[0].plain_text(), "Fee");
+        assert_eq!(
+            table.content[1].content[1].content[0].node_type,
+            "paragraph"
+        );
+        assert_eq!(result.quality().score(), 1.0);
+    }
+
+    #[test]
+    fn test_rejects_invalid_packages() {
+        assert!(matches!(
+            DocxExtractor.extract(b"not a zip"),
+            Err(ExtractionError::InvalidDocument { .. })
+        ));
+        assert!(matches!(
+            DocxExtractor.extract(&OLE_MAGIC),
+            Err(ExtractionError::Encrypted { .. })
+        ));
+    }
+}
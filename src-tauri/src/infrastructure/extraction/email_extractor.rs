@@ -0,0 +1,142 @@
+use crate::domain::email::ParsedEmail;
+use crate::domain::extraction::{
+    DocumentExtractor, ExtractedDocument, ExtractionError, PmMark, PmNode, QualityStats,
+};
+use crate::infrastructure::email::EmailParser;
+
+use super::structure::{assemble_blocks, text_to_blocks, BlockItem};
+
+/// Extractor for email files (.eml, .mbox, .msg)
+///
+/// Each message becomes a heading with its subject, a block of envelope
+/// headers, the body text and the names of its attachments. Messages of a
+/// mailbox are separated by horizontal rules. The attachments themselves
+/// are extracted as child documents of their own.
+pub struct EmailExtractor;
+
+const FORMAT: &str = "email";
+
+impl EmailExtractor {
+    fn message_blocks(message: &ParsedEmail, stats: &mut QualityStats) -> Vec<BlockItem> {
+        let headers = &message.headers;
+        let subject = headers.subject.as_deref().unwrap_or("(no subject)");
+        stats.record_text(subject);
+        let mut items = vec![BlockItem::Block(PmNode::heading(
+            1,
+            PmNode::text_nodes(subject, Vec::new()),
+        ))];
+
+        let date = headers.date.map(|date| date.to_rfc2822());
+        let fields = [
+            ("From", headers.from.clone()),
+            ("To", Some(headers.to.join(", "))),
+            ("Cc", Some(headers.cc.join(", "))),
+            ("Date", date),
+        ];
+        let mut lines = Vec::new();
+        for (label, value) in fields {
+            let Some(value) = value.filter(|value| !value.is_empty()) else {
+                continue;
+            };
+            if !lines.is_empty() {
+                lines.push(PmNode::hard_break());
+            }
+            lines.push(PmNode::text(format!("{}: ", label), vec![PmMark::bold()]));
+            lines.extend(PmNode::text_nodes(&value, Vec::new()));
+        }
+        if !lines.is_empty() {
+            items.push(BlockItem::Block(PmNode::paragraph(lines)));
+        }
+
+        items.extend(text_to_blocks(&message.body, true, stats));
+
+        if !message.attachments.is_empty() {
+            let names: Vec<&str> = message
+                .attachments
+                .iter()
+                .map(|attachment| attachment.file_name.as_str())
+                .collect();
+            let mut line = vec![PmNode::text("Attachments: ", vec![PmMark::bold()])];
+            line.extend(PmNode::text_nodes(&names.join(", "), Vec::new()));
+            items.push(BlockItem::Block(PmNode::paragraph(line)));
+        }
+        items
+    }
+}
+
+impl DocumentExtractor for EmailExtractor {
+    fn method(&self) -> &'static str {
+        "email-text-v1"
+    }
+
+    fn extensions(&self) -> &'static [&'static str] {
+        &EmailParser::EXTENSIONS
+    }
+
+    fn extract(&self, bytes: &[u8]) -> Result<ExtractedDocument, ExtractionError> {
+        let messages =
+            EmailParser::parse(bytes).map_err(|e| ExtractionError::invalid(FORMAT, e))?;
+
+        let mut stats = QualityStats::default();
+        let mut items = Vec::new();
+        for (index, message) in messages.iter().enumerate() {
+            if index > 0 {
+                items.push(BlockItem::Block(PmNode::horizontal_rule()));
+            }
+            items.extend(Self::message_blocks(message, &mut stats));
+        }
+        for item in &items {
+            let (BlockItem::Block(node)
+            | BlockItem::ListEntry {
+                paragraph: node, ..
+            }) = item;
+            if node.node_type != "horizontalRule" {
+                stats.record_unit(!node.content.is_empty());
+            }
+        }
+
+        Ok(ExtractedDocument::new(
+            assemble_blocks(items),
+            Vec::new(),
+            stats,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extracts_headers_body_and_attachment_names() {
+        let eml = b"From: Ana <ana@example.com>\r\n\
+            To: ben@example.com\r\n\
+            Subject: Offer\r\n\
+            Content-Type: multipart/mixed; boundary=x\r\n\
+            \r\n\
+            --x\r\n\
+            Content-Type: text/plain\r\n\
+            \r\n\
+            Dear Ben,\r\n\r\nsee attached.\r\n\
+            --x\r\n\
+            Content-Type: text/csv\r\n\
+            Content-Disposition: attachment; filename=prices.csv\r\n\
+            \r\n\
+            lot,price\r\n\
+            --x--\r\n";
+
+        let result = EmailExtractor.extract(eml).unwrap();
+        let blocks = &result.content.content;
+        assert_eq!(blocks[0].node_type, "heading");
+        assert_eq!(blocks[0].plain_text(), "Offer");
+        assert_eq!(
+            blocks[1].plain_text(),
+            "From: Ana <ana@example.com>\nTo: ben@example.com"
+        );
+        assert_eq!(blocks[2].plain_text(), "Dear Ben,");
+        assert_eq!(blocks[4].plain_text(), "Attachments: prices.csv");
+        assert_eq!(result.quality().level(), "good");
+
+        assert!(EmailExtractor.extract(b"\x00\x01").is_err());
+    }
+}
@@ -0,0 +1,373 @@
+use pulldown_cmark::{Event, HeadingLevel, Options, Parser, Tag, TagEnd};
+
+use crate::domain::extraction::{
+    DocumentExtractor, ExtractedDocument, ExtractionError, ExtractionWarning, PmMark, PmNode,
+    QualityStats,
+};
+
+use super::plain_text_extractor::PlainTextExtractor;
+
+/// Extractor for Markdown files (CommonMark with GFM tables and strikethrough)
+///
+/// Markdown maps onto ProseMirror almost one to one, so this is the
+/// reference for what a structurally faithful extraction looks like.
+pub struct MarkdownExtractor;
+
+/// A container node being built, and whether it was opened implicitly to
+/// hold loose inline content (tight list items, table cells)
+struct Frame {
+    node: PmNode,
+    implicit: bool,
+}
+
+struct Builder {
+    stack: Vec<Frame>,
+    marks: Vec<PmMark>,
+    in_table_head: bool,
+    in_html_block: bool,
+    in_metadata: bool,
+    section: String,
+    warnings: Vec<ExtractionWarning>,
+    stats: QualityStats,
+}
+
+impl Builder {
+    fn new() -> Self {
+        Builder {
+            stack: vec![Frame {
+                node: PmNode::doc(Vec::new()),
+                implicit: false,
+            }],
+            marks: Vec::new(),
+            in_table_head: false,
+            in_html_block: false,
+            in_metadata: false,
+            section: "Introduction".to_string(),
+            warnings: Vec::new(),
+            stats: QualityStats::default(),
+        }
+    }
+
+    fn open(&mut self, node: PmNode) {
+        self.close_implicit();
+        self.stack.push(Frame {
+            node,
+            implicit: false,
+        });
+    }
+
+    /// Close the innermost container and attach it to its parent
+    fn close(&mut self) {
+        self.close_implicit();
+        self.pop_into_parent();
+    }
+
+    fn close_implicit(&mut self) {
+        if self.stack.last().is_some_and(|frame| frame.implicit) {
+            self.pop_into_parent();
+        }
+    }
+
+    fn pop_into_parent(&mut self) {
+        if self.stack.len() < 2 {
+            return;
+        }
+        if let Some(frame) = self.stack.pop() {
+            let node = finish(frame.node);
+            if node.is_textblock() {
+                self.stats.record_unit(!node.content.is_empty());
+            }
+            if let Some(parent) = self.stack.last_mut() {
+                parent.node.content.push(node);
+            }
+        }
+    }
+
+    fn push_inline(&mut self, node: PmNode) {
+        let accepts_inline = self
+            .stack
+            .last()
+            .is_some_and(|frame| frame.node.is_textblock());
+        if !accepts_inline {
+            self.stack.push(Frame {
+                node: PmNode::paragraph(Vec::new()),
+                implicit: true,
+            });
+        }
+        if let Some(frame) = self.stack.last_mut() {
+            frame.node.content.push(node);
+        }
+    }
+
+    fn push_text(&mut self, text: &str) {
+        if text.is_empty() || self.in_metadata {
+            return;
+        }
+        self.stats.record_text(text);
+
+        let in_code_block = self
+            .stack
+            .last()
+            .is_some_and(|frame| frame.node.node_type == "codeBlock");
+        if in_code_block {
+            if let Some(frame) = self.stack.last_mut() {
+                match frame.node.content.last_mut().and_then(|n| n.text.as_mut()) {
+                    Some(existing) => existing.push_str(text),
+                    None => frame.node.content.push(PmNode::text(text, Vec::new())),
+                }
+            }
+            return;
+        }
+
+        self.push_inline(PmNode::text(text, self.marks.clone()));
+    }
+
+    fn warn(&mut self, message: impl Into<String>) {
+        self.stats.skipped_elements += 1;
+        let section = self.section.clone();
+        self.warnings
+            .push(ExtractionWarning::section(section, message.into()));
+    }
+
+    fn handle(&mut self, event: Event<'_>) {
+        match event {
+            Event::Start(tag) => self.start(tag),
+            Event::End(tag) => self.end(tag),
+            Event::Text(text) => self.push_text(&text),
+            Event::Code(code) => {
+                self.stats.record_text(&code);
+                let mut marks = self.marks.clone();
+                marks.push(PmMark::code());
+                self.push_inline(PmNode::text(code.to_string(), marks));
+            }
+            Event::SoftBreak => self.push_text(" "),
+            Event::HardBreak => self.push_inline(PmNode::hard_break()),
+            Event::Rule => {
+                self.close_implicit();
+                self.open(PmNode::horizontal_rule());
+                self.close();
+            }
+            Event::TaskListMarker(checked) => {
+                self.push_text(if checked { "[x] " } else { "[ ] " });
+            }
+            Event::Html(_) if self.in_html_block => {}
+            Event::Html(_) | Event::InlineHtml(_) => {
+                self.warn("Embedded HTML was left out");
+            }
+            Event::InlineMath(math) | Event::DisplayMath(math) => self.push_text(&math),
+            Event::FootnoteReference(label) => self.push_text(&format!("[^{}]", label)),
+        }
+    }
+
+    fn start(&mut self, tag: Tag<'_>) {
+        match tag {
+            Tag::Paragraph => self.open(PmNode::paragraph(Vec::new())),
+            Tag::Heading { level, .. } => {
+                self.open(PmNode::heading(heading_level(level), Vec::new()))
+            }
+            Tag::BlockQuote(_) => self.open(PmNode::blockquote(Vec::new())),
+            Tag::CodeBlock(kind) => {
+                let mut node = PmNode::element("codeBlock", Vec::new());
+                if let pulldown_cmark::CodeBlockKind::Fenced(language) = kind {
+                    if !language.is_empty() {
+                        node = node.with_attr("language", language.to_string());
+                    }
+                }
+                self.open(node);
+            }
+            Tag::List(Some(start)) => self.open(PmNode::ordered_list(Vec::new(), start)),
+            Tag::List(None) => self.open(PmNode::bullet_list(Vec::new())),
+            Tag::Item => self.open(PmNode::list_item(Vec::new())),
+            Tag::Table(_) => self.open(PmNode::table(Vec::new())),
+            Tag::TableHead => {
+                self.in_table_head = true;
+                self.open(PmNode::table_row(Vec::new()));
+            }
+            Tag::TableRow => self.open(PmNode::table_row(Vec::new())),
+            Tag::TableCell => {
+                let cell_type = if self.in_table_head {
+                    "tableHeader"
+                } else {
+                    "tableCell"
+                };
+                self.open(PmNode::element(cell_type, Vec::new()));
+            }
+            Tag::Emphasis => self.marks.push(PmMark::italic()),
+            Tag::Strong => self.marks.push(PmMark::bold()),
+            Tag::Strikethrough => self.marks.push(PmMark::strike()),
+            Tag::Link { dest_url, .. } => self.marks.push(PmMark::link(dest_url.to_string())),
+            Tag::Image { dest_url, .. } => {
+                self.warn(format!(
+                    "Image '{}' was replaced by its description",
+                    dest_url
+                ));
+            }
+            Tag::FootnoteDefinition(label) => {
+                self.open(PmNode::blockquote(Vec::new()));
+                self.push_text(&format!("[^{}]:", label));
+            }
+            Tag::HtmlBlock => {
+                self.in_html_block = true;
+                self.warn("Embedded HTML was left out");
+            }
+            Tag::MetadataBlock(_) => {
+                self.in_metadata = true;
+                self.warn("Front matter was left out");
+            }
+            Tag::DefinitionList => self.open(PmNode::blockquote(Vec::new())),
+            Tag::DefinitionListTitle | Tag::DefinitionListDefinition => {
+                self.open(PmNode::paragraph(Vec::new()))
+            }
+        }
+    }
+
+    fn end(&mut self, tag: TagEnd) {
+        match tag {
+            TagEnd::Heading(_) => {
+                let title = self
+                    .stack
+                    .last()
+                    .map(|frame| frame.node.plain_text())
+                    .unwrap_or_default();
+                if !title.is_empty() {
+                    self.section = title;
+                }
+                self.close();
+            }
+            TagEnd::TableHead => {
+                self.close();
+                self.in_table_head = false;
+            }
+            TagEnd::Emphasis | TagEnd::Strong | TagEnd::Strikethrough | TagEnd::Link => {
+                self.marks.pop();
+            }
+            TagEnd::HtmlBlock => self.in_html_block = false,
+            TagEnd::MetadataBlock(_) => self.in_metadata = false,
+            TagEnd::Image => {}
+            _ => self.close(),
+        }
+    }
+
+    fn finish(mut self) -> ExtractedDocument {
+        while self.stack.len() > 1 {
+            self.close();
+        }
+        let blocks = self
+            .stack
+            .pop()
+            .map(|frame| frame.node.content)
+            .unwrap_or_default();
+
+        ExtractedDocument::new(blocks, self.warnings, self.stats)
+    }
+}
+
+/// Fix up a finished node so it validates against the schema
+fn finish(mut node: PmNode) -> PmNode {
+    match node.node_type.as_str() {
+        "tableCell" | "tableHeader" | "listItem" | "blockquote" if node.content.is_empty() => {
+            node.content.push(PmNode::paragraph(Vec::new()));
+        }
+        "codeBlock" => {
+            // pulldown-cmark keeps the trailing newline of fenced blocks
+            if let Some(text) = node.content.last_mut().and_then(|n| n.text.as_mut()) {
+                let trimmed = text.trim_end_matches('\n').len();
+                text.truncate(trimmed);
+            }
+            node.content.retain(|n| n.text.as_deref() != Some(""));
+        }
+        _ => {}
+    }
+    node
+}
+
+fn heading_level(level: HeadingLevel) -> u8 {
+    level as u8
+}
+
+impl DocumentExtractor for MarkdownExtractor {
+    fn method(&self) -> &'static str {
+        "markdown-conversion-v1"
+    }
+
+    fn extensions(&self) -> &'static [&'static str] {
+        &["md", "markdown", "mdown", "mkd"]
+    }
+
+    fn extract(&self, bytes: &[u8]) -> Result<ExtractedDocument, ExtractionError> {
+        let (text, reencoded) = PlainTextExtractor::decode(bytes);
+
+        let mut options = Options::empty();
+        options.insert(Options::ENABLE_TABLES);
+        options.insert(Options::ENABLE_STRIKETHROUGH);
+        options.insert(Options::ENABLE_TASKLISTS);
+        options.insert(Options::ENABLE_FOOTNOTES);
+        options.insert(Options::ENABLE_YAML_STYLE_METADATA_BLOCKS);
+
+        let mut builder = Builder::new();
+        if reencoded {
+            builder.warnings.push(ExtractionWarning::document(
+                "Text encoding is not valid Unicode; some characters may be decoded wrongly",
+            ));
+        }
+        for event in Parser::new_ext(&text, options) {
+            builder.handle(event);
+        }
+
+        Ok(builder.finish())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NOTES: &str = "# Research Notes\n\nThis document contains **important findings** from our research.\n\n- First key point\n- [Reference link](https://example.com)\n  1. nested\n\n| Item | Cost |\n|------|------|\n| Fee  | 10   |\n\n```rust\nfn main() {}\n```\n";
+
+    #[test]
+    fn test_converts_structure() {
+        let result = MarkdownExtractor.extract(NOTES.as_bytes()).unwrap();
+        crate::domain::det::DetSchema::tiptap()
+            .validate(&result.content)
+            .unwrap();
+        let blocks = &result.content.content;
+
+        assert_eq!(blocks[0].node_type, "heading");
+        assert_eq!(blocks[0].attr("level"), Some(&1.into()));
+        assert_eq!(blocks[1].content[1].marks, vec![PmMark::bold()]);
+
+        let list = &blocks[2];
+        assert_eq!(list.node_type, "bulletList");
+        let link_item = &list.content[1];
+        assert_eq!(link_item.content[0].node_type, "paragraph");
+        assert_eq!(
+            link_item.content[0].content[0].marks,
+            vec![PmMark::link("https://example.com")]
+        );
+        assert_eq!(link_item.content[1].node_type, "orderedList");
+
+        let table = &blocks[3];
+        assert_eq!(table.content[0].content[0].node_type, "tableHeader");
+        assert_eq!(table.content[1].content[1].plain_text(), "10");
+
+        assert_eq!(blocks[4].node_type, "codeBlock");
+        assert_eq!(blocks[4].plain_text(), "fn main() {}");
+
+        assert_eq!(result.quality().score(), 1.0);
+        assert!(result.warnings.is_empty());
+    }
+
+    #[test]
+    fn test_warns_about_html_per_section() {
+        let result = MarkdownExtractor
+            .extract(b"# Intro\n\ntext\n\n## Figures\n\n<div>chart</div>\n")
+            .unwrap();
+
+        assert_eq!(result.warnings.len(), 1);
+        assert_eq!(
+            result.warnings[0].location,
+            crate::domain::extraction::WarningLocation::Section("Figures".to_string())
+        );
+        assert_eq!(result.stats.skipped_elements, 1);
+    }
+}
@@ -0,0 +1,86 @@
+pub mod docx_extractor;
+pub mod email_extractor;
+pub mod markdown_extractor;
+pub mod ocr;
+pub mod pdf_extractor;
+pub mod plain_text_extractor;
+pub mod rtf_extractor;
+mod structure;
+#[cfg(feature = "ocr")]
+pub mod tesseract_engine;
+
+pub use docx_extractor::DocxExtractor;
+pub use email_extractor::EmailExtractor;
+pub use markdown_extractor::MarkdownExtractor;
+pub use ocr::{ImageOcrExtractor, OcrStage};
+pub use pdf_extractor::{OcrPdfExtractor, PdfExtractor};
+pub use plain_text_extractor::PlainTextExtractor;
+pub use rtf_extractor::RtfExtractor;
+#[cfg(feature = "ocr")]
+pub use tesseract_engine::TesseractEngine;
+
+use std::sync::Arc;
+
+use crate::domain::extraction::{DocumentExtractor, OcrEngine};
+
+/// Looks up the extractor for a file extension
+#[derive(Clone, Default)]
+pub struct ExtractorRegistry {
+    extractors: Vec<Arc<dyn DocumentExtractor>>,
+}
+
+impl ExtractorRegistry {
+    /// Create a registry with all built-in extractors
+    ///
+    /// With the `ocr` feature, scanned PDF pages and images are read with
+    /// Tesseract when it can be initialized.
+    pub fn with_defaults() -> Self {
+        let mut registry = Self::default();
+        registry.register(Arc::new(PdfExtractor));
+        registry.register(Arc::new(DocxExtractor));
+        registry.register(Arc::new(RtfExtractor));
+        registry.register(Arc::new(MarkdownExtractor));
+        registry.register(Arc::new(PlainTextExtractor));
+        registry.register(Arc::new(EmailExtractor));
+
+        #[cfg(feature = "ocr")]
+        match TesseractEngine::from_env() {
+            Ok(engine) => registry = registry.with_ocr(Arc::new(engine)),
+            Err(error) => tracing::warn!("OCR is unavailable: {}", error),
+        }
+
+        registry
+    }
+
+    /// Read scanned PDF pages and images with the given OCR engine
+    pub fn with_ocr(mut self, engine: Arc<dyn OcrEngine>) -> Self {
+        let ocr = OcrStage::new(engine);
+        self.register(Arc::new(OcrPdfExtractor::new(ocr.clone())));
+        self.register(Arc::new(ImageOcrExtractor::new(ocr)));
+        self
+    }
+
+    /// Add an extractor; later registrations take precedence
+    pub fn register(&mut self, extractor: Arc<dyn DocumentExtractor>) {
+        self.extractors.insert(0, extractor);
+    }
+
+    pub fn for_extension(&self, extension: &str) -> Option<Arc<dyn DocumentExtractor>> {
+        self.extractors
+            .iter()
+            .find(|extractor| extractor.supports(extension))
+            .cloned()
+    }
+
+    /// All extensions that can be extracted, sorted
+    pub fn supported_extensions(&self) -> Vec<String> {
+        let mut extensions: Vec<String> = self
+            .extractors
+            .iter()
+            .flat_map(|extractor| extractor.extensions().iter().map(|e| e.to_string()))
+            .collect();
+        extensions.sort();
+        extensions.dedup();
+        extensions
+    }
+}
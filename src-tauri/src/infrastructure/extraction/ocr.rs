@@ -0,0 +1,259 @@
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use lopdf::{Document as PdfDocument, Object, ObjectId};
+
+use crate::domain::extraction::{
+    DocumentExtractor, ExtractedDocument, ExtractionError, ExtractionWarning, OcrEngine, OcrPage,
+    PageImage, QualityStats,
+};
+
+use super::structure::{assemble_blocks, text_to_blocks, BlockItem};
+
+/// Runs an OCR engine over page images and turns the words into blocks
+#[derive(Clone)]
+pub struct OcrStage {
+    engine: Arc<dyn OcrEngine>,
+}
+
+impl OcrStage {
+    pub fn new(engine: Arc<dyn OcrEngine>) -> Self {
+        OcrStage { engine }
+    }
+
+    pub fn engine_name(&self) -> &str {
+        self.engine.name()
+    }
+
+    /// Recognize one page and add its text to the extraction
+    ///
+    /// Pages recognized with low confidence get a warning and count as
+    /// uncertain in the quality score.
+    pub(crate) fn read_page(
+        &self,
+        page: u32,
+        image: &PageImage,
+        stats: &mut QualityStats,
+        warnings: &mut Vec<ExtractionWarning>,
+    ) -> Result<(Vec<BlockItem>, OcrPage), ExtractionError> {
+        let mut recognized = self.engine.recognize(image)?;
+        recognized.page = page;
+
+        let text = recognized.text();
+        let items = text_to_blocks(&text, false, stats);
+        if items.is_empty() {
+            stats.record_unit(false);
+            warnings.push(ExtractionWarning::page(
+                page,
+                "OCR found no text on the page",
+            ));
+        }
+        for item in &items {
+            let (BlockItem::Block(node)
+            | BlockItem::ListEntry {
+                paragraph: node, ..
+            }) = item;
+            stats.record_unit(!node.content.is_empty());
+        }
+
+        if let Some(confidence) = recognized.confidence() {
+            if recognized.needs_correction() {
+                stats.uncertain_characters += text.chars().filter(|c| !c.is_whitespace()).count();
+                warnings.push(ExtractionWarning::page(
+                    page,
+                    format!(
+                        "OCR confidence {:.0}%; check and correct the recognized text",
+                        confidence * 100.0
+                    ),
+                ));
+            }
+        }
+
+        Ok((items, recognized))
+    }
+}
+
+/// The scanned images of a PDF's pages
+pub(crate) struct PdfPageImages {
+    document: PdfDocument,
+    pages: BTreeMap<u32, ObjectId>,
+}
+
+impl PdfPageImages {
+    pub(crate) fn load(bytes: &[u8]) -> Result<Self, ExtractionError> {
+        let document =
+            PdfDocument::load_mem(bytes).map_err(|error| ExtractionError::invalid("PDF", error))?;
+        let pages = document.get_pages();
+        Ok(PdfPageImages { document, pages })
+    }
+
+    /// The largest image drawn on a page, in a form the OCR engine reads
+    ///
+    /// `Ok(None)` when the page has no image; an error when its encoding
+    /// is not supported.
+    pub(crate) fn page_image(&self, page: u32) -> Result<Option<PageImage>, String> {
+        let Some(&page_id) = self.pages.get(&page) else {
+            return Ok(None);
+        };
+        // Pages without an XObject dictionary have no images
+        let images = self.document.get_page_images(page_id).unwrap_or_default();
+        let Some(image) = images
+            .iter()
+            .max_by_key(|image| image.width.saturating_mul(image.height))
+        else {
+            return Ok(None);
+        };
+
+        let filters = image.filters.clone().unwrap_or_default();
+        match filters.last().map(String::as_str) {
+            Some("DCTDecode" | "JPXDecode") if filters.len() == 1 => {
+                return Ok(Some(PageImage::Encoded(image.content.to_vec())));
+            }
+            None | Some("FlateDecode" | "LZWDecode" | "ASCII85Decode") => {}
+            Some(filter) => return Err(format!("{} images are not supported", filter)),
+        }
+
+        let channels: u8 = match image.color_space.as_deref() {
+            Some("DeviceGray" | "CalGray") => 1,
+            Some("DeviceRGB" | "CalRGB") => 3,
+            other => {
+                return Err(format!(
+                    "{} images are not supported",
+                    other.unwrap_or("Uncoloured")
+                ))
+            }
+        };
+        if image.bits_per_component != Some(8) {
+            return Err("only 8-bit images are supported".to_string());
+        }
+
+        let data = if filters.is_empty() {
+            image.content.to_vec()
+        } else {
+            decode_image_stream(&self.document, image.id)?
+        };
+        let width = u32::try_from(image.width).map_err(|e| e.to_string())?;
+        let height = u32::try_from(image.height).map_err(|e| e.to_string())?;
+        let expected = width as usize * height as usize * usize::from(channels);
+        if data.len() < expected {
+            return Err("the image data is truncated".to_string());
+        }
+
+        Ok(Some(PageImage::Pixels {
+            width,
+            height,
+            channels,
+            data,
+        }))
+    }
+
+    /// The text drawn on a page, `None` when it cannot be read
+    pub(crate) fn page_text(&self, page: u32) -> Option<String> {
+        self.document.extract_text(&[page]).ok()
+    }
+}
+
+/// Decompress an image stream
+///
+/// lopdf refuses to decode streams marked as images, so the marker is
+/// dropped from a copy first.
+fn decode_image_stream(document: &PdfDocument, id: ObjectId) -> Result<Vec<u8>, String> {
+    let mut stream = document
+        .get_object(id)
+        .and_then(Object::as_stream)
+        .map_err(|e| e.to_string())?
+        .clone();
+    stream.dict.remove(b"Subtype");
+    stream.decompressed_content().map_err(|e| e.to_string())
+}
+
+/// Extractor for scanned images, read with OCR
+pub struct ImageOcrExtractor {
+    ocr: OcrStage,
+}
+
+impl ImageOcrExtractor {
+    pub fn new(ocr: OcrStage) -> Self {
+        ImageOcrExtractor { ocr }
+    }
+}
+
+impl DocumentExtractor for ImageOcrExtractor {
+    fn method(&self) -> &'static str {
+        "image-ocr-v1"
+    }
+
+    fn extensions(&self) -> &'static [&'static str] {
+        &["png", "jpg", "jpeg", "tif", "tiff"]
+    }
+
+    fn extract(&self, bytes: &[u8]) -> Result<ExtractedDocument, ExtractionError> {
+        let mut stats = QualityStats::default();
+        let mut warnings = Vec::new();
+        let image = PageImage::Encoded(bytes.to_vec());
+        let (items, page) = self.ocr.read_page(1, &image, &mut stats, &mut warnings)?;
+
+        Ok(
+            ExtractedDocument::new(assemble_blocks(items), warnings, stats)
+                .with_page_count(1)
+                .with_ocr(self.ocr.engine_name(), vec![page]),
+        )
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::domain::extraction::{BoundingBox, OcrWord};
+
+    /// Recognizes a fixed text with a fixed confidence, one line per entry
+    pub(crate) struct FakeOcrEngine {
+        pub lines: Vec<&'static str>,
+        pub confidence: f32,
+    }
+
+    impl OcrEngine for FakeOcrEngine {
+        fn name(&self) -> &str {
+            "fake-ocr"
+        }
+
+        fn recognize(&self, _image: &PageImage) -> Result<OcrPage, ExtractionError> {
+            let mut words = Vec::new();
+            for (line, text) in self.lines.iter().enumerate() {
+                for (index, word) in text.split_whitespace().enumerate() {
+                    words.push(OcrWord {
+                        text: word.to_string(),
+                        confidence: self.confidence,
+                        bbox: BoundingBox {
+                            x: 50 * index as u32,
+                            y: 20 * line as u32,
+                            width: 40,
+                            height: 12,
+                        },
+                        paragraph: 0,
+                        line: line as u32,
+                    });
+                }
+            }
+            Ok(OcrPage { page: 0, words })
+        }
+    }
+
+    #[test]
+    fn test_image_extraction_flags_low_confidence() {
+        let engine = Arc::new(FakeOcrEngine {
+            lines: vec!["Invoice 2291", "Total due"],
+            confidence: 0.55,
+        });
+        let extractor = ImageOcrExtractor::new(OcrStage::new(engine));
+        let result = extractor.extract(b"scanned").unwrap();
+
+        assert_eq!(result.content.plain_text(), "Invoice 2291 Total due");
+        assert_eq!(result.ocr_engine.as_deref(), Some("fake-ocr"));
+        assert_eq!(result.ocr_pages[0].page, 1);
+        assert_eq!(result.ocr_pages[0].words.len(), 4);
+        assert_eq!(result.warnings.len(), 1);
+        assert!(result.warnings[0].message.contains("55%"));
+        assert!(result.quality().needs_review());
+    }
+}
@@ -0,0 +1,336 @@
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+use crate::domain::extraction::{
+    is_unreadable, DocumentExtractor, ExtractedDocument, ExtractionError, ExtractionWarning,
+    QualityStats,
+};
+
+use super::ocr::{OcrStage, PdfPageImages};
+use super::structure::{assemble_blocks, text_to_blocks, BlockItem};
+
+/// Extractor for the text layer of PDF files
+///
+/// PDF has no reliable paragraph structure, so each page's text is reflowed
+/// into paragraphs and list entries. Pages without a text layer (scans) are
+/// reported so they can be sent to OCR, see `OcrPdfExtractor`.
+pub struct PdfExtractor;
+
+const FORMAT: &str = "PDF";
+
+/// Share of unreadable characters above which a page gets a warning
+const UNREADABLE_PAGE_THRESHOLD: f64 = 0.05;
+
+impl PdfExtractor {
+    fn read_pages(bytes: &[u8]) -> Result<Vec<String>, ExtractionError> {
+        // The PDF parser panics on some malformed files
+        let result = catch_unwind(AssertUnwindSafe(|| {
+            pdf_extract::extract_text_from_mem_by_pages(bytes)
+        }));
+
+        match result {
+            Ok(Ok(pages)) => Ok(pages),
+            Ok(Err(_)) if contains(bytes, b"/Encrypt") => Err(ExtractionError::Encrypted {
+                format: FORMAT.to_string(),
+            }),
+            Ok(Err(error)) => Err(ExtractionError::invalid(FORMAT, error)),
+            Err(_) => Err(ExtractionError::invalid(
+                FORMAT,
+                "the file could not be parsed",
+            )),
+        }
+    }
+}
+
+fn contains(haystack: &[u8], needle: &[u8]) -> bool {
+    haystack
+        .windows(needle.len())
+        .any(|window| window == needle)
+}
+
+impl DocumentExtractor for PdfExtractor {
+    fn method(&self) -> &'static str {
+        "pdf-text-v1"
+    }
+
+    fn extensions(&self) -> &'static [&'static str] {
+        &["pdf"]
+    }
+
+    fn extract(&self, bytes: &[u8]) -> Result<ExtractedDocument, ExtractionError> {
+        extract_pdf(bytes, None)
+    }
+}
+
+/// Extractor for PDF files that reads pages without a text layer with OCR
+///
+/// Pages with text are extracted exactly as by `PdfExtractor`; for the
+/// others the scanned page image is recognized and its words, confidences
+/// and positions are kept with the result.
+pub struct OcrPdfExtractor {
+    ocr: OcrStage,
+}
+
+impl OcrPdfExtractor {
+    pub fn new(ocr: OcrStage) -> Self {
+        OcrPdfExtractor { ocr }
+    }
+}
+
+impl DocumentExtractor for OcrPdfExtractor {
+    fn method(&self) -> &'static str {
+        "pdf-text-ocr-v1"
+    }
+
+    fn extensions(&self) -> &'static [&'static str] {
+        &["pdf"]
+    }
+
+    fn extract(&self, bytes: &[u8]) -> Result<ExtractedDocument, ExtractionError> {
+        extract_pdf(bytes, Some(&self.ocr))
+    }
+}
+
+fn extract_pdf(bytes: &[u8], ocr: Option<&OcrStage>) -> Result<ExtractedDocument, ExtractionError> {
+    if !bytes.starts_with(b"%PDF") {
+        return Err(ExtractionError::invalid(FORMAT, "missing %PDF header"));
+    }
+    let pages = PdfExtractor::read_pages(bytes)?;
+
+    let mut stats = QualityStats::default();
+    let mut warnings = Vec::new();
+    let mut items = Vec::new();
+    let mut ocr_pages = Vec::new();
+    // Page images are only loaded when there are pages to recognize
+    let page_images = match ocr {
+        Some(_) if pages.iter().any(|text| text.trim().is_empty()) => {
+            Some(PdfPageImages::load(bytes)?)
+        }
+        _ => None,
+    };
+
+    for (index, text) in pages.iter().enumerate() {
+        let page = u32::try_from(index + 1).unwrap_or(u32::MAX);
+        let page_items = text_to_blocks(text, false, &mut stats);
+
+        if page_items.is_empty() {
+            let (Some(ocr), Some(images)) = (ocr, &page_images) else {
+                stats.record_unit(false);
+                warnings.push(ExtractionWarning::page(
+                    page,
+                    "No text layer; the page may be a scanned image and need OCR",
+                ));
+                continue;
+            };
+
+            match images.page_image(page) {
+                Ok(Some(image)) => {
+                    let (ocr_items, ocr_page) =
+                        ocr.read_page(page, &image, &mut stats, &mut warnings)?;
+                    items.extend(ocr_items);
+                    ocr_pages.push(ocr_page);
+                }
+                Ok(None) => {
+                    stats.record_unit(false);
+                    warnings.push(ExtractionWarning::page(
+                        page,
+                        "No text layer and no page image to recognize",
+                    ));
+                }
+                Err(reason) => {
+                    stats.record_unit(false);
+                    warnings.push(ExtractionWarning::page(
+                        page,
+                        format!(
+                            "No text layer and the page image could not be read: {}",
+                            reason
+                        ),
+                    ));
+                }
+            }
+            continue;
+        }
+        for item in &page_items {
+            let (BlockItem::Block(node)
+            | BlockItem::ListEntry {
+                paragraph: node, ..
+            }) = item;
+            stats.record_unit(!node.content.is_empty());
+        }
+
+        let characters = text.chars().filter(|c| !c.is_whitespace()).count();
+        let unreadable = text.chars().filter(|c| is_unreadable(*c)).count();
+        if characters > 0 && unreadable as f64 / characters as f64 > UNREADABLE_PAGE_THRESHOLD {
+            warnings.push(ExtractionWarning::page(
+                page,
+                "Part of the text could not be decoded; check the font encoding",
+            ));
+        }
+
+        items.extend(page_items);
+    }
+
+    let page_count = u32::try_from(pages.len()).unwrap_or(u32::MAX);
+    let mut extracted =
+        ExtractedDocument::new(assemble_blocks(items), warnings, stats).with_page_count(page_count);
+    if let Some(ocr) = ocr {
+        extracted = extracted.with_ocr(ocr.engine_name(), ocr_pages);
+    }
+    Ok(extracted)
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    /// Build a minimal PDF with one page per entry, using a standard font
+    ///
+    /// Empty pages get a small grayscale image instead of text, like a scan.
+    pub(crate) fn pdf(pages: &[&str]) -> Vec<u8> {
+        pdf_with_image(
+            pages,
+            "/Width 2 /Height 2 /ColorSpace /DeviceGray /BitsPerComponent 8",
+            b"scan",
+        )
+    }
+
+    /// A PDF whose pages all show one image, described by `image` and
+    /// stored as `data`
+    pub(crate) fn pdf_with_image(pages: &[&str], image: &str, data: &[u8]) -> Vec<u8> {
+        let page_count = pages.len();
+        let font_id = 3 + 2 * page_count;
+        let image_id = font_id + 1;
+        let kids: Vec<String> = (0..page_count)
+            .map(|i| format!("{} 0 R", 3 + 2 * i))
+            .collect();
+
+        let mut objects = vec![
+            "<< /Type /Catalog /Pages 2 0 R >>".as_bytes().to_vec(),
+            format!(
+                "<< /Type /Pages /Kids [{}] /Count {} >>",
+                kids.join(" "),
+                page_count
+            )
+            .into_bytes(),
+        ];
+        for (i, text) in pages.iter().enumerate() {
+            objects.push(format!(
+                "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 612 792] /Contents {} 0 R /Resources << /Font << /F1 {} 0 R >> /XObject << /Im1 {} 0 R >> >> >>",
+                4 + 2 * i,
+                font_id,
+                image_id
+            ).into_bytes());
+            let stream = if text.is_empty() {
+                String::new()
+            } else {
+                format!("BT /F1 12 Tf 72 720 Td ({}) Tj ET", text)
+            };
+            objects.push(
+                format!(
+                    "<< /Length {} >>\nstream\n{}\nendstream",
+                    stream.len(),
+                    stream
+                )
+                .into_bytes(),
+            );
+        }
+        objects.push(
+            "<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica /Encoding /WinAnsiEncoding >>"
+                .as_bytes()
+                .to_vec(),
+        );
+        let mut image_object = format!(
+            "<< /Type /XObject /Subtype /Image {} /Length {} >>\nstream\n",
+            image,
+            data.len()
+        )
+        .into_bytes();
+        image_object.extend_from_slice(data);
+        image_object.extend_from_slice(b"\nendstream");
+        objects.push(image_object);
+
+        let mut output = b"%PDF-1.4\n".to_vec();
+        let mut offsets = Vec::new();
+        for (i, object) in objects.iter().enumerate() {
+            offsets.push(output.len());
+            output.extend(format!("{} 0 obj\n", i + 1).into_bytes());
+            output.extend_from_slice(object);
+            output.extend_from_slice(b"\nendobj\n");
+        }
+        let xref = output.len();
+        output.extend(format!("xref\n0 {}\n0000000000 65535 f \n", objects.len() + 1).into_bytes());
+        for offset in offsets {
+            output.extend(format!("{:010} 00000 n \n", offset).into_bytes());
+        }
+        output.extend(
+            format!(
+                "trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{}\n%%EOF\n",
+                objects.len() + 1,
+                xref
+            )
+            .into_bytes(),
+        );
+        output
+    }
+
+    #[test]
+    fn test_extracts_text_per_page() {
+        let bytes = pdf(&["Settlement agreement", ""]);
+        let result = PdfExtractor.extract(&bytes).unwrap();
+        crate::domain::det::DetSchema::tiptap()
+            .validate(&result.content)
+            .unwrap();
+
+        assert_eq!(result.page_count, Some(2));
+        assert!(result.content.plain_text().contains("Settlement agreement"));
+        assert_eq!(result.warnings.len(), 1);
+        assert_eq!(
+            result.warnings[0].location,
+            crate::domain::extraction::WarningLocation::Page(2)
+        );
+        assert!(result.quality().score() < 1.0);
+    }
+
+    #[test]
+    fn test_recognizes_pages_without_text_layer() {
+        use super::super::ocr::tests::FakeOcrEngine;
+        use crate::domain::extraction::PageImage;
+        use std::sync::Arc;
+
+        let engine = Arc::new(FakeOcrEngine {
+            lines: vec!["Witness statement"],
+            confidence: 0.95,
+        });
+        let extractor = OcrPdfExtractor::new(OcrStage::new(engine));
+        let result = extractor
+            .extract(&pdf(&["Settlement agreement", ""]))
+            .unwrap();
+
+        let text = result.content.plain_text();
+        assert!(text.contains("Settlement agreement"));
+        assert!(text.contains("Witness statement"));
+        assert!(result.warnings.is_empty());
+        assert_eq!(result.ocr_pages.len(), 1);
+        assert_eq!(result.ocr_pages[0].page, 2);
+        assert_eq!(result.quality().score(), 1.0);
+
+        let images = PdfPageImages::load(&pdf(&[""])).unwrap();
+        assert_eq!(
+            images.page_image(1).unwrap(),
+            Some(PageImage::Pixels {
+                width: 2,
+                height: 2,
+                channels: 1,
+                data: b"scan".to_vec(),
+            })
+        );
+    }
+
+    #[test]
+    fn test_rejects_invalid_files() {
+        assert!(matches!(
+            PdfExtractor.extract(b"not a pdf"),
+            Err(ExtractionError::InvalidDocument { .. })
+        ));
+        assert!(PdfExtractor.extract(b"%PDF-1.4\ngarbage").is_err());
+    }
+}
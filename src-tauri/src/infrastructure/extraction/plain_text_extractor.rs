@@ -0,0 +1,117 @@
+use crate::domain::extraction::{
+    DocumentExtractor, ExtractedDocument, ExtractionError, ExtractionWarning, QualityStats,
+};
+
+use super::structure::{assemble_blocks, text_to_blocks, BlockItem};
+
+/// Extractor for plain text files
+///
+/// Decodes UTF-8 (with or without BOM) and UTF-16 with BOM. Line breaks are
+/// kept as hard breaks, since plain text usually means correspondence or
+/// transcripts where line structure matters.
+pub struct PlainTextExtractor;
+
+impl PlainTextExtractor {
+    /// Decode text bytes, reporting whether any bytes were invalid
+    pub(crate) fn decode(bytes: &[u8]) -> (String, bool) {
+        if let Some(rest) = bytes.strip_prefix(&[0xEF, 0xBB, 0xBF]) {
+            return Self::decode_utf8(rest);
+        }
+        if let Some(rest) = bytes.strip_prefix(&[0xFF, 0xFE]) {
+            return Self::decode_utf16(rest, u16::from_le_bytes);
+        }
+        if let Some(rest) = bytes.strip_prefix(&[0xFE, 0xFF]) {
+            return Self::decode_utf16(rest, u16::from_be_bytes);
+        }
+        Self::decode_utf8(bytes)
+    }
+
+    fn decode_utf8(bytes: &[u8]) -> (String, bool) {
+        match std::str::from_utf8(bytes) {
+            Ok(text) => (text.to_string(), false),
+            // Not UTF-8: most likely Windows-1252, which is close enough to
+            // Latin-1 for the letters that matter
+            Err(_) => (bytes.iter().map(|&b| char::from(b)).collect(), true),
+        }
+    }
+
+    fn decode_utf16(bytes: &[u8], from_bytes: fn([u8; 2]) -> u16) -> (String, bool) {
+        let units: Vec<u16> = bytes
+            .chunks_exact(2)
+            .map(|pair| from_bytes([pair[0], pair[1]]))
+            .collect();
+        let text = String::from_utf16_lossy(&units);
+        let lossy = bytes.len() % 2 != 0 || text.contains('\u{FFFD}');
+        (text, lossy)
+    }
+}
+
+impl DocumentExtractor for PlainTextExtractor {
+    fn method(&self) -> &'static str {
+        "plain-text-v1"
+    }
+
+    fn extensions(&self) -> &'static [&'static str] {
+        &["txt", "text", "log"]
+    }
+
+    fn extract(&self, bytes: &[u8]) -> Result<ExtractedDocument, ExtractionError> {
+        let (text, reencoded) = Self::decode(bytes);
+        let mut warnings = Vec::new();
+        if reencoded {
+            warnings.push(ExtractionWarning::document(
+                "Text encoding is not valid Unicode; some characters may be decoded wrongly",
+            ));
+        }
+
+        let mut stats = QualityStats::default();
+        let items = text_to_blocks(&text, true, &mut stats);
+        for item in &items {
+            let (BlockItem::Block(node)
+            | BlockItem::ListEntry {
+                paragraph: node, ..
+            }) = item;
+            stats.record_unit(!node.content.is_empty());
+        }
+
+        Ok(ExtractedDocument::new(
+            assemble_blocks(items),
+            warnings,
+            stats,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extracts_paragraphs_with_line_breaks() {
+        let result = PlainTextExtractor
+            .extract(b"Dear Sir,\n\nPlease find attached\nthe invoice.\n")
+            .unwrap();
+
+        let blocks = &result.content.content;
+        assert_eq!(blocks.len(), 2);
+        assert_eq!(blocks[1].content[1].node_type, "hardBreak");
+        assert_eq!(result.quality().score(), 1.0);
+        assert!(result.warnings.is_empty());
+    }
+
+    #[test]
+    fn test_decodes_utf16_and_latin1() {
+        let utf16: Vec<u8> = [0xFF, 0xFE]
+            .into_iter()
+            .chain("Grüße".encode_utf16().flat_map(|u| u.to_le_bytes()))
+            .collect();
+        assert_eq!(
+            PlainTextExtractor::decode(&utf16),
+            ("Grüße".to_string(), false)
+        );
+
+        let result = PlainTextExtractor.extract(b"Caf\xe9").unwrap();
+        assert_eq!(result.content.plain_text(), "Café");
+        assert_eq!(result.warnings.len(), 1);
+    }
+}
@@ -0,0 +1,449 @@
+use crate::domain::extraction::{
+    DocumentExtractor, ExtractedDocument, ExtractionError, ExtractionWarning, PmMark, PmNode,
+    QualityStats,
+};
+
+use super::structure::{assemble_blocks, split_list_marker, BlockItem};
+
+/// Extractor for Rich Text Format files
+///
+/// A small RTF reader that understands the parts that carry structure:
+/// paragraphs, character formatting, outline levels (headings), list
+/// paragraphs and table rows. Font tables, style sheets and other
+/// destinations are skipped; pictures are skipped with a warning.
+pub struct RtfExtractor;
+
+const FORMAT: &str = "RTF";
+
+/// Destinations whose content is never document text
+const IGNORED_DESTINATIONS: [&str; 16] = [
+    "fonttbl",
+    "colortbl",
+    "stylesheet",
+    "info",
+    "listtable",
+    "listoverridetable",
+    "rsidtbl",
+    "generator",
+    "xmlnstbl",
+    "header",
+    "footer",
+    "headerl",
+    "headerr",
+    "footerl",
+    "footerr",
+    "themedata",
+];
+
+/// Formatting state of an RTF group
+#[derive(Debug, Clone, Default)]
+struct GroupState {
+    bold: bool,
+    italic: bool,
+    underline: bool,
+    strike: bool,
+    /// Characters to skip after a \u escape
+    unicode_skip: usize,
+    /// Inside a destination whose text is dropped
+    skip: bool,
+    /// Inside \listtext, whose text is the list marker
+    list_text: bool,
+}
+
+impl GroupState {
+    fn marks(&self) -> Vec<PmMark> {
+        let mut marks = Vec::new();
+        if self.bold {
+            marks.push(PmMark::bold());
+        }
+        if self.italic {
+            marks.push(PmMark::italic());
+        }
+        if self.underline {
+            marks.push(PmMark::underline());
+        }
+        if self.strike {
+            marks.push(PmMark::strike());
+        }
+        marks
+    }
+}
+
+/// Properties of the current paragraph, reset by \pard
+#[derive(Debug, Default)]
+struct ParagraphState {
+    outline_level: Option<u8>,
+    list_level: Option<usize>,
+    list_marker: String,
+    in_table: bool,
+}
+
+struct Reader<'a> {
+    input: &'a [u8],
+    position: usize,
+    groups: Vec<GroupState>,
+    state: GroupState,
+    paragraph: ParagraphState,
+    inlines: Vec<PmNode>,
+    items: Vec<BlockItem>,
+    cell_items: Vec<BlockItem>,
+    row_cells: Vec<PmNode>,
+    table_rows: Vec<PmNode>,
+    section: String,
+    warnings: Vec<ExtractionWarning>,
+    stats: QualityStats,
+}
+
+impl<'a> Reader<'a> {
+    fn new(input: &'a [u8]) -> Self {
+        Reader {
+            input,
+            position: 0,
+            groups: Vec::new(),
+            state: GroupState::default(),
+            paragraph: ParagraphState::default(),
+            inlines: Vec::new(),
+            items: Vec::new(),
+            cell_items: Vec::new(),
+            row_cells: Vec::new(),
+            table_rows: Vec::new(),
+            section: "Beginning".to_string(),
+            warnings: Vec::new(),
+            stats: QualityStats::default(),
+        }
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.input.get(self.position).copied()
+    }
+
+    fn run(mut self) -> Result<ExtractedDocument, ExtractionError> {
+        while let Some(byte) = self.peek() {
+            self.position += 1;
+            match byte {
+                b'{' => {
+                    self.groups.push(self.state.clone());
+                    // Ignorable destinations start with \*
+                    if self.input[self.position..].starts_with(b"\\*") {
+                        self.state.skip = true;
+                    }
+                }
+                b'}' => {
+                    self.state = self.groups.pop().ok_or_else(|| {
+                        ExtractionError::invalid(FORMAT, "unbalanced closing brace")
+                    })?;
+                }
+                b'\\' => self.control()?,
+                b'\r' | b'\n' => {}
+                _ => self.character(char::from(byte)),
+            }
+        }
+
+        self.finish_paragraph();
+        self.finish_table();
+        Ok(ExtractedDocument::new(
+            assemble_blocks(self.items),
+            self.warnings,
+            self.stats,
+        ))
+    }
+
+    /// Handle a control word or control symbol after a backslash
+    fn control(&mut self) -> Result<(), ExtractionError> {
+        let Some(first) = self.peek() else {
+            return Ok(());
+        };
+
+        if !first.is_ascii_alphabetic() {
+            self.position += 1;
+            match first {
+                b'\'' => {
+                    let hex = self
+                        .input
+                        .get(self.position..self.position + 2)
+                        .and_then(|h| std::str::from_utf8(h).ok())
+                        .and_then(|h| u8::from_str_radix(h, 16).ok())
+                        .ok_or_else(|| ExtractionError::invalid(FORMAT, "bad hex escape"))?;
+                    self.position += 2;
+                    self.character(windows_1252(hex));
+                }
+                b'~' => self.character('\u{00A0}'),
+                b'-' => {}
+                b'_' => self.character('-'),
+                b'\r' | b'\n' => self.finish_paragraph(),
+                other => self.character(char::from(other)),
+            }
+            return Ok(());
+        }
+
+        let start = self.position;
+        while self.peek().is_some_and(|b| b.is_ascii_alphabetic()) {
+            self.position += 1;
+        }
+        let word = String::from_utf8_lossy(&self.input[start..self.position]).into_owned();
+
+        let number_start = self.position;
+        if self.peek() == Some(b'-') {
+            self.position += 1;
+        }
+        while self.peek().is_some_and(|b| b.is_ascii_digit()) {
+            self.position += 1;
+        }
+        let parameter = std::str::from_utf8(&self.input[number_start..self.position])
+            .ok()
+            .and_then(|n| n.parse::<i32>().ok());
+        if self.peek() == Some(b' ') {
+            self.position += 1;
+        }
+
+        self.control_word(&word, parameter);
+        Ok(())
+    }
+
+    fn control_word(&mut self, word: &str, parameter: Option<i32>) {
+        let on = parameter != Some(0);
+
+        match word {
+            w if IGNORED_DESTINATIONS.contains(&w) => self.state.skip = true,
+            "pict" | "object" | "shppict" => {
+                if !self.state.skip {
+                    self.stats.skipped_elements += 1;
+                    let section = self.section.clone();
+                    self.warnings.push(ExtractionWarning::section(
+                        section,
+                        "An image or embedded object was left out",
+                    ));
+                }
+                self.state.skip = true;
+            }
+            "nonshppict" => self.state.skip = true,
+            "listtext" | "pntext" => self.state.list_text = true,
+            _ if self.state.skip => {}
+            "par" => self.finish_paragraph(),
+            "line" => self.inlines.push(PmNode::hard_break()),
+            "tab" => self.character('\t'),
+            "pard" => {
+                self.paragraph = ParagraphState::default();
+            }
+            "plain" => {
+                let skip = self.state.skip;
+                let unicode_skip = self.state.unicode_skip;
+                self.state = GroupState {
+                    skip,
+                    unicode_skip,
+                    ..GroupState::default()
+                };
+            }
+            "b" => self.state.bold = on,
+            "i" => self.state.italic = on,
+            "ul" => self.state.underline = on,
+            "ulnone" => self.state.underline = false,
+            "strike" | "striked" => self.state.strike = on,
+            "outlinelevel" => {
+                self.paragraph.outline_level = parameter
+                    .and_then(|p| u8::try_from(p).ok())
+                    .filter(|level| *level < 9);
+            }
+            "ilvl" => self.paragraph.list_level = parameter.and_then(|p| usize::try_from(p).ok()),
+            "ls" => {
+                self.paragraph.list_level.get_or_insert(0);
+            }
+            "intbl" => self.paragraph.in_table = true,
+            "cell" => {
+                self.finish_paragraph();
+                let blocks = assemble_blocks(std::mem::take(&mut self.cell_items));
+                self.row_cells.push(PmNode::table_cell(blocks));
+            }
+            "row" => {
+                let cells = std::mem::take(&mut self.row_cells);
+                if !cells.is_empty() {
+                    self.table_rows.push(PmNode::table_row(cells));
+                }
+            }
+            "uc" => {}
+            "u" => {
+                if let Some(code) = parameter {
+                    // Values above 32767 are written as negative numbers
+                    let code = if code < 0 { code + 65536 } else { code };
+                    let c = u32::try_from(code)
+                        .ok()
+                        .and_then(char::from_u32)
+                        .unwrap_or('\u{FFFD}');
+                    self.character(c);
+                    self.state.unicode_skip = 1;
+                    return;
+                }
+            }
+            "emdash" => self.character('—'),
+            "endash" => self.character('–'),
+            "bullet" => self.character('•'),
+            "lquote" => self.character('‘'),
+            "rquote" => self.character('’'),
+            "ldblquote" => self.character('“'),
+            "rdblquote" => self.character('”'),
+            _ => {}
+        }
+    }
+
+    fn character(&mut self, c: char) {
+        if self.state.unicode_skip > 0 {
+            self.state.unicode_skip -= 1;
+            return;
+        }
+        if self.state.skip {
+            return;
+        }
+        if self.state.list_text {
+            self.paragraph.list_marker.push(c);
+            return;
+        }
+
+        let marks = self.state.marks();
+        if let Some(last) = self.inlines.last_mut() {
+            if last.is_text() && last.marks == marks {
+                if let Some(text) = last.text.as_mut() {
+                    text.push(c);
+                    return;
+                }
+            }
+        }
+        self.inlines.push(PmNode::text(c.to_string(), marks));
+    }
+
+    fn finish_paragraph(&mut self) {
+        if !self.paragraph.in_table {
+            self.finish_table();
+        }
+
+        let inlines = std::mem::take(&mut self.inlines);
+        let marker = std::mem::take(&mut self.paragraph.list_marker);
+        let text: String = inlines.iter().filter_map(|n| n.text.as_deref()).collect();
+        if text.trim().is_empty() {
+            return;
+        }
+        self.stats.record_text(&text);
+        self.stats.record_unit(true);
+
+        let item = if let Some(level) = self.paragraph.outline_level {
+            let heading = PmNode::heading(level + 1, inlines);
+            self.section = heading.plain_text();
+            BlockItem::Block(heading)
+        } else if let Some(level) = self.paragraph.list_level {
+            let (ordered, start) = split_list_marker(&format!("{} ", marker.trim()))
+                .map(|(ordered, number, _)| (ordered, number))
+                .unwrap_or((false, 1));
+            BlockItem::ListEntry {
+                ordered,
+                level,
+                start,
+                paragraph: PmNode::paragraph(inlines),
+            }
+        } else {
+            BlockItem::Block(PmNode::paragraph(inlines))
+        };
+
+        if self.paragraph.in_table {
+            self.cell_items.push(item);
+        } else {
+            self.items.push(item);
+        }
+    }
+
+    fn finish_table(&mut self) {
+        let rows = std::mem::take(&mut self.table_rows);
+        if !rows.is_empty() {
+            self.items.push(BlockItem::Block(PmNode::table(rows)));
+        }
+    }
+}
+
+/// Decode a byte from a \'hh escape, assuming the default ANSI code page
+fn windows_1252(byte: u8) -> char {
+    const HIGH: [char; 32] = [
+        '€', '\u{81}', '‚', 'ƒ', '„', '…', '†', '‡', 'ˆ', '‰', 'Š', '‹', 'Œ', '\u{8D}', 'Ž',
+        '\u{8F}', '\u{90}', '‘', '’', '“', '”', '•', '–', '—', '˜', '™', 'š', '›', 'œ', '\u{9D}',
+        'ž', 'Ÿ',
+    ];
+    match byte {
+        0x80..=0x9F => HIGH[usize::from(byte - 0x80)],
+        _ => char::from(byte),
+    }
+}
+
+impl DocumentExtractor for RtfExtractor {
+    fn method(&self) -> &'static str {
+        "rtf-reader-v1"
+    }
+
+    fn extensions(&self) -> &'static [&'static str] {
+        &["rtf"]
+    }
+
+    fn extract(&self, bytes: &[u8]) -> Result<ExtractedDocument, ExtractionError> {
+        if !bytes.starts_with(b"{\\rtf") {
+            return Err(ExtractionError::invalid(FORMAT, "missing {\\rtf header"));
+        }
+        Reader::new(bytes).run()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extracts_formatting_and_headings() {
+        let rtf = br"{\rtf1\ansi{\fonttbl{\f0 Arial;}}{\*\generator Word;}
+{\pard\outlinelevel0\b Settlement\b0\par}
+{\pard The parties agree on \i caf\'e9 \i0 terms\u8212?now.\par}
+{\pard{\pict\pngblip 89504e47}\par}
+}";
+        let result = RtfExtractor.extract(rtf).unwrap();
+        let blocks = &result.content.content;
+
+        assert_eq!(blocks.len(), 2);
+        assert_eq!(blocks[0].node_type, "heading");
+        assert_eq!(blocks[0].plain_text(), "Settlement");
+        assert_eq!(
+            blocks[1].plain_text(),
+            "The parties agree on café terms—now."
+        );
+        assert_eq!(blocks[1].content[1].marks, vec![PmMark::italic()]);
+
+        assert_eq!(result.warnings.len(), 1);
+        assert_eq!(result.stats.skipped_elements, 1);
+    }
+
+    #[test]
+    fn test_extracts_lists_and_tables() {
+        let rtf = br"{\rtf1\ansi
+{\pard\ls1\ilvl0{\listtext 1.\tab}First\par}
+{\pard\ls1\ilvl0{\listtext 2.\tab}Second\par}
+\trowd\cellx1000\cellx2000
+\pard\intbl Item\cell Cost\cell\row
+\pard\intbl Fee\cell 10\cell\row
+\pard After the table\par
+}";
+        let result = RtfExtractor.extract(rtf).unwrap();
+        let blocks = &result.content.content;
+        crate::domain::det::DetSchema::tiptap()
+            .validate(&result.content)
+            .unwrap();
+
+        assert_eq!(blocks[0].node_type, "orderedList");
+        assert_eq!(blocks[0].content.len(), 2);
+        assert_eq!(blocks[0].content[1].plain_text(), "Second");
+
+        assert_eq!(blocks[1].node_type, "table");
+        assert_eq!(blocks[1].content.len(), 2);
+        assert_eq!(blocks[1].content[1].content[1].plain_text(), "10");
+
+        assert_eq!(blocks[2].plain_text(), "After the table");
+    }
+
+    #[test]
+    fn test_rejects_non_rtf() {
+        assert!(RtfExtractor.extract(b"plain text").is_err());
+        assert!(RtfExtractor.extract(b"{\\rtf1 }}").is_err());
+    }
+}
@@ -0,0 +1,272 @@
+//! Helpers shared by the extractors to turn flat paragraph sequences into
+//! nested ProseMirror structure.
+
+use crate::domain::extraction::{PmNode, QualityStats};
+
+/// A top-level block or a paragraph that belongs to a list
+///
+/// Formats like DOCX, RTF and extracted PDF text describe lists as
+/// paragraphs with a list level, not as nested containers. Extractors emit
+/// these items in document order and `assemble_blocks` nests them.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum BlockItem {
+    Block(PmNode),
+    ListEntry {
+        ordered: bool,
+        level: usize,
+        start: u64,
+        paragraph: PmNode,
+    },
+}
+
+struct OpenList {
+    ordered: bool,
+    start: u64,
+    items: Vec<Vec<PmNode>>,
+}
+
+impl OpenList {
+    fn into_node(self) -> PmNode {
+        let items = self.items.into_iter().map(PmNode::list_item).collect();
+        if self.ordered {
+            PmNode::ordered_list(items, self.start)
+        } else {
+            PmNode::bullet_list(items)
+        }
+    }
+}
+
+/// Nest consecutive list entries into bullet and ordered lists
+///
+/// A list entry can be at most one level deeper than the previous one;
+/// deeper jumps are flattened so the result always validates against the
+/// ProseMirror schema.
+pub(crate) fn assemble_blocks(items: Vec<BlockItem>) -> Vec<PmNode> {
+    let mut output = Vec::new();
+    let mut stack: Vec<OpenList> = Vec::new();
+
+    for item in items {
+        match item {
+            BlockItem::Block(node) => {
+                close_lists(&mut stack, &mut output, 0);
+                output.push(node);
+            }
+            BlockItem::ListEntry {
+                ordered,
+                level,
+                start,
+                paragraph,
+            } => {
+                let level = level.min(stack.len());
+                close_lists(&mut stack, &mut output, level + 1);
+
+                if stack.len() == level + 1 && stack[level].ordered != ordered {
+                    close_lists(&mut stack, &mut output, level);
+                }
+                if stack.len() == level {
+                    stack.push(OpenList {
+                        ordered,
+                        start,
+                        items: Vec::new(),
+                    });
+                }
+                if let Some(list) = stack.last_mut() {
+                    list.items.push(vec![paragraph]);
+                }
+            }
+        }
+    }
+
+    close_lists(&mut stack, &mut output, 0);
+    output
+}
+
+/// Close open lists until at most `depth` remain
+fn close_lists(stack: &mut Vec<OpenList>, output: &mut Vec<PmNode>, depth: usize) {
+    while stack.len() > depth {
+        let Some(list) = stack.pop() else {
+            break;
+        };
+        let node = list.into_node();
+        match stack.last_mut().and_then(|parent| parent.items.last_mut()) {
+            Some(item) => item.push(node),
+            None => output.push(node),
+        }
+    }
+}
+
+/// Recognise a list marker at the start of a line of plain text
+///
+/// Returns whether the list is ordered, the number for ordered items and
+/// the text after the marker.
+pub(crate) fn split_list_marker(line: &str) -> Option<(bool, u64, &str)> {
+    for bullet in ['•', '◦', '▪', '‣', '●', '-', '*', '–'] {
+        if let Some(rest) = line.strip_prefix(bullet) {
+            if rest.starts_with(' ') || rest.starts_with('\t') {
+                return Some((false, 1, rest.trim_start()));
+            }
+        }
+    }
+
+    let digits = line.chars().take_while(char::is_ascii_digit).count();
+    if (1..=3).contains(&digits) {
+        let rest = &line[digits..];
+        if let Some(rest) = rest.strip_prefix('.').or_else(|| rest.strip_prefix(')')) {
+            if rest.starts_with(' ') || rest.starts_with('\t') {
+                let number = line[..digits].parse().unwrap_or(1);
+                return Some((true, number, rest.trim_start()));
+            }
+        }
+    }
+
+    None
+}
+
+/// Split plain text into paragraphs and list entries
+///
+/// Paragraphs are separated by blank lines. With `keep_line_breaks` the
+/// lines of a paragraph are joined with hard breaks (plain text files);
+/// without it they are reflowed into running text, undoing hyphenation at
+/// line ends (text pulled from PDF pages).
+pub(crate) fn text_to_blocks(
+    text: &str,
+    keep_line_breaks: bool,
+    stats: &mut QualityStats,
+) -> Vec<BlockItem> {
+    let mut items = Vec::new();
+    let mut lines: Vec<&str> = Vec::new();
+    let mut list_entry: Option<(bool, usize, u64)> = None;
+
+    let mut flush = |lines: &mut Vec<&str>, list_entry: &mut Option<(bool, usize, u64)>| {
+        if lines.is_empty() {
+            return;
+        }
+        let inlines = join_lines(lines, keep_line_breaks);
+        lines.clear();
+        let paragraph = PmNode::paragraph(inlines);
+
+        items.push(match list_entry.take() {
+            Some((ordered, level, start)) => BlockItem::ListEntry {
+                ordered,
+                level,
+                start,
+                paragraph,
+            },
+            None => BlockItem::Block(paragraph),
+        });
+    };
+
+    for raw_line in text.lines() {
+        let line = raw_line.trim_end();
+        if line.trim().is_empty() {
+            flush(&mut lines, &mut list_entry);
+            continue;
+        }
+        stats.record_text(line);
+
+        let indent = line.len() - line.trim_start().len();
+        if let Some((ordered, number, rest)) = split_list_marker(line.trim_start()) {
+            flush(&mut lines, &mut list_entry);
+            list_entry = Some((ordered, indent / 2, number));
+            lines.push(rest);
+        } else {
+            lines.push(if keep_line_breaks {
+                line
+            } else {
+                line.trim_start()
+            });
+        }
+    }
+    flush(&mut lines, &mut list_entry);
+
+    items
+}
+
+fn join_lines(lines: &[&str], keep_line_breaks: bool) -> Vec<PmNode> {
+    if keep_line_breaks {
+        let mut inlines = Vec::new();
+        for (index, line) in lines.iter().enumerate() {
+            if index > 0 {
+                inlines.push(PmNode::hard_break());
+            }
+            inlines.extend(PmNode::text_nodes(line, Vec::new()));
+        }
+        return inlines;
+    }
+
+    let mut text = String::new();
+    for line in lines {
+        let line = line.trim();
+        if text.ends_with('-')
+            && !text.ends_with(" -")
+            && line.chars().next().is_some_and(char::is_lowercase)
+        {
+            text.pop();
+        } else if !text.is_empty() {
+            text.push(' ');
+        }
+        text.push_str(line);
+    }
+    PmNode::text_nodes(&text, Vec::new())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(ordered: bool, level: usize, text: &str) -> BlockItem {
+        BlockItem::ListEntry {
+            ordered,
+            level,
+            start: 1,
+            paragraph: PmNode::paragraph(PmNode::text_nodes(text, Vec::new())),
+        }
+    }
+
+    #[test]
+    fn test_assemble_nested_lists() {
+        let blocks = assemble_blocks(vec![
+            BlockItem::Block(PmNode::paragraph(Vec::new())),
+            entry(false, 0, "a"),
+            entry(false, 1, "a.1"),
+            entry(false, 3, "a.1.1"),
+            entry(false, 0, "b"),
+            entry(true, 0, "one"),
+        ]);
+
+        assert_eq!(blocks.len(), 3);
+        assert_eq!(blocks[1].node_type, "bulletList");
+        assert_eq!(blocks[1].content.len(), 2);
+        let nested = &blocks[1].content[0].content[1];
+        assert_eq!(nested.node_type, "bulletList");
+        assert_eq!(nested.content[0].content[1].node_type, "bulletList");
+        assert_eq!(blocks[2].node_type, "orderedList");
+    }
+
+    #[test]
+    fn test_text_to_blocks_reflows_and_detects_lists() {
+        let mut stats = QualityStats::default();
+        let items = text_to_blocks(
+            "The parties agree to the fol-\nlowing terms:\n\n1. Payment\n2. Delivery\n",
+            false,
+            &mut stats,
+        );
+        let blocks = assemble_blocks(items);
+
+        assert_eq!(
+            blocks[0].plain_text(),
+            "The parties agree to the following terms:"
+        );
+        assert_eq!(blocks[1].node_type, "orderedList");
+        assert_eq!(blocks[1].content.len(), 2);
+        assert!(stats.characters > 0);
+    }
+
+    #[test]
+    fn test_split_list_marker() {
+        assert_eq!(split_list_marker("• item"), Some((false, 1, "item")));
+        assert_eq!(split_list_marker("12) item"), Some((true, 12, "item")));
+        assert_eq!(split_list_marker("2024. was a year"), None);
+        assert_eq!(split_list_marker("-5 degrees"), None);
+    }
+}
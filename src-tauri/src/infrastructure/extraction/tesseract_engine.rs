@@ -0,0 +1,169 @@
+use crate::domain::extraction::{
+    BoundingBox, ExtractionError, OcrEngine, OcrPage, OcrWord, PageImage,
+};
+use tesseract::Tesseract;
+
+/// Languages to recognize when none are configured
+const DEFAULT_LANGUAGE: &str = "eng";
+
+/// Local OCR with the Tesseract library
+///
+/// Trained data is looked up in `TESSDATA_PREFIX` as usual; the languages
+/// can be set with `CORPUS_OCR_LANGUAGE`, e.g. "eng+deu".
+pub struct TesseractEngine {
+    datapath: Option<String>,
+    language: String,
+}
+
+impl TesseractEngine {
+    /// Create an engine and check that the trained data can be loaded
+    pub fn new(datapath: Option<String>, language: impl Into<String>) -> Result<Self, String> {
+        let engine = TesseractEngine {
+            datapath,
+            language: language.into(),
+        };
+        engine.start().map_err(|e| e.to_string())?;
+        Ok(engine)
+    }
+
+    /// Create an engine configured from the environment
+    pub fn from_env() -> Result<Self, String> {
+        let language = std::env::var("CORPUS_OCR_LANGUAGE")
+            .ok()
+            .filter(|language| !language.trim().is_empty())
+            .unwrap_or_else(|| DEFAULT_LANGUAGE.to_string());
+        Self::new(None, language)
+    }
+
+    fn start(&self) -> Result<Tesseract, ExtractionError> {
+        Tesseract::new(self.datapath.as_deref(), Some(&self.language))
+            .map_err(|e| ocr_failed(e.to_string()))
+    }
+}
+
+impl OcrEngine for TesseractEngine {
+    fn name(&self) -> &str {
+        "tesseract"
+    }
+
+    fn recognize(&self, image: &PageImage) -> Result<OcrPage, ExtractionError> {
+        // A Tesseract instance is not thread safe, so each page gets its own
+        let tesseract = self.start()?;
+        let tesseract = match image {
+            PageImage::Encoded(bytes) => tesseract.set_image_from_mem(bytes),
+            PageImage::Pixels {
+                width,
+                height,
+                channels,
+                data,
+            } => {
+                let dimension = |value: u32| i32::try_from(value).unwrap_or(i32::MAX);
+                tesseract.set_frame(
+                    data,
+                    dimension(*width),
+                    dimension(*height),
+                    i32::from(*channels),
+                    dimension(*width).saturating_mul(i32::from(*channels)),
+                )
+            }
+        }
+        .map_err(|e| ocr_failed(e.to_string()))?;
+
+        let mut tesseract = tesseract
+            .recognize()
+            .map_err(|e| ocr_failed(e.to_string()))?;
+        let tsv = tesseract
+            .get_tsv_text(0)
+            .map_err(|e| ocr_failed(e.to_string()))?;
+
+        Ok(OcrPage {
+            page: 1,
+            words: parse_tsv(&tsv),
+        })
+    }
+}
+
+fn ocr_failed(reason: String) -> ExtractionError {
+    ExtractionError::invalid("image", format!("OCR failed: {}", reason))
+}
+
+/// Read the words from Tesseract's TSV output
+///
+/// Columns are level, page, block, paragraph, line, word, left, top, width,
+/// height, confidence (0-100) and text; words are level 5. Paragraph and
+/// line numbers restart per block, so they are renumbered across the page.
+fn parse_tsv(tsv: &str) -> Vec<OcrWord> {
+    let mut words = Vec::new();
+    let mut paragraphs: Vec<(u32, u32)> = Vec::new();
+    let mut lines: Vec<(u32, u32, u32)> = Vec::new();
+
+    for row in tsv.lines() {
+        let columns: Vec<&str> = row.split('\t').collect();
+        if columns.len() < 12 || columns[0] != "5" {
+            continue;
+        }
+        let text = columns[11..].join("\t");
+        let text = text.trim();
+        let number = |index: usize| columns[index].trim().parse::<u32>().ok();
+        let (Some(block), Some(paragraph), Some(line)) = (number(2), number(3), number(4)) else {
+            continue;
+        };
+        let confidence = columns[10].trim().parse::<f32>().unwrap_or(-1.0);
+        if text.is_empty() || confidence < 0.0 {
+            continue;
+        }
+
+        if paragraphs.last() != Some(&(block, paragraph)) {
+            paragraphs.push((block, paragraph));
+        }
+        if lines.last() != Some(&(block, paragraph, line)) {
+            lines.push((block, paragraph, line));
+        }
+
+        words.push(OcrWord {
+            text: text.to_string(),
+            confidence: (confidence / 100.0).clamp(0.0, 1.0),
+            bbox: BoundingBox {
+                x: number(6).unwrap_or(0),
+                y: number(7).unwrap_or(0),
+                width: number(8).unwrap_or(0),
+                height: number(9).unwrap_or(0),
+            },
+            paragraph: u32::try_from(paragraphs.len() - 1).unwrap_or(u32::MAX),
+            line: u32::try_from(lines.len() - 1).unwrap_or(u32::MAX),
+        });
+    }
+
+    words
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_tsv_words() {
+        let tsv = "level\tpage_num\tblock_num\tpar_num\tline_num\tword_num\tleft\ttop\twidth\theight\tconf\ttext\n\
+                   1\t1\t0\t0\t0\t0\t0\t0\t600\t800\t-1\t\n\
+                   5\t1\t1\t1\t1\t1\t10\t12\t50\t14\t96.5\tDear\n\
+                   5\t1\t1\t1\t1\t2\t64\t12\t30\t14\t91\tSir,\n\
+                   5\t1\t1\t1\t2\t1\t10\t30\t70\t14\t62\tthanks\n\
+                   5\t1\t2\t1\t1\t1\t10\t60\t80\t14\t88\tRegards\n";
+        let page = OcrPage {
+            page: 1,
+            words: parse_tsv(tsv),
+        };
+
+        assert_eq!(page.text(), "Dear Sir,\nthanks\n\nRegards");
+        assert!((page.words[0].confidence - 0.965).abs() < 1e-6);
+        assert_eq!(
+            page.words[2].bbox,
+            BoundingBox {
+                x: 10,
+                y: 30,
+                width: 70,
+                height: 14
+            }
+        );
+    }
+}
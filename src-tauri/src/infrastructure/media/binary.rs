@@ -0,0 +1,89 @@
+use std::io::{Read, SeekFrom};
+
+use crate::domain::media::{MediaProbeError, MediaSource};
+
+/// Read up to `len` bytes at the current position
+///
+/// Returns fewer bytes at the end of the file instead of failing, so
+/// truncated files yield what metadata they still have.
+pub(super) fn read_up_to(
+    source: &mut dyn MediaSource,
+    len: u64,
+) -> Result<Vec<u8>, MediaProbeError> {
+    let mut buffer = Vec::new();
+    source.take(len).read_to_end(&mut buffer)?;
+    Ok(buffer)
+}
+
+/// Read up to `len` bytes at an absolute offset
+pub(super) fn read_at(
+    source: &mut dyn MediaSource,
+    offset: u64,
+    len: u64,
+) -> Result<Vec<u8>, MediaProbeError> {
+    source.seek(SeekFrom::Start(offset))?;
+    read_up_to(source, len)
+}
+
+/// Bounds-checked reads from an in-memory header
+pub(super) struct ByteReader<'a> {
+    data: &'a [u8],
+    position: usize,
+}
+
+impl<'a> ByteReader<'a> {
+    pub(super) fn new(data: &'a [u8]) -> Self {
+        ByteReader { data, position: 0 }
+    }
+
+    pub(super) fn position(&self) -> usize {
+        self.position
+    }
+
+    pub(super) fn remaining(&self) -> usize {
+        self.data.len() - self.position
+    }
+
+    pub(super) fn bytes(&mut self, len: usize) -> Option<&'a [u8]> {
+        let end = self.position.checked_add(len)?;
+        let bytes = self.data.get(self.position..end)?;
+        self.position = end;
+        Some(bytes)
+    }
+
+    pub(super) fn skip(&mut self, len: usize) -> Option<()> {
+        self.bytes(len).map(|_| ())
+    }
+
+    pub(super) fn u8(&mut self) -> Option<u8> {
+        self.bytes(1).map(|b| b[0])
+    }
+
+    pub(super) fn be_u16(&mut self) -> Option<u16> {
+        self.array().map(u16::from_be_bytes)
+    }
+
+    pub(super) fn be_u32(&mut self) -> Option<u32> {
+        self.array().map(u32::from_be_bytes)
+    }
+
+    pub(super) fn be_u64(&mut self) -> Option<u64> {
+        self.array().map(u64::from_be_bytes)
+    }
+
+    pub(super) fn le_u16(&mut self) -> Option<u16> {
+        self.array().map(u16::from_le_bytes)
+    }
+
+    pub(super) fn le_u32(&mut self) -> Option<u32> {
+        self.array().map(u32::from_le_bytes)
+    }
+
+    pub(super) fn le_u64(&mut self) -> Option<u64> {
+        self.array().map(u64::from_le_bytes)
+    }
+
+    fn array<const N: usize>(&mut self) -> Option<[u8; N]> {
+        self.bytes(N).and_then(|b| b.try_into().ok())
+    }
+}
@@ -0,0 +1,143 @@
+use super::binary::{read_at, ByteReader};
+use super::mp3_probe::id3v2_length;
+use super::tags::read_vorbis_comments;
+use crate::domain::media::{MediaKind, MediaMetadata, MediaProbe, MediaProbeError, MediaSource};
+
+/// Probe for FLAC files (.flac)
+///
+/// Reads the STREAMINFO block for sample rate, channels and total samples
+/// and the VORBIS_COMMENT block for tags. Picture blocks are skipped.
+pub struct FlacProbe;
+
+const FORMAT: &str = "FLAC";
+
+const STREAMINFO: u8 = 0;
+const VORBIS_COMMENT: u8 = 4;
+
+/// Larger comment blocks are not read
+const MAX_COMMENT_BYTES: u64 = 1024 * 1024;
+
+/// Read sample rate, channels and duration from a STREAMINFO block
+pub(super) fn read_streaminfo(block: &[u8], metadata: &mut MediaMetadata) -> Option<()> {
+    let mut reader = ByteReader::new(block);
+    reader.skip(10)?;
+    let packed = reader.be_u64()?;
+    let sample_rate = (packed >> 44) as u32;
+    let channels = ((packed >> 41) & 0x07) as u16 + 1;
+    let total_samples = packed & 0x0F_FFFF_FFFF;
+
+    metadata.sample_rate = Some(sample_rate).filter(|rate| *rate > 0);
+    metadata.channels = Some(channels);
+    metadata.set_duration(total_samples, u64::from(sample_rate));
+    Some(())
+}
+
+impl MediaProbe for FlacProbe {
+    fn method(&self) -> &'static str {
+        "flac-probe-v1"
+    }
+
+    fn extensions(&self) -> &'static [&'static str] {
+        &["flac"]
+    }
+
+    fn probe(
+        &self,
+        source: &mut dyn MediaSource,
+        size: u64,
+    ) -> Result<MediaMetadata, MediaProbeError> {
+        // Some taggers put an ID3v2 tag in front of the stream
+        let start = id3v2_length(&read_at(source, 0, 10)?).unwrap_or(0);
+        if read_at(source, start, 4)? != b"fLaC" {
+            return Err(MediaProbeError::invalid(FORMAT, "missing fLaC marker"));
+        }
+
+        let mut metadata = MediaMetadata::new(MediaKind::Audio, FORMAT);
+        metadata.audio_codec = Some(FORMAT.to_string());
+        let mut position = start + 4;
+        let mut has_streaminfo = false;
+
+        loop {
+            let header = read_at(source, position, 4)?;
+            if header.len() < 4 {
+                break;
+            }
+            let last = header[0] & 0x80 != 0;
+            let block_type = header[0] & 0x7F;
+            let length = u64::from(u32::from_be_bytes([0, header[1], header[2], header[3]]));
+            position += 4;
+
+            match block_type {
+                STREAMINFO => {
+                    let block = read_at(source, position, length)?;
+                    has_streaminfo = read_streaminfo(&block, &mut metadata).is_some();
+                }
+                VORBIS_COMMENT if length <= MAX_COMMENT_BYTES => {
+                    let block = read_at(source, position, length)?;
+                    read_vorbis_comments(&block, &mut metadata.tags);
+                }
+                _ => {}
+            }
+
+            position += length;
+            if last || position >= size {
+                break;
+            }
+        }
+
+        if !has_streaminfo {
+            return Err(MediaProbeError::invalid(FORMAT, "missing STREAMINFO block"));
+        }
+        metadata.fill_bitrate(size.saturating_sub(position));
+        Ok(metadata)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::media::tags::vorbis_comment_block;
+    use std::io::Cursor;
+
+    /// STREAMINFO of `seconds` of 44.1 kHz stereo audio
+    fn streaminfo(seconds: u64) -> Vec<u8> {
+        let packed: u64 = (44_100u64 << 44) | (1 << 41) | (15 << 36) | (44_100 * seconds);
+        let mut block = vec![0u8; 10];
+        block.extend_from_slice(&packed.to_be_bytes());
+        block.extend_from_slice(&[0u8; 16]);
+        block
+    }
+
+    fn block(block_type: u8, last: bool, body: &[u8]) -> Vec<u8> {
+        let mut block = vec![block_type | if last { 0x80 } else { 0 }];
+        block.extend_from_slice(&(body.len() as u32).to_be_bytes()[1..]);
+        block.extend_from_slice(body);
+        block
+    }
+
+    #[test]
+    fn test_reads_streaminfo_and_vorbis_comments() {
+        let mut file = b"fLaC".to_vec();
+        file.extend(block(STREAMINFO, false, &streaminfo(90)));
+        file.extend(block(
+            VORBIS_COMMENT,
+            true,
+            &vorbis_comment_block(&["TITLE=Deposition", "artist=Court", "DATE=2021-06-01"]),
+        ));
+        file.extend(vec![0u8; 1_000]);
+
+        let metadata = FlacProbe
+            .probe(&mut Cursor::new(file.clone()), file.len() as u64)
+            .unwrap();
+        assert_eq!(metadata.sample_rate, Some(44_100));
+        assert_eq!(metadata.channels, Some(2));
+        assert_eq!(metadata.duration_ms, Some(90_000));
+        assert_eq!(metadata.tags.title(), Some("Deposition"));
+        assert_eq!(metadata.tags.artist(), Some("Court"));
+        assert_eq!(metadata.tags.year(), Some("2021"));
+
+        assert!(FlacProbe
+            .probe(&mut Cursor::new(b"OggS".to_vec()), 4)
+            .is_err());
+    }
+}
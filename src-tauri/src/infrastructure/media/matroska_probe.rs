@@ -0,0 +1,440 @@
+use super::binary::{read_at, ByteReader};
+use super::tags::common_tag_name;
+use crate::domain::media::{
+    MediaKind, MediaMetadata, MediaProbe, MediaProbeError, MediaSource, MediaTags,
+};
+
+/// Probe for Matroska and WebM files (.mkv, .mka, .webm)
+///
+/// Reads the EBML header, then the Info, Tracks and Tags elements of the
+/// first segment. When those come after the clusters, as muxers writing
+/// tags at the end do, the SeekHead is used to jump to them.
+pub struct MatroskaProbe;
+
+const FORMAT: &str = "Matroska";
+
+const EBML: u32 = 0x1A45_DFA3;
+const DOC_TYPE: u32 = 0x4282;
+const SEGMENT: u32 = 0x1853_8067;
+const SEEK_HEAD: u32 = 0x114D_9B74;
+const SEEK: u32 = 0x4DBB;
+const SEEK_ID: u32 = 0x53AB;
+const SEEK_POSITION: u32 = 0x53AC;
+const INFO: u32 = 0x1549_A966;
+const TIMECODE_SCALE: u32 = 0x2A_D7B1;
+const DURATION: u32 = 0x4489;
+const TITLE: u32 = 0x7BA9;
+const TRACKS: u32 = 0x1654_AE6B;
+const TRACK_ENTRY: u32 = 0xAE;
+const TRACK_TYPE: u32 = 0x83;
+const CODEC_ID: u32 = 0x86;
+const DEFAULT_DURATION: u32 = 0x23_E383;
+const VIDEO: u32 = 0xE0;
+const PIXEL_WIDTH: u32 = 0xB0;
+const PIXEL_HEIGHT: u32 = 0xBA;
+const AUDIO: u32 = 0xE1;
+const SAMPLING_FREQUENCY: u32 = 0xB5;
+const CHANNELS: u32 = 0x9F;
+const TAGS: u32 = 0x1254_C367;
+const TAG: u32 = 0x7373;
+const SIMPLE_TAG: u32 = 0x67C8;
+const TAG_NAME: u32 = 0x45A3;
+const TAG_STRING: u32 = 0x4487;
+const CLUSTER: u32 = 0x1F43_B675;
+
+const TRACK_TYPE_VIDEO: u64 = 1;
+const TRACK_TYPE_AUDIO: u64 = 2;
+
+/// Larger top-level elements other than clusters are not read
+const MAX_ELEMENT_BYTES: u64 = 16 * 1024 * 1024;
+
+/// An element ID, kept with its length marker bits as Matroska writes them
+fn element_id(reader: &mut ByteReader<'_>) -> Option<u32> {
+    let first = reader.u8()?;
+    let length = first.leading_zeros() as usize + 1;
+    if length > 4 {
+        return None;
+    }
+    let rest = reader.bytes(length - 1)?;
+    Some(
+        rest.iter()
+            .fold(u32::from(first), |id, b| (id << 8) | u32::from(*b)),
+    )
+}
+
+/// An element data size; `None` inside the option means unknown size
+fn element_size(reader: &mut ByteReader<'_>) -> Option<Option<u64>> {
+    let first = reader.u8()?;
+    let length = first.leading_zeros() as usize + 1;
+    if length > 8 {
+        return None;
+    }
+    let rest = reader.bytes(length - 1)?;
+    let mask = if length == 8 { 0 } else { 0xFFu8 >> length };
+    let value = rest.iter().fold(u64::from(first & mask), |size, b| {
+        (size << 8) | u64::from(*b)
+    });
+    let unknown = value == (1u64 << (7 * length)) - 1;
+    Some((!unknown).then_some(value))
+}
+
+/// Element header at the start of `data`: ID, data size and header length
+fn element_header(data: &[u8]) -> Option<(u32, Option<u64>, usize)> {
+    let mut reader = ByteReader::new(data);
+    let id = element_id(&mut reader)?;
+    let size = element_size(&mut reader)?;
+    Some((id, size, reader.position()))
+}
+
+/// Child elements of an in-memory element body
+fn elements(data: &[u8]) -> Vec<(u32, &[u8])> {
+    let mut result = Vec::new();
+    let mut reader = ByteReader::new(data);
+    while reader.remaining() > 0 {
+        let (Some(id), Some(size)) = (element_id(&mut reader), element_size(&mut reader)) else {
+            break;
+        };
+        let length = size.map_or(reader.remaining(), |size| size as usize);
+        let Some(body) = reader.bytes(length.min(reader.remaining())) else {
+            break;
+        };
+        result.push((id, body));
+    }
+    result
+}
+
+fn unsigned(data: &[u8]) -> u64 {
+    data.iter()
+        .take(8)
+        .fold(0u64, |value, b| (value << 8) | u64::from(*b))
+}
+
+fn float(data: &[u8]) -> Option<f64> {
+    match data.len() {
+        4 => Some(f64::from(f32::from_be_bytes(data.try_into().ok()?))),
+        8 => Some(f64::from_be_bytes(data.try_into().ok()?)),
+        _ => None,
+    }
+}
+
+fn text(data: &[u8]) -> String {
+    String::from_utf8_lossy(data)
+        .trim_end_matches('\0')
+        .to_string()
+}
+
+fn codec_name(codec_id: &str) -> String {
+    let name = match codec_id {
+        "V_MPEG4/ISO/AVC" => "H.264",
+        "V_MPEGH/ISO/HEVC" => "H.265",
+        "V_VP8" => "VP8",
+        "V_VP9" => "VP9",
+        "V_AV1" => "AV1",
+        "V_MPEG4/ISO/ASP" | "V_MPEG4/ISO/SP" => "MPEG-4 Visual",
+        "V_MPEG2" => "MPEG-2",
+        "V_MJPEG" => "Motion JPEG",
+        "V_THEORA" => "Theora",
+        "A_OPUS" => "Opus",
+        "A_VORBIS" => "Vorbis",
+        "A_FLAC" => "FLAC",
+        "A_MPEG/L3" => "MP3",
+        "A_AC3" => "AC-3",
+        "A_EAC3" => "E-AC-3",
+        "A_DTS" => "DTS",
+        id if id.starts_with("A_AAC") => "AAC",
+        id if id.starts_with("A_PCM") => "PCM",
+        id => id,
+    };
+    name.to_string()
+}
+
+fn read_info(info: &[u8], metadata: &mut MediaMetadata) {
+    let mut timecode_scale = 1_000_000u64;
+    let mut duration = None;
+    for (id, body) in elements(info) {
+        match id {
+            TIMECODE_SCALE => timecode_scale = unsigned(body),
+            DURATION => duration = float(body),
+            TITLE => metadata.tags.set(MediaTags::TITLE, &text(body)),
+            _ => {}
+        }
+    }
+    if let Some(duration) = duration.filter(|d| d.is_finite() && *d > 0.0) {
+        // Duration is in timecode ticks of `timecode_scale` nanoseconds
+        metadata.duration_ms = Some((duration * timecode_scale as f64 / 1_000_000.0) as u64);
+    }
+}
+
+fn read_tracks(tracks: &[u8], metadata: &mut MediaMetadata) {
+    for (_, entry) in elements(tracks)
+        .into_iter()
+        .filter(|(id, _)| *id == TRACK_ENTRY)
+    {
+        let children = elements(entry);
+        let value = |wanted: u32| {
+            children
+                .iter()
+                .find(|(id, _)| *id == wanted)
+                .map(|(_, body)| *body)
+        };
+        let codec = value(CODEC_ID).map(|body| codec_name(&text(body)));
+
+        match value(TRACK_TYPE).map(unsigned) {
+            Some(TRACK_TYPE_VIDEO) if metadata.video_codec.is_none() => {
+                metadata.kind = MediaKind::Video;
+                metadata.video_codec = codec;
+                for (id, body) in value(VIDEO).map(elements).unwrap_or_default() {
+                    match id {
+                        PIXEL_WIDTH => metadata.width = u32::try_from(unsigned(body)).ok(),
+                        PIXEL_HEIGHT => metadata.height = u32::try_from(unsigned(body)).ok(),
+                        _ => {}
+                    }
+                }
+                // Nanoseconds per frame
+                if let Some(frame_ns) = value(DEFAULT_DURATION).map(unsigned).filter(|ns| *ns > 0) {
+                    let rate = 1_000_000_000.0 / frame_ns as f64;
+                    metadata.frame_rate = Some((rate * 1000.0).round() / 1000.0);
+                }
+            }
+            Some(TRACK_TYPE_AUDIO) if metadata.audio_codec.is_none() => {
+                metadata.audio_codec = codec;
+                for (id, body) in value(AUDIO).map(elements).unwrap_or_default() {
+                    match id {
+                        SAMPLING_FREQUENCY => {
+                            metadata.sample_rate = float(body).map(|rate| rate as u32);
+                        }
+                        CHANNELS => metadata.channels = u16::try_from(unsigned(body)).ok(),
+                        _ => {}
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn read_tags(tags: &[u8], metadata: &mut MediaMetadata) {
+    for (_, tag) in elements(tags).into_iter().filter(|(id, _)| *id == TAG) {
+        for (_, simple) in elements(tag)
+            .into_iter()
+            .filter(|(id, _)| *id == SIMPLE_TAG)
+        {
+            let children = elements(simple);
+            let field = |wanted: u32| {
+                children
+                    .iter()
+                    .find(|(id, _)| *id == wanted)
+                    .map(|(_, body)| text(body))
+            };
+            if let (Some(name), Some(value)) = (field(TAG_NAME), field(TAG_STRING)) {
+                if let Some(name) = common_tag_name(&name) {
+                    metadata.tags.set(name, &value);
+                }
+            }
+        }
+    }
+}
+
+/// Segment-relative positions of Info, Tracks and Tags from a SeekHead
+fn seek_positions(seek_head: &[u8]) -> Vec<(u32, u64)> {
+    elements(seek_head)
+        .into_iter()
+        .filter(|(id, _)| *id == SEEK)
+        .filter_map(|(_, seek)| {
+            let children = elements(seek);
+            let id = children.iter().find(|(id, _)| *id == SEEK_ID)?.1;
+            let position = children.iter().find(|(id, _)| *id == SEEK_POSITION)?.1;
+            Some((unsigned(id) as u32, unsigned(position)))
+        })
+        .collect()
+}
+
+impl MediaProbe for MatroskaProbe {
+    fn method(&self) -> &'static str {
+        "matroska-probe-v1"
+    }
+
+    fn extensions(&self) -> &'static [&'static str] {
+        &["mkv", "mka", "webm"]
+    }
+
+    fn probe(
+        &self,
+        source: &mut dyn MediaSource,
+        size: u64,
+    ) -> Result<MediaMetadata, MediaProbeError> {
+        let head = read_at(source, 0, 64)?;
+        let Some((EBML, Some(header_size), header_length)) = element_header(&head) else {
+            return Err(MediaProbeError::invalid(FORMAT, "missing EBML header"));
+        };
+        let header = read_at(source, header_length as u64, header_size)?;
+        let container = match elements(&header).iter().find(|(id, _)| *id == DOC_TYPE) {
+            Some((_, doc_type)) if text(doc_type) == "webm" => "WebM",
+            _ => FORMAT,
+        };
+
+        let segment_offset = header_length as u64 + header_size;
+        let segment_head = read_at(source, segment_offset, 12)?;
+        let Some((SEGMENT, _, segment_header_length)) = element_header(&segment_head) else {
+            return Err(MediaProbeError::invalid(FORMAT, "missing Segment element"));
+        };
+        let segment_start = segment_offset + segment_header_length as u64;
+
+        let mut metadata = MediaMetadata::new(MediaKind::Audio, container);
+        let mut pending = vec![segment_start];
+        let mut visited = Vec::new();
+        let (mut has_info, mut has_tracks, mut has_tags) = (false, false, false);
+
+        // Walk top-level elements from each start position until the first
+        // cluster; SeekHead entries add further start positions
+        while let Some(mut position) = pending.pop() {
+            while position < size && !visited.contains(&position) {
+                visited.push(position);
+                let element_head = read_at(source, position, 12)?;
+                let Some((id, Some(element_size), length)) = element_header(&element_head) else {
+                    break;
+                };
+                let body_start = position + length as u64;
+                let read_body = |source: &mut dyn MediaSource| {
+                    read_at(source, body_start, element_size.min(MAX_ELEMENT_BYTES))
+                };
+
+                match id {
+                    INFO if !has_info => {
+                        read_info(&read_body(source)?, &mut metadata);
+                        has_info = true;
+                    }
+                    TRACKS if !has_tracks => {
+                        read_tracks(&read_body(source)?, &mut metadata);
+                        has_tracks = true;
+                    }
+                    TAGS if !has_tags => {
+                        read_tags(&read_body(source)?, &mut metadata);
+                        has_tags = true;
+                    }
+                    SEEK_HEAD => {
+                        for (target, offset) in seek_positions(&read_body(source)?) {
+                            if matches!(target, INFO | TRACKS | TAGS | SEEK_HEAD) {
+                                pending.push(segment_start + offset);
+                            }
+                        }
+                    }
+                    CLUSTER => break,
+                    _ => {}
+                }
+                position = body_start + element_size;
+            }
+        }
+
+        if !has_tracks {
+            return Err(MediaProbeError::invalid(FORMAT, "missing Tracks element"));
+        }
+        metadata.fill_bitrate(size);
+        Ok(metadata)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    fn element(id: u32, body: &[u8]) -> Vec<u8> {
+        let mut data: Vec<u8> = id
+            .to_be_bytes()
+            .into_iter()
+            .skip_while(|b| *b == 0)
+            .collect();
+        // Always an 8-byte size, as some muxers write it
+        data.push(0x01);
+        data.extend_from_slice(&(body.len() as u64).to_be_bytes()[1..]);
+        data.extend_from_slice(body);
+        data
+    }
+
+    fn uint(id: u32, value: u64) -> Vec<u8> {
+        element(id, &value.to_be_bytes())
+    }
+
+    #[test]
+    fn test_reads_webm_with_tags_after_clusters() {
+        let header = [element(DOC_TYPE, b"webm"), uint(0x4287, 4)].concat();
+
+        let info = [
+            uint(TIMECODE_SCALE, 1_000_000),
+            element(DURATION, &(754_500.0f64).to_be_bytes()),
+        ]
+        .concat();
+        let video = [uint(PIXEL_WIDTH, 1280), uint(PIXEL_HEIGHT, 720)].concat();
+        let audio = [
+            element(SAMPLING_FREQUENCY, &(48_000.0f32).to_be_bytes()),
+            uint(CHANNELS, 1),
+        ]
+        .concat();
+        let tracks = [
+            element(
+                TRACK_ENTRY,
+                &[
+                    uint(TRACK_TYPE, 1),
+                    element(CODEC_ID, b"V_VP9"),
+                    uint(DEFAULT_DURATION, 40_000_000),
+                    element(VIDEO, &video),
+                ]
+                .concat(),
+            ),
+            element(
+                TRACK_ENTRY,
+                &[
+                    uint(TRACK_TYPE, 2),
+                    element(CODEC_ID, b"A_OPUS"),
+                    element(AUDIO, &audio),
+                ]
+                .concat(),
+            ),
+        ]
+        .concat();
+        let simple_tag = [element(TAG_NAME, b"TITLE"), element(TAG_STRING, b"Hearing")].concat();
+        let tags = element(TAG, &element(SIMPLE_TAG, &simple_tag));
+
+        let info = element(INFO, &info);
+        let tracks = element(TRACKS, &tracks);
+        let cluster = element(CLUSTER, &[0u8; 500]);
+        // The SeekHead has a fixed size, so the offsets can be computed first
+        let seek_head_length = element(
+            SEEK_HEAD,
+            &element(SEEK, &[uint(SEEK_ID, 0), uint(SEEK_POSITION, 0)].concat()),
+        )
+        .len() as u64;
+        let tags_offset = seek_head_length + (info.len() + tracks.len() + cluster.len()) as u64;
+        let seek_head = element(
+            SEEK_HEAD,
+            &element(
+                SEEK,
+                &[
+                    uint(SEEK_ID, u64::from(TAGS)),
+                    uint(SEEK_POSITION, tags_offset),
+                ]
+                .concat(),
+            ),
+        );
+
+        let segment = [seek_head, info, tracks, cluster, element(TAGS, &tags)].concat();
+        let mut file = element(EBML, &header);
+        file.extend(element(SEGMENT, &segment));
+
+        let metadata = MatroskaProbe
+            .probe(&mut Cursor::new(file.clone()), file.len() as u64)
+            .unwrap();
+        assert_eq!(metadata.kind, MediaKind::Video);
+        assert_eq!(metadata.container, "WebM");
+        assert_eq!(metadata.duration_ms, Some(754_500));
+        assert_eq!(metadata.video_codec.as_deref(), Some("VP9"));
+        assert_eq!(metadata.width, Some(1280));
+        assert_eq!(metadata.height, Some(720));
+        assert_eq!(metadata.frame_rate, Some(25.0));
+        assert_eq!(metadata.audio_codec.as_deref(), Some("Opus"));
+        assert_eq!(metadata.sample_rate, Some(48_000));
+        assert_eq!(metadata.channels, Some(1));
+        assert_eq!(metadata.tags.title(), Some("Hearing"));
+    }
+}
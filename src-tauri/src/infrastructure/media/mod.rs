@@ -0,0 +1,63 @@
+mod binary;
+pub mod flac_probe;
+pub mod matroska_probe;
+pub mod mp3_probe;
+pub mod mp4_probe;
+pub mod ogg_probe;
+mod tags;
+pub mod wav_probe;
+
+pub use flac_probe::FlacProbe;
+pub use matroska_probe::MatroskaProbe;
+pub use mp3_probe::Mp3Probe;
+pub use mp4_probe::Mp4Probe;
+pub use ogg_probe::OggProbe;
+pub use wav_probe::WavProbe;
+
+use std::sync::Arc;
+
+use crate::domain::media::MediaProbe;
+
+/// Looks up the media probe for a file extension
+#[derive(Clone, Default)]
+pub struct MediaProbeRegistry {
+    probes: Vec<Arc<dyn MediaProbe>>,
+}
+
+impl MediaProbeRegistry {
+    /// Create a registry with all built-in probes
+    pub fn with_defaults() -> Self {
+        let mut registry = Self::default();
+        registry.register(Arc::new(Mp3Probe));
+        registry.register(Arc::new(WavProbe));
+        registry.register(Arc::new(FlacProbe));
+        registry.register(Arc::new(OggProbe));
+        registry.register(Arc::new(Mp4Probe));
+        registry.register(Arc::new(MatroskaProbe));
+        registry
+    }
+
+    /// Add a probe; later registrations take precedence
+    pub fn register(&mut self, probe: Arc<dyn MediaProbe>) {
+        self.probes.insert(0, probe);
+    }
+
+    pub fn for_extension(&self, extension: &str) -> Option<Arc<dyn MediaProbe>> {
+        self.probes
+            .iter()
+            .find(|probe| probe.supports(extension))
+            .cloned()
+    }
+
+    /// All extensions that can be probed, sorted
+    pub fn supported_extensions(&self) -> Vec<String> {
+        let mut extensions: Vec<String> = self
+            .probes
+            .iter()
+            .flat_map(|probe| probe.extensions().iter().map(|e| e.to_string()))
+            .collect();
+        extensions.sort();
+        extensions.dedup();
+        extensions
+    }
+}
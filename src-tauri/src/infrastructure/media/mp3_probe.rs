@@ -0,0 +1,410 @@
+use std::io::SeekFrom;
+
+use super::binary::{read_at, read_up_to, ByteReader};
+use super::tags::{id3_genre, id3_genre_text, latin1};
+use crate::domain::media::{
+    MediaKind, MediaMetadata, MediaProbe, MediaProbeError, MediaSource, MediaTags,
+};
+
+/// Probe for MPEG audio files (.mp3)
+///
+/// Reads ID3v2 (2.2 to 2.4) and ID3v1 tags and the first MPEG frame
+/// header. Duration comes from a Xing/Info or VBRI header when the file has
+/// one, which variable bitrate encoders write; otherwise it is computed
+/// from the constant bitrate and the size of the audio data.
+pub struct Mp3Probe;
+
+const FORMAT: &str = "MP3";
+
+/// How far past the tag to look for the first frame
+const SYNC_SEARCH_BYTES: u64 = 64 * 1024;
+
+/// Larger tags are mostly cover art; only their start is read
+const MAX_TAG_BYTES: u64 = 1024 * 1024;
+
+/// Length of an ID3v2 tag, header and footer included, from its first
+/// 10 bytes
+pub(super) fn id3v2_length(header: &[u8]) -> Option<u64> {
+    if header.len() < 10 || &header[..3] != b"ID3" {
+        return None;
+    }
+    let size = syncsafe(&header[6..10]);
+    let footer = if header[5] & 0x10 != 0 { 10 } else { 0 };
+    Some(10 + u64::from(size) + footer)
+}
+
+fn syncsafe(bytes: &[u8]) -> u32 {
+    bytes
+        .iter()
+        .fold(0u32, |value, b| (value << 7) | u32::from(b & 0x7F))
+}
+
+/// Text of an ID3v2 text frame, after its encoding byte
+fn id3_text(encoding: u8, bytes: &[u8]) -> String {
+    let utf16 = |bytes: &[u8], big_endian: bool| {
+        let units: Vec<u16> = bytes
+            .chunks_exact(2)
+            .map(|pair| {
+                if big_endian {
+                    u16::from_be_bytes([pair[0], pair[1]])
+                } else {
+                    u16::from_le_bytes([pair[0], pair[1]])
+                }
+            })
+            .collect();
+        String::from_utf16_lossy(&units)
+    };
+    let text = match encoding {
+        1 => match bytes {
+            [0xFE, 0xFF, rest @ ..] => utf16(rest, true),
+            [0xFF, 0xFE, rest @ ..] => utf16(rest, false),
+            _ => utf16(bytes, false),
+        },
+        2 => utf16(bytes, true),
+        3 => String::from_utf8_lossy(bytes).into_owned(),
+        _ => latin1(bytes),
+    };
+    // Version 2.4 separates multiple values with NUL; keep the first
+    text.split('\0').next().unwrap_or_default().to_string()
+}
+
+/// Split a COMM frame after its encoding and language into its text
+fn id3_comment(encoding: u8, bytes: &[u8]) -> String {
+    let wide = matches!(encoding, 1 | 2);
+    let end_of_description = if wide {
+        bytes
+            .chunks_exact(2)
+            .position(|pair| pair == [0, 0])
+            .map(|i| i * 2 + 2)
+    } else {
+        bytes.iter().position(|b| *b == 0).map(|i| i + 1)
+    };
+    let text = &bytes[end_of_description.unwrap_or(bytes.len()).min(bytes.len())..];
+    id3_text(encoding, text)
+}
+
+fn read_id3v2(tag: &[u8], tags: &mut MediaTags) {
+    let major = tag[3];
+    let mut reader = ByteReader::new(&tag[10..]);
+    if major >= 3 && tag[5] & 0x40 != 0 {
+        // Skip the extended header
+        let size = reader.be_u32().unwrap_or(0);
+        let skip = if major == 4 {
+            syncsafe(&size.to_be_bytes()).saturating_sub(4)
+        } else {
+            size
+        };
+        if reader.skip(skip as usize).is_none() {
+            return;
+        }
+    }
+
+    loop {
+        let (id, size) = if major == 2 {
+            let Some(header) = reader.bytes(6) else {
+                return;
+            };
+            let size = u32::from_be_bytes([0, header[3], header[4], header[5]]);
+            (latin1(&header[..3]), size)
+        } else {
+            let Some(header) = reader.bytes(10) else {
+                return;
+            };
+            let size = if major == 4 {
+                syncsafe(&header[4..8])
+            } else {
+                u32::from_be_bytes([header[4], header[5], header[6], header[7]])
+            };
+            (latin1(&header[..4]), size)
+        };
+        if id.starts_with('\0') {
+            // Padding
+            return;
+        }
+        let Some(body) = reader.bytes(size as usize) else {
+            return;
+        };
+        let Some((&encoding, text)) = body.split_first() else {
+            continue;
+        };
+
+        let name = match id.as_str() {
+            "TIT2" | "TT2" => MediaTags::TITLE,
+            "TPE1" | "TP1" => MediaTags::ARTIST,
+            "TALB" | "TAL" => MediaTags::ALBUM,
+            "TYER" | "TYE" | "TDRC" | "TDOR" => MediaTags::YEAR,
+            "TRCK" | "TRK" => MediaTags::TRACK,
+            "TPE2" | "TP2" => "album_artist",
+            "TCOM" | "TCM" => "composer",
+            "TSSE" | "TSS" | "TENC" | "TEN" => "encoder",
+            "TCON" | "TCO" => {
+                tags.set(MediaTags::GENRE, &id3_genre_text(&id3_text(encoding, text)));
+                continue;
+            }
+            "COMM" | "COM" if text.len() > 3 => {
+                tags.set(MediaTags::COMMENT, &id3_comment(encoding, &text[3..]));
+                continue;
+            }
+            _ => continue,
+        };
+        tags.set(name, &id3_text(encoding, text));
+    }
+}
+
+fn read_id3v1(tag: &[u8], tags: &mut MediaTags) {
+    let field = |range: std::ops::Range<usize>| latin1(&tag[range]);
+    tags.set(MediaTags::TITLE, &field(3..33));
+    tags.set(MediaTags::ARTIST, &field(33..63));
+    tags.set(MediaTags::ALBUM, &field(63..93));
+    tags.set(MediaTags::YEAR, &field(93..97));
+    // ID3v1.1 keeps the track number in the last byte of the comment
+    if tag[125] == 0 && tag[126] != 0 {
+        tags.set(MediaTags::COMMENT, &field(97..125));
+        tags.set(MediaTags::TRACK, &tag[126].to_string());
+    } else {
+        tags.set(MediaTags::COMMENT, &field(97..127));
+    }
+    if let Some(genre) = id3_genre(usize::from(tag[127])) {
+        tags.set(MediaTags::GENRE, genre);
+    }
+}
+
+/// Properties of an MPEG audio frame header
+struct FrameHeader {
+    mpeg1: bool,
+    layer: u8,
+    bitrate_kbps: u32,
+    sample_rate: u32,
+    channels: u16,
+}
+
+impl FrameHeader {
+    fn parse(bytes: &[u8]) -> Option<Self> {
+        if bytes.len() < 4 || bytes[0] != 0xFF || bytes[1] & 0xE0 != 0xE0 {
+            return None;
+        }
+        let version = (bytes[1] >> 3) & 0x03;
+        let layer = match (bytes[1] >> 1) & 0x03 {
+            1 => 3,
+            2 => 2,
+            3 => 1,
+            _ => return None,
+        };
+        let bitrate_index = usize::from(bytes[2] >> 4);
+        let rate_index = usize::from((bytes[2] >> 2) & 0x03);
+        if version == 1 || bitrate_index == 0 || bitrate_index == 15 || rate_index == 3 {
+            return None;
+        }
+
+        let mpeg1 = version == 3;
+        let bitrates: [u32; 14] = match (mpeg1, layer) {
+            (true, 1) => [
+                32, 64, 96, 128, 160, 192, 224, 256, 288, 320, 352, 384, 416, 448,
+            ],
+            (true, 2) => [
+                32, 48, 56, 64, 80, 96, 112, 128, 160, 192, 224, 256, 320, 384,
+            ],
+            (true, _) => [
+                32, 40, 48, 56, 64, 80, 96, 112, 128, 160, 192, 224, 256, 320,
+            ],
+            (false, 1) => [
+                32, 48, 56, 64, 80, 96, 112, 128, 144, 160, 176, 192, 224, 256,
+            ],
+            (false, _) => [8, 16, 24, 32, 40, 48, 56, 64, 80, 96, 112, 128, 144, 160],
+        };
+        let rates: [u32; 3] = match version {
+            3 => [44_100, 48_000, 32_000],
+            2 => [22_050, 24_000, 16_000],
+            _ => [11_025, 12_000, 8_000],
+        };
+
+        Some(FrameHeader {
+            mpeg1,
+            layer,
+            bitrate_kbps: bitrates[bitrate_index - 1],
+            sample_rate: rates[rate_index],
+            channels: if bytes[3] >> 6 == 3 { 1 } else { 2 },
+        })
+    }
+
+    fn samples_per_frame(&self) -> u64 {
+        match (self.layer, self.mpeg1) {
+            (1, _) => 384,
+            (3, false) => 576,
+            _ => 1152,
+        }
+    }
+
+    /// Offset of a Xing/Info header from the frame start
+    fn xing_offset(&self) -> usize {
+        4 + match (self.mpeg1, self.channels) {
+            (true, 1) => 17,
+            (true, _) => 32,
+            (false, 1) => 9,
+            (false, _) => 17,
+        }
+    }
+
+    /// Frame count of a Xing/Info or VBRI header in the first frame
+    fn frame_count(&self, frame: &[u8]) -> Option<u64> {
+        let xing = self.xing_offset();
+        if let Some(tag) = frame.get(xing..xing + 12) {
+            if (tag.starts_with(b"Xing") || tag.starts_with(b"Info")) && tag[7] & 0x01 != 0 {
+                return Some(u64::from(u32::from_be_bytes([
+                    tag[8], tag[9], tag[10], tag[11],
+                ])));
+            }
+        }
+        let vbri = frame.get(36..54)?;
+        vbri.starts_with(b"VBRI")
+            .then(|| u64::from(u32::from_be_bytes([vbri[14], vbri[15], vbri[16], vbri[17]])))
+    }
+}
+
+impl MediaProbe for Mp3Probe {
+    fn method(&self) -> &'static str {
+        "mp3-probe-v1"
+    }
+
+    fn extensions(&self) -> &'static [&'static str] {
+        &["mp3"]
+    }
+
+    fn probe(
+        &self,
+        source: &mut dyn MediaSource,
+        size: u64,
+    ) -> Result<MediaMetadata, MediaProbeError> {
+        let mut metadata = MediaMetadata::new(MediaKind::Audio, FORMAT);
+
+        let header = read_at(source, 0, 10)?;
+        let audio_start = match id3v2_length(&header) {
+            Some(length) => {
+                let tag = read_at(source, 0, length.min(MAX_TAG_BYTES))?;
+                read_id3v2(&tag, &mut metadata.tags);
+                length
+            }
+            None => 0,
+        };
+
+        let mut audio_end = size;
+        if size >= audio_start + 128 {
+            let tag = read_at(source, size - 128, 128)?;
+            if tag.starts_with(b"TAG") {
+                read_id3v1(&tag, &mut metadata.tags);
+                audio_end -= 128;
+            }
+        }
+
+        source.seek(SeekFrom::Start(audio_start))?;
+        let window = read_up_to(source, SYNC_SEARCH_BYTES)?;
+        let (offset, frame) = (0..window.len().saturating_sub(4))
+            .find_map(|i| FrameHeader::parse(&window[i..]).map(|frame| (i, frame)))
+            .ok_or_else(|| MediaProbeError::invalid(FORMAT, "no MPEG audio frame found"))?;
+
+        metadata.audio_codec = Some(format!("MP{}", frame.layer));
+        metadata.sample_rate = Some(frame.sample_rate);
+        metadata.channels = Some(frame.channels);
+
+        let audio_bytes = audio_end.saturating_sub(audio_start + offset as u64);
+        match frame.frame_count(&window[offset..]) {
+            Some(frames) => {
+                metadata.set_duration(
+                    frames * frame.samples_per_frame(),
+                    u64::from(frame.sample_rate),
+                );
+                metadata.fill_bitrate(audio_bytes);
+            }
+            None => {
+                metadata.bitrate_kbps = Some(frame.bitrate_kbps);
+                metadata.set_duration(audio_bytes * 8, u64::from(frame.bitrate_kbps) * 1000);
+            }
+        }
+        Ok(metadata)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    /// A 128 kbit/s 44.1 kHz stereo MPEG-1 Layer III frame header
+    const FRAME_HEADER: [u8; 4] = [0xFF, 0xFB, 0x90, 0x00];
+
+    fn text_frame(id: &str, text: &str) -> Vec<u8> {
+        let mut frame = id.as_bytes().to_vec();
+        frame.extend_from_slice(&(text.len() as u32 + 1).to_be_bytes());
+        frame.extend_from_slice(&[0, 0, 3]);
+        frame.extend_from_slice(text.as_bytes());
+        frame
+    }
+
+    fn id3v2(frames: &[Vec<u8>]) -> Vec<u8> {
+        let body: Vec<u8> = frames.concat();
+        let size = body.len() as u32;
+        let mut tag = b"ID3\x03\x00\x00".to_vec();
+        tag.extend((0..4).rev().map(|i| ((size >> (7 * i)) & 0x7F) as u8));
+        tag.extend(body);
+        tag
+    }
+
+    #[test]
+    fn test_reads_cbr_duration_and_id3v2_tags() {
+        let mut file = id3v2(&[
+            text_frame("TIT2", "Interview"),
+            text_frame("TPE1", "Witness A"),
+            text_frame("TCON", "(12)"),
+            text_frame("TYER", "2023"),
+        ]);
+        // One second of 128 kbit/s audio
+        let frame_bytes = 16_000;
+        let mut audio = vec![0u8; frame_bytes];
+        audio[..4].copy_from_slice(&FRAME_HEADER);
+        file.extend(audio);
+
+        let metadata = Mp3Probe
+            .probe(&mut Cursor::new(file.clone()), file.len() as u64)
+            .unwrap();
+        assert_eq!(metadata.audio_codec.as_deref(), Some("MP3"));
+        assert_eq!(metadata.sample_rate, Some(44_100));
+        assert_eq!(metadata.channels, Some(2));
+        assert_eq!(metadata.bitrate_kbps, Some(128));
+        assert_eq!(metadata.duration_ms, Some(1000));
+        assert_eq!(metadata.tags.title(), Some("Interview"));
+        assert_eq!(metadata.tags.artist(), Some("Witness A"));
+        assert_eq!(metadata.tags.genre(), Some("Other"));
+        assert_eq!(metadata.tags.year(), Some("2023"));
+    }
+
+    #[test]
+    fn test_reads_xing_frame_count_and_id3v1() {
+        let mut frame = vec![0u8; 417];
+        frame[..4].copy_from_slice(&FRAME_HEADER);
+        frame[36..40].copy_from_slice(b"Xing");
+        frame[43] = 0x01;
+        // 38.28 frames per second; 3828 frames are 100 seconds
+        frame[44..48].copy_from_slice(&3828u32.to_be_bytes());
+        let mut file = frame;
+        file.extend(vec![0u8; 100_000]);
+
+        let mut v1 = vec![0u8; 128];
+        v1[..3].copy_from_slice(b"TAG");
+        v1[3..8].copy_from_slice(b"Call1");
+        v1[126] = 7;
+        v1[127] = 17;
+        file.extend(v1);
+
+        let metadata = Mp3Probe
+            .probe(&mut Cursor::new(file.clone()), file.len() as u64)
+            .unwrap();
+        assert_eq!(metadata.duration_ms, Some(99_996));
+        assert_eq!(metadata.tags.title(), Some("Call1"));
+        assert_eq!(metadata.tags.get(MediaTags::TRACK), Some("7"));
+        assert_eq!(metadata.tags.genre(), Some("Rock"));
+
+        assert!(Mp3Probe
+            .probe(&mut Cursor::new(vec![0u8; 500]), 500)
+            .is_err());
+    }
+}
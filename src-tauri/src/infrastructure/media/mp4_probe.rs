@@ -0,0 +1,443 @@
+use super::binary::{read_at, ByteReader};
+use super::tags::{id3_genre, latin1};
+use crate::domain::media::{
+    MediaKind, MediaMetadata, MediaProbe, MediaProbeError, MediaSource, MediaTags,
+};
+
+/// Probe for ISO base media files (.mp4, .m4a, .m4v, .mov)
+///
+/// Walks the top-level boxes to the `moov` box and reads the movie header,
+/// the track headers and sample descriptions, and iTunes-style or
+/// QuickTime user data tags. Media data in `mdat` is never read.
+pub struct Mp4Probe;
+
+const FORMAT: &str = "MP4";
+
+/// Larger `moov` boxes are not read; they only occur with very long
+/// recordings that have huge sample tables
+const MAX_MOOV_BYTES: u64 = 64 * 1024 * 1024;
+
+/// A box inside an in-memory parent
+struct Mp4Box<'a> {
+    kind: [u8; 4],
+    body: &'a [u8],
+}
+
+/// Children of an in-memory box body
+fn children(data: &[u8]) -> Vec<Mp4Box<'_>> {
+    let mut boxes = Vec::new();
+    let mut reader = ByteReader::new(data);
+    while reader.remaining() >= 8 {
+        let (Some(size), Some(kind)) = (reader.be_u32(), reader.bytes(4)) else {
+            break;
+        };
+        let (header_length, box_size) = match size {
+            0 => (8, reader.remaining() as u64 + 8),
+            1 => match reader.be_u64() {
+                Some(size) => (16, size),
+                None => break,
+            },
+            size => (8, u64::from(size)),
+        };
+        // Sizes smaller than the header or past the parent end are damaged
+        let Some(body_length) = box_size
+            .checked_sub(header_length)
+            .and_then(|length| usize::try_from(length).ok())
+            .filter(|length| *length <= reader.remaining())
+        else {
+            break;
+        };
+        let Some(body) = reader.bytes(body_length) else {
+            break;
+        };
+        boxes.push(Mp4Box {
+            kind: [kind[0], kind[1], kind[2], kind[3]],
+            body,
+        });
+    }
+    boxes
+}
+
+fn child<'a>(data: &'a [u8], kind: &[u8; 4]) -> Option<Mp4Box<'a>> {
+    children(data).into_iter().find(|b| &b.kind == kind)
+}
+
+/// Follow a path of box types from `data`
+fn find<'a>(data: &'a [u8], path: &[&[u8; 4]]) -> Option<&'a [u8]> {
+    path.iter()
+        .try_fold(data, |body, kind| child(body, kind).map(|b| b.body))
+}
+
+/// Time scale and duration from an `mvhd` or `mdhd` full box
+fn header_duration(body: &[u8]) -> Option<(u64, u64)> {
+    let mut reader = ByteReader::new(body);
+    let version = reader.u8()?;
+    reader.skip(3)?;
+    if version == 1 {
+        reader.skip(16)?;
+        Some((u64::from(reader.be_u32()?), reader.be_u64()?))
+    } else {
+        reader.skip(8)?;
+        Some((u64::from(reader.be_u32()?), u64::from(reader.be_u32()?)))
+    }
+}
+
+fn codec_name(fourcc: &[u8]) -> String {
+    match fourcc {
+        b"avc1" | b"avc3" => "H.264",
+        b"hvc1" | b"hev1" => "H.265",
+        b"av01" => "AV1",
+        b"vp08" => "VP8",
+        b"vp09" => "VP9",
+        b"mp4v" => "MPEG-4 Visual",
+        b"jpeg" => "Motion JPEG",
+        b"apcn" | b"apch" | b"apcs" | b"apco" | b"ap4h" => "ProRes",
+        b"mp4a" => "AAC",
+        b"alac" => "ALAC",
+        b"ac-3" => "AC-3",
+        b"ec-3" => "E-AC-3",
+        b"Opus" => "Opus",
+        b"fLaC" => "FLAC",
+        b".mp3" => "MP3",
+        b"samr" => "AMR",
+        b"lpcm" | b"sowt" | b"twos" | b"in24" | b"in32" | b"fl32" => "PCM",
+        other => return latin1(other).trim().to_string(),
+    }
+    .to_string()
+}
+
+/// Read one `trak` box into `metadata`
+fn read_track(trak: &[u8], metadata: &mut MediaMetadata) {
+    let Some(mdia) = find(trak, &[b"mdia"]) else {
+        return;
+    };
+    let handler = find(mdia, &[b"hdlr"]).and_then(|hdlr| hdlr.get(8..12));
+    let Some(entry) = find(mdia, &[b"minf", b"stbl", b"stsd"])
+        .and_then(|stsd| stsd.get(8..))
+        .and_then(|entries| children(entries).into_iter().next())
+    else {
+        return;
+    };
+    // Sample entries start with 6 reserved bytes and a data reference index
+    let mut reader = ByteReader::new(entry.body);
+
+    match handler {
+        Some(b"vide") if metadata.video_codec.is_none() => {
+            metadata.kind = MediaKind::Video;
+            metadata.video_codec = Some(codec_name(&entry.kind));
+            if reader.skip(24).is_some() {
+                metadata.width = reader.be_u16().map(u32::from);
+                metadata.height = reader.be_u16().map(u32::from);
+            }
+            let timescale = find(mdia, &[b"mdhd"])
+                .and_then(header_duration)
+                .map(|(timescale, _)| timescale);
+            let samples = find(mdia, &[b"minf", b"stbl", b"stts"]).and_then(sample_timing);
+            if let (Some(timescale), Some((count, duration))) = (timescale, samples) {
+                if duration > 0 {
+                    let rate = count as f64 * timescale as f64 / duration as f64;
+                    metadata.frame_rate = Some((rate * 1000.0).round() / 1000.0);
+                }
+            }
+        }
+        Some(b"soun") if metadata.audio_codec.is_none() => {
+            metadata.audio_codec = Some(codec_name(&entry.kind));
+            if reader.skip(16).is_some() {
+                metadata.channels = reader.be_u16();
+                reader.skip(6);
+                // 16.16 fixed point
+                metadata.sample_rate = reader.be_u32().map(|rate| rate >> 16).filter(|r| *r > 0);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Total sample count and duration from an `stts` box
+fn sample_timing(stts: &[u8]) -> Option<(u64, u64)> {
+    let mut reader = ByteReader::new(stts);
+    reader.skip(4)?;
+    let entries = reader.be_u32()?;
+    let mut count = 0u64;
+    let mut duration = 0u64;
+    for _ in 0..entries {
+        let samples = u64::from(reader.be_u32()?);
+        count += samples;
+        duration += samples * u64::from(reader.be_u32()?);
+    }
+    Some((count, duration))
+}
+
+/// Canonical tag name of an iTunes or QuickTime metadata atom
+fn tag_name(kind: &[u8; 4]) -> Option<&'static str> {
+    Some(match kind {
+        b"\xA9nam" => MediaTags::TITLE,
+        b"\xA9ART" | b"\xA9aut" => MediaTags::ARTIST,
+        b"\xA9alb" => MediaTags::ALBUM,
+        b"\xA9gen" => MediaTags::GENRE,
+        b"\xA9day" => MediaTags::YEAR,
+        b"\xA9cmt" | b"desc" => MediaTags::COMMENT,
+        b"aART" => "album_artist",
+        b"\xA9wrt" => "composer",
+        b"\xA9too" | b"\xA9enc" => "encoder",
+        _ => return None,
+    })
+}
+
+/// Read iTunes-style `ilst` items
+fn read_ilst(ilst: &[u8], tags: &mut MediaTags) {
+    for item in children(ilst) {
+        let Some(data) = child(item.body, b"data") else {
+            continue;
+        };
+        // Type indicator and locale precede the value
+        let Some(value) = data.body.get(8..) else {
+            continue;
+        };
+        match &item.kind {
+            b"trkn" if value.len() >= 4 => {
+                tags.set(
+                    MediaTags::TRACK,
+                    &u16::from_be_bytes([value[2], value[3]]).to_string(),
+                );
+            }
+            b"gnre" if value.len() >= 2 => {
+                let index = usize::from(u16::from_be_bytes([value[0], value[1]]));
+                if let Some(genre) = index.checked_sub(1).and_then(id3_genre) {
+                    tags.set(MediaTags::GENRE, genre);
+                }
+            }
+            kind => {
+                if let Some(name) = tag_name(kind) {
+                    tags.set(name, &String::from_utf8_lossy(value));
+                }
+            }
+        }
+    }
+}
+
+/// Read classic QuickTime `udta` text atoms
+fn read_quicktime_udta(udta: &[u8], tags: &mut MediaTags) {
+    for atom in children(udta) {
+        let Some(name) = tag_name(&atom.kind).filter(|_| atom.kind[0] == 0xA9) else {
+            continue;
+        };
+        // A list of (length, language, text) entries; the first is used
+        let mut reader = ByteReader::new(atom.body);
+        if let (Some(length), Some(_)) = (reader.be_u16(), reader.be_u16()) {
+            if let Some(text) = reader.bytes(usize::from(length)) {
+                tags.set(name, &String::from_utf8_lossy(text));
+            }
+        }
+    }
+}
+
+impl MediaProbe for Mp4Probe {
+    fn method(&self) -> &'static str {
+        "mp4-probe-v1"
+    }
+
+    fn extensions(&self) -> &'static [&'static str] {
+        &["mp4", "m4a", "m4v", "mov"]
+    }
+
+    fn probe(
+        &self,
+        source: &mut dyn MediaSource,
+        size: u64,
+    ) -> Result<MediaMetadata, MediaProbeError> {
+        let mut container = None;
+        let mut moov = None;
+        let mut position = 0u64;
+
+        while position + 8 <= size && moov.is_none() {
+            let header = read_at(source, position, 16)?;
+            let mut reader = ByteReader::new(&header);
+            let (Some(box_size), Some(kind)) = (reader.be_u32(), reader.bytes(4)) else {
+                break;
+            };
+            let (header_length, box_size) = match box_size {
+                0 => (8, size - position),
+                1 => (16, reader.be_u64().unwrap_or(0)),
+                box_size => (8, u64::from(box_size)),
+            };
+            if box_size < header_length || box_size > size - position {
+                break;
+            }
+            let body_start = position + header_length;
+            let body_length = box_size - header_length;
+
+            match kind {
+                b"ftyp" => {
+                    let brand = read_at(source, body_start, 4)?;
+                    container = Some(match brand.as_slice() {
+                        b"qt  " => "QuickTime",
+                        b"M4A " | b"M4B " => "M4A",
+                        _ => FORMAT,
+                    });
+                }
+                b"moov" if body_length <= MAX_MOOV_BYTES => {
+                    moov = Some(read_at(source, body_start, body_length)?);
+                }
+                b"moov" => {
+                    return Err(MediaProbeError::invalid(FORMAT, "moov box is too large"));
+                }
+                _ => {}
+            }
+            let Some(next) = position.checked_add(box_size) else {
+                break;
+            };
+            position = next;
+        }
+
+        let Some(moov) = moov else {
+            return Err(MediaProbeError::invalid(FORMAT, "missing moov box"));
+        };
+        // Old QuickTime files have no ftyp box
+        let mut metadata = MediaMetadata::new(MediaKind::Audio, container.unwrap_or("QuickTime"));
+
+        if let Some((timescale, duration)) = find(&moov, &[b"mvhd"]).and_then(header_duration) {
+            metadata.set_duration(duration, timescale);
+        }
+        for trak in children(&moov).iter().filter(|b| &b.kind == b"trak") {
+            read_track(trak.body, &mut metadata);
+        }
+        if let Some(udta) = find(&moov, &[b"udta"]) {
+            // The meta box is a full box: skip its version and flags
+            if let Some(ilst) = find(udta, &[b"meta"])
+                .and_then(|meta| meta.get(4..))
+                .and_then(|meta| find(meta, &[b"ilst"]))
+            {
+                read_ilst(ilst, &mut metadata.tags);
+            }
+            read_quicktime_udta(udta, &mut metadata.tags);
+        }
+
+        metadata.fill_bitrate(size);
+        Ok(metadata)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    fn boxed(kind: &[u8; 4], body: &[u8]) -> Vec<u8> {
+        let mut b = ((body.len() + 8) as u32).to_be_bytes().to_vec();
+        b.extend_from_slice(kind);
+        b.extend_from_slice(body);
+        b
+    }
+
+    fn mvhd(timescale: u32, duration: u32) -> Vec<u8> {
+        let mut body = vec![0u8; 12];
+        body.extend_from_slice(&timescale.to_be_bytes());
+        body.extend_from_slice(&duration.to_be_bytes());
+        body.extend_from_slice(&[0u8; 80]);
+        body
+    }
+
+    fn track(handler: &[u8; 4], entry: Vec<u8>, stts: &[(u32, u32)]) -> Vec<u8> {
+        let mut hdlr = vec![0u8; 8];
+        hdlr.extend_from_slice(handler);
+        hdlr.extend_from_slice(&[0u8; 12]);
+
+        let mut stsd = vec![0, 0, 0, 0, 0, 0, 0, 1];
+        stsd.extend(entry);
+
+        let mut stts_body = vec![0u8; 4];
+        stts_body.extend_from_slice(&(stts.len() as u32).to_be_bytes());
+        for (count, delta) in stts {
+            stts_body.extend_from_slice(&count.to_be_bytes());
+            stts_body.extend_from_slice(&delta.to_be_bytes());
+        }
+
+        let stbl = [boxed(b"stsd", &stsd), boxed(b"stts", &stts_body)].concat();
+        let minf = boxed(b"stbl", &stbl);
+        let mdia = [
+            boxed(b"mdhd", &mvhd(30_000, 0)),
+            boxed(b"hdlr", &hdlr),
+            boxed(b"minf", &minf),
+        ]
+        .concat();
+        boxed(b"trak", &boxed(b"mdia", &mdia))
+    }
+
+    #[test]
+    fn test_reads_video_and_audio_tracks_and_ilst_tags() {
+        let mut visual = vec![0u8; 24];
+        visual.extend_from_slice(&1920u16.to_be_bytes());
+        visual.extend_from_slice(&1080u16.to_be_bytes());
+        visual.extend_from_slice(&[0u8; 50]);
+
+        let mut audio = vec![0u8; 16];
+        audio.extend_from_slice(&2u16.to_be_bytes());
+        audio.extend_from_slice(&[0u8; 6]);
+        audio.extend_from_slice(&(48_000u32 << 16).to_be_bytes());
+
+        let mut title = vec![0, 0, 0, 1, 0, 0, 0, 0];
+        title.extend_from_slice(b"Bodycam 12");
+        let ilst = boxed(b"\xA9nam", &boxed(b"data", &title));
+        let mut meta = vec![0u8; 4];
+        meta.extend(boxed(b"ilst", &ilst));
+
+        let moov = [
+            boxed(b"mvhd", &mvhd(1000, 125_000)),
+            track(b"vide", boxed(b"avc1", &visual), &[(3746, 1001)]),
+            track(b"soun", boxed(b"mp4a", &audio), &[]),
+            boxed(b"udta", &boxed(b"meta", &meta)),
+        ]
+        .concat();
+
+        let mut file = boxed(b"ftyp", b"isom\0\0\x02\0");
+        file.extend(boxed(b"mdat", &[0u8; 2_000]));
+        file.extend(boxed(b"moov", &moov));
+
+        let metadata = Mp4Probe
+            .probe(&mut Cursor::new(file.clone()), file.len() as u64)
+            .unwrap();
+        assert_eq!(metadata.kind, MediaKind::Video);
+        assert_eq!(metadata.container, "MP4");
+        assert_eq!(metadata.duration_ms, Some(125_000));
+        assert_eq!(metadata.video_codec.as_deref(), Some("H.264"));
+        assert_eq!(metadata.width, Some(1920));
+        assert_eq!(metadata.height, Some(1080));
+        assert_eq!(metadata.frame_rate, Some(29.97));
+        assert_eq!(metadata.audio_codec.as_deref(), Some("AAC"));
+        assert_eq!(metadata.sample_rate, Some(48_000));
+        assert_eq!(metadata.channels, Some(2));
+        assert_eq!(metadata.tags.title(), Some("Bodycam 12"));
+    }
+
+    #[test]
+    fn test_oversized_largesize_boxes_end_the_walk() {
+        let mut file = boxed(b"ftyp", b"M4A \0\0\0\0");
+        file.extend(1u32.to_be_bytes());
+        file.extend(b"mdat");
+        file.extend(u64::MAX.to_be_bytes());
+        file.extend(boxed(b"moov", &boxed(b"mvhd", &mvhd(1000, 5000))));
+        assert!(Mp4Probe
+            .probe(&mut Cursor::new(file.clone()), file.len() as u64)
+            .is_err());
+
+        // A truncated largesize inside moov stops reading its children
+        let mut moov = boxed(b"mvhd", &mvhd(1000, 5000));
+        moov.extend(1u32.to_be_bytes());
+        moov.extend(b"trak");
+        moov.extend(u64::MAX.to_be_bytes());
+        let file = boxed(b"moov", &moov);
+        let metadata = Mp4Probe
+            .probe(&mut Cursor::new(file.clone()), file.len() as u64)
+            .unwrap();
+        assert_eq!(metadata.duration_ms, Some(5000));
+    }
+
+    #[test]
+    fn test_rejects_file_without_moov() {
+        let file = boxed(b"ftyp", b"M4A \0\0\0\0");
+        assert!(Mp4Probe
+            .probe(&mut Cursor::new(file.clone()), file.len() as u64)
+            .is_err());
+    }
+}
@@ -0,0 +1,247 @@
+use std::io::SeekFrom;
+
+use super::binary::{read_at, read_up_to, ByteReader};
+use super::flac_probe::read_streaminfo;
+use super::tags::read_vorbis_comments;
+use crate::domain::media::{MediaKind, MediaMetadata, MediaProbe, MediaProbeError, MediaSource};
+
+/// Probe for Ogg files carrying Vorbis, Opus or FLAC audio
+/// (.ogg, .oga, .opus)
+///
+/// Reads the identification and comment packets at the start of the first
+/// logical stream, then the granule position of the last page in the
+/// file for the duration.
+pub struct OggProbe;
+
+const FORMAT: &str = "Ogg";
+
+/// Header packets of the first stream must arrive within this many bytes
+const HEADER_BYTES: u64 = 1024 * 1024;
+
+/// The last page is searched for in this many bytes at the end
+const TAIL_BYTES: u64 = 64 * 1024;
+
+/// One page of an Ogg bitstream
+struct Page<'a> {
+    granule: u64,
+    serial: u32,
+    /// Segment lengths from the lacing table
+    lacing: &'a [u8],
+    body: &'a [u8],
+}
+
+impl<'a> Page<'a> {
+    /// Parse the page at the start of `data`, returning it with its length
+    fn parse(data: &'a [u8]) -> Option<(Self, usize)> {
+        let mut reader = ByteReader::new(data);
+        if reader.bytes(4)? != b"OggS" {
+            return None;
+        }
+        reader.skip(2)?;
+        let granule = reader.le_u64()?;
+        let serial = reader.le_u32()?;
+        reader.skip(8)?;
+        let segments = reader.u8()?;
+        let lacing = reader.bytes(usize::from(segments))?;
+        let body_length = lacing.iter().map(|l| usize::from(*l)).sum();
+        let body = reader.bytes(body_length)?;
+        Some((
+            Page {
+                granule,
+                serial,
+                lacing,
+                body,
+            },
+            reader.position(),
+        ))
+    }
+}
+
+/// The first `count` packets of the first logical stream in `data`
+fn first_packets(data: &[u8], count: usize) -> Vec<Vec<u8>> {
+    let mut packets = Vec::new();
+    let mut current = Vec::new();
+    let mut serial = None;
+    let mut offset = 0;
+
+    while let Some((page, length)) = data.get(offset..).and_then(Page::parse) {
+        offset += length;
+        if *serial.get_or_insert(page.serial) != page.serial {
+            continue;
+        }
+        let mut body = ByteReader::new(page.body);
+        for &segment in page.lacing {
+            current.extend_from_slice(body.bytes(usize::from(segment)).unwrap_or_default());
+            // A segment shorter than 255 bytes ends the packet
+            if segment < 255 {
+                packets.push(std::mem::take(&mut current));
+                if packets.len() == count {
+                    return packets;
+                }
+            }
+        }
+    }
+    packets
+}
+
+/// Granule position of the last page of `serial` in `tail`
+fn last_granule(tail: &[u8], serial: u32) -> Option<u64> {
+    (0..tail.len().saturating_sub(27))
+        .rev()
+        .filter(|&i| tail[i..].starts_with(b"OggS"))
+        .filter_map(|i| Page::parse(&tail[i..]))
+        .find(|(page, _)| page.serial == serial && page.granule != u64::MAX)
+        .map(|(page, _)| page.granule)
+}
+
+impl MediaProbe for OggProbe {
+    fn method(&self) -> &'static str {
+        "ogg-probe-v1"
+    }
+
+    fn extensions(&self) -> &'static [&'static str] {
+        &["ogg", "oga", "opus"]
+    }
+
+    fn probe(
+        &self,
+        source: &mut dyn MediaSource,
+        size: u64,
+    ) -> Result<MediaMetadata, MediaProbeError> {
+        let head = read_at(source, 0, HEADER_BYTES)?;
+        let Some((first_page, _)) = Page::parse(&head) else {
+            return Err(MediaProbeError::invalid(FORMAT, "missing OggS page"));
+        };
+        let packets = first_packets(&head, 2);
+        let identification = packets.first().map(Vec::as_slice).unwrap_or_default();
+        let comments = packets.get(1).map(Vec::as_slice).unwrap_or_default();
+
+        let mut metadata = MediaMetadata::new(MediaKind::Audio, FORMAT);
+        let mut pre_skip = 0u64;
+        let granule_rate = if identification.starts_with(b"\x01vorbis") {
+            let mut reader = ByteReader::new(identification.get(11..).unwrap_or_default());
+            metadata.audio_codec = Some("Vorbis".to_string());
+            metadata.channels = reader.u8().map(u16::from);
+            metadata.sample_rate = reader.le_u32();
+            reader.skip(4);
+            metadata.bitrate_kbps = reader
+                .le_u32()
+                // Signed in the header; zero or negative means not declared
+                .filter(|bps| (1..=i32::MAX as u32).contains(bps))
+                .map(|bps| bps / 1000);
+            if let Some(comments) = comments.strip_prefix(b"\x03vorbis") {
+                read_vorbis_comments(comments, &mut metadata.tags);
+            }
+            metadata.sample_rate.unwrap_or(0)
+        } else if identification.starts_with(b"OpusHead") {
+            let mut reader = ByteReader::new(identification.get(9..).unwrap_or_default());
+            metadata.audio_codec = Some("Opus".to_string());
+            metadata.channels = reader.u8().map(u16::from);
+            pre_skip = reader.le_u16().map_or(0, u64::from);
+            // The rate of the original input; Opus always decodes at 48 kHz
+            metadata.sample_rate = reader.le_u32().filter(|rate| *rate > 0);
+            if let Some(comments) = comments.strip_prefix(b"OpusTags") {
+                read_vorbis_comments(comments, &mut metadata.tags);
+            }
+            48_000
+        } else if identification.starts_with(b"\x7FFLAC") {
+            // Ogg FLAC: the mapping header is followed by fLaC and STREAMINFO
+            metadata.audio_codec = Some("FLAC".to_string());
+            if let Some(streaminfo) = identification.get(17..) {
+                read_streaminfo(streaminfo, &mut metadata);
+            }
+            if let Some(comments) = comments.get(4..) {
+                read_vorbis_comments(comments, &mut metadata.tags);
+            }
+            metadata.sample_rate.unwrap_or(0)
+        } else {
+            return Err(MediaProbeError::invalid(
+                FORMAT,
+                "unsupported codec in first stream",
+            ));
+        };
+
+        let tail_start = size.saturating_sub(TAIL_BYTES);
+        source.seek(SeekFrom::Start(tail_start))?;
+        let tail = read_up_to(source, TAIL_BYTES)?;
+        if let Some(granule) = last_granule(&tail, first_page.serial) {
+            metadata.set_duration(granule.saturating_sub(pre_skip), u64::from(granule_rate));
+        }
+        metadata.fill_bitrate(size);
+        Ok(metadata)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::media::tags::vorbis_comment_block;
+    use std::io::Cursor;
+
+    fn page(serial: u32, granule: u64, packets: &[&[u8]]) -> Vec<u8> {
+        let mut lacing = Vec::new();
+        for packet in packets {
+            lacing.extend(std::iter::repeat(255u8).take(packet.len() / 255));
+            lacing.push((packet.len() % 255) as u8);
+        }
+        let mut page = b"OggS\x00\x00".to_vec();
+        page.extend_from_slice(&granule.to_le_bytes());
+        page.extend_from_slice(&serial.to_le_bytes());
+        page.extend_from_slice(&[0u8; 8]);
+        page.push(lacing.len() as u8);
+        page.extend(lacing);
+        for packet in packets {
+            page.extend_from_slice(packet);
+        }
+        page
+    }
+
+    #[test]
+    fn test_reads_opus_headers_and_duration() {
+        let mut head = b"OpusHead\x01\x02".to_vec();
+        head.extend_from_slice(&312u16.to_le_bytes());
+        head.extend_from_slice(&16_000u32.to_le_bytes());
+        head.extend_from_slice(&[0, 0, 0]);
+
+        let mut tags = b"OpusTags".to_vec();
+        tags.extend(vorbis_comment_block(&["TITLE=Meeting", "GENRE=Speech"]));
+
+        let mut file = page(7, 0, &[&head]);
+        file.extend(page(7, 0, &[&tags]));
+        file.extend(page(7, 48_000 * 30, &[&[0u8; 600]]));
+        // 12 minutes at 48 kHz, after the pre-skip
+        file.extend(page(7, 48_000 * 720 + 312, &[&[0u8; 300]]));
+
+        let metadata = OggProbe
+            .probe(&mut Cursor::new(file.clone()), file.len() as u64)
+            .unwrap();
+        assert_eq!(metadata.audio_codec.as_deref(), Some("Opus"));
+        assert_eq!(metadata.channels, Some(2));
+        assert_eq!(metadata.sample_rate, Some(16_000));
+        assert_eq!(metadata.duration_ms, Some(720_000));
+        assert_eq!(metadata.tags.title(), Some("Meeting"));
+        assert_eq!(metadata.tags.genre(), Some("Speech"));
+    }
+
+    #[test]
+    fn test_reads_vorbis_identification() {
+        let mut identification = b"\x01vorbis".to_vec();
+        identification.extend_from_slice(&0u32.to_le_bytes());
+        identification.push(1);
+        identification.extend_from_slice(&44_100u32.to_le_bytes());
+        identification.extend_from_slice(&0u32.to_le_bytes());
+        identification.extend_from_slice(&96_000u32.to_le_bytes());
+        identification.extend_from_slice(&[0u8; 6]);
+
+        let mut file = page(1, 0, &[&identification]);
+        file.extend(page(1, 44_100 * 5, &[&[0u8; 10]]));
+
+        let metadata = OggProbe
+            .probe(&mut Cursor::new(file.clone()), file.len() as u64)
+            .unwrap();
+        assert_eq!(metadata.audio_codec.as_deref(), Some("Vorbis"));
+        assert_eq!(metadata.channels, Some(1));
+        assert_eq!(metadata.bitrate_kbps, Some(96));
+        assert_eq!(metadata.duration_ms, Some(5_000));
+    }
+}
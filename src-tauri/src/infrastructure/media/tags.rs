@@ -0,0 +1,179 @@
+use super::binary::ByteReader;
+use crate::domain::media::MediaTags;
+
+/// Genre names of ID3v1 and Winamp, by index
+const ID3_GENRES: [&str; 80] = [
+    "Blues",
+    "Classic Rock",
+    "Country",
+    "Dance",
+    "Disco",
+    "Funk",
+    "Grunge",
+    "Hip-Hop",
+    "Jazz",
+    "Metal",
+    "New Age",
+    "Oldies",
+    "Other",
+    "Pop",
+    "R&B",
+    "Rap",
+    "Reggae",
+    "Rock",
+    "Techno",
+    "Industrial",
+    "Alternative",
+    "Ska",
+    "Death Metal",
+    "Pranks",
+    "Soundtrack",
+    "Euro-Techno",
+    "Ambient",
+    "Trip-Hop",
+    "Vocal",
+    "Jazz+Funk",
+    "Fusion",
+    "Trance",
+    "Classical",
+    "Instrumental",
+    "Acid",
+    "House",
+    "Game",
+    "Sound Clip",
+    "Gospel",
+    "Noise",
+    "AlternRock",
+    "Bass",
+    "Soul",
+    "Punk",
+    "Space",
+    "Meditative",
+    "Instrumental Pop",
+    "Instrumental Rock",
+    "Ethnic",
+    "Gothic",
+    "Darkwave",
+    "Techno-Industrial",
+    "Electronic",
+    "Pop-Folk",
+    "Eurodance",
+    "Dream",
+    "Southern Rock",
+    "Comedy",
+    "Cult",
+    "Gangsta",
+    "Top 40",
+    "Christian Rap",
+    "Pop/Funk",
+    "Jungle",
+    "Native American",
+    "Cabaret",
+    "New Wave",
+    "Psychadelic",
+    "Rave",
+    "Showtunes",
+    "Trailer",
+    "Lo-Fi",
+    "Tribal",
+    "Acid Punk",
+    "Acid Jazz",
+    "Polka",
+    "Retro",
+    "Musical",
+    "Rock & Roll",
+    "Hard Rock",
+];
+
+/// Name of an ID3v1 genre index
+pub(super) fn id3_genre(index: usize) -> Option<&'static str> {
+    ID3_GENRES.get(index).copied()
+}
+
+/// Resolve ID3v2 genre references such as "(17)" or "17" to names
+pub(super) fn id3_genre_text(value: &str) -> String {
+    let value = value.trim();
+    let reference = value
+        .strip_prefix('(')
+        .and_then(|rest| rest.split_once(')'))
+        .map(|(index, rest)| (index, rest.trim()));
+    match reference {
+        Some((_, rest)) if !rest.is_empty() => rest.to_string(),
+        Some((index, _)) => index
+            .parse()
+            .ok()
+            .and_then(id3_genre)
+            .unwrap_or(value)
+            .to_string(),
+        None => value
+            .parse()
+            .ok()
+            .and_then(id3_genre)
+            .unwrap_or(value)
+            .to_string(),
+    }
+}
+
+/// Text in ISO 8859-1, which maps byte for byte onto the first 256 code points
+pub(super) fn latin1(bytes: &[u8]) -> String {
+    bytes.iter().map(|&b| char::from(b)).collect()
+}
+
+/// Format-independent name of a Vorbis comment or Matroska tag field
+pub(super) fn common_tag_name(field: &str) -> Option<&'static str> {
+    Some(match field.to_uppercase().as_str() {
+        "TITLE" => MediaTags::TITLE,
+        "ARTIST" | "PERFORMER" => MediaTags::ARTIST,
+        "ALBUM" => MediaTags::ALBUM,
+        "GENRE" => MediaTags::GENRE,
+        "DATE" | "YEAR" | "DATE_RELEASED" | "DATE_RECORDED" => MediaTags::YEAR,
+        "TRACKNUMBER" | "PART_NUMBER" => MediaTags::TRACK,
+        "COMMENT" | "DESCRIPTION" => MediaTags::COMMENT,
+        "ALBUMARTIST" | "ALBUM ARTIST" => "album_artist",
+        "COMPOSER" => "composer",
+        "ENCODER" | "ENCODED_BY" => "encoder",
+        _ => return None,
+    })
+}
+
+/// Read a Vorbis comment block as used by FLAC, Ogg Vorbis and Opus
+///
+/// `data` starts at the vendor string length. Fields other than the
+/// common ones are ignored.
+pub(super) fn read_vorbis_comments(data: &[u8], tags: &mut MediaTags) {
+    let mut reader = ByteReader::new(data);
+    let Some(vendor_length) = reader.le_u32() else {
+        return;
+    };
+    if reader.skip(vendor_length as usize).is_none() {
+        return;
+    }
+    let count = reader.le_u32().unwrap_or(0);
+    for _ in 0..count {
+        let Some(comment) = reader
+            .le_u32()
+            .and_then(|length| reader.bytes(length as usize))
+        else {
+            return;
+        };
+        let comment = String::from_utf8_lossy(comment);
+        if let Some((field, value)) = comment.split_once('=') {
+            if let Some(name) = common_tag_name(field) {
+                tags.set(name, value);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+pub(super) fn vorbis_comment_block(fields: &[&str]) -> Vec<u8> {
+    let mut block = Vec::new();
+    block.extend_from_slice(&4u32.to_le_bytes());
+    block.extend_from_slice(b"test");
+    block.extend_from_slice(&(fields.len() as u32).to_le_bytes());
+    for field in fields {
+        block.extend_from_slice(&(field.len() as u32).to_le_bytes());
+        block.extend_from_slice(field.as_bytes());
+    }
+    block
+}
@@ -0,0 +1,196 @@
+use std::io::SeekFrom;
+
+use super::binary::{read_at, read_up_to, ByteReader};
+use super::tags::latin1;
+use crate::domain::media::{
+    MediaKind, MediaMetadata, MediaProbe, MediaProbeError, MediaSource, MediaTags,
+};
+
+/// Probe for RIFF wave files (.wav)
+///
+/// Reads the `fmt ` chunk for the sample format, the size of the `data`
+/// chunk for the duration and a `LIST/INFO` chunk for tags. The sample
+/// data itself is skipped.
+pub struct WavProbe;
+
+const FORMAT: &str = "WAV";
+
+/// Larger chunks other than `data` are skipped unread
+const MAX_CHUNK_BYTES: u64 = 1024 * 1024;
+
+fn codec_name(format_tag: u16) -> String {
+    match format_tag {
+        0x0001 => "PCM".to_string(),
+        0x0003 => "IEEE float".to_string(),
+        0x0006 => "A-law".to_string(),
+        0x0007 => "mu-law".to_string(),
+        0x0011 => "IMA ADPCM".to_string(),
+        0x0055 => "MP3".to_string(),
+        other => format!("0x{:04X}", other),
+    }
+}
+
+fn read_info(list: &[u8], tags: &mut MediaTags) {
+    let mut reader = ByteReader::new(list);
+    while let (Some(id), Some(size)) = (reader.bytes(4), reader.le_u32()) {
+        let Some(value) = reader.bytes(size as usize) else {
+            return;
+        };
+        if size % 2 == 1 {
+            reader.skip(1);
+        }
+        let name = match id {
+            b"INAM" => MediaTags::TITLE,
+            b"IART" => MediaTags::ARTIST,
+            b"IPRD" => MediaTags::ALBUM,
+            b"IGNR" => MediaTags::GENRE,
+            b"ICRD" => MediaTags::YEAR,
+            b"ICMT" => MediaTags::COMMENT,
+            b"ITRK" | b"IPRT" => MediaTags::TRACK,
+            b"ISFT" => "encoder",
+            _ => continue,
+        };
+        // INFO strings are NUL-terminated and usually Latin-1
+        let text = match std::str::from_utf8(value) {
+            Ok(text) => text.to_string(),
+            Err(_) => latin1(value),
+        };
+        tags.set(name, &text);
+    }
+}
+
+impl MediaProbe for WavProbe {
+    fn method(&self) -> &'static str {
+        "wav-probe-v1"
+    }
+
+    fn extensions(&self) -> &'static [&'static str] {
+        &["wav", "wave"]
+    }
+
+    fn probe(
+        &self,
+        source: &mut dyn MediaSource,
+        size: u64,
+    ) -> Result<MediaMetadata, MediaProbeError> {
+        let header = read_at(source, 0, 12)?;
+        if header.len() < 12 || &header[..4] != b"RIFF" || &header[8..12] != b"WAVE" {
+            return Err(MediaProbeError::invalid(FORMAT, "missing RIFF/WAVE header"));
+        }
+
+        let mut metadata = MediaMetadata::new(MediaKind::Audio, FORMAT);
+        let mut byte_rate = 0u32;
+        let mut data_size = None;
+        let mut position = 12u64;
+
+        while position + 8 <= size {
+            let chunk_header = read_at(source, position, 8)?;
+            let mut reader = ByteReader::new(&chunk_header);
+            let (Some(id), Some(chunk_size)) = (reader.bytes(4), reader.le_u32()) else {
+                break;
+            };
+            let chunk_size = u64::from(chunk_size);
+            let body_start = position + 8;
+
+            match id {
+                b"fmt " => {
+                    let body = read_up_to(source, chunk_size.min(64))?;
+                    let mut fmt = ByteReader::new(&body);
+                    let mut format_tag = fmt.le_u16().unwrap_or(0);
+                    metadata.channels = fmt.le_u16();
+                    metadata.sample_rate = fmt.le_u32();
+                    byte_rate = fmt.le_u32().unwrap_or(0);
+                    if format_tag == 0xFFFE {
+                        // WAVE_FORMAT_EXTENSIBLE: the sub-format GUID starts with the tag
+                        format_tag = body
+                            .get(24..26)
+                            .map_or(format_tag, |b| u16::from_le_bytes([b[0], b[1]]));
+                    }
+                    metadata.audio_codec = Some(codec_name(format_tag));
+                }
+                b"data" => {
+                    // Streams written without knowing their length use 0 or the maximum
+                    let available = size.saturating_sub(body_start);
+                    data_size = Some(if chunk_size == 0 || chunk_size > available {
+                        available
+                    } else {
+                        chunk_size
+                    });
+                }
+                b"LIST" if chunk_size <= MAX_CHUNK_BYTES => {
+                    let body = read_up_to(source, chunk_size)?;
+                    if let Some(info) = body.strip_prefix(b"INFO") {
+                        read_info(info, &mut metadata.tags);
+                    }
+                }
+                _ => {}
+            }
+
+            position = body_start + chunk_size + chunk_size % 2;
+            source.seek(SeekFrom::Start(position))?;
+        }
+
+        if metadata.audio_codec.is_none() {
+            return Err(MediaProbeError::invalid(FORMAT, "missing fmt chunk"));
+        }
+        if byte_rate > 0 {
+            metadata.bitrate_kbps = Some(byte_rate * 8 / 1000);
+            if let Some(data_size) = data_size {
+                metadata.set_duration(data_size, u64::from(byte_rate));
+            }
+        }
+        Ok(metadata)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    fn chunk(id: &[u8], body: &[u8]) -> Vec<u8> {
+        let mut chunk = id.to_vec();
+        chunk.extend_from_slice(&(body.len() as u32).to_le_bytes());
+        chunk.extend_from_slice(body);
+        if body.len() % 2 == 1 {
+            chunk.push(0);
+        }
+        chunk
+    }
+
+    #[test]
+    fn test_reads_format_duration_and_info_tags() {
+        let mut fmt = Vec::new();
+        fmt.extend_from_slice(&1u16.to_le_bytes());
+        fmt.extend_from_slice(&1u16.to_le_bytes());
+        fmt.extend_from_slice(&8_000u32.to_le_bytes());
+        fmt.extend_from_slice(&16_000u32.to_le_bytes());
+        fmt.extend_from_slice(&2u16.to_le_bytes());
+        fmt.extend_from_slice(&16u16.to_le_bytes());
+
+        let mut info = b"INFO".to_vec();
+        info.extend(chunk(b"INAM", b"Voicemail\0"));
+        info.extend(chunk(b"ICRD", b"2022-11-03\0"));
+
+        let mut body = b"WAVE".to_vec();
+        body.extend(chunk(b"fmt ", &fmt));
+        body.extend(chunk(b"LIST", &info));
+        body.extend(chunk(b"data", &vec![0u8; 48_000]));
+        let file = chunk(b"RIFF", &body);
+
+        let metadata = WavProbe
+            .probe(&mut Cursor::new(file.clone()), file.len() as u64)
+            .unwrap();
+        assert_eq!(metadata.audio_codec.as_deref(), Some("PCM"));
+        assert_eq!(metadata.sample_rate, Some(8_000));
+        assert_eq!(metadata.channels, Some(1));
+        assert_eq!(metadata.bitrate_kbps, Some(128));
+        assert_eq!(metadata.duration_ms, Some(3_000));
+        assert_eq!(metadata.tags.title(), Some("Voicemail"));
+        assert_eq!(metadata.tags.year(), Some("2022"));
+
+        assert!(WavProbe
+            .probe(&mut Cursor::new(b"RIFX".to_vec()), 4)
+            .is_err());
+    }
+}
@@ -1,12 +1,33 @@
+pub mod archive;
+pub mod cost_table;
 pub mod database;
 pub mod dtos;
+pub mod email;
 pub mod errors;
+pub mod export;
+pub mod extraction;
+pub mod media;
+pub mod preview;
 pub mod repositories;
 
+pub use archive::ArchiveReader;
+pub use cost_table::CostTableCodecRegistry;
 pub use database::{DatabaseConnection, DatabaseHealth};
 pub use dtos::{
     CreateProjectRequest, DeleteProjectRequest, ProjectDto, ProjectListDto, RepositoryStatsDto,
     UpdateProjectRequest,
 };
+pub use email::EmailParser;
 pub use errors::{AppError, AppResult, ErrorResponse};
-pub use repositories::SqliteProjectRepository;
+pub use export::ExporterRegistry;
+pub use extraction::ExtractorRegistry;
+pub use media::MediaProbeRegistry;
+pub use preview::{PreviewCache, PreviewGeneratorRegistry, DEFAULT_PREVIEW_CACHE_BYTES};
+pub use repositories::{
+    FileDetStore, FilePseudonymMapRepository, SqliteAnnotationRepository, SqliteCategoryRepository,
+    SqliteCitationRepository, SqliteCodingRepository, SqliteDerivationRepository,
+    SqliteDocumentRepository, SqliteEmailRepository, SqliteFileCategoryConfigRepository,
+    SqliteFileHashRepository, SqliteJobRepository, SqliteManifestSnapshotRepository,
+    SqliteMediaMetadataRepository, SqliteProjectRepository, SqliteReportRepository,
+    SqliteReviewRepository, SqliteSavedSearchRepository, SqliteSearchIndexRepository,
+};
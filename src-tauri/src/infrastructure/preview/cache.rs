@@ -0,0 +1,306 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::domain::preview::{Preview, PreviewFormat};
+
+/// Default limit for the total size of all cached previews
+pub const DEFAULT_PREVIEW_CACHE_BYTES: u64 = 256 * 1024 * 1024;
+
+/// Prefix of files that are still being written
+const PARTIAL_PREFIX: &str = ".partial-";
+
+/// One cached preview file
+#[derive(Debug, Clone)]
+struct CacheEntry {
+    format: PreviewFormat,
+    size: u64,
+    last_used: u128,
+}
+
+/// Cached previews in least-recently-used order
+#[derive(Debug, Default)]
+struct CacheIndex {
+    entries: HashMap<String, CacheEntry>,
+    by_use: BTreeMap<u128, String>,
+    total_size: u64,
+    clock: u128,
+}
+
+impl CacheIndex {
+    /// A use time later than every earlier one
+    fn tick(&mut self) -> u128 {
+        self.clock = now_nanos().max(self.clock + 1);
+        self.clock
+    }
+
+    fn insert(&mut self, key: String, entry: CacheEntry) {
+        self.remove(&key);
+        self.clock = self.clock.max(entry.last_used);
+        self.total_size += entry.size;
+        self.by_use.insert(entry.last_used, key.clone());
+        self.entries.insert(key, entry);
+    }
+
+    fn remove(&mut self, key: &str) -> Option<CacheEntry> {
+        let entry = self.entries.remove(key)?;
+        self.by_use.remove(&entry.last_used);
+        self.total_size -= entry.size;
+        Some(entry)
+    }
+
+    fn touch(&mut self, key: &str) -> Option<PreviewFormat> {
+        let last_used = self.tick();
+        let entry = self.entries.get_mut(key)?;
+        self.by_use.remove(&entry.last_used);
+        entry.last_used = last_used;
+        self.by_use.insert(last_used, key.to_string());
+        Some(entry.format)
+    }
+
+    /// The least recently used key other than `keep`
+    fn oldest_except(&self, keep: &str) -> Option<String> {
+        self.by_use
+            .values()
+            .find(|key| key.as_str() != keep)
+            .cloned()
+    }
+}
+
+/// On-disk cache of generated previews with a total size limit
+///
+/// Each preview is one file named after its key, which callers derive from
+/// the content hash of the source file and the generator method. When the
+/// cache grows past its limit the least recently used previews are deleted.
+/// Use times are kept as file modification times, so the order survives a
+/// restart; the folder is only scanned on first use.
+pub struct PreviewCache {
+    root: PathBuf,
+    max_bytes: u64,
+    index: Mutex<Option<CacheIndex>>,
+}
+
+impl PreviewCache {
+    /// Create a cache in `root` holding at most `max_bytes` of previews
+    pub fn new(root: PathBuf, max_bytes: u64) -> Self {
+        PreviewCache {
+            root,
+            max_bytes,
+            index: Mutex::new(None),
+        }
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// The cached preview file for a key, marking it as used
+    pub fn get(&self, key: &str) -> Option<(PathBuf, PreviewFormat)> {
+        let mut guard = self.index.lock().ok()?;
+        let index = guard.get_or_insert_with(|| self.scan());
+
+        let format = index.touch(key)?;
+        let path = self.path_for(key, format);
+        if !path.is_file() {
+            // Deleted from outside the application
+            index.remove(key);
+            return None;
+        }
+
+        // Best effort: the in-memory order is authoritative until restart
+        let _ = fs::File::options()
+            .write(true)
+            .open(&path)
+            .and_then(|file| file.set_modified(SystemTime::now()));
+        Some((path, format))
+    }
+
+    /// Store a preview under a key, evicting old previews over the limit
+    pub fn put(&self, key: &str, preview: &Preview) -> io::Result<PathBuf> {
+        if key.is_empty()
+            || !key
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("invalid preview cache key '{}'", key),
+            ));
+        }
+
+        fs::create_dir_all(&self.root)?;
+        let format = preview.format();
+        let path = self.path_for(key, format);
+        let partial = self
+            .root
+            .join(format!("{}{}", PARTIAL_PREFIX, uuid::Uuid::new_v4()));
+        fs::write(&partial, preview.bytes())?;
+        fs::rename(&partial, &path)?;
+
+        let mut guard = self
+            .index
+            .lock()
+            .map_err(|_| io::Error::other("preview cache index is poisoned"))?;
+        let index = guard.get_or_insert_with(|| self.scan());
+        let last_used = index.tick();
+        if let Some(previous) = index.remove(key) {
+            if previous.format != format {
+                let _ = fs::remove_file(self.path_for(key, previous.format));
+            }
+        }
+        index.insert(
+            key.to_string(),
+            CacheEntry {
+                format,
+                size: preview.bytes().len() as u64,
+                last_used,
+            },
+        );
+
+        while index.total_size > self.max_bytes {
+            let Some(oldest) = index.oldest_except(key) else {
+                break;
+            };
+            if let Some(entry) = index.remove(&oldest) {
+                tracing::debug!("Evicting preview {}", oldest);
+                let _ = fs::remove_file(self.path_for(&oldest, entry.format));
+            }
+        }
+
+        Ok(path)
+    }
+
+    /// Total size of all cached previews in bytes
+    pub fn total_size(&self) -> u64 {
+        self.index
+            .lock()
+            .ok()
+            .map(|mut guard| guard.get_or_insert_with(|| self.scan()).total_size)
+            .unwrap_or(0)
+    }
+
+    fn path_for(&self, key: &str, format: PreviewFormat) -> PathBuf {
+        self.root.join(format!("{}.{}", key, format.extension()))
+    }
+
+    /// Build the index from the files in the cache folder
+    fn scan(&self) -> CacheIndex {
+        let mut index = CacheIndex::default();
+        let Ok(entries) = fs::read_dir(&self.root) else {
+            return index;
+        };
+
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let name = entry.file_name().to_string_lossy().to_string();
+            if name.starts_with(PARTIAL_PREFIX) {
+                // Left over from an interrupted write
+                let _ = fs::remove_file(&path);
+                continue;
+            }
+
+            let (Some(key), Some(format)) = (
+                path.file_stem()
+                    .map(|stem| stem.to_string_lossy().to_string()),
+                path.extension().and_then(|extension| {
+                    PreviewFormat::from_extension(&extension.to_string_lossy())
+                }),
+            ) else {
+                continue;
+            };
+            let Ok(metadata) = entry.metadata() else {
+                continue;
+            };
+
+            let mut last_used = metadata
+                .modified()
+                .ok()
+                .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+                .map(|duration| duration.as_nanos())
+                .unwrap_or(0);
+            // Use times double as order keys and must be unique
+            while index.by_use.contains_key(&last_used) {
+                last_used += 1;
+            }
+            index.insert(
+                key,
+                CacheEntry {
+                    format,
+                    size: metadata.len(),
+                    last_used,
+                },
+            );
+        }
+
+        index
+    }
+}
+
+fn now_nanos() -> u128 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_nanos())
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn image(size: usize) -> Preview {
+        Preview::image(PreviewFormat::Png, vec![1; size])
+    }
+
+    #[test]
+    fn test_put_and_get() {
+        let folder = TempDir::new().unwrap();
+        let cache = PreviewCache::new(folder.path().join("previews"), 1024);
+
+        assert!(cache.get("abc-text-v1").is_none());
+        let stored = cache
+            .put("abc-text-v1", &Preview::snippet("Minutes").unwrap())
+            .unwrap();
+
+        let (path, format) = cache.get("abc-text-v1").unwrap();
+        assert_eq!(path, stored);
+        assert_eq!(format, PreviewFormat::Text);
+        assert_eq!(fs::read_to_string(path).unwrap(), "Minutes");
+        assert!(cache.put("../escape", &image(1)).is_err());
+    }
+
+    #[test]
+    fn test_least_recently_used_previews_are_evicted() {
+        let folder = TempDir::new().unwrap();
+        let cache = PreviewCache::new(folder.path().to_path_buf(), 250);
+
+        cache.put("first", &image(100)).unwrap();
+        cache.put("second", &image(100)).unwrap();
+        // Using the first preview makes the second one the oldest
+        assert!(cache.get("first").is_some());
+        cache.put("third", &image(100)).unwrap();
+
+        assert!(cache.get("first").is_some());
+        assert!(cache.get("second").is_none());
+        assert!(cache.get("third").is_some());
+        assert_eq!(cache.total_size(), 200);
+        assert!(!folder.path().join("second.png").exists());
+    }
+
+    #[test]
+    fn test_index_is_rebuilt_from_disk() {
+        let folder = TempDir::new().unwrap();
+        PreviewCache::new(folder.path().to_path_buf(), 1024)
+            .put("kept", &image(10))
+            .unwrap();
+        fs::write(folder.path().join(".partial-stale"), b"x").unwrap();
+
+        let cache = PreviewCache::new(folder.path().to_path_buf(), 1024);
+        assert_eq!(cache.total_size(), 10);
+        assert!(cache.get("kept").is_some());
+        assert!(!folder.path().join(".partial-stale").exists());
+    }
+}
@@ -0,0 +1,242 @@
+use std::io::Read;
+
+use crate::domain::preview::{Preview, PreviewError, PreviewFormat, PreviewGenerator};
+
+use super::thumbnail::Pixels;
+
+/// Bytes read to find the format and an embedded EXIF thumbnail
+const HEADER_BYTES: u64 = 128 * 1024;
+
+/// Largest PNG that is decoded to make a thumbnail
+const MAX_DECODED_BYTES: u64 = 64 * 1024 * 1024;
+
+/// Largest image shown as it is when it cannot be scaled down
+const MAX_PASSTHROUGH_BYTES: u64 = 2 * 1024 * 1024;
+
+/// Thumbnails for image files
+///
+/// PNG images are decoded and scaled down. JPEG photos use the thumbnail
+/// the camera embedded in their EXIF data. Other images, and JPEGs without
+/// an embedded thumbnail, are shown as they are when small enough for the
+/// viewer to scale them itself.
+pub struct ImagePreviewGenerator;
+
+impl PreviewGenerator for ImagePreviewGenerator {
+    fn method(&self) -> &'static str {
+        "image-thumbnail-v1"
+    }
+
+    fn extensions(&self) -> &'static [&'static str] {
+        &["png", "jpg", "jpeg", "gif", "webp", "bmp"]
+    }
+
+    fn generate(&self, source: &mut dyn Read, size: u64) -> Result<Preview, PreviewError> {
+        let mut bytes = Vec::new();
+        source.take(HEADER_BYTES).read_to_end(&mut bytes)?;
+
+        let format = sniff_format(&bytes)
+            .ok_or_else(|| PreviewError::invalid("image", "the image format is not recognised"))?;
+
+        if format == PreviewFormat::Png {
+            if size > MAX_DECODED_BYTES {
+                return Err(PreviewError::not_available("the image is too large"));
+            }
+            source.read_to_end(&mut bytes)?;
+            return Pixels::decode_png(&bytes)?.fit_thumbnail().into_png();
+        }
+
+        if format == PreviewFormat::Jpeg {
+            if let Some(thumbnail) = exif_thumbnail(&bytes) {
+                return Ok(Preview::image(PreviewFormat::Jpeg, thumbnail.to_vec()));
+            }
+        }
+
+        if size > MAX_PASSTHROUGH_BYTES {
+            return Err(PreviewError::not_available(
+                "the image is too large to show without a thumbnail",
+            ));
+        }
+        source.read_to_end(&mut bytes)?;
+        Ok(Preview::image(format, bytes))
+    }
+}
+
+/// Recognise an image format by its leading bytes
+fn sniff_format(bytes: &[u8]) -> Option<PreviewFormat> {
+    if bytes.starts_with(b"\x89PNG\r\n\x1a\n") {
+        Some(PreviewFormat::Png)
+    } else if bytes.starts_with(&[0xFF, 0xD8, 0xFF]) {
+        Some(PreviewFormat::Jpeg)
+    } else if bytes.starts_with(b"GIF8") {
+        Some(PreviewFormat::Gif)
+    } else if bytes.starts_with(b"RIFF") && bytes.get(8..12) == Some(b"WEBP") {
+        Some(PreviewFormat::Webp)
+    } else if bytes.starts_with(b"BM") {
+        Some(PreviewFormat::Bmp)
+    } else {
+        None
+    }
+}
+
+/// The JPEG thumbnail stored in the EXIF segment of a JPEG file
+fn exif_thumbnail(jpeg: &[u8]) -> Option<&[u8]> {
+    let mut position = 2;
+
+    while jpeg.get(position) == Some(&0xFF) {
+        let marker = *jpeg.get(position + 1)?;
+        // Image data starts at the first scan; metadata comes before it
+        if marker == 0xDA || marker == 0xD9 {
+            return None;
+        }
+        let length = usize::from(u16::from_be_bytes([
+            *jpeg.get(position + 2)?,
+            *jpeg.get(position + 3)?,
+        ]));
+        if length < 2 {
+            return None;
+        }
+
+        let segment = jpeg.get(position + 4..position + 2 + length)?;
+        if marker == 0xE1 {
+            if let Some(tiff) = segment.strip_prefix(b"Exif\0\0") {
+                return tiff_thumbnail(tiff);
+            }
+        }
+        position += 2 + length;
+    }
+
+    None
+}
+
+/// Follow the TIFF structure of EXIF data to the thumbnail in its second IFD
+fn tiff_thumbnail(tiff: &[u8]) -> Option<&[u8]> {
+    let big_endian = match tiff.get(..2)? {
+        b"MM" => true,
+        b"II" => false,
+        _ => return None,
+    };
+    let read_u16 = |offset: usize| {
+        let bytes = [*tiff.get(offset)?, *tiff.get(offset + 1)?];
+        Some(usize::from(if big_endian {
+            u16::from_be_bytes(bytes)
+        } else {
+            u16::from_le_bytes(bytes)
+        }))
+    };
+    let read_u32 = |offset: usize| {
+        let bytes: [u8; 4] = tiff.get(offset..offset + 4)?.try_into().ok()?;
+        usize::try_from(if big_endian {
+            u32::from_be_bytes(bytes)
+        } else {
+            u32::from_le_bytes(bytes)
+        })
+        .ok()
+    };
+
+    let first_ifd = read_u32(4)?;
+    let second_ifd = read_u32(first_ifd + 2 + read_u16(first_ifd)? * 12)?;
+    if second_ifd == 0 {
+        return None;
+    }
+
+    let mut offset = None;
+    let mut length = None;
+    for index in 0..read_u16(second_ifd)? {
+        let entry = second_ifd + 2 + index * 12;
+        match read_u16(entry)? {
+            0x0201 => offset = read_u32(entry + 8),
+            0x0202 => length = read_u32(entry + 8),
+            _ => {}
+        }
+    }
+
+    let (offset, length) = (offset?, length?);
+    tiff.get(offset..offset.checked_add(length)?)
+        .filter(|thumbnail| thumbnail.starts_with(&[0xFF, 0xD8]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A JPEG whose EXIF segment holds `thumbnail`, stored little-endian
+    fn jpeg_with_thumbnail(thumbnail: &[u8]) -> Vec<u8> {
+        let mut tiff = b"II*\0".to_vec();
+        tiff.extend(8u32.to_le_bytes());
+        // First IFD: no entries, second IFD follows at offset 14
+        tiff.extend(0u16.to_le_bytes());
+        tiff.extend(14u32.to_le_bytes());
+        // Second IFD: thumbnail offset and length, thumbnail at offset 44
+        tiff.extend(2u16.to_le_bytes());
+        for (tag, value) in [(0x0201u16, 44u32), (0x0202, thumbnail.len() as u32)] {
+            tiff.extend(tag.to_le_bytes());
+            tiff.extend(4u16.to_le_bytes());
+            tiff.extend(1u32.to_le_bytes());
+            tiff.extend(value.to_le_bytes());
+        }
+        tiff.extend(0u32.to_le_bytes());
+        tiff.extend(thumbnail);
+
+        let mut jpeg = vec![0xFF, 0xD8, 0xFF, 0xE1];
+        jpeg.extend(((tiff.len() + 8) as u16).to_be_bytes());
+        jpeg.extend(b"Exif\0\0");
+        jpeg.extend(tiff);
+        jpeg.extend([0xFF, 0xDA, 0x00, 0x02, 0x12, 0x34, 0xFF, 0xD9]);
+        jpeg
+    }
+
+    #[test]
+    fn test_jpeg_uses_exif_thumbnail() {
+        let thumbnail = [0xFF, 0xD8, 0xFF, 0xD9];
+        let jpeg = jpeg_with_thumbnail(&thumbnail);
+
+        let preview = ImagePreviewGenerator
+            .generate(&mut jpeg.as_slice(), jpeg.len() as u64)
+            .unwrap();
+
+        assert_eq!(preview.format(), PreviewFormat::Jpeg);
+        assert_eq!(preview.bytes(), thumbnail);
+    }
+
+    #[test]
+    fn test_small_images_pass_through_and_large_ones_are_refused() {
+        let gif = b"GIF89a\x01\x00\x01\x00".to_vec();
+        let preview = ImagePreviewGenerator
+            .generate(&mut gif.as_slice(), gif.len() as u64)
+            .unwrap();
+        assert_eq!(preview.format(), PreviewFormat::Gif);
+        assert_eq!(preview.bytes(), gif);
+
+        let jpeg = [0xFF, 0xD8, 0xFF, 0xDB, 0x00, 0x02];
+        assert!(matches!(
+            ImagePreviewGenerator.generate(&mut jpeg.as_slice(), 50 * 1024 * 1024),
+            Err(PreviewError::NotAvailable(_))
+        ));
+
+        assert!(matches!(
+            ImagePreviewGenerator.generate(&mut b"plain text".as_slice(), 10),
+            Err(PreviewError::InvalidFile { .. })
+        ));
+    }
+
+    #[test]
+    fn test_png_is_scaled_down() {
+        let png = Pixels {
+            width: 600,
+            height: 300,
+            channels: 3,
+            data: vec![90; 600 * 300 * 3],
+        }
+        .into_png()
+        .unwrap()
+        .into_bytes();
+
+        let preview = ImagePreviewGenerator
+            .generate(&mut png.as_slice(), png.len() as u64)
+            .unwrap();
+        let thumbnail = Pixels::decode_png(preview.bytes()).unwrap();
+
+        assert_eq!((thumbnail.width, thumbnail.height), (256, 128));
+        assert!(thumbnail.data.iter().all(|sample| *sample == 90));
+    }
+}
@@ -0,0 +1,57 @@
+pub mod cache;
+pub mod image_preview;
+pub mod pdf_preview;
+#[cfg(feature = "pdf-render")]
+pub mod pdfium_preview;
+pub mod text_preview;
+mod thumbnail;
+
+pub use cache::{PreviewCache, DEFAULT_PREVIEW_CACHE_BYTES};
+pub use image_preview::ImagePreviewGenerator;
+pub use pdf_preview::PdfPreviewGenerator;
+#[cfg(feature = "pdf-render")]
+pub use pdfium_preview::PdfiumPreviewGenerator;
+pub use text_preview::TextSnippetGenerator;
+
+use std::sync::Arc;
+
+use crate::domain::preview::PreviewGenerator;
+
+/// Looks up the preview generator for a file extension
+#[derive(Clone, Default)]
+pub struct PreviewGeneratorRegistry {
+    generators: Vec<Arc<dyn PreviewGenerator>>,
+}
+
+impl PreviewGeneratorRegistry {
+    /// Create a registry with all built-in generators
+    ///
+    /// With the `pdf-render` feature, PDF pages are rendered with Pdfium
+    /// when the library can be loaded.
+    pub fn with_defaults() -> Self {
+        let mut registry = Self::default();
+        registry.register(Arc::new(ImagePreviewGenerator));
+        registry.register(Arc::new(PdfPreviewGenerator));
+        registry.register(Arc::new(TextSnippetGenerator));
+
+        #[cfg(feature = "pdf-render")]
+        match PdfiumPreviewGenerator::from_system() {
+            Ok(generator) => registry.register(Arc::new(generator)),
+            Err(error) => tracing::warn!("PDF rendering is unavailable: {}", error),
+        }
+
+        registry
+    }
+
+    /// Add a generator; later registrations take precedence
+    pub fn register(&mut self, generator: Arc<dyn PreviewGenerator>) {
+        self.generators.insert(0, generator);
+    }
+
+    pub fn for_extension(&self, extension: &str) -> Option<Arc<dyn PreviewGenerator>> {
+        self.generators
+            .iter()
+            .find(|generator| generator.supports(extension))
+            .cloned()
+    }
+}
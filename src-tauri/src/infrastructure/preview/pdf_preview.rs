@@ -0,0 +1,143 @@
+use std::io::Read;
+
+use crate::domain::extraction::{ExtractionError, PageImage};
+use crate::domain::preview::{Preview, PreviewError, PreviewGenerator};
+use crate::infrastructure::extraction::ocr::PdfPageImages;
+
+use super::thumbnail::Pixels;
+
+/// Largest PDF that is parsed for its first page
+pub(crate) const MAX_PDF_BYTES: u64 = 256 * 1024 * 1024;
+
+/// Pages with less text than this are treated as scans
+const MIN_PAGE_TEXT_CHARS: usize = 20;
+
+/// Previews of the first page of PDF files without a PDF renderer
+///
+/// The first page is shown the way it is most recognisable: a scanned page
+/// as its page image, scaled down, and a page with a text layer as a
+/// snippet of that text. With the `pdf-render` feature and Pdfium
+/// installed, `PdfiumPreviewGenerator` renders the page instead; this
+/// generator remains the fallback where Pdfium is missing.
+pub struct PdfPreviewGenerator;
+
+impl PreviewGenerator for PdfPreviewGenerator {
+    fn method(&self) -> &'static str {
+        "pdf-first-page-v2"
+    }
+
+    fn extensions(&self) -> &'static [&'static str] {
+        &["pdf"]
+    }
+
+    fn generate(&self, source: &mut dyn Read, size: u64) -> Result<Preview, PreviewError> {
+        if size > MAX_PDF_BYTES {
+            return Err(PreviewError::not_available("the PDF is too large"));
+        }
+        let mut bytes = Vec::new();
+        source.read_to_end(&mut bytes)?;
+
+        let pages = PdfPageImages::load(&bytes).map_err(|error| match error {
+            ExtractionError::InvalidDocument { reason, .. } => PreviewError::invalid("PDF", reason),
+            other => PreviewError::not_available(other.to_string()),
+        })?;
+        let text = pages.page_text(1).unwrap_or_default();
+
+        if text.trim().chars().count() < MIN_PAGE_TEXT_CHARS {
+            match pages.page_image(1) {
+                Ok(Some(PageImage::Encoded(image))) if image.starts_with(&[0xFF, 0xD8]) => {
+                    // CMYK and damaged scans fall back to the text
+                    if let Ok(pixels) = Pixels::decode_jpeg(&image) {
+                        return pixels.fit_thumbnail().into_png();
+                    }
+                }
+                Ok(Some(PageImage::Pixels {
+                    width,
+                    height,
+                    channels,
+                    mut data,
+                })) => {
+                    data.truncate(width as usize * height as usize * usize::from(channels));
+                    let pixels = Pixels {
+                        width,
+                        height,
+                        channels,
+                        data,
+                    };
+                    return pixels.fit_thumbnail().into_png();
+                }
+                // JPEG 2000 and other encodings fall back to the text
+                _ => {}
+            }
+        }
+
+        Preview::snippet(&text).ok_or_else(|| {
+            PreviewError::not_available("the first page has no text or readable image")
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::preview::{PreviewFormat, THUMBNAIL_MAX_EDGE};
+    use crate::infrastructure::extraction::pdf_extractor::tests::{pdf, pdf_with_image};
+    use jpeg_encoder::{ColorType, Encoder};
+
+    fn preview(bytes: &[u8]) -> Result<Preview, PreviewError> {
+        PdfPreviewGenerator.generate(&mut &bytes[..], bytes.len() as u64)
+    }
+
+    #[test]
+    fn test_text_page_becomes_snippet() {
+        let preview = preview(&pdf(&["Settlement agreement between the parties", ""])).unwrap();
+
+        assert_eq!(preview.format(), PreviewFormat::Text);
+        assert!(preview
+            .text()
+            .unwrap()
+            .contains("Settlement agreement between the parties"));
+    }
+
+    #[test]
+    fn test_scanned_page_becomes_image() {
+        let preview = preview(&pdf(&[""])).unwrap();
+        assert_eq!(preview.format(), PreviewFormat::Png);
+
+        let image = Pixels::decode_png(preview.bytes()).unwrap();
+        assert_eq!((image.width, image.height, image.channels), (2, 2, 1));
+        assert_eq!(image.data, b"scan");
+    }
+
+    #[test]
+    fn test_scanned_jpeg_page_is_scaled_down() {
+        let (width, height) = (1200u16, 800u16);
+        let samples: Vec<u8> = (0..u32::from(width) * u32::from(height))
+            .map(|i| (i % 256) as u8)
+            .collect();
+        let mut jpeg = Vec::new();
+        Encoder::new(&mut jpeg, 80)
+            .encode(&samples, width, height, ColorType::Luma)
+            .unwrap();
+        let bytes = pdf_with_image(
+            &[""],
+            "/Width 1200 /Height 800 /ColorSpace /DeviceGray /BitsPerComponent 8 /Filter /DCTDecode",
+            &jpeg,
+        );
+
+        let preview = preview(&bytes).unwrap();
+        assert_eq!(preview.format(), PreviewFormat::Png);
+
+        let image = Pixels::decode_png(preview.bytes()).unwrap();
+        assert_eq!((image.width, image.height), (THUMBNAIL_MAX_EDGE, 170));
+        assert_eq!(image.channels, 1);
+    }
+
+    #[test]
+    fn test_invalid_pdf_is_rejected() {
+        assert!(matches!(
+            preview(b"not a pdf"),
+            Err(PreviewError::InvalidFile { .. })
+        ));
+    }
+}
@@ -0,0 +1,73 @@
+use std::io::Read;
+
+use pdfium_render::prelude::{PdfRenderConfig, Pdfium};
+
+use crate::domain::preview::{Preview, PreviewError, PreviewGenerator, THUMBNAIL_MAX_EDGE};
+
+use super::pdf_preview::MAX_PDF_BYTES;
+use super::thumbnail::Pixels;
+
+/// Previews of PDF files rendered with the Pdfium library
+///
+/// The first page is rendered to fit `THUMBNAIL_MAX_EDGE`, whether it is a
+/// scan or has a text layer. The library is looked up on the system as
+/// usual for the platform.
+pub struct PdfiumPreviewGenerator {
+    pdfium: Pdfium,
+}
+
+impl PdfiumPreviewGenerator {
+    /// Create a generator bound to the system's Pdfium library
+    pub fn from_system() -> Result<Self, String> {
+        let bindings = Pdfium::bind_to_system_library().map_err(|e| e.to_string())?;
+        Ok(PdfiumPreviewGenerator {
+            pdfium: Pdfium::new(bindings),
+        })
+    }
+}
+
+impl PreviewGenerator for PdfiumPreviewGenerator {
+    fn method(&self) -> &'static str {
+        "pdf-render-v1"
+    }
+
+    fn extensions(&self) -> &'static [&'static str] {
+        &["pdf"]
+    }
+
+    fn generate(&self, source: &mut dyn Read, size: u64) -> Result<Preview, PreviewError> {
+        if size > MAX_PDF_BYTES {
+            return Err(PreviewError::not_available("the PDF is too large"));
+        }
+        let mut bytes = Vec::new();
+        source.read_to_end(&mut bytes)?;
+
+        let document = self
+            .pdfium
+            .load_pdf_from_byte_slice(&bytes, None)
+            .map_err(|e| PreviewError::invalid("PDF", e))?;
+        let page = document
+            .pages()
+            .first()
+            .map_err(|_| PreviewError::not_available("the PDF has no pages"))?;
+
+        let edge = i32::try_from(THUMBNAIL_MAX_EDGE).unwrap_or(i32::MAX);
+        let bitmap = page
+            .render_with_config(
+                &PdfRenderConfig::new()
+                    .set_target_width(edge)
+                    .set_maximum_height(edge),
+            )
+            .map_err(|e| PreviewError::invalid("PDF", e))?;
+
+        let dimension = |value: i32| u32::try_from(value).unwrap_or(0);
+        Pixels {
+            width: dimension(bitmap.width()),
+            height: dimension(bitmap.height()),
+            channels: 4,
+            data: bitmap.as_rgba_bytes(),
+        }
+        .fit_thumbnail()
+        .into_png()
+    }
+}
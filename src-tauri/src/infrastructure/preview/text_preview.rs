@@ -0,0 +1,108 @@
+use std::io::Read;
+
+use crate::domain::preview::{Preview, PreviewError, PreviewGenerator};
+
+/// Bytes read from the start of a file for its snippet
+const SNIPPET_READ_BYTES: u64 = 16 * 1024;
+
+/// Snippets of the opening lines of plain-text files
+///
+/// Reads UTF-8 (with or without byte order mark) and UTF-16 with a byte
+/// order mark. Files that turn out to hold binary data get no snippet.
+pub struct TextSnippetGenerator;
+
+impl PreviewGenerator for TextSnippetGenerator {
+    fn method(&self) -> &'static str {
+        "text-snippet-v1"
+    }
+
+    fn extensions(&self) -> &'static [&'static str] {
+        &[
+            "txt", "text", "md", "markdown", "csv", "tsv", "json", "xml", "log", "yaml", "yml",
+            "ini", "html", "htm", "eml",
+        ]
+    }
+
+    fn generate(&self, source: &mut dyn Read, _size: u64) -> Result<Preview, PreviewError> {
+        let mut bytes = Vec::new();
+        source.take(SNIPPET_READ_BYTES).read_to_end(&mut bytes)?;
+
+        let text = decode(&bytes)
+            .ok_or_else(|| PreviewError::not_available("the file does not contain text"))?;
+
+        Preview::snippet(&text).ok_or_else(|| PreviewError::not_available("the file is empty"))
+    }
+}
+
+/// Decode the start of a text file; `None` for binary data
+fn decode(bytes: &[u8]) -> Option<String> {
+    let utf16 = |big_endian: bool| {
+        let units: Vec<u16> = bytes[2..]
+            .chunks_exact(2)
+            .map(|pair| {
+                if big_endian {
+                    u16::from_be_bytes([pair[0], pair[1]])
+                } else {
+                    u16::from_le_bytes([pair[0], pair[1]])
+                }
+            })
+            .collect();
+        String::from_utf16_lossy(&units)
+    };
+
+    let text = match bytes {
+        [0xFF, 0xFE, ..] => utf16(false),
+        [0xFE, 0xFF, ..] => utf16(true),
+        _ if bytes.contains(&0) => return None,
+        _ => match std::str::from_utf8(bytes) {
+            Ok(text) => text.to_string(),
+            // The read may end inside a character
+            Err(error) if error.error_len().is_none() => {
+                String::from_utf8_lossy(&bytes[..error.valid_up_to()]).into_owned()
+            }
+            Err(_) => String::from_utf8_lossy(bytes).into_owned(),
+        },
+    };
+
+    let replaced = text
+        .chars()
+        .filter(|c| *c == char::REPLACEMENT_CHARACTER)
+        .count();
+    (replaced * 20 <= text.chars().count()).then_some(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snippet(bytes: &[u8]) -> Result<Preview, PreviewError> {
+        TextSnippetGenerator.generate(&mut &bytes[..], bytes.len() as u64)
+    }
+
+    #[test]
+    fn test_reads_utf8_and_utf16() {
+        let preview = snippet("Déposition\nPage 1".as_bytes()).unwrap();
+        assert_eq!(preview.text(), Some("Déposition\nPage 1"));
+
+        let mut utf16 = vec![0xFF, 0xFE];
+        for unit in "Notes".encode_utf16() {
+            utf16.extend(unit.to_le_bytes());
+        }
+        assert_eq!(snippet(&utf16).unwrap().text(), Some("Notes"));
+    }
+
+    #[test]
+    fn test_cut_off_character_is_dropped() {
+        let bytes = "Café".as_bytes();
+        assert_eq!(decode(&bytes[..bytes.len() - 1]).as_deref(), Some("Caf"));
+    }
+
+    #[test]
+    fn test_binary_and_empty_files_have_no_snippet() {
+        assert!(matches!(
+            snippet(b"PK\x03\x04\x00\x00"),
+            Err(PreviewError::NotAvailable(_))
+        ));
+        assert!(matches!(snippet(b""), Err(PreviewError::NotAvailable(_))));
+    }
+}
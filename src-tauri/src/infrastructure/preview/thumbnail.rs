@@ -0,0 +1,235 @@
+use png::{BitDepth, ColorType, Decoder, Encoder, Transformations};
+
+use crate::domain::preview::{Preview, PreviewError, PreviewFormat, THUMBNAIL_MAX_EDGE};
+
+/// Largest image, in pixels, that is decoded to make a thumbnail
+const MAX_DECODED_PIXELS: u64 = 64 * 1024 * 1024;
+
+/// Uncompressed 8-bit samples, `channels` per pixel, rows top to bottom
+pub(crate) struct Pixels {
+    pub(crate) width: u32,
+    pub(crate) height: u32,
+    pub(crate) channels: u8,
+    pub(crate) data: Vec<u8>,
+}
+
+impl Pixels {
+    /// Decode a PNG image to 8-bit samples
+    pub(crate) fn decode_png(bytes: &[u8]) -> Result<Self, PreviewError> {
+        let mut decoder = Decoder::new(bytes);
+        decoder.set_transformations(Transformations::normalize_to_color8());
+        let mut reader = decoder
+            .read_info()
+            .map_err(|e| PreviewError::invalid("PNG", e))?;
+
+        let info = reader.info();
+        if u64::from(info.width) * u64::from(info.height) > MAX_DECODED_PIXELS {
+            return Err(PreviewError::not_available(format!(
+                "the image is too large ({} x {} pixels)",
+                info.width, info.height
+            )));
+        }
+
+        let mut data = vec![0; reader.output_buffer_size()];
+        let frame = reader
+            .next_frame(&mut data)
+            .map_err(|e| PreviewError::invalid("PNG", e))?;
+        data.truncate(frame.buffer_size());
+
+        let channels = match frame.color_type {
+            ColorType::Grayscale => 1,
+            ColorType::GrayscaleAlpha => 2,
+            ColorType::Rgb => 3,
+            ColorType::Rgba => 4,
+            ColorType::Indexed => {
+                return Err(PreviewError::invalid(
+                    "PNG",
+                    "indexed colours were not expanded",
+                ))
+            }
+        };
+
+        Ok(Pixels {
+            width: frame.width,
+            height: frame.height,
+            channels,
+            data,
+        })
+    }
+
+    /// Decode a JPEG image to 8-bit samples
+    ///
+    /// Grayscale and RGB images are supported, which covers scanned pages.
+    pub(crate) fn decode_jpeg(bytes: &[u8]) -> Result<Self, PreviewError> {
+        let mut decoder = jpeg_decoder::Decoder::new(bytes);
+        decoder
+            .read_info()
+            .map_err(|e| PreviewError::invalid("JPEG", e))?;
+        let info = decoder
+            .info()
+            .ok_or_else(|| PreviewError::invalid("JPEG", "the image header is missing"))?;
+
+        let (width, height) = (u32::from(info.width), u32::from(info.height));
+        if u64::from(width) * u64::from(height) > MAX_DECODED_PIXELS {
+            return Err(PreviewError::not_available(format!(
+                "the image is too large ({} x {} pixels)",
+                width, height
+            )));
+        }
+        let channels = match info.pixel_format {
+            jpeg_decoder::PixelFormat::L8 => 1,
+            jpeg_decoder::PixelFormat::RGB24 => 3,
+            other => {
+                return Err(PreviewError::not_available(format!(
+                    "{:?} JPEG images are not supported",
+                    other
+                )))
+            }
+        };
+
+        let data = decoder
+            .decode()
+            .map_err(|e| PreviewError::invalid("JPEG", e))?;
+        Ok(Pixels {
+            width,
+            height,
+            channels,
+            data,
+        })
+    }
+
+    /// Shrink the image so its longest edge is at most `THUMBNAIL_MAX_EDGE`
+    ///
+    /// Each output pixel is the average of the source pixels it covers,
+    /// which keeps scanned text legible. Smaller images are left unchanged.
+    pub(crate) fn fit_thumbnail(self) -> Self {
+        let longest = self.width.max(self.height);
+        if longest <= THUMBNAIL_MAX_EDGE || self.width == 0 || self.height == 0 {
+            return self;
+        }
+
+        let scale = |edge: u32| {
+            ((u64::from(edge) * u64::from(THUMBNAIL_MAX_EDGE) / u64::from(longest)) as u32).max(1)
+        };
+        let (width, height) = (scale(self.width), scale(self.height));
+        let channels = usize::from(self.channels);
+        let source_row = self.width as usize * channels;
+        let mut data = Vec::with_capacity(width as usize * height as usize * channels);
+
+        for y in 0..height {
+            let top = span_start(y, self.height, height);
+            let bottom = span_start(y + 1, self.height, height).max(top + 1);
+            for x in 0..width {
+                let left = span_start(x, self.width, width);
+                let right = span_start(x + 1, self.width, width).max(left + 1);
+                let count = u64::from((bottom - top) * (right - left));
+
+                for channel in 0..channels {
+                    let mut sum = 0u64;
+                    for row in top..bottom {
+                        let start = row as usize * source_row + channel;
+                        for column in left..right {
+                            sum += u64::from(self.data[start + column as usize * channels]);
+                        }
+                    }
+                    data.push((sum / count) as u8);
+                }
+            }
+        }
+
+        Pixels {
+            width,
+            height,
+            channels: self.channels,
+            data,
+        }
+    }
+
+    /// Encode the image as a PNG preview
+    pub(crate) fn into_png(self) -> Result<Preview, PreviewError> {
+        let color = match self.channels {
+            1 => ColorType::Grayscale,
+            2 => ColorType::GrayscaleAlpha,
+            3 => ColorType::Rgb,
+            4 => ColorType::Rgba,
+            other => {
+                return Err(PreviewError::not_available(format!(
+                    "images with {} channels are not supported",
+                    other
+                )))
+            }
+        };
+
+        let mut bytes = Vec::new();
+        let mut encoder = Encoder::new(&mut bytes, self.width, self.height);
+        encoder.set_color(color);
+        encoder.set_depth(BitDepth::Eight);
+        encoder
+            .write_header()
+            .and_then(|mut writer| writer.write_image_data(&self.data))
+            .map_err(|e| PreviewError::invalid("PNG", e))?;
+
+        Ok(Preview::image(PreviewFormat::Png, bytes))
+    }
+}
+
+/// First source row or column covered by output index `index`
+fn span_start(index: u32, source: u32, target: u32) -> u32 {
+    (u64::from(index) * u64::from(source) / u64::from(target)) as u32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_thumbnail_averages_and_round_trips() {
+        // Left half black, right half white
+        let width = 1024;
+        let height = 512;
+        let data: Vec<u8> = (0..width * height)
+            .map(|i| if i % width < width / 2 { 0 } else { 255 })
+            .collect();
+        let pixels = Pixels {
+            width,
+            height,
+            channels: 1,
+            data,
+        }
+        .fit_thumbnail();
+
+        assert_eq!((pixels.width, pixels.height), (256, 128));
+        assert_eq!(pixels.data[0], 0);
+        assert_eq!(pixels.data[255], 255);
+
+        let preview = pixels.into_png().unwrap();
+        assert_eq!(preview.format(), PreviewFormat::Png);
+        let decoded = Pixels::decode_png(preview.bytes()).unwrap();
+        assert_eq!(
+            (decoded.width, decoded.height, decoded.channels),
+            (256, 128, 1)
+        );
+    }
+
+    #[test]
+    fn test_small_images_are_kept() {
+        let pixels = Pixels {
+            width: 2,
+            height: 3,
+            channels: 3,
+            data: vec![7; 18],
+        }
+        .fit_thumbnail();
+
+        assert_eq!((pixels.width, pixels.height), (2, 3));
+        assert_eq!(pixels.data, vec![7; 18]);
+    }
+
+    #[test]
+    fn test_invalid_png_is_rejected() {
+        assert!(matches!(
+            Pixels::decode_png(b"not a png"),
+            Err(PreviewError::InvalidFile { .. })
+        ));
+    }
+}
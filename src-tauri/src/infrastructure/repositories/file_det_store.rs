@@ -0,0 +1,189 @@
+use async_trait::async_trait;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use crate::domain::det::{DetDocument, DetError, DetMigrator, DetSchema, DetStore};
+
+/// `.det` storage as pretty-printed JSON files
+///
+/// Writes go to a temporary file in the target folder, which is flushed to
+/// disk and then renamed over the target, so readers see either the old
+/// or the new document.
+pub struct FileDetStore {
+    schema: DetSchema,
+    migrator: DetMigrator,
+}
+
+impl Default for FileDetStore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FileDetStore {
+    /// Create a store using the editor schema and built-in migrations
+    pub fn new() -> Self {
+        FileDetStore {
+            schema: DetSchema::tiptap(),
+            migrator: DetMigrator::new(),
+        }
+    }
+
+    /// Validate against a different schema
+    pub fn with_schema(mut self, schema: DetSchema) -> Self {
+        self.schema = schema;
+        self
+    }
+
+    /// Use a different set of migrations
+    pub fn with_migrator(mut self, migrator: DetMigrator) -> Self {
+        self.migrator = migrator;
+        self
+    }
+
+    fn parse(&self, path: &Path, bytes: &[u8]) -> Result<DetDocument, DetError> {
+        let value: serde_json::Value =
+            serde_json::from_slice(bytes).map_err(|e| DetError::Parse(e.to_string()))?;
+
+        let (value, version) = self.migrator.upgrade(value)?;
+        let document = DetDocument::from_json(value)?;
+        document.validate(&self.schema)?;
+
+        if version != document.schema_version() {
+            tracing::debug!(
+                "Upgraded {} from schema version {} to {}",
+                path.display(),
+                version,
+                document.schema_version()
+            );
+        }
+        Ok(document)
+    }
+
+    fn write(path: &Path, json: &[u8]) -> Result<(), DetError> {
+        let io_error = |e: std::io::Error| DetError::Io(format!("{}: {}", path.display(), e));
+
+        let folder = path
+            .parent()
+            .ok_or_else(|| DetError::Io(format!("{}: no parent folder", path.display())))?;
+        fs::create_dir_all(folder).map_err(io_error)?;
+
+        let temp_path = temp_path_for(path);
+        let result = fs::File::create(&temp_path)
+            .and_then(|mut file| {
+                file.write_all(json)?;
+                file.sync_all()
+            })
+            .and_then(|()| fs::rename(&temp_path, path));
+
+        if let Err(error) = result {
+            let _ = fs::remove_file(&temp_path);
+            return Err(io_error(error));
+        }
+        Ok(())
+    }
+}
+
+/// Hidden sibling of `path`, unique per write
+fn temp_path_for(path: &Path) -> PathBuf {
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    path.with_file_name(format!(".{}.{}.tmp", name, uuid::Uuid::new_v4().simple()))
+}
+
+#[async_trait]
+impl DetStore for FileDetStore {
+    async fn load(&self, path: &Path) -> Result<DetDocument, DetError> {
+        let bytes = tokio::fs::read(path)
+            .await
+            .map_err(|e| DetError::Io(format!("{}: {}", path.display(), e)))?;
+        self.parse(path, &bytes)
+    }
+
+    async fn save(&self, path: &Path, document: &DetDocument) -> Result<(), DetError> {
+        document.validate(&self.schema)?;
+        let json = serde_json::to_vec_pretty(document)
+            .map_err(|e| DetError::InvalidEnvelope(e.to_string()))?;
+        let path = path.to_path_buf();
+
+        tokio::task::spawn_blocking(move || Self::write(&path, &json))
+            .await
+            .map_err(|e| DetError::Io(e.to_string()))?
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::det::{DetKind, PmNode, ProcessingMetadata, SourceReference};
+    use tempfile::TempDir;
+
+    fn create_test_document() -> DetDocument {
+        DetDocument::new(
+            DetKind::Summary,
+            PmNode::doc(vec![
+                PmNode::heading(1, PmNode::text_nodes("Summary", Vec::new())),
+                PmNode::paragraph(PmNode::text_nodes("Costs rose.", Vec::new())),
+            ]),
+            ProcessingMetadata::completed("manual"),
+        )
+        .with_title("Cost summary")
+        .with_source(SourceReference::new("/corpus/invoice.pdf"))
+    }
+
+    #[tokio::test]
+    async fn test_save_and_load_round_trip() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("family").join("summary-costs.det");
+        let store = FileDetStore::new();
+        let document = create_test_document();
+
+        store.save(&path, &document).await.unwrap();
+        let loaded = store.load(&path).await.unwrap();
+        assert_eq!(loaded, document);
+
+        // Only the document itself is left behind
+        let files: Vec<_> = fs::read_dir(path.parent().unwrap()).unwrap().collect();
+        assert_eq!(files.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_save_rejects_invalid_content_and_keeps_old_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("summary.det");
+        let store = FileDetStore::new();
+        let document = create_test_document();
+        store.save(&path, &document).await.unwrap();
+
+        let mut invalid = document.clone();
+        invalid.set_content(PmNode::doc(vec![PmNode::text("loose", Vec::new())]));
+        let result = store.save(&path, &invalid).await;
+        assert!(matches!(result, Err(DetError::SchemaViolations(_))));
+
+        assert_eq!(store.load(&path).await.unwrap(), document);
+    }
+
+    #[tokio::test]
+    async fn test_load_upgrades_bare_documents() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("extracted.det");
+        fs::write(
+            &path,
+            r#"{"type":"doc","content":[{"type":"paragraph"}],"metadata":{"extractionMethod":"docx-ooxml-v1","sourcePath":"/corpus/a.docx"}}"#,
+        )
+        .unwrap();
+
+        let document = FileDetStore::new().load(&path).await.unwrap();
+        assert_eq!(document.kind(), DetKind::Extracted);
+        assert_eq!(document.primary_source().unwrap().path, "/corpus/a.docx");
+
+        fs::write(&path, "not json").unwrap();
+        assert!(matches!(
+            FileDetStore::new().load(&path).await,
+            Err(DetError::Parse(_))
+        ));
+    }
+}
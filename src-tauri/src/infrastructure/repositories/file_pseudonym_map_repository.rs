@@ -0,0 +1,168 @@
+use async_trait::async_trait;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use crate::domain::anonymization::{PseudonymMap, PseudonymMapRepository};
+use crate::domain::project::repositories::ProjectRepository;
+use crate::domain::project::{Project, ProjectId};
+use crate::domain::workspace::repositories::RepositoryError;
+
+/// Pseudonym maps as JSON files in each project's own folder
+///
+/// The file sits in the project's private folder, `<reports folder>/.private`,
+/// so it stays with the project (an immutable source keeps it in its
+/// external reports folder) and away from the app data holding the document
+/// families. Scans of the source folder skip it, so it never becomes a
+/// document. It is readable by the owner only where the platform supports
+/// it.
+pub struct FilePseudonymMapRepository {
+    project_repository: Arc<dyn ProjectRepository>,
+}
+
+impl FilePseudonymMapRepository {
+    pub const MAP_FILE: &'static str = "pseudonyms.json";
+
+    /// Create a repository storing maps in the folders of the projects
+    pub fn new(project_repository: Arc<dyn ProjectRepository>) -> Self {
+        FilePseudonymMapRepository { project_repository }
+    }
+
+    /// Location of a project's map
+    pub fn path_for(project: &Project) -> PathBuf {
+        project.private_folder().join(Self::MAP_FILE)
+    }
+
+    async fn map_path(&self, project_id: &ProjectId) -> Result<PathBuf, RepositoryError> {
+        let project = self
+            .project_repository
+            .find_by_id(project_id)
+            .await
+            .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?
+            .ok_or_else(|| RepositoryError::NotFound(format!("Project '{}'", project_id)))?;
+        Ok(Self::path_for(&project))
+    }
+
+    fn write(path: &Path, json: &[u8]) -> std::io::Result<()> {
+        let folder = path.parent().unwrap_or(Path::new("."));
+        fs::create_dir_all(folder)?;
+
+        let temp_path = path.with_file_name(format!(
+            ".{}.{}.tmp",
+            Self::MAP_FILE,
+            uuid::Uuid::new_v4().simple()
+        ));
+        let result = Self::create_private(&temp_path)
+            .and_then(|mut file| {
+                file.write_all(json)?;
+                file.sync_all()
+            })
+            .and_then(|()| fs::rename(&temp_path, path));
+
+        if result.is_err() {
+            let _ = fs::remove_file(&temp_path);
+        }
+        result
+    }
+
+    #[cfg(unix)]
+    fn create_private(path: &Path) -> std::io::Result<fs::File> {
+        use std::os::unix::fs::OpenOptionsExt;
+        fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .mode(0o600)
+            .open(path)
+    }
+
+    #[cfg(not(unix))]
+    fn create_private(path: &Path) -> std::io::Result<fs::File> {
+        fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(path)
+    }
+}
+
+#[async_trait]
+impl PseudonymMapRepository for FilePseudonymMapRepository {
+    async fn load(&self, project_id: &ProjectId) -> Result<PseudonymMap, RepositoryError> {
+        let path = self.map_path(project_id).await?;
+        match tokio::fs::read(&path).await {
+            Ok(bytes) => serde_json::from_slice(&bytes)
+                .map_err(|e| RepositoryError::SerializationError(e.to_string())),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(PseudonymMap::new()),
+            Err(e) => Err(RepositoryError::FileSystemError(format!(
+                "{}: {}",
+                path.display(),
+                e
+            ))),
+        }
+    }
+
+    async fn save(
+        &self,
+        project_id: &ProjectId,
+        map: &PseudonymMap,
+    ) -> Result<(), RepositoryError> {
+        let path = self.map_path(project_id).await?;
+        let json = serde_json::to_vec_pretty(map)
+            .map_err(|e| RepositoryError::SerializationError(e.to_string()))?;
+
+        tokio::task::spawn_blocking(move || {
+            Self::write(&path, &json)
+                .map_err(|e| RepositoryError::FileSystemError(format!("{}: {}", path.display(), e)))
+        })
+        .await
+        .map_err(|e| RepositoryError::InternalError(e.to_string()))?
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::project::repositories::MockProjectRepository;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_save_and_load_map_in_project_folder() {
+        let source = TempDir::new().unwrap();
+        let projects = Arc::new(MockProjectRepository::new());
+        let project = Project::new(
+            "Pseudonyms".to_string(),
+            source.path().to_string_lossy().to_string(),
+            None,
+        )
+        .unwrap();
+        projects.create(&project).await.unwrap();
+        let repository = FilePseudonymMapRepository::new(projects);
+        let project_id = project.id();
+
+        assert!(repository.load(project_id).await.unwrap().is_empty());
+
+        let mut map = PseudonymMap::new();
+        map.pseudonym_for("PERSON", "Jane Doe");
+        repository.save(project_id, &map).await.unwrap();
+        map.pseudonym_for("PERSON", "John Roe");
+        repository.save(project_id, &map).await.unwrap();
+
+        assert_eq!(repository.load(project_id).await.unwrap(), map);
+        let path = FilePseudonymMapRepository::path_for(&project);
+        assert!(path.starts_with(source.path()));
+        let entries: Vec<_> = fs::read_dir(path.parent().unwrap()).unwrap().collect();
+        assert_eq!(entries.len(), 1);
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = fs::metadata(&path).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+
+        assert!(matches!(
+            repository.load(&ProjectId::new()).await,
+            Err(RepositoryError::NotFound(_))
+        ));
+    }
+}
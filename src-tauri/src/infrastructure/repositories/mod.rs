@@ -1,12 +1,48 @@
 pub mod workspace_layout_repository;
 // TODO: workspace_repository_new requires domain entities that were removed
 // pub mod workspace_repository_new;
+pub mod file_det_store;
+pub mod file_pseudonym_map_repository;
 pub mod file_system_repository;
 pub mod mock_project_repository;
+pub mod sqlite_annotation_repository;
+pub mod sqlite_category_repository;
+pub mod sqlite_citation_repository;
+pub mod sqlite_coding_repository;
+pub mod sqlite_derivation_repository;
+pub mod sqlite_document_repository;
+pub mod sqlite_email_repository;
+pub mod sqlite_file_category_config_repository;
+pub mod sqlite_file_hash_repository;
+pub mod sqlite_job_repository;
+pub mod sqlite_manifest_snapshot_repository;
+pub mod sqlite_media_metadata_repository;
 pub mod sqlite_project_repository;
+pub mod sqlite_report_repository;
+pub mod sqlite_review_repository;
+pub mod sqlite_saved_search_repository;
+pub mod sqlite_search_index_repository;
 
 pub use workspace_layout_repository::SqlxWorkspaceLayoutRepository;
 // pub use workspace_repository_new::{WorkspaceRepository, SqliteWorkspaceRepository, InMemoryWorkspaceRepository, WorkspaceRepositoryError};
+pub use file_det_store::FileDetStore;
+pub use file_pseudonym_map_repository::FilePseudonymMapRepository;
 pub use file_system_repository::TauriFileSystemRepository;
 pub use mock_project_repository::MockProjectRepository;
+pub use sqlite_annotation_repository::SqliteAnnotationRepository;
+pub use sqlite_category_repository::SqliteCategoryRepository;
+pub use sqlite_citation_repository::SqliteCitationRepository;
+pub use sqlite_coding_repository::SqliteCodingRepository;
+pub use sqlite_derivation_repository::SqliteDerivationRepository;
+pub use sqlite_document_repository::SqliteDocumentRepository;
+pub use sqlite_email_repository::SqliteEmailRepository;
+pub use sqlite_file_category_config_repository::SqliteFileCategoryConfigRepository;
+pub use sqlite_file_hash_repository::SqliteFileHashRepository;
+pub use sqlite_job_repository::SqliteJobRepository;
+pub use sqlite_manifest_snapshot_repository::SqliteManifestSnapshotRepository;
+pub use sqlite_media_metadata_repository::SqliteMediaMetadataRepository;
 pub use sqlite_project_repository::SqliteProjectRepository;
+pub use sqlite_report_repository::SqliteReportRepository;
+pub use sqlite_review_repository::SqliteReviewRepository;
+pub use sqlite_saved_search_repository::SqliteSavedSearchRepository;
+pub use sqlite_search_index_repository::SqliteSearchIndexRepository;
@@ -0,0 +1,220 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::{Row, SqlitePool};
+use std::sync::Arc;
+
+use crate::domain::annotation::{
+    AnchorStatus, Annotation, AnnotationAnchor, AnnotationId, AnnotationRepository,
+};
+use crate::domain::category::CategoryColor;
+use crate::domain::document::DocumentId;
+use crate::domain::project::ProjectId;
+use crate::domain::workspace::repositories::RepositoryError;
+
+const ANNOTATION_COLUMNS: &str = "id, project_uuid, document_id, anchor, quote, color, body, \
+     author, status, created_at, updated_at";
+
+/// SQLite implementation of the AnnotationRepository trait
+///
+/// Anchors are kept as JSON, tagged with their type.
+pub struct SqliteAnnotationRepository {
+    pool: Arc<SqlitePool>,
+}
+
+impl SqliteAnnotationRepository {
+    /// Create a new SqliteAnnotationRepository with the given connection pool
+    pub fn new(pool: Arc<SqlitePool>) -> Self {
+        SqliteAnnotationRepository { pool }
+    }
+
+    /// Convert database row to Annotation
+    fn row_to_annotation(row: &sqlx::sqlite::SqliteRow) -> Result<Annotation, RepositoryError> {
+        let db_error = |e: sqlx::Error| RepositoryError::DatabaseError(e.to_string());
+        let invalid = |e: String| RepositoryError::ValidationError(e);
+
+        let id: String = row.try_get("id").map_err(db_error)?;
+        let project_uuid: String = row.try_get("project_uuid").map_err(db_error)?;
+        let document_id: String = row.try_get("document_id").map_err(db_error)?;
+        let anchor: String = row.try_get("anchor").map_err(db_error)?;
+        let color: Option<String> = row.try_get("color").map_err(db_error)?;
+        let status: String = row.try_get("status").map_err(db_error)?;
+
+        Ok(Annotation::from_data(
+            AnnotationId::from_string(id).map_err(|e| invalid(e.to_string()))?,
+            ProjectId::from_string(project_uuid).map_err(|e| invalid(e.to_string()))?,
+            DocumentId::from_string(document_id).map_err(|e| invalid(e.to_string()))?,
+            serde_json::from_str::<AnnotationAnchor>(&anchor)?,
+            row.try_get("quote").map_err(db_error)?,
+            color
+                .as_deref()
+                .map(CategoryColor::parse)
+                .transpose()
+                .map_err(|e| invalid(e.to_string()))?,
+            row.try_get("body").map_err(db_error)?,
+            row.try_get("author").map_err(db_error)?,
+            AnchorStatus::parse(&status)
+                .ok_or_else(|| invalid(format!("Unknown anchor status '{}'", status)))?,
+            row.try_get::<DateTime<Utc>, _>("created_at")
+                .map_err(db_error)?,
+            row.try_get::<DateTime<Utc>, _>("updated_at")
+                .map_err(db_error)?,
+        ))
+    }
+
+    async fn list_where(
+        &self,
+        column: &str,
+        value: &str,
+    ) -> Result<Vec<Annotation>, RepositoryError> {
+        let query = format!(
+            "SELECT {} FROM annotations WHERE {} = ?1 ORDER BY created_at, id",
+            ANNOTATION_COLUMNS, column
+        );
+
+        let rows = sqlx::query(&query)
+            .bind(value)
+            .fetch_all(&*self.pool)
+            .await
+            .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+
+        rows.iter().map(Self::row_to_annotation).collect()
+    }
+}
+
+#[async_trait]
+impl AnnotationRepository for SqliteAnnotationRepository {
+    async fn save(&self, annotation: &Annotation) -> Result<(), RepositoryError> {
+        let query = r#"
+            INSERT INTO annotations (id, project_uuid, document_id, anchor, quote, color, body,
+                                     author, status, created_at, updated_at)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)
+            ON CONFLICT(id) DO UPDATE SET
+                anchor = excluded.anchor,
+                quote = excluded.quote,
+                color = excluded.color,
+                body = excluded.body,
+                status = excluded.status,
+                updated_at = excluded.updated_at
+        "#;
+
+        sqlx::query(query)
+            .bind(annotation.id().value())
+            .bind(annotation.project_id().value())
+            .bind(annotation.document_id().value())
+            .bind(serde_json::to_string(annotation.anchor())?)
+            .bind(annotation.quote())
+            .bind(annotation.color().map(CategoryColor::value))
+            .bind(annotation.body())
+            .bind(annotation.author())
+            .bind(annotation.status().as_str())
+            .bind(annotation.created_at())
+            .bind(annotation.updated_at())
+            .execute(&*self.pool)
+            .await
+            .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+
+        Ok(())
+    }
+
+    async fn find_by_id(&self, id: &AnnotationId) -> Result<Option<Annotation>, RepositoryError> {
+        let query = format!(
+            "SELECT {} FROM annotations WHERE id = ?1",
+            ANNOTATION_COLUMNS
+        );
+
+        let row = sqlx::query(&query)
+            .bind(id.value())
+            .fetch_optional(&*self.pool)
+            .await
+            .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+
+        row.as_ref().map(Self::row_to_annotation).transpose()
+    }
+
+    async fn list_by_document(
+        &self,
+        document_id: &DocumentId,
+    ) -> Result<Vec<Annotation>, RepositoryError> {
+        self.list_where("document_id", document_id.value()).await
+    }
+
+    async fn list_by_project(
+        &self,
+        project_id: &ProjectId,
+    ) -> Result<Vec<Annotation>, RepositoryError> {
+        self.list_where("project_uuid", project_id.value()).await
+    }
+
+    async fn delete(&self, id: &AnnotationId) -> Result<(), RepositoryError> {
+        sqlx::query("DELETE FROM annotations WHERE id = ?1")
+            .bind(id.value())
+            .execute(&*self.pool)
+            .await
+            .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::DatabaseConnection;
+
+    #[tokio::test]
+    async fn test_save_list_and_delete() {
+        let (database, _dir) = DatabaseConnection::new_temp().await.unwrap();
+        let repository = SqliteAnnotationRepository::new(database.pool());
+        let project_id = ProjectId::new();
+        let document_id = DocumentId::new();
+
+        let mut highlight = Annotation::new(
+            project_id.clone(),
+            document_id.clone(),
+            AnnotationAnchor::Range { from: 3, to: 9 },
+            "budget".to_string(),
+            "Ana".to_string(),
+        )
+        .unwrap();
+        highlight.set_color(Some(CategoryColor::parse("#fde047").unwrap()));
+        highlight
+            .set_body("Check against the ledger".to_string())
+            .unwrap();
+        let note = Annotation::new(
+            project_id.clone(),
+            DocumentId::new(),
+            AnnotationAnchor::Page {
+                page: 2,
+                start: 0,
+                end: 12,
+            },
+            "Introduction".to_string(),
+            "Ben".to_string(),
+        )
+        .unwrap();
+        repository.save(&highlight).await.unwrap();
+        repository.save(&note).await.unwrap();
+
+        let found = repository
+            .find_by_id(highlight.id())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(found, highlight);
+        assert_eq!(
+            repository.list_by_document(&document_id).await.unwrap(),
+            [highlight.clone()]
+        );
+        assert_eq!(
+            repository.list_by_project(&project_id).await.unwrap().len(),
+            2
+        );
+
+        repository.delete(highlight.id()).await.unwrap();
+        assert!(repository
+            .find_by_id(highlight.id())
+            .await
+            .unwrap()
+            .is_none());
+    }
+}
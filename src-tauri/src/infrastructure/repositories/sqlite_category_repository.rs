@@ -0,0 +1,367 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::{QueryBuilder, Row, Sqlite, SqlitePool};
+use std::sync::Arc;
+
+use crate::domain::category::{
+    Category, CategoryAssignment, CategoryColor, CategoryId, CategoryKind, CategoryRepository,
+    Shortcut,
+};
+use crate::domain::document::DocumentId;
+use crate::domain::project::ProjectId;
+use crate::domain::workspace::repositories::RepositoryError;
+
+const CATEGORY_COLUMNS: &str =
+    "id, project_uuid, kind, name, parent_id, color, shortcut, created_at, updated_at";
+
+/// SQLite implementation of the CategoryRepository trait
+///
+/// Sibling names and shortcuts are kept unique by indexes, so concurrent
+/// edits cannot create duplicates.
+pub struct SqliteCategoryRepository {
+    pool: Arc<SqlitePool>,
+}
+
+impl SqliteCategoryRepository {
+    /// Create a new SqliteCategoryRepository with the given connection pool
+    pub fn new(pool: Arc<SqlitePool>) -> Self {
+        SqliteCategoryRepository { pool }
+    }
+
+    /// Convert database row to Category
+    fn row_to_category(row: &sqlx::sqlite::SqliteRow) -> Result<Category, RepositoryError> {
+        let db_error = |e: sqlx::Error| RepositoryError::DatabaseError(e.to_string());
+        let invalid = |e: String| RepositoryError::ValidationError(e);
+
+        let id: String = row.try_get("id").map_err(db_error)?;
+        let project_uuid: String = row.try_get("project_uuid").map_err(db_error)?;
+        let kind: String = row.try_get("kind").map_err(db_error)?;
+        let parent_id: Option<String> = row.try_get("parent_id").map_err(db_error)?;
+        let color: Option<String> = row.try_get("color").map_err(db_error)?;
+        let shortcut: Option<String> = row.try_get("shortcut").map_err(db_error)?;
+
+        Ok(Category::from_data(
+            CategoryId::from_string(id).map_err(|e| invalid(e.to_string()))?,
+            ProjectId::from_string(project_uuid).map_err(|e| invalid(e.to_string()))?,
+            CategoryKind::parse(&kind).map_err(|e| invalid(e.to_string()))?,
+            row.try_get("name").map_err(db_error)?,
+            parent_id
+                .map(CategoryId::from_string)
+                .transpose()
+                .map_err(|e| invalid(e.to_string()))?,
+            color
+                .as_deref()
+                .map(CategoryColor::parse)
+                .transpose()
+                .map_err(|e| invalid(e.to_string()))?,
+            shortcut
+                .as_deref()
+                .map(Shortcut::parse)
+                .transpose()
+                .map_err(|e| invalid(e.to_string()))?,
+            row.try_get::<DateTime<Utc>, _>("created_at")
+                .map_err(db_error)?,
+            row.try_get::<DateTime<Utc>, _>("updated_at")
+                .map_err(db_error)?,
+        ))
+    }
+}
+
+#[async_trait]
+impl CategoryRepository for SqliteCategoryRepository {
+    async fn save(&self, category: &Category) -> Result<(), RepositoryError> {
+        let query = r#"
+            INSERT INTO categories (id, project_uuid, kind, name, parent_id, color, shortcut,
+                                    created_at, updated_at)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)
+            ON CONFLICT(id) DO UPDATE SET
+                name = excluded.name,
+                parent_id = excluded.parent_id,
+                color = excluded.color,
+                shortcut = excluded.shortcut,
+                updated_at = excluded.updated_at
+        "#;
+
+        sqlx::query(query)
+            .bind(category.id().value())
+            .bind(category.project_id().value())
+            .bind(category.kind().as_str())
+            .bind(category.name())
+            .bind(category.parent_id().map(CategoryId::value))
+            .bind(category.color().map(CategoryColor::value))
+            .bind(category.shortcut().map(Shortcut::value))
+            .bind(category.created_at())
+            .bind(category.updated_at())
+            .execute(&*self.pool)
+            .await
+            .map_err(|e| match e {
+                sqlx::Error::Database(ref db) if db.message().contains("UNIQUE") => {
+                    if db.message().contains("shortcut") {
+                        RepositoryError::ConstraintViolation(format!(
+                            "The shortcut {} is already used in this project",
+                            category.shortcut().map(Shortcut::value).unwrap_or_default()
+                        ))
+                    } else {
+                        RepositoryError::ConstraintViolation(format!(
+                            "A {} named '{}' already exists here",
+                            category.kind().as_str(),
+                            category.name()
+                        ))
+                    }
+                }
+                e => RepositoryError::DatabaseError(e.to_string()),
+            })?;
+
+        Ok(())
+    }
+
+    async fn find_by_id(&self, id: &CategoryId) -> Result<Option<Category>, RepositoryError> {
+        let query = format!("SELECT {} FROM categories WHERE id = ?1", CATEGORY_COLUMNS);
+
+        let row = sqlx::query(&query)
+            .bind(id.value())
+            .fetch_optional(&*self.pool)
+            .await
+            .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+
+        row.as_ref().map(Self::row_to_category).transpose()
+    }
+
+    async fn list_by_project(
+        &self,
+        project_id: &ProjectId,
+    ) -> Result<Vec<Category>, RepositoryError> {
+        let query = format!(
+            "SELECT {} FROM categories WHERE project_uuid = ?1 ORDER BY name COLLATE NOCASE",
+            CATEGORY_COLUMNS
+        );
+
+        let rows = sqlx::query(&query)
+            .bind(project_id.value())
+            .fetch_all(&*self.pool)
+            .await
+            .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+
+        rows.iter().map(Self::row_to_category).collect()
+    }
+
+    async fn delete(&self, ids: &[CategoryId]) -> Result<(), RepositoryError> {
+        let db_error = |e: sqlx::Error| RepositoryError::DatabaseError(e.to_string());
+        if ids.is_empty() {
+            return Ok(());
+        }
+
+        let mut tx = self.pool.begin().await.map_err(db_error)?;
+        for (table, column) in [("document_categories", "category_id"), ("categories", "id")] {
+            let mut query: QueryBuilder<Sqlite> =
+                QueryBuilder::new(format!("DELETE FROM {} WHERE {} IN (", table, column));
+            let mut separated = query.separated(", ");
+            for id in ids {
+                separated.push_bind(id.value());
+            }
+            separated.push_unseparated(")");
+            query.build().execute(&mut *tx).await.map_err(db_error)?;
+        }
+        tx.commit().await.map_err(db_error)?;
+
+        Ok(())
+    }
+
+    async fn assign(
+        &self,
+        category_id: &CategoryId,
+        document_ids: &[DocumentId],
+    ) -> Result<usize, RepositoryError> {
+        let db_error = |e: sqlx::Error| RepositoryError::DatabaseError(e.to_string());
+        let assigned_at = Utc::now();
+
+        let mut tx = self.pool.begin().await.map_err(db_error)?;
+        let mut added = 0;
+        for document_id in document_ids {
+            let result = sqlx::query(
+                "INSERT OR IGNORE INTO document_categories (document_id, category_id, assigned_at) \
+                 VALUES (?1, ?2, ?3)",
+            )
+            .bind(document_id.value())
+            .bind(category_id.value())
+            .bind(assigned_at)
+            .execute(&mut *tx)
+            .await
+            .map_err(db_error)?;
+            added += result.rows_affected() as usize;
+        }
+        tx.commit().await.map_err(db_error)?;
+
+        Ok(added)
+    }
+
+    async fn unassign(
+        &self,
+        category_id: &CategoryId,
+        document_ids: &[DocumentId],
+    ) -> Result<usize, RepositoryError> {
+        let db_error = |e: sqlx::Error| RepositoryError::DatabaseError(e.to_string());
+
+        let mut tx = self.pool.begin().await.map_err(db_error)?;
+        let mut removed = 0;
+        for document_id in document_ids {
+            let result = sqlx::query(
+                "DELETE FROM document_categories WHERE document_id = ?1 AND category_id = ?2",
+            )
+            .bind(document_id.value())
+            .bind(category_id.value())
+            .execute(&mut *tx)
+            .await
+            .map_err(db_error)?;
+            removed += result.rows_affected() as usize;
+        }
+        tx.commit().await.map_err(db_error)?;
+
+        Ok(removed)
+    }
+
+    async fn list_assignments(
+        &self,
+        project_id: &ProjectId,
+    ) -> Result<Vec<CategoryAssignment>, RepositoryError> {
+        let db_error = |e: sqlx::Error| RepositoryError::DatabaseError(e.to_string());
+        let invalid = |e: String| RepositoryError::ValidationError(e);
+
+        let rows = sqlx::query(
+            r#"
+            SELECT a.document_id, a.category_id, a.assigned_at
+            FROM document_categories a
+            JOIN categories c ON c.id = a.category_id
+            WHERE c.project_uuid = ?1
+            "#,
+        )
+        .bind(project_id.value())
+        .fetch_all(&*self.pool)
+        .await
+        .map_err(db_error)?;
+
+        rows.iter()
+            .map(|row| {
+                let document_id: String = row.try_get("document_id").map_err(db_error)?;
+                let category_id: String = row.try_get("category_id").map_err(db_error)?;
+                Ok(CategoryAssignment {
+                    document_id: DocumentId::from_string(document_id)
+                        .map_err(|e| invalid(e.to_string()))?,
+                    category_id: CategoryId::from_string(category_id)
+                        .map_err(|e| invalid(e.to_string()))?,
+                    assigned_at: row.try_get("assigned_at").map_err(db_error)?,
+                })
+            })
+            .collect()
+    }
+
+    async fn categories_of(
+        &self,
+        document_id: &DocumentId,
+    ) -> Result<Vec<CategoryId>, RepositoryError> {
+        let rows = sqlx::query(
+            "SELECT category_id FROM document_categories WHERE document_id = ?1 \
+             ORDER BY assigned_at",
+        )
+        .bind(document_id.value())
+        .fetch_all(&*self.pool)
+        .await
+        .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+
+        rows.iter()
+            .map(|row| {
+                let id: String = row
+                    .try_get("category_id")
+                    .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+                CategoryId::from_string(id)
+                    .map_err(|e| RepositoryError::ValidationError(e.to_string()))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::DatabaseConnection;
+
+    #[tokio::test]
+    async fn test_categories_and_assignments() {
+        let (database, _dir) = DatabaseConnection::new_temp().await.unwrap();
+        let repository = SqliteCategoryRepository::new(database.pool());
+        let project_id = ProjectId::new();
+
+        let mut finance = Category::new(
+            project_id.clone(),
+            CategoryKind::Category,
+            "Finance".to_string(),
+        )
+        .unwrap();
+        finance.set_color(Some(CategoryColor::parse("#22c55e").unwrap()));
+        finance.set_shortcut(Some(Shortcut::parse("1").unwrap()));
+        let mut invoices = Category::new(
+            project_id.clone(),
+            CategoryKind::Category,
+            "Invoices".to_string(),
+        )
+        .unwrap();
+        invoices.move_to(Some(&finance), &[]).unwrap();
+        repository.save(&finance).await.unwrap();
+        repository.save(&invoices).await.unwrap();
+        assert_eq!(
+            repository.find_by_id(finance.id()).await.unwrap().unwrap(),
+            finance
+        );
+
+        // Sibling names and shortcuts are unique
+        let mut twin = Category::new(
+            project_id.clone(),
+            CategoryKind::Category,
+            "finance".to_string(),
+        )
+        .unwrap();
+        assert!(matches!(
+            repository.save(&twin).await,
+            Err(RepositoryError::ConstraintViolation(_))
+        ));
+        twin.rename("Legal".to_string()).unwrap();
+        twin.set_shortcut(Some(Shortcut::parse("1").unwrap()));
+        let error = repository.save(&twin).await.unwrap_err();
+        assert!(error.to_string().contains("shortcut"));
+
+        let documents = [DocumentId::new(), DocumentId::new()];
+        assert_eq!(
+            repository.assign(invoices.id(), &documents).await.unwrap(),
+            2
+        );
+        assert_eq!(
+            repository.assign(invoices.id(), &documents).await.unwrap(),
+            0
+        );
+        assert_eq!(
+            repository
+                .unassign(invoices.id(), &documents[..1])
+                .await
+                .unwrap(),
+            1
+        );
+        let assignments = repository.list_assignments(&project_id).await.unwrap();
+        assert_eq!(assignments.len(), 1);
+        assert_eq!(assignments[0].document_id, documents[1]);
+        assert_eq!(
+            repository.categories_of(&documents[1]).await.unwrap(),
+            [invoices.id().clone()]
+        );
+
+        repository.delete(&[invoices.id().clone()]).await.unwrap();
+        assert!(repository
+            .categories_of(&documents[1])
+            .await
+            .unwrap()
+            .is_empty());
+        assert_eq!(
+            repository.list_by_project(&project_id).await.unwrap().len(),
+            1
+        );
+    }
+}
@@ -0,0 +1,230 @@
+use async_trait::async_trait;
+use sqlx::{Row, SqlitePool};
+use std::sync::Arc;
+
+use crate::domain::document::DocumentId;
+use crate::domain::report::{Citation, CitationLocator, CitationRepository, ReportId};
+use crate::domain::workspace::repositories::RepositoryError;
+
+/// SQLite implementation of the CitationRepository trait
+///
+/// The locator is stored as its kind plus the page or the range bounds,
+/// whichever the kind uses.
+pub struct SqliteCitationRepository {
+    pool: Arc<SqlitePool>,
+}
+
+impl SqliteCitationRepository {
+    /// Create a new SqliteCitationRepository with the given connection pool
+    pub fn new(pool: Arc<SqlitePool>) -> Self {
+        SqliteCitationRepository { pool }
+    }
+
+    /// Convert database row to Citation
+    fn row_to_citation(row: &sqlx::sqlite::SqliteRow) -> Result<Citation, RepositoryError> {
+        let db_error = |e: sqlx::Error| RepositoryError::DatabaseError(e.to_string());
+
+        let document_id: Option<String> = row.try_get("document_id").map_err(db_error)?;
+        let locator: String = row.try_get("locator").map_err(db_error)?;
+        let page: Option<u32> = row.try_get("page").map_err(db_error)?;
+        let start: Option<u32> = row.try_get("range_start").map_err(db_error)?;
+        let end: Option<u32> = row.try_get("range_end").map_err(db_error)?;
+
+        let locator = match (locator.as_str(), page, start.zip(end)) {
+            ("file", _, _) => CitationLocator::File,
+            ("page", Some(page), _) => CitationLocator::Page { page },
+            ("char_range", _, Some((start, end))) => CitationLocator::CharRange { start, end },
+            ("position", _, Some((from, to))) => CitationLocator::Position { from, to },
+            (other, _, _) => {
+                return Err(RepositoryError::ValidationError(format!(
+                    "Incomplete citation locator '{}'",
+                    other
+                )))
+            }
+        };
+        let document_id = document_id
+            .map(DocumentId::from_string)
+            .transpose()
+            .map_err(|e| RepositoryError::ValidationError(e.to_string()))?;
+
+        Citation::new(document_id, row.try_get("path").map_err(db_error)?, locator)
+            .map_err(|e| RepositoryError::ValidationError(e.to_string()))
+    }
+}
+
+#[async_trait]
+impl CitationRepository for SqliteCitationRepository {
+    async fn replace_for_report(
+        &self,
+        report_id: &ReportId,
+        citations: &[Citation],
+    ) -> Result<(), RepositoryError> {
+        let db_error = |e: sqlx::Error| RepositoryError::DatabaseError(e.to_string());
+
+        let mut tx = self.pool.begin().await.map_err(db_error)?;
+
+        sqlx::query("DELETE FROM citations WHERE report_id = ?1")
+            .bind(report_id.value())
+            .execute(&mut *tx)
+            .await
+            .map_err(db_error)?;
+
+        for (position, citation) in citations.iter().enumerate() {
+            let (page, start, end) = match citation.locator() {
+                CitationLocator::File => (None, None, None),
+                CitationLocator::Page { page } => (Some(page), None, None),
+                CitationLocator::CharRange { start, end } => (None, Some(start), Some(end)),
+                CitationLocator::Position { from, to } => (None, Some(from), Some(to)),
+            };
+
+            sqlx::query(
+                r#"
+                INSERT INTO citations (report_id, position, document_id, path, locator,
+                                       page, range_start, range_end)
+                VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)
+                "#,
+            )
+            .bind(report_id.value())
+            .bind(position as i64)
+            .bind(citation.document_id().map(DocumentId::value))
+            .bind(citation.path())
+            .bind(citation.locator().as_str())
+            .bind(page)
+            .bind(start)
+            .bind(end)
+            .execute(&mut *tx)
+            .await
+            .map_err(db_error)?;
+        }
+
+        tx.commit().await.map_err(db_error)?;
+
+        Ok(())
+    }
+
+    async fn list_by_report(&self, report_id: &ReportId) -> Result<Vec<Citation>, RepositoryError> {
+        let rows = sqlx::query(
+            r#"
+            SELECT document_id, path, locator, page, range_start, range_end
+            FROM citations
+            WHERE report_id = ?1
+            ORDER BY position
+            "#,
+        )
+        .bind(report_id.value())
+        .fetch_all(&*self.pool)
+        .await
+        .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+
+        rows.iter().map(Self::row_to_citation).collect()
+    }
+
+    async fn find_reports_citing(
+        &self,
+        document_id: &DocumentId,
+    ) -> Result<Vec<ReportId>, RepositoryError> {
+        let rows = sqlx::query(
+            r#"
+            SELECT DISTINCT c.report_id
+            FROM citations c
+            JOIN reports r ON r.id = c.report_id
+            WHERE c.document_id = ?1
+            ORDER BY r.updated_at DESC
+            "#,
+        )
+        .bind(document_id.value())
+        .fetch_all(&*self.pool)
+        .await
+        .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+
+        rows.iter()
+            .map(|row| {
+                let id: String = row
+                    .try_get("report_id")
+                    .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+                ReportId::from_string(id)
+                    .map_err(|e| RepositoryError::ValidationError(e.to_string()))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::project::ProjectId;
+    use crate::domain::report::{Report, ReportCategory, ReportRepository};
+    use crate::infrastructure::{DatabaseConnection, SqliteReportRepository};
+
+    #[tokio::test]
+    async fn test_replace_list_and_find_citing_reports() {
+        let (database, _dir) = DatabaseConnection::new_temp().await.unwrap();
+        let reports = SqliteReportRepository::new(database.pool());
+        let repository = SqliteCitationRepository::new(database.pool());
+
+        let report = Report::new(
+            ProjectId::new(),
+            "Findings".to_string(),
+            ReportCategory::Findings,
+            "findings/findings.det".to_string(),
+        )
+        .unwrap();
+        reports.save(&report).await.unwrap();
+
+        let document_id = DocumentId::new();
+        let citations = vec![
+            Citation::new(
+                Some(document_id.clone()),
+                "/corpus/a.pdf".to_string(),
+                CitationLocator::Page { page: 2 },
+            )
+            .unwrap(),
+            Citation::new(
+                None,
+                "/corpus/b.txt".to_string(),
+                CitationLocator::CharRange { start: 3, end: 9 },
+            )
+            .unwrap(),
+            Citation::new(
+                Some(document_id.clone()),
+                "/corpus/a.pdf".to_string(),
+                CitationLocator::Position { from: 1, to: 4 },
+            )
+            .unwrap(),
+        ];
+        repository
+            .replace_for_report(report.id(), &citations)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            repository.list_by_report(report.id()).await.unwrap(),
+            citations
+        );
+        assert_eq!(
+            repository.find_reports_citing(&document_id).await.unwrap(),
+            vec![report.id().clone()]
+        );
+
+        repository
+            .replace_for_report(report.id(), &citations[1..2])
+            .await
+            .unwrap();
+        assert!(repository
+            .find_reports_citing(&document_id)
+            .await
+            .unwrap()
+            .is_empty());
+
+        repository
+            .replace_for_report(report.id(), &citations)
+            .await
+            .unwrap();
+        reports.delete(report.id()).await.unwrap();
+        assert!(repository
+            .list_by_report(report.id())
+            .await
+            .unwrap()
+            .is_empty());
+    }
+}
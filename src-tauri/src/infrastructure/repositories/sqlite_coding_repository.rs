@@ -0,0 +1,246 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::{Row, SqlitePool};
+use std::sync::Arc;
+
+use crate::domain::coding::{
+    CodingDecision, CodingDecisionId, CodingField, CodingRepository, CodingSchema, CodingValues,
+};
+use crate::domain::document::DocumentId;
+use crate::domain::project::ProjectId;
+use crate::domain::workspace::repositories::RepositoryError;
+
+const DECISION_COLUMNS: &str = r#"id, project_uuid, document_id, "values", reviewer, decided_at"#;
+
+/// SQLite implementation of the CodingRepository trait
+///
+/// Schema fields and decision values are kept as JSON.
+pub struct SqliteCodingRepository {
+    pool: Arc<SqlitePool>,
+}
+
+impl SqliteCodingRepository {
+    /// Create a new SqliteCodingRepository with the given connection pool
+    pub fn new(pool: Arc<SqlitePool>) -> Self {
+        SqliteCodingRepository { pool }
+    }
+
+    /// Convert database row to CodingDecision
+    fn row_to_decision(row: &sqlx::sqlite::SqliteRow) -> Result<CodingDecision, RepositoryError> {
+        let db_error = |e: sqlx::Error| RepositoryError::DatabaseError(e.to_string());
+        let invalid = |e: String| RepositoryError::ValidationError(e);
+
+        let id: String = row.try_get("id").map_err(db_error)?;
+        let project_uuid: String = row.try_get("project_uuid").map_err(db_error)?;
+        let document_id: String = row.try_get("document_id").map_err(db_error)?;
+        let values: String = row.try_get("values").map_err(db_error)?;
+
+        Ok(CodingDecision::from_data(
+            CodingDecisionId::from_string(id).map_err(|e| invalid(e.to_string()))?,
+            ProjectId::from_string(project_uuid).map_err(|e| invalid(e.to_string()))?,
+            DocumentId::from_string(document_id).map_err(|e| invalid(e.to_string()))?,
+            serde_json::from_str::<CodingValues>(&values)?,
+            row.try_get("reviewer").map_err(db_error)?,
+            row.try_get::<DateTime<Utc>, _>("decided_at")
+                .map_err(db_error)?,
+        ))
+    }
+}
+
+#[async_trait]
+impl CodingRepository for SqliteCodingRepository {
+    async fn find_schema(
+        &self,
+        project_id: &ProjectId,
+    ) -> Result<Option<CodingSchema>, RepositoryError> {
+        let row =
+            sqlx::query("SELECT fields, updated_at FROM coding_schemas WHERE project_uuid = ?1")
+                .bind(project_id.value())
+                .fetch_optional(&*self.pool)
+                .await
+                .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+
+        let Some(row) = row else {
+            return Ok(None);
+        };
+        let db_error = |e: sqlx::Error| RepositoryError::DatabaseError(e.to_string());
+        let fields: String = row.try_get("fields").map_err(db_error)?;
+
+        Ok(Some(CodingSchema::from_data(
+            project_id.clone(),
+            serde_json::from_str::<Vec<CodingField>>(&fields)?,
+            row.try_get::<DateTime<Utc>, _>("updated_at")
+                .map_err(db_error)?,
+        )))
+    }
+
+    async fn save_schema(&self, schema: &CodingSchema) -> Result<(), RepositoryError> {
+        let query = r#"
+            INSERT INTO coding_schemas (project_uuid, fields, updated_at)
+            VALUES (?1, ?2, ?3)
+            ON CONFLICT(project_uuid) DO UPDATE SET
+                fields = excluded.fields,
+                updated_at = excluded.updated_at
+        "#;
+
+        sqlx::query(query)
+            .bind(schema.project_id().value())
+            .bind(serde_json::to_string(schema.fields())?)
+            .bind(schema.updated_at().unwrap_or_else(Utc::now))
+            .execute(&*self.pool)
+            .await
+            .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+
+        Ok(())
+    }
+
+    async fn record_decision(&self, decision: &CodingDecision) -> Result<(), RepositoryError> {
+        let query = r#"
+            INSERT INTO coding_decisions (id, project_uuid, document_id, "values", reviewer,
+                                          decided_at)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+        "#;
+
+        sqlx::query(query)
+            .bind(decision.id().value())
+            .bind(decision.project_id().value())
+            .bind(decision.document_id().value())
+            .bind(serde_json::to_string(decision.values())?)
+            .bind(decision.reviewer())
+            .bind(decision.decided_at())
+            .execute(&*self.pool)
+            .await
+            .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+
+        Ok(())
+    }
+
+    async fn history(
+        &self,
+        document_id: &DocumentId,
+    ) -> Result<Vec<CodingDecision>, RepositoryError> {
+        let query = format!(
+            "SELECT {} FROM coding_decisions WHERE document_id = ?1 ORDER BY decided_at, rowid",
+            DECISION_COLUMNS
+        );
+
+        let rows = sqlx::query(&query)
+            .bind(document_id.value())
+            .fetch_all(&*self.pool)
+            .await
+            .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+
+        rows.iter().map(Self::row_to_decision).collect()
+    }
+
+    async fn decisions(
+        &self,
+        project_id: &ProjectId,
+    ) -> Result<Vec<CodingDecision>, RepositoryError> {
+        let query = format!(
+            "SELECT {} FROM coding_decisions WHERE project_uuid = ?1 ORDER BY decided_at, rowid",
+            DECISION_COLUMNS
+        );
+
+        let rows = sqlx::query(&query)
+            .bind(project_id.value())
+            .fetch_all(&*self.pool)
+            .await
+            .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+
+        rows.iter().map(Self::row_to_decision).collect()
+    }
+
+    async fn current_decisions(
+        &self,
+        project_id: &ProjectId,
+    ) -> Result<Vec<CodingDecision>, RepositoryError> {
+        // The latest decision per document; rowid breaks ties between
+        // decisions made within the same instant
+        let query = format!(
+            r#"
+            SELECT {} FROM coding_decisions AS d
+            WHERE project_uuid = ?1
+              AND rowid = (
+                  SELECT latest.rowid FROM coding_decisions AS latest
+                  WHERE latest.document_id = d.document_id
+                  ORDER BY latest.decided_at DESC, latest.rowid DESC
+                  LIMIT 1
+              )
+            ORDER BY document_id
+            "#,
+            DECISION_COLUMNS
+        );
+
+        let rows = sqlx::query(&query)
+            .bind(project_id.value())
+            .fetch_all(&*self.pool)
+            .await
+            .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+
+        rows.iter().map(Self::row_to_decision).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::coding::CodingValue;
+    use crate::infrastructure::DatabaseConnection;
+
+    #[tokio::test]
+    async fn test_schema_and_decision_history() {
+        let (database, _dir) = DatabaseConnection::new_temp().await.unwrap();
+        let repository = SqliteCodingRepository::new(database.pool());
+        let project_id = ProjectId::new();
+        let document_id = DocumentId::new();
+
+        assert!(repository.find_schema(&project_id).await.unwrap().is_none());
+        let default = CodingSchema::default_for(project_id.clone());
+        let schema = CodingSchema::new(project_id.clone(), default.fields().to_vec()).unwrap();
+        repository.save_schema(&schema).await.unwrap();
+        let found = repository.find_schema(&project_id).await.unwrap().unwrap();
+        assert_eq!(found.fields(), schema.fields());
+
+        let designation = |value: &str| {
+            CodingValues::from([(
+                "designation".to_string(),
+                CodingValue::Text(value.to_string()),
+            )])
+        };
+        let first = CodingDecision::new(
+            project_id.clone(),
+            document_id.clone(),
+            designation("Relevant"),
+            "Ana".to_string(),
+        )
+        .unwrap();
+        let second = CodingDecision::new(
+            project_id.clone(),
+            document_id.clone(),
+            designation("Not Relevant"),
+            "Ben".to_string(),
+        )
+        .unwrap();
+        let other = CodingDecision::new(
+            project_id.clone(),
+            DocumentId::new(),
+            designation("Privileged"),
+            "Ana".to_string(),
+        )
+        .unwrap();
+        for decision in [&first, &second, &other] {
+            repository.record_decision(decision).await.unwrap();
+        }
+
+        assert_eq!(
+            repository.history(&document_id).await.unwrap(),
+            [first, second.clone()]
+        );
+        assert_eq!(repository.decisions(&project_id).await.unwrap().len(), 3);
+        let current = repository.current_decisions(&project_id).await.unwrap();
+        assert_eq!(current.len(), 2);
+        assert!(current.contains(&second));
+        assert!(current.contains(&other));
+    }
+}
@@ -0,0 +1,181 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::{Row, SqlitePool};
+use std::sync::Arc;
+
+use crate::domain::document::{
+    DerivationGraph, DerivationRepository, DerivationStep, DocumentId, FamilyMember,
+};
+use crate::domain::project::ProjectId;
+use crate::domain::workspace::repositories::RepositoryError;
+
+/// SQLite implementation of the DerivationRepository trait
+///
+/// Each step is one row keyed by document and edge; members are stored by
+/// their keys and parameters as JSON text.
+pub struct SqliteDerivationRepository {
+    pool: Arc<SqlitePool>,
+}
+
+impl SqliteDerivationRepository {
+    /// Create a new SqliteDerivationRepository with the given connection pool
+    pub fn new(pool: Arc<SqlitePool>) -> Self {
+        SqliteDerivationRepository { pool }
+    }
+
+    /// Convert database row to DerivationStep
+    fn row_to_step(row: &sqlx::sqlite::SqliteRow) -> Result<DerivationStep, RepositoryError> {
+        let db_error = |e: sqlx::Error| RepositoryError::DatabaseError(e.to_string());
+        let invalid = |e: crate::domain::document::DerivationError| {
+            RepositoryError::ValidationError(e.to_string())
+        };
+
+        let parent: String = row.try_get("parent").map_err(db_error)?;
+        let child: String = row.try_get("child").map_err(db_error)?;
+        let parameters: String = row.try_get("parameters").map_err(db_error)?;
+
+        DerivationStep::from_data(
+            FamilyMember::parse(&parent).map_err(invalid)?,
+            FamilyMember::parse(&child).map_err(invalid)?,
+            row.try_get("operation").map_err(db_error)?,
+            serde_json::from_str(&parameters)
+                .map_err(|e| RepositoryError::SerializationError(e.to_string()))?,
+            row.try_get("tool_version").map_err(db_error)?,
+            row.try_get::<DateTime<Utc>, _>("produced_at")
+                .map_err(db_error)?,
+        )
+        .map_err(invalid)
+    }
+}
+
+#[async_trait]
+impl DerivationRepository for SqliteDerivationRepository {
+    async fn find_by_document(
+        &self,
+        project_id: &ProjectId,
+        document_id: &DocumentId,
+    ) -> Result<DerivationGraph, RepositoryError> {
+        let rows = sqlx::query(
+            r#"
+            SELECT parent, child, operation, parameters, tool_version, produced_at
+            FROM derivation_steps
+            WHERE document_id = ?1
+            ORDER BY position
+            "#,
+        )
+        .bind(document_id.value())
+        .fetch_all(&*self.pool)
+        .await
+        .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+
+        let steps = rows
+            .iter()
+            .map(Self::row_to_step)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(DerivationGraph::from_data(
+            project_id.clone(),
+            document_id.clone(),
+            steps,
+        ))
+    }
+
+    async fn save(&self, graph: &DerivationGraph) -> Result<(), RepositoryError> {
+        let db_error = |e: sqlx::Error| RepositoryError::DatabaseError(e.to_string());
+
+        let mut tx = self.pool.begin().await.map_err(db_error)?;
+
+        sqlx::query("DELETE FROM derivation_steps WHERE document_id = ?1")
+            .bind(graph.document_id().value())
+            .execute(&mut *tx)
+            .await
+            .map_err(db_error)?;
+
+        for (position, step) in graph.steps().iter().enumerate() {
+            let parameters = serde_json::to_string(step.parameters())
+                .map_err(|e| RepositoryError::SerializationError(e.to_string()))?;
+
+            sqlx::query(
+                r#"
+                INSERT INTO derivation_steps (document_id, project_uuid, position, parent, child,
+                                              operation, parameters, tool_version, produced_at)
+                VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)
+                "#,
+            )
+            .bind(graph.document_id().value())
+            .bind(graph.project_id().value())
+            .bind(position as i64)
+            .bind(step.parent().key())
+            .bind(step.child().key())
+            .bind(step.operation())
+            .bind(parameters)
+            .bind(step.tool_version())
+            .bind(step.produced_at())
+            .execute(&mut *tx)
+            .await
+            .map_err(db_error)?;
+        }
+
+        tx.commit().await.map_err(db_error)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::DatabaseConnection;
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn test_save_and_load_graph() {
+        let (database, _dir) = DatabaseConnection::new_temp().await.unwrap();
+        let repository = SqliteDerivationRepository::new(database.pool());
+
+        let project_id = ProjectId::new();
+        let document_id = DocumentId::new();
+        let empty = repository
+            .find_by_document(&project_id, &document_id)
+            .await
+            .unwrap();
+        assert!(empty.steps().is_empty());
+
+        let summary = FamilyMember::derivative("summary.det").unwrap();
+        let mut graph = DerivationGraph::new(project_id.clone(), document_id.clone());
+        graph
+            .record(
+                DerivationStep::new(
+                    FamilyMember::Original,
+                    FamilyMember::extracted(),
+                    "extraction",
+                    json!({ "method": "pdf-text" }),
+                    "corpus-review 0.1.0",
+                )
+                .unwrap(),
+            )
+            .unwrap();
+        graph
+            .record(
+                DerivationStep::new(
+                    FamilyMember::extracted(),
+                    summary.clone(),
+                    "summary",
+                    json!({ "maxWords": 200 }),
+                    "summarizer 2",
+                )
+                .unwrap(),
+            )
+            .unwrap();
+        repository.save(&graph).await.unwrap();
+
+        let loaded = repository
+            .find_by_document(&project_id, &document_id)
+            .await
+            .unwrap();
+        assert_eq!(loaded.steps().len(), 2);
+        assert_eq!(loaded.steps()[1].child(), &summary);
+        assert_eq!(loaded.steps()[1].parameters(), &json!({ "maxWords": 200 }));
+        assert_eq!(loaded.generation(&summary), 2);
+    }
+}
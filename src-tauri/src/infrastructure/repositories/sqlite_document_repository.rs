@@ -0,0 +1,249 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::{Row, Sqlite, SqlitePool};
+use std::sync::Arc;
+
+use crate::domain::document::{Document, DocumentId, DocumentRepository};
+use crate::domain::project::ProjectId;
+use crate::domain::workspace::repositories::RepositoryError;
+use crate::domain::workspace::value_objects::ContentHash;
+
+const DOCUMENT_COLUMNS: &str = "id, project_uuid, path, content_hash, size, modified_ns, missing, \
+     parent_id, created_at, updated_at";
+
+const UPSERT_DOCUMENT: &str = r#"
+    INSERT INTO documents (id, project_uuid, path, content_hash, size, modified_ns,
+                           missing, parent_id, created_at, updated_at)
+    VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)
+    ON CONFLICT(id) DO UPDATE SET
+        path = excluded.path,
+        content_hash = excluded.content_hash,
+        size = excluded.size,
+        modified_ns = excluded.modified_ns,
+        missing = excluded.missing,
+        updated_at = excluded.updated_at
+"#;
+
+/// SQLite implementation of the DocumentRepository trait
+pub struct SqliteDocumentRepository {
+    pool: Arc<SqlitePool>,
+}
+
+impl SqliteDocumentRepository {
+    /// Create a new SqliteDocumentRepository with the given connection pool
+    pub fn new(pool: Arc<SqlitePool>) -> Self {
+        SqliteDocumentRepository { pool }
+    }
+
+    fn upsert_query(
+        document: &Document,
+    ) -> sqlx::query::Query<'_, Sqlite, sqlx::sqlite::SqliteArguments<'_>> {
+        sqlx::query(UPSERT_DOCUMENT)
+            .bind(document.id().value())
+            .bind(document.project_id().value())
+            .bind(document.path())
+            .bind(document.content_hash().to_string())
+            .bind(document.size() as i64)
+            .bind(document.modified_ns())
+            .bind(document.is_missing())
+            .bind(document.parent_id().map(DocumentId::value))
+            .bind(document.created_at())
+            .bind(document.updated_at())
+    }
+
+    /// Convert database row to Document
+    fn row_to_document(row: &sqlx::sqlite::SqliteRow) -> Result<Document, RepositoryError> {
+        let db_error = |e: sqlx::Error| RepositoryError::DatabaseError(e.to_string());
+
+        let id: String = row.try_get("id").map_err(db_error)?;
+        let project_uuid: String = row.try_get("project_uuid").map_err(db_error)?;
+        let content_hash: String = row.try_get("content_hash").map_err(db_error)?;
+        let size: i64 = row.try_get("size").map_err(db_error)?;
+        let parent_id: Option<String> = row.try_get("parent_id").map_err(db_error)?;
+
+        Ok(Document::from_data(
+            DocumentId::from_string(id)
+                .map_err(|e| RepositoryError::ValidationError(e.to_string()))?,
+            ProjectId::from_string(project_uuid)
+                .map_err(|e| RepositoryError::ValidationError(e.to_string()))?,
+            row.try_get("path").map_err(db_error)?,
+            ContentHash::parse(&content_hash).map_err(RepositoryError::ValidationError)?,
+            size.max(0) as u64,
+            row.try_get("modified_ns").map_err(db_error)?,
+            row.try_get("missing").map_err(db_error)?,
+            parent_id
+                .map(DocumentId::from_string)
+                .transpose()
+                .map_err(|e| RepositoryError::ValidationError(e.to_string()))?,
+            row.try_get::<DateTime<Utc>, _>("created_at")
+                .map_err(db_error)?,
+            row.try_get::<DateTime<Utc>, _>("updated_at")
+                .map_err(db_error)?,
+        ))
+    }
+}
+
+#[async_trait]
+impl DocumentRepository for SqliteDocumentRepository {
+    async fn save(&self, document: &Document) -> Result<(), RepositoryError> {
+        Self::upsert_query(document)
+            .execute(&*self.pool)
+            .await
+            .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+
+        Ok(())
+    }
+
+    async fn save_all(&self, documents: &[Document]) -> Result<(), RepositoryError> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+
+        for document in documents {
+            Self::upsert_query(document)
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+        }
+
+        tx.commit()
+            .await
+            .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+
+        Ok(())
+    }
+
+    async fn find_by_id(&self, id: &DocumentId) -> Result<Option<Document>, RepositoryError> {
+        let query = format!("SELECT {} FROM documents WHERE id = ?1", DOCUMENT_COLUMNS);
+
+        let row = sqlx::query(&query)
+            .bind(id.value())
+            .fetch_optional(&*self.pool)
+            .await
+            .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+
+        row.as_ref().map(Self::row_to_document).transpose()
+    }
+
+    async fn find_by_path(
+        &self,
+        project_id: &ProjectId,
+        path: &str,
+    ) -> Result<Option<Document>, RepositoryError> {
+        let query = format!(
+            "SELECT {} FROM documents WHERE project_uuid = ?1 AND path = ?2 \
+             ORDER BY missing ASC, updated_at DESC LIMIT 1",
+            DOCUMENT_COLUMNS
+        );
+
+        let row = sqlx::query(&query)
+            .bind(project_id.value())
+            .bind(path)
+            .fetch_optional(&*self.pool)
+            .await
+            .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+
+        row.as_ref().map(Self::row_to_document).transpose()
+    }
+
+    async fn list_by_project(
+        &self,
+        project_id: &ProjectId,
+    ) -> Result<Vec<Document>, RepositoryError> {
+        let query = format!(
+            "SELECT {} FROM documents WHERE project_uuid = ?1 ORDER BY path ASC",
+            DOCUMENT_COLUMNS
+        );
+
+        let rows = sqlx::query(&query)
+            .bind(project_id.value())
+            .fetch_all(&*self.pool)
+            .await
+            .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+
+        rows.iter().map(Self::row_to_document).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::workspace::value_objects::HashAlgorithm;
+    use crate::infrastructure::DatabaseConnection;
+
+    fn create_test_document(project_id: &ProjectId, path: &str) -> Document {
+        Document::new(
+            project_id.clone(),
+            path.to_string(),
+            ContentHash::of_bytes(HashAlgorithm::Blake3, path.as_bytes()),
+            10,
+            1_000,
+        )
+    }
+
+    #[tokio::test]
+    async fn test_save_and_find_document() {
+        let (connection, _temp_dir) = DatabaseConnection::new_temp().await.unwrap();
+        let repository = SqliteDocumentRepository::new(connection.pool());
+        let project_id = ProjectId::new();
+
+        let mut document = create_test_document(&project_id, "/corpus/a.pdf");
+        repository.save(&document).await.unwrap();
+
+        let loaded = repository.find_by_id(document.id()).await.unwrap().unwrap();
+        assert_eq!(loaded.path(), "/corpus/a.pdf");
+        assert_eq!(loaded.content_hash(), document.content_hash());
+
+        document.relink("/corpus/moved/a.pdf".to_string(), 10, 2_000);
+        repository.save(&document).await.unwrap();
+
+        assert!(repository
+            .find_by_path(&project_id, "/corpus/a.pdf")
+            .await
+            .unwrap()
+            .is_none());
+        let moved = repository
+            .find_by_path(&project_id, "/corpus/moved/a.pdf")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(moved.id(), document.id());
+        assert_eq!(moved.modified_ns(), 2_000);
+    }
+
+    #[tokio::test]
+    async fn test_save_all_and_list_by_project() {
+        let (connection, _temp_dir) = DatabaseConnection::new_temp().await.unwrap();
+        let repository = SqliteDocumentRepository::new(connection.pool());
+        let project_id = ProjectId::new();
+        let other_project = ProjectId::new();
+
+        let mut missing = create_test_document(&project_id, "/corpus/b.pdf");
+        missing.mark_missing();
+        let email = create_test_document(&project_id, "/corpus/mail.eml");
+        let attachment = Document::child(
+            &email,
+            "/derivatives/a/attachments/0-0/c.pdf".to_string(),
+            ContentHash::of_bytes(HashAlgorithm::Blake3, b"c"),
+            1,
+            1_000,
+        );
+        let documents = vec![
+            missing,
+            create_test_document(&project_id, "/corpus/a.pdf"),
+            email.clone(),
+            attachment,
+            create_test_document(&other_project, "/other/c.pdf"),
+        ];
+        repository.save_all(&documents).await.unwrap();
+
+        let listed = repository.list_by_project(&project_id).await.unwrap();
+        assert_eq!(listed.len(), 4);
+        assert_eq!(listed[0].path(), "/corpus/a.pdf");
+        assert!(listed[1].is_missing());
+        assert_eq!(listed[2].parent_id(), None);
+        assert_eq!(listed[3].parent_id(), Some(email.id()));
+    }
+}
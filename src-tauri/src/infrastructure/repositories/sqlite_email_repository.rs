@@ -0,0 +1,205 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::{Row, SqlitePool};
+use std::sync::Arc;
+
+use crate::domain::document::DocumentId;
+use crate::domain::email::{EmailHeaders, EmailMessage, EmailRepository};
+use crate::domain::project::ProjectId;
+use crate::domain::workspace::repositories::RepositoryError;
+
+const EMAIL_COLUMNS: &str = "document_id, message_index, project_uuid, content_hash, method, \
+     headers, attachment_ids, parsed_at";
+
+/// SQLite implementation of the EmailRepository trait
+///
+/// Headers and attachment ids are stored as JSON; the message id and
+/// sending date also get their own columns for lookups and ordering.
+pub struct SqliteEmailRepository {
+    pool: Arc<SqlitePool>,
+}
+
+impl SqliteEmailRepository {
+    /// Create a new SqliteEmailRepository with the given connection pool
+    pub fn new(pool: Arc<SqlitePool>) -> Self {
+        SqliteEmailRepository { pool }
+    }
+
+    /// Convert database row to EmailMessage
+    fn row_to_message(row: &sqlx::sqlite::SqliteRow) -> Result<EmailMessage, RepositoryError> {
+        let db_error = |e: sqlx::Error| RepositoryError::DatabaseError(e.to_string());
+        let invalid = |e: String| RepositoryError::ValidationError(e);
+
+        let document_id: String = row.try_get("document_id").map_err(db_error)?;
+        let project_uuid: String = row.try_get("project_uuid").map_err(db_error)?;
+        let index: i64 = row.try_get("message_index").map_err(db_error)?;
+        let headers: String = row.try_get("headers").map_err(db_error)?;
+        let attachment_ids: String = row.try_get("attachment_ids").map_err(db_error)?;
+        let attachment_ids = serde_json::from_str::<Vec<String>>(&attachment_ids)?
+            .into_iter()
+            .map(|id| DocumentId::from_string(id).map_err(|e| invalid(e.to_string())))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(EmailMessage::from_data(
+            DocumentId::from_string(document_id).map_err(|e| invalid(e.to_string()))?,
+            ProjectId::from_string(project_uuid).map_err(|e| invalid(e.to_string()))?,
+            index.max(0) as u32,
+            row.try_get("content_hash").map_err(db_error)?,
+            row.try_get("method").map_err(db_error)?,
+            serde_json::from_str::<EmailHeaders>(&headers)?,
+            attachment_ids,
+            row.try_get::<DateTime<Utc>, _>("parsed_at")
+                .map_err(db_error)?,
+        ))
+    }
+}
+
+#[async_trait]
+impl EmailRepository for SqliteEmailRepository {
+    async fn replace_messages(
+        &self,
+        document_id: &DocumentId,
+        messages: &[EmailMessage],
+    ) -> Result<(), RepositoryError> {
+        let db_error = |e: sqlx::Error| RepositoryError::DatabaseError(e.to_string());
+
+        let mut tx = self.pool.begin().await.map_err(db_error)?;
+        sqlx::query("DELETE FROM email_messages WHERE document_id = ?1")
+            .bind(document_id.value())
+            .execute(&mut *tx)
+            .await
+            .map_err(db_error)?;
+        for message in messages {
+            let attachment_ids: Vec<&str> = message
+                .attachment_ids()
+                .iter()
+                .map(|id| id.value())
+                .collect();
+            sqlx::query(
+                "INSERT INTO email_messages (document_id, message_index, project_uuid, \
+                 content_hash, method, headers, message_id, sent_at, attachment_ids, parsed_at) \
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+            )
+            .bind(message.document_id().value())
+            .bind(i64::from(message.index()))
+            .bind(message.project_id().value())
+            .bind(message.content_hash())
+            .bind(message.method())
+            .bind(serde_json::to_string(message.headers())?)
+            .bind(&message.headers().message_id)
+            .bind(message.headers().date)
+            .bind(serde_json::to_string(&attachment_ids)?)
+            .bind(message.parsed_at())
+            .execute(&mut *tx)
+            .await
+            .map_err(db_error)?;
+        }
+        tx.commit().await.map_err(db_error)?;
+
+        Ok(())
+    }
+
+    async fn find_by_document(
+        &self,
+        document_id: &DocumentId,
+    ) -> Result<Vec<EmailMessage>, RepositoryError> {
+        let query = format!(
+            "SELECT {} FROM email_messages WHERE document_id = ?1 ORDER BY message_index",
+            EMAIL_COLUMNS
+        );
+
+        let rows = sqlx::query(&query)
+            .bind(document_id.value())
+            .fetch_all(&*self.pool)
+            .await
+            .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+
+        rows.iter().map(Self::row_to_message).collect()
+    }
+
+    async fn list_by_project(
+        &self,
+        project_id: &ProjectId,
+    ) -> Result<Vec<EmailMessage>, RepositoryError> {
+        let query = format!(
+            "SELECT {} FROM email_messages WHERE project_uuid = ?1 \
+             ORDER BY sent_at IS NULL, sent_at, document_id, message_index",
+            EMAIL_COLUMNS
+        );
+
+        let rows = sqlx::query(&query)
+            .bind(project_id.value())
+            .fetch_all(&*self.pool)
+            .await
+            .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+
+        rows.iter().map(Self::row_to_message).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::DatabaseConnection;
+    use chrono::TimeZone;
+
+    fn message(
+        document_id: &DocumentId,
+        project_id: &ProjectId,
+        index: u32,
+        day: Option<u32>,
+    ) -> EmailMessage {
+        EmailMessage::new(
+            document_id.clone(),
+            project_id.clone(),
+            index,
+            "blake3:abc".to_string(),
+            "email-parser-v1",
+            EmailHeaders {
+                from: Some("Ana <ana@example.com>".to_string()),
+                to: vec!["ben@example.com".to_string()],
+                date: day.map(|day| Utc.with_ymd_and_hms(2024, 3, day, 9, 0, 0).unwrap()),
+                subject: Some(format!("Message {}", index)),
+                message_id: Some(format!("{}@example.com", index)),
+                ..EmailHeaders::default()
+            },
+            vec![DocumentId::new()],
+        )
+    }
+
+    #[tokio::test]
+    async fn test_replace_find_and_list_messages() {
+        let (database, _dir) = DatabaseConnection::new_temp().await.unwrap();
+        let repository = SqliteEmailRepository::new(database.pool());
+        let project_id = ProjectId::new();
+        let mbox = DocumentId::new();
+        let eml = DocumentId::new();
+
+        repository
+            .replace_messages(&mbox, &[message(&mbox, &project_id, 0, Some(9))])
+            .await
+            .unwrap();
+        let messages = vec![
+            message(&mbox, &project_id, 0, Some(4)),
+            message(&mbox, &project_id, 1, None),
+        ];
+        repository.replace_messages(&mbox, &messages).await.unwrap();
+        let single = message(&eml, &project_id, 0, Some(2));
+        repository
+            .replace_messages(&eml, std::slice::from_ref(&single))
+            .await
+            .unwrap();
+
+        let found = repository.find_by_document(&mbox).await.unwrap();
+        assert_eq!(found, messages);
+
+        let listed = repository.list_by_project(&project_id).await.unwrap();
+        let keys: Vec<String> = listed.iter().map(EmailMessage::key).collect();
+        assert_eq!(keys, [single.key(), messages[0].key(), messages[1].key()]);
+        assert!(repository
+            .list_by_project(&ProjectId::new())
+            .await
+            .unwrap()
+            .is_empty());
+    }
+}
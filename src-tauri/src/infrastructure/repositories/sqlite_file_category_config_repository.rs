@@ -0,0 +1,101 @@
+use async_trait::async_trait;
+use sqlx::{Row, SqlitePool};
+use std::sync::Arc;
+
+use crate::domain::project::ProjectId;
+use crate::domain::workspace::repositories::{FileCategoryConfigRepository, RepositoryError};
+use crate::domain::workspace::value_objects::FileCategoryConfig;
+
+/// SQLite implementation of the FileCategoryConfigRepository trait
+///
+/// Each project's configuration is stored as a single JSON document so new
+/// settings can be added without further migrations.
+pub struct SqliteFileCategoryConfigRepository {
+    pool: Arc<SqlitePool>,
+}
+
+impl SqliteFileCategoryConfigRepository {
+    /// Create a new repository with the given connection pool
+    pub fn new(pool: Arc<SqlitePool>) -> Self {
+        SqliteFileCategoryConfigRepository { pool }
+    }
+}
+
+#[async_trait]
+impl FileCategoryConfigRepository for SqliteFileCategoryConfigRepository {
+    async fn load(&self, project_id: &ProjectId) -> Result<FileCategoryConfig, RepositoryError> {
+        let query = "SELECT config_json FROM file_category_configs WHERE project_uuid = ?1";
+
+        let row = sqlx::query(query)
+            .bind(project_id.value())
+            .fetch_optional(&*self.pool)
+            .await
+            .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+
+        match row {
+            Some(row) => {
+                let json: String = row
+                    .try_get("config_json")
+                    .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+                Ok(serde_json::from_str(&json)?)
+            }
+            None => Ok(FileCategoryConfig::default()),
+        }
+    }
+
+    async fn save(
+        &self,
+        project_id: &ProjectId,
+        config: &FileCategoryConfig,
+    ) -> Result<(), RepositoryError> {
+        let query = r#"
+            INSERT INTO file_category_configs (project_uuid, config_json, updated_at)
+            VALUES (?1, ?2, CURRENT_TIMESTAMP)
+            ON CONFLICT(project_uuid) DO UPDATE SET
+                config_json = excluded.config_json,
+                updated_at = excluded.updated_at
+        "#;
+
+        sqlx::query(query)
+            .bind(project_id.value())
+            .bind(serde_json::to_string(config)?)
+            .execute(&*self.pool)
+            .await
+            .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::workspace::value_objects::FileCategory;
+    use crate::infrastructure::DatabaseConnection;
+
+    #[tokio::test]
+    async fn test_load_returns_default_when_missing() {
+        let (connection, _temp_dir) = DatabaseConnection::new_temp().await.unwrap();
+        let repository = SqliteFileCategoryConfigRepository::new(connection.pool());
+
+        let config = repository.load(&ProjectId::new()).await.unwrap();
+        assert_eq!(config, FileCategoryConfig::default());
+    }
+
+    #[tokio::test]
+    async fn test_save_and_load_round_trip() {
+        let (connection, _temp_dir) = DatabaseConnection::new_temp().await.unwrap();
+        let repository = SqliteFileCategoryConfigRepository::new(connection.pool());
+        let project_id = ProjectId::new();
+
+        let config = FileCategoryConfig::default().with_override("dat", FileCategory::Audio);
+        repository.save(&project_id, &config).await.unwrap();
+
+        let loaded = repository.load(&project_id).await.unwrap();
+        assert_eq!(loaded, config);
+
+        let updated = loaded.with_override("dat", FileCategory::Video);
+        repository.save(&project_id, &updated).await.unwrap();
+        assert_eq!(repository.load(&project_id).await.unwrap(), updated);
+    }
+}
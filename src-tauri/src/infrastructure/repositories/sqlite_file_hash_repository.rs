@@ -0,0 +1,203 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::{QueryBuilder, Row, Sqlite, SqlitePool};
+use std::sync::Arc;
+
+use crate::domain::workspace::repositories::{FileHashRepository, RepositoryError};
+use crate::domain::workspace::value_objects::{ContentHash, FileHashRecord, HashAlgorithm};
+
+/// SQLite implementation of the FileHashRepository trait
+pub struct SqliteFileHashRepository {
+    pool: Arc<SqlitePool>,
+}
+
+impl SqliteFileHashRepository {
+    /// Create a new repository with the given connection pool
+    pub fn new(pool: Arc<SqlitePool>) -> Self {
+        SqliteFileHashRepository { pool }
+    }
+
+    /// Convert database row to FileHashRecord
+    fn row_to_record(
+        row: &sqlx::sqlite::SqliteRow,
+        algorithm: HashAlgorithm,
+    ) -> Result<FileHashRecord, RepositoryError> {
+        let db_error = |e: sqlx::Error| RepositoryError::DatabaseError(e.to_string());
+
+        let digest: String = row.try_get("digest").map_err(db_error)?;
+        let size: i64 = row.try_get("size").map_err(db_error)?;
+
+        Ok(FileHashRecord {
+            path: row.try_get("path").map_err(db_error)?,
+            size: size.max(0) as u64,
+            modified_ns: row.try_get("modified_ns").map_err(db_error)?,
+            hash: ContentHash::new(algorithm, digest).map_err(RepositoryError::ValidationError)?,
+            computed_at: row
+                .try_get::<DateTime<Utc>, _>("computed_at")
+                .map_err(db_error)?,
+        })
+    }
+}
+
+#[async_trait]
+impl FileHashRepository for SqliteFileHashRepository {
+    async fn find(
+        &self,
+        path: &str,
+        algorithm: HashAlgorithm,
+    ) -> Result<Option<FileHashRecord>, RepositoryError> {
+        let query = r#"
+            SELECT path, size, modified_ns, digest, computed_at
+            FROM file_hashes
+            WHERE path = ?1 AND algorithm = ?2
+        "#;
+
+        let row = sqlx::query(query)
+            .bind(path)
+            .bind(algorithm.as_str())
+            .fetch_optional(&*self.pool)
+            .await
+            .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+
+        row.map(|row| Self::row_to_record(&row, algorithm))
+            .transpose()
+    }
+
+    async fn find_many(
+        &self,
+        paths: &[String],
+        algorithm: HashAlgorithm,
+    ) -> Result<Vec<FileHashRecord>, RepositoryError> {
+        let mut records = Vec::new();
+
+        // Stay well below SQLite's bound parameter limit
+        for chunk in paths.chunks(500) {
+            let mut builder: QueryBuilder<Sqlite> = QueryBuilder::new(
+                "SELECT path, size, modified_ns, digest, computed_at FROM file_hashes WHERE algorithm = ",
+            );
+            builder.push_bind(algorithm.as_str());
+            builder.push(" AND path IN (");
+            let mut separated = builder.separated(", ");
+            for path in chunk {
+                separated.push_bind(path.clone());
+            }
+            separated.push_unseparated(")");
+
+            let rows = builder
+                .build()
+                .fetch_all(&*self.pool)
+                .await
+                .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+
+            for row in &rows {
+                records.push(Self::row_to_record(row, algorithm)?);
+            }
+        }
+
+        Ok(records)
+    }
+
+    async fn save(&self, record: &FileHashRecord) -> Result<(), RepositoryError> {
+        let query = r#"
+            INSERT INTO file_hashes (path, algorithm, size, modified_ns, digest, computed_at)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+            ON CONFLICT(path, algorithm) DO UPDATE SET
+                size = excluded.size,
+                modified_ns = excluded.modified_ns,
+                digest = excluded.digest,
+                computed_at = excluded.computed_at
+        "#;
+
+        sqlx::query(query)
+            .bind(&record.path)
+            .bind(record.hash.algorithm().as_str())
+            .bind(record.size as i64)
+            .bind(record.modified_ns)
+            .bind(record.hash.digest())
+            .bind(record.computed_at)
+            .execute(&*self.pool)
+            .await
+            .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::DatabaseConnection;
+
+    #[tokio::test]
+    async fn test_save_find_and_replace() {
+        let (connection, _temp_dir) = DatabaseConnection::new_temp().await.unwrap();
+        let repository = SqliteFileHashRepository::new(connection.pool());
+
+        let hash = ContentHash::of_bytes(HashAlgorithm::Blake3, b"one");
+        let record = FileHashRecord::new("/corpus/a.pdf".to_string(), 3, 1_000, hash);
+        repository.save(&record).await.unwrap();
+
+        let loaded = repository
+            .find("/corpus/a.pdf", HashAlgorithm::Blake3)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(loaded.hash, record.hash);
+        assert!(loaded.is_current(3, 1_000));
+
+        // Other algorithms are cached separately
+        assert!(repository
+            .find("/corpus/a.pdf", HashAlgorithm::Sha256)
+            .await
+            .unwrap()
+            .is_none());
+
+        let changed = FileHashRecord::new(
+            "/corpus/a.pdf".to_string(),
+            4,
+            2_000,
+            ContentHash::of_bytes(HashAlgorithm::Blake3, b"four"),
+        );
+        repository.save(&changed).await.unwrap();
+
+        let loaded = repository
+            .find("/corpus/a.pdf", HashAlgorithm::Blake3)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(loaded.hash, changed.hash);
+        assert!(!loaded.is_current(3, 1_000));
+    }
+
+    #[tokio::test]
+    async fn test_find_many() {
+        let (connection, _temp_dir) = DatabaseConnection::new_temp().await.unwrap();
+        let repository = SqliteFileHashRepository::new(connection.pool());
+
+        for name in ["a", "b", "c"] {
+            let record = FileHashRecord::new(
+                format!("/corpus/{}", name),
+                1,
+                1,
+                ContentHash::of_bytes(HashAlgorithm::Sha256, name.as_bytes()),
+            );
+            repository.save(&record).await.unwrap();
+        }
+
+        let found = repository
+            .find_many(
+                &[
+                    "/corpus/a".to_string(),
+                    "/corpus/c".to_string(),
+                    "/corpus/missing".to_string(),
+                ],
+                HashAlgorithm::Sha256,
+            )
+            .await
+            .unwrap();
+
+        let mut paths: Vec<_> = found.iter().map(|r| r.path.as_str()).collect();
+        paths.sort();
+        assert_eq!(paths, vec!["/corpus/a", "/corpus/c"]);
+    }
+}
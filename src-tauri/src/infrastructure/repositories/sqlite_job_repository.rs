@@ -0,0 +1,242 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::{QueryBuilder, Row, Sqlite, SqlitePool};
+use std::sync::Arc;
+
+use crate::application::jobs::{
+    JobFilter, JobId, JobProgress, JobRecord, JobRepository, JobStatus,
+};
+use crate::domain::workspace::repositories::RepositoryError;
+
+const JOB_COLUMNS: &str = "id, kind, project_uuid, params_json, status, idempotent, \
+     progress_current, progress_total, progress_message, result_json, error, attempts, \
+     created_at, started_at, finished_at";
+
+/// SQLite implementation of the JobRepository trait
+pub struct SqliteJobRepository {
+    pool: Arc<SqlitePool>,
+}
+
+impl SqliteJobRepository {
+    /// Create a new SqliteJobRepository with the given connection pool
+    pub fn new(pool: Arc<SqlitePool>) -> Self {
+        SqliteJobRepository { pool }
+    }
+
+    /// Convert database row to JobRecord
+    fn row_to_job(row: &sqlx::sqlite::SqliteRow) -> Result<JobRecord, RepositoryError> {
+        let db_error = |e: sqlx::Error| RepositoryError::DatabaseError(e.to_string());
+
+        let id: String = row.try_get("id").map_err(db_error)?;
+        let status: String = row.try_get("status").map_err(db_error)?;
+        let params_json: String = row.try_get("params_json").map_err(db_error)?;
+        let result_json: Option<String> = row.try_get("result_json").map_err(db_error)?;
+        let progress_current: i64 = row.try_get("progress_current").map_err(db_error)?;
+        let progress_total: Option<i64> = row.try_get("progress_total").map_err(db_error)?;
+        let attempts: i64 = row.try_get("attempts").map_err(db_error)?;
+
+        Ok(JobRecord {
+            id: JobId::from_string(id).map_err(RepositoryError::ValidationError)?,
+            kind: row.try_get("kind").map_err(db_error)?,
+            project_id: row.try_get("project_uuid").map_err(db_error)?,
+            params: serde_json::from_str(&params_json)?,
+            status: JobStatus::from_str(&status).map_err(RepositoryError::ValidationError)?,
+            idempotent: row.try_get("idempotent").map_err(db_error)?,
+            progress: JobProgress {
+                current: progress_current.max(0) as u64,
+                total: progress_total.map(|total| total.max(0) as u64),
+                message: row.try_get("progress_message").map_err(db_error)?,
+            },
+            result: result_json
+                .map(|json| serde_json::from_str(&json))
+                .transpose()?,
+            error: row.try_get("error").map_err(db_error)?,
+            attempts: attempts.max(0) as u32,
+            created_at: row
+                .try_get::<DateTime<Utc>, _>("created_at")
+                .map_err(db_error)?,
+            started_at: row.try_get("started_at").map_err(db_error)?,
+            finished_at: row.try_get("finished_at").map_err(db_error)?,
+        })
+    }
+}
+
+#[async_trait]
+impl JobRepository for SqliteJobRepository {
+    async fn save(&self, job: &JobRecord) -> Result<(), RepositoryError> {
+        let query = r#"
+            INSERT INTO jobs (id, kind, project_uuid, params_json, status, idempotent,
+                              progress_current, progress_total, progress_message,
+                              result_json, error, attempts, created_at, started_at, finished_at)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15)
+            ON CONFLICT(id) DO UPDATE SET
+                status = excluded.status,
+                progress_current = excluded.progress_current,
+                progress_total = excluded.progress_total,
+                progress_message = excluded.progress_message,
+                result_json = excluded.result_json,
+                error = excluded.error,
+                attempts = excluded.attempts,
+                started_at = excluded.started_at,
+                finished_at = excluded.finished_at
+        "#;
+
+        let result_json = job.result.as_ref().map(serde_json::to_string).transpose()?;
+
+        sqlx::query(query)
+            .bind(job.id.as_str())
+            .bind(&job.kind)
+            .bind(&job.project_id)
+            .bind(serde_json::to_string(&job.params)?)
+            .bind(job.status.as_str())
+            .bind(job.idempotent)
+            .bind(job.progress.current as i64)
+            .bind(job.progress.total.map(|total| total as i64))
+            .bind(&job.progress.message)
+            .bind(result_json)
+            .bind(&job.error)
+            .bind(i64::from(job.attempts))
+            .bind(job.created_at)
+            .bind(job.started_at)
+            .bind(job.finished_at)
+            .execute(&*self.pool)
+            .await
+            .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+
+        Ok(())
+    }
+
+    async fn find_by_id(&self, id: &JobId) -> Result<Option<JobRecord>, RepositoryError> {
+        let query = format!("SELECT {} FROM jobs WHERE id = ?1", JOB_COLUMNS);
+
+        let row = sqlx::query(&query)
+            .bind(id.as_str())
+            .fetch_optional(&*self.pool)
+            .await
+            .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+
+        row.as_ref().map(Self::row_to_job).transpose()
+    }
+
+    async fn list(&self, filter: &JobFilter) -> Result<Vec<JobRecord>, RepositoryError> {
+        let mut builder: QueryBuilder<Sqlite> =
+            QueryBuilder::new(format!("SELECT {} FROM jobs WHERE 1 = 1", JOB_COLUMNS));
+
+        if let Some(status) = filter.status {
+            builder.push(" AND status = ").push_bind(status.as_str());
+        }
+        if let Some(project_id) = &filter.project_id {
+            builder
+                .push(" AND project_uuid = ")
+                .push_bind(project_id.clone());
+        }
+        builder.push(" ORDER BY created_at DESC");
+        if let Some(limit) = filter.limit {
+            builder.push(" LIMIT ").push_bind(limit as i64);
+        }
+
+        let rows = builder
+            .build()
+            .fetch_all(&*self.pool)
+            .await
+            .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+
+        rows.iter().map(Self::row_to_job).collect()
+    }
+
+    async fn find_unfinished(&self) -> Result<Vec<JobRecord>, RepositoryError> {
+        let query = format!(
+            "SELECT {} FROM jobs WHERE status IN ('queued', 'running') ORDER BY created_at ASC",
+            JOB_COLUMNS
+        );
+
+        let rows = sqlx::query(&query)
+            .fetch_all(&*self.pool)
+            .await
+            .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+
+        rows.iter().map(Self::row_to_job).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::DatabaseConnection;
+
+    #[tokio::test]
+    async fn test_save_and_find_job() {
+        let (connection, _temp_dir) = DatabaseConnection::new_temp().await.unwrap();
+        let repository = SqliteJobRepository::new(connection.pool());
+
+        let mut job = JobRecord::new(
+            "hash_project",
+            Some("proj_123".to_string()),
+            serde_json::json!({ "algorithm": "blake3" }),
+            true,
+        );
+        repository.save(&job).await.unwrap();
+
+        job.mark_running();
+        job.progress = JobProgress {
+            current: 4,
+            total: Some(8),
+            message: Some("Hashing".to_string()),
+        };
+        repository.save(&job).await.unwrap();
+
+        let loaded = repository.find_by_id(&job.id).await.unwrap().unwrap();
+        assert_eq!(loaded.status, JobStatus::Running);
+        assert_eq!(loaded.progress, job.progress);
+        assert_eq!(loaded.params, job.params);
+        assert_eq!(loaded.attempts, 1);
+        assert!(loaded.idempotent);
+    }
+
+    #[tokio::test]
+    async fn test_list_and_find_unfinished() {
+        let (connection, _temp_dir) = DatabaseConnection::new_temp().await.unwrap();
+        let repository = SqliteJobRepository::new(connection.pool());
+
+        let queued = JobRecord::new(
+            "a",
+            Some("proj_1".to_string()),
+            serde_json::Value::Null,
+            false,
+        );
+        let mut done = JobRecord::new(
+            "b",
+            Some("proj_2".to_string()),
+            serde_json::Value::Null,
+            false,
+        );
+        done.mark_running();
+        done.mark_succeeded(serde_json::json!(42));
+
+        repository.save(&queued).await.unwrap();
+        repository.save(&done).await.unwrap();
+
+        let unfinished = repository.find_unfinished().await.unwrap();
+        assert_eq!(unfinished.len(), 1);
+        assert_eq!(unfinished[0].id, queued.id);
+
+        let for_project = repository
+            .list(&JobFilter {
+                project_id: Some("proj_2".to_string()),
+                ..JobFilter::default()
+            })
+            .await
+            .unwrap();
+        assert_eq!(for_project.len(), 1);
+        assert_eq!(for_project[0].result, Some(serde_json::json!(42)));
+
+        let limited = repository
+            .list(&JobFilter {
+                limit: Some(1),
+                ..JobFilter::default()
+            })
+            .await
+            .unwrap();
+        assert_eq!(limited.len(), 1);
+    }
+}
@@ -0,0 +1,251 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::{Row, SqlitePool};
+use std::sync::Arc;
+
+use crate::domain::project::ProjectId;
+use crate::domain::workspace::entities::{
+    ManifestEntry, ManifestSnapshot, ManifestSnapshotSummary,
+};
+use crate::domain::workspace::repositories::{ManifestSnapshotRepository, RepositoryError};
+use crate::domain::workspace::value_objects::{ContentHash, HashAlgorithm, SnapshotId};
+
+/// SQLite implementation of the ManifestSnapshotRepository trait
+///
+/// Snapshot metadata lives in `manifest_snapshots`; entries are stored one
+/// row per file in `manifest_entries` so large productions do not have to
+/// be loaded as a single JSON document.
+pub struct SqliteManifestSnapshotRepository {
+    pool: Arc<SqlitePool>,
+}
+
+impl SqliteManifestSnapshotRepository {
+    /// Create a new repository with the given connection pool
+    pub fn new(pool: Arc<SqlitePool>) -> Self {
+        SqliteManifestSnapshotRepository { pool }
+    }
+
+    /// Convert database row to ManifestSnapshotSummary
+    fn row_to_summary(
+        row: &sqlx::sqlite::SqliteRow,
+    ) -> Result<ManifestSnapshotSummary, RepositoryError> {
+        let db_error = |e: sqlx::Error| RepositoryError::DatabaseError(e.to_string());
+
+        let id: String = row.try_get("id").map_err(db_error)?;
+        let project_uuid: String = row.try_get("project_uuid").map_err(db_error)?;
+        let algorithm: String = row.try_get("algorithm").map_err(db_error)?;
+        let file_count: i64 = row.try_get("file_count").map_err(db_error)?;
+        let total_size: i64 = row.try_get("total_size").map_err(db_error)?;
+
+        Ok(ManifestSnapshotSummary {
+            id: SnapshotId::from_string(id).map_err(RepositoryError::ValidationError)?,
+            project_id: ProjectId::from_string(project_uuid)
+                .map_err(|e| RepositoryError::ValidationError(e.to_string()))?,
+            label: row.try_get("label").map_err(db_error)?,
+            algorithm: HashAlgorithm::from_str(&algorithm)
+                .map_err(RepositoryError::ValidationError)?,
+            file_count: file_count.max(0) as usize,
+            total_size: total_size.max(0) as u64,
+            created_at: row
+                .try_get::<DateTime<Utc>, _>("created_at")
+                .map_err(db_error)?,
+        })
+    }
+}
+
+#[async_trait]
+impl ManifestSnapshotRepository for SqliteManifestSnapshotRepository {
+    async fn save(&self, snapshot: &ManifestSnapshot) -> Result<(), RepositoryError> {
+        let db_error = |e: sqlx::Error| RepositoryError::DatabaseError(e.to_string());
+
+        let mut tx = self.pool.begin().await.map_err(db_error)?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO manifest_snapshots (id, project_uuid, label, algorithm, file_count,
+                                            total_size, created_at)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
+            "#,
+        )
+        .bind(snapshot.id().as_str())
+        .bind(snapshot.project_id().value())
+        .bind(snapshot.label())
+        .bind(snapshot.algorithm().as_str())
+        .bind(snapshot.file_count() as i64)
+        .bind(snapshot.total_size() as i64)
+        .bind(snapshot.created_at())
+        .execute(&mut *tx)
+        .await
+        .map_err(db_error)?;
+
+        for entry in snapshot.entries() {
+            sqlx::query(
+                r#"
+                INSERT INTO manifest_entries (snapshot_id, path, size, modified_ns, digest)
+                VALUES (?1, ?2, ?3, ?4, ?5)
+                "#,
+            )
+            .bind(snapshot.id().as_str())
+            .bind(&entry.path)
+            .bind(entry.size as i64)
+            .bind(entry.modified_ns)
+            .bind(entry.hash.digest())
+            .execute(&mut *tx)
+            .await
+            .map_err(db_error)?;
+        }
+
+        tx.commit().await.map_err(db_error)?;
+
+        Ok(())
+    }
+
+    async fn find_by_id(
+        &self,
+        id: &SnapshotId,
+    ) -> Result<Option<ManifestSnapshot>, RepositoryError> {
+        let db_error = |e: sqlx::Error| RepositoryError::DatabaseError(e.to_string());
+
+        let row = sqlx::query(
+            r#"
+            SELECT id, project_uuid, label, algorithm, file_count, total_size, created_at
+            FROM manifest_snapshots
+            WHERE id = ?1
+            "#,
+        )
+        .bind(id.as_str())
+        .fetch_optional(&*self.pool)
+        .await
+        .map_err(db_error)?;
+
+        let Some(row) = row else {
+            return Ok(None);
+        };
+        let summary = Self::row_to_summary(&row)?;
+
+        let rows = sqlx::query(
+            r#"
+            SELECT path, size, modified_ns, digest
+            FROM manifest_entries
+            WHERE snapshot_id = ?1
+            ORDER BY path ASC
+            "#,
+        )
+        .bind(id.as_str())
+        .fetch_all(&*self.pool)
+        .await
+        .map_err(db_error)?;
+
+        let mut entries = Vec::with_capacity(rows.len());
+        for row in &rows {
+            let size: i64 = row.try_get("size").map_err(db_error)?;
+            let digest: String = row.try_get("digest").map_err(db_error)?;
+            entries.push(ManifestEntry {
+                path: row.try_get("path").map_err(db_error)?,
+                size: size.max(0) as u64,
+                modified_ns: row.try_get("modified_ns").map_err(db_error)?,
+                hash: ContentHash::new(summary.algorithm, digest)
+                    .map_err(RepositoryError::ValidationError)?,
+            });
+        }
+
+        Ok(Some(ManifestSnapshot::from_data(
+            summary.id,
+            summary.project_id,
+            summary.label,
+            summary.algorithm,
+            entries,
+            summary.created_at,
+        )))
+    }
+
+    async fn list_by_project(
+        &self,
+        project_id: &ProjectId,
+    ) -> Result<Vec<ManifestSnapshotSummary>, RepositoryError> {
+        let rows = sqlx::query(
+            r#"
+            SELECT id, project_uuid, label, algorithm, file_count, total_size, created_at
+            FROM manifest_snapshots
+            WHERE project_uuid = ?1
+            ORDER BY created_at DESC
+            "#,
+        )
+        .bind(project_id.value())
+        .fetch_all(&*self.pool)
+        .await
+        .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+
+        rows.iter().map(Self::row_to_summary).collect()
+    }
+
+    async fn delete(&self, id: &SnapshotId) -> Result<bool, RepositoryError> {
+        let db_error = |e: sqlx::Error| RepositoryError::DatabaseError(e.to_string());
+
+        let mut tx = self.pool.begin().await.map_err(db_error)?;
+
+        sqlx::query("DELETE FROM manifest_entries WHERE snapshot_id = ?1")
+            .bind(id.as_str())
+            .execute(&mut *tx)
+            .await
+            .map_err(db_error)?;
+
+        let result = sqlx::query("DELETE FROM manifest_snapshots WHERE id = ?1")
+            .bind(id.as_str())
+            .execute(&mut *tx)
+            .await
+            .map_err(db_error)?;
+
+        tx.commit().await.map_err(db_error)?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::DatabaseConnection;
+
+    fn entry(path: &str, contents: &str) -> ManifestEntry {
+        ManifestEntry {
+            path: path.to_string(),
+            size: contents.len() as u64,
+            modified_ns: 42,
+            hash: ContentHash::of_bytes(HashAlgorithm::Sha256, contents.as_bytes()),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_save_find_list_and_delete() {
+        let (connection, _temp_dir) = DatabaseConnection::new_temp().await.unwrap();
+        let repository = SqliteManifestSnapshotRepository::new(connection.pool());
+        let project_id = ProjectId::new();
+
+        let snapshot = ManifestSnapshot::new(
+            project_id.clone(),
+            Some("Production 1".to_string()),
+            HashAlgorithm::Sha256,
+            vec![entry("b/two.pdf", "two"), entry("one.pdf", "one")],
+        );
+        repository.save(&snapshot).await.unwrap();
+
+        let loaded = repository.find_by_id(snapshot.id()).await.unwrap().unwrap();
+        assert_eq!(loaded.entries(), snapshot.entries());
+        assert_eq!(loaded.label(), Some("Production 1"));
+        assert_eq!(loaded.algorithm(), HashAlgorithm::Sha256);
+
+        let listed = repository.list_by_project(&project_id).await.unwrap();
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].file_count, 2);
+        assert_eq!(listed[0].total_size, 6);
+
+        assert!(repository.delete(snapshot.id()).await.unwrap());
+        assert!(!repository.delete(snapshot.id()).await.unwrap());
+        assert!(repository
+            .find_by_id(snapshot.id())
+            .await
+            .unwrap()
+            .is_none());
+    }
+}
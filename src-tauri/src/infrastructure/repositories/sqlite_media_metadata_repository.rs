@@ -0,0 +1,304 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::{QueryBuilder, Row, Sqlite, SqlitePool};
+use std::sync::Arc;
+
+use crate::domain::document::DocumentId;
+use crate::domain::media::{
+    MediaFilter, MediaKind, MediaMetadata, MediaMetadataRepository, MediaRecord, MediaTags,
+};
+use crate::domain::project::ProjectId;
+use crate::domain::workspace::repositories::RepositoryError;
+
+const MEDIA_COLUMNS: &str = "document_id, project_uuid, kind, container, duration_ms, \
+     audio_codec, video_codec, bitrate_kbps, sample_rate, channels, width, height, frame_rate, \
+     tags, method, content_hash, probed_at";
+
+/// SQLite implementation of the MediaMetadataRepository trait
+///
+/// Properties and the common tags are stored in their own columns so
+/// searches run in SQL; all tags are also kept as JSON.
+pub struct SqliteMediaMetadataRepository {
+    pool: Arc<SqlitePool>,
+}
+
+impl SqliteMediaMetadataRepository {
+    /// Create a new SqliteMediaMetadataRepository with the given connection pool
+    pub fn new(pool: Arc<SqlitePool>) -> Self {
+        SqliteMediaMetadataRepository { pool }
+    }
+
+    /// Convert database row to MediaRecord
+    fn row_to_record(row: &sqlx::sqlite::SqliteRow) -> Result<MediaRecord, RepositoryError> {
+        let db_error = |e: sqlx::Error| RepositoryError::DatabaseError(e.to_string());
+        let invalid = |e: String| RepositoryError::ValidationError(e);
+
+        let document_id: String = row.try_get("document_id").map_err(db_error)?;
+        let project_uuid: String = row.try_get("project_uuid").map_err(db_error)?;
+        let kind: String = row.try_get("kind").map_err(db_error)?;
+        let tags: String = row.try_get("tags").map_err(db_error)?;
+        let unsigned = |column: &str| -> Result<Option<u64>, RepositoryError> {
+            let value: Option<i64> = row.try_get(column).map_err(db_error)?;
+            Ok(value.map(|value| value.max(0) as u64))
+        };
+
+        let metadata = MediaMetadata {
+            kind: MediaKind::parse(&kind)
+                .ok_or_else(|| invalid(format!("Unknown media kind: {}", kind)))?,
+            container: row.try_get("container").map_err(db_error)?,
+            duration_ms: unsigned("duration_ms")?,
+            audio_codec: row.try_get("audio_codec").map_err(db_error)?,
+            video_codec: row.try_get("video_codec").map_err(db_error)?,
+            bitrate_kbps: unsigned("bitrate_kbps")?.map(|value| value as u32),
+            sample_rate: unsigned("sample_rate")?.map(|value| value as u32),
+            channels: unsigned("channels")?.map(|value| value as u16),
+            width: unsigned("width")?.map(|value| value as u32),
+            height: unsigned("height")?.map(|value| value as u32),
+            frame_rate: row.try_get("frame_rate").map_err(db_error)?,
+            tags: serde_json::from_str::<MediaTags>(&tags)?,
+        };
+
+        Ok(MediaRecord::from_data(
+            DocumentId::from_string(document_id).map_err(|e| invalid(e.to_string()))?,
+            ProjectId::from_string(project_uuid).map_err(|e| invalid(e.to_string()))?,
+            row.try_get("content_hash").map_err(db_error)?,
+            row.try_get("method").map_err(db_error)?,
+            metadata,
+            row.try_get::<DateTime<Utc>, _>("probed_at")
+                .map_err(db_error)?,
+        ))
+    }
+}
+
+#[async_trait]
+impl MediaMetadataRepository for SqliteMediaMetadataRepository {
+    async fn save(&self, record: &MediaRecord) -> Result<(), RepositoryError> {
+        let query = r#"
+            INSERT INTO media_metadata (document_id, project_uuid, kind, container, duration_ms,
+                                        audio_codec, video_codec, bitrate_kbps, sample_rate,
+                                        channels, width, height, frame_rate, title, artist,
+                                        album, genre, year, tags, method, content_hash, probed_at)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17,
+                    ?18, ?19, ?20, ?21, ?22)
+            ON CONFLICT(document_id) DO UPDATE SET
+                kind = excluded.kind,
+                container = excluded.container,
+                duration_ms = excluded.duration_ms,
+                audio_codec = excluded.audio_codec,
+                video_codec = excluded.video_codec,
+                bitrate_kbps = excluded.bitrate_kbps,
+                sample_rate = excluded.sample_rate,
+                channels = excluded.channels,
+                width = excluded.width,
+                height = excluded.height,
+                frame_rate = excluded.frame_rate,
+                title = excluded.title,
+                artist = excluded.artist,
+                album = excluded.album,
+                genre = excluded.genre,
+                year = excluded.year,
+                tags = excluded.tags,
+                method = excluded.method,
+                content_hash = excluded.content_hash,
+                probed_at = excluded.probed_at
+        "#;
+
+        let metadata = record.metadata();
+        sqlx::query(query)
+            .bind(record.document_id().value())
+            .bind(record.project_id().value())
+            .bind(metadata.kind.as_str())
+            .bind(&metadata.container)
+            .bind(metadata.duration_ms.map(|ms| ms as i64))
+            .bind(&metadata.audio_codec)
+            .bind(&metadata.video_codec)
+            .bind(metadata.bitrate_kbps.map(i64::from))
+            .bind(metadata.sample_rate.map(i64::from))
+            .bind(metadata.channels.map(i64::from))
+            .bind(metadata.width.map(i64::from))
+            .bind(metadata.height.map(i64::from))
+            .bind(metadata.frame_rate)
+            .bind(metadata.tags.title())
+            .bind(metadata.tags.artist())
+            .bind(metadata.tags.album())
+            .bind(metadata.tags.genre())
+            .bind(metadata.tags.year())
+            .bind(serde_json::to_string(&metadata.tags)?)
+            .bind(record.method())
+            .bind(record.content_hash())
+            .bind(record.probed_at())
+            .execute(&*self.pool)
+            .await
+            .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+
+        Ok(())
+    }
+
+    async fn find_by_document(
+        &self,
+        document_id: &DocumentId,
+    ) -> Result<Option<MediaRecord>, RepositoryError> {
+        let query = format!(
+            "SELECT {} FROM media_metadata WHERE document_id = ?1",
+            MEDIA_COLUMNS
+        );
+
+        let row = sqlx::query(&query)
+            .bind(document_id.value())
+            .fetch_optional(&*self.pool)
+            .await
+            .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+
+        row.as_ref().map(Self::row_to_record).transpose()
+    }
+
+    async fn search(
+        &self,
+        project_id: &ProjectId,
+        filter: &MediaFilter,
+    ) -> Result<Vec<MediaRecord>, RepositoryError> {
+        let mut builder: QueryBuilder<Sqlite> = QueryBuilder::new(format!(
+            "SELECT {} FROM media_metadata WHERE project_uuid = ",
+            MEDIA_COLUMNS
+        ));
+        builder.push_bind(project_id.value().to_string());
+
+        if let Some(kind) = filter.kind {
+            builder.push(" AND kind = ").push_bind(kind.as_str());
+        }
+        if let Some(min) = filter.min_duration_ms {
+            builder.push(" AND duration_ms >= ").push_bind(min as i64);
+        }
+        if let Some(max) = filter.max_duration_ms {
+            builder.push(" AND duration_ms <= ").push_bind(max as i64);
+        }
+        if let Some(container) = &filter.container {
+            builder
+                .push(" AND container = ")
+                .push_bind(container.clone())
+                .push(" COLLATE NOCASE");
+        }
+        if let Some(codec) = &filter.codec {
+            builder
+                .push(" AND (audio_codec = ")
+                .push_bind(codec.clone())
+                .push(" COLLATE NOCASE OR video_codec = ")
+                .push_bind(codec.clone())
+                .push(" COLLATE NOCASE)");
+        }
+        if let Some(width) = filter.min_width {
+            builder.push(" AND width >= ").push_bind(i64::from(width));
+        }
+        if let Some(height) = filter.min_height {
+            builder.push(" AND height >= ").push_bind(i64::from(height));
+        }
+        if let Some(text) = &filter.text {
+            let pattern = format!("%{}%", text);
+            builder
+                .push(" AND (title LIKE ")
+                .push_bind(pattern.clone())
+                .push(" OR artist LIKE ")
+                .push_bind(pattern.clone())
+                .push(" OR album LIKE ")
+                .push_bind(pattern)
+                .push(")");
+        }
+        builder.push(" ORDER BY duration_ms IS NULL, duration_ms DESC, document_id");
+
+        let rows = builder
+            .build()
+            .fetch_all(&*self.pool)
+            .await
+            .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+
+        rows.iter().map(Self::row_to_record).collect()
+    }
+
+    async fn delete(&self, document_id: &DocumentId) -> Result<(), RepositoryError> {
+        sqlx::query("DELETE FROM media_metadata WHERE document_id = ?1")
+            .bind(document_id.value())
+            .execute(&*self.pool)
+            .await
+            .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::DatabaseConnection;
+
+    fn record(project_id: &ProjectId, kind: MediaKind, duration_ms: u64) -> MediaRecord {
+        let mut metadata = MediaMetadata::new(kind, "MP4");
+        metadata.duration_ms = Some(duration_ms);
+        metadata.audio_codec = Some("AAC".to_string());
+        metadata.tags.set(MediaTags::TITLE, "Board meeting");
+        metadata.tags.set("composer", "Clerk");
+        MediaRecord::new(
+            DocumentId::new(),
+            project_id.clone(),
+            "hash".to_string(),
+            "mp4-probe-v1",
+            metadata,
+        )
+    }
+
+    #[tokio::test]
+    async fn test_save_find_search_and_delete() {
+        let (database, _dir) = DatabaseConnection::new_temp().await.unwrap();
+        let repository = SqliteMediaMetadataRepository::new(database.pool());
+        let project_id = ProjectId::new();
+
+        let short = record(&project_id, MediaKind::Audio, 120_000);
+        let long = record(&project_id, MediaKind::Audio, 900_000);
+        let video = record(&project_id, MediaKind::Video, 1_800_000);
+        for record in [&short, &long, &video] {
+            repository.save(record).await.unwrap();
+        }
+        repository
+            .save(&record(&ProjectId::new(), MediaKind::Audio, 700_000))
+            .await
+            .unwrap();
+
+        let found = repository
+            .find_by_document(long.document_id())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(found.metadata(), long.metadata());
+        assert_eq!(found.metadata().tags.get("composer"), Some("Clerk"));
+
+        let long_recordings = MediaFilter {
+            kind: Some(MediaKind::Audio),
+            min_duration_ms: Some(600_000),
+            ..MediaFilter::default()
+        };
+        let results = repository
+            .search(&project_id, &long_recordings)
+            .await
+            .unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].document_id(), long.document_id());
+
+        let by_text = MediaFilter {
+            codec: Some("aac".to_string()),
+            text: Some("MEETING".to_string()),
+            ..MediaFilter::default()
+        };
+        let results = repository.search(&project_id, &by_text).await.unwrap();
+        let durations: Vec<_> = results
+            .iter()
+            .map(|record| record.metadata().duration_ms)
+            .collect();
+        assert_eq!(durations, [Some(1_800_000), Some(900_000), Some(120_000)]);
+
+        repository.delete(video.document_id()).await.unwrap();
+        assert!(repository
+            .find_by_document(video.document_id())
+            .await
+            .unwrap()
+            .is_none());
+    }
+}
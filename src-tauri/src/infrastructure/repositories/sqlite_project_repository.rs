@@ -44,7 +44,18 @@ impl SqliteProjectRepository {
             ProjectError::repository_error(format!("Failed to get created_at: {}", e))
         })?;
 
-        Project::from_data(id, name, source_folder, note, created_at.to_rfc3339())
+        let reports_folder: Option<String> = row.try_get("reports_folder").map_err(|e| {
+            ProjectError::repository_error(format!("Failed to get reports_folder: {}", e))
+        })?;
+
+        let immutable_source: bool = row.try_get("immutable_source").map_err(|e| {
+            ProjectError::repository_error(format!("Failed to get immutable_source: {}", e))
+        })?;
+
+        Ok(
+            Project::from_data(id, name, source_folder, note, created_at.to_rfc3339())?
+                .with_stored_reports_settings(reports_folder, immutable_source),
+        )
     }
 }
 
@@ -57,12 +68,16 @@ impl ProjectRepository for SqliteProjectRepository {
         }
 
         let query = r#"
-            INSERT INTO projects (uuid, name, source_folder, note, created_at)
-            VALUES (?1, ?2, ?3, ?4, ?5)
+            INSERT INTO projects (uuid, name, source_folder, note, created_at,
+                                  reports_folder, immutable_source)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
         "#;
 
         let note_value = project.note().map(|n| n.value());
         let created_at_str = project.created_at().to_string();
+        let reports_folder = project
+            .configured_reports_folder()
+            .map(|folder| folder.to_string_lossy().to_string());
 
         sqlx::query(query)
             .bind(project.id().value())
@@ -70,6 +85,8 @@ impl ProjectRepository for SqliteProjectRepository {
             .bind(project.source_folder().as_string())
             .bind(note_value)
             .bind(created_at_str)
+            .bind(reports_folder)
+            .bind(project.is_source_immutable())
             .execute(&*self.pool)
             .await
             .map_err(|e| {
@@ -125,7 +142,7 @@ impl ProjectRepository for SqliteProjectRepository {
 
     async fn find_by_id(&self, id: &ProjectId) -> ProjectResult<Option<Project>> {
         let query = r#"
-            SELECT id, uuid, name, source_folder, note, created_at
+            SELECT id, uuid, name, source_folder, note, created_at, reports_folder, immutable_source
             FROM projects
             WHERE uuid = ?1
         "#;
@@ -146,7 +163,7 @@ impl ProjectRepository for SqliteProjectRepository {
 
     async fn find_by_name(&self, name: &str) -> ProjectResult<Option<Project>> {
         let query = r#"
-            SELECT id, uuid, name, source_folder, note, created_at
+            SELECT id, uuid, name, source_folder, note, created_at, reports_folder, immutable_source
             FROM projects
             WHERE name = ?1 COLLATE NOCASE
         "#;
@@ -167,7 +184,7 @@ impl ProjectRepository for SqliteProjectRepository {
 
     async fn list_all(&self) -> ProjectResult<Vec<Project>> {
         let query = r#"
-            SELECT id, uuid, name, source_folder, note, created_at
+            SELECT id, uuid, name, source_folder, note, created_at, reports_folder, immutable_source
             FROM projects
             ORDER BY created_at DESC
         "#;
@@ -236,7 +253,7 @@ impl ProjectRepository for SqliteProjectRepository {
 
     async fn list_paged(&self, offset: usize, limit: usize) -> ProjectResult<Vec<Project>> {
         let query = r#"
-            SELECT id, uuid, name, source_folder, note, created_at
+            SELECT id, uuid, name, source_folder, note, created_at, reports_folder, immutable_source
             FROM projects
             ORDER BY created_at DESC
             LIMIT ?1 OFFSET ?2
@@ -261,7 +278,7 @@ impl ProjectRepository for SqliteProjectRepository {
 
     async fn search_by_name(&self, pattern: &str) -> ProjectResult<Vec<Project>> {
         let query = r#"
-            SELECT id, uuid, name, source_folder, note, created_at
+            SELECT id, uuid, name, source_folder, note, created_at, reports_folder, immutable_source
             FROM projects
             WHERE name LIKE ?1 COLLATE NOCASE
             ORDER BY
@@ -295,7 +312,7 @@ impl ProjectRepository for SqliteProjectRepository {
         end_date: &DateTime<Utc>,
     ) -> ProjectResult<Vec<Project>> {
         let query = r#"
-            SELECT id, uuid, name, source_folder, note, created_at
+            SELECT id, uuid, name, source_folder, note, created_at, reports_folder, immutable_source
             FROM projects
             WHERE created_at BETWEEN ?1 AND ?2
             ORDER BY created_at DESC
@@ -473,7 +490,9 @@ mod tests {
                 name TEXT NOT NULL CHECK(length(name) > 0 AND length(name) <= 255),
                 source_folder TEXT NOT NULL,
                 note TEXT CHECK(length(note) <= 1000),
-                created_at DATETIME DEFAULT CURRENT_TIMESTAMP
+                created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+                reports_folder TEXT,
+                immutable_source BOOLEAN NOT NULL DEFAULT 0
             );
 
             CREATE INDEX idx_projects_uuid ON projects(uuid);
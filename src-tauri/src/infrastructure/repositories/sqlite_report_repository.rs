@@ -0,0 +1,311 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::{Row, SqlitePool};
+use std::sync::Arc;
+
+use crate::domain::document::DocumentId;
+use crate::domain::project::ProjectId;
+use crate::domain::report::{Report, ReportCategory, ReportId, ReportRepository, ReportStatus};
+use crate::domain::workspace::repositories::RepositoryError;
+
+const REPORT_COLUMNS: &str =
+    "id, project_uuid, title, category, status, path, created_at, updated_at";
+
+const LINK_DOCUMENT: &str = "document";
+const LINK_DERIVATIVE: &str = "derivative";
+
+/// SQLite implementation of the ReportRepository trait
+///
+/// Linked documents and derivatives are kept in `report_links`, so reports
+/// can be looked up by what they link to.
+pub struct SqliteReportRepository {
+    pool: Arc<SqlitePool>,
+}
+
+impl SqliteReportRepository {
+    /// Create a new SqliteReportRepository with the given connection pool
+    pub fn new(pool: Arc<SqlitePool>) -> Self {
+        SqliteReportRepository { pool }
+    }
+
+    /// Load the links of a report, in the order they were given
+    async fn load_links(
+        &self,
+        id: &str,
+    ) -> Result<(Vec<DocumentId>, Vec<String>), RepositoryError> {
+        let rows = sqlx::query(
+            "SELECT kind, target FROM report_links WHERE report_id = ?1 ORDER BY position",
+        )
+        .bind(id)
+        .fetch_all(&*self.pool)
+        .await
+        .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+
+        let mut documents = Vec::new();
+        let mut derivatives = Vec::new();
+        for row in rows {
+            let kind: String = row
+                .try_get("kind")
+                .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+            let target: String = row
+                .try_get("target")
+                .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+            if kind == LINK_DOCUMENT {
+                documents.push(
+                    DocumentId::from_string(target)
+                        .map_err(|e| RepositoryError::ValidationError(e.to_string()))?,
+                );
+            } else {
+                derivatives.push(target);
+            }
+        }
+        Ok((documents, derivatives))
+    }
+
+    /// Convert database row and links to Report
+    async fn row_to_report(
+        &self,
+        row: &sqlx::sqlite::SqliteRow,
+    ) -> Result<Report, RepositoryError> {
+        let db_error = |e: sqlx::Error| RepositoryError::DatabaseError(e.to_string());
+        let invalid =
+            |e: crate::domain::report::ReportError| RepositoryError::ValidationError(e.to_string());
+
+        let id: String = row.try_get("id").map_err(db_error)?;
+        let project_uuid: String = row.try_get("project_uuid").map_err(db_error)?;
+        let category: String = row.try_get("category").map_err(db_error)?;
+        let status: String = row.try_get("status").map_err(db_error)?;
+        let (documents, derivatives) = self.load_links(&id).await?;
+
+        Ok(Report::from_data(
+            ReportId::from_string(id).map_err(invalid)?,
+            ProjectId::from_string(project_uuid)
+                .map_err(|e| RepositoryError::ValidationError(e.to_string()))?,
+            row.try_get("title").map_err(db_error)?,
+            ReportCategory::parse(&category).map_err(invalid)?,
+            ReportStatus::parse(&status).map_err(invalid)?,
+            row.try_get("path").map_err(db_error)?,
+            documents,
+            derivatives,
+            row.try_get::<DateTime<Utc>, _>("created_at")
+                .map_err(db_error)?,
+            row.try_get::<DateTime<Utc>, _>("updated_at")
+                .map_err(db_error)?,
+        ))
+    }
+}
+
+#[async_trait]
+impl ReportRepository for SqliteReportRepository {
+    async fn save(&self, report: &Report) -> Result<(), RepositoryError> {
+        let db_error = |e: sqlx::Error| RepositoryError::DatabaseError(e.to_string());
+
+        let mut tx = self.pool.begin().await.map_err(db_error)?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO reports (id, project_uuid, title, category, status, path,
+                                 created_at, updated_at)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)
+            ON CONFLICT(id) DO UPDATE SET
+                title = excluded.title,
+                category = excluded.category,
+                status = excluded.status,
+                path = excluded.path,
+                updated_at = excluded.updated_at
+            "#,
+        )
+        .bind(report.id().value())
+        .bind(report.project_id().value())
+        .bind(report.title())
+        .bind(report.category().as_str())
+        .bind(report.status().as_str())
+        .bind(report.path())
+        .bind(report.created_at())
+        .bind(report.updated_at())
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| match e {
+            sqlx::Error::Database(ref db) if db.message().contains("UNIQUE") => {
+                RepositoryError::ConstraintViolation(format!(
+                    "A report is already stored at {}",
+                    report.path()
+                ))
+            }
+            e => db_error(e),
+        })?;
+
+        sqlx::query("DELETE FROM report_links WHERE report_id = ?1")
+            .bind(report.id().value())
+            .execute(&mut *tx)
+            .await
+            .map_err(db_error)?;
+
+        let links = report
+            .source_documents()
+            .iter()
+            .map(|id| (LINK_DOCUMENT, id.value()))
+            .chain(
+                report
+                    .derivatives()
+                    .iter()
+                    .map(|path| (LINK_DERIVATIVE, path.as_str())),
+            );
+        for (position, (kind, target)) in links.enumerate() {
+            sqlx::query(
+                "INSERT INTO report_links (report_id, kind, target, position) VALUES (?1, ?2, ?3, ?4)",
+            )
+            .bind(report.id().value())
+            .bind(kind)
+            .bind(target)
+            .bind(position as i64)
+            .execute(&mut *tx)
+            .await
+            .map_err(db_error)?;
+        }
+
+        tx.commit().await.map_err(db_error)?;
+
+        Ok(())
+    }
+
+    async fn find_by_id(&self, id: &ReportId) -> Result<Option<Report>, RepositoryError> {
+        let query = format!("SELECT {} FROM reports WHERE id = ?1", REPORT_COLUMNS);
+
+        let row = sqlx::query(&query)
+            .bind(id.value())
+            .fetch_optional(&*self.pool)
+            .await
+            .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+
+        match row {
+            Some(row) => Ok(Some(self.row_to_report(&row).await?)),
+            None => Ok(None),
+        }
+    }
+
+    async fn list_by_project(
+        &self,
+        project_id: &ProjectId,
+    ) -> Result<Vec<Report>, RepositoryError> {
+        let query = format!(
+            "SELECT {} FROM reports WHERE project_uuid = ?1 ORDER BY updated_at DESC",
+            REPORT_COLUMNS
+        );
+
+        let rows = sqlx::query(&query)
+            .bind(project_id.value())
+            .fetch_all(&*self.pool)
+            .await
+            .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+
+        let mut reports = Vec::with_capacity(rows.len());
+        for row in &rows {
+            reports.push(self.row_to_report(row).await?);
+        }
+        Ok(reports)
+    }
+
+    async fn path_exists(
+        &self,
+        project_id: &ProjectId,
+        path: &str,
+    ) -> Result<bool, RepositoryError> {
+        let row = sqlx::query("SELECT 1 FROM reports WHERE project_uuid = ?1 AND path = ?2")
+            .bind(project_id.value())
+            .bind(path)
+            .fetch_optional(&*self.pool)
+            .await
+            .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+
+        Ok(row.is_some())
+    }
+
+    async fn delete(&self, id: &ReportId) -> Result<bool, RepositoryError> {
+        let db_error = |e: sqlx::Error| RepositoryError::DatabaseError(e.to_string());
+
+        let mut tx = self.pool.begin().await.map_err(db_error)?;
+
+        sqlx::query("DELETE FROM report_links WHERE report_id = ?1")
+            .bind(id.value())
+            .execute(&mut *tx)
+            .await
+            .map_err(db_error)?;
+
+        let result = sqlx::query("DELETE FROM reports WHERE id = ?1")
+            .bind(id.value())
+            .execute(&mut *tx)
+            .await
+            .map_err(db_error)?;
+
+        tx.commit().await.map_err(db_error)?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::DatabaseConnection;
+
+    #[tokio::test]
+    async fn test_save_find_list_and_delete() {
+        let (database, _dir) = DatabaseConnection::new_temp().await.unwrap();
+        let repository = SqliteReportRepository::new(database.pool());
+        let project_id = ProjectId::new();
+
+        let mut report = Report::new(
+            project_id.clone(),
+            "Findings".to_string(),
+            ReportCategory::Findings,
+            "findings/findings.det".to_string(),
+        )
+        .unwrap();
+        let first = DocumentId::new();
+        let second = DocumentId::new();
+        report.set_source_documents(vec![second.clone(), first.clone(), second.clone()]);
+        report.set_derivatives(vec!["/derivatives/a/summary.det".to_string()]);
+        repository.save(&report).await.unwrap();
+
+        let found = repository.find_by_id(report.id()).await.unwrap().unwrap();
+        assert_eq!(found.source_documents(), &[second, first]);
+        assert_eq!(found.derivatives(), report.derivatives());
+        assert_eq!(found.category(), ReportCategory::Findings);
+
+        report.set_status(ReportStatus::Final);
+        report.set_source_documents(Vec::new());
+        repository.save(&report).await.unwrap();
+        let found = repository.find_by_id(report.id()).await.unwrap().unwrap();
+        assert!(found.is_final());
+        assert!(found.source_documents().is_empty());
+
+        assert!(repository
+            .path_exists(&project_id, "findings/findings.det")
+            .await
+            .unwrap());
+        let clash = Report::new(
+            project_id.clone(),
+            "Findings".to_string(),
+            ReportCategory::Findings,
+            "findings/findings.det".to_string(),
+        )
+        .unwrap();
+        assert!(matches!(
+            repository.save(&clash).await,
+            Err(RepositoryError::ConstraintViolation(_))
+        ));
+
+        assert_eq!(
+            repository.list_by_project(&project_id).await.unwrap().len(),
+            1
+        );
+        assert!(repository.delete(report.id()).await.unwrap());
+        assert!(!repository.delete(report.id()).await.unwrap());
+        assert!(repository
+            .list_by_project(&project_id)
+            .await
+            .unwrap()
+            .is_empty());
+    }
+}
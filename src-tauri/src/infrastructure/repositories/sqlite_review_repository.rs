@@ -0,0 +1,296 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::{Row, SqlitePool};
+use std::sync::Arc;
+
+use crate::domain::document::DocumentId;
+use crate::domain::project::ProjectId;
+use crate::domain::review::{
+    BatchSource, BatchStatus, ReviewBatch, ReviewBatchId, ReviewRepository, Reviewer, ReviewerId,
+};
+use crate::domain::workspace::repositories::RepositoryError;
+
+const REVIEWER_COLUMNS: &str = "id, name, created_at";
+
+const BATCH_COLUMNS: &str = "id, project_uuid, name, source, reviewer_id, status, document_ids, \
+     created_at, updated_at, closed_at";
+
+/// SQLite implementation of the ReviewRepository trait
+///
+/// Batch sources and document lists are kept as JSON. The active reviewer
+/// is flagged on its row.
+pub struct SqliteReviewRepository {
+    pool: Arc<SqlitePool>,
+}
+
+impl SqliteReviewRepository {
+    /// Create a new SqliteReviewRepository with the given connection pool
+    pub fn new(pool: Arc<SqlitePool>) -> Self {
+        SqliteReviewRepository { pool }
+    }
+
+    /// Convert database row to Reviewer
+    fn row_to_reviewer(row: &sqlx::sqlite::SqliteRow) -> Result<Reviewer, RepositoryError> {
+        let db_error = |e: sqlx::Error| RepositoryError::DatabaseError(e.to_string());
+
+        let id: String = row.try_get("id").map_err(db_error)?;
+
+        Ok(Reviewer::from_data(
+            ReviewerId::from_string(id)
+                .map_err(|e| RepositoryError::ValidationError(e.to_string()))?,
+            row.try_get("name").map_err(db_error)?,
+            row.try_get::<DateTime<Utc>, _>("created_at")
+                .map_err(db_error)?,
+        ))
+    }
+
+    /// Convert database row to ReviewBatch
+    fn row_to_batch(row: &sqlx::sqlite::SqliteRow) -> Result<ReviewBatch, RepositoryError> {
+        let db_error = |e: sqlx::Error| RepositoryError::DatabaseError(e.to_string());
+        let invalid = |e: String| RepositoryError::ValidationError(e);
+
+        let id: String = row.try_get("id").map_err(db_error)?;
+        let project_uuid: String = row.try_get("project_uuid").map_err(db_error)?;
+        let source: String = row.try_get("source").map_err(db_error)?;
+        let reviewer_id: Option<String> = row.try_get("reviewer_id").map_err(db_error)?;
+        let status: String = row.try_get("status").map_err(db_error)?;
+        let document_ids: String = row.try_get("document_ids").map_err(db_error)?;
+
+        Ok(ReviewBatch::from_data(
+            ReviewBatchId::from_string(id).map_err(|e| invalid(e.to_string()))?,
+            ProjectId::from_string(project_uuid).map_err(|e| invalid(e.to_string()))?,
+            row.try_get("name").map_err(db_error)?,
+            serde_json::from_str::<BatchSource>(&source)?,
+            reviewer_id
+                .map(ReviewerId::from_string)
+                .transpose()
+                .map_err(|e| invalid(e.to_string()))?,
+            BatchStatus::parse(&status)
+                .ok_or_else(|| invalid(format!("Unknown batch status '{}'", status)))?,
+            serde_json::from_str::<Vec<String>>(&document_ids)?
+                .into_iter()
+                .map(DocumentId::from_string)
+                .collect::<Result<_, _>>()
+                .map_err(|e| invalid(e.to_string()))?,
+            row.try_get::<DateTime<Utc>, _>("created_at")
+                .map_err(db_error)?,
+            row.try_get::<DateTime<Utc>, _>("updated_at")
+                .map_err(db_error)?,
+            row.try_get::<Option<DateTime<Utc>>, _>("closed_at")
+                .map_err(db_error)?,
+        ))
+    }
+}
+
+#[async_trait]
+impl ReviewRepository for SqliteReviewRepository {
+    async fn save_reviewer(&self, reviewer: &Reviewer) -> Result<(), RepositoryError> {
+        let query = r#"
+            INSERT INTO reviewers (id, name, created_at)
+            VALUES (?1, ?2, ?3)
+            ON CONFLICT(id) DO UPDATE SET
+                name = excluded.name
+        "#;
+
+        sqlx::query(query)
+            .bind(reviewer.id().value())
+            .bind(reviewer.name())
+            .bind(reviewer.created_at())
+            .execute(&*self.pool)
+            .await
+            .map_err(|e| match e {
+                sqlx::Error::Database(ref db) if db.message().contains("UNIQUE") => {
+                    RepositoryError::ConstraintViolation(format!(
+                        "A reviewer named '{}' already exists",
+                        reviewer.name()
+                    ))
+                }
+                _ => RepositoryError::DatabaseError(e.to_string()),
+            })?;
+
+        Ok(())
+    }
+
+    async fn find_reviewer(&self, id: &ReviewerId) -> Result<Option<Reviewer>, RepositoryError> {
+        let query = format!("SELECT {} FROM reviewers WHERE id = ?1", REVIEWER_COLUMNS);
+
+        let row = sqlx::query(&query)
+            .bind(id.value())
+            .fetch_optional(&*self.pool)
+            .await
+            .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+
+        row.as_ref().map(Self::row_to_reviewer).transpose()
+    }
+
+    async fn list_reviewers(&self) -> Result<Vec<Reviewer>, RepositoryError> {
+        let query = format!(
+            "SELECT {} FROM reviewers ORDER BY name COLLATE NOCASE",
+            REVIEWER_COLUMNS
+        );
+
+        let rows = sqlx::query(&query)
+            .fetch_all(&*self.pool)
+            .await
+            .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+
+        rows.iter().map(Self::row_to_reviewer).collect()
+    }
+
+    async fn active_reviewer(&self) -> Result<Option<Reviewer>, RepositoryError> {
+        let query = format!(
+            "SELECT {} FROM reviewers WHERE active = 1 LIMIT 1",
+            REVIEWER_COLUMNS
+        );
+
+        let row = sqlx::query(&query)
+            .fetch_optional(&*self.pool)
+            .await
+            .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+
+        row.as_ref().map(Self::row_to_reviewer).transpose()
+    }
+
+    async fn set_active_reviewer(&self, id: Option<&ReviewerId>) -> Result<(), RepositoryError> {
+        sqlx::query("UPDATE reviewers SET active = (id IS ?1)")
+            .bind(id.map(ReviewerId::value))
+            .execute(&*self.pool)
+            .await
+            .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+
+        Ok(())
+    }
+
+    async fn save_batch(&self, batch: &ReviewBatch) -> Result<(), RepositoryError> {
+        let query = r#"
+            INSERT INTO review_batches (id, project_uuid, name, source, reviewer_id, status,
+                                        document_ids, created_at, updated_at, closed_at)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)
+            ON CONFLICT(id) DO UPDATE SET
+                name = excluded.name,
+                reviewer_id = excluded.reviewer_id,
+                status = excluded.status,
+                document_ids = excluded.document_ids,
+                updated_at = excluded.updated_at,
+                closed_at = excluded.closed_at
+        "#;
+
+        let document_ids: Vec<&str> = batch.document_ids().iter().map(DocumentId::value).collect();
+
+        sqlx::query(query)
+            .bind(batch.id().value())
+            .bind(batch.project_id().value())
+            .bind(batch.name())
+            .bind(serde_json::to_string(batch.source())?)
+            .bind(batch.reviewer_id().map(ReviewerId::value))
+            .bind(batch.status().as_str())
+            .bind(serde_json::to_string(&document_ids)?)
+            .bind(batch.created_at())
+            .bind(batch.updated_at())
+            .bind(batch.closed_at())
+            .execute(&*self.pool)
+            .await
+            .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+
+        Ok(())
+    }
+
+    async fn find_batch(&self, id: &ReviewBatchId) -> Result<Option<ReviewBatch>, RepositoryError> {
+        let query = format!("SELECT {} FROM review_batches WHERE id = ?1", BATCH_COLUMNS);
+
+        let row = sqlx::query(&query)
+            .bind(id.value())
+            .fetch_optional(&*self.pool)
+            .await
+            .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+
+        row.as_ref().map(Self::row_to_batch).transpose()
+    }
+
+    async fn list_batches(
+        &self,
+        project_id: &ProjectId,
+    ) -> Result<Vec<ReviewBatch>, RepositoryError> {
+        let query = format!(
+            "SELECT {} FROM review_batches WHERE project_uuid = ?1 ORDER BY created_at, rowid",
+            BATCH_COLUMNS
+        );
+
+        let rows = sqlx::query(&query)
+            .bind(project_id.value())
+            .fetch_all(&*self.pool)
+            .await
+            .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+
+        rows.iter().map(Self::row_to_batch).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::DatabaseConnection;
+
+    #[tokio::test]
+    async fn test_reviewers_and_batches() {
+        let (database, _dir) = DatabaseConnection::new_temp().await.unwrap();
+        let repository = SqliteReviewRepository::new(database.pool());
+
+        let ana = Reviewer::new("Ana".to_string()).unwrap();
+        let ben = Reviewer::new("Ben".to_string()).unwrap();
+        repository.save_reviewer(&ben).await.unwrap();
+        repository.save_reviewer(&ana).await.unwrap();
+        assert!(matches!(
+            repository
+                .save_reviewer(&Reviewer::new("ana".to_string()).unwrap())
+                .await,
+            Err(RepositoryError::ConstraintViolation(_))
+        ));
+        assert_eq!(
+            repository.list_reviewers().await.unwrap(),
+            [ana.clone(), ben.clone()]
+        );
+
+        assert!(repository.active_reviewer().await.unwrap().is_none());
+        repository
+            .set_active_reviewer(Some(ben.id()))
+            .await
+            .unwrap();
+        repository
+            .set_active_reviewer(Some(ana.id()))
+            .await
+            .unwrap();
+        assert_eq!(
+            repository.active_reviewer().await.unwrap(),
+            Some(ana.clone())
+        );
+        repository.set_active_reviewer(None).await.unwrap();
+        assert!(repository.active_reviewer().await.unwrap().is_none());
+
+        let project_id = ProjectId::new();
+        let mut batch = ReviewBatch::new(
+            project_id.clone(),
+            "Privilege review".to_string(),
+            BatchSource::Category {
+                category_id: "category_1".to_string(),
+                include_descendants: true,
+            },
+            vec![DocumentId::new(), DocumentId::new()],
+        )
+        .unwrap();
+        batch.assign(Some(ana.id().clone())).unwrap();
+        batch.close().unwrap();
+        repository.save_batch(&batch).await.unwrap();
+
+        assert_eq!(
+            repository.find_batch(batch.id()).await.unwrap(),
+            Some(batch.clone())
+        );
+        assert_eq!(repository.list_batches(&project_id).await.unwrap(), [batch]);
+        assert!(repository
+            .list_batches(&ProjectId::new())
+            .await
+            .unwrap()
+            .is_empty());
+    }
+}
@@ -0,0 +1,207 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::{Row, SqlitePool};
+use std::collections::BTreeSet;
+use std::sync::Arc;
+
+use crate::domain::project::ProjectId;
+use crate::domain::search::{SavedSearch, SavedSearchId, SavedSearchRepository};
+use crate::domain::workspace::repositories::RepositoryError;
+
+const SAVED_SEARCH_COLUMNS: &str = "id, project_uuid, name, query, track_new, seen, new_results, \
+     last_viewed_at, checked_at, created_at, updated_at";
+
+/// SQLite implementation of the SavedSearchRepository trait
+///
+/// The keys of seen and new results are kept as JSON arrays.
+pub struct SqliteSavedSearchRepository {
+    pool: Arc<SqlitePool>,
+}
+
+impl SqliteSavedSearchRepository {
+    /// Create a new SqliteSavedSearchRepository with the given connection pool
+    pub fn new(pool: Arc<SqlitePool>) -> Self {
+        SqliteSavedSearchRepository { pool }
+    }
+
+    /// Convert database row to SavedSearch
+    fn row_to_search(row: &sqlx::sqlite::SqliteRow) -> Result<SavedSearch, RepositoryError> {
+        let db_error = |e: sqlx::Error| RepositoryError::DatabaseError(e.to_string());
+        let invalid = |e: String| RepositoryError::ValidationError(e);
+
+        let id: String = row.try_get("id").map_err(db_error)?;
+        let project_uuid: String = row.try_get("project_uuid").map_err(db_error)?;
+        let seen: String = row.try_get("seen").map_err(db_error)?;
+        let new_results: String = row.try_get("new_results").map_err(db_error)?;
+
+        Ok(SavedSearch::from_data(
+            SavedSearchId::from_string(id).map_err(|e| invalid(e.to_string()))?,
+            ProjectId::from_string(project_uuid).map_err(|e| invalid(e.to_string()))?,
+            row.try_get("name").map_err(db_error)?,
+            row.try_get("query").map_err(db_error)?,
+            row.try_get("track_new").map_err(db_error)?,
+            serde_json::from_str::<BTreeSet<String>>(&seen)?,
+            serde_json::from_str::<BTreeSet<String>>(&new_results)?,
+            row.try_get::<Option<DateTime<Utc>>, _>("last_viewed_at")
+                .map_err(db_error)?,
+            row.try_get::<Option<DateTime<Utc>>, _>("checked_at")
+                .map_err(db_error)?,
+            row.try_get::<DateTime<Utc>, _>("created_at")
+                .map_err(db_error)?,
+            row.try_get::<DateTime<Utc>, _>("updated_at")
+                .map_err(db_error)?,
+        ))
+    }
+}
+
+#[async_trait]
+impl SavedSearchRepository for SqliteSavedSearchRepository {
+    async fn save(&self, search: &SavedSearch) -> Result<(), RepositoryError> {
+        let query = r#"
+            INSERT INTO saved_searches (id, project_uuid, name, query, track_new, seen,
+                                        new_results, last_viewed_at, checked_at, created_at,
+                                        updated_at)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)
+            ON CONFLICT(id) DO UPDATE SET
+                name = excluded.name,
+                query = excluded.query,
+                track_new = excluded.track_new,
+                seen = excluded.seen,
+                new_results = excluded.new_results,
+                last_viewed_at = excluded.last_viewed_at,
+                checked_at = excluded.checked_at,
+                updated_at = excluded.updated_at
+        "#;
+
+        sqlx::query(query)
+            .bind(search.id().value())
+            .bind(search.project_id().value())
+            .bind(search.name())
+            .bind(search.query())
+            .bind(search.tracks_new())
+            .bind(serde_json::to_string(search.seen())?)
+            .bind(serde_json::to_string(search.new_results())?)
+            .bind(search.last_viewed_at())
+            .bind(search.checked_at())
+            .bind(search.created_at())
+            .bind(search.updated_at())
+            .execute(&*self.pool)
+            .await
+            .map_err(|e| match e {
+                sqlx::Error::Database(ref db) if db.message().contains("UNIQUE") => {
+                    RepositoryError::ConstraintViolation(format!(
+                        "A saved search named '{}' already exists",
+                        search.name()
+                    ))
+                }
+                e => RepositoryError::DatabaseError(e.to_string()),
+            })?;
+
+        Ok(())
+    }
+
+    async fn find_by_id(&self, id: &SavedSearchId) -> Result<Option<SavedSearch>, RepositoryError> {
+        let query = format!(
+            "SELECT {} FROM saved_searches WHERE id = ?1",
+            SAVED_SEARCH_COLUMNS
+        );
+
+        let row = sqlx::query(&query)
+            .bind(id.value())
+            .fetch_optional(&*self.pool)
+            .await
+            .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+
+        row.as_ref().map(Self::row_to_search).transpose()
+    }
+
+    async fn list_by_project(
+        &self,
+        project_id: &ProjectId,
+    ) -> Result<Vec<SavedSearch>, RepositoryError> {
+        let query = format!(
+            "SELECT {} FROM saved_searches WHERE project_uuid = ?1 ORDER BY name COLLATE NOCASE",
+            SAVED_SEARCH_COLUMNS
+        );
+
+        let rows = sqlx::query(&query)
+            .bind(project_id.value())
+            .fetch_all(&*self.pool)
+            .await
+            .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+
+        rows.iter().map(Self::row_to_search).collect()
+    }
+
+    async fn delete(&self, id: &SavedSearchId) -> Result<(), RepositoryError> {
+        sqlx::query("DELETE FROM saved_searches WHERE id = ?1")
+            .bind(id.value())
+            .execute(&*self.pool)
+            .await
+            .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::DatabaseConnection;
+
+    #[tokio::test]
+    async fn test_save_list_and_delete() {
+        let (database, _dir) = DatabaseConnection::new_temp().await.unwrap();
+        let repository = SqliteSavedSearchRepository::new(database.pool());
+        let project_id = ProjectId::new();
+
+        let mut weekly = SavedSearch::new(
+            project_id.clone(),
+            "Weekly PDFs".to_string(),
+            "ext:pdf".to_string(),
+            true,
+        )
+        .unwrap();
+        weekly.mark_viewed(vec!["original:a.pdf".to_string()], Utc::now());
+        weekly.record_changed_matches(vec!["original:b.pdf".to_string()], Utc::now());
+        let emails = SavedSearch::new(
+            project_id.clone(),
+            "emails".to_string(),
+            "ext:eml".to_string(),
+            false,
+        )
+        .unwrap();
+        repository.save(&weekly).await.unwrap();
+        repository.save(&emails).await.unwrap();
+
+        let found = repository.find_by_id(weekly.id()).await.unwrap().unwrap();
+        assert_eq!(found.seen(), weekly.seen());
+        assert_eq!(found.new_result_count(), Some(1));
+        assert!(found.checked_at().is_some());
+
+        let names: Vec<String> = repository
+            .list_by_project(&project_id)
+            .await
+            .unwrap()
+            .iter()
+            .map(|search| search.name().to_string())
+            .collect();
+        assert_eq!(names, ["emails", "Weekly PDFs"]);
+
+        // Names are unique per project
+        let duplicate = SavedSearch::new(
+            project_id.clone(),
+            "emails".to_string(),
+            "ext:msg".to_string(),
+            false,
+        )
+        .unwrap();
+        assert!(matches!(
+            repository.save(&duplicate).await,
+            Err(RepositoryError::ConstraintViolation(_))
+        ));
+
+        repository.delete(emails.id()).await.unwrap();
+        assert!(repository.find_by_id(emails.id()).await.unwrap().is_none());
+    }
+}
@@ -0,0 +1,300 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::{QueryBuilder, Row, Sqlite, SqlitePool};
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+use crate::domain::document::DocumentId;
+use crate::domain::project::ProjectId;
+use crate::domain::search::{IndexedText, SearchIndexRepository, SourceKind};
+use crate::domain::workspace::repositories::RepositoryError;
+
+/// SQLite implementation of the SearchIndexRepository trait
+///
+/// Files are rows of `search_index`; their text is in the FTS5 table
+/// `search_text` under the same rowid.
+pub struct SqliteSearchIndexRepository {
+    pool: Arc<SqlitePool>,
+}
+
+impl SqliteSearchIndexRepository {
+    /// Create a new SqliteSearchIndexRepository with the given connection pool
+    pub fn new(pool: Arc<SqlitePool>) -> Self {
+        SqliteSearchIndexRepository { pool }
+    }
+
+    /// Convert database row to IndexedText
+    fn row_to_entry(row: &sqlx::sqlite::SqliteRow) -> Result<IndexedText, RepositoryError> {
+        let db_error = |e: sqlx::Error| RepositoryError::DatabaseError(e.to_string());
+        let invalid = |e: String| RepositoryError::ValidationError(e);
+
+        let project_uuid: String = row.try_get("project_uuid").map_err(db_error)?;
+        let kind: String = row.try_get("kind").map_err(db_error)?;
+        let document_id: Option<String> = row.try_get("document_id").map_err(db_error)?;
+
+        Ok(IndexedText {
+            project_id: ProjectId::from_string(project_uuid).map_err(|e| invalid(e.to_string()))?,
+            path: row.try_get("path").map_err(db_error)?,
+            kind: SourceKind::parse(&kind)
+                .ok_or_else(|| invalid(format!("Unknown search index kind '{}'", kind)))?,
+            document_id: document_id
+                .map(DocumentId::from_string)
+                .transpose()
+                .map_err(|e| invalid(e.to_string()))?,
+            modified_at: row
+                .try_get::<DateTime<Utc>, _>("modified_at")
+                .map_err(db_error)?,
+            written_at: row
+                .try_get::<DateTime<Utc>, _>("written_at")
+                .map_err(db_error)?,
+        })
+    }
+}
+
+/// FTS5 query for `words`: a prefix for one word, a phrase for several
+fn match_expression(words: &[String]) -> String {
+    let quoted = format!("\"{}\"", words.join(" ").replace('"', "\"\""));
+    if words.len() == 1 {
+        format!("{}*", quoted)
+    } else {
+        quoted
+    }
+}
+
+/// Append a parenthesised list of `paths` to an `IN (` clause
+fn push_paths(builder: &mut QueryBuilder<Sqlite>, paths: &[String]) {
+    let mut separated = builder.separated(", ");
+    for path in paths {
+        separated.push_bind(path.clone());
+    }
+    separated.push_unseparated(")");
+}
+
+#[async_trait]
+impl SearchIndexRepository for SqliteSearchIndexRepository {
+    async fn save(&self, entry: &IndexedText, text: &str) -> Result<(), RepositoryError> {
+        let db_error = |e: sqlx::Error| RepositoryError::DatabaseError(e.to_string());
+        let query = r#"
+            INSERT INTO search_index (path, project_uuid, kind, document_id, modified_at,
+                                      written_at)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+            ON CONFLICT(path) DO UPDATE SET
+                project_uuid = excluded.project_uuid,
+                kind = excluded.kind,
+                document_id = excluded.document_id,
+                modified_at = excluded.modified_at,
+                written_at = excluded.written_at
+            RETURNING rowid
+        "#;
+
+        let mut tx = self.pool.begin().await.map_err(db_error)?;
+        let rowid: i64 = sqlx::query_scalar(query)
+            .bind(&entry.path)
+            .bind(entry.project_id.value())
+            .bind(entry.kind.as_str())
+            .bind(entry.document_id.as_ref().map(DocumentId::value))
+            .bind(entry.modified_at)
+            .bind(entry.written_at)
+            .fetch_one(&mut *tx)
+            .await
+            .map_err(db_error)?;
+        sqlx::query("DELETE FROM search_text WHERE rowid = ?1")
+            .bind(rowid)
+            .execute(&mut *tx)
+            .await
+            .map_err(db_error)?;
+        sqlx::query("INSERT INTO search_text (rowid, text) VALUES (?1, ?2)")
+            .bind(rowid)
+            .bind(text)
+            .execute(&mut *tx)
+            .await
+            .map_err(db_error)?;
+        tx.commit().await.map_err(db_error)?;
+
+        Ok(())
+    }
+
+    async fn list_by_project(
+        &self,
+        project_id: &ProjectId,
+    ) -> Result<Vec<IndexedText>, RepositoryError> {
+        let query = r#"
+            SELECT path, project_uuid, kind, document_id, modified_at, written_at
+            FROM search_index WHERE project_uuid = ?1 ORDER BY path
+        "#;
+
+        let rows = sqlx::query(query)
+            .bind(project_id.value())
+            .fetch_all(&*self.pool)
+            .await
+            .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+
+        rows.iter().map(Self::row_to_entry).collect()
+    }
+
+    async fn paths_containing(
+        &self,
+        project_id: &ProjectId,
+        words: &[String],
+    ) -> Result<HashSet<String>, RepositoryError> {
+        if words.is_empty() {
+            return Ok(HashSet::new());
+        }
+        let query = r#"
+            SELECT search_index.path FROM search_text
+            JOIN search_index ON search_index.rowid = search_text.rowid
+            WHERE search_text MATCH ?1 AND search_index.project_uuid = ?2
+        "#;
+
+        let paths: Vec<String> = sqlx::query_scalar(query)
+            .bind(match_expression(words))
+            .bind(project_id.value())
+            .fetch_all(&*self.pool)
+            .await
+            .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+
+        Ok(paths.into_iter().collect())
+    }
+
+    async fn texts(&self, paths: &[String]) -> Result<HashMap<String, String>, RepositoryError> {
+        let mut texts = HashMap::new();
+
+        // Stay well below SQLite's bound parameter limit
+        for chunk in paths.chunks(500) {
+            let mut builder: QueryBuilder<Sqlite> = QueryBuilder::new(
+                "SELECT search_index.path, search_text.text FROM search_index \
+                 JOIN search_text ON search_text.rowid = search_index.rowid \
+                 WHERE search_index.path IN (",
+            );
+            push_paths(&mut builder, chunk);
+
+            let rows = builder
+                .build()
+                .fetch_all(&*self.pool)
+                .await
+                .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+            for row in &rows {
+                let path: String = row
+                    .try_get("path")
+                    .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+                let text: String = row
+                    .try_get("text")
+                    .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+                texts.insert(path, text);
+            }
+        }
+
+        Ok(texts)
+    }
+
+    async fn remove(&self, paths: &[String]) -> Result<(), RepositoryError> {
+        let db_error = |e: sqlx::Error| RepositoryError::DatabaseError(e.to_string());
+        if paths.is_empty() {
+            return Ok(());
+        }
+
+        let mut tx = self.pool.begin().await.map_err(db_error)?;
+        for chunk in paths.chunks(500) {
+            let mut texts: QueryBuilder<Sqlite> = QueryBuilder::new(
+                "DELETE FROM search_text WHERE rowid IN \
+                 (SELECT rowid FROM search_index WHERE path IN (",
+            );
+            push_paths(&mut texts, chunk);
+            texts.push(")");
+            texts.build().execute(&mut *tx).await.map_err(db_error)?;
+
+            let mut files: QueryBuilder<Sqlite> =
+                QueryBuilder::new("DELETE FROM search_index WHERE path IN (");
+            push_paths(&mut files, chunk);
+            files.build().execute(&mut *tx).await.map_err(db_error)?;
+        }
+        tx.commit().await.map_err(db_error)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::DatabaseConnection;
+
+    fn entry(project_id: &ProjectId, path: &str) -> IndexedText {
+        IndexedText {
+            project_id: project_id.clone(),
+            path: path.to_string(),
+            kind: SourceKind::Derivative,
+            document_id: Some(DocumentId::new()),
+            modified_at: Utc::now(),
+            written_at: Utc::now(),
+        }
+    }
+
+    fn words(text: &str) -> Vec<String> {
+        text.split(' ').map(ToString::to_string).collect()
+    }
+
+    #[tokio::test]
+    async fn test_save_match_and_remove() {
+        let (database, _dir) = DatabaseConnection::new_temp().await.unwrap();
+        let repository = SqliteSearchIndexRepository::new(database.pool());
+        let project_id = ProjectId::new();
+        let other_project = ProjectId::new();
+
+        let minutes = entry(&project_id, "/derivatives/a/extracted.det");
+        let memo = entry(&project_id, "/derivatives/b/extracted.det");
+        repository
+            .save(&minutes, "Board minutes on the Café \"settlement\"")
+            .await
+            .unwrap();
+        repository
+            .save(&memo, "Memo about minutes of the board")
+            .await
+            .unwrap();
+        repository
+            .save(
+                &entry(&other_project, "/other/extracted.det"),
+                "Board minutes",
+            )
+            .await
+            .unwrap();
+
+        let found = |text: &'static str| {
+            let (repository, project_id) = (&repository, &project_id);
+            async move {
+                repository
+                    .paths_containing(project_id, &words(text))
+                    .await
+                    .unwrap()
+            }
+        };
+        assert_eq!(found("settle").await.len(), 1);
+        assert_eq!(found("café").await.len(), 1);
+        assert_eq!(found("board").await.len(), 2);
+        assert_eq!(
+            found("board minutes").await,
+            HashSet::from([minutes.path.clone()])
+        );
+
+        // Saving again replaces the text
+        repository.save(&minutes, "Nothing left").await.unwrap();
+        assert!(found("settle").await.is_empty());
+        let texts = repository
+            .texts(&[minutes.path.clone(), "/unknown.det".to_string()])
+            .await
+            .unwrap();
+        assert_eq!(texts.len(), 1);
+        assert_eq!(texts[&minutes.path], "Nothing left");
+
+        let listed = repository.list_by_project(&project_id).await.unwrap();
+        assert_eq!(listed.len(), 2);
+        assert_eq!(listed[0].document_id, minutes.document_id);
+
+        repository.remove(&[memo.path.clone()]).await.unwrap();
+        assert!(found("board").await.is_empty());
+        assert_eq!(
+            repository.list_by_project(&project_id).await.unwrap().len(),
+            1
+        );
+    }
+}
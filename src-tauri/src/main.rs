@@ -103,6 +103,121 @@ async fn main() {
             commands::workspace_commands::list_directory,
             commands::workspace_commands::navigate_to_folder,
             commands::workspace_commands::navigate_to_parent,
+            commands::workspace_commands::get_project_file_summary,
+            commands::workspace_commands::get_file_category_config,
+            commands::workspace_commands::update_file_category_config,
+            // Content hashing commands
+            commands::hashing_commands::find_duplicates,
+            commands::hashing_commands::start_duplicate_scan,
+            commands::hashing_commands::get_file_hash,
+            // Document identity commands
+            commands::document_commands::reconcile_documents,
+            commands::document_commands::start_document_reconciliation,
+            commands::document_commands::list_documents,
+            commands::document_commands::get_document,
+            commands::document_commands::find_document_by_path,
+            // Manifest snapshot commands
+            commands::snapshot_commands::create_snapshot,
+            commands::snapshot_commands::start_snapshot_job,
+            commands::snapshot_commands::list_snapshots,
+            commands::snapshot_commands::delete_snapshot,
+            commands::snapshot_commands::diff_snapshots,
+            commands::snapshot_commands::export_snapshot_diff,
+            // Document extraction commands
+            commands::extraction_commands::extract_document,
+            commands::extraction_commands::start_project_extraction,
+            commands::extraction_commands::get_supported_extraction_formats,
+            // Document export commands
+            commands::export_commands::export_document,
+            commands::export_commands::get_export_formats,
+            // Report commands
+            commands::report_commands::create_report,
+            commands::report_commands::get_report,
+            commands::report_commands::list_reports,
+            commands::report_commands::update_report,
+            commands::report_commands::delete_report,
+            commands::report_commands::get_report_categories,
+            // Citation commands
+            commands::citation_commands::list_citing_reports,
+            commands::citation_commands::list_cited_documents,
+            commands::citation_commands::resolve_report_citations,
+            commands::citation_commands::check_broken_citations,
+            // Derivation commands
+            commands::derivation_commands::get_derivation_graph,
+            // Anonymization commands
+            commands::anonymization_commands::preview_anonymization,
+            commands::anonymization_commands::anonymize_document,
+            commands::anonymization_commands::get_anonymization_detectors,
+            // Cost table commands
+            commands::cost_table_commands::get_cost_table,
+            commands::cost_table_commands::save_cost_table,
+            commands::cost_table_commands::import_cost_table,
+            commands::cost_table_commands::export_cost_table,
+            commands::cost_table_commands::get_cost_categories,
+            // Media metadata commands
+            commands::media_commands::probe_media,
+            commands::media_commands::start_media_probe,
+            commands::media_commands::get_media_metadata,
+            commands::media_commands::search_media,
+            commands::media_commands::get_supported_media_formats,
+            // Search commands
+            commands::search_commands::search_project,
+            commands::search_commands::search_all_projects,
+            commands::search_commands::create_saved_search,
+            commands::search_commands::update_saved_search,
+            commands::search_commands::delete_saved_search,
+            commands::search_commands::list_saved_searches,
+            commands::search_commands::run_saved_search,
+            // Category commands
+            commands::category_commands::create_category,
+            commands::category_commands::update_category,
+            commands::category_commands::delete_category,
+            commands::category_commands::list_categories,
+            commands::category_commands::assign_category,
+            commands::category_commands::unassign_category,
+            commands::category_commands::list_documents_by_category,
+            commands::category_commands::get_document_categories,
+            // Annotation commands
+            commands::annotation_commands::create_annotation,
+            commands::annotation_commands::update_annotation,
+            commands::annotation_commands::delete_annotation,
+            commands::annotation_commands::list_annotations,
+            commands::annotation_commands::reattach_annotations,
+            commands::annotation_commands::export_annotations,
+            // Coding commands
+            commands::coding_commands::get_coding_schema,
+            commands::coding_commands::save_coding_schema,
+            commands::coding_commands::code_document,
+            commands::coding_commands::get_document_coding,
+            commands::coding_commands::get_coding_history,
+            commands::coding_commands::next_uncoded_document,
+            commands::coding_commands::get_coding_progress,
+            commands::coding_commands::list_coding_progress,
+            // Review commands
+            commands::review_commands::create_reviewer,
+            commands::review_commands::list_reviewers,
+            commands::review_commands::set_active_reviewer,
+            commands::review_commands::get_active_reviewer,
+            commands::review_commands::create_review_batches,
+            commands::review_commands::assign_review_batch,
+            commands::review_commands::list_review_batches,
+            commands::review_commands::rebalance_review_batches,
+            commands::review_commands::close_review_batch,
+            commands::review_commands::get_reviewer_stats,
+            // Email commands
+            commands::email_commands::parse_email,
+            commands::email_commands::start_email_parse,
+            commands::email_commands::get_email,
+            commands::email_commands::list_email_threads,
+            commands::email_commands::get_email_thread,
+            // Archive commands
+            commands::archive_commands::extract_archive_member,
+            // Preview commands
+            commands::preview_commands::get_preview,
+            // Background job commands
+            commands::job_commands::list_jobs,
+            commands::job_commands::get_job,
+            commands::job_commands::cancel_job,
             // Application state commands
             application::app_state::get_app_status,
             application::app_state::health_check